@@ -0,0 +1,38 @@
+use floem::{
+    reactive::create_rw_signal,
+    taffy::style_helpers::{auto, fr},
+    views::{button, h_stack, label, stack, text_input, Decorators},
+    IntoView,
+};
+
+pub fn grid_form_view() -> impl IntoView {
+    let name = create_rw_signal(String::new());
+    let email = create_rw_signal(String::new());
+    let street = create_rw_signal(String::new());
+    let city = create_rw_signal(String::new());
+
+    let field = |text: &'static str| label(move || text).style(|s| s.items_center());
+    let input = |buffer| text_input(buffer).style(|s| s.width_full());
+
+    stack((
+        label(|| "Contact").style(|s| s.font_size(20.0).grid_area(1..2, 1..-1)),
+        field("Name"),
+        input(name),
+        field("Email"),
+        input(email),
+        field("Street"),
+        input(street),
+        field("City"),
+        input(city),
+        h_stack((button("Cancel"), button("Save")))
+            .style(|s| s.gap(10.0).justify_end().grid_column_span(2)),
+    ))
+    .style(|s| {
+        s.grid()
+            .grid_template_columns(vec![auto(), fr(1.0)])
+            .grid_flow_row()
+            .gap(10.0)
+            .padding(20.0)
+            .width_full()
+    })
+}
@@ -9,6 +9,7 @@ use floem::{
 };
 
 pub mod draggable_sidebar;
+pub mod grid_form;
 pub mod holy_grail;
 pub mod left_sidebar;
 pub mod right_sidebar;
@@ -62,6 +63,18 @@ fn app_view() -> impl IntoView {
                 );
             })
         }),
+        list_item(String::from("Grid form"), move || {
+            button("Open").action(|| {
+                new_window(
+                    |_| grid_form::grid_form_view(),
+                    Some(
+                        WindowConfig::default()
+                            .size(Size::new(500.0, 300.0))
+                            .title("Grid form"),
+                    ),
+                );
+            })
+        }),
         label(move || String::from("Interactive layouts"))
             .style(|s| s.font_size(30.0).margin_top(15.0).margin_bottom(15.0)),
         list_item(String::from("Tab navigation"), move || {
@@ -0,0 +1,186 @@
+//! An inspector for apps that serve their views with `floem::remote_inspector::serve`.
+//!
+//! Run it with `--serve` to also serve its own views on 127.0.0.1:7878, so a second instance
+//! can connect to it.
+
+use std::{cell::RefCell, rc::Rc};
+
+use floem::{
+    peniko::Color,
+    reactive::{create_rw_signal, RwSignal, SignalGet, SignalUpdate},
+    remote_inspector::{
+        serve, PerfCounters, RemoteInspectorClient, RemoteWindow, Request, Response,
+    },
+    views::{
+        button, dyn_container, empty, h_stack, label, scroll, static_label, text_input, v_stack,
+        v_stack_from_iter, Checkbox, Decorators,
+    },
+    IntoView,
+};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+fn main() {
+    let _server = std::env::args()
+        .any(|arg| arg == "--serve")
+        .then(|| serve(DEFAULT_ADDR).expect("failed to start the remote inspector"));
+    floem::launch(app_view);
+}
+
+#[derive(Clone)]
+struct Inspector {
+    client: Rc<RefCell<Option<RemoteInspectorClient>>>,
+    status: RwSignal<String>,
+    windows: RwSignal<Vec<RemoteWindow>>,
+    selected: RwSignal<Option<u64>>,
+    styles: RwSignal<bool>,
+    tree: RwSignal<String>,
+    counters: RwSignal<Option<PerfCounters>>,
+}
+
+impl Inspector {
+    fn connect(&self, addr: &str) {
+        match RemoteInspectorClient::connect(addr) {
+            Ok(client) => {
+                *self.client.borrow_mut() = Some(client);
+                self.status.set(format!("Connected to {addr}"));
+                self.refresh();
+            }
+            Err(err) => {
+                *self.client.borrow_mut() = None;
+                self.status
+                    .set(format!("Failed to connect to {addr}: {err}"));
+            }
+        }
+    }
+
+    fn request(&self, request: Request) -> Option<Response> {
+        let mut client = self.client.borrow_mut();
+        let result = client.as_mut()?.request(&request);
+        match result {
+            Ok(Response::Error(err)) => {
+                self.status.set(err);
+                None
+            }
+            Ok(response) => Some(response),
+            Err(err) => {
+                *client = None;
+                self.status.set(format!("Disconnected: {err}"));
+                None
+            }
+        }
+    }
+
+    fn refresh(&self) {
+        if let Some(Response::Windows(windows)) = self.request(Request::Windows) {
+            let selected = self.selected.get_untracked();
+            if !windows.iter().any(|window| Some(window.id) == selected) {
+                self.selected.set(windows.first().map(|window| window.id));
+            }
+            self.windows.set(windows);
+        }
+        let Some(window) = self.selected.get_untracked() else {
+            return;
+        };
+        let style = self.styles.get_untracked();
+        if let Some(Response::Tree(tree)) = self.request(Request::Tree { window, style }) {
+            self.tree.set(tree.to_string());
+        }
+        if let Some(Response::Counters(counters)) = self.request(Request::Counters { window }) {
+            self.counters.set(Some(counters));
+        }
+    }
+}
+
+fn counters_view(inspector: Inspector) -> impl IntoView {
+    label(move || match inspector.counters.get() {
+        Some(counters) => format!(
+            "{} views, {} frames, {} style passes, {} layout passes\n\
+             last style {:.2}ms, last layout {:.2}ms, last paint {:.2}ms",
+            counters.views,
+            counters.frames,
+            counters.style_passes,
+            counters.layout_passes,
+            counters.last_style_ms,
+            counters.last_layout_ms,
+            counters.last_paint_ms,
+        ),
+        None => "No counters".to_string(),
+    })
+    .style(|s| s.padding(5.0))
+}
+
+fn windows_view(inspector: Inspector) -> impl IntoView {
+    let windows = inspector.windows;
+    dyn_container(
+        move || windows.get(),
+        move |windows| {
+            let inspector = inspector.clone();
+            v_stack_from_iter(windows.into_iter().map(move |window| {
+                let inspector = inspector.clone();
+                let selected = inspector.selected;
+                let id = window.id;
+                static_label(format!(
+                    "{} ({}x{}){}",
+                    window.title,
+                    window.width.round(),
+                    window.height.round(),
+                    if window.focused { ", focused" } else { "" }
+                ))
+                .on_click_stop(move |_| {
+                    inspector.selected.set(Some(id));
+                    inspector.refresh();
+                })
+                .style(move |s| {
+                    s.padding(5.0)
+                        .hover(|s| s.background(Color::rgb8(228, 237, 216)))
+                        .apply_if(selected.get() == Some(id), |s| {
+                            s.background(Color::rgb8(213, 208, 216))
+                        })
+                })
+            }))
+            .style(|s| s.width_full())
+        },
+    )
+    .style(|s| s.width(240.0))
+}
+
+fn app_view() -> impl IntoView {
+    let addr = create_rw_signal(DEFAULT_ADDR.to_string());
+    let inspector = Inspector {
+        client: Rc::new(RefCell::new(None)),
+        status: create_rw_signal("Not connected".to_string()),
+        windows: create_rw_signal(Vec::new()),
+        selected: create_rw_signal(None),
+        styles: create_rw_signal(false),
+        tree: create_rw_signal(String::new()),
+        counters: create_rw_signal(None),
+    };
+
+    let (status, styles, tree) = (inspector.status, inspector.styles, inspector.tree);
+    let toolbar = {
+        let (connect, refresh) = (inspector.clone(), inspector.clone());
+        h_stack((
+            text_input(addr).style(|s| s.width(200.0)),
+            button("Connect").action(move || connect.connect(&addr.get_untracked())),
+            button("Refresh").action(move || refresh.refresh()),
+            Checkbox::labeled_rw(styles, || "Styles"),
+            label(move || status.get()),
+        ))
+        .style(|s| s.padding(5.0).gap(10.0).items_center())
+    };
+
+    let tree = scroll(
+        label(move || tree.get()).style(|s| s.padding(5.0).font_family("monospace".to_string())),
+    )
+    .style(|s| s.flex_grow(1.0).flex_basis(0.0).height_full());
+
+    v_stack((
+        toolbar,
+        empty().style(|s| s.height(1.0).width_full().background(Color::LIGHT_GRAY)),
+        h_stack((windows_view(inspector.clone()), tree))
+            .style(|s| s.flex_grow(1.0).min_height(0.0).width_full()),
+        counters_view(inspector),
+    ))
+    .style(|s| s.width_full().height_full())
+}
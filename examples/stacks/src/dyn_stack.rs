@@ -15,11 +15,20 @@ pub fn dyn_stack_view() -> impl IntoView {
         "Add an item".class(ButtonClass).on_click_stop(move |_| {
             long_list.update(|list| list.push_front(list.len() as i32 + 1))
         }),
+        "Reverse the list"
+            .class(ButtonClass)
+            .on_click_stop(move |_| {
+                long_list.update(|list| *list = list.iter().rev().copied().collect())
+            }),
         scroll(
             dyn_stack(
                 move || long_list.get(),
                 move |item| *item,
-                move |item| item.style(|s| s.height(20).justify_center()),
+                // `animate_layout` makes the items slide to their new positions when the list changes
+                move |item| {
+                    item.style(|s| s.height(20).justify_center())
+                        .animate_layout()
+                },
             )
             .style(|s| s.flex_col().width_full()),
         )
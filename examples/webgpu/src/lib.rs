@@ -1,4 +1,4 @@
-use floem::text::FONT_SYSTEM;
+use floem::fonts;
 use floem::window::WindowConfig;
 use floem::Application;
 use floem::{
@@ -40,13 +40,9 @@ pub fn run() {
     #[cfg(target_family = "wasm")]
     console_error_panic_hook::set_once();
 
-    {
-        let mut font_system = FONT_SYSTEM.lock();
-        let font_db = font_system.db_mut();
-        font_db.load_font_data(Vec::from(FIRA_MONO));
-        font_db.load_font_data(Vec::from(FIRA_SANS));
-        font_db.load_font_data(Vec::from(DEJAVU_SERIF));
-    }
+    fonts::load_font_data(Vec::from(FIRA_MONO));
+    fonts::load_font_data(Vec::from(FIRA_SANS));
+    fonts::load_font_data(Vec::from(DEJAVU_SERIF));
 
     let window_config = WindowConfig::default().with_web_config(|w| w.canvas_id("the-canvas"));
 
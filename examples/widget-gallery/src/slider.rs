@@ -1,7 +1,10 @@
 use floem::{
     reactive::{create_rw_signal, DerivedRwSignal, SignalGet},
     unit::UnitExt,
-    views::{label, slider, stack, text_input, Decorators},
+    views::{
+        indeterminate_progress_bar, label, progress_bar, slider, spinner, stack, text_input,
+        Decorators,
+    },
     IntoView,
 };
 
@@ -14,6 +17,8 @@ pub fn slider_view() -> impl IntoView {
         |val| val.parse::<f64>().unwrap_or_default().pct(),
         |val| val.0.to_string(),
     );
+    let low = create_rw_signal(20.pct());
+    let high = create_rw_signal(70.pct());
     form::form({
         (
             form_item("Input Control:".to_string(), 120.0, move || {
@@ -49,6 +54,43 @@ pub fn slider_view() -> impl IntoView {
                 ))
                 .style(|s| s.gap(10))
             }),
+            form_item("Progress View:".to_string(), 120.0, move || {
+                stack((
+                    progress_bar(move || slider_state.get().0 / 100.).style(|s| s.width(200)),
+                    spinner(),
+                ))
+                .style(|s| s.gap(10).items_center())
+            }),
+            form_item("Indeterminate:".to_string(), 120.0, move || {
+                indeterminate_progress_bar().style(|s| s.width(200))
+            }),
+            form_item("Range Slider:".to_string(), 120.0, move || {
+                stack((
+                    slider::range_slider(low, high)
+                        .step(10.)
+                        .ticks(true)
+                        .tick_labels(|pct| format!("{}", pct.0))
+                        .slider_style(|s| {
+                            s.handle_radius(8)
+                                .bar_height(6)
+                                .accent_bar_height(6)
+                                .tick_height(4)
+                        })
+                        .style(|s| s.width(200).height(44).font_size(10.)),
+                    label(move || format!("{:.0}% - {:.0}%", low.get().0, high.get().0)),
+                ))
+                .style(|s| s.gap(10).items_center())
+            }),
+            form_item("Vertical Slider:".to_string(), 120.0, move || {
+                stack((
+                    slider::Slider::new_rw(slider_state)
+                        .vertical()
+                        .step(5.)
+                        .style(|s| s.width(15).height(120)),
+                    label(move || format!("{:.1}%", slider_state.get().0)),
+                ))
+                .style(|s| s.gap(10).items_center())
+            }),
         )
     })
 }
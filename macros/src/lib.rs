@@ -0,0 +1,57 @@
+//! Derive macros for Floem, which are re-exported in `floem::macros`.
+
+mod props;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives a builder for the props of a component, which is created with `builder()` and
+/// checks at compile time that the required props are set.
+///
+/// The fields of the struct are required props, unless they have one of these attributes:
+///
+/// - `#[prop(optional)]`: the prop is `Default::default()` when it isn't set.
+/// - `#[prop(default = expr)]`: the prop is `expr` when it isn't set.
+///
+/// With `#[prop(into)]`, the setter of the prop takes any value that converts into it.
+///
+/// The props are built with `build()`, which only exists once all the required props are set,
+/// so a missing prop is an error like "no method named `build` found".
+///
+/// ```
+/// use floem_macros::Props;
+///
+/// #[derive(Props)]
+/// pub struct CardProps {
+///     /// The title of the card.
+///     #[prop(into)]
+///     title: String,
+///     #[prop(optional)]
+///     subtitle: Option<String>,
+///     #[prop(default = 8.0)]
+///     padding: f64,
+/// }
+///
+/// let props = CardProps::builder().title("Inbox").padding(12.0).build();
+/// assert_eq!(props.title, "Inbox");
+/// assert_eq!(props.subtitle, None);
+/// assert_eq!(props.padding, 12.0);
+/// ```
+///
+/// ```compile_fail
+/// # use floem_macros::Props;
+/// #[derive(Props)]
+/// pub struct CardProps {
+///     title: String,
+/// }
+///
+/// // the title is required
+/// let props = CardProps::builder().build();
+/// ```
+#[proc_macro_derive(Props, attributes(prop))]
+pub fn derive_props(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    props::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,201 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Expr, Fields, Ident, Type};
+
+/// A field of the props.
+struct Prop {
+    name: Ident,
+    ty: Type,
+    docs: Vec<Attribute>,
+    into: bool,
+    /// The value of an optional prop that isn't set, which is `Default::default()` when it's
+    /// `Some(None)`.
+    default: Option<Option<Expr>>,
+}
+
+impl Prop {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut into = false;
+        let mut default = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("prop"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("optional") {
+                    default = Some(None);
+                } else if meta.path.is_ident("default") {
+                    default = Some(Some(meta.value()?.parse()?));
+                } else if meta.path.is_ident("into") {
+                    into = true;
+                } else {
+                    return Err(meta.error("expected `optional`, `default = ...` or `into`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(Prop {
+            name: field.ident.clone().unwrap(),
+            ty: field.ty.clone(),
+            docs: field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("doc"))
+                .cloned()
+                .collect(),
+            into,
+            default,
+        })
+    }
+
+    /// The parameter of the setter, and the expression converting it to the prop.
+    fn setter_param(&self) -> (TokenStream, TokenStream) {
+        let ty = &self.ty;
+        if self.into {
+            (
+                quote!(value: impl ::core::convert::Into<#ty>),
+                quote!(value.into()),
+            )
+        } else {
+            (quote!(value: #ty), quote!(value))
+        }
+    }
+}
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`Props` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`Props` can only be derived for structs with named fields",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`Props` can't be derived for generic structs",
+        ));
+    }
+    let props = fields
+        .named
+        .iter()
+        .map(Prop::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let (required, optional): (Vec<_>, Vec<_>) =
+        props.iter().partition(|prop| prop.default.is_none());
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let builder = format_ident!("{}Builder", name);
+    let builder_doc = format!("A builder for [`{name}`], created with [`{name}::builder`].");
+
+    // each required prop is a type parameter of the builder, which is `()` until it's set and
+    // `(T,)` after
+    let params = (0..required.len())
+        .map(|i| format_ident!("__P{}", i))
+        .collect::<Vec<_>>();
+    let required_names = required.iter().map(|prop| &prop.name).collect::<Vec<_>>();
+    let required_types = required.iter().map(|prop| &prop.ty).collect::<Vec<_>>();
+    let optional_names = optional.iter().map(|prop| &prop.name).collect::<Vec<_>>();
+    let optional_types = optional.iter().map(|prop| &prop.ty).collect::<Vec<_>>();
+    let optional_defaults = optional.iter().map(|prop| match &prop.default {
+        Some(Some(default)) => quote!(#default),
+        _ => quote!(::core::default::Default::default()),
+    });
+
+    let required_setters = required.iter().enumerate().map(|(i, prop)| {
+        let prop_name = &prop.name;
+        let ty = &prop.ty;
+        let docs = &prop.docs;
+        let (param, value) = prop.setter_param();
+        let other_params = params
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, param)| param);
+        let unset = params
+            .iter()
+            .enumerate()
+            .map(|(j, param)| if j == i { quote!(()) } else { quote!(#param) });
+        let set = params.iter().enumerate().map(|(j, param)| {
+            if j == i {
+                quote!((#ty,))
+            } else {
+                quote!(#param)
+            }
+        });
+        let fields = required_names.iter().map(|field| {
+            if *field == prop_name {
+                quote!(#field: (#value,))
+            } else {
+                quote!(#field: self.#field)
+            }
+        });
+        quote! {
+            impl<#(#other_params),*> #builder<#(#unset),*> {
+                #(#docs)*
+                #vis fn #prop_name(self, #param) -> #builder<#(#set),*> {
+                    #builder {
+                        #(#fields,)*
+                        #(#optional_names: self.#optional_names,)*
+                    }
+                }
+            }
+        }
+    });
+
+    let optional_setters = optional.iter().map(|prop| {
+        let prop_name = &prop.name;
+        let docs = &prop.docs;
+        let (param, value) = prop.setter_param();
+        quote! {
+            #(#docs)*
+            #vis fn #prop_name(mut self, #param) -> Self {
+                self.#prop_name = ::core::option::Option::Some(#value);
+                self
+            }
+        }
+    });
+
+    let unset = params.iter().map(|_| quote!(()));
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #[must_use]
+        #vis struct #builder<#(#params),*> {
+            #(#required_names: #params,)*
+            #(#optional_names: ::core::option::Option<#optional_types>,)*
+        }
+
+        impl #name {
+            /// Creates a builder for the props.
+            #vis fn builder() -> #builder<#(#unset),*> {
+                #builder {
+                    #(#required_names: (),)*
+                    #(#optional_names: ::core::option::Option::None,)*
+                }
+            }
+        }
+
+        #(#required_setters)*
+
+        impl<#(#params),*> #builder<#(#params),*> {
+            #(#optional_setters)*
+        }
+
+        impl #builder<#((#required_types,)),*> {
+            /// Builds the props, once all the required props are set.
+            #vis fn build(self) -> #name {
+                #name {
+                    #(#required_names: self.#required_names.0,)*
+                    #(#optional_names: self.#optional_names.unwrap_or_else(|| #optional_defaults),)*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,43 @@
+use floem_macros::Props;
+
+#[derive(Props)]
+struct ButtonProps {
+    #[prop(into)]
+    label: String,
+    size: u32,
+    #[prop(optional)]
+    disabled: bool,
+    #[prop(default = "primary".to_string(), into)]
+    variant: String,
+}
+
+#[test]
+fn required_props_are_set_in_any_order() {
+    let props = ButtonProps::builder().size(2).label("Save").build();
+    assert_eq!(props.label, "Save");
+    assert_eq!(props.size, 2);
+}
+
+#[test]
+fn optional_props_have_defaults() {
+    let props = ButtonProps::builder().label("Save").size(1).build();
+    assert!(!props.disabled);
+    assert_eq!(props.variant, "primary");
+
+    let props = ButtonProps::builder()
+        .disabled(true)
+        .label("Save")
+        .variant("danger")
+        .size(1)
+        .build();
+    assert!(props.disabled);
+    assert_eq!(props.variant, "danger");
+}
+
+#[derive(Props)]
+struct EmptyProps {}
+
+#[test]
+fn props_without_fields() {
+    let EmptyProps {} = EmptyProps::builder().build();
+}
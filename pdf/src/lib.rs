@@ -0,0 +1,775 @@
+//! A [`Renderer`] that writes PDF documents, which floem uses to print views.
+//!
+//! One logical pixel is one PDF point, i.e. 1/72 inch. Shapes, gradients and text are written
+//! as vector paths, with the text drawn as the outlines of its glyphs, while images and SVGs
+//! are embedded as images.
+
+use std::collections::HashMap;
+
+use floem_renderer::text::{CacheKey, LayoutGlyph, LayoutRun, FONT_SYSTEM};
+use floem_renderer::tiny_skia::{Pixmap, Transform};
+use floem_renderer::{Img, Renderer, Svg};
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use pdf_writer::types::{ColorSpaceOperand, FunctionShadingType, LineCapStyle, LineJoinStyle};
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Ref, TextStr};
+use peniko::kurbo::{Affine, BezPath, Cap, Join, PathEl, Point, Rect, Shape, Size, Stroke};
+use peniko::{BrushRef, Color, ColorStop, Gradient, GradientKind};
+use swash::scale::ScaleContext;
+use swash::zeno::{Command, PathData};
+
+/// The number of pixels per point that SVGs are rasterized with.
+const SVG_RESOLUTION: f64 = 4.0;
+
+/// The size of the page added to documents without pages, A4.
+const EMPTY_PAGE: Size = Size::new(595.0, 842.0);
+
+struct Page {
+    size: Size,
+    content: Content,
+    /// Whether the clip of the page is applied.
+    page_clip: bool,
+    /// Whether the clip set with [`Renderer::clip`] is applied.
+    clip: bool,
+}
+
+impl Page {
+    fn clear_clip(&mut self) {
+        if self.clip {
+            self.content.restore_state();
+            self.clip = false;
+        }
+    }
+}
+
+struct WrittenPage {
+    id: Ref,
+    content: Ref,
+    size: Size,
+}
+
+/// Renders into a PDF document, one page at a time.
+pub struct PdfRenderer {
+    pdf: Pdf,
+    next_ref: i32,
+    catalog: Ref,
+    page_tree: Ref,
+    title: Option<String>,
+    pages: Vec<WrittenPage>,
+    page: Option<Page>,
+    transform: Affine,
+
+    /// The images and rasterized SVGs, keyed by their hash, with their names.
+    images: HashMap<Vec<u8>, String>,
+    x_objects: Vec<(String, Ref)>,
+    /// The graphics states that set an opacity, keyed by the alpha value.
+    opacities: HashMap<u8, Ref>,
+    patterns: Vec<(String, Ref)>,
+
+    glyph_outlines: HashMap<CacheKey, Option<BezPath>>,
+    scale_context: ScaleContext,
+}
+
+impl Default for PdfRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfRenderer {
+    pub fn new() -> Self {
+        Self {
+            pdf: Pdf::new(),
+            next_ref: 3,
+            catalog: Ref::new(1),
+            page_tree: Ref::new(2),
+            title: None,
+            pages: Vec::new(),
+            page: None,
+            transform: Affine::IDENTITY,
+            images: HashMap::new(),
+            x_objects: Vec::new(),
+            opacities: HashMap::new(),
+            patterns: Vec::new(),
+            glyph_outlines: HashMap::new(),
+            scale_context: ScaleContext::new(),
+        }
+    }
+
+    /// Sets the title in the metadata of the document.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = Some(title.into());
+    }
+
+    /// Starts a new page of `size` points, ending the current page.
+    pub fn begin_page(&mut self, size: Size) {
+        self.end_page();
+        let mut content = Content::new();
+        content.save_state();
+        // the origin of PDF is at the bottom left of the page
+        content.transform([1.0, 0.0, 0.0, -1.0, 0.0, size.height as f32]);
+        self.page = Some(Page {
+            size,
+            content,
+            page_clip: false,
+            clip: false,
+        });
+        self.transform = Affine::IDENTITY;
+    }
+
+    /// Restricts everything drawn afterwards on the current page to `rect`, in page
+    /// coordinates, in addition to the clip set with [`Renderer::clip`].
+    pub fn set_page_clip(&mut self, rect: Option<Rect>) {
+        let Some(page) = self.page.as_mut() else {
+            return;
+        };
+        page.clear_clip();
+        if page.page_clip {
+            page.content.restore_state();
+            page.page_clip = false;
+        }
+        if let Some(rect) = rect {
+            page.content.save_state();
+            page.content.rect(
+                rect.x0 as f32,
+                rect.y0 as f32,
+                rect.width() as f32,
+                rect.height() as f32,
+            );
+            page.content.clip_nonzero();
+            page.content.end_path();
+            page.page_clip = true;
+        }
+    }
+
+    /// Ends the current page.
+    pub fn end_page(&mut self) {
+        let Some(mut page) = self.page.take() else {
+            return;
+        };
+        page.clear_clip();
+        if page.page_clip {
+            page.content.restore_state();
+        }
+        page.content.restore_state();
+        let id = self.alloc();
+        let content = self.alloc();
+        self.pdf.stream(content, &page.content.finish());
+        self.pages.push(WrittenPage {
+            id,
+            content,
+            size: page.size,
+        });
+    }
+
+    /// The size of the current page.
+    pub fn page_size(&self) -> Size {
+        self.page.as_ref().map(|page| page.size).unwrap_or_default()
+    }
+
+    /// The number of pages in the document, including the current page.
+    pub fn page_count(&self) -> usize {
+        self.pages.len() + usize::from(self.page.is_some())
+    }
+
+    /// Ends the current page and returns the document.
+    pub fn finish_document(mut self) -> Vec<u8> {
+        self.end_page();
+        if self.pages.is_empty() {
+            self.begin_page(EMPTY_PAGE);
+            self.end_page();
+        }
+        let info = self.title.is_some().then(|| self.alloc());
+
+        self.pdf.catalog(self.catalog).pages(self.page_tree);
+        self.pdf
+            .pages(self.page_tree)
+            .kids(self.pages.iter().map(|page| page.id))
+            .count(self.pages.len() as i32);
+
+        let opacities: Vec<(String, Ref)> = self
+            .opacities
+            .iter()
+            .map(|(alpha, id)| (opacity_name(*alpha), *id))
+            .collect();
+        for page in &self.pages {
+            let mut writer = self.pdf.page(page.id);
+            writer.media_box(pdf_writer::Rect::new(
+                0.0,
+                0.0,
+                page.size.width as f32,
+                page.size.height as f32,
+            ));
+            writer.parent(self.page_tree);
+            writer.contents(page.content);
+            let mut resources = writer.resources();
+            resources.x_objects().pairs(names(&self.x_objects));
+            resources.ext_g_states().pairs(names(&opacities));
+            resources.patterns().pairs(names(&self.patterns));
+        }
+
+        if let (Some(info), Some(title)) = (info, self.title.as_ref()) {
+            self.pdf.document_info(info).title(TextStr(title));
+        }
+        self.pdf.finish()
+    }
+
+    fn alloc(&mut self) -> Ref {
+        let id = Ref::new(self.next_ref);
+        self.next_ref += 1;
+        id
+    }
+
+    /// Fills or strokes the path, which is transformed by `transform`.
+    fn draw_path(
+        &mut self,
+        elements: impl Iterator<Item = PathEl>,
+        brush: BrushRef<'_>,
+        stroke: Option<&Stroke>,
+        transform: Affine,
+    ) {
+        let Some(paint) = self.paint(brush, transform) else {
+            return;
+        };
+        let Some(page) = self.page.as_mut() else {
+            return;
+        };
+        let content = &mut page.content;
+        content.save_state();
+        paint.apply(content, stroke.is_some());
+        content.transform(coeffs(transform));
+        if let Some(stroke) = stroke {
+            content.set_line_width(stroke.width as f32);
+            content.set_line_cap(match stroke.end_cap {
+                Cap::Butt => LineCapStyle::ButtCap,
+                Cap::Square => LineCapStyle::ProjectingSquareCap,
+                Cap::Round => LineCapStyle::RoundCap,
+            });
+            content.set_line_join(match stroke.join {
+                Join::Bevel => LineJoinStyle::BevelJoin,
+                Join::Miter => LineJoinStyle::MiterJoin,
+                Join::Round => LineJoinStyle::RoundJoin,
+            });
+            content.set_miter_limit(stroke.miter_limit as f32);
+            if !stroke.dash_pattern.is_empty() {
+                content.set_dash_pattern(
+                    stroke.dash_pattern.iter().map(|dash| *dash as f32),
+                    stroke.dash_offset as f32,
+                );
+            }
+        }
+        write_path(content, elements);
+        if stroke.is_some() {
+            content.stroke();
+        } else {
+            content.fill_nonzero();
+        }
+        content.restore_state();
+    }
+
+    /// Returns the paint for `brush`, or `None` if it draws nothing.
+    fn paint(&mut self, brush: BrushRef<'_>, transform: Affine) -> Option<Paint> {
+        match brush {
+            BrushRef::Solid(color) => self.solid(color),
+            BrushRef::Gradient(gradient) => match self.gradient_pattern(gradient, transform) {
+                Some(pattern) => Some(Paint::Pattern(pattern)),
+                // gradients that PDF can't draw use their first color
+                None => self.solid(gradient.stops.first()?.color),
+            },
+            BrushRef::Image(_) => None,
+        }
+    }
+
+    fn solid(&mut self, color: Color) -> Option<Paint> {
+        if color.a == 0 {
+            return None;
+        }
+        let opacity = (color.a < u8::MAX).then(|| self.opacity(color.a));
+        Some(Paint::Solid(color, opacity))
+    }
+
+    /// Returns the name of the graphics state that sets the opacity to `alpha`.
+    fn opacity(&mut self, alpha: u8) -> String {
+        if !self.opacities.contains_key(&alpha) {
+            let id = self.alloc();
+            let value = alpha as f32 / 255.0;
+            self.pdf
+                .ext_graphics(id)
+                .non_stroking_alpha(value)
+                .stroking_alpha(value);
+            self.opacities.insert(alpha, id);
+        }
+        opacity_name(alpha)
+    }
+
+    /// Writes a shading pattern for `gradient`, returning its name. The opacity of the colors
+    /// is ignored.
+    fn gradient_pattern(&mut self, gradient: &Gradient, transform: Affine) -> Option<String> {
+        let (shading_type, coords) = match gradient.kind {
+            GradientKind::Linear { start, end } => (
+                FunctionShadingType::Axial,
+                vec![start.x, start.y, end.x, end.y],
+            ),
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+            } => (
+                FunctionShadingType::Radial,
+                vec![
+                    start_center.x,
+                    start_center.y,
+                    start_radius as f64,
+                    end_center.x,
+                    end_center.y,
+                    end_radius as f64,
+                ],
+            ),
+            GradientKind::Sweep { .. } => return None,
+        };
+        let function = self.gradient_function(&gradient.stops)?;
+        let page_height = self.page.as_ref()?.size.height;
+
+        let id = self.alloc();
+        let mut pattern = self.pdf.shading_pattern(id);
+        let mut shading = pattern.function_shading();
+        shading.shading_type(shading_type);
+        shading.color_space().device_rgb();
+        shading.function(function);
+        shading.coords(coords.into_iter().map(|coord| coord as f32));
+        shading.extend([true, true]);
+        shading.finish();
+        // patterns are in the coordinates of the page, not of the content
+        let flip = Affine::new([1.0, 0.0, 0.0, -1.0, 0.0, page_height]);
+        pattern.matrix(coeffs(flip * transform));
+        pattern.finish();
+
+        let name = format!("Pa{}", self.patterns.len());
+        self.patterns.push((name.clone(), id));
+        Some(name)
+    }
+
+    /// Writes a function interpolating between the colors of `stops`.
+    fn gradient_function(&mut self, stops: &[ColorStop]) -> Option<Ref> {
+        let (first, last) = (stops.first()?, stops.last()?);
+        if first.offset >= last.offset {
+            return None;
+        }
+        let domain = [first.offset, last.offset];
+        let mut functions = Vec::new();
+        for pair in stops.windows(2) {
+            let id = self.alloc();
+            self.pdf
+                .exponential_function(id)
+                .domain([0.0, 1.0])
+                .c0(rgb(pair[0].color))
+                .c1(rgb(pair[1].color))
+                .n(1.0);
+            functions.push(id);
+        }
+        let id = self.alloc();
+        self.pdf
+            .stitching_function(id)
+            .domain(domain)
+            .functions(functions.iter().copied())
+            .bounds(stops[1..stops.len() - 1].iter().map(|stop| stop.offset))
+            .encode(functions.iter().flat_map(|_| [0.0, 1.0]));
+        Some(id)
+    }
+
+    fn glyph_outline(&mut self, glyph: &LayoutGlyph) -> Option<BezPath> {
+        let (cache_key, _, _) = CacheKey::new(
+            glyph.font_id,
+            glyph.glyph_id,
+            glyph.font_size,
+            (0.0, 0.0),
+            glyph.cache_key_flags,
+        );
+        if let Some(outline) = self.glyph_outlines.get(&cache_key) {
+            return outline.clone();
+        }
+
+        let font = FONT_SYSTEM.lock().get_font(glyph.font_id);
+        let outline = font.and_then(|font| {
+            let mut scaler = self
+                .scale_context
+                .builder(font.as_swash())
+                .size(glyph.font_size)
+                .build();
+            let outline = scaler.scale_outline(glyph.glyph_id)?;
+            // the outlines of glyphs point up
+            let point = |vector: swash::zeno::Vector| Point::new(vector.x as f64, -vector.y as f64);
+            let mut path = BezPath::new();
+            for command in outline.path().commands() {
+                match command {
+                    Command::MoveTo(p) => path.move_to(point(p)),
+                    Command::LineTo(p) => path.line_to(point(p)),
+                    Command::QuadTo(p1, p2) => path.quad_to(point(p1), point(p2)),
+                    Command::CurveTo(p1, p2, p3) => path.curve_to(point(p1), point(p2), point(p3)),
+                    Command::Close => path.close_path(),
+                }
+            }
+            Some(path)
+        });
+        self.glyph_outlines.insert(cache_key, outline.clone());
+        outline
+    }
+
+    /// Writes an image from RGBA pixels, returning its name.
+    fn write_image(&mut self, width: u32, height: u32, rgba: &[u8]) -> String {
+        let mut rgb = Vec::with_capacity(rgba.len() / 4 * 3);
+        let mut alpha = Vec::with_capacity(rgba.len() / 4);
+        for pixel in rgba.chunks_exact(4) {
+            rgb.extend_from_slice(&pixel[..3]);
+            alpha.push(pixel[3]);
+        }
+
+        let id = self.alloc();
+        let mask = self.alloc();
+        let mut image = self.pdf.image_xobject(id, &compress_to_vec_zlib(&rgb, 6));
+        image.filter(Filter::FlateDecode);
+        image.width(width as i32);
+        image.height(height as i32);
+        image.color_space().device_rgb();
+        image.bits_per_component(8);
+        image.s_mask(mask);
+        image.finish();
+        let mut image = self
+            .pdf
+            .image_xobject(mask, &compress_to_vec_zlib(&alpha, 6));
+        image.filter(Filter::FlateDecode);
+        image.width(width as i32);
+        image.height(height as i32);
+        image.color_space().device_gray();
+        image.bits_per_component(8);
+        image.finish();
+
+        let name = format!("Im{}", self.x_objects.len());
+        self.x_objects.push((name.clone(), id));
+        name
+    }
+
+    /// Draws the image named `name` into `rect`.
+    fn draw_image(&mut self, name: &str, rect: Rect) {
+        // images are drawn into the unit square, with their first row at the top
+        let transform = self.transform
+            * Affine::new([rect.width(), 0.0, 0.0, -rect.height(), rect.x0, rect.y1]);
+        let Some(page) = self.page.as_mut() else {
+            return;
+        };
+        page.content.save_state();
+        page.content.transform(coeffs(transform));
+        page.content.x_object(Name(name.as_bytes()));
+        page.content.restore_state();
+    }
+}
+
+/// How a path is filled or stroked.
+enum Paint {
+    /// A color, and the graphics state setting its opacity.
+    Solid(Color, Option<String>),
+    /// A shading pattern.
+    Pattern(String),
+}
+
+impl Paint {
+    fn apply(&self, content: &mut Content, stroke: bool) {
+        match self {
+            Paint::Solid(color, opacity) => {
+                if let Some(opacity) = opacity {
+                    content.set_parameters(Name(opacity.as_bytes()));
+                }
+                let [r, g, b] = rgb(*color);
+                if stroke {
+                    content.set_stroke_rgb(r, g, b);
+                } else {
+                    content.set_fill_rgb(r, g, b);
+                }
+            }
+            Paint::Pattern(pattern) => {
+                if stroke {
+                    content.set_stroke_color_space(ColorSpaceOperand::Pattern);
+                    content.set_stroke_pattern(None, Name(pattern.as_bytes()));
+                } else {
+                    content.set_fill_color_space(ColorSpaceOperand::Pattern);
+                    content.set_fill_pattern(None, Name(pattern.as_bytes()));
+                }
+            }
+        }
+    }
+}
+
+fn rgb(color: Color) -> [f32; 3] {
+    [
+        color.r as f32 / 255.0,
+        color.g as f32 / 255.0,
+        color.b as f32 / 255.0,
+    ]
+}
+
+fn coeffs(transform: Affine) -> [f32; 6] {
+    transform.as_coeffs().map(|coeff| coeff as f32)
+}
+
+fn opacity_name(alpha: u8) -> String {
+    format!("Gs{alpha}")
+}
+
+fn names(resources: &[(String, Ref)]) -> impl Iterator<Item = (Name<'_>, Ref)> {
+    resources
+        .iter()
+        .map(|(name, id)| (Name(name.as_bytes()), *id))
+}
+
+fn write_path(content: &mut Content, elements: impl Iterator<Item = PathEl>) {
+    let mut current = Point::ZERO;
+    let mut start = Point::ZERO;
+    for element in elements {
+        match element {
+            PathEl::MoveTo(p) => {
+                content.move_to(p.x as f32, p.y as f32);
+                current = p;
+                start = p;
+            }
+            PathEl::LineTo(p) => {
+                content.line_to(p.x as f32, p.y as f32);
+                current = p;
+            }
+            PathEl::QuadTo(p1, p2) => {
+                // PDF only has cubic curves
+                let c1 = current + (p1 - current) * (2.0 / 3.0);
+                let c2 = p2 + (p1 - p2) * (2.0 / 3.0);
+                content.cubic_to(
+                    c1.x as f32,
+                    c1.y as f32,
+                    c2.x as f32,
+                    c2.y as f32,
+                    p2.x as f32,
+                    p2.y as f32,
+                );
+                current = p2;
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                content.cubic_to(
+                    p1.x as f32,
+                    p1.y as f32,
+                    p2.x as f32,
+                    p2.y as f32,
+                    p3.x as f32,
+                    p3.y as f32,
+                );
+                current = p3;
+            }
+            PathEl::ClosePath => {
+                content.close_path();
+                current = start;
+            }
+        }
+    }
+}
+
+impl Renderer for PdfRenderer {
+    fn begin(&mut self, _capture: bool) {
+        self.transform = Affine::IDENTITY;
+    }
+
+    fn transform(&mut self, transform: Affine) {
+        self.transform = transform;
+    }
+
+    fn set_z_index(&mut self, _z_index: i32) {}
+
+    fn clip(&mut self, shape: &impl Shape) {
+        let transform = self.transform;
+        let Some(page) = self.page.as_mut() else {
+            return;
+        };
+        page.clear_clip();
+        page.content.save_state();
+        // the clip stays applied to the drawing after it, so the path is transformed instead of
+        // the content
+        write_path(
+            &mut page.content,
+            shape.path_elements(0.1).map(|element| transform * element),
+        );
+        page.content.clip_nonzero();
+        page.content.end_path();
+        page.clip = true;
+    }
+
+    fn clear_clip(&mut self) {
+        if let Some(page) = self.page.as_mut() {
+            page.clear_clip();
+        }
+    }
+
+    fn stroke<'b, 's>(
+        &mut self,
+        shape: &impl Shape,
+        brush: impl Into<BrushRef<'b>>,
+        stroke: &'s Stroke,
+    ) {
+        self.draw_path(
+            shape.path_elements(0.1),
+            brush.into(),
+            Some(stroke),
+            self.transform,
+        );
+    }
+
+    fn fill<'b>(&mut self, path: &impl Shape, brush: impl Into<BrushRef<'b>>, blur_radius: f64) {
+        // PDF can't blur, and a sharp shadow looks worse than none
+        if blur_radius > 0.0 {
+            return;
+        }
+        self.draw_path(path.path_elements(0.1), brush.into(), None, self.transform);
+    }
+
+    fn draw_text_with_layout<'b>(
+        &mut self,
+        layout: impl Iterator<Item = LayoutRun<'b>>,
+        pos: impl Into<Point>,
+    ) {
+        let pos: Point = pos.into();
+        for line in layout {
+            for glyph in line.glyphs {
+                let Some(outline) = self.glyph_outline(glyph) else {
+                    continue;
+                };
+                let color = glyph
+                    .color_opt
+                    .map_or(Color::BLACK, |c| Color::rgba8(c.r(), c.g(), c.b(), c.a()));
+                let transform = self.transform
+                    * Affine::translate((pos.x + glyph.x as f64, pos.y + line.line_y as f64));
+                self.draw_path(
+                    outline.elements().iter().copied(),
+                    BrushRef::Solid(color),
+                    None,
+                    transform,
+                );
+            }
+        }
+    }
+
+    fn draw_svg<'b>(&mut self, svg: Svg<'b>, rect: Rect, brush: Option<impl Into<BrushRef<'b>>>) {
+        let width = (rect.width() * SVG_RESOLUTION).round() as u32;
+        let height = (rect.height() * SVG_RESOLUTION).round() as u32;
+        let color = brush.and_then(|brush| match brush.into() {
+            BrushRef::Solid(color) => Some(color),
+            BrushRef::Gradient(gradient) => gradient.stops.first().map(|stop| stop.color),
+            BrushRef::Image(_) => None,
+        });
+
+        let mut key = svg.hash.to_vec();
+        key.extend(width.to_le_bytes());
+        key.extend(height.to_le_bytes());
+        if let Some(color) = color {
+            key.extend([color.r, color.g, color.b, color.a]);
+        }
+        if let Some(name) = self.images.get(&key).cloned() {
+            self.draw_image(&name, rect);
+            return;
+        }
+
+        let Some(mut pixmap) = Pixmap::new(width, height) else {
+            return;
+        };
+        let transform = Transform::from_scale(
+            width as f32 / svg.tree.size().width(),
+            height as f32 / svg.tree.size().height(),
+        );
+        resvg::render(svg.tree, transform, &mut pixmap.as_mut());
+        let rgba: Vec<u8> = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                match color {
+                    // the brush colors the shape of the SVG
+                    Some(color) => [
+                        color.r,
+                        color.g,
+                        color.b,
+                        (pixel.alpha() as u16 * color.a as u16 / 255) as u8,
+                    ],
+                    None => [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()],
+                }
+            })
+            .collect();
+
+        let name = self.write_image(width, height, &rgba);
+        self.images.insert(key, name.clone());
+        self.draw_image(&name, rect);
+    }
+
+    fn draw_img(&mut self, img: Img<'_>, rect: Rect) {
+        let name = match self.images.get(img.hash) {
+            Some(name) => name.clone(),
+            None => {
+                let name = self.write_image(img.img.width, img.img.height, img.img.data.data());
+                self.images.insert(img.hash.to_vec(), name.clone());
+                name
+            }
+        };
+        self.draw_image(&name, rect);
+    }
+
+    fn finish(&mut self) -> Option<peniko::Image> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_renderer::Renderer;
+    use peniko::kurbo::{Rect, Size};
+    use peniko::Color;
+
+    use super::{PdfRenderer, EMPTY_PAGE};
+
+    /// Parses `document`, returning the width and height of each of its pages.
+    fn page_sizes(document: &[u8]) -> Vec<(f32, f32)> {
+        assert!(document.starts_with(b"%PDF-"));
+        let document = lopdf::Document::load_mem(document).unwrap();
+        document
+            .get_pages()
+            .values()
+            .map(|id| {
+                let media_box = document.get_dictionary(*id).unwrap().get(b"MediaBox");
+                let media_box: Vec<f32> = media_box
+                    .and_then(lopdf::Object::as_array)
+                    .unwrap()
+                    .iter()
+                    .map(|value| value.as_float().unwrap())
+                    .collect();
+                (media_box[2] - media_box[0], media_box[3] - media_box[1])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn documents_have_a_page_for_each_begun_page() {
+        let mut renderer = PdfRenderer::new();
+        renderer.set_title("Pages");
+        for height in [100.0, 200.0, 300.0] {
+            renderer.begin_page(Size::new(250.0, height));
+            renderer.begin(false);
+            renderer.fill(&Rect::new(10.0, 10.0, 50.0, 50.0), Color::RED, 0.0);
+        }
+        assert_eq!(renderer.page_count(), 3);
+        assert_eq!(
+            page_sizes(&renderer.finish_document()),
+            [(250.0, 100.0), (250.0, 200.0), (250.0, 300.0)]
+        );
+    }
+
+    #[test]
+    fn empty_documents_have_a_page() {
+        let renderer = PdfRenderer::new();
+        assert_eq!(renderer.page_count(), 0);
+        assert_eq!(
+            page_sizes(&renderer.finish_document()),
+            [(EMPTY_PAGE.width as f32, EMPTY_PAGE.height as f32)]
+        );
+    }
+}
@@ -30,6 +30,7 @@ impl<T> Drop for BaseSignal<T> {
     }
 }
 
+#[track_caller]
 pub fn create_base_signal<T: 'static>(value: T) -> BaseSignal<T> {
     let id = Signal::create(value);
     BaseSignal {
@@ -2,6 +2,11 @@ use std::{marker::PhantomData, sync::atomic::AtomicU64};
 
 use crate::{effect::observer_clean_up, runtime::RUNTIME, signal::Signal};
 
+/// The panic message for using a signal after the scope that owns it was disposed.
+pub(crate) const DISPOSED: &str = "signal used after its scope was disposed; signals that are \
+    shared outside of the scope they were created in, e.g. between windows, have to be created \
+    in a scope that outlives all of their users";
+
 /// Marker type explaining why something can't be sent across threads
 #[allow(dead_code)]
 struct NotThreadSafe(*const ());
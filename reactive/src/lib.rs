@@ -15,6 +15,7 @@ mod read;
 mod runtime;
 mod scope;
 mod signal;
+mod trace;
 mod trigger;
 mod write;
 
@@ -26,5 +27,6 @@ pub use memo::{create_memo, Memo};
 pub use read::{ReadSignalValue, SignalGet, SignalRead, SignalTrack, SignalWith};
 pub use scope::{as_child_of_current_scope, with_scope, Scope};
 pub use signal::{create_rw_signal, create_signal, ReadSignal, RwSignal, WriteSignal};
+pub use trace::{clear_signal_update_hook, set_signal_update_hook, SignalUpdateInfo};
 pub use trigger::{create_trigger, Trigger};
 pub use write::{SignalUpdate, SignalWrite, WriteSignalValue};
@@ -45,6 +45,7 @@ impl<T> SignalTrack<T> for Memo<T> {
 
 /// Create a Memo which takes the computed value of the given function, and triggers
 /// the reactive system when the computed value is different with the last computed value.
+#[track_caller]
 pub fn create_memo<T>(f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
 where
     T: PartialEq + 'static,
@@ -3,7 +3,7 @@ use std::{
     rc::Rc,
 };
 
-use crate::id::Id;
+use crate::id::{Id, DISPOSED};
 
 #[derive(Clone)]
 pub struct ReadSignalValue<T> {
@@ -27,7 +27,7 @@ pub trait SignalGet<T: Clone> {
     where
         T: 'static,
     {
-        self.try_get_untracked().unwrap()
+        self.try_get_untracked().expect(DISPOSED)
     }
 
     /// Clones and returns the current value stored in the Signal, and subscribes
@@ -36,7 +36,7 @@ pub trait SignalGet<T: Clone> {
     where
         T: 'static,
     {
-        self.try_get().unwrap()
+        self.try_get().expect(DISPOSED)
     }
 
     /// Try to clone and return the current value stored in the Signal, and returns None
@@ -63,7 +63,7 @@ pub trait SignalTrack<T> {
     /// Only subscribes to the current running effect to this Signal.
     ///
     fn track(&self) {
-        self.id().signal().unwrap().subscribe();
+        self.id().signal().expect(DISPOSED).subscribe();
     }
 
     /// If the signal isn't disposed,
@@ -85,7 +85,7 @@ pub trait SignalWith<T> {
     where
         T: 'static,
     {
-        self.id().signal().unwrap().with(f)
+        self.id().signal().expect(DISPOSED).with(f)
     }
 
     /// Applies a closure to the current value stored in the Signal, but it doesn't subscribe
@@ -94,7 +94,7 @@ pub trait SignalWith<T> {
     where
         T: 'static,
     {
-        self.id().signal().unwrap().with_untracked(f)
+        self.id().signal().expect(DISPOSED).with_untracked(f)
     }
 
     /// If the signal isn't disposed, applies a closure to the current value stored in the Signal.
@@ -135,7 +135,7 @@ pub trait SignalRead<T> {
     where
         T: 'static,
     {
-        self.try_read().unwrap()
+        self.try_read().expect(DISPOSED)
     }
 
     /// Reads the data stored in the Signal to a RefCell, so that you can `borrow()`
@@ -145,7 +145,7 @@ pub trait SignalRead<T> {
     where
         T: 'static,
     {
-        self.try_read_untracked().unwrap()
+        self.try_read_untracked().expect(DISPOSED)
     }
 
     /// If the signal isn't disposed,
@@ -11,6 +11,7 @@ use crate::{
     effect::{run_effect, EffectTrait},
     id::Id,
     signal::Signal,
+    trace::SignalUpdateHook,
 };
 
 thread_local! {
@@ -27,6 +28,7 @@ pub(crate) struct Runtime {
     pub(crate) contexts: RefCell<HashMap<TypeId, Box<dyn Any>>>,
     pub(crate) batching: Cell<bool>,
     pub(crate) pending_effects: RefCell<SmallVec<[Rc<dyn EffectTrait>; 10]>>,
+    pub(crate) signal_update_hook: RefCell<Option<SignalUpdateHook>>,
 }
 
 impl Default for Runtime {
@@ -45,6 +47,7 @@ impl Runtime {
             contexts: Default::default(),
             batching: Cell::new(false),
             pending_effects: RefCell::new(SmallVec::new()),
+            signal_update_hook: RefCell::new(None),
         }
     }
 
@@ -1,11 +1,11 @@
-use std::{any::Any, cell::RefCell, collections::HashMap, fmt, rc::Rc};
+use std::{any::Any, cell::RefCell, collections::HashMap, fmt, panic::Location, rc::Rc};
 
 use crate::{
     create_effect, create_updater,
     id::Id,
     memo::{create_memo, Memo},
     runtime::RUNTIME,
-    signal::{create_rw_signal, create_signal, ReadSignal, RwSignal, Signal, WriteSignal},
+    signal::{create_rw_signal_at, ReadSignal, RwSignal, Signal, WriteSignal},
     trigger::{create_trigger, Trigger},
 };
 
@@ -55,19 +55,23 @@ impl Scope {
     }
 
     /// Create a new Signal under this Scope
+    #[track_caller]
     pub fn create_signal<T>(self, value: T) -> (ReadSignal<T>, WriteSignal<T>)
     where
         T: Any + 'static,
     {
-        with_scope(self, || create_signal(value))
+        let signal = self.create_rw_signal(value);
+        (signal.read_only(), signal.write_only())
     }
 
     /// Create a RwSignal under this Scope
+    #[track_caller]
     pub fn create_rw_signal<T>(self, value: T) -> RwSignal<T>
     where
         T: Any + 'static,
     {
-        with_scope(self, || create_rw_signal(value))
+        let created_at = Location::caller();
+        with_scope(self, || create_rw_signal_at(value, created_at))
     }
 
     /// Create a Memo under this Scope
@@ -105,6 +109,7 @@ impl Scope {
 
     /// This is normally used in create_effect, and it will bind the effect's lifetime
     /// to this scope
+    #[track_caller]
     pub fn track(&self) {
         let tracker = if let Some(signal) = self.0.signal() {
             signal
@@ -113,6 +118,8 @@ impl Scope {
                 id: self.0,
                 subscribers: Rc::new(RefCell::new(HashMap::new())),
                 value: Rc::new(RefCell::new(())),
+                created_at: Location::caller(),
+                value_type: "()",
             };
             self.0.add_signal(signal.clone());
             signal
@@ -4,6 +4,7 @@ use std::{
     collections::HashMap,
     fmt,
     marker::PhantomData,
+    panic::Location,
     rc::Rc,
 };
 
@@ -12,6 +13,7 @@ use crate::{
     id::Id,
     read::{SignalRead, SignalTrack, SignalWith},
     runtime::RUNTIME,
+    trace::{signal_updated, SignalUpdateInfo},
     write::SignalWrite,
     SignalGet, SignalUpdate,
 };
@@ -66,9 +68,11 @@ impl<T> RwSignal<T> {
 }
 
 impl<T: 'static> RwSignal<T> {
+    #[track_caller]
     pub fn new(value: T) -> Self {
         create_rw_signal(value)
     }
+    #[track_caller]
     pub fn new_split(value: T) -> (ReadSignal<T>, WriteSignal<T>) {
         let sig = Self::new(value);
         (sig.read_only(), sig.write_only())
@@ -80,11 +84,23 @@ impl<T: 'static> RwSignal<T> {
 /// Accessing the signal value in an Effect will make the Effect subscribe
 /// to the value change of the Signal. And whenever the signal value changes,
 /// it will trigger an effect run.
+#[track_caller]
 pub fn create_rw_signal<T>(value: T) -> RwSignal<T>
 where
     T: Any + 'static,
 {
-    let id = Signal::create(value);
+    create_rw_signal_at(value, Location::caller())
+}
+
+/// Creates a new RwSignal, which was created by the code at `created_at`.
+pub(crate) fn create_rw_signal_at<T>(
+    value: T,
+    created_at: &'static Location<'static>,
+) -> RwSignal<T>
+where
+    T: Any + 'static,
+{
+    let id = Signal::create_at(value, created_at);
     id.set_scope();
     RwSignal {
         id,
@@ -141,6 +157,7 @@ impl<T> PartialEq for WriteSignal<T> {
 /// Accessing the signal value in an Effect will make the Effect subscribe
 /// to the value change of the Signal. And whenever the signal value changes,
 /// it will trigger an effect run.
+#[track_caller]
 pub fn create_signal<T>(value: T) -> (ReadSignal<T>, WriteSignal<T>)
 where
     T: Any + 'static,
@@ -155,10 +172,21 @@ pub(crate) struct Signal {
     pub(crate) id: Id,
     pub(crate) value: Rc<dyn Any>,
     pub(crate) subscribers: Rc<RefCell<HashMap<Id, Rc<dyn EffectTrait>>>>,
+    /// Where the signal was created, for debugging.
+    pub(crate) created_at: &'static Location<'static>,
+    pub(crate) value_type: &'static str,
 }
 
 impl Signal {
+    #[track_caller]
     pub fn create<T>(value: T) -> Id
+    where
+        T: Any + 'static,
+    {
+        Self::create_at(value, Location::caller())
+    }
+
+    pub(crate) fn create_at<T>(value: T, created_at: &'static Location<'static>) -> Id
     where
         T: Any + 'static,
     {
@@ -168,6 +196,8 @@ impl Signal {
             id,
             subscribers: Rc::new(RefCell::new(HashMap::new())),
             value: Rc::new(value),
+            created_at,
+            value_type: std::any::type_name::<T>(),
         };
         id.add_signal(signal);
         id
@@ -216,6 +246,12 @@ impl Signal {
     }
 
     pub(crate) fn run_effects(&self) {
+        signal_updated(SignalUpdateInfo {
+            created_at: self.created_at,
+            value_type: self.value_type,
+            subscribers: self.subscribers.borrow().len(),
+        });
+
         // If we are batching then add it as a pending effect
         if RUNTIME.with(|r| r.batching.get()) {
             RUNTIME.with(|r| {
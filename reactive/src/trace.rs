@@ -0,0 +1,44 @@
+use std::{panic::Location, rc::Rc};
+
+use crate::runtime::RUNTIME;
+
+pub(crate) type SignalUpdateHook = Rc<dyn Fn(&SignalUpdateInfo)>;
+
+/// A signal whose value was updated, passed to the hook of [`set_signal_update_hook`].
+#[derive(Debug, Clone, Copy)]
+pub struct SignalUpdateInfo {
+    /// Where the signal was created.
+    pub created_at: &'static Location<'static>,
+    /// The type of the value of the signal.
+    pub value_type: &'static str,
+    /// The number of effects that subscribe to the signal, which run because of the update.
+    pub subscribers: usize,
+}
+
+/// Sets a hook that is called whenever the value of a signal on this thread is updated, before
+/// the effects that subscribe to it run.
+///
+/// This is meant for debugging tools, to find out which signals cause effects to run. Signals
+/// updated by the hook itself don't call it again.
+pub fn set_signal_update_hook(hook: impl Fn(&SignalUpdateInfo) + 'static) {
+    RUNTIME.with(|runtime| *runtime.signal_update_hook.borrow_mut() = Some(Rc::new(hook)));
+}
+
+/// Removes the hook set by [`set_signal_update_hook`].
+pub fn clear_signal_update_hook() {
+    RUNTIME.with(|runtime| *runtime.signal_update_hook.borrow_mut() = None);
+}
+
+pub(crate) fn signal_updated(info: SignalUpdateInfo) {
+    let hook = RUNTIME.with(|runtime| runtime.signal_update_hook.borrow_mut().take());
+    if let Some(hook) = hook {
+        hook(&info);
+        RUNTIME.with(|runtime| {
+            // the hook may have been replaced or cleared while it ran
+            let mut current = runtime.signal_update_hook.borrow_mut();
+            if current.is_none() {
+                *current = Some(hook);
+            }
+        });
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     rc::Rc,
 };
 
-use crate::id::Id;
+use crate::id::{Id, DISPOSED};
 
 #[derive(Clone)]
 pub struct WriteSignalValue<T> {
@@ -71,7 +71,7 @@ pub trait SignalWrite<T> {
     where
         T: 'static,
     {
-        self.try_write().unwrap()
+        self.try_write().expect(DISPOSED)
     }
 
     /// If the Signal isn't disposed,
@@ -0,0 +1,56 @@
+use std::{cell::RefCell, rc::Rc};
+
+use floem_reactive::{
+    clear_signal_update_hook, create_effect, create_rw_signal, set_signal_update_hook, Scope,
+    SignalGet, SignalTrack, SignalUpdate, SignalUpdateInfo,
+};
+
+#[test]
+fn signal_updates_report_their_creation_site() {
+    let updates = Rc::new(RefCell::new(Vec::<SignalUpdateInfo>::new()));
+    set_signal_update_hook({
+        let updates = updates.clone();
+        move |info| updates.borrow_mut().push(*info)
+    });
+
+    let count = create_rw_signal(0);
+    let line = line!() - 1;
+    let (name, set_name) = Scope::new().create_signal(String::new());
+    let scope_line = line!() - 1;
+    create_effect(move |_| count.track());
+
+    count.set(1);
+    set_name.set("a".to_string());
+    name.track();
+    clear_signal_update_hook();
+    count.set(2);
+
+    let updates = updates.borrow();
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates[0].created_at.file(), file!());
+    assert_eq!(updates[0].created_at.line(), line);
+    assert_eq!(updates[0].value_type, "i32");
+    assert_eq!(updates[0].subscribers, 1);
+    assert_eq!(updates[1].created_at.line(), scope_line);
+    assert_eq!(updates[1].value_type, "alloc::string::String");
+    assert_eq!(updates[1].subscribers, 0);
+}
+
+#[test]
+fn signals_updated_by_the_hook_are_not_reported_again() {
+    let other = create_rw_signal(0);
+    let calls = Rc::new(RefCell::new(0));
+    set_signal_update_hook({
+        let calls = calls.clone();
+        move |_| {
+            *calls.borrow_mut() += 1;
+            other.update(|value| *value += 1);
+        }
+    });
+
+    create_rw_signal(0).set(1);
+    clear_signal_update_hook();
+
+    assert_eq!(*calls.borrow(), 1);
+    assert_eq!(other.get_untracked(), 1);
+}
@@ -0,0 +1,381 @@
+//! Drawing scenarios that are run through each renderer in capture mode and compared against
+//! reference images, so the renderers draw the same and the features that a renderer is missing
+//! are tracked.
+//!
+//! The scenarios are run by `tests/conformance.rs`. The reference images are in `references`,
+//! and are written again from the output of a renderer with
+//! `UPDATE_REFERENCES=<renderer> cargo test -p floem_renderer_tests`, after checking that the
+//! renderer draws them right. The scenarios that a renderer draws differently are listed in
+//! [`known_gap`], and only fail once the renderer draws them right, so the list is kept up to
+//! date. A scenario without a reference image fails when the `CI` environment variable is set.
+
+use std::sync::Arc;
+
+use floem_renderer::{
+    text::{Attrs, AttrsList, TextLayout},
+    usvg, Img, Renderer, Svg,
+};
+use peniko::{
+    kurbo::{Affine, Circle, Point, Rect, RoundedRect, Stroke},
+    Blob, Color, Format, Gradient, Image,
+};
+
+/// The size of the images of the scenarios.
+pub const SIZE: u32 = 64;
+
+const ICON: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16">
+<path d="M2 2h12v12H2z" fill="#2060c0"/><circle cx="8" cy="8" r="4" fill="#f0c020"/>
+</svg>"##;
+
+/// A drawing that each renderer is compared on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scenario {
+    /// Rects, rounded rects and circles filled with solid colors.
+    Shapes,
+    /// Translucent shapes drawn over each other.
+    AlphaBlending,
+    /// A linear gradient with two stops.
+    LinearGradient,
+    /// A linear gradient with more than two stops.
+    MultiStopGradient,
+    /// A radial gradient.
+    RadialGradient,
+    /// Shapes drawn in a clip, and in a clip within a transform.
+    Clips,
+    /// Transforms within transforms, like the views within views.
+    NestedTransforms,
+    /// A rect filled with a blur, like a shadow.
+    BlurredFill,
+    /// Strokes of different widths.
+    Strokes,
+    /// A stroke with a dash pattern.
+    DashedStroke,
+    /// A line of text.
+    Text,
+    /// An image scaled up to a rect.
+    Image,
+    /// An SVG with its own colors, and filled with a color.
+    Svg,
+}
+
+impl Scenario {
+    /// All the scenarios.
+    pub const ALL: [Scenario; 13] = [
+        Scenario::Shapes,
+        Scenario::AlphaBlending,
+        Scenario::LinearGradient,
+        Scenario::MultiStopGradient,
+        Scenario::RadialGradient,
+        Scenario::Clips,
+        Scenario::NestedTransforms,
+        Scenario::BlurredFill,
+        Scenario::Strokes,
+        Scenario::DashedStroke,
+        Scenario::Text,
+        Scenario::Image,
+        Scenario::Svg,
+    ];
+
+    /// The name of the reference image of the scenario.
+    pub fn name(self) -> &'static str {
+        match self {
+            Scenario::Shapes => "shapes",
+            Scenario::AlphaBlending => "alpha_blending",
+            Scenario::LinearGradient => "linear_gradient",
+            Scenario::MultiStopGradient => "multi_stop_gradient",
+            Scenario::RadialGradient => "radial_gradient",
+            Scenario::Clips => "clips",
+            Scenario::NestedTransforms => "nested_transforms",
+            Scenario::BlurredFill => "blurred_fill",
+            Scenario::Strokes => "strokes",
+            Scenario::DashedStroke => "dashed_stroke",
+            Scenario::Text => "text",
+            Scenario::Image => "image",
+            Scenario::Svg => "svg",
+        }
+    }
+
+    /// The most that a channel of a pixel may differ from the reference, which is higher for the
+    /// scenarios whose edges are antialiased differently by each renderer.
+    pub fn tolerance(self) -> Tolerance {
+        match self {
+            Scenario::Text | Scenario::BlurredFill => Tolerance {
+                channel: 48,
+                pixels: 0.05,
+            },
+            _ => Tolerance {
+                channel: 24,
+                pixels: 0.02,
+            },
+        }
+    }
+
+    /// Draws the scenario with `renderer`, between [`Renderer::begin`] and [`Renderer::finish`].
+    pub fn draw(self, renderer: &mut impl Renderer) {
+        let full = Rect::new(0.0, 0.0, SIZE as f64, SIZE as f64);
+        match self {
+            Scenario::Shapes => {
+                renderer.fill(
+                    &Rect::new(4.0, 4.0, 28.0, 28.0),
+                    Color::rgb8(200, 40, 40),
+                    0.0,
+                );
+                renderer.fill(
+                    &RoundedRect::new(36.0, 4.0, 60.0, 28.0, 6.0),
+                    Color::rgb8(40, 160, 60),
+                    0.0,
+                );
+                renderer.fill(
+                    &Circle::new((32.0, 46.0), 14.0),
+                    Color::rgb8(40, 80, 200),
+                    0.0,
+                );
+            }
+            Scenario::AlphaBlending => {
+                renderer.fill(
+                    &Rect::new(8.0, 8.0, 40.0, 40.0),
+                    Color::rgba8(255, 0, 0, 160),
+                    0.0,
+                );
+                renderer.fill(
+                    &Rect::new(24.0, 24.0, 56.0, 56.0),
+                    Color::rgba8(0, 0, 255, 96),
+                    0.0,
+                );
+            }
+            Scenario::LinearGradient => {
+                let gradient = Gradient::new_linear((0.0, 0.0), (SIZE as f64, 0.0))
+                    .with_stops([Color::rgb8(255, 0, 0), Color::rgb8(0, 0, 255)]);
+                renderer.fill(&full, &gradient, 0.0);
+            }
+            Scenario::MultiStopGradient => {
+                let gradient = Gradient::new_linear((0.0, 0.0), (0.0, SIZE as f64)).with_stops([
+                    Color::rgb8(255, 0, 0),
+                    Color::rgb8(0, 255, 0),
+                    Color::rgb8(0, 0, 255),
+                ]);
+                renderer.fill(&full, &gradient, 0.0);
+            }
+            Scenario::RadialGradient => {
+                let gradient = Gradient::new_radial((32.0, 32.0), 30.0)
+                    .with_stops([Color::rgb8(255, 255, 0), Color::rgb8(0, 128, 0)]);
+                renderer.fill(&full, &gradient, 0.0);
+            }
+            Scenario::Clips => {
+                renderer.clip(&Rect::new(8.0, 8.0, 32.0, 32.0));
+                renderer.fill(
+                    &Circle::new((20.0, 20.0), 20.0),
+                    Color::rgb8(200, 40, 40),
+                    0.0,
+                );
+                renderer.transform(Affine::translate((24.0, 24.0)));
+                renderer.clip(&Rect::new(8.0, 8.0, 32.0, 32.0));
+                renderer.fill(
+                    &Circle::new((20.0, 20.0), 20.0),
+                    Color::rgb8(40, 80, 200),
+                    0.0,
+                );
+                renderer.clear_clip();
+                renderer.transform(Affine::IDENTITY);
+            }
+            Scenario::NestedTransforms => {
+                let mut transform = Affine::IDENTITY;
+                for (i, color) in [Color::RED, Color::GREEN, Color::BLUE]
+                    .into_iter()
+                    .enumerate()
+                {
+                    renderer.transform(transform);
+                    renderer.fill(&Rect::new(0.0, 0.0, 24.0, 24.0), color, 0.0);
+                    transform = transform
+                        * Affine::translate((12.0, 12.0))
+                        * Affine::scale(if i == 0 { 1.0 } else { 0.75 });
+                }
+                renderer.transform(Affine::IDENTITY);
+            }
+            Scenario::BlurredFill => {
+                renderer.fill(
+                    &Rect::new(16.0, 16.0, 48.0, 48.0),
+                    Color::rgb8(0, 0, 0),
+                    6.0,
+                );
+            }
+            Scenario::Strokes => {
+                for (i, width) in [1.0, 2.0, 4.0].into_iter().enumerate() {
+                    let y = 12.0 + i as f64 * 18.0;
+                    renderer.stroke(
+                        &Rect::new(8.0, y, 56.0, y + 10.0),
+                        Color::rgb8(40, 40, 40),
+                        &Stroke::new(width),
+                    );
+                }
+            }
+            Scenario::DashedStroke => {
+                let stroke = Stroke::new(2.0).with_dashes(0.0, [6.0, 4.0]);
+                renderer.stroke(
+                    &Rect::new(8.0, 8.0, 56.0, 56.0),
+                    Color::rgb8(40, 40, 40),
+                    &stroke,
+                );
+            }
+            Scenario::Text => {
+                let attrs = Attrs::new().font_size(20.0).color(Color::BLACK);
+                let layout = TextLayout::new_with_text("Floem", AttrsList::new(attrs));
+                renderer.draw_text(&layout, Point::new(4.0, 18.0));
+            }
+            Scenario::Image => {
+                // a checkerboard of 4 by 4 pixels, scaled up 8 times
+                let data = (0..16)
+                    .flat_map(|i| {
+                        if (i % 4 + i / 4) % 2 == 0 {
+                            [255, 128, 0, 255]
+                        } else {
+                            [0, 128, 255, 255]
+                        }
+                    })
+                    .collect::<Vec<u8>>();
+                let image = Image::new(Blob::new(Arc::new(data)), Format::Rgba8, 4, 4);
+                renderer.draw_img(
+                    Img {
+                        img: image,
+                        hash: b"renderer-tests-checkerboard",
+                    },
+                    Rect::new(16.0, 16.0, 48.0, 48.0),
+                );
+            }
+            Scenario::Svg => {
+                let tree = usvg::Tree::from_str(ICON, &usvg::Options::default()).unwrap();
+                let svg = || Svg {
+                    tree: &tree,
+                    hash: b"renderer-tests-icon",
+                };
+                renderer.draw_svg(svg(), Rect::new(4.0, 4.0, 28.0, 28.0), None::<Color>);
+                renderer.draw_svg(
+                    svg(),
+                    Rect::new(32.0, 32.0, 60.0, 60.0),
+                    Some(Color::rgb8(200, 40, 40)),
+                );
+            }
+        }
+    }
+}
+
+/// Whether `renderer` is known to draw `scenario` differently from the reference, because it
+/// doesn't support one of the features that the scenario uses.
+pub fn known_gap(renderer: &str, scenario: Scenario) -> bool {
+    matches!(
+        (renderer, scenario),
+        // the dash pattern of strokes is ignored
+        ("tiny_skia" | "vger", Scenario::DashedStroke)
+            // vger only draws two stop linear gradients
+            | ("vger", Scenario::RadialGradient | Scenario::MultiStopGradient)
+    )
+}
+
+/// How much an image may differ from its reference.
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+    /// The most that a channel of a pixel may differ without the pixel counting as different.
+    pub channel: u8,
+    /// The share of the pixels that may be different, from 0 to 1.
+    pub pixels: f64,
+}
+
+/// How an image differs from its reference.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Difference {
+    /// The pixels that differ by more than the tolerance of a channel.
+    pub pixels: usize,
+    /// The largest difference of a channel.
+    pub max_channel: u8,
+}
+
+impl Difference {
+    /// Whether the difference is within `tolerance` for an image of `SIZE` by `SIZE`.
+    pub fn within(&self, tolerance: Tolerance) -> bool {
+        self.pixels as f64 <= tolerance.pixels * (SIZE * SIZE) as f64
+    }
+}
+
+/// Compares the RGBA pixels of `actual` and `expected`, or returns `None` if their sizes differ.
+/// Transparent pixels are compared as if drawn on white, like the captures of the renderers.
+pub fn compare(
+    actual: &Image,
+    expected: &image::RgbaImage,
+    tolerance: Tolerance,
+) -> Option<Difference> {
+    if (actual.width, actual.height) != expected.dimensions() {
+        return None;
+    }
+    let on_white = |rgba: &[u8]| -> [u8; 3] {
+        let alpha = rgba[3] as u32;
+        [0, 1, 2].map(|c| ((rgba[c] as u32 * alpha + 255 * (255 - alpha) + 127) / 255) as u8)
+    };
+    let mut difference = Difference {
+        pixels: 0,
+        max_channel: 0,
+    };
+    for (actual, expected) in actual
+        .data
+        .data()
+        .chunks_exact(4)
+        .zip(expected.as_raw().chunks_exact(4))
+    {
+        let max = on_white(actual)
+            .into_iter()
+            .zip(on_white(expected))
+            .map(|(a, b)| a.abs_diff(b))
+            .max()
+            .unwrap_or(0);
+        difference.max_channel = difference.max_channel.max(max);
+        if max > tolerance.channel {
+            difference.pixels += 1;
+        }
+    }
+    Some(difference)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use peniko::{Blob, Format, Image};
+
+    use super::{compare, Tolerance};
+
+    fn image(pixels: &[[u8; 4]]) -> Image {
+        let data = pixels.concat();
+        Image::new(
+            Blob::new(Arc::new(data)),
+            Format::Rgba8,
+            pixels.len() as u32,
+            1,
+        )
+    }
+
+    #[test]
+    fn differences_within_the_tolerance_of_a_channel() {
+        let tolerance = Tolerance {
+            channel: 10,
+            pixels: 0.0,
+        };
+        let expected =
+            image::RgbaImage::from_raw(2, 1, [[100, 100, 100, 255]; 2].concat()).unwrap();
+        let actual = image(&[[105, 100, 100, 255], [100, 130, 100, 255]]);
+        let difference = compare(&actual, &expected, tolerance).unwrap();
+        assert_eq!(difference.pixels, 1);
+        assert_eq!(difference.max_channel, 30);
+    }
+
+    #[test]
+    fn transparent_pixels_are_on_white() {
+        let tolerance = Tolerance {
+            channel: 0,
+            pixels: 0.0,
+        };
+        let expected = image::RgbaImage::from_raw(1, 1, vec![255, 255, 255, 255]).unwrap();
+        let actual = image(&[[0, 0, 0, 0]]);
+        assert_eq!(compare(&actual, &expected, tolerance).unwrap().pixels, 0);
+        let other_size = image(&[[0, 0, 0, 0], [0, 0, 0, 0]]);
+        assert!(compare(&other_size, &expected, tolerance).is_none());
+    }
+}
@@ -0,0 +1,216 @@
+//! Runs the scenarios through each renderer and compares them against the reference images.
+//!
+//! The test has its own `main`, as the wgpu renderers need a window, which is created on the
+//! main thread.
+
+use std::{env, path::PathBuf, process::ExitCode};
+
+use floem_renderer::Renderer;
+use floem_renderer_tests::{compare, known_gap, Scenario, SIZE};
+use floem_tiny_skia_renderer::TinySkiaRenderer;
+use raw_window_handle::{
+    DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, WindowHandle,
+};
+
+/// A renderer that the scenarios are run through.
+trait Backend {
+    fn name(&self) -> &'static str;
+
+    /// Draws `scenario` in capture mode and returns the image, or `None` if the renderer can't
+    /// be created.
+    fn render(&mut self, scenario: Scenario) -> Option<peniko::Image>;
+}
+
+fn capture(mut renderer: impl Renderer, scenario: Scenario) -> Option<peniko::Image> {
+    renderer.begin(true);
+    scenario.draw(&mut renderer);
+    renderer.finish()
+}
+
+/// Stands in for the window of the offscreen renderer, which never uses it.
+struct NoWindow;
+
+impl HasWindowHandle for NoWindow {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        Err(HandleError::Unavailable)
+    }
+}
+
+impl HasDisplayHandle for NoWindow {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        Err(HandleError::Unavailable)
+    }
+}
+
+struct TinySkia;
+
+impl Backend for TinySkia {
+    fn name(&self) -> &'static str {
+        "tiny_skia"
+    }
+
+    fn render(&mut self, scenario: Scenario) -> Option<peniko::Image> {
+        let renderer = TinySkiaRenderer::<NoWindow>::new_offscreen(SIZE, SIZE, 1.0, 0.0).ok()?;
+        capture(renderer, scenario)
+    }
+}
+
+#[cfg(feature = "gpu")]
+mod gpu {
+    use std::sync::Arc;
+
+    use floem_renderer::gpu_resources::GpuResources;
+    use floem_renderer_tests::{Scenario, SIZE};
+    use floem_vello_renderer::VelloRenderer;
+    use floem_vger_renderer::VgerRenderer;
+    use floem_winit::{
+        dpi::PhysicalSize,
+        event_loop::EventLoop,
+        window::{Window, WindowBuilder},
+    };
+
+    use super::{capture, Backend};
+
+    /// A hidden window that the surfaces of the wgpu renderers are created for.
+    pub(super) struct GpuWindow {
+        window: Arc<Window>,
+        _event_loop: EventLoop<()>,
+    }
+
+    impl GpuWindow {
+        pub(super) fn new() -> Option<Self> {
+            let event_loop = EventLoop::new().ok()?;
+            let window = WindowBuilder::new()
+                .with_visible(false)
+                .with_inner_size(PhysicalSize::new(SIZE, SIZE))
+                .build(&event_loop)
+                .ok()?;
+            Some(GpuWindow {
+                window: Arc::new(window),
+                _event_loop: event_loop,
+            })
+        }
+
+        fn resources(&self) -> Option<GpuResources> {
+            GpuResources::request(|_| {}, self.window.clone())
+                .recv()
+                .ok()?
+                .ok()
+        }
+    }
+
+    pub(super) struct Vger(pub(super) Arc<GpuWindow>);
+
+    impl Backend for Vger {
+        fn name(&self) -> &'static str {
+            "vger"
+        }
+
+        fn render(&mut self, scenario: Scenario) -> Option<peniko::Image> {
+            let renderer = VgerRenderer::new(self.0.resources()?, SIZE, SIZE, 1.0, 0.0).ok()?;
+            capture(renderer, scenario)
+        }
+    }
+
+    pub(super) struct Vello(pub(super) Arc<GpuWindow>);
+
+    impl Backend for Vello {
+        fn name(&self) -> &'static str {
+            "vello"
+        }
+
+        fn render(&mut self, scenario: Scenario) -> Option<peniko::Image> {
+            let renderer = VelloRenderer::new(self.0.resources()?, SIZE, SIZE, 1.0, 0.0).ok()?;
+            capture(renderer, scenario)
+        }
+    }
+}
+
+fn backends() -> Vec<Box<dyn Backend>> {
+    #[allow(unused_mut)]
+    let mut backends: Vec<Box<dyn Backend>> = vec![Box::new(TinySkia)];
+    #[cfg(feature = "gpu")]
+    match gpu::GpuWindow::new() {
+        Some(window) => {
+            let window = std::sync::Arc::new(window);
+            backends.push(Box::new(gpu::Vger(window.clone())));
+            backends.push(Box::new(gpu::Vello(window)));
+        }
+        None => eprintln!("skipping the wgpu renderers: no window could be created"),
+    }
+    backends
+}
+
+fn reference_path(scenario: Scenario) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("references")
+        .join(format!("{}.png", scenario.name()))
+}
+
+fn main() -> ExitCode {
+    // the renderer whose output is written as the references
+    let update = env::var("UPDATE_REFERENCES").ok();
+    let mut failures = Vec::new();
+
+    for mut backend in backends() {
+        let name = backend.name();
+        for scenario in Scenario::ALL {
+            let gap = known_gap(name, scenario);
+            let Some(image) = backend.render(scenario) else {
+                failures.push(format!("{name}: {} couldn't be rendered", scenario.name()));
+                continue;
+            };
+            let path = reference_path(scenario);
+
+            if update.as_deref() == Some(name) && !gap {
+                let reference = image::RgbaImage::from_raw(
+                    image.width,
+                    image.height,
+                    image.data.data().to_vec(),
+                )
+                .unwrap();
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                reference.save(&path).unwrap();
+                println!("{name}: wrote {}", path.display());
+                continue;
+            }
+
+            let Ok(reference) = image::open(&path) else {
+                // like the golden images of the headless harness, a missing reference only
+                // fails in CI, so new scenarios can be added before their references. The known
+                // gaps of the renderer the references were written with have no reference until
+                // another renderer writes it.
+                if env::var_os("CI").is_some() && !gap {
+                    failures.push(format!("{name}: {} has no reference", scenario.name()));
+                } else {
+                    println!("{name}: {} has no reference", scenario.name());
+                }
+                continue;
+            };
+            let tolerance = scenario.tolerance();
+            let matches = compare(&image, &reference.into_rgba8(), tolerance)
+                .filter(|difference| difference.within(tolerance));
+            match (matches, gap) {
+                (Some(_), false) => println!("{name}: {} ok", scenario.name()),
+                (None, true) => println!("{name}: {} differs, as expected", scenario.name()),
+                (Some(_), true) => failures.push(format!(
+                    "{name}: {} matches the reference, remove it from `known_gap`",
+                    scenario.name()
+                )),
+                (None, false) => failures.push(format!(
+                    "{name}: {} differs from the reference",
+                    scenario.name()
+                )),
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        for failure in &failures {
+            eprintln!("{failure}");
+        }
+        ExitCode::FAILURE
+    }
+}
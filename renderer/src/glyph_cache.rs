@@ -0,0 +1,185 @@
+//! The budget and the statistics of the caches of rasterized glyphs of the renderers.
+//!
+//! Each renderer keeps the glyphs it drew in the last two frames in a [`GlyphCache`], which
+//! drops them early once it holds more than [`budget`] bytes. The [`stats`] add up the caches of
+//! all the renderers. The vger renderer keeps its glyphs in its own atlas, so its glyphs only
+//! count towards the hits and misses, and aren't dropped by [`clear`].
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+/// The budget of each cache until it's changed with [`set_budget`].
+pub const DEFAULT_BUDGET: usize = 32 * 1024 * 1024;
+
+static BUDGET: AtomicUsize = AtomicUsize::new(DEFAULT_BUDGET);
+static ENTRIES: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+/// Counts the calls to [`clear`], so the caches are cleared at their next use.
+static CLEARS: AtomicU64 = AtomicU64::new(0);
+
+/// The statistics of the glyph caches of all the renderers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GlyphCacheStats {
+    /// The glyphs in the caches.
+    pub entries: usize,
+    /// The size of the glyphs in the caches.
+    pub bytes: usize,
+    /// The glyphs that were drawn from a cache, since the statistics were reset.
+    pub hits: u64,
+    /// The glyphs that were rasterized to be drawn, since the statistics were reset.
+    pub misses: u64,
+}
+
+impl GlyphCacheStats {
+    /// The share of the glyphs that were drawn from a cache, from 0 to 1.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// The statistics of the glyph caches of all the renderers.
+pub fn stats() -> GlyphCacheStats {
+    GlyphCacheStats {
+        entries: ENTRIES.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets the hits and the misses of the [`stats`], e.g. before measuring a scenario.
+pub fn reset_stats() {
+    HITS.store(0, Ordering::Relaxed);
+    MISSES.store(0, Ordering::Relaxed);
+}
+
+/// Counts a glyph drawn from a cache, or rasterized if it wasn't `hit`.
+pub fn record(hit: bool) {
+    if hit {
+        HITS.fetch_add(1, Ordering::Relaxed);
+    } else {
+        MISSES.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Sets the most bytes that the glyph cache of each renderer holds.
+pub fn set_budget(bytes: usize) {
+    BUDGET.store(bytes, Ordering::Relaxed);
+}
+
+/// The most bytes that the glyph cache of each renderer holds.
+pub fn budget() -> usize {
+    BUDGET.load(Ordering::Relaxed)
+}
+
+/// Drops the glyphs of the caches of all the renderers, the next time they draw text.
+pub fn clear() {
+    CLEARS.fetch_add(1, Ordering::Relaxed);
+}
+
+struct Entry<V> {
+    value: V,
+    size: usize,
+    /// The frame the glyph was last drawn in.
+    frame: bool,
+}
+
+/// The glyphs of a renderer that were drawn in the last two frames, counted in the [`stats`].
+pub struct GlyphCache<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    bytes: usize,
+    /// Flips each frame, to drop the glyphs that aren't drawn anymore.
+    frame: bool,
+    clears: u64,
+}
+
+impl<K, V> Default for GlyphCache<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            bytes: 0,
+            frame: false,
+            clears: CLEARS.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> GlyphCache<K, V> {
+    /// The glyph with `key`, which is made with `make`, along with its size in bytes, if it isn't
+    /// in the cache.
+    pub fn get_or_insert_with(&mut self, key: K, make: impl FnOnce() -> (V, usize)) -> V {
+        let clears = CLEARS.load(Ordering::Relaxed);
+        if clears != self.clears {
+            self.clears = clears;
+            self.retain(|_| false);
+        }
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.frame = self.frame;
+            record(true);
+            return entry.value.clone();
+        }
+
+        record(false);
+        let (value, size) = make();
+        self.entries.insert(
+            key,
+            Entry {
+                value: value.clone(),
+                size,
+                frame: self.frame,
+            },
+        );
+        self.bytes += size;
+        ENTRIES.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        if self.bytes > budget() {
+            // the glyphs of the previous frame go first
+            let frame = self.frame;
+            self.retain(|entry| entry.frame == frame);
+            if self.bytes > budget() {
+                self.retain(|_| false);
+            }
+        }
+        value
+    }
+
+    /// Drops the glyphs that weren't drawn in the frame that is finished.
+    pub fn finish_frame(&mut self) {
+        let frame = self.frame;
+        self.retain(|entry| entry.frame == frame);
+        self.frame = !frame;
+    }
+}
+
+impl<K, V> GlyphCache<K, V> {
+    fn retain(&mut self, keep: impl Fn(&Entry<V>) -> bool) {
+        let (mut entries, mut bytes) = (0, 0);
+        self.entries.retain(|_, entry| {
+            let kept = keep(entry);
+            if !kept {
+                entries += 1;
+                bytes += entry.size;
+            }
+            kept
+        });
+        self.bytes -= bytes;
+        ENTRIES.fetch_sub(entries, Ordering::Relaxed);
+        BYTES.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+impl<K, V> Drop for GlyphCache<K, V> {
+    fn drop(&mut self) {
+        self.retain(|_| false);
+    }
+}
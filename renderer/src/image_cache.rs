@@ -0,0 +1,145 @@
+//! The premultiplied pixels of images, shared by the renderers of every window.
+//!
+//! The cache holds at most [`limit`] bytes, and drops the images that were drawn the longest
+//! time ago once it's full.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
+
+use parking_lot::Mutex;
+use resvg::tiny_skia::{ColorU8, Pixmap};
+
+/// The limit of the cache until it's changed with [`set_limit`].
+pub const DEFAULT_LIMIT: usize = 256 * 1024 * 1024;
+
+static CACHE: OnceLock<Mutex<PixmapCache>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<PixmapCache> {
+    CACHE.get_or_init(|| Mutex::new(PixmapCache::new(DEFAULT_LIMIT)))
+}
+
+struct Entry {
+    pixmap: Arc<Pixmap>,
+    last_used: u64,
+}
+
+/// Pixmaps that take at most `limit` bytes, which drops the least recently used ones once
+/// it's full.
+pub(crate) struct PixmapCache {
+    entries: HashMap<Vec<u8>, Entry>,
+    /// The size of the pixels of the entries.
+    pub(crate) bytes: usize,
+    pub(crate) limit: usize,
+    /// Counts the uses of the entries, to find the least recently used one.
+    tick: u64,
+}
+
+impl PixmapCache {
+    pub(crate) fn new(limit: usize) -> Self {
+        PixmapCache {
+            entries: HashMap::new(),
+            bytes: 0,
+            limit,
+            tick: 0,
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &[u8]) -> Option<Arc<Pixmap>> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = self.tick;
+        Some(entry.pixmap.clone())
+    }
+
+    /// Adds `pixmap` unless it's larger than the limit, and returns it.
+    pub(crate) fn insert(&mut self, key: &[u8], pixmap: Pixmap) -> Arc<Pixmap> {
+        let pixmap = Arc::new(pixmap);
+        let size = pixmap.data().len();
+        if size > self.limit {
+            return pixmap;
+        }
+        self.tick += 1;
+        let entry = Entry {
+            pixmap: pixmap.clone(),
+            last_used: self.tick,
+        };
+        if let Some(old) = self.entries.insert(key.to_owned(), entry) {
+            self.bytes -= old.pixmap.data().len();
+        }
+        self.bytes += size;
+        self.evict();
+        pixmap
+    }
+
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.bytes > self.limit {
+            let Some(key) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.pixmap.data().len();
+            }
+        }
+    }
+}
+
+/// The pixels of the image with `hash`, if they're in the cache.
+pub fn get(hash: &[u8]) -> Option<Arc<Pixmap>> {
+    cache().lock().get(hash)
+}
+
+/// Adds the pixels of the image with `hash`, and returns them. Images larger than the limit
+/// aren't kept.
+pub fn insert(hash: &[u8], pixmap: Pixmap) -> Arc<Pixmap> {
+    cache().lock().insert(hash, pixmap)
+}
+
+/// The pixels of the image with `hash`, which are created from `image` and added to the cache
+/// if they aren't in it.
+pub fn get_or_insert(hash: &[u8], image: &peniko::Image) -> Option<Arc<Pixmap>> {
+    if let Some(pixmap) = get(hash) {
+        return Some(pixmap);
+    }
+    Some(insert(hash, premultiply(image)?))
+}
+
+/// The premultiplied pixels of `image`, or `None` if it's empty.
+pub fn premultiply(image: &peniko::Image) -> Option<Pixmap> {
+    let mut pixmap = Pixmap::new(image.width, image.height)?;
+    for (pixel, rgba) in pixmap
+        .pixels_mut()
+        .iter_mut()
+        .zip(image.data.data().chunks_exact(4))
+    {
+        *pixel = ColorU8::from_rgba(rgba[0], rgba[1], rgba[2], rgba[3]).premultiply();
+    }
+    Some(pixmap)
+}
+
+/// Sets the most bytes the cache holds, and drops the least recently drawn images until it
+/// holds less.
+pub fn set_limit(limit: usize) {
+    cache().lock().set_limit(limit);
+}
+
+/// The most bytes the cache holds.
+pub fn limit() -> usize {
+    cache().lock().limit
+}
+
+/// The bytes that the cache holds.
+pub fn usage() -> usize {
+    cache().lock().bytes
+}
@@ -1,3 +1,6 @@
+pub mod glyph_cache;
+pub mod image_cache;
+pub mod svg_cache;
 pub mod swash;
 pub mod text;
 
@@ -0,0 +1,88 @@
+//! The rasterized SVGs, shared by the renderers of every window.
+//!
+//! An SVG is rasterized once for each size it's drawn at, and for each color it's drawn with by
+//! the renderers that color the raster itself, so an icon drawn at several sizes doesn't
+//! rasterize again each time. The cache holds at most [`limit`] bytes, and drops the rasters
+//! that were drawn the longest time ago once it's full.
+
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::Mutex;
+use peniko::Color;
+use resvg::{
+    tiny_skia::{Pixmap, Transform},
+    usvg,
+};
+
+use crate::image_cache::PixmapCache;
+
+/// The limit of the cache until it's changed with [`set_limit`].
+pub const DEFAULT_LIMIT: usize = 64 * 1024 * 1024;
+
+static CACHE: OnceLock<Mutex<PixmapCache>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<PixmapCache> {
+    CACHE.get_or_init(|| Mutex::new(PixmapCache::new(DEFAULT_LIMIT)))
+}
+
+/// How an SVG is scaled to the size of its raster.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Fit {
+    /// Stretched to fill the raster.
+    Fill,
+    /// Scaled by the same factor on both axes to fit in the raster, from its top left corner.
+    Contain,
+}
+
+/// The key of the raster of the SVG with `hash` at `width` by `height`, which is filled with
+/// `color` if it's set.
+pub fn key(hash: &[u8], width: u32, height: u32, fit: Fit, color: Option<Color>) -> Vec<u8> {
+    let mut key = hash.to_vec();
+    key.extend_from_slice(&width.to_le_bytes());
+    key.extend_from_slice(&height.to_le_bytes());
+    key.push(fit as u8);
+    match color {
+        Some(color) => key.extend_from_slice(&[1, color.r, color.g, color.b, color.a]),
+        None => key.push(0),
+    }
+    key
+}
+
+/// The raster with `key`, which is made with `raster` and added to the cache if it isn't in it.
+pub fn get_or_insert(key: &[u8], raster: impl FnOnce() -> Option<Pixmap>) -> Option<Arc<Pixmap>> {
+    if let Some(pixmap) = cache().lock().get(key) {
+        return Some(pixmap);
+    }
+    // rasterized without holding the lock, so other windows aren't blocked
+    let pixmap = raster()?;
+    Some(cache().lock().insert(key, pixmap))
+}
+
+/// Rasterizes `tree` at `width` by `height`, or returns `None` if the size is empty.
+pub fn rasterize(tree: &usvg::Tree, width: u32, height: u32, fit: Fit) -> Option<Pixmap> {
+    let mut pixmap = Pixmap::new(width, height)?;
+    let scale_x = width as f32 / tree.size().width();
+    let scale_y = height as f32 / tree.size().height();
+    let transform = match fit {
+        Fit::Fill => Transform::from_scale(scale_x, scale_y),
+        Fit::Contain => Transform::from_scale(scale_x.min(scale_y), scale_x.min(scale_y)),
+    };
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+    Some(pixmap)
+}
+
+/// Sets the most bytes the cache holds, and drops the least recently drawn rasters until it
+/// holds less.
+pub fn set_limit(limit: usize) {
+    cache().lock().set_limit(limit);
+}
+
+/// The most bytes the cache holds.
+pub fn limit() -> usize {
+    cache().lock().limit
+}
+
+/// The bytes that the cache holds.
+pub fn usage() -> usize {
+    cache().lock().bytes
+}
@@ -1,6 +1,7 @@
-use cosmic_text::{CacheKey, CacheKeyFlags, SwashImage};
+use cosmic_text::{fontdb, CacheKey, CacheKeyFlags, SwashImage};
 use swash::{
     scale::{Render, ScaleContext, Source, StrikeWith},
+    tag_from_bytes,
     zeno::{Angle, Format, Transform, Vector},
 };
 
@@ -8,6 +9,19 @@ use crate::text::FONT_SYSTEM;
 
 const IS_MACOS: bool = cfg!(target_os = "macos");
 
+/// Whether the font has color glyphs, from `COLR` layers or `CBDT` and `sbix` bitmaps, like the
+/// emoji fonts. Their glyphs are rendered as images by [`SwashScaler::get_image`], and aren't
+/// drawn with the text color.
+pub fn is_color_font(font_id: fontdb::ID) -> bool {
+    let Some(font) = FONT_SYSTEM.lock().get_font(font_id) else {
+        return false;
+    };
+    let font = font.as_swash();
+    [b"COLR", b"CBDT", b"sbix"]
+        .into_iter()
+        .any(|tag| font.table(tag_from_bytes(tag)).is_some())
+}
+
 pub struct SwashScaler {
     context: ScaleContext,
     font_embolden: f32,
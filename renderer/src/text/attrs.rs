@@ -1,6 +1,8 @@
 use std::ops::Range;
 
-use crate::text::{fontdb, Family, Stretch, Style, Weight};
+use crate::text::{
+    fallback_chain, fontdb, is_font_family_installed, Family, Stretch, Style, Weight,
+};
 use peniko::Color;
 
 /// An owned version of [`Family`]
@@ -37,12 +39,21 @@ impl FamilyOwned {
         }
     }
 
+    /// Parses a comma separated list of families, replacing the names of fallback chains with
+    /// their families.
     pub fn parse_list(s: &str) -> impl Iterator<Item = FamilyOwned> + '_ + Clone {
         ParseList {
             source: s.as_bytes(),
             len: s.len(),
             pos: 0,
         }
+        .flat_map(|family| {
+            let chain = match &family {
+                FamilyOwned::Name(name) => fallback_chain(name),
+                _ => None,
+            };
+            chain.unwrap_or_else(|| vec![family])
+        })
     }
 }
 
@@ -111,9 +122,16 @@ impl<'a> Attrs<'a> {
         self
     }
 
-    /// Set [Family]
+    /// Set [Family] to the first of the families that is installed, or the first one if none
+    /// are
     pub fn family(mut self, family: &'a [FamilyOwned]) -> Self {
-        if let Some(family) = family.first() {
+        let installed = match family {
+            [_, _, ..] => family
+                .iter()
+                .find(|family| is_font_family_installed(family)),
+            _ => None,
+        };
+        if let Some(family) = installed.or(family.first()) {
             self.attrs = self.attrs.family(family.as_family());
         }
         self
@@ -0,0 +1,82 @@
+use std::{ops::Range, sync::LazyLock};
+
+use super::FONT_SYSTEM;
+
+/// The color emoji fonts, in the order they're preferred when several are installed.
+const EMOJI_FAMILIES: &[&str] = &[
+    "Apple Color Emoji",
+    "Segoe UI Emoji",
+    "Noto Color Emoji",
+    "Twemoji",
+    "Twitter Color Emoji",
+    "JoyPixels",
+    "EmojiOne Color",
+    "Blobmoji",
+];
+
+/// The color emoji font that is installed, which emoji are shaped with instead of the
+/// monochrome symbol fonts the regular fallback may find first.
+pub static EMOJI_FAMILY: LazyLock<Option<String>> = LazyLock::new(|| {
+    let font_system = FONT_SYSTEM.lock();
+    let db = font_system.db();
+    EMOJI_FAMILIES
+        .iter()
+        .find(|family| {
+            db.faces().any(|face| {
+                face.families
+                    .iter()
+                    .any(|(name, _)| name.eq_ignore_ascii_case(family))
+            })
+        })
+        .map(|family| family.to_string())
+});
+
+/// Whether `c` is an emoji that is shown in color by default.
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x1F000..=0x1FAFF)
+}
+
+/// Whether `c` is shown as an emoji when followed by the emoji variation selector, like `❤️`.
+fn has_emoji_variant(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x00A9 | 0x00AE | 0x203C | 0x2049 | 0x2122 | 0x2139 | 0x2194..=0x21AA
+            | 0x2300..=0x23FF | 0x24C2 | 0x25AA..=0x25FE | 0x2600..=0x27BF
+            | 0x2934 | 0x2935 | 0x2B00..=0x2BFF | 0x3030 | 0x303D | 0x3297 | 0x3299
+            | 0x0023 | 0x002A | 0x0030..=0x0039
+    )
+}
+
+/// Whether `c` continues the emoji before it: a variation selector, a skin tone, a keycap or a
+/// tag of a flag.
+fn continues_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0xFE0F | 0x20E3 | 0x1F3FB..=0x1F3FF | 0xE0020..=0xE007F
+    )
+}
+
+/// The byte ranges of the emoji in `text`, including their modifiers and the sequences joined
+/// with a zero width joiner.
+pub fn emoji_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    // whether the next character is part of the emoji before it
+    let mut joined = false;
+    while let Some((i, c)) = chars.next() {
+        let end = i + c.len_utf8();
+        let next = chars.peek().map(|(_, c)| *c);
+        let in_emoji = ranges.last().is_some_and(|range| range.end == i);
+        if in_emoji && (joined || continues_emoji(c)) {
+            ranges.last_mut().unwrap().end = end;
+            joined = false;
+        } else if in_emoji && c == '\u{200D}' {
+            ranges.last_mut().unwrap().end = end;
+            joined = true;
+        } else if is_emoji(c) || (has_emoji_variant(c) && next == Some('\u{FE0F}')) {
+            ranges.push(i..end);
+            joined = false;
+        }
+    }
+    ranges
+}
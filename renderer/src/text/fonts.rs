@@ -0,0 +1,74 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, LazyLock},
+};
+
+use parking_lot::RwLock;
+
+use super::{FamilyOwned, FONT_SYSTEM};
+
+/// The families of the fonts in the font system, sorted by name.
+#[derive(Default)]
+struct Families {
+    names: Arc<Vec<String>>,
+    /// The names in lowercase, for the case-insensitive lookups of the font system.
+    lowercase: HashSet<String>,
+}
+
+/// The families, collected when they're first needed after fonts were loaded.
+static FAMILIES: LazyLock<RwLock<Option<Families>>> = LazyLock::new(|| RwLock::new(None));
+
+static FALLBACK_CHAINS: LazyLock<RwLock<HashMap<String, Vec<FamilyOwned>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+fn with_families<R>(f: impl FnOnce(&Families) -> R) -> R {
+    if let Some(families) = FAMILIES.read().as_ref() {
+        return f(families);
+    }
+    let mut names = FONT_SYSTEM
+        .lock()
+        .db()
+        .faces()
+        .flat_map(|face| face.families.iter().map(|(name, _)| name.clone()))
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+    let families = Families {
+        lowercase: names.iter().map(|name| name.to_lowercase()).collect(),
+        names: Arc::new(names),
+    };
+    let result = f(&families);
+    *FAMILIES.write() = Some(families);
+    result
+}
+
+/// The names of the font families in the font system, sorted by name.
+pub fn font_families() -> Arc<Vec<String>> {
+    with_families(|families| families.names.clone())
+}
+
+/// Updates the families after fonts were added to the font system.
+pub fn font_families_changed() {
+    *FAMILIES.write() = None;
+}
+
+/// Whether `family` is a generic family or a family in the font system.
+pub fn is_font_family_installed(family: &FamilyOwned) -> bool {
+    match family {
+        FamilyOwned::Name(name) => {
+            with_families(|families| families.lowercase.contains(&name.to_lowercase()))
+        }
+        _ => true,
+    }
+}
+
+/// Names a list of families, which is used in place of the name in a family list, so the first
+/// of the families that is installed is used.
+pub fn set_fallback_chain(name: impl Into<String>, families: Vec<FamilyOwned>) {
+    FALLBACK_CHAINS.write().insert(name.into(), families);
+}
+
+/// The families of the fallback chain with `name`, if there is one.
+pub fn fallback_chain(name: &str) -> Option<Vec<FamilyOwned>> {
+    FALLBACK_CHAINS.read().get(name).cloned()
+}
@@ -1,9 +1,12 @@
 use std::{ops::Range, sync::LazyLock};
 
-use crate::text::AttrsList;
+use crate::text::{
+    emoji::{emoji_ranges, EMOJI_FAMILY},
+    AttrsList,
+};
 use cosmic_text::{
-    Affinity, Buffer, BufferLine, Cursor, FontSystem, LayoutCursor, LayoutGlyph, LineEnding,
-    LineIter, Metrics, Scroll, Shaping, Wrap,
+    Affinity, AttrsOwned, Buffer, BufferLine, Cursor, Family, FontSystem, LayoutCursor,
+    LayoutGlyph, LineEnding, LineIter, Metrics, Scroll, Shaping, Wrap,
 };
 use parking_lot::Mutex;
 use peniko::kurbo::{Point, Size};
@@ -228,9 +231,28 @@ impl TextLayout {
     }
 
     pub fn set_text(&mut self, text: &str, attrs_list: AttrsList) {
+        self.set_lines(text, attrs_list);
+        let mut font_system = FONT_SYSTEM.lock();
+        self.shape(&mut font_system);
+    }
+
+    /// Splits `text` into the lines of the buffer, without shaping them.
+    pub(crate) fn set_lines(&mut self, text: &str, attrs_list: AttrsList) {
         self.buffer.lines.clear();
         self.lines_range.clear();
         let mut attrs_list = attrs_list.0;
+        // emoji are shaped with a color emoji font, rather than the first font of the fallback
+        // that has a glyph for them
+        if let Some(family) = EMOJI_FAMILY.as_deref() {
+            for range in emoji_ranges(text) {
+                let attrs = AttrsOwned::new(
+                    attrs_list
+                        .get_span(range.start)
+                        .family(Family::Name(family)),
+                );
+                attrs_list.add_span(range, attrs.as_attrs());
+            }
+        }
         for (range, ending) in LineIter::new(text) {
             self.lines_range.push(range.clone());
             let line_text = &text[range];
@@ -254,9 +276,12 @@ impl TextLayout {
             ));
             self.lines_range.push(0..0)
         }
+    }
+
+    /// Shapes the lines of the buffer with `font_system`.
+    pub(crate) fn shape(&mut self, font_system: &mut FontSystem) {
         self.buffer.set_scroll(Scroll::default());
-        let mut font_system = FONT_SYSTEM.lock();
-        self.buffer.shape_until_scroll(&mut font_system, false);
+        self.buffer.shape_until_scroll(font_system, false);
     }
 
     pub fn set_wrap(&mut self, wrap: Wrap) {
@@ -1,9 +1,20 @@
 mod attrs;
+mod emoji;
+mod fonts;
 mod layout;
+#[cfg(feature = "parallel")]
+mod parallel;
 
 pub use attrs::{Attrs, AttrsList, AttrsOwned, FamilyOwned, LineHeightValue};
 pub use cosmic_text::{
     fontdb, CacheKey, Cursor, Family, LayoutGlyph, LayoutLine, Stretch, Style, SubpixelBin,
     SwashCache, SwashContent, Weight, Wrap,
 };
+pub use emoji::EMOJI_FAMILY;
+pub use fonts::{
+    fallback_chain, font_families, font_families_changed, is_font_family_installed,
+    set_fallback_chain,
+};
 pub use layout::{HitPoint, HitPosition, LayoutRun, TextLayout, FONT_SYSTEM};
+#[cfg(feature = "parallel")]
+pub use parallel::shape_all;
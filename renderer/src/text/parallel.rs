@@ -0,0 +1,84 @@
+//! Shapes many texts at once on the rayon thread pool, for windows with many labels, like the
+//! cells of a data grid.
+//!
+//! Shaping with [`FONT_SYSTEM`] would serialize the workers on its lock, so each worker shapes
+//! with its own [`FontSystem`], built from a snapshot of the font database. The snapshot keeps
+//! the ids of the fonts, so the glyphs can be rasterized with [`FONT_SYSTEM`] like any other.
+
+use std::{
+    cell::RefCell,
+    sync::{Arc, LazyLock},
+};
+
+use cosmic_text::{fontdb, FontSystem};
+use parking_lot::Mutex;
+use rayon::prelude::*;
+
+use super::{AttrsList, TextLayout, FONT_SYSTEM};
+
+/// Below this many texts, shaping them on the calling thread is faster than sending them to the
+/// workers.
+const MIN_PARALLEL: usize = 16;
+
+/// The font database of [`FONT_SYSTEM`] that the workers shape with.
+struct Snapshot {
+    locale: String,
+    db: fontdb::Database,
+}
+
+/// The last snapshot, which is taken again when fonts are loaded into [`FONT_SYSTEM`].
+static SNAPSHOT: LazyLock<Mutex<Option<Arc<Snapshot>>>> = LazyLock::new(|| Mutex::new(None));
+
+thread_local! {
+    /// The font system of a worker, and the snapshot it was built from.
+    static WORKER_FONT_SYSTEM: RefCell<Option<(Arc<Snapshot>, FontSystem)>> =
+        const { RefCell::new(None) };
+}
+
+fn snapshot() -> Arc<Snapshot> {
+    let font_system = FONT_SYSTEM.lock();
+    let mut snapshot = SNAPSHOT.lock();
+    match snapshot.as_ref() {
+        // fonts are only ever added to the database, so the same number of faces means it
+        // hasn't changed
+        Some(snapshot) if snapshot.db.len() == font_system.db().len() => snapshot.clone(),
+        _ => snapshot
+            .insert(Arc::new(Snapshot {
+                locale: font_system.locale().to_owned(),
+                db: font_system.db().clone(),
+            }))
+            .clone(),
+    }
+}
+
+fn shape_on_worker(snapshot: &Arc<Snapshot>, text: &str, attrs: &AttrsList) -> TextLayout {
+    let mut layout = TextLayout::new();
+    layout.set_lines(text, attrs.clone());
+    WORKER_FONT_SYSTEM.with_borrow_mut(|worker| {
+        if !matches!(worker, Some((built_from, _)) if Arc::ptr_eq(built_from, snapshot)) {
+            let font_system =
+                FontSystem::new_with_locale_and_db(snapshot.locale.clone(), snapshot.db.clone());
+            *worker = Some((snapshot.clone(), font_system));
+        }
+        let (_, font_system) = worker.as_mut().unwrap();
+        layout.shape(font_system);
+    });
+    layout
+}
+
+/// Shapes each text with its attributes, in parallel when there are enough of them.
+///
+/// The layouts are the same as the ones [`TextLayout::set_text`] makes, in the order of `texts`.
+pub fn shape_all(texts: &[(String, AttrsList)]) -> Vec<TextLayout> {
+    if texts.len() < MIN_PARALLEL {
+        return texts
+            .iter()
+            .map(|(text, attrs)| TextLayout::new_with_text(text, attrs.clone()))
+            .collect();
+    }
+    let snapshot = snapshot();
+    texts
+        .par_iter()
+        .map(|(text, attrs)| shape_on_worker(&snapshot, text, attrs))
+        .collect()
+}
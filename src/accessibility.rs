@@ -0,0 +1,287 @@
+#![deny(missing_docs)]
+//! The accessibility preferences of the user, read from the settings of the operating system.
+//!
+//! The preferences are signals, so views that depend on them update when they change. They're
+//! read again when the OS color scheme changes, as the platforms change these settings
+//! together, and [`refresh_preferences`] reads them on demand. On macOS and Linux they're read
+//! by running `defaults` and `gsettings`, which happens on a separate thread, so the
+//! preferences are the defaults until the first read finishes.
+//!
+//! While [`prefers_reduced_motion`] is set, [`Animation`](crate::animate::Animation)s jump to
+//! their end and style transitions are skipped, unless an animation opts out with
+//! [`Animation::ignore_reduced_motion`](crate::animate::Animation::ignore_reduced_motion).
+
+use std::cell::Cell;
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+use crate::app_scope;
+
+/// The accessibility preferences of the user.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Preferences {
+    /// Whether animations should be reduced to a minimum.
+    pub reduced_motion: bool,
+    /// Whether colors with a higher contrast should be used.
+    pub high_contrast: bool,
+    /// The factor that text should be scaled by, `1.0` for the default size.
+    pub font_scale: f64,
+    /// Whether the colors of the application should be replaced by the colors chosen by the
+    /// user, see [`forced_colors`](crate::forced_colors).
+    pub forced_colors: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            reduced_motion: false,
+            high_contrast: false,
+            font_scale: 1.0,
+            forced_colors: false,
+        }
+    }
+}
+
+thread_local! {
+    static PREFERENCES: RwSignal<Preferences> = {
+        let preferences = app_scope().create_rw_signal(Preferences::default());
+        read_system_preferences(preferences);
+        preferences
+    };
+    /// How many times the preferences were set with [`set_preferences`], so that a read of the
+    /// system preferences that finishes after that doesn't replace them.
+    static OVERRIDES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Reads the preferences of the system into `signal`. The commands that read them on macOS and
+/// Linux are run on a separate thread.
+fn read_system_preferences(signal: RwSignal<Preferences>) {
+    let overrides = OVERRIDES.with(Cell::get);
+    let apply = move |preferences: Option<Preferences>| {
+        if OVERRIDES.with(Cell::get) != overrides {
+            return;
+        }
+        if let Some(preferences) = preferences {
+            if signal.get_untracked() != preferences {
+                signal.set(preferences);
+            }
+        }
+    };
+
+    #[cfg(all(unix, not(target_arch = "wasm32")))]
+    {
+        let send = crate::ext_event::create_ext_action(floem_reactive::Scope::new(), apply);
+        std::thread::spawn(move || send(system::preferences()));
+    }
+    #[cfg(not(all(unix, not(target_arch = "wasm32"))))]
+    apply(system::preferences());
+}
+
+/// The accessibility preferences of the user, which is tracked when called in a reactive
+/// context.
+pub fn preferences() -> Preferences {
+    PREFERENCES.with(|preferences| preferences.get())
+}
+
+/// Whether the user prefers reduced motion, which is tracked when called in a reactive context.
+pub fn prefers_reduced_motion() -> bool {
+    preferences().reduced_motion
+}
+
+/// Whether the user prefers a high contrast, which is tracked when called in a reactive
+/// context.
+pub fn prefers_high_contrast() -> bool {
+    preferences().high_contrast
+}
+
+/// Whether the user prefers the colors chosen in the settings of the system, e.g. with a high
+/// contrast theme on Windows, which is tracked when called in a reactive context.
+pub fn prefers_forced_colors() -> bool {
+    preferences().forced_colors
+}
+
+/// The factor that the user prefers text to be scaled by, which is tracked when called in a
+/// reactive context.
+///
+/// ```
+/// # use floem::{accessibility::font_scale, views::{label, Decorators}};
+/// label(|| "Scaled").style(|s| s.font_size(14.0 * font_scale()));
+/// ```
+pub fn font_scale() -> f64 {
+    preferences().font_scale
+}
+
+/// Overrides the preferences read from the system, e.g. with the settings of the application.
+/// They're replaced again by the system preferences when those change.
+pub fn set_preferences(preferences: Preferences) {
+    PREFERENCES.with(|signal| {
+        OVERRIDES.with(|overrides| overrides.set(overrides.get() + 1));
+        if signal.get_untracked() != preferences {
+            signal.set(preferences);
+        }
+    });
+}
+
+/// Reads the preferences from the system again. On macOS and Linux they're updated when the
+/// read on a separate thread finishes.
+pub fn refresh_preferences() {
+    PREFERENCES.with(|signal| read_system_preferences(*signal));
+}
+
+/// Whether reduced motion is preferred, without tracking it.
+pub(crate) fn reduced_motion_untracked() -> bool {
+    preferences_untracked().reduced_motion
+}
+
+pub(crate) fn preferences_untracked() -> Preferences {
+    PREFERENCES.with(|preferences| preferences.get_untracked())
+}
+
+#[cfg(target_os = "windows")]
+mod system {
+    use windows_sys::Win32::{
+        Foundation::BOOL,
+        System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD},
+        UI::{
+            Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW},
+            WindowsAndMessaging::{
+                SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST,
+            },
+        },
+    };
+
+    use super::Preferences;
+
+    fn wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(Some(0)).collect()
+    }
+
+    pub(super) fn preferences() -> Option<Preferences> {
+        let mut animations: BOOL = 1;
+        let mut contrast = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            dwFlags: 0,
+            lpszDefaultScheme: std::ptr::null_mut(),
+        };
+        // the text size of the accessibility settings, in percent
+        let mut text_scale: u32 = 100;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                &mut animations as *mut BOOL as *mut _,
+                0,
+            );
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                contrast.cbSize,
+                &mut contrast as *mut HIGHCONTRASTW as *mut _,
+                0,
+            );
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                wide("Software\\Microsoft\\Accessibility").as_ptr(),
+                wide("TextScaleFactor").as_ptr(),
+                RRF_RT_REG_DWORD,
+                std::ptr::null_mut(),
+                &mut text_scale as *mut u32 as *mut _,
+                &mut size,
+            );
+        }
+        let high_contrast = contrast.dwFlags & HCF_HIGHCONTRASTON != 0;
+        Some(Preferences {
+            reduced_motion: animations == 0,
+            high_contrast,
+            font_scale: text_scale as f64 / 100.0,
+            // the high contrast themes replace the colors of applications
+            forced_colors: high_contrast,
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod system {
+    use std::process::Command;
+
+    use super::Preferences;
+
+    fn read(key: &str) -> Option<String> {
+        let output = Command::new("defaults")
+            .args(["read", "com.apple.universalaccess", key])
+            .output()
+            .ok()?;
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub(super) fn preferences() -> Option<Preferences> {
+        Some(Preferences {
+            reduced_motion: read("reduceMotion")? == "1",
+            high_contrast: read("increaseContrast").is_some_and(|value| value == "1"),
+            font_scale: 1.0,
+            forced_colors: false,
+        })
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos"), not(target_arch = "wasm32")))]
+mod system {
+    use std::process::Command;
+
+    use super::Preferences;
+
+    fn read(schema: &str, key: &str) -> Option<String> {
+        let output = Command::new("gsettings")
+            .args(["get", schema, key])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub(super) fn preferences() -> Option<Preferences> {
+        let interface = "org.gnome.desktop.interface";
+        Some(Preferences {
+            reduced_motion: read(interface, "enable-animations")? == "false",
+            high_contrast: read("org.gnome.desktop.a11y.interface", "high-contrast")
+                .is_some_and(|value| value == "true"),
+            font_scale: read(interface, "text-scaling-factor")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(1.0),
+            forced_colors: false,
+        })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod system {
+    use super::Preferences;
+
+    pub(super) fn preferences() -> Option<Preferences> {
+        let window = web_sys::window()?;
+        let matches = |query: &str| {
+            window
+                .match_media(query)
+                .ok()
+                .flatten()
+                .is_some_and(|list| list.matches())
+        };
+        Some(Preferences {
+            reduced_motion: matches("(prefers-reduced-motion: reduce)"),
+            high_contrast: matches("(prefers-contrast: more)"),
+            font_scale: 1.0,
+            forced_colors: matches("(forced-colors: active)"),
+        })
+    }
+}
+
+#[cfg(not(any(unix, target_os = "windows", target_arch = "wasm32")))]
+mod system {
+    use super::Preferences;
+
+    pub(super) fn preferences() -> Option<Preferences> {
+        None
+    }
+}
@@ -4,13 +4,14 @@
 //!
 //! This module includes a variety of functions that can interact with the window from which the function is being called.
 //!
-//! This includes, moving the window, resizing the window, adding context menus and overlays, and running a callback after a specified duration.
+//! This includes, moving the window, resizing the window, adding context menus and overlays, running a callback after a specified duration, and running a callback once the event loop is idle.
 
 use std::sync::atomic::AtomicU64;
 
 use floem_reactive::SignalWith;
 use floem_winit::window::ResizeDirection;
-use peniko::kurbo::{Point, Size, Vec2};
+use image::RgbaImage;
+use peniko::kurbo::{Point, Rect, Size, Vec2};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, Instant};
@@ -19,15 +20,19 @@ use web_time::{Duration, Instant};
 
 use crate::{
     app::{add_app_update_event, AppUpdateEvent},
+    clock,
     id::ViewId,
     menu::Menu,
-    update::{UpdateMessage, UPDATE_MESSAGES},
+    notification::{NotificationAction, NotificationHandle},
+    sound::SystemSound,
+    style::CursorStyle,
+    update::{CaptureRegion, UpdateMessage, UPDATE_MESSAGES},
     view::View,
     window_handle::{get_current_view, set_current_view},
 };
 
-#[cfg(any(feature = "rfd-async-std", feature = "rfd-tokio"))]
 pub use crate::file_action::*;
+pub use crate::idle::{schedule_idle, IdleDeadline, IdleToken};
 
 pub(crate) fn add_update_message(msg: UpdateMessage) {
     let current_view = get_current_view();
@@ -111,7 +116,11 @@ impl TimerToken {
 
     /// Cancel a timer
     pub fn cancel(self) {
-        add_app_update_event(AppUpdateEvent::CancelTimer { timer: self });
+        if clock::is_virtual() {
+            clock::cancel_timer(self);
+        } else {
+            add_app_update_event(AppUpdateEvent::CancelTimer { timer: self });
+        }
     }
 }
 
@@ -126,14 +135,16 @@ pub fn exec_after(duration: Duration, action: impl FnOnce(TimerToken) + 'static)
     };
 
     let token = TimerToken::next();
-    let deadline = Instant::now() + duration;
-    add_app_update_event(AppUpdateEvent::RequestTimer {
-        timer: Timer {
-            token,
-            action: Box::new(action),
-            deadline,
-        },
-    });
+    let timer = Timer {
+        token,
+        action: Box::new(action),
+        deadline: clock::now() + duration,
+    };
+    if clock::is_virtual() {
+        clock::add_timer(timer);
+    } else {
+        add_app_update_event(AppUpdateEvent::RequestTimer { timer });
+    }
     token
 }
 
@@ -233,7 +244,129 @@ pub fn add_overlay<V: View + 'static>(
     id
 }
 
+/// Moves an overlay created with [`add_overlay`] to a new position on the current window.
+pub fn move_overlay(id: ViewId, position: Point) {
+    id.update_state(position);
+}
+
 /// Removes an overlay from the current window.
 pub fn remove_overlay(id: ViewId) {
     add_update_message(UpdateMessage::RemoveOverlay { id });
 }
+
+/// Captures the pixels of the view `id` and its descendants as they're painted in the current
+/// window, including the views painted over them, and calls `on_capture` with the image.
+///
+/// The image is in physical pixels, so it's larger than the view on a display that is scaled. The
+/// capture happens once pending updates are processed, and the image is `None` when the view isn't
+/// shown or the renderer can't capture the window.
+///
+/// ```no_run
+/// # use floem::action::capture_view;
+/// # use floem::views::label;
+/// # use floem::View;
+/// # let view = label(|| "Report");
+/// let id = view.id();
+/// capture_view(id, |image| {
+///     if let Some(image) = image {
+///         let _ = image.save("report.png");
+///     }
+/// });
+/// ```
+pub fn capture_view(id: ViewId, on_capture: impl FnOnce(Option<RgbaImage>) + 'static) {
+    add_update_message(UpdateMessage::Capture {
+        region: CaptureRegion::View(id),
+        on_capture: Box::new(on_capture),
+    });
+}
+
+/// Captures the pixels of `rect`, in the coordinates of the current window, like
+/// [`capture_view`] does for a view. It can be used with the rect picked with a
+/// [`region_picker`](crate::views::region_picker).
+pub fn capture_region(rect: Rect, on_capture: impl FnOnce(Option<RgbaImage>) + 'static) {
+    add_update_message(UpdateMessage::Capture {
+        region: CaptureRegion::Rect(rect),
+        on_capture: Box::new(on_capture),
+    });
+}
+
+/// Shows `cursor` on the current window, wherever the pointer is, until the returned
+/// [`CursorOverride`] is dropped, like a busy cursor during a long operation.
+///
+/// When several overrides are active, the one created last is shown, and dropping it shows the
+/// one before it again.
+///
+/// ```no_run
+/// # use floem::action::override_cursor;
+/// # use floem::style::CursorStyle;
+/// let busy = override_cursor(CursorStyle::Wait);
+/// // ... the long operation ...
+/// drop(busy);
+/// ```
+pub fn override_cursor(cursor: CursorStyle) -> CursorOverride {
+    static OVERRIDE_COUNTER: AtomicU64 = AtomicU64::new(0);
+    let token = OVERRIDE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    add_update_message(UpdateMessage::OverrideCursor { token, cursor });
+    CursorOverride {
+        window: get_current_view(),
+        token,
+    }
+}
+
+/// A cursor override created with [`override_cursor`], which restores the previous cursor when
+/// it's dropped.
+#[must_use = "the previous cursor is restored as soon as the override is dropped"]
+pub struct CursorOverride {
+    window: ViewId,
+    token: u64,
+}
+
+impl Drop for CursorOverride {
+    fn drop(&mut self) {
+        let msg = UpdateMessage::RestoreCursor { token: self.token };
+        // the override may be dropped while another window is handled
+        UPDATE_MESSAGES.with_borrow_mut(|msgs| {
+            msgs.entry(self.window).or_default().push(msg);
+        });
+    }
+}
+
+/// Plays a sound of the operating system, like the sound of an error when an action isn't
+/// possible. The sound can be replaced, or muted, with
+/// [`set_system_sound_handler`](crate::sound::set_system_sound_handler).
+///
+/// ```no_run
+/// # use floem::action::play_system_sound;
+/// # use floem::sound::SystemSound;
+/// play_system_sound(SystemSound::Error);
+/// ```
+pub fn play_system_sound(sound: SystemSound) {
+    crate::sound::play_system_sound(sound);
+}
+
+/// Sends a desktop notification with `title` and `body`, and buttons for the `actions`.
+///
+/// The `icon` is the name of an icon of the icon theme on Linux, or the path to an image. The
+/// notification is shown by the notification center of the system with the `notifications`
+/// feature, and as a toast in the current window otherwise, see the
+/// [`notification`](crate::notification) module. The callbacks are called on the main thread.
+///
+/// ```no_run
+/// # use floem::action::{focus_window, send_notification};
+/// # use floem::notification::NotificationAction;
+/// send_notification(
+///     "Download finished",
+///     "report.pdf was saved to Downloads",
+///     None,
+///     vec![NotificationAction::new("Open", || println!("open report.pdf"))],
+/// )
+/// .on_click(focus_window);
+/// ```
+pub fn send_notification(
+    title: impl Into<String>,
+    body: impl Into<String>,
+    icon: Option<&str>,
+    actions: Vec<NotificationAction>,
+) -> NotificationHandle {
+    crate::notification::send(title.into(), body.into(), icon.map(str::to_string), actions)
+}
@@ -3,17 +3,23 @@
 //! Animations
 
 use crate::{
+    accessibility, clock,
     easing::*,
-    style::{Style, StylePropRef},
+    style::{Style, StylePropRef, Transition},
     unit::UnitExt,
     view_state::StackOffset,
     ViewId,
 };
 
 use std::any::Any;
+use std::cell::Cell;
 use std::rc::Rc;
 
-use floem_reactive::{create_updater, RwSignal, SignalGet, Trigger};
+use floem_reactive::{
+    create_effect, create_updater, untrack, with_scope, RwSignal, Scope, SignalGet, SignalUpdate,
+    Trigger,
+};
+use peniko::kurbo::{Affine, Rect, Vec2};
 use smallvec::{smallvec, SmallVec};
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, Instant};
@@ -345,7 +351,361 @@ pub enum AnimStateCommand {
     Stop,
 }
 
-type EffectStateVec = SmallVec<[RwSignal<SmallVec<[(ViewId, StackOffset<Animation>); 1]>>; 1]>;
+/// A property that is being driven by a [SpringDriver].
+#[derive(Debug, Clone)]
+struct SpringProp {
+    from: Rc<dyn Any>,
+    to: Rc<dyn Any>,
+    /// The progress from `from` to `to`. This can go past `1.` when the spring overshoots.
+    position: f64,
+    /// The velocity of `position` per second.
+    velocity: f64,
+}
+
+impl SpringProp {
+    fn at_rest(value: Rc<dyn Any>) -> Self {
+        Self {
+            from: value.clone(),
+            to: value,
+            position: 1.,
+            velocity: 0.,
+        }
+    }
+
+    fn is_at_rest(&self) -> bool {
+        (1. - self.position).abs() < Spring::THRESHOLD && self.velocity.abs() < Spring::THRESHOLD
+    }
+}
+
+/// Drives the props of a view towards their values in the computed style using a spring.
+///
+/// Instead of animating between keyframes, every time a prop in the computed style changes, the spring is re-targeted at the new value.
+/// If the prop was still moving, the spring starts from the current value and keeps the current velocity.
+#[derive(Debug, Clone)]
+pub(crate) struct SpringDriver {
+    stiffness: f64,
+    damping: f64,
+    props: im_rc::HashMap<StylePropRef, SpringProp>,
+    last_step: Option<Instant>,
+}
+
+impl SpringDriver {
+    /// The springs are simulated in steps of at most this many seconds.
+    const STEP: f64 = 1. / 240.;
+    /// Limit how much time is simulated at once so that a long frame doesn't make the spring jump.
+    const MAX_ELAPSED: f64 = 0.1;
+
+    fn new(stiffness: f64, damping: f64) -> Self {
+        Self {
+            stiffness,
+            damping,
+            props: im_rc::HashMap::new(),
+            last_step: None,
+        }
+    }
+
+    fn retarget(prop: StylePropRef, spring: &mut SpringProp, target: Rc<dyn Any>) {
+        let eq = prop.info().eq;
+        if spring.is_at_rest() {
+            *spring = SpringProp {
+                from: spring.to.clone(),
+                to: target,
+                position: 0.,
+                velocity: 0.,
+            };
+        } else if eq(&*target, &*spring.from) {
+            // going back to where the spring came from, so the current motion can be mirrored.
+            std::mem::swap(&mut spring.from, &mut spring.to);
+            spring.position = 1. - spring.position;
+            spring.velocity = -spring.velocity;
+        } else {
+            let info = prop.info();
+            let current = (info.interpolate)(&*spring.from, &*spring.to, spring.position)
+                .unwrap_or_else(|| spring.to.clone());
+            // `velocity` is relative to the distance between `from` and `to`, so it's scaled by the
+            // ratio of the old and new distances to keep the same speed in the prop's own units.
+            // If either distance can't be measured, as for colors, or the new one is zero, the
+            // velocity is kept relative to the distance instead.
+            let velocity = match (
+                (info.difference)(&*spring.from, &*spring.to),
+                (info.difference)(&*current, &*target),
+            ) {
+                (Some(old), Some(new)) if new != 0. => spring.velocity * old / new,
+                _ => spring.velocity,
+            };
+            *spring = SpringProp {
+                from: current,
+                to: target,
+                position: 0.,
+                velocity,
+            };
+        }
+    }
+
+    pub(crate) fn is_moving(&self) -> bool {
+        self.last_step.is_some()
+    }
+
+    /// Advance all of the springs and apply their current values to the computed style.
+    ///
+    /// Returns `true` if any spring is still moving and another frame is needed.
+    pub(crate) fn animate_into(&mut self, computed_style: &mut Style) -> bool {
+        let now = clock::now();
+        let elapsed = self
+            .last_step
+            .map(|last| (now - last).as_secs_f64())
+            .unwrap_or(0.)
+            .min(Self::MAX_ELAPSED);
+
+        let props: Vec<_> = computed_style.style_props().collect();
+        self.props.retain(|prop, _| props.contains(prop));
+
+        let mut moving = false;
+        for prop in props {
+            let Some(target) = computed_style.map.get(&prop.key).cloned() else {
+                continue;
+            };
+            let spring = self
+                .props
+                .entry(prop)
+                .or_insert_with(|| SpringProp::at_rest(target.clone()));
+            if !(prop.info().eq)(&*target, &*spring.to) {
+                Self::retarget(prop, spring, target);
+            }
+            if spring.is_at_rest() {
+                continue;
+            }
+
+            let mut remaining = elapsed;
+            while remaining > 0. {
+                let step = remaining.min(Self::STEP);
+                let accel =
+                    -self.stiffness * (spring.position - 1.) - self.damping * spring.velocity;
+                spring.velocity += accel * step;
+                spring.position += spring.velocity * step;
+                remaining -= step;
+            }
+
+            if spring.is_at_rest() {
+                spring.position = 1.;
+                spring.velocity = 0.;
+                continue;
+            }
+            match (prop.info().interpolate)(&*spring.from, &*spring.to, spring.position) {
+                Some(value) => {
+                    computed_style.map.insert(prop.key, value);
+                    moving = true;
+                }
+                None => {
+                    // the prop can't be interpolated so it jumps to the target
+                    spring.position = 1.;
+                    spring.velocity = 0.;
+                }
+            }
+        }
+
+        self.last_step = moving.then_some(now);
+        moving
+    }
+}
+
+/// Animates a view from its previous layout rect to its new one using the FLIP technique.
+///
+/// When the layout of the view changes, the view is immediately moved to its new rect and then a transform
+/// is applied that makes it appear at its old rect. The transform is then animated away.
+///
+/// See [Decorators::animate_layout](crate::views::Decorators::animate_layout).
+#[derive(Debug, Clone)]
+pub(crate) struct LayoutAnimation {
+    transition: Transition,
+    /// The rect of the view relative to its parent from the last layout
+    rect: Option<Rect>,
+    /// The start time, and the translation and scale needed to make the view appear at its old rect
+    active: Option<(Instant, Vec2, Vec2)>,
+}
+
+impl LayoutAnimation {
+    pub(crate) fn new(transition: Transition) -> Self {
+        Self {
+            transition,
+            rect: None,
+            active: None,
+        }
+    }
+
+    /// The translation and scale that are still left to be animated away at `now`.
+    fn remaining(&self, now: Instant) -> Option<(Vec2, Vec2)> {
+        let (start, translate, scale) = self.active?;
+        let duration = self.transition.duration.as_secs_f64();
+        let time = now.saturating_duration_since(start).as_secs_f64();
+        let time_percent = if duration == 0. { 1. } else { time / duration };
+        if time_percent >= 1. && self.transition.easing.finished(time_percent) {
+            return None;
+        }
+        let left = 1. - self.transition.easing.eval(time_percent);
+        Some((
+            translate * left,
+            Vec2::new(1. + (scale.x - 1.) * left, 1. + (scale.y - 1.) * left),
+        ))
+    }
+
+    /// Records the new layout rect of the view. Returns `true` if the rect changed and an animation was started.
+    pub(crate) fn layout_changed(&mut self, rect: Rect, now: Instant) -> bool {
+        let Some(old) = self.rect.replace(rect) else {
+            return false;
+        };
+        if old == rect {
+            return false;
+        }
+        if accessibility::reduced_motion_untracked() {
+            self.active = None;
+            return false;
+        }
+        // start from where the view currently appears if it was already animating
+        let (translate, scale) = self
+            .remaining(now)
+            .unwrap_or((Vec2::ZERO, Vec2::new(1., 1.)));
+        let ratio = |old: f64, new: f64| if new == 0. { 1. } else { old / new };
+        self.active = Some((
+            now,
+            old.origin() - rect.origin() + translate,
+            Vec2::new(
+                ratio(old.width() * scale.x, rect.width()),
+                ratio(old.height() * scale.y, rect.height()),
+            ),
+        ));
+        true
+    }
+
+    /// The transform that should be applied when painting the view at `now`, or `None` once the animation is finished.
+    pub(crate) fn transform(&mut self, now: Instant) -> Option<Affine> {
+        let Some((translate, scale)) = self.remaining(now) else {
+            self.active = None;
+            return None;
+        };
+        Some(Affine::translate(translate) * Affine::scale_non_uniform(scale.x, scale.y))
+    }
+}
+
+type EffectStateVec = SmallVec<[RwSignal<AnimatedViews>; 1]>;
+/// The views an animation was added to, with the offset of the animation in their stack.
+type AnimatedViews = SmallVec<[(ViewId, StackOffset<Animation>); 1]>;
+
+/// A handle that can be used to control an animation after it has been added to a view.
+///
+/// Create a handle with [AnimationHandle::new] and bind it to an animation with [Animation::handle].
+/// The same handle can be bound to animations on multiple views; every command is sent to all of them.
+///
+/// ```rust
+/// # use floem::animate::{Animation, AnimationHandle};
+/// # use floem::views::{empty, Decorators};
+/// # use floem::unit::UnitExt;
+/// let handle = AnimationHandle::new();
+/// handle.on_complete(|| println!("done"));
+/// let view = empty().animation(move |a| {
+///     a.handle(handle)
+///         .keyframe(100, |f| f.style(|s| s.width(100.pct())))
+/// });
+/// // later
+/// handle.pause();
+/// handle.seek(0.5);
+/// handle.set_speed(2.);
+/// handle.reverse();
+/// handle.resume();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationHandle {
+    views: RwSignal<AnimatedViews>,
+    completed: Trigger,
+    iteration: Trigger,
+}
+impl Default for AnimationHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl AnimationHandle {
+    /// Create a new handle that is not yet bound to any animation.
+    pub fn new() -> Self {
+        Self {
+            views: RwSignal::new(SmallVec::new()),
+            completed: Trigger::new(),
+            iteration: Trigger::new(),
+        }
+    }
+
+    fn update(&self, update: impl Fn(&mut Animation) + Copy + 'static) {
+        for (view_id, stack_offset) in self.views.get_untracked() {
+            view_id.update_animation_with(stack_offset, update);
+        }
+    }
+
+    /// Pause the animation. See [AnimStateCommand::Pause].
+    pub fn pause(&self) {
+        self.update(|anim| anim.transition(AnimStateCommand::Pause));
+    }
+
+    /// Resume a paused animation. See [AnimStateCommand::Resume].
+    pub fn resume(&self) {
+        self.update(|anim| anim.transition(AnimStateCommand::Resume));
+    }
+
+    /// Start the animation from the beginning. See [AnimStateCommand::Start].
+    pub fn start(&self) {
+        self.update(|anim| anim.transition(AnimStateCommand::Start));
+    }
+
+    /// Stop the animation. See [AnimStateCommand::Stop].
+    pub fn stop(&self) {
+        self.update(|anim| anim.transition(AnimStateCommand::Stop));
+    }
+
+    /// Jump to a position in the current pass of the animation, where `percent` is in the range `0. - 1.`.
+    ///
+    /// A running animation keeps running from the new position.
+    /// An animation that is not running is paused at the new position.
+    pub fn seek(&self, percent: f64) {
+        self.update(move |anim| anim.seek_mut(percent));
+    }
+
+    /// Set the playback speed of the animation. `1.` is the normal speed, `2.` is twice as fast.
+    pub fn set_speed(&self, speed: f64) {
+        self.update(move |anim| anim.set_speed_mut(speed));
+    }
+
+    /// Reverse the direction the animation is playing in, starting from its current position.
+    ///
+    /// If the animation has already completed, it is started again in the new direction.
+    pub fn reverse(&self) {
+        self.update(|anim| anim.reverse_mut());
+    }
+
+    /// Run `on_complete` every time the animation completes all of its passes.
+    pub fn on_complete(&self, on_complete: impl Fn() + 'static) {
+        let trigger = self.completed;
+        create_effect(move |prev: Option<()>| {
+            trigger.track();
+            if prev.is_some() {
+                on_complete();
+            }
+        });
+    }
+
+    /// Run `on_iteration` every time the animation finishes a pass and starts the next one.
+    pub fn on_iteration(&self, on_iteration: impl Fn() + 'static) {
+        let trigger = self.iteration;
+        create_effect(move |prev: Option<()>| {
+            trigger.track();
+            if prev.is_some() {
+                on_iteration();
+            }
+        });
+    }
+}
+
+/// How much faster animations play while the user prefers reduced motion, which makes them end
+/// in about a frame.
+const REDUCED_MOTION_SPEEDUP: f64 = 1000.;
 
 /// The main animation struct
 ///
@@ -360,6 +720,12 @@ pub struct Animation {
     pub(crate) repeat_mode: RepeatMode,
     /// How many times the animation has been repeated so far
     pub(crate) repeat_count: usize,
+    /// The playback speed that the elapsed time is scaled by
+    pub(crate) speed: f64,
+    /// Set by [AnimationHandle::reverse]. When true the passes play from the last keyframe to the first
+    pub(crate) reversed: bool,
+    /// Set when the position of an animation that isn't advancing has changed and needs to be applied once
+    pub(crate) seeked: bool,
     /// run on remove and run on create should be checked for and respected by any view that dynamically creates sub views
     pub(crate) run_on_remove: bool,
     pub(crate) run_on_create: bool,
@@ -379,7 +745,12 @@ pub struct Animation {
     pub(crate) on_visual_complete: Trigger,
     /// This trigger will fire at the total compltetion of an animation when the easing function of all props report 'finished`.
     pub(crate) on_complete: Trigger,
+    pub(crate) handle: Option<AnimationHandle>,
+    /// When set, the animation drives the props of the computed style with a spring instead of using keyframes.
+    pub(crate) spring: Option<SpringDriver>,
     pub(crate) debug_description: Option<String>,
+    /// Whether the animation jumps to its end while the user prefers reduced motion.
+    pub(crate) respect_reduced_motion: bool,
 }
 impl Default for Animation {
     fn default() -> Self {
@@ -391,6 +762,9 @@ impl Default for Animation {
             duration: Duration::from_millis(200),
             repeat_mode: RepeatMode::Times(1),
             repeat_count: 0,
+            speed: 1.,
+            reversed: false,
+            seeked: false,
             run_on_remove: false,
             run_on_create: false,
             reverse_once: ReverseOnce::Val(false),
@@ -403,7 +777,10 @@ impl Default for Animation {
             on_start: Trigger::new(),
             on_complete: Trigger::new(),
             on_visual_complete: Trigger::new(),
+            handle: None,
+            spring: None,
             debug_description: None,
+            respect_reduced_motion: true,
         }
     }
 }
@@ -564,6 +941,35 @@ impl Animation {
         self
     }
 
+    /// Bind an [AnimationHandle] to this animation so that it can be controlled from outside of the animation closure.
+    pub fn handle(mut self, handle: AnimationHandle) -> Self {
+        self.effect_states.push(handle.views);
+        self.handle = Some(handle);
+        self
+    }
+
+    /// Use a spring to animate the view instead of keyframes.
+    ///
+    /// Any time a prop of the view's style changes (e.g. from a hover selector or a reactive style update)
+    /// the prop will move to its new value as if it was attached to a spring.
+    /// If the prop is interrupted while still moving, the spring is re-targeted from the current value and velocity,
+    /// which makes this well suited for drag-release and hover effects.
+    ///
+    /// A higher `stiffness` makes the spring faster and a higher `damping` reduces the overshoot.
+    /// See [Spring] for some reasonable values.
+    ///
+    /// Keyframes, the duration and state commands are ignored while the animation is in spring mode.
+    pub fn spring(mut self, stiffness: f64, damping: f64) -> Self {
+        self.spring = Some(SpringDriver::new(stiffness, damping));
+        self
+    }
+
+    /// Sets the playback speed of the animation. `1.` is the normal speed, `2.` is twice as fast.
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = speed.max(0.);
+        self
+    }
+
     /// Set whether this animation should run when being created.
     ///
     /// I.e when being created by a dyn container or when being shown after being hidden.
@@ -599,6 +1005,16 @@ impl Animation {
         self
     }
 
+    /// Keeps the animation playing while the user prefers reduced motion, for animations that
+    /// convey information, like a progress indicator.
+    ///
+    /// Other animations play a single pass that ends almost immediately, see
+    /// [`prefers_reduced_motion`](crate::accessibility::prefers_reduced_motion).
+    pub const fn ignore_reduced_motion(mut self) -> Self {
+        self.respect_reduced_motion = false;
+        self
+    }
+
     /// Sets if this animation should auto reverse.
     /// If true, the animation will reach the final key frame twice as fast and then animate backwards
     pub const fn auto_reverse(mut self, auto_rev: bool) -> Self {
@@ -676,6 +1092,23 @@ impl Animation {
         self
     }
 
+    /// Drive the position of the animation from `progress` instead of time.
+    ///
+    /// `progress` should return a value in the range `0. - 1.` and is tracked reactively,
+    /// so the animation can be driven by e.g. a [scroll progress](crate::views::Scroll::scroll_progress) signal.
+    /// The animation is kept paused and only moves when `progress` changes.
+    pub fn driven_by(mut self, progress: impl Fn() -> f64 + 'static) -> Self {
+        let states = RwSignal::new(SmallVec::new());
+        self.effect_states.push(states);
+        let initial_progress = create_updater(progress, move |percent| {
+            for (view_id, stack_offset) in states.get_untracked() {
+                view_id.update_animation_with(stack_offset, move |anim| anim.seek_mut(percent))
+            }
+        });
+        self.seek_mut(initial_progress);
+        self
+    }
+
     /// The animation will receive a pause command any time the trigger function tracks any reactive updates.
     pub fn pause(self, trigger: impl Fn() + 'static) -> Self {
         self.state(
@@ -764,6 +1197,20 @@ impl Animation {
         }
     }
 
+    /// Whether the animation is reduced to a single, almost immediate pass.
+    fn reduced_motion(&self) -> bool {
+        self.respect_reduced_motion && accessibility::reduced_motion_untracked()
+    }
+
+    /// The speed that the animation plays at, which is sped up to reduce motion.
+    fn effective_speed(&self) -> f64 {
+        if self.reduced_motion() {
+            self.speed * REDUCED_MOTION_SPEEDUP
+        } else {
+            self.speed
+        }
+    }
+
     /// Returns the current amount of time that has elapsed since the animation started.
     pub fn elapsed(&self) -> Option<Duration> {
         match &self.state {
@@ -777,8 +1224,8 @@ impl Animation {
                 started_on,
                 elapsed,
             } => {
-                let duration = Instant::now() - *started_on;
-                Some(*elapsed + duration)
+                let duration = clock::now() - *started_on;
+                Some(*elapsed + duration.mul_f64(self.effective_speed()))
             }
             AnimState::PassFinished { elapsed, .. } => Some(*elapsed),
             AnimState::Completed { elapsed, .. } => *elapsed,
@@ -788,6 +1235,13 @@ impl Animation {
 
     /// Advance the animation.
     pub fn advance(&mut self) {
+        let speed = self.effective_speed();
+        // repeating animations stop after their first pass while motion is reduced
+        let repeat_mode = if self.reduced_motion() {
+            RepeatMode::Times(1)
+        } else {
+            self.repeat_mode.clone()
+        };
         match &mut self.state {
             AnimState::Idle => {
                 self.start_mut();
@@ -797,10 +1251,10 @@ impl Animation {
                 started_on,
                 mut elapsed,
             } => {
-                let now = Instant::now();
+                let now = clock::now();
                 let duration = now - *started_on;
                 let og_elapsed = elapsed;
-                elapsed = duration;
+                elapsed += duration.mul_f64(speed);
 
                 let temp_elapsed = if elapsed <= self.delay {
                     // The animation hasn't started yet
@@ -828,9 +1282,9 @@ impl Animation {
                 started_on,
                 mut elapsed,
             } => {
-                let now = Instant::now();
+                let now = clock::now();
                 let duration = now - *started_on;
-                elapsed = duration;
+                elapsed += duration.mul_f64(speed);
 
                 if self.props_in_ext_progress.is_empty() {
                     self.state = AnimState::PassFinished {
@@ -842,10 +1296,13 @@ impl Animation {
             AnimState::PassFinished {
                 elapsed,
                 was_in_ext,
-            } => match self.repeat_mode {
+            } => match repeat_mode {
                 RepeatMode::LoopForever => {
+                    if let Some(handle) = self.handle {
+                        handle.iteration.notify();
+                    }
                     self.state = AnimState::PassInProgress {
-                        started_on: Instant::now(),
+                        started_on: clock::now(),
                         elapsed: Duration::ZERO,
                     }
                 }
@@ -854,6 +1311,9 @@ impl Animation {
                     if self.repeat_count >= times {
                         self.reverse_once.set(false);
                         self.on_complete.notify();
+                        if let Some(handle) = self.handle {
+                            handle.completed.notify();
+                        }
                         if !*was_in_ext {
                             self.on_visual_complete.notify();
                         }
@@ -861,8 +1321,11 @@ impl Animation {
                             elapsed: Some(*elapsed),
                         }
                     } else {
+                        if let Some(handle) = self.handle {
+                            handle.iteration.notify();
+                        }
                         self.state = AnimState::PassInProgress {
-                            started_on: Instant::now(),
+                            started_on: clock::now(),
                             elapsed: Duration::ZERO,
                         }
                     }
@@ -888,7 +1351,7 @@ impl Animation {
             AnimStateCommand::Resume => {
                 if let AnimState::Paused { elapsed } = &self.state {
                     self.state = AnimState::PassInProgress {
-                        started_on: Instant::now(),
+                        started_on: clock::now(),
                         elapsed: elapsed.unwrap_or(Duration::ZERO),
                     }
                 }
@@ -897,7 +1360,7 @@ impl Animation {
                 self.folded_style.map.clear();
                 self.repeat_count = 0;
                 self.state = AnimState::PassInProgress {
-                    started_on: Instant::now(),
+                    started_on: clock::now(),
                     elapsed: Duration::ZERO,
                 }
             }
@@ -908,6 +1371,73 @@ impl Animation {
         }
     }
 
+    /// Move the elapsed time of the current pass to `elapsed` while keeping the animation in a running or paused state.
+    fn set_elapsed(&mut self, elapsed: Duration) {
+        self.props_in_ext_progress.clear();
+        match self.state {
+            AnimState::PassInProgress { .. }
+            | AnimState::ExtMode { .. }
+            | AnimState::PassFinished { .. } => {
+                self.state = AnimState::PassInProgress {
+                    started_on: clock::now(),
+                    elapsed,
+                }
+            }
+            AnimState::Idle
+            | AnimState::Stopped
+            | AnimState::Paused { .. }
+            | AnimState::Completed { .. } => {
+                self.state = AnimState::Paused {
+                    elapsed: Some(elapsed),
+                };
+                self.seeked = true;
+            }
+        }
+    }
+
+    /// The progress of the current pass as a percent (0. - 1.), not accounting for any reversing.
+    fn pass_percent(&self) -> f64 {
+        if self.duration == Duration::ZERO {
+            return 0.;
+        }
+        let elapsed = self.elapsed().unwrap_or(Duration::ZERO);
+        let elapsed = elapsed.saturating_sub(self.delay);
+        (elapsed.as_secs_f64() / self.duration.as_secs_f64()).clamp(0., 1.)
+    }
+
+    pub(crate) fn seek_mut(&mut self, percent: f64) {
+        let percent = percent.clamp(0., 1.);
+        let percent = if self.reversed { 1. - percent } else { percent };
+        self.set_elapsed(self.delay + self.duration.mul_f64(percent));
+    }
+
+    pub(crate) fn set_speed_mut(&mut self, speed: f64) {
+        if let AnimState::PassInProgress { .. } | AnimState::ExtMode { .. } = self.state {
+            // restart the clock so that the time elapsed so far keeps the old speed
+            let elapsed = self.elapsed().unwrap_or(Duration::ZERO);
+            self.state = AnimState::PassInProgress {
+                started_on: clock::now(),
+                elapsed,
+            };
+        }
+        self.speed = speed.max(0.);
+    }
+
+    pub(crate) fn reverse_mut(&mut self) {
+        self.reversed = !self.reversed;
+        match self.state {
+            AnimState::Completed { .. } => self.transition(AnimStateCommand::Start),
+            AnimState::Idle | AnimState::Stopped => {}
+            AnimState::Paused { .. }
+            | AnimState::PassInProgress { .. }
+            | AnimState::ExtMode { .. }
+            | AnimState::PassFinished { .. } => {
+                let percent = 1. - self.pass_percent();
+                self.set_elapsed(self.delay + self.duration.mul_f64(percent));
+            }
+        }
+    }
+
     /// Get the total time the animation has been running as a percent (0. - 1.)
     pub(crate) fn total_time_percent(&self) -> f64 {
         if self.duration == Duration::ZERO {
@@ -935,6 +1465,10 @@ impl Animation {
             percent *= 2.0; // Normalize to [0.0, 1.0] range after reversal adjustment
         }
 
+        if self.reversed {
+            percent = 1. - percent;
+        }
+
         if self.reverse_once.is_rev() {
             1. - percent
         } else {
@@ -1186,3 +1720,447 @@ impl Animation {
             }
     }
 }
+
+/// Starts the animations of `id` and all of its children that should run when the view is removed or hidden.
+///
+/// `on_complete` is called every time one of the started animations visually completes.
+/// The effects that call it are created in `scope`, so they are dropped together with the view.
+///
+/// Returns how many animations were started.
+pub(crate) fn start_exit_animations(
+    id: ViewId,
+    scope: Scope,
+    on_complete: impl Fn() + Clone + 'static,
+) -> u16 {
+    let mut wait_for = 0;
+    let state = id.state();
+    let mut state = state.borrow_mut();
+    let mut request_style = false;
+    for anim in &mut state.animations.stack {
+        if anim.run_on_remove && !matches!(anim.repeat_mode, RepeatMode::LoopForever) {
+            anim.reverse_once.set(true);
+            anim.start_mut();
+            request_style = true;
+            wait_for += 1;
+            let trigger = anim.on_visual_complete;
+            let on_complete = on_complete.clone();
+            scope.create_updater(move || trigger.track(), move |_| on_complete());
+        }
+    }
+    drop(state);
+    if request_style {
+        id.request_style();
+    }
+
+    id.children().into_iter().fold(wait_for, |acc, id| {
+        acc + start_exit_animations(id, scope, on_complete.clone())
+    })
+}
+
+/// Starts the animations of `id` and all of its children that should run when the view is created or shown.
+pub(crate) fn start_enter_animations(id: ViewId) {
+    let state = id.state();
+    let mut state = state.borrow_mut();
+    let mut request_style = false;
+    for anim in &mut state.animations.stack {
+        if anim.run_on_create && !matches!(anim.repeat_mode, RepeatMode::LoopForever) {
+            anim.reverse_once.set(false);
+            anim.start_mut();
+            request_style = true;
+        }
+    }
+    drop(state);
+    if request_style {
+        id.request_style();
+    }
+
+    id.children().into_iter().for_each(start_enter_animations);
+}
+
+/// Coordinates the exit animations of several views, e.g. to wait until all of them have completed.
+///
+/// Views are added to a group with the `transition_group` method of
+/// [`dyn_stack`](crate::views::DynStack::transition_group),
+/// [`dyn_container`](crate::views::DynamicContainer::transition_group) and
+/// [`Decorators`](crate::views::Decorators::transition_group), and the views exit while their
+/// exit animations play.
+///
+/// ```
+/// # use floem::animate::TransitionGroup;
+/// # use floem::reactive::{RwSignal, SignalGet, SignalUpdate};
+/// # use floem::views::{dyn_stack, label, Decorators};
+/// # use floem::unit::UnitExt;
+/// let items = RwSignal::new(vec![1, 2, 3]);
+/// let group = TransitionGroup::new();
+/// let list = dyn_stack(move || items.get(), |item| *item, |item| label(move || item))
+///     .on_exit_animation(|a| a.keyframe(100, |f| f.style(|s| s.height(0.pct()))))
+///     .transition_group(group);
+///
+/// items.set(Vec::new());
+/// group.after_exit(|| println!("the list is empty"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TransitionGroup {
+    exiting: RwSignal<usize>,
+}
+
+impl Default for TransitionGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransitionGroup {
+    /// Create a new group without any views.
+    pub fn new() -> Self {
+        Self {
+            exiting: RwSignal::new(0),
+        }
+    }
+
+    /// How many views of the group are playing their exit animations, which is tracked.
+    pub fn exiting(&self) -> usize {
+        self.exiting.get()
+    }
+
+    /// Run `after_exit` once no view of the group is playing its exit animations, which is right
+    /// away if none is.
+    pub fn after_exit(&self, after_exit: impl FnOnce() + 'static) {
+        let exiting = self.exiting;
+        let scope = Scope::current().create_child();
+        let after_exit = Cell::new(Some(after_exit));
+        with_scope(scope, || {
+            create_effect(move |_| {
+                if exiting.get() == 0 {
+                    if let Some(after_exit) = after_exit.take() {
+                        untrack(after_exit);
+                        scope.dispose();
+                    }
+                }
+            });
+        });
+    }
+
+    pub(crate) fn exit_started(&self) {
+        self.exiting.update(|exiting| *exiting += 1);
+    }
+
+    pub(crate) fn exit_ended(&self) {
+        self.exiting
+            .update(|exiting| *exiting = exiting.saturating_sub(1));
+    }
+}
+
+type AnimationFn = Rc<dyn Fn(Animation) -> Animation>;
+
+/// The enter and exit animations that a dynamic view adds to each of the child views it creates.
+///
+/// The animations are started with [start_enter_animations] and [start_exit_animations].
+#[derive(Default, Clone)]
+pub(crate) struct ChildAnimations {
+    enter: Option<AnimationFn>,
+    exit: Option<AnimationFn>,
+    group: Option<TransitionGroup>,
+}
+
+impl ChildAnimations {
+    pub(crate) fn set_group(&mut self, group: TransitionGroup) {
+        self.group = Some(group);
+    }
+
+    /// Tells the group, if there's one, that a child started playing its exit animations.
+    pub(crate) fn exit_started(&self) {
+        if let Some(group) = self.group {
+            group.exit_started();
+        }
+    }
+
+    /// Tells the group, if there's one, that a child has exited.
+    pub(crate) fn exit_ended(&self) {
+        if let Some(group) = self.group {
+            group.exit_ended();
+        }
+    }
+
+    pub(crate) fn set_enter(&mut self, animation: impl Fn(Animation) -> Animation + 'static) {
+        self.enter = Some(Rc::new(animation));
+    }
+
+    pub(crate) fn set_exit(&mut self, animation: impl Fn(Animation) -> Animation + 'static) {
+        self.exit = Some(Rc::new(animation));
+    }
+
+    /// Adds the animations to a child view. The effects of the animations are created in the `scope` of the child.
+    pub(crate) fn attach(&self, id: ViewId, scope: Scope) {
+        self.attach_enter(id, scope);
+        self.attach_exit(id, scope);
+    }
+
+    pub(crate) fn attach_enter(&self, id: ViewId, scope: Scope) {
+        if let Some(enter) = self.enter.clone() {
+            with_scope(scope, || add_enter_animation(id, move |a| enter(a)));
+        }
+    }
+
+    pub(crate) fn attach_exit(&self, id: ViewId, scope: Scope) {
+        if let Some(exit) = self.exit.clone() {
+            with_scope(scope, || add_exit_animation(id, move |a| exit(a)));
+        }
+    }
+}
+
+/// Adds an animation to `id` that is started by [start_enter_animations].
+pub(crate) fn add_enter_animation(id: ViewId, enter: impl Fn(Animation) -> Animation + 'static) {
+    id.add_animation(move |a| {
+        enter(a)
+            .run_on_create(true)
+            .run_on_remove(false)
+            .initial_state(AnimStateCommand::Stop)
+    });
+}
+
+/// Adds an animation to `id` that is started by [start_exit_animations].
+pub(crate) fn add_exit_animation(id: ViewId, exit: impl Fn(Animation) -> Animation + 'static) {
+    id.add_animation(move |a| {
+        // exit animations are written from the shown to the removed state, so they are never reversed
+        exit(a)
+            .only_on_remove()
+            .reverse_on_exit(false)
+            .apply_when_finished(true)
+            .initial_state(AnimStateCommand::Stop)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{any::Any, cell::Cell, rc::Rc, time::Duration};
+
+    use super::{
+        AnimStateCommand, AnimStateKind, Animation, AnimationHandle, SpringDriver, SpringProp,
+    };
+    use crate::{
+        accessibility::{self, Preferences},
+        clock,
+        style::{Style, StyleMapValue, StyleProp, Width},
+        unit::PxPctAuto,
+    };
+
+    /// An animation of 100ms that is started on the virtual clock.
+    fn started() -> Animation {
+        clock::use_virtual_clock();
+        accessibility::set_preferences(Preferences::default());
+        let mut animation = Animation::new().duration(Duration::from_millis(100));
+        animation.transition(AnimStateCommand::Start);
+        animation
+    }
+
+    fn advance(millis: u64) {
+        clock::advance(Duration::from_millis(millis));
+    }
+
+    fn assert_percent(animation: &Animation, expected: f64) {
+        let percent = animation.total_time_percent();
+        assert!(
+            (percent - expected).abs() < 1e-6,
+            "the animation is at {percent}, not {expected}"
+        );
+    }
+
+    #[test]
+    fn seeking_moves_to_a_percent() {
+        let mut animation = started();
+        advance(10);
+        animation.seek_mut(0.25);
+        assert_percent(&animation, 0.25);
+        // a running animation keeps running from there
+        advance(10);
+        assert!(animation.is_in_progress());
+        assert_percent(&animation, 0.35);
+
+        animation.transition(AnimStateCommand::Pause);
+        animation.seek_mut(0.75);
+        advance(10);
+        assert_eq!(animation.state_kind(), AnimStateKind::Paused);
+        assert_percent(&animation, 0.75);
+        clock::release_virtual_clock();
+    }
+
+    #[test]
+    fn speed_scales_the_time_after_it_changes() {
+        let mut animation = started();
+        advance(20);
+        assert_percent(&animation, 0.2);
+        animation.set_speed_mut(2.);
+        assert_percent(&animation, 0.2);
+        advance(20);
+        assert_percent(&animation, 0.6);
+        animation.set_speed_mut(0.5);
+        advance(20);
+        assert_percent(&animation, 0.7);
+        clock::release_virtual_clock();
+    }
+
+    #[test]
+    fn reversing_continues_from_the_current_point() {
+        let mut animation = started();
+        advance(30);
+        animation.reverse_mut();
+        assert_percent(&animation, 0.3);
+        advance(10);
+        assert_percent(&animation, 0.2);
+        advance(20);
+        assert_percent(&animation, 0.);
+
+        // reversing again plays forwards from where it is
+        animation.reverse_mut();
+        assert_percent(&animation, 0.);
+        advance(40);
+        assert_percent(&animation, 0.4);
+        clock::release_virtual_clock();
+    }
+
+    #[test]
+    fn iteration_and_completion_callbacks() {
+        let mut animation = started().repeat_times(2);
+        let handle = AnimationHandle::new();
+        animation = animation.handle(handle);
+        let iterations = Rc::new(Cell::new(0));
+        let completions = Rc::new(Cell::new(0));
+        handle.on_iteration({
+            let iterations = iterations.clone();
+            move || iterations.set(iterations.get() + 1)
+        });
+        handle.on_complete({
+            let completions = completions.clone();
+            move || completions.set(completions.get() + 1)
+        });
+
+        advance(50);
+        animation.advance();
+        assert_eq!((iterations.get(), completions.get()), (0, 0));
+
+        // the first pass finishes, and the second one starts
+        advance(50);
+        animation.advance();
+        animation.advance();
+        assert_eq!((iterations.get(), completions.get()), (1, 0));
+        assert!(animation.is_in_progress());
+
+        advance(100);
+        animation.advance();
+        animation.advance();
+        assert_eq!((iterations.get(), completions.get()), (1, 1));
+        assert!(animation.is_completed());
+
+        animation.advance();
+        assert_eq!((iterations.get(), completions.get()), (1, 1));
+        clock::release_virtual_clock();
+    }
+
+    /// Runs a frame of 16ms in which the computed width is `target`, returning the width the
+    /// spring moved the view to and whether it's still moving.
+    fn spring_frame(driver: &mut SpringDriver, target: f64) -> (f64, bool) {
+        let mut style = Style::new().width(target);
+        let moving = driver.animate_into(&mut style);
+        advance(16);
+        let PxPctAuto::Px(width) = style.get(Width) else {
+            panic!("the width isn't in pixels");
+        };
+        (width, moving)
+    }
+
+    /// A spring that started moving from a width of 0 to 100 in the last frame.
+    fn moving_spring(stiffness: f64, damping: f64) -> SpringDriver {
+        let mut driver = SpringDriver::new(stiffness, damping);
+        spring_frame(&mut driver, 0.);
+        assert_eq!(spring_frame(&mut driver, 100.), (0., true));
+        driver
+    }
+
+    #[test]
+    fn spring_converges_to_the_target() {
+        clock::use_virtual_clock();
+        let mut driver = moving_spring(170., 26.);
+        let mut frames = 0;
+        while spring_frame(&mut driver, 100.).1 {
+            frames += 1;
+            assert!(frames < 120, "the spring hasn't stopped after 2 seconds");
+        }
+        assert!(!driver.is_moving());
+        assert_eq!(spring_frame(&mut driver, 100.), (100., false));
+        clock::release_virtual_clock();
+    }
+
+    #[test]
+    fn interrupted_spring_keeps_its_velocity() {
+        clock::use_virtual_clock();
+        let mut driver = moving_spring(170., 26.);
+        let mut width = 0.;
+        for _ in 0..5 {
+            width = spring_frame(&mut driver, 100.).0;
+        }
+        let (interrupted, _) = spring_frame(&mut driver, 200.);
+
+        // a spring that starts at rest from the same width moves less in the same time
+        let mut at_rest = SpringDriver::new(170., 26.);
+        spring_frame(&mut at_rest, width);
+        spring_frame(&mut at_rest, 200.);
+        let (from_rest, _) = spring_frame(&mut at_rest, 200.);
+        assert!(interrupted > from_rest, "{interrupted} <= {from_rest}");
+        assert!(from_rest > width);
+
+        // and it still settles on the new target
+        while spring_frame(&mut driver, 200.).1 {}
+        assert_eq!(spring_frame(&mut driver, 200.).0, 200.);
+        clock::release_virtual_clock();
+    }
+
+    #[test]
+    fn retargeted_spring_keeps_its_speed_in_pixels() {
+        let px = |width: f64| Rc::new(StyleMapValue::Val(PxPctAuto::Px(width))) as Rc<dyn Any>;
+        // halfway from 0 to 100, moving at 200px/s
+        let moving = SpringProp {
+            from: px(0.),
+            to: px(100.),
+            position: 0.5,
+            velocity: 2.,
+        };
+
+        let mut spring = moving.clone();
+        SpringDriver::retarget(Width::prop_ref(), &mut spring, px(450.));
+        assert_eq!(spring.position, 0.);
+        assert_eq!(spring.velocity, 0.5, "200px/s is half of the 400px to go");
+
+        let mut spring = moving.clone();
+        SpringDriver::retarget(Width::prop_ref(), &mut spring, px(-150.));
+        assert_eq!(spring.velocity, -1., "200px the other way");
+
+        // pixels and percentages can't be compared, so the velocity stays relative
+        let mut spring = moving;
+        let pct = Rc::new(StyleMapValue::Val(PxPctAuto::Pct(50.)));
+        SpringDriver::retarget(Width::prop_ref(), &mut spring, pct);
+        assert_eq!(spring.velocity, 2.);
+    }
+
+    #[test]
+    fn critically_damped_spring_does_not_overshoot() {
+        clock::use_virtual_clock();
+        // the damping is 2 * sqrt(stiffness)
+        let mut driver = moving_spring(100., 20.);
+        loop {
+            let (width, moving) = spring_frame(&mut driver, 100.);
+            assert!(width <= 100., "the spring overshot to {width}");
+            if !moving {
+                break;
+            }
+        }
+
+        let mut bouncy = moving_spring(100., 5.);
+        let mut max: f64 = 0.;
+        while let (width, true) = spring_frame(&mut bouncy, 100.) {
+            max = max.max(width);
+        }
+        assert!(max > 100.);
+        clock::release_virtual_clock();
+    }
+}
@@ -1,6 +1,6 @@
 use std::{cell::RefCell, rc::Rc};
 
-use floem_reactive::WriteSignal;
+use floem_reactive::{ReadSignal, RwSignal, Scope, SignalGet, SignalUpdate, WriteSignal};
 use floem_winit::{
     event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopProxy},
     monitor::MonitorHandle,
@@ -14,10 +14,13 @@ use crate::{
     action::{Timer, TimerToken},
     app_handle::ApplicationHandle,
     clipboard::Clipboard,
+    event::Event,
     inspector::Capture,
     profiler::Profile,
+    screens::Monitor,
     view::{IntoView, View},
-    window::WindowConfig,
+    window::{WindowConfig, WindowInfo},
+    window_tracking,
 };
 
 type AppEventCallback = dyn Fn(AppEvent);
@@ -26,6 +29,8 @@ static EVENT_LOOP_PROXY: Mutex<Option<EventLoopProxy<UserEvent>>> = Mutex::new(N
 
 thread_local! {
     pub(crate) static APP_UPDATE_EVENTS: RefCell<Vec<AppUpdateEvent>> = Default::default();
+    static APP_SCOPE: Scope = Scope::new();
+    static LIFECYCLE: RwSignal<Lifecycle> = app_scope().create_rw_signal(Lifecycle::Running);
 }
 
 /// Initializes and runs an application with a single window.
@@ -44,9 +49,74 @@ pub fn launch<V: IntoView + 'static>(app_view: impl FnOnce() -> V + 'static) {
     Application::new().window(move |_| app_view(), None).run()
 }
 
+/// Returns the reactive scope of the application, which is never disposed.
+///
+/// Each window runs its view function in a scope of its own, which is disposed together with
+/// the signals created in it when the window is closed. All windows run on the same thread, so
+/// a signal can be used by several windows as long as it outlives them, which is what this
+/// scope is for. Using a signal after its scope was disposed panics.
+///
+/// ```no_run
+/// # use floem::{app_scope, new_window, reactive::SignalGet, views::label};
+/// let count = app_scope().create_rw_signal(0);
+/// new_window(move |_| label(move || count.get()), None);
+/// new_window(move |_| label(move || count.get()), None);
+/// ```
+pub fn app_scope() -> Scope {
+    APP_SCOPE.with(|scope| *scope)
+}
+
 pub enum AppEvent {
+    /// The application is about to exit, because it was quit or the OS session is ending.
+    /// It's the last chance to save state.
     WillTerminate,
-    Reopen { has_visible_windows: bool },
+    Reopen {
+        has_visible_windows: bool,
+    },
+    /// A window was created.
+    WindowCreated(WindowId),
+    /// A window was closed.
+    WindowClosed(WindowId),
+    /// A window gained the keyboard focus.
+    WindowFocused(WindowId),
+    /// A window moved to another monitor, or the monitor it is on changed, e.g. its scale
+    /// factor.
+    WindowMonitorChanged(WindowId, Monitor),
+    /// A global hotkey was pressed, see [`register_global_hotkey`](crate::hotkey::register_global_hotkey).
+    #[cfg(feature = "global-hotkey")]
+    GlobalHotkey(crate::hotkey::GlobalHotkey),
+    /// The application was suspended by the system, e.g. moved to the background on a mobile
+    /// platform or hidden on the web. Work like animations and polling should be paused until
+    /// it's [`Resumed`](AppEvent::Resumed), see also [`lifecycle`].
+    Suspended,
+    /// The application was resumed after being [`Suspended`](AppEvent::Suspended).
+    Resumed,
+    /// The system is low on memory, and the application should free what it can, like caches.
+    /// It's only reported on mobile platforms.
+    LowMemory,
+}
+
+/// Whether the application is running or suspended by the system, see [`lifecycle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle {
+    /// The application is running, which it is from the start.
+    Running,
+    /// The application is suspended, until it's resumed.
+    Suspended,
+}
+
+/// Returns the lifecycle of the application as a signal, so views can pause their work while
+/// the application is suspended.
+///
+/// ```no_run
+/// # use floem::{lifecycle, Lifecycle, reactive::SignalGet, views::label};
+/// label(move || match lifecycle().get() {
+///     Lifecycle::Running => "Syncing",
+///     Lifecycle::Suspended => "Paused",
+/// });
+/// ```
+pub fn lifecycle() -> ReadSignal<Lifecycle> {
+    LIFECYCLE.with(|lifecycle| lifecycle.read_only())
 }
 
 #[derive(Debug)]
@@ -54,7 +124,15 @@ pub(crate) enum UserEvent {
     AppUpdate,
     Idle,
     QuitApp,
-    GpuResourcesUpdate { window_id: WindowId },
+    GpuResourcesUpdate {
+        window_id: WindowId,
+    },
+    #[cfg(feature = "tray")]
+    Tray(crate::tray::TrayMessage),
+    #[cfg(feature = "global-hotkey")]
+    GlobalHotkey(u32),
+    #[cfg(all(feature = "remote-inspector", not(target_arch = "wasm32")))]
+    RemoteInspector(crate::remote_inspector::PendingRequest),
 }
 
 pub(crate) enum AppUpdateEvent {
@@ -65,6 +143,10 @@ pub(crate) enum AppUpdateEvent {
     CloseWindow {
         window_id: WindowId,
     },
+    DispatchEvent {
+        window_id: WindowId,
+        event: Event,
+    },
     CaptureWindow {
         window_id: WindowId,
         capture: WriteSignal<Option<Rc<Capture>>>,
@@ -73,6 +155,10 @@ pub(crate) enum AppUpdateEvent {
         window_id: WindowId,
         end_profile: Option<WriteSignal<Option<Rc<Profile>>>>,
     },
+    LayoutOverlay {
+        window_id: WindowId,
+        enabled: bool,
+    },
     RequestTimer {
         timer: Timer,
     },
@@ -133,6 +219,16 @@ impl Application {
         self
     }
 
+    /// Sets the tokio runtime that futures are spawned on, like the ones of
+    /// [`create_signal_from_tokio_channel`](crate::ext_event::create_signal_from_tokio_channel)
+    /// and [`spawn_async`](crate::task::spawn_async), so an app that already runs a runtime
+    /// doesn't need to enter it on the UI thread.
+    #[cfg(feature = "tokio")]
+    pub fn with_async_runtime(self, handle: tokio::runtime::Handle) -> Self {
+        crate::ext_event::set_async_runtime(handle);
+        self
+    }
+
     /// Create a new window for the application, if you want multiple windows,
     /// just chain more window method to the builder.
     ///
@@ -167,21 +263,57 @@ impl Application {
                 floem_winit::event::Event::WindowEvent { window_id, event } => {
                     handle.handle_window_event(window_id, event, event_loop);
                 }
+                floem_winit::event::Event::DeviceEvent {
+                    event: floem_winit::event::DeviceEvent::MouseMotion { delta },
+                    ..
+                } => {
+                    handle.mouse_motion(delta);
+                }
                 floem_winit::event::Event::DeviceEvent { .. } => {}
                 floem_winit::event::Event::UserEvent(event) => {
                     handle.handle_user_event(event_loop, event_loop_proxy.clone(), event);
                 }
-                floem_winit::event::Event::Suspended => {}
-                floem_winit::event::Event::Resumed => {}
-                floem_winit::event::Event::AboutToWait => {}
+                floem_winit::event::Event::Suspended => {
+                    // only Android destroys the surfaces of the windows in the background
+                    #[cfg(target_os = "android")]
+                    handle.suspend();
+                    LIFECYCLE.with(|lifecycle| lifecycle.set(Lifecycle::Suspended));
+                    if let Some(action) = self.event_listener.as_ref() {
+                        action(AppEvent::Suspended);
+                    }
+                }
+                floem_winit::event::Event::Resumed => {
+                    handle.resume();
+                    // the event loop starts with a `Resumed` event on every platform
+                    if LIFECYCLE.with(|lifecycle| lifecycle.get_untracked()) == Lifecycle::Suspended
+                    {
+                        LIFECYCLE.with(|lifecycle| lifecycle.set(Lifecycle::Running));
+                        if let Some(action) = self.event_listener.as_ref() {
+                            action(AppEvent::Resumed);
+                        }
+                    }
+                }
+                floem_winit::event::Event::AboutToWait => {
+                    handle.run_idle_callbacks(event_loop);
+                }
                 floem_winit::event::Event::LoopExiting => {
                     if let Some(action) = self.event_listener.as_ref() {
                         action(AppEvent::WillTerminate);
                     }
                 }
-                floem_winit::event::Event::MemoryWarning => {}
+                floem_winit::event::Event::MemoryWarning => {
+                    if let Some(action) = self.event_listener.as_ref() {
+                        action(AppEvent::LowMemory);
+                    }
+                }
                 floem_winit::event::Event::Reopen => {}
             }
+
+            for event in handle.take_app_events() {
+                if let Some(action) = self.event_listener.as_ref() {
+                    action(event);
+                }
+            }
         });
     }
 
@@ -191,6 +323,11 @@ impl Application {
         }
     }
 
+    /// Returns the open windows of the application.
+    pub fn windows() -> Vec<WindowInfo> {
+        window_tracking::windows()
+    }
+
     pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
         self.event_loop.available_monitors()
     }
@@ -11,22 +11,27 @@ use web_time::Instant;
 use wgpu::web_sys;
 
 use floem_winit::{
-    dpi::{LogicalPosition, LogicalSize},
-    event::WindowEvent,
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+    event::{ElementState, WindowEvent},
     event_loop::{ControlFlow, EventLoopProxy, EventLoopWindowTarget},
     window::WindowId,
 };
 
-use peniko::kurbo::{Point, Size};
+use peniko::kurbo::{Point, Rect, Size, Vec2};
 
 use crate::{
     action::{Timer, TimerToken},
-    app::{AppUpdateEvent, UserEvent, APP_UPDATE_EVENTS},
+    app::{AppEvent, AppUpdateEvent, UserEvent, APP_UPDATE_EVENTS},
     ext_event::EXT_EVENT_HANDLER,
+    fonts, idle,
     inspector::Capture,
-    profiler::{Profile, ProfileEvent},
+    profiler::{self, Profile, ProfileEvent},
+    screens::{self, Monitor},
+    ui_state::UiStateRegistry,
     view::View,
     window::WindowConfig,
+    window_effects,
+    window_geometry::{self, WindowGeometry},
     window_handle::WindowHandle,
     window_id::process_window_updates,
 };
@@ -34,6 +39,14 @@ use crate::{
 pub(crate) struct ApplicationHandle {
     window_handles: HashMap<floem_winit::window::WindowId, WindowHandle>,
     timers: HashMap<TimerToken, Timer>,
+    /// The window events for the listener of the [`Application`](crate::Application).
+    app_events: Vec<AppEvent>,
+    /// The open modal windows and the parents that they block.
+    modal_parents: HashMap<WindowId, WindowId>,
+    /// The monitor that each window is on.
+    window_monitors: HashMap<WindowId, Monitor>,
+    /// The windows whose geometry is saved when they are closed, with their key and geometry.
+    persisted_geometry: HashMap<WindowId, (String, WindowGeometry)>,
 }
 
 impl ApplicationHandle {
@@ -41,9 +54,17 @@ impl ApplicationHandle {
         Self {
             window_handles: HashMap::new(),
             timers: HashMap::new(),
+            app_events: Vec::new(),
+            modal_parents: HashMap::new(),
+            window_monitors: HashMap::new(),
+            persisted_geometry: HashMap::new(),
         }
     }
 
+    pub(crate) fn take_app_events(&mut self) -> Vec<AppEvent> {
+        mem::take(&mut self.app_events)
+    }
+
     pub(crate) fn handle_user_event(
         &mut self,
         event_loop: &EventLoopWindowTarget<UserEvent>,
@@ -66,6 +87,22 @@ impl ApplicationHandle {
                     .unwrap()
                     .init_renderer();
             }
+            #[cfg(feature = "tray")]
+            UserEvent::Tray(message) => {
+                crate::tray::handle_message(message);
+                self.handle_updates_for_all_windows();
+            }
+            #[cfg(feature = "global-hotkey")]
+            UserEvent::GlobalHotkey(id) => {
+                if let Some(hotkey) = crate::hotkey::handle_event(id) {
+                    self.app_events.push(AppEvent::GlobalHotkey(hotkey));
+                }
+                self.handle_updates_for_all_windows();
+            }
+            #[cfg(all(feature = "remote-inspector", not(target_arch = "wasm32")))]
+            UserEvent::RemoteInspector(request) => {
+                request.answer(&self.window_handles);
+            }
         }
     }
 
@@ -89,6 +126,11 @@ impl ApplicationHandle {
                 AppUpdateEvent::CloseWindow { window_id } => {
                     self.close_window(window_id, event_loop);
                 }
+                AppUpdateEvent::DispatchEvent { window_id, event } => {
+                    if let Some(handle) = self.window_handles.get_mut(&window_id) {
+                        handle.dispatch_event(event);
+                    }
+                }
                 AppUpdateEvent::RequestTimer { timer } => {
                     self.request_timer(timer, event_loop);
                 }
@@ -114,6 +156,11 @@ impl ApplicationHandle {
                         }
                     }
                 }
+                AppUpdateEvent::LayoutOverlay { window_id, enabled } => {
+                    if let Some(handle) = self.window_handles.get_mut(&window_id) {
+                        handle.set_layout_overlay(enabled);
+                    }
+                }
                 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
                 AppUpdateEvent::MenuAction {
                     window_id,
@@ -135,6 +182,42 @@ impl ApplicationHandle {
         event: WindowEvent,
         event_loop: &EventLoopWindowTarget<UserEvent>,
     ) {
+        if let Some(modal) = self.modal_child(window_id) {
+            if matches!(
+                event,
+                WindowEvent::CloseRequested
+                    | WindowEvent::DroppedFile(_)
+                    | WindowEvent::KeyboardInput { .. }
+                    | WindowEvent::Ime(_)
+                    | WindowEvent::CursorMoved { .. }
+                    | WindowEvent::MouseWheel { .. }
+                    | WindowEvent::MouseInput { .. }
+                    | WindowEvent::TouchpadMagnify { .. }
+                    | WindowEvent::Touch(_)
+            ) {
+                // input goes to the modal window until it is closed
+                if let WindowEvent::MouseInput {
+                    state: ElementState::Pressed,
+                    ..
+                } = event
+                {
+                    if let Some(window) = self
+                        .window_handles
+                        .get(&modal)
+                        .and_then(|handle| handle.window.as_ref())
+                    {
+                        window.focus_window();
+                    }
+                }
+                return;
+            }
+        }
+
+        let monitor_may_change = matches!(
+            event,
+            WindowEvent::Moved(_) | WindowEvent::ScaleFactorChanged { .. }
+        );
+        let geometry_may_change = matches!(event, WindowEvent::Moved(_) | WindowEvent::Resized(_));
         let window_handle = match self.window_handles.get_mut(&window_id) {
             Some(window_handle) => window_handle,
             None => return,
@@ -171,6 +254,7 @@ impl ApplicationHandle {
                 WindowEvent::MenuAction(..) => "MenuAction",
                 WindowEvent::RedrawRequested => "RedrawRequested",
             };
+            profiler::start_recording();
             (
                 name,
                 Instant::now(),
@@ -203,6 +287,9 @@ impl ApplicationHandle {
             WindowEvent::HoveredFileCancelled => {}
             WindowEvent::Focused(focused) => {
                 window_handle.focused(focused);
+                if focused {
+                    self.app_events.push(AppEvent::WindowFocused(window_id));
+                }
             }
             WindowEvent::KeyboardInput {
                 event,
@@ -241,7 +328,9 @@ impl ApplicationHandle {
             WindowEvent::TouchpadRotate { .. } => {}
             WindowEvent::TouchpadPressure { .. } => {}
             WindowEvent::AxisMotion { .. } => {}
-            WindowEvent::Touch(_) => {}
+            WindowEvent::Touch(touch) => {
+                window_handle.touch(touch);
+            }
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                 window_handle.scale(scale_factor);
             }
@@ -259,6 +348,7 @@ impl ApplicationHandle {
 
         if let Some((name, start, new_frame)) = start {
             let end = Instant::now();
+            let (spans, layout_cache) = profiler::finish_recording();
 
             if let Some(window_handle) = self.window_handles.get_mut(&window_id) {
                 let profile = window_handle.profile.as_mut().unwrap();
@@ -267,15 +357,68 @@ impl ApplicationHandle {
                     .current
                     .events
                     .push(ProfileEvent { start, end, name });
+                profile.current.spans.extend(spans);
+                profile.current.layout_cache += layout_cache;
 
                 if new_frame {
                     profile.next_frame();
                 }
             }
         }
+        if monitor_may_change {
+            self.update_monitor(window_id);
+        }
+        if geometry_may_change {
+            self.update_persisted_geometry(window_id);
+        }
         self.handle_updates_for_all_windows();
     }
 
+    /// Records the geometry of a window that is saved when it is closed. The geometry of a
+    /// maximized or minimized window isn't recorded, so that the window is restored to the
+    /// geometry it had before.
+    fn update_persisted_geometry(&mut self, window_id: WindowId) {
+        let Some((_, geometry)) = self.persisted_geometry.get_mut(&window_id) else {
+            return;
+        };
+        let Some(window) = self
+            .window_handles
+            .get(&window_id)
+            .and_then(|handle| handle.window.as_ref())
+        else {
+            return;
+        };
+        if window.is_maximized() || window.is_minimized() == Some(true) {
+            return;
+        }
+        if let Ok(position) = window.outer_position() {
+            geometry.position = Point::new(position.x as f64, position.y as f64);
+        }
+        let size = window.inner_size();
+        geometry.size = Size::new(size.width as f64, size.height as f64);
+    }
+
+    /// Updates the list of monitors and reports when the window moved to another monitor.
+    fn update_monitor(&mut self, window_id: WindowId) {
+        let Some(window) = self
+            .window_handles
+            .get(&window_id)
+            .and_then(|handle| handle.window.as_ref())
+        else {
+            return;
+        };
+        screens::update_screens(window);
+        let Some(monitor) = screens::current_monitor(window) else {
+            return;
+        };
+        if let Some(previous) = self.window_monitors.insert(window_id, monitor.clone()) {
+            if previous != monitor {
+                self.app_events
+                    .push(AppEvent::WindowMonitorChanged(window_id, monitor));
+            }
+        }
+    }
+
     pub(crate) fn new_window(
         &mut self,
         event_loop: &EventLoopWindowTarget<UserEvent>,
@@ -296,10 +439,20 @@ impl ApplicationHandle {
             undecorated,
             undecorated_shadow,
             window_level,
+            backdrop,
+            corners,
+            skip_taskbar,
+            persisted_geometry,
             apply_default_theme,
+            color_scheme_themes,
             mac_os_config,
             web_config,
             font_embolden,
+            zoom_shortcuts,
+            default_font_family,
+            default_font_size,
+            parent,
+            modal,
         }: WindowConfig,
     ) {
         let logical_size = size.map(|size| LogicalSize::new(size.width, size.height));
@@ -322,7 +475,8 @@ impl ApplicationHandle {
             use floem_winit::platform::web::WindowBuilderExtWebSys;
             use wgpu::web_sys::wasm_bindgen::JsCast;
 
-            let parent_id = web_config.expect("Specify an id for the canvas.").canvas_id;
+            let web_config = web_config.expect("Specify an id for the canvas.");
+            let parent_id = web_config.canvas_id;
             let doc = web_sys::window()
                 .and_then(|win| win.document())
                 .expect("Couldn't get document.");
@@ -337,6 +491,9 @@ impl ApplicationHandle {
                 canvas.set_width(size.width as u32);
                 canvas.set_height(size.height as u32);
             }
+            if web_config.fill_parent {
+                crate::web::fill_parent(&canvas);
+            }
 
             window_builder = window_builder.with_canvas(Some(canvas));
         };
@@ -355,6 +512,24 @@ impl ApplicationHandle {
             window_builder = window_builder.with_max_inner_size(logical_max_size);
         }
 
+        let restored = persisted_geometry
+            .as_ref()
+            .and_then(|key| window_geometry::storage()?.load(key));
+        if let Some(geometry) = restored {
+            let monitors: Vec<Rect> = event_loop
+                .available_monitors()
+                .map(|monitor| Monitor::new(&monitor, None).physical_bounds)
+                .collect();
+            let (position, size) = window_geometry::fit_to_monitors(geometry, &monitors);
+            if let Some(position) = position {
+                window_builder =
+                    window_builder.with_position(PhysicalPosition::new(position.x, position.y));
+            }
+            window_builder = window_builder
+                .with_inner_size(PhysicalSize::new(size.width, size.height))
+                .with_maximized(geometry.maximized);
+        }
+
         #[cfg(not(target_os = "macos"))]
         if !show_titlebar {
             window_builder = window_builder.with_decorations(false);
@@ -363,7 +538,9 @@ impl ApplicationHandle {
         #[cfg(target_os = "windows")]
         {
             use floem_winit::platform::windows::WindowBuilderExtWindows;
-            window_builder = window_builder.with_undecorated_shadow(undecorated_shadow);
+            window_builder = window_builder
+                .with_undecorated_shadow(undecorated_shadow)
+                .with_skip_taskbar(skip_taskbar);
         }
 
         #[cfg(target_os = "macos")]
@@ -429,20 +606,61 @@ impl ApplicationHandle {
             }
         }
 
+        let parent_window = parent
+            .and_then(|parent| self.window_handles.get(&parent))
+            .and_then(|handle| handle.window.clone());
+        if let Some(parent_window) = parent_window {
+            use raw_window_handle::HasWindowHandle;
+            if let Ok(handle) = parent_window.window_handle() {
+                let handle = handle.as_raw();
+                // an owned window stays above its owner, while a child window would be
+                // confined to the client area of its parent on Windows
+                #[cfg(target_os = "windows")]
+                if let raw_window_handle::RawWindowHandle::Win32(handle) = handle {
+                    use floem_winit::platform::windows::WindowBuilderExtWindows;
+                    window_builder = window_builder.with_owner_window(handle.hwnd.get());
+                }
+                #[cfg(not(target_os = "windows"))]
+                {
+                    // SAFETY: the handle belongs to a window that is still open
+                    window_builder = unsafe { window_builder.with_parent_window(Some(handle)) };
+                }
+            }
+        }
+
         let Ok(window) = window_builder.build(event_loop) else {
             return;
         };
+        window_effects::set_backdrop(&window, backdrop);
+        window_effects::set_corners(&window, corners);
         let window_id = window.id();
-        let window_handle = WindowHandle::new(
+        if let Some(parent) = parent.filter(|_| modal) {
+            self.modal_parents.insert(window_id, parent);
+        }
+        let mut window_handle = WindowHandle::new(
             window,
             event_proxy,
             view_fn,
             transparent,
             apply_default_theme,
+            color_scheme_themes,
             logical_size,
             font_embolden,
+            fonts::window_font_style(default_font_family, default_font_size),
         );
+        window_handle.zoom_shortcuts = zoom_shortcuts;
         self.window_handles.insert(window_id, window_handle);
+        self.app_events.push(AppEvent::WindowCreated(window_id));
+        self.update_monitor(window_id);
+        if let Some(key) = persisted_geometry {
+            let geometry = restored.unwrap_or(WindowGeometry {
+                position: Point::ZERO,
+                size: Size::ZERO,
+                maximized: false,
+            });
+            self.persisted_geometry.insert(window_id, (key, geometry));
+            self.update_persisted_geometry(window_id);
+        }
     }
 
     fn close_window(
@@ -452,22 +670,59 @@ impl ApplicationHandle {
         #[cfg(not(target_os = "macos"))] event_loop: &EventLoopWindowTarget<UserEvent>,
     ) {
         if let Some(handle) = self.window_handles.get_mut(&window_id) {
+            if let Some((key, mut geometry)) = self.persisted_geometry.remove(&window_id) {
+                if let (Some(window), Some(storage)) = (&handle.window, window_geometry::storage())
+                {
+                    geometry.maximized = window.is_maximized();
+                    storage.save(&key, geometry);
+                }
+            }
             handle.window = None;
             handle.destroy();
+            UiStateRegistry::save();
         }
-        self.window_handles.remove(&window_id);
+        if self.window_handles.remove(&window_id).is_some() {
+            self.app_events.push(AppEvent::WindowClosed(window_id));
+        }
+        self.modal_parents
+            .retain(|child, parent| *child != window_id && *parent != window_id);
+        self.window_monitors.remove(&window_id);
         #[cfg(not(target_os = "macos"))]
         if self.window_handles.is_empty() {
             event_loop.exit();
         }
     }
 
+    /// The modal window that blocks the input of `window_id`, if any.
+    fn modal_child(&self, window_id: WindowId) -> Option<WindowId> {
+        self.modal_parents
+            .iter()
+            .find(|(_, parent)| **parent == window_id)
+            .map(|(child, _)| *child)
+    }
+
     fn capture_window(&mut self, window_id: WindowId) -> Option<Capture> {
         self.window_handles
             .get_mut(&window_id)
             .map(|handle| handle.capture())
     }
 
+    /// Drops the renderers of the windows, whose surfaces are destroyed while the app is
+    /// suspended.
+    #[cfg(target_os = "android")]
+    pub(crate) fn suspend(&mut self) {
+        for handle in self.window_handles.values_mut() {
+            handle.suspend();
+        }
+    }
+
+    /// Creates the renderers of the windows again after the app was suspended.
+    pub(crate) fn resume(&mut self) {
+        for handle in self.window_handles.values_mut() {
+            handle.resume();
+        }
+    }
+
     pub(crate) fn idle(&mut self) {
         let ext_events = { mem::take(&mut *EXT_EVENT_HANDLER.queue.lock()) };
 
@@ -478,6 +733,14 @@ impl ApplicationHandle {
         self.handle_updates_for_all_windows();
     }
 
+    /// Sends the relative motion of the mouse to the windows with a pointer lock.
+    pub(crate) fn mouse_motion(&mut self, (x, y): (f64, f64)) {
+        for handle in self.window_handles.values_mut() {
+            handle.pointer_delta(Vec2::new(x, y));
+        }
+        self.handle_updates_for_all_windows();
+    }
+
     fn handle_updates_for_all_windows(&mut self) {
         for (window_id, handle) in self.window_handles.iter_mut() {
             handle.process_update();
@@ -495,16 +758,35 @@ impl ApplicationHandle {
     }
 
     fn fire_timer(&mut self, event_loop: &EventLoopWindowTarget<UserEvent>) {
-        if self.timers.is_empty() {
-            return;
-        }
-
-        let deadline = self.timers.values().map(|timer| timer.deadline).min();
+        let deadline = self
+            .timers
+            .values()
+            .map(|timer| timer.deadline)
+            .chain(idle::next_deadline())
+            .min();
         if let Some(deadline) = deadline {
             event_loop.set_control_flow(ControlFlow::WaitUntil(deadline));
         }
     }
 
+    /// Runs the callbacks of [`schedule_idle`](crate::action::schedule_idle) once the events of
+    /// an iteration of the event loop are handled.
+    pub(crate) fn run_idle_callbacks(&mut self, event_loop: &EventLoopWindowTarget<UserEvent>) {
+        // while views are animating the loop isn't idle, so only the overdue callbacks run
+        let idle = self
+            .window_handles
+            .values()
+            .all(|handle| handle.app_state.scheduled_updates.is_empty());
+        if idle::run(idle) {
+            self.handle_updates_for_all_windows();
+        }
+        if idle && idle::has_pending() {
+            event_loop.set_control_flow(ControlFlow::Poll);
+        } else {
+            self.fire_timer(event_loop);
+        }
+    }
+
     pub(crate) fn handle_timer(&mut self, event_loop: &EventLoopWindowTarget<UserEvent>) {
         let now = Instant::now();
         let tokens: Vec<TimerToken> = self
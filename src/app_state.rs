@@ -10,9 +10,14 @@ use crate::{
     id::ViewId,
     inspector::CaptureState,
     menu::Menu,
-    responsive::{GridBreakpoints, ScreenSizeBp},
+    pointer::{PointerId, PointerLockMode},
+    profiler,
+    responsive::{GridBreakpoints, ScreenInfo, ScreenSizeBp},
     style::{CursorStyle, Style, StyleClassRef, StyleSelector},
+    text_layout_cache::TextLayoutCache,
     view_storage::VIEW_STORAGE,
+    views::{popover::OpenPopover, scroll::ScrollIntoView},
+    window::CaptionButton,
 };
 
 /// Encapsulates and owns the global state of the application,
@@ -23,10 +28,21 @@ pub struct AppState {
     /// when a view is active, it gets mouse event even when the mouse is
     /// not on it
     pub(crate) active: Option<ViewId>,
+    /// The views that captured a touch, which get its events even when it is not on them.
+    pub(crate) pointer_captures: HashMap<PointerId, ViewId>,
+    /// The open popovers that are dismissed by clicking outside of them, topmost last.
+    pub(crate) popovers: Vec<OpenPopover>,
+    /// The views that are the caption buttons of a custom title bar.
+    pub(crate) caption_buttons: HashMap<ViewId, CaptionButton>,
     pub(crate) root_view_id: ViewId,
     pub(crate) root: Option<NodeId>,
+    /// The root node and the space it was last laid out in, which is kept until either of them
+    /// or a node in the tree changes.
+    pub(crate) last_layout: Option<(NodeId, taffy::Size<AvailableSpace>)>,
     pub(crate) root_size: Size,
     pub(crate) scale: f64,
+    /// The text layouts shared by the labels of the window.
+    pub(crate) text_layouts: TextLayoutCache,
     pub(crate) scheduled_updates: Vec<FrameUpdate>,
     pub(crate) request_compute_layout: bool,
     pub(crate) request_paint: bool,
@@ -37,13 +53,28 @@ pub struct AppState {
     pub(crate) drag_start: Option<(ViewId, Point)>,
     pub(crate) dragging_over: HashSet<ViewId>,
     pub(crate) screen_size_bp: ScreenSizeBp,
+    /// The scale factor of the window reported by the OS, without the application zoom.
+    pub(crate) window_scale: f64,
+    /// The amount by which the fonts of the window are emboldened, for the renderers of cached
+    /// layers.
+    pub(crate) font_embolden: f32,
     pub(crate) grid_bps: GridBreakpoints,
     pub(crate) clicking: HashSet<ViewId>,
     pub(crate) hovered: HashSet<ViewId>,
+    /// Views restyled during the current update because their container queries changed.
+    pub(crate) container_query_restyled: HashSet<ViewId>,
+    /// The options of the [`ViewId::scroll_into_view_with`] request that is currently being handled.
+    pub(crate) scroll_into_view: Option<ScrollIntoView>,
     /// This keeps track of all views that have an animation,
     /// regardless of the status of the animation
     pub(crate) cursor: Option<CursorStyle>,
-    pub(crate) last_cursor: CursorIcon,
+    /// The cursors set with [`override_cursor`](crate::action::override_cursor), which are shown
+    /// instead of the cursor of the hovered view. The last one wins.
+    pub(crate) cursor_overrides: Vec<(u64, CursorStyle)>,
+    /// The view that holds the pointer, see [`ViewId::request_pointer_lock`].
+    pub(crate) pointer_lock: Option<(ViewId, PointerLockMode)>,
+    /// The system cursor that was set last, or `None` while it's hidden for a custom cursor.
+    pub(crate) last_cursor: Option<CursorIcon>,
     pub(crate) last_cursor_location: Point,
     pub(crate) keyboard_navigation: bool,
     pub(crate) window_menu: HashMap<usize, Box<dyn Fn()>>,
@@ -51,19 +82,28 @@ pub struct AppState {
 
     /// This is set if we're currently capturing the window for the inspector.
     pub(crate) capture: Option<CaptureState>,
+    /// Whether the layout overlay of the inspector is drawn over the window.
+    pub(crate) layout_overlay: bool,
 }
 
 impl AppState {
     pub fn new(root_view_id: ViewId) -> Self {
         Self {
             root: None,
+            last_layout: None,
             root_view_id,
             focus: None,
             prev_focus: None,
             active: None,
+            pointer_captures: HashMap::new(),
+            popovers: Vec::new(),
+            caption_buttons: HashMap::new(),
             scale: 1.0,
             root_size: Size::ZERO,
+            text_layouts: TextLayoutCache::default(),
             screen_size_bp: ScreenSizeBp::Xs,
+            window_scale: 1.0,
+            font_embolden: 0.0,
             scheduled_updates: Vec::new(),
             request_paint: false,
             request_compute_layout: false,
@@ -75,14 +115,19 @@ impl AppState {
             dragging_over: HashSet::new(),
             clicking: HashSet::new(),
             hovered: HashSet::new(),
+            container_query_restyled: HashSet::new(),
+            scroll_into_view: None,
             cursor: None,
-            last_cursor: CursorIcon::Default,
+            cursor_overrides: Vec::new(),
+            pointer_lock: None,
+            last_cursor: Some(CursorIcon::Default),
             last_cursor_location: Default::default(),
             keyboard_navigation: false,
             grid_bps: GridBreakpoints::default(),
             window_menu: HashMap::new(),
             context_menu: HashMap::new(),
             capture: None,
+            layout_overlay: false,
         }
     }
 
@@ -104,6 +149,17 @@ impl AppState {
             action();
         }
 
+        // a view that is removed while it's exiting after it was hidden leaves its group
+        let exiting_group = {
+            let view_state = view_state.borrow();
+            view_state
+                .transition_group
+                .filter(|_| view_state.is_hidden_state.get_display().is_some())
+        };
+        if let Some(group) = exiting_group {
+            group.exit_ended();
+        }
+
         let node = view_state.borrow().node;
         let taffy = id.taffy();
         let mut taffy = taffy.borrow_mut();
@@ -133,6 +189,12 @@ impl AppState {
         if self.active == Some(id) {
             self.active = None;
         }
+        self.pointer_captures.retain(|_, captured| *captured != id);
+        if self.pointer_lock.is_some_and(|(locked, _)| locked == id) {
+            self.pointer_lock = None;
+        }
+        self.popovers.retain(|open| !open.is_panel(id));
+        self.caption_buttons.remove(&id);
     }
 
     pub(crate) fn can_focus(&self, id: ViewId) -> bool {
@@ -147,6 +209,15 @@ impl AppState {
         self.disabled.contains(id)
     }
 
+    /// The cursor that is shown, which is the last cursor override or else the cursor of the
+    /// hovered view.
+    pub(crate) fn current_cursor(&self) -> Option<&CursorStyle> {
+        self.cursor_overrides
+            .last()
+            .map(|(_, cursor)| cursor)
+            .or(self.cursor.as_ref())
+    }
+
     pub fn is_focused(&self, id: &ViewId) -> bool {
         self.focus.map(|f| &f == id).unwrap_or(false)
     }
@@ -155,6 +226,11 @@ impl AppState {
         self.active.map(|a| &a == id).unwrap_or(false)
     }
 
+    /// Returns the view that captured `pointer`, if any.
+    pub fn pointer_capture(&self, pointer: PointerId) -> Option<ViewId> {
+        self.pointer_captures.get(&pointer).copied()
+    }
+
     pub fn is_clicking(&self, id: &ViewId) -> bool {
         self.clicking.contains(id)
     }
@@ -180,12 +256,12 @@ impl AppState {
         view_class: Option<StyleClassRef>,
         context: &Style,
     ) -> bool {
-        let screen_size_bp = self.screen_size_bp;
+        let screen = self.screen_info();
         let view_state = view_id.state();
         let request_new_frame = view_state.borrow_mut().compute_style(
             view_style,
             view_interact_state,
-            screen_size_bp,
+            screen,
             view_class,
             context,
         );
@@ -194,13 +270,18 @@ impl AppState {
 
     pub fn compute_layout(&mut self) {
         if let Some(root) = self.root {
-            let _ = self.root_view_id.taffy().borrow_mut().compute_layout(
-                root,
-                taffy::prelude::Size {
-                    width: AvailableSpace::Definite((self.root_size.width / self.scale) as f32),
-                    height: AvailableSpace::Definite((self.root_size.height / self.scale) as f32),
-                },
-            );
+            let space = taffy::prelude::Size {
+                width: AvailableSpace::Definite((self.root_size.width / self.scale) as f32),
+                height: AvailableSpace::Definite((self.root_size.height / self.scale) as f32),
+            };
+            let taffy = self.root_view_id.taffy();
+            let mut taffy = taffy.borrow_mut();
+            if self.last_layout == Some((root, space)) && !taffy.dirty(root).unwrap_or(true) {
+                profiler::record_layout_cache(|stats| stats.skipped += 1);
+                return;
+            }
+            let _ = taffy.compute_layout(root, space);
+            self.last_layout = Some((root, space));
         }
     }
 
@@ -246,6 +327,15 @@ impl AppState {
         }
     }
 
+    pub(crate) fn screen_info(&self) -> ScreenInfo {
+        ScreenInfo {
+            breakpoint: self.screen_size_bp,
+            size: self.root_size / self.scale,
+            scale: self.window_scale * self.scale,
+            forced_colors: crate::forced_colors::forced_colors_untracked(),
+        }
+    }
+
     pub(crate) fn update_screen_size_bp(&mut self, size: Size) {
         let bp = self.grid_bps.get_width_bp(size.width);
         self.screen_size_bp = bp;
@@ -315,7 +405,7 @@ impl AppState {
             if self.has_style_for_sel(id, StyleSelector::Focus)
                 || self.has_style_for_sel(id, StyleSelector::FocusVisible)
             {
-                id.request_style_recursive();
+                id.request_style();
             }
             id.apply_event(&EventListener::FocusGained, &Event::FocusGained);
             id.scroll_to(None);
@@ -326,7 +416,7 @@ impl AppState {
             if self.has_style_for_sel(old_id, StyleSelector::Focus)
                 || self.has_style_for_sel(old_id, StyleSelector::FocusVisible)
             {
-                old_id.request_style_recursive();
+                old_id.request_style();
             }
             old_id.apply_event(&EventListener::FocusLost, &Event::FocusLost);
         }
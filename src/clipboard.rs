@@ -7,7 +7,9 @@ use copypasta::{
     x11_clipboard::{Primary as X11SelectionClipboard, X11ClipboardContext},
 };
 
-use copypasta::{ClipboardContext, ClipboardProvider};
+#[cfg(not(target_arch = "wasm32"))]
+use copypasta::ClipboardContext;
+use copypasta::ClipboardProvider;
 
 static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
 
@@ -83,11 +85,16 @@ impl Clipboard {
             )),
         };
 
-        // TODO: Implement clipboard support for the web
-        #[cfg(any(target_os = "macos", windows, target_arch = "wasm32"))]
+        #[cfg(any(target_os = "macos", windows))]
         return Self {
             clipboard: Box::new(ClipboardContext::new().unwrap()),
             selection: None,
         };
+
+        #[cfg(target_arch = "wasm32")]
+        return Self {
+            clipboard: Box::new(crate::web::WebClipboard),
+            selection: None,
+        };
     }
 }
@@ -0,0 +1,175 @@
+//! The time used by animations, transitions and timers.
+//!
+//! It's the system time, except while a [`HeadlessHarness`](crate::headless::HeadlessHarness)
+//! exists on the thread: then it's a virtual clock that only moves when the harness advances
+//! it, and the timers of [`exec_after`](crate::action::exec_after) wait for the virtual clock
+//! instead of the event loop.
+
+use std::cell::RefCell;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use crate::action::{Timer, TimerToken};
+
+thread_local! {
+    static VIRTUAL_CLOCK: RefCell<Option<VirtualClock>> = const { RefCell::new(None) };
+}
+
+struct VirtualClock {
+    now: Instant,
+    timers: Vec<Timer>,
+    /// The number of harnesses using the clock, which is removed when the last one is dropped.
+    users: usize,
+}
+
+/// The current time.
+pub(crate) fn now() -> Instant {
+    VIRTUAL_CLOCK
+        .with_borrow(|clock| clock.as_ref().map(|clock| clock.now))
+        .unwrap_or_else(Instant::now)
+}
+
+/// Switches to the virtual clock, starting it at the current time if it isn't used yet.
+pub(crate) fn use_virtual_clock() {
+    VIRTUAL_CLOCK.with_borrow_mut(|clock| {
+        clock
+            .get_or_insert_with(|| VirtualClock {
+                now: Instant::now(),
+                timers: Vec::new(),
+                users: 0,
+            })
+            .users += 1;
+    });
+}
+
+/// Stops using the virtual clock, switching back to the system time and dropping the pending
+/// timers when it has no other users.
+pub(crate) fn release_virtual_clock() {
+    let timers = VIRTUAL_CLOCK.with_borrow_mut(|current| {
+        let clock = current.as_mut()?;
+        clock.users -= 1;
+        if clock.users > 0 {
+            return None;
+        }
+        current.take().map(|clock| clock.timers)
+    });
+    // the actions are dropped outside the borrow, as they may own views
+    drop(timers);
+}
+
+pub(crate) fn is_virtual() -> bool {
+    VIRTUAL_CLOCK.with_borrow(|clock| clock.is_some())
+}
+
+/// Advances the virtual clock by `duration`.
+pub(crate) fn advance(duration: Duration) {
+    VIRTUAL_CLOCK.with_borrow_mut(|clock| {
+        if let Some(clock) = clock {
+            clock.now += duration;
+        }
+    });
+}
+
+/// Adds a timer that fires when the virtual clock reaches its deadline.
+pub(crate) fn add_timer(timer: Timer) {
+    VIRTUAL_CLOCK.with_borrow_mut(|clock| {
+        if let Some(clock) = clock {
+            clock.timers.push(timer);
+        }
+    });
+}
+
+pub(crate) fn cancel_timer(token: TimerToken) {
+    let timer = VIRTUAL_CLOCK.with_borrow_mut(|clock| {
+        let timers = &mut clock.as_mut()?.timers;
+        let index = timers.iter().position(|timer| timer.token == token)?;
+        Some(timers.remove(index))
+    });
+    drop(timer);
+}
+
+/// The earliest deadline of the timers of the virtual clock.
+pub(crate) fn next_deadline() -> Option<Instant> {
+    VIRTUAL_CLOCK.with_borrow(|clock| {
+        clock
+            .as_ref()?
+            .timers
+            .iter()
+            .map(|timer| timer.deadline)
+            .min()
+    })
+}
+
+/// Removes the timers whose deadline has passed, in the order of their deadlines.
+pub(crate) fn take_due_timers() -> Vec<Timer> {
+    VIRTUAL_CLOCK.with_borrow_mut(|clock| {
+        let Some(clock) = clock else {
+            return Vec::new();
+        };
+        let now = clock.now;
+        let (mut due, pending) = std::mem::take(&mut clock.timers)
+            .into_iter()
+            .partition::<Vec<_>, _>(|timer| timer.deadline <= now);
+        clock.timers = pending;
+        due.sort_by_key(|timer| timer.deadline);
+        due
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+
+    fn timer(fired: &Rc<Cell<u64>>, after: Duration) -> Timer {
+        let fired = fired.clone();
+        Timer {
+            token: TimerToken::next(),
+            action: Box::new(move |token| fired.set(token.into_raw())),
+            deadline: now() + after,
+        }
+    }
+
+    #[test]
+    fn timers_fire_when_the_clock_reaches_them() {
+        use_virtual_clock();
+        let fired = Rc::new(Cell::new(0));
+        let later = timer(&fired, Duration::from_millis(200));
+        let sooner = timer(&fired, Duration::from_millis(100));
+        let (later_token, sooner_token) = (later.token, sooner.token);
+        add_timer(later);
+        add_timer(sooner);
+
+        advance(Duration::from_millis(50));
+        assert!(take_due_timers().is_empty());
+
+        advance(Duration::from_millis(200));
+        let due = take_due_timers();
+        assert_eq!(
+            due.iter().map(|timer| timer.token).collect::<Vec<_>>(),
+            [sooner_token, later_token]
+        );
+        for timer in due {
+            (timer.action)(timer.token);
+        }
+        assert_eq!(fired.get(), later_token.into_raw());
+        release_virtual_clock();
+        assert!(!is_virtual());
+    }
+
+    #[test]
+    fn cancelled_timers_are_removed() {
+        use_virtual_clock();
+        let fired = Rc::new(Cell::new(0));
+        let timer = timer(&fired, Duration::from_millis(100));
+        let token = timer.token;
+        add_timer(timer);
+        cancel_timer(token);
+        assert_eq!(next_deadline(), None);
+        release_virtual_clock();
+    }
+}
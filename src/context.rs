@@ -13,9 +13,9 @@ use std::time::{Duration, Instant};
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, Instant};
 
-use taffy::prelude::NodeId;
+use taffy::{prelude::NodeId, TaffyTree};
 
-use crate::animate::{AnimStateKind, RepeatMode};
+use crate::animate::{start_enter_animations, start_exit_animations, AnimStateKind, RepeatMode};
 use crate::easing::{Easing, Linear};
 use crate::renderer::Renderer;
 use crate::style::DisplayProp;
@@ -23,17 +23,24 @@ use crate::view_state::IsHiddenState;
 use crate::{
     action::{exec_after, show_context_menu},
     app_state::AppState,
+    clock,
     event::{Event, EventListener, EventPropagation},
+    hit_test,
     id::ViewId,
-    inspector::CaptureState,
+    inspector::{self, CaptureState},
+    layer,
     menu::Menu,
-    style::{Style, StyleProp, ZIndex},
+    occlusion,
+    pointer::PointerId,
+    profiler,
+    style::{Style, StyleChanges, StyleProp, ZIndex},
     view::{paint_bg, paint_border, paint_outline, View},
     view_state::ChangeFlags,
 };
 
 pub type EventCallback = dyn FnMut(&Event) -> EventPropagation;
 pub type ResizeCallback = dyn Fn(Rect);
+pub type PaintCallback = dyn Fn(&mut PaintCx, Rect);
 pub type MenuCallback = dyn Fn() -> Menu;
 
 pub(crate) struct ResizeListener {
@@ -82,11 +89,53 @@ impl EventCx<'_> {
         self.app_state.is_active(&id)
     }
 
+    /// Sends the rest of the events of `pointer` to `id`, even when the touch leaves the view.
+    /// The capture is released when the touch ends.
+    pub fn capture_pointer(&mut self, pointer: PointerId, id: ViewId) {
+        self.app_state.pointer_captures.insert(pointer, id);
+    }
+
+    /// Releases the capture of `pointer`, so its events go to the views under it again.
+    pub fn release_pointer(&mut self, pointer: PointerId) {
+        self.app_state.pointer_captures.remove(&pointer);
+    }
+
+    pub fn has_pointer_capture(&self, pointer: PointerId, id: ViewId) -> bool {
+        self.app_state.pointer_capture(pointer) == Some(id)
+    }
+
     #[allow(unused)]
     pub(crate) fn update_focus(&mut self, id: ViewId, keyboard_navigation: bool) {
         self.app_state.update_focus(id, keyboard_navigation);
     }
 
+    /// Runs the capture listeners of the ancestors of `id`, from the root down, for a
+    /// window-positioned event that is sent straight to `id` instead of through its ancestors.
+    pub(crate) fn capture_on_ancestors(&mut self, id: ViewId, event: &Event) -> EventPropagation {
+        let Some(listener) = event.listener() else {
+            return EventPropagation::Continue;
+        };
+        let mut ancestors = Vec::new();
+        let mut current = id.event_parent();
+        while let Some(ancestor) = current {
+            ancestors.push(ancestor);
+            current = ancestor.event_parent();
+        }
+        for ancestor in ancestors.into_iter().rev() {
+            if ancestor.style_has_hidden() {
+                continue;
+            }
+            let window_origin = ancestor.state().borrow().window_origin;
+            let event = event
+                .clone()
+                .transform(Affine::translate(window_origin.to_vec2()));
+            if ancestor.apply_capture(&listener, &event).is_processed() {
+                return EventPropagation::Stop;
+            }
+        }
+        EventPropagation::Continue
+    }
+
     /// Internal method used by Floem. This can be called from parent `View`s to propagate an event to the child `View`.
     pub(crate) fn unconditional_view_event(
         &mut self,
@@ -104,12 +153,57 @@ impl EventCx<'_> {
             return EventPropagation::Continue;
         }
 
+        let _span = profiler::view_span("event", view_id, || view_id.view().borrow().debug_name());
+        inspector::events::visit(view_id);
+        let propagation = self.dispatch_to_view(view_id, event, directed);
+        if propagation.is_processed() {
+            inspector::events::handled_by(view_id);
+        }
+        propagation
+    }
+
+    /// Dispatches `event` to the view `view_id`, which is neither hidden nor disabled, and to
+    /// its children unless the event is `directed`.
+    fn dispatch_to_view(
+        &mut self,
+        view_id: ViewId,
+        event: Event,
+        directed: bool,
+    ) -> EventPropagation {
         // offset the event positions if the event has positions
         // e.g. pointer events, so that the position is relative
         // to the view, taking into account of the layout location
         // of the view and the viewport of the view if it's in a scroll.
+        // the content of a portal is routed through its logical ancestors, so its events are
+        // also kept in window coordinates to hand them to those
+        let logical_parent = view_id.state().borrow().logical_parent;
+        let window_event = logical_parent.map(|_| {
+            let parent_origin = view_id
+                .parent()
+                .map(|parent| parent.state().borrow().window_origin)
+                .unwrap_or_default();
+            event
+                .clone()
+                .transform(Affine::translate(-parent_origin.to_vec2()))
+        });
+        if let Some(window_event) = window_event.as_ref().filter(|_| !directed) {
+            // directed events already ran the capture listeners of the logical ancestors
+            if self
+                .capture_on_ancestors(view_id, window_event)
+                .is_processed()
+            {
+                return EventPropagation::Stop;
+            }
+        }
+
         let event = self.offset_event(view_id, event);
 
+        if let Some(listener) = event.listener() {
+            if view_id.apply_capture(&listener, &event).is_processed() {
+                return EventPropagation::Stop;
+            }
+        }
+
         let view = view_id.view();
         let view_state = view_id.state();
 
@@ -141,8 +235,7 @@ impl EventCx<'_> {
         }
 
         if !directed {
-            let children = view_id.children();
-            for child in children.into_iter().rev() {
+            for child in hit_test::children_at(view_id, event.point()) {
                 if !self.should_send(child, &event) {
                     continue;
                 }
@@ -152,7 +245,7 @@ impl EventCx<'_> {
                 {
                     return EventPropagation::Stop;
                 }
-                if event.is_pointer() {
+                if event.is_pointer() || event.is_touch() {
                     break;
                 }
             }
@@ -313,7 +406,7 @@ impl EventCx<'_> {
                             self.app_state.dragging.as_mut().filter(|d| d.id == view_id)
                         {
                             let dragging_id = dragging.id;
-                            dragging.released_at = Some(Instant::now());
+                            dragging.released_at = Some(clock::now());
                             dragging.release_location = Some(pointer_event.pos);
                             self.app_state.request_paint(view_id);
                             dragging_id.apply_event(&EventListener::DragEnd, &event);
@@ -423,6 +516,36 @@ impl EventCx<'_> {
             }
         }
 
+        if let (Some(logical_parent), Some(window_event)) = (logical_parent, window_event) {
+            return self.bubble_on_ancestors(logical_parent, &window_event);
+        }
+
+        EventPropagation::Continue
+    }
+
+    /// Runs the listeners of `id` and then of its ancestors until one of them processes `event`,
+    /// which is in window coordinates. This is how unprocessed events of the content of a
+    /// portal reach the views around the portal.
+    fn bubble_on_ancestors(&mut self, id: ViewId, event: &Event) -> EventPropagation {
+        let Some(listener) = event.listener() else {
+            return EventPropagation::Continue;
+        };
+        let mut current = Some(id);
+        while let Some(ancestor) = current {
+            if !ancestor.style_has_hidden() {
+                let window_origin = ancestor.state().borrow().window_origin;
+                let event = event
+                    .clone()
+                    .transform(Affine::translate(window_origin.to_vec2()));
+                if ancestor
+                    .apply_event(&listener, &event)
+                    .is_some_and(|prop| prop.is_processed())
+                {
+                    return EventPropagation::Stop;
+                }
+            }
+            current = ancestor.event_parent();
+        }
         EventPropagation::Continue
     }
 
@@ -431,13 +554,15 @@ impl EventCx<'_> {
         let state = id.state();
         let viewport = state.borrow().viewport;
         let transform = state.borrow().transform;
+        let zoom_transform = state.borrow().zoom_transform;
 
         if let Some(layout) = id.get_layout() {
             event.transform(
                 Affine::translate((
                     layout.location.x as f64 - viewport.map(|rect| rect.x0).unwrap_or(0.0),
                     layout.location.y as f64 - viewport.map(|rect| rect.y0).unwrap_or(0.0),
-                )) * transform,
+                )) * zoom_transform
+                    * transform,
             )
         } else {
             event
@@ -486,6 +611,8 @@ pub struct InteractionState {
     pub(crate) is_focused: bool,
     pub(crate) is_clicking: bool,
     pub(crate) using_keyboard_navigation: bool,
+    /// The size of the view that container queries are matched against.
+    pub(crate) size: Size,
 }
 
 pub struct StyleCx<'a> {
@@ -509,7 +636,7 @@ impl<'a> StyleCx<'a> {
             current: Default::default(),
             direct: Default::default(),
             saved: Default::default(),
-            now: Instant::now(),
+            now: clock::now(),
             saved_disabled: Default::default(),
             saved_selected: Default::default(),
             disabled: false,
@@ -530,6 +657,7 @@ impl<'a> StyleCx<'a> {
             is_focused: self.app_state.is_focused(id),
             is_clicking: self.app_state.is_clicking(id),
             using_keyboard_navigation: self.app_state.keyboard_navigation,
+            size: id.state().borrow().container_size,
         }
     }
 
@@ -545,25 +673,16 @@ impl<'a> StyleCx<'a> {
                 return;
             }
             view_state.requested_changes.remove(ChangeFlags::STYLE);
+            if let Some(layer) = view_state.layer.as_mut() {
+                layer.invalidate();
+            }
         }
+        let _span = profiler::view_span("style", view_id, || view.borrow().debug_name());
 
         let view_style = view.borrow().view_style();
         let view_class = view.borrow().view_class();
-        {
-            let mut view_state = view_state.borrow_mut();
-
-            // Propagate style requests to children if needed.
-            if view_state.request_style_recursive {
-                view_state.request_style_recursive = false;
-                let children = view_id.children();
-                for child in children {
-                    let view_state = child.state();
-                    let mut state = view_state.borrow_mut();
-                    state.request_style_recursive = true;
-                    state.requested_changes.insert(ChangeFlags::STYLE);
-                }
-            }
-        }
+        let restyle_children = std::mem::take(&mut view_state.borrow_mut().request_style_recursive);
+        let old_style = view_state.borrow().combined_style.clone();
 
         let mut view_interact_state = self.get_interact_state(&view_id);
         view_interact_state.is_disabled |= self.disabled;
@@ -577,6 +696,17 @@ impl<'a> StyleCx<'a> {
         );
 
         let style = view_state.borrow().combined_style.clone();
+        // Only the changed groups of properties are updated: the descendants are restyled if
+        // the properties they inherit changed, and the window is painted if anything changed.
+        let mut changes = old_style.changes(&style);
+        if restyle_children || changes.contains(StyleChanges::INHERITED) {
+            for child in view_id.children() {
+                let view_state = child.state();
+                let mut state = view_state.borrow_mut();
+                state.request_style_recursive = true;
+                state.requested_changes.insert(ChangeFlags::STYLE);
+            }
+        }
         self.direct = style;
         Style::apply_only_inherited(&mut self.current, &self.direct);
         CaptureState::capture_style(view_id, self);
@@ -611,21 +741,29 @@ impl<'a> StyleCx<'a> {
         if taffy_style != view_state.borrow().taffy_style {
             view_state.borrow_mut().taffy_style = taffy_style;
             view_id.request_layout();
+            changes |= StyleChanges::LAYOUT;
+        }
+        if !changes.is_empty() {
+            self.app_state.request_paint(view_id);
         }
 
         view.borrow_mut().style_pass(self);
 
         let mut is_hidden_state = view_state.borrow().is_hidden_state;
+        let was_exiting = is_hidden_state.get_display().is_some();
         let computed_display = view_state.borrow().combined_style.get(DisplayProp);
         is_hidden_state.transition(
             computed_display,
             || {
-                let count = animations_on_remove(view_id, Scope::current());
+                let count = start_exit_animations(view_id, Scope::current(), move || {
+                    view_id.transition_anim_complete()
+                });
                 view_state.borrow_mut().num_waiting_animations = count;
                 count > 0
             },
             || {
-                animations_on_create(view_id);
+                view_state.borrow_mut().num_waiting_animations = 0;
+                start_enter_animations(view_id);
             },
             || {
                 stop_reset_remove_animations(view_id);
@@ -637,6 +775,15 @@ impl<'a> StyleCx<'a> {
         // }
 
         view_state.borrow_mut().is_hidden_state = is_hidden_state;
+        let is_exiting = is_hidden_state.get_display().is_some();
+        let group = view_state.borrow().transition_group;
+        if let Some(group) = group.filter(|_| was_exiting != is_exiting) {
+            if is_exiting {
+                group.exit_started();
+            } else {
+                group.exit_ended();
+            }
+        }
         let modified = view_state
             .borrow()
             .combined_style
@@ -787,7 +934,10 @@ impl<'a> ComputeLayoutCx<'a> {
         let view_state = id.state();
 
         if view_state.borrow().is_hidden_state == IsHiddenState::Hidden {
-            view_state.borrow_mut().layout_rect = Rect::ZERO;
+            let old = std::mem::replace(&mut view_state.borrow_mut().layout_rect, Rect::ZERO);
+            if old != Rect::ZERO {
+                hit_test::invalidate_parent(id);
+            }
             return None;
         }
 
@@ -815,6 +965,12 @@ impl<'a> ComputeLayoutCx<'a> {
             view_state.borrow_mut().window_origin = window_origin;
         }
 
+        if let Some(layout_animation) = view_state.borrow_mut().layout_animation.as_mut() {
+            if layout_animation.layout_changed(size.to_rect().with_origin(origin), clock::now()) {
+                self.app_state.request_paint(id);
+            }
+        }
+
         let resize_listener = view_state.borrow().resize_listener.clone();
         if let Some(resize) = resize_listener.as_ref() {
             let mut resize = resize.borrow_mut();
@@ -834,6 +990,22 @@ impl<'a> ComputeLayoutCx<'a> {
             }
         }
 
+        if view_state
+            .borrow()
+            .has_style_selectors
+            .has_container_query()
+            && view_state.borrow().container_size != size
+        {
+            view_state.borrow_mut().container_size = size;
+            // Restyle in this frame unless the view was already restyled for its size,
+            // which avoids looping forever on styles that flip the query result.
+            if self.app_state.container_query_restyled.insert(id) {
+                id.request_style();
+            } else {
+                self.app_state.schedule_style(id);
+            }
+        }
+
         let view = id.view();
         let child_layout_rect = view.borrow_mut().compute_layout(self);
 
@@ -844,7 +1016,10 @@ impl<'a> ComputeLayoutCx<'a> {
             layout_rect
         };
 
-        view_state.borrow_mut().layout_rect = layout_rect;
+        let old = std::mem::replace(&mut view_state.borrow_mut().layout_rect, layout_rect);
+        if old != layout_rect {
+            hit_test::invalidate_parent(id);
+        }
 
         self.restore();
 
@@ -891,10 +1066,13 @@ impl<'a> LayoutCx<'a> {
         {
             return node;
         }
-        view_state
-            .borrow_mut()
-            .requested_changes
-            .remove(ChangeFlags::LAYOUT);
+        {
+            let mut view_state = view_state.borrow_mut();
+            view_state.requested_changes.remove(ChangeFlags::LAYOUT);
+            if let Some(layer) = view_state.layer.as_mut() {
+                layer.invalidate();
+            }
+        }
         let layout_style = view_state.borrow().layout_props.to_style();
         let animate_out_display = view_state.borrow().is_hidden_state.get_display();
         let style = view_state
@@ -904,22 +1082,92 @@ impl<'a> LayoutCx<'a> {
             .apply(layout_style)
             .apply_opt(animate_out_display, Style::display)
             .to_taffy_style();
-        let _ = id.taffy().borrow_mut().set_style(node, style);
+        let mut changed = set_taffy_style(&mut id.taffy().borrow_mut(), node, style);
 
         if has_children {
             let nodes = children(self);
-            let _ = id.taffy().borrow_mut().set_children(node, &nodes);
+            let taffy = id.taffy();
+            let mut taffy = taffy.borrow_mut();
+            if !taffy.children(node).is_ok_and(|current| current == nodes) {
+                let _ = taffy.set_children(node, &nodes);
+                changed = true;
+            }
         }
+        profiler::record_layout_cache(|stats| {
+            if changed {
+                stats.misses += 1;
+            } else {
+                stats.hits += 1;
+            }
+        });
 
         node
     }
 
+    /// Lays out a view without children whose content is drawn in its `paint`, like text or an
+    /// image, where `size` is the size that the content is measured to have. The padding and
+    /// border of the view are added around it.
+    ///
+    /// `content` is the layout node of the content, which is created on the first call and
+    /// should be kept by the view for the next ones.
+    ///
+    /// ```
+    /// # use floem::context::LayoutCx;
+    /// # use floem::kurbo::Size;
+    /// # use floem::taffy::prelude::NodeId;
+    /// # use floem::{View, ViewId};
+    /// struct Swatch {
+    ///     id: ViewId,
+    ///     content: Option<NodeId>,
+    /// }
+    ///
+    /// impl View for Swatch {
+    ///     fn id(&self) -> ViewId {
+    ///         self.id
+    ///     }
+    ///
+    ///     fn layout(&mut self, cx: &mut LayoutCx) -> NodeId {
+    ///         cx.layout_measured(self.id, &mut self.content, Size::new(24.0, 24.0))
+    ///     }
+    /// }
+    /// ```
+    pub fn layout_measured(
+        &mut self,
+        id: ViewId,
+        content: &mut Option<NodeId>,
+        size: Size,
+    ) -> NodeId {
+        self.layout_node(id, true, |_| {
+            let taffy = id.taffy();
+            let mut taffy = taffy.borrow_mut();
+            let node = *content
+                .get_or_insert_with(|| taffy.new_leaf(taffy::style::Style::DEFAULT).unwrap());
+            let style = Style::new()
+                .width(size.width)
+                .height(size.height)
+                .to_taffy_style();
+            set_taffy_style(&mut taffy, node, style);
+            vec![node]
+        })
+    }
+
     /// Internal method used by Floem to invoke the user-defined `View::layout` method.
     pub fn layout_view(&mut self, view: &mut dyn View) -> NodeId {
+        let _span = profiler::view_span("layout", view.id(), || view.debug_name());
         view.layout(self)
     }
 }
 
+/// Sets the style of `node` unless it already has `style`, as setting it drops the cached layout
+/// of the node and its ancestors. Returns whether the style was set.
+fn set_taffy_style(taffy: &mut TaffyTree, node: NodeId, style: taffy::style::Style) -> bool {
+    if taffy.style(node).is_ok_and(|current| *current == style) {
+        return false;
+    }
+    let _ = taffy.set_style(node, style);
+    true
+}
+
 std::thread_local! {
     /// Holds the ID of a View being painted very briefly if it is being rendered as
     /// a moving drag image.  Since that is a relatively unusual thing to need, it
@@ -980,11 +1228,15 @@ impl PaintCx<'_> {
         false
     }
 
-    /// paint the children of this view
+    /// paint the children of this view, except for those that are completely covered by an
+    /// opaque sibling painted after them
     pub fn paint_children(&mut self, id: ViewId) {
         let children = id.children();
-        for child in children {
-            self.paint_view(child);
+        let occluded = occlusion::occluded(self, &children);
+        for (child, occluded) in children.into_iter().zip(occluded) {
+            if !occluded {
+                self.paint_view(child);
+            }
         }
     }
 
@@ -1000,6 +1252,7 @@ impl PaintCx<'_> {
         }
         let view = id.view();
         let view_state = id.state();
+        let _span = profiler::view_span("paint", id, || view.borrow().debug_name());
 
         self.save();
         let size = self.transform(id);
@@ -1016,11 +1269,21 @@ impl PaintCx<'_> {
                 self.set_z_index(z_index);
             }
 
-            paint_bg(self, &view_style_props, size);
-
-            view.borrow_mut().paint(self);
-            paint_border(self, &layout_props, &view_style_props, size);
-            paint_outline(self, &view_style_props, size)
+            let paint_content = |cx: &mut PaintCx| {
+                paint_bg(cx, &view_style_props, size);
+                cx.paint_hooks(id, size, false);
+                view.borrow_mut().paint(cx);
+                paint_border(cx, &layout_props, &view_style_props, size);
+            };
+            if view_state.borrow().layer.is_some() {
+                // the outline and the hooks painted after the content can reach out of the
+                // view, so they aren't cached
+                layer::paint(self, id, size, paint_content);
+            } else {
+                paint_content(self);
+            }
+            paint_outline(self, &view_style_props, size);
+            self.paint_hooks(id, size, true);
         }
         let mut drag_set_to_none = false;
 
@@ -1031,7 +1294,9 @@ impl PaintCx<'_> {
                 {
                     let easing = Linear;
                     const ANIMATION_DURATION_MS: f64 = 300.0;
-                    let elapsed = released_at.elapsed().as_millis() as f64;
+                    let elapsed = clock::now()
+                        .saturating_duration_since(released_at)
+                        .as_millis() as f64;
                     let progress = elapsed / ANIMATION_DURATION_MS;
 
                     if !(easing.finished(progress)) {
@@ -1072,7 +1337,7 @@ impl PaintCx<'_> {
                         view_style_props.read_explicit(
                             &style,
                             &style,
-                            &Instant::now(),
+                            &clock::now(),
                             &mut _new_frame,
                         );
                     }
@@ -1087,9 +1352,11 @@ impl PaintCx<'_> {
                     CURRENT_DRAG_PAINTING_ID.set(Some(id));
 
                     paint_bg(self, &view_style_props, size);
+                    self.paint_hooks(id, size, false);
                     view.borrow_mut().paint(self);
                     paint_border(self, &layout_props, &view_style_props, size);
                     paint_outline(self, &view_style_props, size);
+                    self.paint_hooks(id, size, true);
 
                     self.restore();
 
@@ -1104,6 +1371,24 @@ impl PaintCx<'_> {
         self.restore();
     }
 
+    /// Calls the paint hooks of the view that are painted before its content, or after it.
+    fn paint_hooks(&mut self, id: ViewId, size: Size, after: bool) {
+        let hooks = {
+            let view_state = id.state();
+            let view_state = view_state.borrow();
+            if after {
+                view_state.paint_after.clone()
+            } else {
+                view_state.paint_before.clone()
+            }
+        };
+        for hook in hooks {
+            self.save();
+            hook(self, size.to_rect());
+            self.restore();
+        }
+    }
+
     /// Clip the drawing area to the given shape.
     pub fn clip(&mut self, shape: &impl Shape) {
         let rect = if let Some(rect) = shape.as_rect() {
@@ -1147,6 +1432,18 @@ impl PaintCx<'_> {
         }
     }
 
+    /// Scales everything that is painted afterwards by `scale`, around the current origin.
+    pub(crate) fn scale(&mut self, scale: f64) {
+        self.transform *= Affine::scale(scale);
+        self.paint_state.renderer_mut().transform(self.transform);
+        if let Some(rect) = self.clip.as_mut() {
+            let radius = rect.radii();
+            *rect = Affine::scale(scale.recip())
+                .transform_rect_bbox(rect.rect())
+                .to_rounded_rect(radius);
+        }
+    }
+
     pub fn transform(&mut self, id: ViewId) -> Size {
         if let Some(layout) = id.get_layout() {
             let offset = layout.location;
@@ -1154,8 +1451,21 @@ impl PaintCx<'_> {
                 x: offset.x as f64,
                 y: offset.y as f64,
             });
+            let zoom_transform = id.state().borrow().zoom_transform;
+            self.transform *= zoom_transform;
             self.transform *= id.state().borrow().transform;
 
+            let layout_transform = id
+                .state()
+                .borrow_mut()
+                .layout_animation
+                .as_mut()
+                .and_then(|anim| anim.transform(clock::now()));
+            if let Some(layout_transform) = layout_transform {
+                self.transform *= layout_transform;
+                self.app_state.schedule_paint(id);
+            }
+
             self.paint_state.renderer_mut().transform(self.transform);
 
             if let Some(rect) = self.clip.as_mut() {
@@ -1164,6 +1474,12 @@ impl PaintCx<'_> {
                     .rect()
                     .with_origin(rect.origin() - Vec2::new(offset.x as f64, offset.y as f64))
                     .to_rounded_rect(raidus);
+                if zoom_transform != Affine::IDENTITY {
+                    *rect = zoom_transform
+                        .inverse()
+                        .transform_rect_bbox(rect.rect())
+                        .to_rounded_rect(raidus);
+                }
             }
 
             Size::new(layout.size.width as f64, layout.size.height as f64)
@@ -1201,6 +1517,13 @@ pub enum PaintState {
     Initialized {
         renderer: crate::renderer::Renderer<Arc<dyn wgpu::WindowHandle>>,
     },
+    /// The surface of the window doesn't exist, as the app is suspended on a mobile platform.
+    /// The GPU resources are requested again when the app is resumed.
+    ///
+    /// This field holds an instance of `Renderer::Uninitialized`, like the pending state.
+    Suspended {
+        renderer: crate::renderer::Renderer<Arc<dyn wgpu::WindowHandle>>,
+    },
 }
 
 impl PaintState {
@@ -1219,7 +1542,9 @@ impl PaintState {
         }
     }
 
-    pub(crate) fn init_renderer(&mut self) {
+    /// Initializes the renderer with the requested GPU resources, and returns whether it was
+    /// initialized.
+    pub(crate) fn init_renderer(&mut self) -> bool {
         if let PaintState::PendingGpuResources {
             window,
             rx,
@@ -1227,7 +1552,20 @@ impl PaintState {
             renderer,
         } = self
         {
-            let gpu_resources = rx.recv().unwrap().unwrap();
+            let gpu_resources = rx.recv().unwrap();
+            // the surface can't be created before the app is resumed on Android
+            #[cfg(target_os = "android")]
+            let Ok(gpu_resources) = gpu_resources
+            else {
+                let renderer = Renderer::Uninitialized {
+                    scale: renderer.scale(),
+                    size: renderer.size(),
+                };
+                *self = PaintState::Suspended { renderer };
+                return false;
+            };
+            #[cfg(not(target_os = "android"))]
+            let gpu_resources = gpu_resources.unwrap();
             let renderer = crate::renderer::Renderer::new(
                 window.clone(),
                 gpu_resources,
@@ -1236,15 +1574,33 @@ impl PaintState {
                 *font_embolden,
             );
             *self = PaintState::Initialized { renderer };
+            true
         } else {
             panic!("Called PaintState::init_renderer when it was already initialized");
         }
     }
 
+    /// Drops the renderer, along with the surface of the window, which is destroyed by the
+    /// system while the app is suspended.
+    #[cfg(target_os = "android")]
+    pub(crate) fn suspend(&mut self) {
+        if let PaintState::Initialized { renderer } = self {
+            let (scale, size) = (renderer.scale(), renderer.size());
+            *self = PaintState::Suspended {
+                renderer: Renderer::Uninitialized { scale, size },
+            };
+        }
+    }
+
+    pub(crate) fn is_suspended(&self) -> bool {
+        matches!(self, PaintState::Suspended { .. })
+    }
+
     pub(crate) fn renderer(&self) -> &crate::renderer::Renderer<Arc<dyn wgpu::WindowHandle>> {
         match self {
             PaintState::PendingGpuResources { renderer, .. } => renderer,
             PaintState::Initialized { renderer } => renderer,
+            PaintState::Suspended { renderer } => renderer,
         }
     }
 
@@ -1254,6 +1610,7 @@ impl PaintState {
         match self {
             PaintState::PendingGpuResources { renderer, .. } => renderer,
             PaintState::Initialized { renderer } => renderer,
+            PaintState::Suspended { renderer } => renderer,
         }
     }
 
@@ -1294,37 +1651,6 @@ impl DerefMut for PaintCx<'_> {
     }
 }
 
-fn animations_on_remove(id: ViewId, scope: Scope) -> u16 {
-    let mut wait_for = 0;
-    let state = id.state();
-    let mut state = state.borrow_mut();
-    state.num_waiting_animations = 0;
-    let animations = &mut state.animations.stack;
-    let mut request_style = false;
-    for anim in animations {
-        if anim.run_on_remove && !matches!(anim.repeat_mode, RepeatMode::LoopForever) {
-            anim.reverse_once.set(true);
-            anim.start_mut();
-            request_style = true;
-            wait_for += 1;
-            let trigger = anim.on_visual_complete;
-            scope.create_updater(
-                move || trigger.track(),
-                move |_| {
-                    id.transition_anim_complete();
-                },
-            );
-        }
-    }
-    drop(state);
-    if request_style {
-        id.request_style();
-    }
-
-    id.children()
-        .into_iter()
-        .fold(wait_for, |acc, id| acc + animations_on_remove(id, scope))
-}
 fn stop_reset_remove_animations(id: ViewId) {
     let state = id.state();
     let mut state = state.borrow_mut();
@@ -1349,24 +1675,3 @@ fn stop_reset_remove_animations(id: ViewId) {
         .into_iter()
         .for_each(stop_reset_remove_animations)
 }
-
-fn animations_on_create(id: ViewId) {
-    let state = id.state();
-    let mut state = state.borrow_mut();
-    state.num_waiting_animations = 0;
-    let animations = &mut state.animations.stack;
-    let mut request_style = false;
-    for anim in animations {
-        if anim.run_on_create && !matches!(anim.repeat_mode, RepeatMode::LoopForever) {
-            anim.reverse_once.set(false);
-            anim.start_mut();
-            request_style = true;
-        }
-    }
-    drop(state);
-    if request_style {
-        id.request_style();
-    }
-
-    id.children().into_iter().for_each(animations_on_create);
-}
@@ -0,0 +1,325 @@
+#![deny(missing_docs)]
+//! A snapshot of a view tree for tests and tools, see [`ViewId::debug_tree`].
+//!
+//! The snapshot has the type, layout, text and style properties of the views, which are
+//! formatted the same way on every run, so it can be compared to a snapshot saved by an earlier
+//! run. Its [`Display`](std::fmt::Display) format is an indented text, and it can be serialized
+//! with serde when floem is built with the `serde` feature.
+//!
+//! ```no_run
+//! # use floem::headless::HeadlessHarness;
+//! # use floem::views::{label, Decorators};
+//! let harness = HeadlessHarness::new(|| label(|| "Hello").style(|s| s.padding(10)));
+//! // prints `Label [x, y, width, height] "Hello"` followed by its padding properties
+//! println!("{}", harness.tree_snapshot());
+//! ```
+
+use std::{collections::BTreeMap, fmt};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{id::ViewId, style::StyleKeyInfo};
+
+/// A view in a snapshot of a view tree, with the views below it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DebugTree {
+    /// The type of the view without its module path, like `Label`.
+    pub kind: String,
+    /// The names added with [`Decorators::debug_name`](crate::views::Decorators::debug_name).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub names: Vec<String>,
+    /// The id added with [`Decorators::test_id`](crate::views::Decorators::test_id).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub test_id: Option<String>,
+    /// The style classes of the view without their module path.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub classes: Vec<String>,
+    /// The rectangle of the view in the window, unless layouts are left out of the snapshot.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub rect: Option<DebugRect>,
+    /// The text that the view shows, see [`View::text_content`](crate::view::View::text_content).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub text: Option<String>,
+    /// The style properties set on the view and by its classes, by their names.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub style: BTreeMap<String, String>,
+    /// The views below this view.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub children: Vec<DebugTree>,
+}
+
+/// The rectangle of a view in a [`DebugTree`], in logical pixels rounded to two decimals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DebugRect {
+    /// The horizontal position of the view in the window.
+    pub x: f64,
+    /// The vertical position of the view in the window.
+    pub y: f64,
+    /// The width of the view.
+    pub width: f64,
+    /// The height of the view.
+    pub height: f64,
+}
+
+/// What a [`DebugTree`] includes, see [`ViewId::debug_tree_with`].
+#[derive(Debug, Clone)]
+pub struct DebugTreeOptions {
+    layout: bool,
+    text: bool,
+    style: bool,
+    style_props: Option<Vec<String>>,
+    hidden: bool,
+    max_depth: Option<usize>,
+}
+
+impl Default for DebugTreeOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DebugTreeOptions {
+    /// Creates options that include the layout, text and style of the views, without the hidden
+    /// views.
+    pub fn new() -> Self {
+        Self {
+            layout: true,
+            text: true,
+            style: true,
+            style_props: None,
+            hidden: false,
+            max_depth: None,
+        }
+    }
+
+    /// Sets whether the rectangles of the views are included.
+    pub fn layout(mut self, layout: bool) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Sets whether the text of the views is included.
+    pub fn text(mut self, text: bool) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Sets whether the style properties of the views are included.
+    pub fn style(mut self, style: bool) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Includes only the style properties named `props`, like `Background` or `PaddingLeft`.
+    pub fn style_props<S: Into<String>>(mut self, props: impl IntoIterator<Item = S>) -> Self {
+        self.style_props = Some(props.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets whether the views hidden with `Display::None`, and the views below them, are
+    /// included.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Leaves out the views that are more than `max_depth` levels below the root of the snapshot.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+}
+
+pub(crate) fn debug_tree(id: ViewId, options: &DebugTreeOptions, depth: usize) -> DebugTree {
+    let view = id.view();
+    let view = view.borrow();
+    let state = id.state();
+    let state = state.borrow();
+
+    let classes = view
+        .view_class()
+        .into_iter()
+        .chain(state.classes.iter().copied())
+        .map(|class| short_name(&format!("{:?}", class.key)).to_string())
+        .collect();
+
+    let rect = options.layout.then(|| {
+        let rect = state.layout_rect;
+        DebugRect {
+            x: round(rect.x0),
+            y: round(rect.y0),
+            width: round(rect.width()),
+            height: round(rect.height()),
+        }
+    });
+
+    let mut style = BTreeMap::new();
+    if options.style {
+        for (key, value) in state.combined_style.map.iter() {
+            if !matches!(key.info, StyleKeyInfo::Prop(..)) {
+                continue;
+            }
+            let name = short_name(&format!("{key:?}")).to_string();
+            let included = match &options.style_props {
+                Some(props) => props.contains(&name),
+                None => true,
+            };
+            if included {
+                style.insert(name, key.debug_any(&**value));
+            }
+        }
+    }
+
+    let children = if options
+        .max_depth
+        .is_some_and(|max_depth| depth >= max_depth)
+    {
+        Vec::new()
+    } else {
+        id.children()
+            .into_iter()
+            .filter(|child| options.hidden || !child.style_has_hidden())
+            .map(|child| debug_tree(child, options, depth + 1))
+            .collect()
+    };
+
+    DebugTree {
+        kind: kind(&view.debug_name()).to_string(),
+        names: state.debug_name.to_vec(),
+        test_id: state.test_id.clone(),
+        classes,
+        rect,
+        text: options.text.then(|| view.text_content()).flatten(),
+        style,
+        children,
+    }
+}
+
+/// The type of a view from its debug name, which is either its type name or starts with it,
+/// like `Label: "Hello"`.
+pub(crate) fn kind(debug_name: &str) -> &str {
+    let name = debug_name.split(": ").next().unwrap_or(debug_name);
+    short_name(name)
+}
+
+/// Strips the module path and the generic parameters from a type name.
+fn short_name(type_name: &str) -> &str {
+    let name = type_name.split('<').next().unwrap_or(type_name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn round(value: f64) -> f64 {
+    let value = (value * 100.0).round() / 100.0;
+    // avoid `-0` in the output
+    value + 0.0
+}
+
+impl DebugTree {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        write!(f, "{:indent$}{}", "", self.kind)?;
+        for name in &self.names {
+            write!(f, " ({name})")?;
+        }
+        if let Some(test_id) = &self.test_id {
+            write!(f, " #{test_id}")?;
+        }
+        for class in &self.classes {
+            write!(f, " .{class}")?;
+        }
+        if let Some(rect) = &self.rect {
+            write!(
+                f,
+                " [{}, {}, {}, {}]",
+                rect.x, rect.y, rect.width, rect.height
+            )?;
+        }
+        if let Some(text) = &self.text {
+            write!(f, " {text:?}")?;
+        }
+        writeln!(f)?;
+        for (name, value) in &self.style {
+            writeln!(f, "{:indent$}  {name}: {value}", "")?;
+        }
+        for child in &self.children {
+            child.fmt_indented(f, indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DebugTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_strips_paths_and_details() {
+        assert_eq!(kind("floem::views::stack::Stack"), "Stack");
+        assert_eq!(
+            kind("floem::views::dyn_stack::DynStack<alloc::string::String>"),
+            "DynStack"
+        );
+        assert_eq!(kind("Label: \"a::b\""), "Label");
+    }
+
+    #[test]
+    fn display_is_indented() {
+        let tree = DebugTree {
+            kind: "Stack".to_string(),
+            names: Vec::new(),
+            test_id: Some("root".to_string()),
+            classes: Vec::new(),
+            rect: Some(DebugRect {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 20.5,
+            }),
+            text: None,
+            style: BTreeMap::new(),
+            children: vec![DebugTree {
+                kind: "Label".to_string(),
+                names: Vec::new(),
+                test_id: None,
+                classes: vec!["ButtonClass".to_string()],
+                rect: None,
+                text: Some("Hi".to_string()),
+                style: BTreeMap::from([("Background".to_string(), "Red".to_string())]),
+                children: Vec::new(),
+            }],
+        };
+        assert_eq!(
+            tree.to_string(),
+            "Stack #root [0, 0, 100, 20.5]\n  Label .ButtonClass \"Hi\"\n    Background: Red\n"
+        );
+    }
+}
@@ -0,0 +1,608 @@
+#![deny(missing_docs)]
+//! Native file dialogs and message boxes.
+//!
+//! The dialogs are native when floem is built with the `dialogs` feature, or with one of the
+//! `rfd-async-std` and `rfd-tokio` features which choose the async runtime used by the XDG
+//! portal on Linux. Otherwise every dialog is cancelled, and a message on stderr says why. The
+//! functions are the same either way, and [`dialogs_available`] tells at runtime whether the
+//! dialogs can be shown. Tests can answer the dialogs without showing them with
+//! [`set_headless_dialogs`].
+//!
+//! The dialogs run on a separate thread, and their results are passed to the callbacks on the
+//! main thread.
+//!
+//! [`confirm`], [`prompt`] and [`alert`] ask simple questions in a small modal window of their
+//! own, which is always available. They return an [`Answer`], which can be awaited, read as a
+//! signal or passed to a callback.
+
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use floem_reactive::{
+    create_effect, ReadSignal, RwSignal, Scope, SignalGet, SignalUpdate, SignalWith,
+};
+use floem_winit::keyboard::{Key, NamedKey};
+use parking_lot::Mutex;
+
+use crate::{
+    app_scope,
+    event::{Event, EventListener, EventPropagation},
+    ext_event::create_ext_action,
+    file::{FileDialogOptions, FileInfo},
+    view::View,
+    views::{button, empty, h_stack, static_label, text_input, v_stack, Decorators},
+    window::{close_window, new_window, WindowConfig},
+    window_handle::get_current_view,
+};
+
+static BACKEND: Mutex<Option<Arc<dyn DialogBackend>>> = Mutex::new(None);
+
+/// The icon of a message box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MessageLevel {
+    /// An information.
+    #[default]
+    Info,
+    /// A warning.
+    Warning,
+    /// An error.
+    Error,
+}
+
+/// The buttons of a message box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MessageButtons {
+    /// A single "OK" button.
+    #[default]
+    Ok,
+    /// "OK" and "Cancel" buttons.
+    OkCancel,
+    /// "Yes" and "No" buttons.
+    YesNo,
+    /// "Yes", "No" and "Cancel" buttons.
+    YesNoCancel,
+}
+
+/// The button that closed a message box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageResult {
+    /// The "OK" button.
+    Ok,
+    /// The "Cancel" button, or the message box was closed without a button.
+    Cancel,
+    /// The "Yes" button.
+    Yes,
+    /// The "No" button.
+    No,
+}
+
+/// The content of a message box, see [`message`].
+#[derive(Debug, Clone, Default)]
+pub struct MessageOptions {
+    pub(crate) title: String,
+    pub(crate) description: String,
+    pub(crate) level: MessageLevel,
+    pub(crate) buttons: MessageButtons,
+}
+
+impl MessageOptions {
+    /// Creates a message box with the description `description`.
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the title of the message box.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the icon of the message box.
+    pub fn level(mut self, level: MessageLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the buttons of the message box.
+    pub fn buttons(mut self, buttons: MessageButtons) -> Self {
+        self.buttons = buttons;
+        self
+    }
+}
+
+/// Shows the dialogs. The methods are called on a separate thread and block until the dialog
+/// is closed.
+pub(crate) trait DialogBackend: Send + Sync {
+    /// Whether the backend can show dialogs.
+    fn is_available(&self) -> bool;
+    /// Shows a dialog that picks files or directories, returning the picked paths or `None` if
+    /// the dialog was cancelled.
+    fn open(&self, options: &FileDialogOptions) -> Option<Vec<PathBuf>>;
+    /// Shows a dialog that picks the path to save a file to, returning `None` if the dialog
+    /// was cancelled.
+    fn save(&self, options: &FileDialogOptions) -> Option<PathBuf>;
+    /// Shows a message box, returning the button it was closed with.
+    fn message(&self, options: &MessageOptions) -> MessageResult;
+}
+
+/// Answers the dialogs without showing them, for tests and for environments without a
+/// display, see [`set_headless_dialogs`].
+///
+/// ```rust
+/// # use floem::dialogs::{set_headless_dialogs, HeadlessDialogs, MessageResult};
+/// set_headless_dialogs(
+///     HeadlessDialogs::new()
+///         .open_result(vec!["notes.txt".into()])
+///         .message_result(MessageResult::Yes),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeadlessDialogs {
+    open: Option<Vec<PathBuf>>,
+    save: Option<PathBuf>,
+    message: MessageResult,
+}
+
+impl Default for HeadlessDialogs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeadlessDialogs {
+    /// Creates a backend that cancels every dialog.
+    pub fn new() -> Self {
+        Self {
+            open: None,
+            save: None,
+            message: MessageResult::Cancel,
+        }
+    }
+
+    /// Sets the paths that the open dialogs pick.
+    pub fn open_result(mut self, paths: Vec<PathBuf>) -> Self {
+        self.open = Some(paths);
+        self
+    }
+
+    /// Sets the path that the save dialogs pick.
+    pub fn save_result(mut self, path: impl Into<PathBuf>) -> Self {
+        self.save = Some(path.into());
+        self
+    }
+
+    /// Sets the button that closes the message boxes.
+    pub fn message_result(mut self, result: MessageResult) -> Self {
+        self.message = result;
+        self
+    }
+}
+
+impl DialogBackend for HeadlessDialogs {
+    fn is_available(&self) -> bool {
+        false
+    }
+
+    fn open(&self, options: &FileDialogOptions) -> Option<Vec<PathBuf>> {
+        let mut paths = self.open.clone()?;
+        if !options.multi_selection {
+            paths.truncate(1);
+        }
+        Some(paths)
+    }
+
+    fn save(&self, _options: &FileDialogOptions) -> Option<PathBuf> {
+        self.save.clone()
+    }
+
+    fn message(&self, _options: &MessageOptions) -> MessageResult {
+        self.message
+    }
+}
+
+/// Answers the dialogs with `dialogs` instead of showing them.
+pub fn set_headless_dialogs(dialogs: HeadlessDialogs) {
+    *BACKEND.lock() = Some(Arc::new(dialogs));
+}
+
+/// Returns `true` if the dialogs can be shown, i.e. floem was built with native dialogs and
+/// they are supported in the current environment.
+pub fn dialogs_available() -> bool {
+    backend().is_available()
+}
+
+fn backend() -> Arc<dyn DialogBackend> {
+    BACKEND.lock().get_or_insert_with(default_backend).clone()
+}
+
+#[cfg(any(feature = "rfd-async-std", feature = "rfd-tokio"))]
+fn default_backend() -> Arc<dyn DialogBackend> {
+    Arc::new(native::NativeDialogs)
+}
+
+#[cfg(not(any(feature = "rfd-async-std", feature = "rfd-tokio")))]
+fn default_backend() -> Arc<dyn DialogBackend> {
+    Arc::new(unavailable::NoNativeDialogs)
+}
+
+/// Runs `dialog` on the backend on a separate thread, and passes its result to `action`.
+fn show<T: Send + 'static>(
+    dialog: impl FnOnce(&dyn DialogBackend) -> T + Send + 'static,
+    action: impl FnOnce(T) + 'static,
+) {
+    let send = create_ext_action(Scope::new(), action);
+    let backend = backend();
+    std::thread::spawn(move || send(dialog(backend.as_ref())));
+}
+
+/// Open a file using the system file dialog
+pub fn open_file(
+    options: FileDialogOptions,
+    file_info_action: impl Fn(Option<FileInfo>) + 'static,
+) {
+    show(
+        move |backend| backend.open(&options),
+        move |paths| {
+            file_info_action(paths.map(|path| FileInfo { path, format: None }));
+        },
+    );
+}
+
+/// Open a system file save dialog
+pub fn save_as(options: FileDialogOptions, file_info_action: impl Fn(Option<FileInfo>) + 'static) {
+    show(
+        move |backend| backend.save(&options),
+        move |path| {
+            file_info_action(path.map(|path| FileInfo {
+                path: vec![path],
+                format: None,
+            }));
+        },
+    );
+}
+
+/// Shows a message box, and calls `action` with the button it was closed with.
+///
+/// ```no_run
+/// # use floem::dialogs::{message, MessageButtons, MessageLevel, MessageOptions, MessageResult};
+/// message(
+///     MessageOptions::new("Discard the unsaved changes?")
+///         .level(MessageLevel::Warning)
+///         .buttons(MessageButtons::YesNo),
+///     |result| {
+///         if result == MessageResult::Yes {
+///             // discard
+///         }
+///     },
+/// );
+/// ```
+pub fn message(options: MessageOptions, action: impl FnOnce(MessageResult) + 'static) {
+    show(move |backend| backend.message(&options), action);
+}
+
+/// The answer to a dialog shown with [`confirm`], [`prompt`] or [`alert`].
+///
+/// It's a future that resolves on the main thread when the dialog is closed, and the answer can
+/// also be read from [`Answer::signal`] or passed to [`Answer::on_answer`].
+pub struct Answer<T: 'static> {
+    value: RwSignal<Option<T>>,
+    waker: Rc<RefCell<Option<Waker>>>,
+}
+
+impl<T> Clone for Answer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value,
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<T: Clone + 'static> Answer<T> {
+    fn new() -> Self {
+        Self {
+            // the answer has to outlive the scope of the dialog window
+            value: app_scope().create_rw_signal(None),
+            waker: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Sets the answer, unless the dialog was answered already.
+    fn answer(&self, value: T) {
+        if self.value.with_untracked(Option::is_some) {
+            return;
+        }
+        self.value.set(Some(value));
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+
+    /// A signal that is set to the answer when the dialog is closed.
+    pub fn signal(&self) -> ReadSignal<Option<T>> {
+        self.value.read_only()
+    }
+
+    /// Calls `action` with the answer when the dialog is closed.
+    pub fn on_answer(&self, action: impl FnOnce(T) + 'static) {
+        let value = self.value;
+        let action = Cell::new(Some(action));
+        create_effect(move |_| {
+            if let Some(answer) = value.get() {
+                if let Some(action) = action.take() {
+                    action(answer);
+                }
+            }
+        });
+    }
+}
+
+impl<T: Clone + 'static> Future for Answer<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        match self.value.get_untracked() {
+            Some(answer) => Poll::Ready(answer),
+            None => {
+                *self.waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Asks the user to confirm `message` with "OK" or "Cancel". The answer is `true` for "OK", and
+/// `false` when the dialog is cancelled or closed.
+///
+/// The dialog is a modal window of the current window, where `Enter` answers "OK" and `Escape`
+/// cancels.
+///
+/// ```no_run
+/// # use floem::dialogs::confirm;
+/// # async fn delete() {
+/// if confirm("Delete the selected files?").await {
+///     // delete
+/// }
+/// # }
+/// ```
+pub fn confirm(message: impl Into<String>) -> Answer<bool> {
+    ask(message.into(), None, true, |ok, _| ok)
+}
+
+/// Asks the user for a text, which is `default` at first. The answer is `None` when the dialog
+/// is cancelled or closed.
+///
+/// ```no_run
+/// # use floem::dialogs::prompt;
+/// # use floem::reactive::{RwSignal, SignalGet, SignalUpdate};
+/// let name = RwSignal::new("notes.txt".to_string());
+/// prompt("Rename the file to:", name.get_untracked()).on_answer(move |answer| {
+///     if let Some(new_name) = answer {
+///         name.set(new_name);
+///     }
+/// });
+/// ```
+pub fn prompt(message: impl Into<String>, default: impl Into<String>) -> Answer<Option<String>> {
+    ask(message.into(), Some(default.into()), true, |ok, text| {
+        ok.then_some(text)
+    })
+}
+
+/// Shows `message` with an "OK" button. The answer is there when the dialog is closed.
+pub fn alert(message: impl Into<String>) -> Answer<()> {
+    ask(message.into(), None, false, |_, _| ())
+}
+
+/// Shows a dialog with `message`, a text input if there is an `input`, and an "OK" button, and
+/// answers with `answer_with` called with whether it was closed with "OK" and the text.
+fn ask<T: Clone + 'static>(
+    message: String,
+    input: Option<String>,
+    cancellable: bool,
+    answer_with: impl Fn(bool, String) -> T + 'static,
+) -> Answer<T> {
+    let answer = Answer::new();
+    let parent = get_current_view().window_id();
+    let config = WindowConfig {
+        parent,
+        modal: parent.is_some(),
+        ..WindowConfig::default()
+            .size((360.0, if input.is_some() { 160.0 } else { 130.0 }))
+            .resizable(false)
+    };
+    let dialog_answer = answer.clone();
+    new_window(
+        move |window_id| {
+            let answer = dialog_answer;
+            let has_input = input.is_some();
+            let text = RwSignal::new(input.unwrap_or_default());
+            let finish =
+                Rc::new(move |ok: bool| answer.answer(answer_with(ok, text.get_untracked())));
+            let respond = {
+                let finish = finish.clone();
+                Rc::new(move |ok: bool| {
+                    finish(ok);
+                    close_window(window_id);
+                })
+            };
+
+            let input =
+                text_input(text).style(move |s| s.width_full().apply_if(!has_input, |s| s.hide()));
+            let ok = button("OK").action({
+                let respond = respond.clone();
+                move || respond(true)
+            });
+            let cancel = button("Cancel")
+                .action({
+                    let respond = respond.clone();
+                    move || respond(false)
+                })
+                .style(move |s| s.apply_if(!cancellable, |s| s.hide()));
+            // the focus starts in the text input, or on "OK"
+            if has_input {
+                input.id().request_focus();
+            } else {
+                ok.id().request_focus();
+            }
+
+            v_stack((
+                static_label(message),
+                input,
+                h_stack((empty().style(|s| s.flex_grow(1.0)), cancel, ok))
+                    .style(|s| s.width_full().gap(8)),
+            ))
+            .style(|s| s.size_full().padding(16).gap(12))
+            .on_event_capture(EventListener::KeyDown, move |event| match event {
+                Event::KeyDown(event) => match event.key.logical_key {
+                    Key::Named(NamedKey::Enter) => {
+                        respond(true);
+                        EventPropagation::Stop
+                    }
+                    Key::Named(NamedKey::Escape) => {
+                        respond(false);
+                        EventPropagation::Stop
+                    }
+                    _ => EventPropagation::Continue,
+                },
+                _ => EventPropagation::Continue,
+            })
+            // closing the window answers like "Cancel"
+            .on_event_cont(EventListener::WindowClosed, move |_| finish(false))
+        },
+        Some(config),
+    );
+    answer
+}
+
+#[cfg(any(feature = "rfd-async-std", feature = "rfd-tokio"))]
+mod native {
+    use std::path::PathBuf;
+
+    use super::{DialogBackend, MessageButtons, MessageLevel, MessageOptions, MessageResult};
+    use crate::file::FileDialogOptions;
+
+    pub(super) struct NativeDialogs;
+
+    impl DialogBackend for NativeDialogs {
+        fn is_available(&self) -> bool {
+            // the dialogs need a display server on Linux
+            if cfg!(any(target_os = "linux", target_os = "freebsd")) {
+                std::env::var_os("WAYLAND_DISPLAY").is_some()
+                    || std::env::var_os("DISPLAY").is_some()
+            } else {
+                true
+            }
+        }
+
+        fn open(&self, options: &FileDialogOptions) -> Option<Vec<PathBuf>> {
+            let mut dialog = rfd::FileDialog::new();
+            if let Some(path) = options.starting_directory.as_ref() {
+                dialog = dialog.set_directory(path);
+            }
+            if let Some(title) = options.title.as_ref() {
+                dialog = dialog.set_title(title);
+            }
+            if let Some(allowed_types) = options.allowed_types.as_ref() {
+                dialog = allowed_types.iter().fold(dialog, |dialog, filter| {
+                    dialog.add_filter(filter.name, filter.extensions)
+                });
+            }
+
+            match (options.select_directories, options.multi_selection) {
+                (true, true) => dialog.pick_folders(),
+                (true, false) => dialog.pick_folder().map(|path| vec![path]),
+                (false, true) => dialog.pick_files(),
+                (false, false) => dialog.pick_file().map(|path| vec![path]),
+            }
+        }
+
+        fn save(&self, options: &FileDialogOptions) -> Option<PathBuf> {
+            let mut dialog = rfd::FileDialog::new();
+            if let Some(path) = options.starting_directory.as_ref() {
+                dialog = dialog.set_directory(path);
+            }
+            if let Some(name) = options.default_name.as_ref() {
+                dialog = dialog.set_file_name(name);
+            }
+            if let Some(title) = options.title.as_ref() {
+                dialog = dialog.set_title(title);
+            }
+            dialog.save_file()
+        }
+
+        fn message(&self, options: &MessageOptions) -> MessageResult {
+            let level = match options.level {
+                MessageLevel::Info => rfd::MessageLevel::Info,
+                MessageLevel::Warning => rfd::MessageLevel::Warning,
+                MessageLevel::Error => rfd::MessageLevel::Error,
+            };
+            let buttons = match options.buttons {
+                MessageButtons::Ok => rfd::MessageButtons::Ok,
+                MessageButtons::OkCancel => rfd::MessageButtons::OkCancel,
+                MessageButtons::YesNo => rfd::MessageButtons::YesNo,
+                MessageButtons::YesNoCancel => rfd::MessageButtons::YesNoCancel,
+            };
+            let result = rfd::MessageDialog::new()
+                .set_title(&options.title)
+                .set_description(&options.description)
+                .set_level(level)
+                .set_buttons(buttons)
+                .show();
+            match result {
+                rfd::MessageDialogResult::Ok => MessageResult::Ok,
+                rfd::MessageDialogResult::Yes => MessageResult::Yes,
+                rfd::MessageDialogResult::No => MessageResult::No,
+                _ => MessageResult::Cancel,
+            }
+        }
+    }
+}
+
+#[cfg(not(any(feature = "rfd-async-std", feature = "rfd-tokio")))]
+mod unavailable {
+    use std::path::PathBuf;
+
+    use super::{DialogBackend, MessageOptions, MessageResult};
+    use crate::file::FileDialogOptions;
+
+    /// Cancels every dialog, as floem was built without native dialogs.
+    pub(super) struct NoNativeDialogs;
+
+    fn cancel(dialog: &str) {
+        eprintln!(
+            "{dialog} was cancelled, as floem was built without native dialogs. \
+            Enable the `dialogs` feature to show them."
+        );
+    }
+
+    impl DialogBackend for NoNativeDialogs {
+        fn is_available(&self) -> bool {
+            false
+        }
+
+        fn open(&self, _options: &FileDialogOptions) -> Option<Vec<PathBuf>> {
+            cancel("An open dialog");
+            None
+        }
+
+        fn save(&self, _options: &FileDialogOptions) -> Option<PathBuf> {
+            cancel("A save dialog");
+            None
+        }
+
+        fn message(&self, options: &MessageOptions) -> MessageResult {
+            cancel(&format!("The message box {:?}", options.description));
+            MessageResult::Cancel
+        }
+    }
+}
@@ -7,7 +7,9 @@ use peniko::kurbo::{Affine, Point, Size};
 use crate::{
     dropped_file::DroppedFileEvent,
     keyboard::KeyEvent,
-    pointer::{PointerInputEvent, PointerMoveEvent, PointerWheelEvent},
+    pointer::{
+        PointerDeltaEvent, PointerInputEvent, PointerMoveEvent, PointerWheelEvent, TouchEvent,
+    },
     touchpad::TouchpadMagnifyEvent,
 };
 
@@ -70,6 +72,8 @@ pub enum EventListener {
     PointerLeave,
     /// Receives [`Event::TouchpadMagnify`]
     TouchpadMagnify,
+    /// Receives [`Event::Touch`]
+    Touch,
     /// Receives [`Event::ImeEnabled`]
     ImeEnabled,
     /// Receives [`Event::ImeDisabled`]
@@ -100,6 +104,10 @@ pub enum EventListener {
     WindowMaximizeChanged,
     /// Receives [`Event::DroppedFile`]
     DroppedFile,
+    /// Receives [`Event::PointerDelta`]
+    PointerDelta,
+    /// Receives [`Event::PointerLockReleased`]
+    PointerLockReleased,
 }
 
 #[derive(Debug, Clone)]
@@ -110,6 +118,7 @@ pub enum Event {
     PointerWheel(PointerWheelEvent),
     PointerLeave,
     TouchpadMagnify(TouchpadMagnifyEvent),
+    Touch(TouchEvent),
     DroppedFile(DroppedFileEvent),
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
@@ -129,6 +138,11 @@ pub enum Event {
     ThemeChanged(Theme),
     FocusGained,
     FocusLost,
+    /// The pointer moved while the view has the pointer lock, see
+    /// [`ViewId::request_pointer_lock`](crate::id::ViewId::request_pointer_lock).
+    PointerDelta(PointerDeltaEvent),
+    /// The view lost the pointer lock.
+    PointerLockReleased,
 }
 
 impl Event {
@@ -140,6 +154,7 @@ impl Event {
             | Event::PointerWheel(_)
             | Event::PointerLeave
             | Event::TouchpadMagnify(..)
+            | Event::Touch(_)
             | Event::FocusGained
             | Event::FocusLost
             | Event::ImeEnabled
@@ -153,7 +168,9 @@ impl Event {
             | Event::WindowMaximizeChanged(_)
             | Event::WindowGotFocus
             | Event::WindowLostFocus
-            | Event::DroppedFile(_) => false,
+            | Event::DroppedFile(_)
+            | Event::PointerDelta(_)
+            | Event::PointerLockReleased => false,
             Event::KeyDown(_) | Event::KeyUp(_) => true,
         }
     }
@@ -166,6 +183,7 @@ impl Event {
             | Event::PointerWheel(_)
             | Event::PointerLeave => true,
             Event::TouchpadMagnify(_)
+            | Event::Touch(_)
             | Event::KeyDown(_)
             | Event::KeyUp(_)
             | Event::FocusGained
@@ -181,10 +199,16 @@ impl Event {
             | Event::WindowMaximizeChanged(_)
             | Event::WindowGotFocus
             | Event::WindowLostFocus
-            | Event::DroppedFile(_) => false,
+            | Event::DroppedFile(_)
+            | Event::PointerDelta(_)
+            | Event::PointerLockReleased => false,
         }
     }
 
+    pub(crate) fn is_touch(&self) -> bool {
+        matches!(self, Event::Touch(_))
+    }
+
     /// Enter, numpad enter and space cause a view to be activated with the keyboard
     pub(crate) fn is_keyboard_trigger(&self) -> bool {
         match self {
@@ -205,6 +229,7 @@ impl Event {
             Event::PointerDown(_)
             | Event::PointerUp(_)
             | Event::PointerWheel(_)
+            | Event::Touch(_)
             | Event::FocusGained
             | Event::FocusLost
             | Event::ImeEnabled
@@ -212,7 +237,8 @@ impl Event {
             | Event::ImePreedit { .. }
             | Event::ImeCommit(_)
             | Event::KeyDown(_)
-            | Event::KeyUp(_) => false,
+            | Event::KeyUp(_)
+            | Event::PointerDelta(_) => false,
             Event::TouchpadMagnify(_)
             | Event::PointerLeave
             | Event::PointerMove(_)
@@ -223,7 +249,8 @@ impl Event {
             | Event::WindowGotFocus
             | Event::WindowMaximizeChanged(_)
             | Event::WindowLostFocus
-            | Event::DroppedFile(_) => true,
+            | Event::DroppedFile(_)
+            | Event::PointerLockReleased => true,
         }
     }
 
@@ -234,6 +261,7 @@ impl Event {
             }
             Event::PointerMove(pointer_event) => Some(pointer_event.pos),
             Event::PointerWheel(pointer_event) => Some(pointer_event.pos),
+            Event::Touch(event) => Some(event.pos),
             Event::DroppedFile(event) => Some(event.pos),
             Event::TouchpadMagnify(_)
             | Event::PointerLeave
@@ -251,7 +279,9 @@ impl Event {
             | Event::WindowMoved(_)
             | Event::WindowMaximizeChanged(_)
             | Event::WindowGotFocus
-            | Event::WindowLostFocus => None,
+            | Event::WindowLostFocus
+            | Event::PointerDelta(_)
+            | Event::PointerLockReleased => None,
         }
     }
 
@@ -266,6 +296,9 @@ impl Event {
             Event::PointerWheel(pointer_event) => {
                 pointer_event.pos = transform.inverse() * pointer_event.pos;
             }
+            Event::Touch(event) => {
+                event.pos = transform.inverse() * event.pos;
+            }
             Event::DroppedFile(event) => {
                 event.pos = transform.inverse() * event.pos;
             }
@@ -285,7 +318,9 @@ impl Event {
             | Event::WindowMoved(_)
             | Event::WindowMaximizeChanged(_)
             | Event::WindowGotFocus
-            | Event::WindowLostFocus => {}
+            | Event::WindowLostFocus
+            | Event::PointerDelta(_)
+            | Event::PointerLockReleased => {}
         }
         self
     }
@@ -298,6 +333,7 @@ impl Event {
             Event::PointerWheel(_) => Some(EventListener::PointerWheel),
             Event::PointerLeave => Some(EventListener::PointerLeave),
             Event::TouchpadMagnify(_) => Some(EventListener::TouchpadMagnify),
+            Event::Touch(_) => Some(EventListener::Touch),
             Event::KeyDown(_) => Some(EventListener::KeyDown),
             Event::KeyUp(_) => Some(EventListener::KeyUp),
             Event::ImeEnabled => Some(EventListener::ImeEnabled),
@@ -313,6 +349,8 @@ impl Event {
             Event::FocusLost => Some(EventListener::FocusLost),
             Event::FocusGained => Some(EventListener::FocusGained),
             Event::ThemeChanged(_) => Some(EventListener::ThemeChanged),
+            Event::PointerDelta(_) => Some(EventListener::PointerDelta),
+            Event::PointerLockReleased => Some(EventListener::PointerLockReleased),
             Event::DroppedFile(_) => Some(EventListener::DroppedFile),
         }
     }
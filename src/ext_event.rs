@@ -53,6 +53,32 @@ pub fn create_trigger() -> ExtSendTrigger {
 unsafe impl Send for ExtSendTrigger {}
 unsafe impl Sync for ExtSendTrigger {}
 
+#[cfg(feature = "tokio")]
+static ASYNC_RUNTIME: Mutex<Option<tokio::runtime::Handle>> = Mutex::new(None);
+
+#[cfg(feature = "tokio")]
+pub(crate) fn set_async_runtime(handle: tokio::runtime::Handle) {
+    *ASYNC_RUNTIME.lock() = Some(handle);
+}
+
+/// The tokio runtime that futures are spawned on: the one set with
+/// [`Application::with_async_runtime`], or else the runtime of the current thread.
+///
+/// # Panics
+///
+/// Panics if no runtime was set and the current thread isn't inside one.
+#[cfg(feature = "tokio")]
+pub fn async_runtime() -> tokio::runtime::Handle {
+    ASYNC_RUNTIME
+        .lock()
+        .clone()
+        .or_else(|| tokio::runtime::Handle::try_current().ok())
+        .expect(
+            "there is no tokio runtime, set one with `Application::with_async_runtime` \
+             or call this from inside a runtime",
+        )
+}
+
 pub(crate) static EXT_EVENT_HANDLER: ExtEventHandler = ExtEventHandler::new();
 
 pub(crate) struct ExtEventHandler {
@@ -204,7 +230,7 @@ pub fn create_signal_from_tokio_channel<T: Send + 'static>(
     mut rx: tokio::sync::mpsc::UnboundedReceiver<T>,
 ) -> ReadSignal<Option<T>> {
     let cx = Scope::new();
-    let trigger = cx.create_trigger();
+    let trigger = with_scope(cx, ExtSendTrigger::new);
 
     let channel_closed = cx.create_rw_signal(false);
     let (read, write) = cx.create_signal(None);
@@ -228,7 +254,7 @@ pub fn create_signal_from_tokio_channel<T: Send + 'static>(
         channel_closed.set(true);
     });
 
-    tokio::spawn(async move {
+    async_runtime().spawn(async move {
         while let Some(event) = rx.recv().await {
             data.lock().unwrap().push_back(Some(event));
             crate::ext_event::register_ext_trigger(trigger);
@@ -239,6 +265,21 @@ pub fn create_signal_from_tokio_channel<T: Send + 'static>(
     read
 }
 
+/// Runs `future` on the [`async_runtime`], and returns a signal that is set to its output once
+/// it completes.
+#[cfg(feature = "tokio")]
+pub fn create_signal_from_tokio_future<T: Send + 'static>(
+    future: impl std::future::Future<Output = T> + Send + 'static,
+) -> ReadSignal<Option<T>> {
+    let cx = Scope::current();
+    let (read, write) = cx.create_signal(None);
+    let send = create_ext_action(cx, move |value| write.set(Some(value)));
+    async_runtime().spawn(async move {
+        send(future.await);
+    });
+    read
+}
+
 #[cfg(feature = "futures")]
 pub fn create_signal_from_stream<T: 'static>(
     initial_value: T,
@@ -0,0 +1,111 @@
+#![deny(missing_docs)]
+//! Adding fonts to the application and choosing between them.
+//!
+//! Fonts are added from their data with [`load_font_data`], or from files with
+//! [`load_font_file`] and [`load_fonts_dir`], and are then used by their family name in the
+//! `font_family` of a style. A family list like `"Inter, Noto Sans, sans-serif"` uses the first
+//! of the families that is installed, and [`set_fallback_chain`] names such a list, so styles
+//! can refer to it by that name.
+//!
+//! The defaults of a window are set with
+//! [`WindowConfig::default_font_family`](crate::window::WindowConfig::default_font_family) and
+//! [`WindowConfig::default_font_size`](crate::window::WindowConfig::default_font_size).
+//!
+//! Fonts are best added before the windows are created, as text that is already laid out isn't
+//! laid out again with them.
+//!
+//! ```
+//! # use floem::fonts::{load_font_data, set_fallback_chain};
+//! # use floem::views::{label, Decorators};
+//! # let font_data: Vec<u8> = Vec::new();
+//! load_font_data(font_data);
+//! set_fallback_chain("ui", "Inter, Segoe UI, Noto Sans, sans-serif");
+//! label(|| "Hello").style(|s| s.font_family("ui".to_string()));
+//! ```
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{io, path::Path};
+
+use floem_reactive::{ReadSignal, RwSignal, SignalUpdate};
+use floem_renderer::text::{self, FamilyOwned, FONT_SYSTEM};
+
+use crate::{app_scope, style::Style};
+
+thread_local! {
+    static FAMILIES: RwSignal<Vec<String>> =
+        app_scope().create_rw_signal(text::font_families().to_vec());
+}
+
+fn families_changed() {
+    text::font_families_changed();
+    FAMILIES.with(|families| families.set(text::font_families().to_vec()));
+}
+
+/// Adds the fonts of a font file or collection in memory, like the contents of a TTF or OTF
+/// file embedded with `include_bytes!`.
+pub fn load_font_data(data: Vec<u8>) {
+    FONT_SYSTEM.lock().db_mut().load_font_data(data);
+    families_changed();
+}
+
+/// Adds the fonts of a font file.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_font_file(path: impl AsRef<Path>) -> io::Result<()> {
+    FONT_SYSTEM.lock().db_mut().load_font_file(path)?;
+    families_changed();
+    Ok(())
+}
+
+/// Adds the fonts of the font files in a directory and its subdirectories. Files that aren't
+/// fonts are skipped.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_fonts_dir(path: impl AsRef<Path>) {
+    FONT_SYSTEM.lock().db_mut().load_fonts_dir(path);
+    families_changed();
+}
+
+/// Names a comma separated list of families, like `"Inter, Noto Sans, sans-serif"`, so a style
+/// with the name as its `font_family` uses the first of them that is installed.
+///
+/// Setting a chain with the same name again replaces it.
+pub fn set_fallback_chain(name: impl Into<String>, families: &str) {
+    let families = FamilyOwned::parse_list(families).collect();
+    text::set_fallback_chain(name, families);
+}
+
+/// The names of the font families that can be used, sorted by name, which is updated when fonts
+/// are added.
+pub fn families() -> ReadSignal<Vec<String>> {
+    FAMILIES.with(|families| families.read_only())
+}
+
+/// The style that sets the default font of a window.
+pub(crate) fn window_font_style(family: Option<String>, size: Option<f64>) -> Style {
+    let mut style = Style::new();
+    if let Some(family) = family {
+        style = style.font_family(family);
+    }
+    if let Some(size) = size {
+        style = style.font_size(size);
+    }
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback_chains_are_expanded_in_family_lists() {
+        set_fallback_chain("test-chain", "'Missing Font', monospace");
+        let families = FamilyOwned::parse_list("test-chain, serif").collect::<Vec<_>>();
+        assert_eq!(
+            families,
+            [
+                FamilyOwned::Name("Missing Font".to_string()),
+                FamilyOwned::Monospace,
+                FamilyOwned::Serif,
+            ]
+        );
+    }
+}
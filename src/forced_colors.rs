@@ -0,0 +1,235 @@
+#![deny(missing_docs)]
+//! The forced colors mode, in which the colors of the views are replaced by a small palette of
+//! system colors chosen by the user, like the high contrast themes of Windows.
+//!
+//! The mode follows the preference of the system, see
+//! [`prefers_forced_colors`](crate::accessibility::prefers_forced_colors), unless it's
+//! overridden with [`set_forced_colors`]. While it's active:
+//!
+//! - the color tokens of the theme are mapped to the [`SystemColors`],
+//! - the text, background, border and outline colors of views are mapped to the system colors,
+//! - selected views are highlighted and focused views are outlined, as the subtle backgrounds
+//!   that usually show these states are lost in the mapping.
+//!
+//! Views that show colors that carry meaning, like images or gradients, declare their own
+//! fallback for the mode with [`Style::forced_colors`], which opts them out of the mapping.
+//!
+//! ```rust
+//! # use floem::peniko::Color;
+//! # use floem::style::Style;
+//! let swatch = Style::new()
+//!     .background(Color::rgb8(255, 120, 0))
+//!     .forced_colors(|s| s.border(2.0).border_color(Color::WHITE));
+//! ```
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+use peniko::Color;
+
+use crate::{
+    accessibility, app_scope,
+    context::InteractionState,
+    prop,
+    style::{Background, BorderColor, OutlineColor, Style, TextColor},
+    theme, window,
+};
+
+prop!(
+    /// Whether the colors of the view are mapped to the system colors in the forced colors
+    /// mode, like `forced-color-adjust` in CSS.
+    pub ForcedColorAdjust: bool {} = true
+);
+
+/// The palette of the forced colors mode, named after the CSS system colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemColors {
+    /// The background of the window and of views.
+    pub canvas: Color,
+    /// The text and borders on the [`canvas`](Self::canvas).
+    pub canvas_text: Color,
+    /// The background of selected views, and the outline of focused views.
+    pub highlight: Color,
+    /// The text on the [`highlight`](Self::highlight).
+    pub highlight_text: Color,
+    /// The text and borders of disabled views.
+    pub gray_text: Color,
+}
+
+impl SystemColors {
+    /// White text on black, like the "High Contrast Black" theme of Windows.
+    pub const DARK: Self = Self {
+        canvas: Color::BLACK,
+        canvas_text: Color::WHITE,
+        highlight: Color::rgb8(26, 235, 255),
+        highlight_text: Color::BLACK,
+        gray_text: Color::rgb8(63, 242, 63),
+    };
+
+    /// Black text on white, like the "High Contrast White" theme of Windows.
+    pub const LIGHT: Self = Self {
+        canvas: Color::WHITE,
+        canvas_text: Color::BLACK,
+        highlight: Color::rgb8(55, 0, 110),
+        highlight_text: Color::WHITE,
+        gray_text: Color::rgb8(96, 0, 0),
+    };
+}
+
+thread_local! {
+    static FORCED_COLORS: RwSignal<Option<bool>> = app_scope().create_rw_signal(None);
+}
+
+/// Whether the forced colors mode is active, which is tracked when called in a reactive context.
+pub fn forced_colors() -> bool {
+    FORCED_COLORS
+        .with(|forced| forced.get())
+        .unwrap_or_else(|| accessibility::preferences().forced_colors)
+}
+
+/// Turns the forced colors mode on or off regardless of the preference of the system, or makes
+/// it follow the system again with `None`.
+pub fn set_forced_colors(forced: Option<bool>) {
+    FORCED_COLORS.with(|signal| {
+        if signal.get_untracked() != forced {
+            signal.set(forced);
+        }
+    });
+}
+
+pub(crate) fn forced_colors_untracked() -> bool {
+    FORCED_COLORS
+        .with(|forced| forced.get_untracked())
+        .unwrap_or_else(|| accessibility::preferences_untracked().forced_colors)
+}
+
+/// The colors of the forced colors mode chosen in the settings of the system, or a palette that
+/// matches the light or dark color scheme of the system.
+pub fn system_colors() -> SystemColors {
+    system::colors().unwrap_or_else(|| match theme::system_color_scheme().get_untracked() {
+        Some(window::Theme::Dark) => SystemColors::DARK,
+        _ => SystemColors::LIGHT,
+    })
+}
+
+/// Maps the colors of the computed `style` of a view to `colors`.
+pub(crate) fn adjust(style: Style, interact: &InteractionState, colors: &SystemColors) -> Style {
+    if !style.get(ForcedColorAdjust) {
+        return style;
+    }
+    let mut style = style;
+    let selected = interact.is_selected && !interact.is_disabled;
+    if interact.is_disabled {
+        style = style.color(colors.gray_text);
+    } else if selected {
+        style = style.color(colors.highlight_text);
+    } else if style.get_prop::<TextColor>().flatten().is_some() {
+        style = style.color(colors.canvas_text);
+    }
+    if selected {
+        style = style.background(colors.highlight);
+    } else if style.get_prop::<Background>().flatten().is_some() {
+        style = style.background(colors.canvas);
+    }
+    if style.get_prop::<BorderColor>().is_some() {
+        let border = if interact.is_disabled {
+            colors.gray_text
+        } else {
+            colors.canvas_text
+        };
+        style = style.border_color(border);
+    }
+    if style.get_prop::<OutlineColor>().is_some() {
+        style = style.outline_color(colors.highlight);
+    }
+    if interact.is_focused {
+        style = style.outline(2.0).outline_color(colors.highlight);
+    }
+    style
+}
+
+#[cfg(target_os = "windows")]
+mod system {
+    use peniko::Color;
+    use windows_sys::Win32::Graphics::Gdi::{
+        GetSysColor, COLOR_GRAYTEXT, COLOR_HIGHLIGHT, COLOR_HIGHLIGHTTEXT, COLOR_WINDOW,
+        COLOR_WINDOWTEXT, SYS_COLOR_INDEX,
+    };
+
+    use super::SystemColors;
+
+    fn color(index: SYS_COLOR_INDEX) -> Color {
+        // a COLORREF is 0x00BBGGRR
+        let [r, g, b, _] = unsafe { GetSysColor(index) }.to_le_bytes();
+        Color::rgb8(r, g, b)
+    }
+
+    pub(super) fn colors() -> Option<SystemColors> {
+        Some(SystemColors {
+            canvas: color(COLOR_WINDOW),
+            canvas_text: color(COLOR_WINDOWTEXT),
+            highlight: color(COLOR_HIGHLIGHT),
+            highlight_text: color(COLOR_HIGHLIGHTTEXT),
+            gray_text: color(COLOR_GRAYTEXT),
+        })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod system {
+    use super::SystemColors;
+
+    pub(super) fn colors() -> Option<SystemColors> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::Color;
+
+    use super::{adjust, SystemColors};
+    use crate::{
+        context::InteractionState,
+        style::{Background, BorderColor, Style, TextColor},
+    };
+
+    const COLORS: SystemColors = SystemColors::DARK;
+
+    #[test]
+    fn colors_are_mapped() {
+        let style = Style::new()
+            .color(Color::rgb8(40, 40, 40))
+            .background(Color::rgb8(240, 240, 240))
+            .border_color(Color::GRAY);
+        let style = adjust(style, &InteractionState::default(), &COLORS);
+        assert_eq!(style.get(TextColor), Some(COLORS.canvas_text));
+        assert_eq!(style.get(Background), Some(COLORS.canvas.into()));
+        assert_eq!(style.get(BorderColor), COLORS.canvas_text.into());
+    }
+
+    #[test]
+    fn selected_views_are_highlighted() {
+        let interact = InteractionState {
+            is_selected: true,
+            ..Default::default()
+        };
+        let style = adjust(Style::new(), &interact, &COLORS);
+        assert_eq!(style.get(Background), Some(COLORS.highlight.into()));
+        assert_eq!(style.get(TextColor), Some(COLORS.highlight_text));
+    }
+
+    #[test]
+    fn unset_colors_are_inherited() {
+        let style = adjust(Style::new(), &InteractionState::default(), &COLORS);
+        assert_eq!(style.get(TextColor), None);
+        assert_eq!(style.get(Background), None);
+    }
+
+    #[test]
+    fn fallbacks_opt_out() {
+        let style = Style::new()
+            .background(Color::RED)
+            .forced_color_adjust(false);
+        let style = adjust(style, &InteractionState::default(), &COLORS);
+        assert_eq!(style.get(Background), Some(Color::RED.into()));
+    }
+}
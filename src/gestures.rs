@@ -0,0 +1,661 @@
+//! Gesture recognizers that turn pointer events into long presses, swipes and pans.
+//!
+//! Recognizers are attached to a view with the [`Decorators`](crate::views::Decorators) methods
+//! [`on_long_press`](crate::views::Decorators::on_long_press),
+//! [`on_swipe`](crate::views::Decorators::on_swipe),
+//! [`on_pan`](crate::views::Decorators::on_pan) or, for a custom [`GestureRecognizer`],
+//! [`on_gesture`](crate::views::Decorators::on_gesture).
+//!
+//! All the recognizers of a view watch the same press of the pointer, and the first one that
+//! claims it wins: the others are cancelled for the rest of the press. Recognizers of a child
+//! view get to claim a press before their parents, including a parent [`Scroll`](crate::views::Scroll)
+//! that drags its content, so e.g. a horizontal swipe can be recognized inside a vertical scroll view.
+
+use std::{cell::Cell, cell::RefCell, collections::VecDeque, rc::Rc};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use peniko::kurbo::{Point, Vec2};
+
+use crate::{
+    action::{exec_after, TimerToken},
+    clock,
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+};
+
+/// How far the pointer has to move before a press can be claimed as a drag.
+///
+/// This is shared with the kinetic scrolling of [`Scroll`](crate::views::Scroll) so that
+/// recognizers of its content get to claim a drag first.
+pub(crate) const DRAG_SLOP: f64 = 5.0;
+
+/// The pointer positions from this window before the latest one are used to compute the velocity.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+thread_local! {
+    /// The view that claimed the current press of the pointer, if any.
+    static POINTER_OWNER: Cell<Option<ViewId>> = const { Cell::new(None) };
+}
+
+/// Marks `id` as the owner of the current press of the pointer.
+pub(crate) fn claim_pointer(id: ViewId) {
+    POINTER_OWNER.with(|owner| owner.set(Some(id)));
+}
+
+/// Returns true if the current press of the pointer was claimed by a view other than `id`.
+pub(crate) fn pointer_claimed_by_other(id: ViewId) -> bool {
+    POINTER_OWNER.with(|owner| owner.get().is_some_and(|owner| owner != id))
+}
+
+/// Releases the claim on the pointer when a new press starts.
+pub(crate) fn reset_pointer_owner() {
+    POINTER_OWNER.with(|owner| owner.set(None));
+}
+
+/// The path of the pointer during a press, relative to the view.
+#[derive(Debug, Clone)]
+pub struct PointerTrack {
+    /// Where the pointer was pressed.
+    pub start: Point,
+    /// Where the pointer is now.
+    pub pos: Point,
+    samples: VecDeque<(Instant, Point)>,
+}
+
+impl PointerTrack {
+    fn new(pos: Point) -> Self {
+        let mut samples = VecDeque::new();
+        samples.push_back((clock::now(), pos));
+        Self {
+            start: pos,
+            pos,
+            samples,
+        }
+    }
+
+    fn push(&mut self, pos: Point) {
+        let now = clock::now();
+        self.pos = pos;
+        self.samples.push_back((now, pos));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(time, _)| now.duration_since(*time) > VELOCITY_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// How far the pointer has moved since it was pressed.
+    pub fn translation(&self) -> Vec2 {
+        self.pos - self.start
+    }
+
+    /// The recent velocity of the pointer in pixels per second.
+    pub fn velocity(&self) -> Vec2 {
+        let (Some((start, from)), Some((end, to))) = (self.samples.front(), self.samples.back())
+        else {
+            return Vec2::ZERO;
+        };
+        let dt = end.duration_since(*start).as_secs_f64();
+        if dt <= 0. {
+            return Vec2::ZERO;
+        }
+        (*to - *from) / dt
+    }
+}
+
+/// What a [`GestureRecognizer`] makes of the press of the pointer so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recognition {
+    /// The press could still become the gesture.
+    Possible,
+    /// The press is the gesture. The recognizer takes the pointer for the rest of the press,
+    /// cancelling the other recognizers of the view and of its parents.
+    Claim,
+    /// The press isn't the gesture. The recognizer gets no more events until the next press.
+    Fail,
+}
+
+/// Recognizes a gesture from the press of the pointer on a view.
+///
+/// Until a recognizer claims the press, it is only watching; once it returns
+/// [`Recognition::Claim`] it receives the rest of the press with `claimed` set.
+pub trait GestureRecognizer {
+    /// The pointer was pressed on the view.
+    fn pointer_down(&mut self, _pointer: &PointerTrack) -> Recognition {
+        Recognition::Possible
+    }
+
+    /// The pointer moved while pressed.
+    fn pointer_move(&mut self, pointer: &PointerTrack, claimed: bool) -> Recognition;
+
+    /// The pointer was released, ending the press.
+    fn pointer_up(&mut self, pointer: &PointerTrack, claimed: bool);
+
+    /// Another recognizer claimed the press, or the press ended before this one was done.
+    fn cancel(&mut self) {}
+
+    /// If set, [`timeout`](Self::timeout) is called this long after the pointer was pressed.
+    fn timeout_after(&self) -> Option<Duration> {
+        None
+    }
+
+    /// The press lasted for [`timeout_after`](Self::timeout_after).
+    fn timeout(&mut self, _pointer: &PointerTrack) -> Recognition {
+        Recognition::Possible
+    }
+}
+
+/// The phase of a continuous gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+    /// The gesture was recognized.
+    Began,
+    /// The pointer moved during the gesture.
+    Changed,
+    /// The pointer was released.
+    Ended,
+    /// The gesture was interrupted.
+    Cancelled,
+}
+
+/// A pan of the pointer over a view. See [`Decorators::on_pan`](crate::views::Decorators::on_pan).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PanGesture {
+    /// The phase of the pan.
+    pub phase: GesturePhase,
+    /// The position of the pointer relative to the view.
+    pub pos: Point,
+    /// How far the pointer has moved since it was pressed.
+    pub translation: Vec2,
+    /// The velocity of the pointer in pixels per second.
+    pub velocity: Vec2,
+}
+
+/// The direction of a swipe. See [`Decorators::on_swipe`](crate::views::Decorators::on_swipe).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    /// A swipe towards the left.
+    Left,
+    /// A swipe towards the right.
+    Right,
+    /// A swipe upwards.
+    Up,
+    /// A swipe downwards.
+    Down,
+}
+
+impl SwipeDirection {
+    /// Splits `v` into the distance along the direction and across it.
+    fn split(self, v: Vec2) -> (f64, f64) {
+        match self {
+            SwipeDirection::Left => (-v.x, v.y.abs()),
+            SwipeDirection::Right => (v.x, v.y.abs()),
+            SwipeDirection::Up => (-v.y, v.x.abs()),
+            SwipeDirection::Down => (v.y, v.x.abs()),
+        }
+    }
+}
+
+/// Fires when the pointer is held down without moving for `duration`.
+pub(crate) struct LongPress {
+    pub(crate) duration: Duration,
+    pub(crate) action: Box<dyn Fn(Point)>,
+}
+
+impl GestureRecognizer for LongPress {
+    fn pointer_move(&mut self, pointer: &PointerTrack, claimed: bool) -> Recognition {
+        if !claimed && pointer.translation().hypot() > DRAG_SLOP {
+            Recognition::Fail
+        } else {
+            Recognition::Possible
+        }
+    }
+
+    fn pointer_up(&mut self, _pointer: &PointerTrack, _claimed: bool) {}
+
+    fn timeout_after(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+
+    fn timeout(&mut self, pointer: &PointerTrack) -> Recognition {
+        (self.action)(pointer.pos);
+        Recognition::Claim
+    }
+}
+
+/// Fires when the pointer is dragged at least `threshold` pixels in `direction` and released.
+pub(crate) struct Swipe {
+    pub(crate) direction: SwipeDirection,
+    pub(crate) threshold: f64,
+    pub(crate) action: Box<dyn Fn(Vec2)>,
+}
+
+impl GestureRecognizer for Swipe {
+    fn pointer_move(&mut self, pointer: &PointerTrack, claimed: bool) -> Recognition {
+        if claimed {
+            return Recognition::Possible;
+        }
+        let (along, across) = self.direction.split(pointer.translation());
+        if across > DRAG_SLOP && across > along.abs() {
+            // moving across the direction, or back
+            Recognition::Fail
+        } else if along > DRAG_SLOP && along > across {
+            Recognition::Claim
+        } else if -along > DRAG_SLOP {
+            Recognition::Fail
+        } else {
+            Recognition::Possible
+        }
+    }
+
+    fn pointer_up(&mut self, pointer: &PointerTrack, claimed: bool) {
+        let (along, _) = self.direction.split(pointer.translation());
+        if claimed && along >= self.threshold {
+            (self.action)(pointer.velocity());
+        }
+    }
+}
+
+/// Follows the pointer once it is dragged past [`DRAG_SLOP`].
+pub(crate) struct Pan {
+    pub(crate) action: Box<dyn Fn(&PanGesture)>,
+    pub(crate) last: Option<PanGesture>,
+}
+
+impl Pan {
+    fn fire(&mut self, phase: GesturePhase, pointer: &PointerTrack) {
+        let pan = PanGesture {
+            phase,
+            pos: pointer.pos,
+            translation: pointer.translation(),
+            velocity: pointer.velocity(),
+        };
+        self.last = Some(pan);
+        (self.action)(&pan);
+    }
+}
+
+impl GestureRecognizer for Pan {
+    fn pointer_down(&mut self, _pointer: &PointerTrack) -> Recognition {
+        self.last = None;
+        Recognition::Possible
+    }
+
+    fn pointer_move(&mut self, pointer: &PointerTrack, claimed: bool) -> Recognition {
+        if claimed {
+            self.fire(GesturePhase::Changed, pointer);
+            Recognition::Possible
+        } else if pointer.translation().hypot() > DRAG_SLOP {
+            self.fire(GesturePhase::Began, pointer);
+            Recognition::Claim
+        } else {
+            Recognition::Possible
+        }
+    }
+
+    fn pointer_up(&mut self, pointer: &PointerTrack, claimed: bool) {
+        if claimed {
+            self.fire(GesturePhase::Ended, pointer);
+        }
+    }
+
+    fn cancel(&mut self) {
+        if let Some(mut pan) = self.last.take() {
+            pan.phase = GesturePhase::Cancelled;
+            (self.action)(&pan);
+        }
+    }
+}
+
+/// The recognizers of a view, competing for the presses of the pointer on it.
+pub(crate) struct GestureArena {
+    id: ViewId,
+    recognizers: Vec<Box<dyn GestureRecognizer>>,
+    /// Which recognizers are still watching the current press.
+    possible: Vec<bool>,
+    pointer: Option<PointerTrack>,
+    /// The recognizer that claimed the current press.
+    owner: Option<usize>,
+    timers: Vec<TimerToken>,
+}
+
+impl GestureArena {
+    /// Adds `recognizer` to the arena of `id`, creating the arena on first use.
+    pub(crate) fn add(id: ViewId, recognizer: Box<dyn GestureRecognizer>) {
+        let existing = id.state().borrow().gestures.clone();
+        if let Some(arena) = existing {
+            arena.borrow_mut().recognizers.push(recognizer);
+            return;
+        }
+
+        let arena = Rc::new(RefCell::new(GestureArena {
+            id,
+            recognizers: vec![recognizer],
+            possible: Vec::new(),
+            pointer: None,
+            owner: None,
+            timers: Vec::new(),
+        }));
+        id.state().borrow_mut().gestures = Some(arena.clone());
+
+        let handler = arena.clone();
+        id.add_event_listener(
+            EventListener::PointerDown,
+            Box::new(move |event| {
+                if let Event::PointerDown(event) = event {
+                    if event.button.is_primary() {
+                        GestureArena::pointer_down(&handler, event.pos);
+                    }
+                }
+                EventPropagation::Continue
+            }),
+        );
+        let handler = arena.clone();
+        id.add_event_listener(
+            EventListener::PointerMove,
+            Box::new(move |event| match event {
+                Event::PointerMove(event) => handler.borrow_mut().pointer_move(event.pos),
+                _ => EventPropagation::Continue,
+            }),
+        );
+        id.add_event_listener(
+            EventListener::PointerUp,
+            Box::new(move |event| match event {
+                Event::PointerUp(event) if event.button.is_primary() => {
+                    arena.borrow_mut().pointer_up(event.pos)
+                }
+                _ => EventPropagation::Continue,
+            }),
+        );
+    }
+
+    fn pointer_down(arena: &Rc<RefCell<GestureArena>>, pos: Point) {
+        let mut this = arena.borrow_mut();
+        this.cancel_all();
+        let pointer = PointerTrack::new(pos);
+        this.owner = None;
+        this.possible = vec![true; this.recognizers.len()];
+        for i in 0..this.recognizers.len() {
+            let recognition = this.recognizers[i].pointer_down(&pointer);
+            this.recognized(i, recognition);
+            if let Some(duration) = this.recognizers[i].timeout_after() {
+                let weak = Rc::downgrade(arena);
+                let token = exec_after(duration, move |token| {
+                    if let Some(arena) = weak.upgrade() {
+                        arena.borrow_mut().timeout(i, token);
+                    }
+                });
+                this.timers.push(token);
+            }
+        }
+        this.pointer = Some(pointer);
+    }
+
+    fn pointer_move(&mut self, pos: Point) -> EventPropagation {
+        let Some(mut pointer) = self.pointer.take() else {
+            return EventPropagation::Continue;
+        };
+        if pointer_claimed_by_other(self.id) {
+            self.cancel_all();
+            return EventPropagation::Continue;
+        }
+        if pos == pointer.pos {
+            // the listeners of a view can see the same move more than once
+            let propagation = if self.owner.is_some() {
+                EventPropagation::Stop
+            } else {
+                EventPropagation::Continue
+            };
+            self.pointer = Some(pointer);
+            return propagation;
+        }
+        pointer.push(pos);
+        let propagation = if let Some(owner) = self.owner {
+            self.recognizers[owner].pointer_move(&pointer, true);
+            EventPropagation::Stop
+        } else {
+            for i in 0..self.recognizers.len() {
+                if self.possible[i] {
+                    let recognition = self.recognizers[i].pointer_move(&pointer, false);
+                    self.recognized(i, recognition);
+                }
+            }
+            if self.owner.is_some() {
+                EventPropagation::Stop
+            } else {
+                EventPropagation::Continue
+            }
+        };
+        self.pointer = Some(pointer);
+        propagation
+    }
+
+    fn pointer_up(&mut self, pos: Point) -> EventPropagation {
+        let Some(mut pointer) = self.pointer.take() else {
+            return EventPropagation::Continue;
+        };
+        self.cancel_timers();
+        if pointer_claimed_by_other(self.id) {
+            self.cancel_all();
+            return EventPropagation::Continue;
+        }
+        pointer.push(pos);
+        if let Some(owner) = self.owner.take() {
+            self.recognizers[owner].pointer_up(&pointer, true);
+            self.id.clear_active();
+            return EventPropagation::Stop;
+        }
+        for i in 0..self.recognizers.len() {
+            if self.possible[i] {
+                self.recognizers[i].pointer_up(&pointer, false);
+            }
+        }
+        EventPropagation::Continue
+    }
+
+    fn timeout(&mut self, index: usize, token: TimerToken) {
+        if !self.timers.contains(&token) || self.owner.is_some() || !self.possible[index] {
+            return;
+        }
+        let Some(pointer) = self.pointer.take() else {
+            return;
+        };
+        if pointer_claimed_by_other(self.id) {
+            self.cancel_all();
+            return;
+        }
+        let recognition = self.recognizers[index].timeout(&pointer);
+        self.recognized(index, recognition);
+        self.pointer = Some(pointer);
+    }
+
+    fn recognized(&mut self, index: usize, recognition: Recognition) {
+        match recognition {
+            Recognition::Possible => {}
+            Recognition::Fail => self.possible[index] = false,
+            Recognition::Claim => {
+                self.owner = Some(index);
+                for (i, recognizer) in self.recognizers.iter_mut().enumerate() {
+                    if i != index && self.possible[i] {
+                        self.possible[i] = false;
+                        recognizer.cancel();
+                    }
+                }
+                claim_pointer(self.id);
+                // keep receiving the press when the pointer leaves the view
+                self.id.request_active();
+            }
+        }
+    }
+
+    fn cancel_timers(&mut self) {
+        for token in self.timers.drain(..) {
+            token.cancel();
+        }
+    }
+
+    fn cancel_all(&mut self) {
+        self.cancel_timers();
+        for (i, recognizer) in self.recognizers.iter_mut().enumerate() {
+            if self.possible.get(i).copied().unwrap_or(false) || self.owner == Some(i) {
+                recognizer.cancel();
+            }
+        }
+        self.possible
+            .iter_mut()
+            .for_each(|possible| *possible = false);
+        self.owner = None;
+        self.pointer = None;
+    }
+}
+
+/// Reports that the pointer settled on or off a view, see
+/// [`on_hover_intent`](crate::views::Decorators::on_hover_intent).
+pub(crate) struct HoverIntent {
+    enter_delay: Duration,
+    exit_delay: Duration,
+    action: Rc<dyn Fn(bool)>,
+    /// Whether the pointer is currently over the view.
+    inside: bool,
+    /// What the action was last called with.
+    hovered: bool,
+    timer: Option<TimerToken>,
+}
+
+impl HoverIntent {
+    pub(crate) fn add(
+        id: ViewId,
+        enter_delay: Duration,
+        exit_delay: Duration,
+        action: Rc<dyn Fn(bool)>,
+    ) {
+        let intent = Rc::new(RefCell::new(HoverIntent {
+            enter_delay,
+            exit_delay,
+            action,
+            inside: false,
+            hovered: false,
+            timer: None,
+        }));
+
+        let handler = intent.clone();
+        id.add_event_listener(
+            EventListener::PointerMove,
+            Box::new(move |_| {
+                HoverIntent::set_inside(&handler, true);
+                EventPropagation::Continue
+            }),
+        );
+        id.add_event_listener(
+            EventListener::PointerLeave,
+            Box::new(move |_| {
+                HoverIntent::set_inside(&intent, false);
+                EventPropagation::Continue
+            }),
+        );
+    }
+
+    /// Waits for the pointer to stay `inside` or outside of the view before calling the action.
+    /// Going back before the delay has passed cancels the wait.
+    fn set_inside(intent: &Rc<RefCell<HoverIntent>>, inside: bool) {
+        let mut this = intent.borrow_mut();
+        if this.inside == inside {
+            return;
+        }
+        this.inside = inside;
+        if let Some(timer) = this.timer.take() {
+            timer.cancel();
+        }
+        if this.hovered == inside {
+            return;
+        }
+
+        let delay = if inside {
+            this.enter_delay
+        } else {
+            this.exit_delay
+        };
+        let weak = Rc::downgrade(intent);
+        let token = exec_after(delay, move |token| {
+            let Some(intent) = weak.upgrade() else {
+                return;
+            };
+            let (action, hovered) = {
+                let mut this = intent.borrow_mut();
+                if this.timer != Some(token) {
+                    return;
+                }
+                this.timer = None;
+                this.hovered = this.inside;
+                (this.action.clone(), this.hovered)
+            };
+            action(hovered);
+        });
+        this.timer = Some(token);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use peniko::kurbo::{Point, Vec2};
+
+    use super::{GestureRecognizer, PointerTrack, Recognition, Swipe, SwipeDirection};
+
+    fn swipe_right(fired: Rc<Cell<bool>>) -> Swipe {
+        Swipe {
+            direction: SwipeDirection::Right,
+            threshold: 50.,
+            action: Box::new(move |_| fired.set(true)),
+        }
+    }
+
+    #[test]
+    fn swipe_claims_movement_along_its_direction() {
+        let fired = Rc::new(Cell::new(false));
+        let mut swipe = swipe_right(fired.clone());
+        let mut pointer = PointerTrack::new(Point::ZERO);
+        assert_eq!(swipe.pointer_down(&pointer), Recognition::Possible);
+
+        pointer.push(Point::new(3., 1.));
+        assert_eq!(swipe.pointer_move(&pointer, false), Recognition::Possible);
+        pointer.push(Point::new(20., 2.));
+        assert_eq!(swipe.pointer_move(&pointer, false), Recognition::Claim);
+
+        pointer.push(Point::new(40., 2.));
+        swipe.pointer_up(&pointer, true);
+        assert!(!fired.get(), "released before the threshold");
+
+        let mut pointer = PointerTrack::new(Point::ZERO);
+        pointer.push(Point::new(60., 4.));
+        swipe.pointer_up(&pointer, true);
+        assert!(fired.get());
+    }
+
+    #[test]
+    fn swipe_fails_across_or_against_its_direction() {
+        let mut swipe = swipe_right(Rc::new(Cell::new(false)));
+
+        let mut pointer = PointerTrack::new(Point::ZERO);
+        pointer.push(Point::new(2., 20.));
+        assert_eq!(swipe.pointer_move(&pointer, false), Recognition::Fail);
+
+        let mut pointer = PointerTrack::new(Point::ZERO);
+        pointer.push(Point::new(-20., 0.));
+        assert_eq!(swipe.pointer_move(&pointer, false), Recognition::Fail);
+    }
+
+    #[test]
+    fn pointer_track_translation() {
+        let mut pointer = PointerTrack::new(Point::new(10., 10.));
+        pointer.push(Point::new(15., 30.));
+        assert_eq!(pointer.translation(), Vec2::new(5., 20.));
+    }
+}
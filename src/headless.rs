@@ -0,0 +1,452 @@
+#![deny(missing_docs)]
+//! Running views without a window, for tests.
+//!
+//! A [`HeadlessHarness`] owns a view tree that is styled, laid out and painted like the content
+//! of a window, but with a CPU renderer and without a display, so tests using it can run in CI
+//! without a GPU.
+//!
+//! ```no_run
+//! # use floem::headless::HeadlessHarness;
+//! # use floem::views::label;
+//! let mut harness = HeadlessHarness::new(|| label(|| "Hello"));
+//! harness.assert_matches_golden("hello");
+//! ```
+//!
+//! Views are found by their text, style class or [test id](crate::views::Decorators::test_id),
+//! so that tests interact with them without hard-coding their positions:
+//!
+//! ```no_run
+//! # use floem::headless::HeadlessHarness;
+//! # use floem::views::{button, Decorators};
+//! let mut harness = HeadlessHarness::new(|| button("Save").test_id("save"));
+//! harness.find_by_test_id("save").expect("the button is shown").click();
+//! ```
+//!
+//! Time is virtual while a harness exists: animations, style transitions and timers only move
+//! on when the test calls [`HeadlessHarness::advance_time`].
+
+use std::path::{Path, PathBuf};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+use web_time::Duration;
+
+use floem_winit::event::{ElementState, MouseButton};
+use image::{Rgba, RgbaImage};
+use peniko::kurbo::{Point, Rect, Size};
+
+use crate::{
+    accessibility::{self, Preferences},
+    clock,
+    debug_tree::{DebugTree, DebugTreeOptions},
+    event::Event,
+    id::ViewId,
+    idle,
+    style::{Style, StyleClass},
+    view::IntoView,
+    window_handle::WindowHandle,
+};
+
+/// The largest difference of two colors in the YIQ color space, see [`color_delta`].
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+/// The interval of the frames rendered while views are animating, which is 60 frames per second.
+const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// Runs a view without a window.
+///
+/// The harness has the default theme and the default
+/// [accessibility preferences](crate::accessibility::Preferences) rather than the ones of the
+/// system. It's styled and laid out when it's created and after each event. The view tree is
+/// disposed when the harness is dropped.
+///
+/// While a harness exists, the thread uses a virtual clock, see
+/// [`advance_time`](Self::advance_time).
+pub struct HeadlessHarness {
+    handle: WindowHandle,
+}
+
+impl HeadlessHarness {
+    /// Creates a harness with the view built by `view`, in a window of 800 × 600 logical pixels
+    /// with a scale factor of 1.
+    pub fn new<V: IntoView + 'static>(view: impl FnOnce() -> V) -> Self {
+        Self::new_with_size(view, Size::new(800.0, 600.0), 1.0)
+    }
+
+    /// Creates a harness with the view built by `view`, in a window of `size` logical pixels
+    /// with the scale factor `scale`.
+    pub fn new_with_size<V: IntoView + 'static>(
+        view: impl FnOnce() -> V,
+        size: Size,
+        scale: f64,
+    ) -> Self {
+        clock::use_virtual_clock();
+        accessibility::set_preferences(Preferences::default());
+        Self {
+            handle: WindowHandle::new_headless(|_| view().into_any(), size, scale),
+        }
+    }
+
+    /// The id of the view built when the harness was created.
+    pub fn root(&self) -> ViewId {
+        self.handle.main_view
+    }
+
+    /// Resizes the window to `size` logical pixels.
+    pub fn set_size(&mut self, size: Size) {
+        self.handle.size(size);
+    }
+
+    /// Handles `event` like the window would, and processes the updates it caused.
+    pub fn dispatch_event(&mut self, event: Event) {
+        self.handle.dispatch_event(event);
+    }
+
+    /// Returns a snapshot of the view tree, see [`ViewId::debug_tree`].
+    pub fn tree_snapshot(&self) -> DebugTree {
+        self.root().debug_tree()
+    }
+
+    /// Returns a snapshot of the view tree including what `options` selects, see
+    /// [`ViewId::debug_tree_with`].
+    pub fn tree_snapshot_with(&self, options: &DebugTreeOptions) -> DebugTree {
+        self.root().debug_tree_with(options)
+    }
+
+    /// Advances the virtual clock by `duration`.
+    ///
+    /// The clock of animations, style transitions, and timers like the ones of
+    /// [`exec_after`](crate::action::exec_after) and
+    /// [`debounce_action`](crate::action::debounce_action) doesn't move on its own while there
+    /// is a harness, so that tests of them are deterministic. While it's advanced, the timers
+    /// fire in the order of their deadlines, and frames are rendered every 1/60 second while
+    /// views are animating. The callbacks of [`schedule_idle`](crate::action::schedule_idle)
+    /// run after the timers of each step in which no views are animating. The updates caused by
+    /// the test since the last event, like setting a signal, are processed first, so the
+    /// transitions they start begin at the current time.
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.handle.process_update();
+        let end = clock::now() + duration;
+        loop {
+            let animating = !self.handle.app_state.scheduled_updates.is_empty();
+            let mut next = end;
+            if animating {
+                next = next.min(clock::now() + FRAME_INTERVAL);
+            }
+            if let Some(deadline) = clock::next_deadline()
+                .into_iter()
+                .chain(idle::next_deadline())
+                .min()
+            {
+                next = next.min(deadline);
+            }
+            clock::advance(next.saturating_duration_since(clock::now()));
+
+            for timer in clock::take_due_timers() {
+                (timer.action)(timer.token);
+            }
+            idle::run(!animating);
+            self.handle.process_update();
+            if animating {
+                self.handle.render_image();
+            }
+
+            if clock::now() >= end {
+                break;
+            }
+        }
+    }
+
+    /// Finds the first view, in tree order, that shows the text `text`, like a label or a text
+    /// input, see [`View::text_content`](crate::view::View::text_content).
+    pub fn find_by_text(&mut self, text: &str) -> Option<FoundView<'_>> {
+        self.find(|id| id.view().borrow().text_content().as_deref() == Some(text))
+    }
+
+    /// Finds the first view, in tree order, that has the style class `C`, either as the class of
+    /// the view or one added with [`Decorators::class`](crate::views::Decorators::class).
+    pub fn find_by_class<C: StyleClass>(&mut self, _class: C) -> Option<FoundView<'_>> {
+        let class = C::class_ref();
+        self.find(|id| {
+            id.state().borrow().classes.contains(&class)
+                || id.view().borrow().view_class() == Some(class)
+        })
+    }
+
+    /// Finds the first view, in tree order, with the test id `test_id`, see
+    /// [`Decorators::test_id`](crate::views::Decorators::test_id).
+    pub fn find_by_test_id(&mut self, test_id: &str) -> Option<FoundView<'_>> {
+        self.find(|id| id.state().borrow().test_id.as_deref() == Some(test_id))
+    }
+
+    /// Searches the views depth first, including the overlays of the window.
+    fn find(&mut self, matches: impl Fn(ViewId) -> bool) -> Option<FoundView<'_>> {
+        let mut stack = vec![self.handle.id];
+        while let Some(id) = stack.pop() {
+            if matches(id) {
+                return Some(FoundView { harness: self, id });
+            }
+            stack.extend(id.children().into_iter().rev());
+        }
+        None
+    }
+
+    /// Renders a frame, returning its image, which has the size of the window in physical
+    /// pixels.
+    pub fn render_to_image(&mut self) -> RgbaImage {
+        let image = self
+            .handle
+            .render_image()
+            .expect("the headless renderer returns the frames");
+        RgbaImage::from_raw(image.width, image.height, image.data.data().to_vec())
+            .expect("the frames are RGBA images")
+    }
+
+    /// Asserts that a rendered frame matches the golden image `name`, with the default
+    /// [`GoldenOptions`].
+    ///
+    /// The golden images are the PNG files in the directory set by the `FLOEM_GOLDEN_DIR`
+    /// environment variable, which defaults to `tests/golden` in the package being tested. A
+    /// missing golden image is created from the frame, except when the `CI` environment variable
+    /// is set, and setting `FLOEM_UPDATE_GOLDEN=1` replaces the golden images instead of
+    /// comparing them.
+    ///
+    /// When the frame doesn't match, it's saved as `failures/<name>.png` in the golden
+    /// directory, next to `failures/<name>.diff.png` where the differing pixels are red.
+    pub fn assert_matches_golden(&mut self, name: &str) {
+        self.assert_matches_golden_with(name, GoldenOptions::new());
+    }
+
+    /// Asserts that a rendered frame matches the golden image `name`, see
+    /// [`assert_matches_golden`](Self::assert_matches_golden).
+    pub fn assert_matches_golden_with(&mut self, name: &str, options: GoldenOptions) {
+        let actual = self.render_to_image();
+        let dir = golden_dir();
+        let path = dir.join(format!("{name}.png"));
+
+        let update = std::env::var("FLOEM_UPDATE_GOLDEN").is_ok_and(|update| update == "1");
+        if update || !path.exists() {
+            if !update && std::env::var_os("CI").is_some() {
+                panic!("the golden image {} is missing", path.display());
+            }
+            save(&actual, &path);
+            return;
+        }
+
+        let expected = image::open(&path)
+            .unwrap_or_else(|err| panic!("failed to open {}: {err}", path.display()))
+            .into_rgba8();
+        let failure = if actual.dimensions() != expected.dimensions() {
+            Some((
+                format!(
+                    "has the size {:?} instead of {:?}",
+                    actual.dimensions(),
+                    expected.dimensions()
+                ),
+                None,
+            ))
+        } else {
+            let (diff_pixels, diff) = diff_images(&actual, &expected, options.threshold);
+            (diff_pixels > options.max_diff_pixels)
+                .then(|| (format!("has {diff_pixels} different pixels"), Some(diff)))
+        };
+
+        if let Some((message, diff)) = failure {
+            let failures = dir.join("failures");
+            save(&actual, &failures.join(format!("{name}.png")));
+            if let Some(diff) = diff {
+                save(&diff, &failures.join(format!("{name}.diff.png")));
+            }
+            panic!(
+                "the frame doesn't match the golden image {}: it {message}, see {}",
+                path.display(),
+                failures.display()
+            );
+        }
+    }
+}
+
+impl Drop for HeadlessHarness {
+    fn drop(&mut self) {
+        self.handle.destroy();
+        clock::release_virtual_clock();
+    }
+}
+
+/// A view found in a [`HeadlessHarness`], see [`HeadlessHarness::find_by_text`].
+pub struct FoundView<'a> {
+    harness: &'a mut HeadlessHarness,
+    id: ViewId,
+}
+
+impl FoundView<'_> {
+    /// The id of the view.
+    pub fn id(&self) -> ViewId {
+        self.id
+    }
+
+    /// The rectangle of the view in the window, as it was last laid out.
+    pub fn layout_rect(&self) -> Rect {
+        self.id.layout_rect()
+    }
+
+    /// The style of the view, combined with the styles of its classes and the selectors that
+    /// apply to it.
+    pub fn style(&self) -> Style {
+        self.id.get_combined_style()
+    }
+
+    /// The text that the view shows, see [`View::text_content`](crate::view::View::text_content).
+    pub fn text(&self) -> Option<String> {
+        self.id.view().borrow().text_content()
+    }
+
+    /// Moves the pointer to the center of the view.
+    pub fn hover(&mut self) {
+        let center = self.center();
+        self.harness.handle.pointer_move(center);
+    }
+
+    /// Moves the pointer to the center of the view, and presses and releases the primary button.
+    pub fn click(&mut self) {
+        self.hover();
+        let handle = &mut self.harness.handle;
+        handle.mouse_input(MouseButton::Left, ElementState::Pressed);
+        handle.mouse_input(MouseButton::Left, ElementState::Released);
+    }
+
+    /// The center of the view in window coordinates, which are scaled by the zoom of the window.
+    fn center(&self) -> Point {
+        let scale = self.harness.handle.app_state.scale;
+        let center = self.layout_rect().center();
+        Point::new(center.x * scale, center.y * scale)
+    }
+}
+
+/// How closely a frame has to match its golden image, see
+/// [`HeadlessHarness::assert_matches_golden_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoldenOptions {
+    threshold: f32,
+    max_diff_pixels: usize,
+}
+
+impl Default for GoldenOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GoldenOptions {
+    /// Creates options with a threshold of 0.1 that allow no different pixels.
+    pub fn new() -> Self {
+        Self {
+            threshold: 0.1,
+            max_diff_pixels: 0,
+        }
+    }
+
+    /// Sets how different the colors of a pixel can be, from 0 to 1, for the pixel to match.
+    /// The difference is perceptual, so changes of brightness count more than changes of hue.
+    pub fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets how many pixels can differ for the frame to match, e.g. to allow for the
+    /// antialiasing of text on different platforms.
+    pub fn max_diff_pixels(mut self, max_diff_pixels: usize) -> Self {
+        self.max_diff_pixels = max_diff_pixels;
+        self
+    }
+}
+
+fn golden_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("FLOEM_GOLDEN_DIR") {
+        return PathBuf::from(dir);
+    }
+    let package = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    package.unwrap_or_default().join("tests").join("golden")
+}
+
+fn save(image: &RgbaImage, path: &Path) {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    image
+        .save(path)
+        .unwrap_or_else(|err| panic!("failed to save {}: {err}", path.display()));
+}
+
+/// Compares two images of the same size, returning the number of pixels whose colors differ by
+/// more than `threshold`, and an image where those pixels are red and the others are faded.
+fn diff_images(actual: &RgbaImage, expected: &RgbaImage, threshold: f32) -> (usize, RgbaImage) {
+    let mut diff_pixels = 0;
+    let mut diff = RgbaImage::new(expected.width(), expected.height());
+    for ((actual, expected), diff) in actual
+        .pixels()
+        .zip(expected.pixels())
+        .zip(diff.pixels_mut())
+    {
+        *diff = if color_delta(*actual, *expected) > threshold {
+            diff_pixels += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            let (luma, _, _) = yiq(*expected);
+            let faded = (255.0 + (luma - 255.0) * 0.1) as u8;
+            Rgba([faded, faded, faded, 255])
+        };
+    }
+    (diff_pixels, diff)
+}
+
+/// The perceptual difference of two colors from 0 to 1, which is their distance in the YIQ
+/// color space weighted so that differences of brightness count more than differences of hue.
+fn color_delta(a: Rgba<u8>, b: Rgba<u8>) -> f32 {
+    let (y1, i1, q1) = yiq(a);
+    let (y2, i2, q2) = yiq(b);
+    let (y, i, q) = (y1 - y2, i1 - i2, q1 - q2);
+    let delta = 0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q;
+    (delta / MAX_YIQ_DELTA).sqrt()
+}
+
+/// Converts a color blended onto white to the YIQ color space.
+fn yiq(color: Rgba<u8>) -> (f32, f32, f32) {
+    let alpha = color[3] as f32 / 255.0;
+    let [r, g, b] = [color[0], color[1], color[2]].map(|c| 255.0 + (c as f32 - 255.0) * alpha);
+    (
+        0.298_895_3 * r + 0.586_622_5 * g + 0.114_482_23 * b,
+        0.595_977_99 * r - 0.274_176_1 * g - 0.321_801_9 * b,
+        0.211_470_17 * r - 0.522_617_1 * g + 0.311_146_94 * b,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn black_and_white_differ_most() {
+        let delta = color_delta(Rgba([0, 0, 0, 255]), Rgba([255, 255, 255, 255]));
+        assert!(delta > 0.9 && delta <= 1.0);
+    }
+
+    #[test]
+    fn transparent_is_white() {
+        assert!(color_delta(Rgba([0, 0, 0, 0]), Rgba([255, 255, 255, 255])) < 0.001);
+    }
+
+    #[test]
+    fn diff_counts_pixels_over_threshold() {
+        let expected = RgbaImage::from_pixel(2, 2, Rgba([100, 100, 100, 255]));
+        let mut actual = expected.clone();
+        actual.put_pixel(0, 0, Rgba([102, 100, 100, 255]));
+        actual.put_pixel(1, 1, Rgba([0, 0, 0, 255]));
+
+        let (diff_pixels, diff) = diff_images(&actual, &expected, 0.1);
+        assert_eq!(diff_pixels, 1);
+        assert_eq!(*diff.get_pixel(1, 1), Rgba([255, 0, 0, 255]));
+        assert_ne!(*diff.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+    }
+}
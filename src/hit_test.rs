@@ -0,0 +1,188 @@
+//! An index of the children of views with many children, which finds the children under the
+//! pointer without testing each of them.
+//!
+//! The index of a view is built the first time a pointer event is sent to its children, and
+//! built again once the layout of one of its children changes or its children are replaced.
+
+use peniko::kurbo::{Point, Rect};
+
+use crate::ViewId;
+
+/// Views with fewer children test each of them.
+const MIN_CHILDREN: usize = 64;
+
+/// The children of a view with their rects, see [`children_at`].
+pub(crate) struct HitIndex {
+    /// The children of the view when the index was built.
+    children: Vec<ViewId>,
+    intervals: Intervals,
+    /// Whether the layout of one of the children changed since the index was built.
+    pub(crate) dirty: bool,
+}
+
+/// Rects sorted by where they start along the axis they are spread out on, like the rows of a
+/// list, so the rects that contain a point are found with binary searches.
+struct Intervals {
+    horizontal: bool,
+    /// The rects with the index of their child, sorted by their start.
+    entries: Vec<(Rect, usize)>,
+    /// The furthest end of the entries up to each one.
+    max_end: Vec<f64>,
+}
+
+impl Intervals {
+    fn new(rects: impl IntoIterator<Item = Option<Rect>>) -> Self {
+        let mut entries = rects
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, rect)| Some((rect?, i)))
+            .collect::<Vec<_>>();
+        let bounds = entries
+            .iter()
+            .map(|(rect, _)| *rect)
+            .reduce(|bounds, rect| bounds.union(rect))
+            .unwrap_or_default();
+        let (width, height) = entries
+            .iter()
+            .fold((0.0, 0.0), |(width, height), (rect, _)| {
+                (width + rect.width(), height + rect.height())
+            });
+        // how many children are next to each other along each axis, e.g. a row has as many as
+        // it has children horizontally and one vertically
+        let horizontal = bounds.width() * height > bounds.height() * width;
+
+        let start = |rect: &Rect| if horizontal { rect.x0 } else { rect.y0 };
+        let end = |rect: &Rect| if horizontal { rect.x1 } else { rect.y1 };
+        entries.sort_by(|(a, _), (b, _)| start(a).total_cmp(&start(b)));
+        let max_end = entries
+            .iter()
+            .scan(f64::NEG_INFINITY, |max, (rect, _)| {
+                *max = max.max(end(rect));
+                Some(*max)
+            })
+            .collect();
+        Self {
+            horizontal,
+            entries,
+            max_end,
+        }
+    }
+
+    /// The indices of the children whose rect contains `point`, from the last child.
+    fn hits(&self, point: Point) -> Vec<usize> {
+        let coord = if self.horizontal { point.x } else { point.y };
+        let start = |rect: &Rect| if self.horizontal { rect.x0 } else { rect.y0 };
+        // the entries that start before the point, from the first one that ends after it
+        let last = self
+            .entries
+            .partition_point(|(rect, _)| start(rect) <= coord);
+        let first = self.max_end[..last].partition_point(|end| *end <= coord);
+        let mut hits = self.entries[first..last]
+            .iter()
+            .filter(|(rect, _)| rect.contains(point))
+            .map(|(_, i)| *i)
+            .collect::<Vec<_>>();
+        hits.sort_unstable_by(|a, b| b.cmp(a));
+        hits
+    }
+}
+
+/// The rect of `child` that is tested against the pointer, in the coordinates of its parent.
+fn child_rect(child: ViewId) -> Option<Rect> {
+    let layout = child.get_layout()?;
+    Some(child.layout_rect().with_origin(Point::new(
+        layout.location.x as f64,
+        layout.location.y as f64,
+    )))
+}
+
+/// The children of `id` that can receive an event at `point`, from the last one. When `id` has
+/// many children, those that `point` is outside of are left out.
+pub(crate) fn children_at(id: ViewId, point: Option<Point>) -> Vec<ViewId> {
+    let mut children = id.children();
+    let Some(point) = point.filter(|_| children.len() >= MIN_CHILDREN) else {
+        children.reverse();
+        return children;
+    };
+
+    let state = id.state();
+    let fresh = state
+        .borrow()
+        .hit_index
+        .as_ref()
+        .is_some_and(|index| !index.dirty && index.children == children);
+    if !fresh {
+        // the layout of the children is read through the state of `id`
+        let intervals = Intervals::new(children.iter().map(|child| child_rect(*child)));
+        state.borrow_mut().hit_index = Some(HitIndex {
+            children,
+            intervals,
+            dirty: false,
+        });
+    }
+
+    let state = state.borrow();
+    let index = state.hit_index.as_ref().unwrap();
+    index
+        .intervals
+        .hits(point)
+        .into_iter()
+        .map(|i| index.children[i])
+        .collect()
+}
+
+/// Marks the index of the parent of `id` as outdated, after the layout of `id` changed.
+pub(crate) fn invalidate_parent(id: ViewId) {
+    if let Some(parent) = id.parent() {
+        if let Some(index) = parent.state().borrow_mut().hit_index.as_mut() {
+            index.dirty = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::kurbo::{Point, Rect};
+
+    use super::Intervals;
+
+    fn list(count: usize) -> Intervals {
+        Intervals::new((0..count).map(|i| {
+            let y = i as f64 * 20.0;
+            Some(Rect::new(0.0, y, 100.0, y + 20.0))
+        }))
+    }
+
+    #[test]
+    fn finds_the_row_under_the_point() {
+        let intervals = list(100);
+        assert!(!intervals.horizontal);
+        assert_eq!(intervals.hits(Point::new(50.0, 410.0)), vec![20]);
+        // the bottom edge belongs to the next row
+        assert_eq!(intervals.hits(Point::new(50.0, 420.0)), vec![21]);
+        assert!(intervals.hits(Point::new(150.0, 410.0)).is_empty());
+        assert!(intervals.hits(Point::new(50.0, 2000.0)).is_empty());
+    }
+
+    #[test]
+    fn finds_columns() {
+        let intervals = Intervals::new((0..100).map(|i| {
+            let x = i as f64 * 10.0;
+            Some(Rect::new(x, 0.0, x + 10.0, 30.0))
+        }));
+        assert!(intervals.horizontal);
+        assert_eq!(intervals.hits(Point::new(55.0, 10.0)), vec![5]);
+    }
+
+    #[test]
+    fn overlapping_children_from_the_last() {
+        let intervals = Intervals::new([
+            Some(Rect::new(0.0, 0.0, 100.0, 1000.0)),
+            None,
+            Some(Rect::new(0.0, 100.0, 100.0, 120.0)),
+            Some(Rect::new(0.0, 110.0, 100.0, 130.0)),
+        ]);
+        assert_eq!(intervals.hits(Point::new(10.0, 115.0)), vec![3, 2, 0]);
+        assert_eq!(intervals.hits(Point::new(10.0, 500.0)), vec![0]);
+    }
+}
@@ -0,0 +1,214 @@
+#![deny(missing_docs)]
+//! Reloading the code of views while the application runs, which makes iterating on them much
+//! faster during development.
+//!
+//! The views that are worked on are built by functions in a separate crate that is compiled as
+//! a `dylib`, and loaded by the application with [`HotLibrary`]. When the library is rebuilt,
+//! e.g. with `cargo watch -x "build -p my-views"`, it's loaded again and the views built with
+//! [`hot_view`] are rebuilt with the new code. The signals that the application passes to the
+//! functions keep their values, so only the state created inside the views is reset.
+//!
+//! The views are passed from the library to the application with the Rust ABI, so both must be
+//! built with the same compiler and link the same build of floem dynamically, e.g. through a
+//! `dylib` crate that both depend on. Libraries are never unloaded, since the closures of views
+//! built by an older version can still be around, but the copies of them that are loaded are
+//! deleted when the [`HotLibrary`] is dropped. When a version can't be loaded, the error is
+//! available from [`HotLibrary::load_error`].
+//!
+//! ```no_run
+//! # use floem::hot_reload::{hot_view, HotLibrary};
+//! # use floem::reactive::RwSignal;
+//! // in the library:
+//! //
+//! // #[no_mangle]
+//! // pub fn counter(count: RwSignal<i32>) -> AnyView {
+//! //     label(move || count.get()).into_any()
+//! // }
+//!
+//! let library = HotLibrary::new("target/debug/libmy_views.so");
+//! let count = RwSignal::new(0);
+//! hot_view(&library, "counter", count);
+//! ```
+
+use std::{
+    cell::{Cell, RefCell},
+    path::{Path, PathBuf},
+    rc::{Rc, Weak},
+    time::{Duration, SystemTime},
+};
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+use libloading::Library;
+
+use crate::{
+    action::exec_after,
+    view::{AnyView, IntoView},
+    views::{dyn_container, text, DynamicContainer},
+};
+
+/// How often the library is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A dynamic library whose functions build views, which is loaded again when it's rebuilt.
+#[derive(Clone)]
+pub struct HotLibrary {
+    inner: Rc<HotLibraryInner>,
+}
+
+struct HotLibraryInner {
+    path: PathBuf,
+    /// The versions of the library that were loaded, the latest last, with the paths of the
+    /// copies they were loaded from.
+    libraries: RefCell<Vec<(Library, PathBuf)>>,
+    /// When the loaded version was modified.
+    loaded: Cell<Option<SystemTime>>,
+    /// When the file was modified when it was last checked, so it's only loaded once it's done
+    /// being written.
+    seen: Cell<Option<SystemTime>>,
+    /// The number of versions that were loaded, which is tracked by the views.
+    version: RwSignal<u64>,
+    /// Why the library couldn't be loaded the last time it changed.
+    error: RwSignal<Option<String>>,
+}
+
+impl HotLibrary {
+    /// Loads the library at `path` and watches it for changes while the returned value, or a
+    /// clone of it, is alive.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let inner = Rc::new(HotLibraryInner {
+            path: path.into(),
+            libraries: RefCell::new(Vec::new()),
+            loaded: Cell::new(None),
+            seen: Cell::new(None),
+            version: RwSignal::new(0),
+            error: RwSignal::new(None),
+        });
+        let modified = modified(&inner.path);
+        if let Some(modified) = modified {
+            inner.load(modified);
+        }
+        inner.seen.set(modified);
+        watch(Rc::downgrade(&inner));
+        Self { inner }
+    }
+
+    /// The number of times the library was loaded, which is tracked.
+    pub fn version(&self) -> u64 {
+        self.inner.version.get()
+    }
+
+    /// Why the library couldn't be loaded the last time it changed, which is tracked. It's
+    /// `None` once a version is loaded.
+    pub fn load_error(&self) -> Option<String> {
+        self.inner.error.get()
+    }
+
+    /// Calls the function `symbol` of the latest version of the library with `args`.
+    ///
+    /// # Safety
+    ///
+    /// The function must have the signature `fn(A) -> AnyView`.
+    unsafe fn build<A>(&self, symbol: &str, args: A) -> Option<AnyView> {
+        let libraries = self.inner.libraries.borrow();
+        let build = *libraries
+            .last()?
+            .0
+            .get::<fn(A) -> AnyView>(symbol.as_bytes())
+            .ok()?;
+        drop(libraries);
+        Some(build(args))
+    }
+}
+
+impl HotLibraryInner {
+    /// Loads a copy of the library, so the library can be written again while it's loaded and
+    /// the system doesn't return the version it already loaded.
+    fn load(&self, modified: SystemTime) {
+        let count = self.libraries.borrow().len();
+        let name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let copy = std::env::temp_dir().join(format!("{}-{count}-{name}", std::process::id()));
+        let library = std::fs::copy(&self.path, &copy)
+            .map_err(|err| err.to_string())
+            .and_then(|_| unsafe { Library::new(&copy) }.map_err(|err| err.to_string()));
+        match library {
+            Ok(library) => {
+                self.libraries.borrow_mut().push((library, copy));
+                self.loaded.set(Some(modified));
+                self.error.set(None);
+                self.version.update(|version| *version += 1);
+            }
+            Err(err) => {
+                let _ = std::fs::remove_file(&copy);
+                self.error.set(Some(format!(
+                    "couldn't load {}: {err}",
+                    self.path.display()
+                )));
+            }
+        }
+    }
+}
+
+impl Drop for HotLibraryInner {
+    fn drop(&mut self) {
+        for (library, copy) in self.libraries.take() {
+            // views built by the library can outlive it, so it stays loaded. Systems that don't
+            // allow deleting a loaded library, like Windows, keep the copy.
+            std::mem::forget(library);
+            let _ = std::fs::remove_file(copy);
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+fn watch(inner: Weak<HotLibraryInner>) {
+    exec_after(POLL_INTERVAL, move |_| {
+        let Some(library) = inner.upgrade() else {
+            return;
+        };
+        let modified = modified(&library.path);
+        // the file is loaded when it didn't change since the last check
+        let changed = modified != library.loaded.get() && modified == library.seen.get();
+        if let Some(modified) = modified.filter(|_| changed) {
+            library.load(modified);
+        }
+        library.seen.set(modified);
+        watch(inner);
+    });
+}
+
+/// A view built by the function `symbol` of `library` with `args`, which is rebuilt when the
+/// library is loaded again.
+///
+/// The function must be `#[no_mangle]` and have the signature `fn(A) -> AnyView`, or the
+/// behavior is undefined. While the library doesn't have the function, or couldn't be loaded, a
+/// message is shown.
+pub fn hot_view<A: Clone + 'static>(
+    library: &HotLibrary,
+    symbol: &'static str,
+    args: A,
+) -> DynamicContainer<u64> {
+    let library = library.clone();
+    let version = library.clone();
+    dyn_container(
+        move || version.version(),
+        move |_| {
+            unsafe { library.build(symbol, args.clone()) }.unwrap_or_else(|| {
+                let message = library
+                    .inner
+                    .error
+                    .get_untracked()
+                    .unwrap_or_else(|| format!("`{symbol}` isn't in the library"));
+                text(message).into_any()
+            })
+        },
+    )
+}
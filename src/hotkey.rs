@@ -0,0 +1,190 @@
+#![deny(missing_docs)]
+//! Hotkeys that are handled even when no window of the application is focused.
+//!
+//! On Linux the hotkeys are only supported on X11.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Once,
+    },
+};
+
+use floem_reactive::Scope;
+use global_hotkey::{hotkey::HotKey, GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+use crate::app::{Application, UserEvent};
+
+thread_local! {
+    static MANAGER: RefCell<Option<GlobalHotKeyManager>> = const { RefCell::new(None) };
+    static HOTKEYS: RefCell<HashMap<GlobalHotkey, Registered>> = Default::default();
+}
+
+struct Registered {
+    hotkey: HotKey,
+    action: Rc<dyn Fn()>,
+}
+
+/// A hotkey registered with [`register_global_hotkey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlobalHotkey(u64);
+
+impl GlobalHotkey {
+    /// Unregisters the hotkey, so that its key combination is available to other applications
+    /// again.
+    pub fn unregister(self) {
+        let Some(registered) = HOTKEYS
+            .try_with(|hotkeys| hotkeys.borrow_mut().remove(&self))
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let _ = MANAGER.try_with(|manager| {
+            if let Some(manager) = manager.borrow().as_ref() {
+                let _ = manager.unregister(registered.hotkey);
+            }
+        });
+    }
+
+    /// Returns `true` if the hotkey hasn't been unregistered yet.
+    pub fn is_registered(&self) -> bool {
+        HOTKEYS.with(|hotkeys| hotkeys.borrow().contains_key(self))
+    }
+}
+
+/// The error returned when a hotkey can't be registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyError {
+    /// The hotkey couldn't be parsed.
+    Parse(String),
+    /// The key combination is already used, either by this application or by another one.
+    Conflict(String),
+    /// The platform doesn't support global hotkeys or failed to register the hotkey.
+    Platform(String),
+}
+
+impl fmt::Display for HotkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HotkeyError::Parse(err) => write!(f, "invalid hotkey: {err}"),
+            HotkeyError::Conflict(hotkey) => write!(f, "the hotkey {hotkey} is already in use"),
+            HotkeyError::Platform(err) => write!(f, "failed to register the hotkey: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for HotkeyError {}
+
+/// Registers a system-wide hotkey, e.g. `"Ctrl+Shift+Space"`, that runs `action` whenever it is
+/// pressed, even when no window of the application is focused. Each press is also reported as
+/// an [`AppEvent::GlobalHotkey`](crate::AppEvent::GlobalHotkey).
+///
+/// The hotkey is unregistered when the current reactive scope is disposed, e.g. when the window
+/// it was registered in is closed, or with [`GlobalHotkey::unregister`]. A hotkey that should
+/// stay registered for the lifetime of the application can be registered in the
+/// [`app_scope`](crate::app_scope).
+///
+/// Registering a key combination that is already in use returns [`HotkeyError::Conflict`].
+///
+/// ```no_run
+/// # use floem::hotkey::register_global_hotkey;
+/// # use floem::{app_scope, reactive::with_scope};
+/// with_scope(app_scope(), || {
+///     register_global_hotkey("Ctrl+Shift+Space", || println!("pressed")).unwrap()
+/// });
+/// ```
+pub fn register_global_hotkey(
+    hotkey: &str,
+    action: impl Fn() + 'static,
+) -> Result<GlobalHotkey, HotkeyError> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    let parsed: HotKey = hotkey
+        .parse()
+        .map_err(|err| HotkeyError::Parse(format!("{err}")))?;
+    let in_use = HOTKEYS.with(|hotkeys| {
+        hotkeys
+            .borrow()
+            .values()
+            .any(|registered| registered.hotkey.id() == parsed.id())
+    });
+    if in_use {
+        return Err(HotkeyError::Conflict(hotkey.to_string()));
+    }
+
+    MANAGER.with(|manager| {
+        let mut manager = manager.borrow_mut();
+        if manager.is_none() {
+            *manager = Some(
+                GlobalHotKeyManager::new().map_err(|err| HotkeyError::Platform(err.to_string()))?,
+            );
+            init_event_handler();
+        }
+        manager
+            .as_ref()
+            .unwrap()
+            .register(parsed)
+            .map_err(|err| match err {
+                global_hotkey::Error::AlreadyRegistered(_)
+                | global_hotkey::Error::FailedToRegister(_) => {
+                    HotkeyError::Conflict(hotkey.to_string())
+                }
+                err => HotkeyError::Platform(err.to_string()),
+            })
+    })?;
+
+    let id = GlobalHotkey(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    HOTKEYS.with(|hotkeys| {
+        hotkeys.borrow_mut().insert(
+            id,
+            Registered {
+                hotkey: parsed,
+                action: Rc::new(action),
+            },
+        )
+    });
+    // the reactive runtime drops the value of a signal when its scope is disposed
+    Scope::current().create_rw_signal(Unregister(id));
+    Ok(id)
+}
+
+/// Unregisters the hotkey when dropped.
+struct Unregister(GlobalHotkey);
+
+impl Drop for Unregister {
+    fn drop(&mut self) {
+        self.0.unregister();
+    }
+}
+
+/// Forwards the hotkey events to the event loop, as they may be reported on another thread.
+fn init_event_handler() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        GlobalHotKeyEvent::set_event_handler(Some(|event: GlobalHotKeyEvent| {
+            if event.state == HotKeyState::Pressed {
+                Application::with_event_loop_proxy(|proxy| {
+                    let _ = proxy.send_event(UserEvent::GlobalHotkey(event.id));
+                });
+            }
+        }));
+    });
+}
+
+/// Runs the action of the pressed hotkey, returning the hotkey.
+pub(crate) fn handle_event(id: u32) -> Option<GlobalHotkey> {
+    let (hotkey, action) = HOTKEYS.with(|hotkeys| {
+        hotkeys
+            .borrow()
+            .iter()
+            .find(|(_, registered)| registered.hotkey.id() == id)
+            .map(|(hotkey, registered)| (*hotkey, registered.action.clone()))
+    })?;
+    action();
+    Some(hotkey)
+}
@@ -0,0 +1,284 @@
+#![deny(missing_docs)]
+//! Translating the text of an application with [Fluent](https://projectfluent.org).
+//!
+//! The translations are Fluent (FTL) resources added for each locale with [`add_resource`] or
+//! loaded from a directory with [`load_dir`]. The [`t!`](crate::t) macro returns a closure that
+//! translates a message in the current [`locale`], so a label using it is translated again when
+//! the locale is switched with [`set_locale`].
+//!
+//! Plurals and genders are handled by Fluent selectors: a number argument selects the plural
+//! category of the locale, and a [`Gender`] argument selects by its name.
+//!
+//! ```
+//! # use floem::i18n::{add_resource, set_locale, Gender};
+//! # use floem::views::label;
+//! # use floem::t;
+//! add_resource(
+//!     "en-US",
+//!     r#"
+//! inbox = { $count ->
+//!     [one] One new message
+//!    *[other] { $count } new messages
+//! }
+//! welcome = { $gender ->
+//!     [female] Welcome to her profile
+//!     [male] Welcome to his profile
+//!    *[other] Welcome to their profile
+//! }
+//! "#,
+//! )
+//! .unwrap();
+//! set_locale("en-US").unwrap();
+//!
+//! label(t!("inbox", count = 3));
+//! label(t!("welcome", gender = Gender::Other));
+//! ```
+
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, fmt, fs, io, path::Path};
+
+use floem_reactive::{ReadSignal, RwSignal, SignalGet, SignalUpdate, SignalWith, Trigger};
+use fluent_bundle::{FluentBundle, FluentResource};
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+pub use unic_langid::LanguageIdentifier;
+
+use crate::app_scope;
+
+/// The locale that translations fall back to when they're missing in the current locale.
+const DEFAULT_FALLBACK: &str = "en-US";
+
+thread_local! {
+    static BUNDLES: RefCell<HashMap<LanguageIdentifier, FluentBundle<FluentResource>>> =
+        RefCell::new(HashMap::new());
+    static LOCALE: RwSignal<LanguageIdentifier> =
+        app_scope().create_rw_signal(DEFAULT_FALLBACK.parse().unwrap());
+    static FALLBACK: RefCell<LanguageIdentifier> = RefCell::new(DEFAULT_FALLBACK.parse().unwrap());
+    /// Notified when resources are added, so the translations are updated.
+    static RESOURCES_CHANGED: Trigger = app_scope().create_trigger();
+}
+
+/// The error returned when translations can't be added or a locale can't be set.
+#[derive(Debug)]
+pub enum I18nError {
+    /// The locale isn't a valid language identifier.
+    Locale(String),
+    /// The resource isn't valid FTL.
+    Parse(String),
+    /// A resource couldn't be read.
+    Io(io::Error),
+}
+
+impl fmt::Display for I18nError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            I18nError::Locale(locale) => write!(f, "invalid locale: {locale}"),
+            I18nError::Parse(err) => write!(f, "invalid FTL resource: {err}"),
+            I18nError::Io(err) => write!(f, "failed to read the FTL resource: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for I18nError {}
+
+impl From<io::Error> for I18nError {
+    fn from(err: io::Error) -> Self {
+        I18nError::Io(err)
+    }
+}
+
+fn parse_locale(locale: &str) -> Result<LanguageIdentifier, I18nError> {
+    locale
+        .parse()
+        .map_err(|_| I18nError::Locale(locale.to_string()))
+}
+
+/// The gender of a person, passed as an argument to select a message variant by its name:
+/// `female`, `male` or `other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gender {
+    /// Selects the `female` variant.
+    Female,
+    /// Selects the `male` variant.
+    Male,
+    /// Selects the `other` variant.
+    Other,
+}
+
+impl From<Gender> for FluentValue<'_> {
+    fn from(gender: Gender) -> Self {
+        FluentValue::String(Cow::Borrowed(match gender {
+            Gender::Female => "female",
+            Gender::Male => "male",
+            Gender::Other => "other",
+        }))
+    }
+}
+
+/// Adds the messages of the FTL resource `source` to the translations of `locale`, replacing
+/// the messages it already has with the same names.
+pub fn add_resource(locale: &str, source: &str) -> Result<(), I18nError> {
+    let locale = parse_locale(locale)?;
+    let resource = FluentResource::try_new(source.to_string()).map_err(|(_, errors)| {
+        let errors = errors.iter().map(|err| err.to_string()).collect::<Vec<_>>();
+        I18nError::Parse(errors.join(", "))
+    })?;
+    BUNDLES.with_borrow_mut(|bundles| {
+        bundles
+            .entry(locale.clone())
+            .or_insert_with(|| {
+                let mut bundle = FluentBundle::new(vec![locale]);
+                // the text is shown by the views, which don't need the bidi isolation marks
+                bundle.set_use_isolating(false);
+                bundle
+            })
+            .add_resource_overriding(resource);
+    });
+    RESOURCES_CHANGED.with(|changed| changed.notify());
+    Ok(())
+}
+
+/// Adds the FTL resources of a directory with a subdirectory for each locale, like
+/// `locales/en-US/main.ftl` and `locales/de/main.ftl`.
+pub fn load_dir(path: impl AsRef<Path>) -> Result<(), I18nError> {
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let locale = entry.file_name().to_string_lossy().to_string();
+        for file in fs::read_dir(entry.path())? {
+            let file = file?.path();
+            if file.extension().is_some_and(|extension| extension == "ftl") {
+                add_resource(&locale, &fs::read_to_string(file)?)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The locale that the text is translated to, which is `en-US` until it's set.
+pub fn locale() -> ReadSignal<LanguageIdentifier> {
+    LOCALE.with(|locale| locale.read_only())
+}
+
+/// Switches the locale that the text is translated to, like `de` or `pt-BR`, which updates the
+/// translations of [`t!`](crate::t).
+pub fn set_locale(locale: &str) -> Result<(), I18nError> {
+    let locale = parse_locale(locale)?;
+    LOCALE.with(|signal| {
+        if signal.with_untracked(|current| *current != locale) {
+            signal.set(locale);
+        }
+    });
+    Ok(())
+}
+
+/// Sets the locale whose messages are used when a message is missing in the current locale,
+/// which is `en-US` by default.
+pub fn set_fallback_locale(locale: &str) -> Result<(), I18nError> {
+    let locale = parse_locale(locale)?;
+    FALLBACK.with_borrow_mut(|fallback| *fallback = locale);
+    RESOURCES_CHANGED.with(|changed| changed.notify());
+    Ok(())
+}
+
+/// Translates the message `key` in the current locale, which is tracked when called in a
+/// reactive context. Use [`t!`](crate::t) to create a closure for a view.
+///
+/// The message is looked up in the current locale, then in a locale of the same language, like
+/// `de` for `de-AT`, and then in the fallback locale. Missing messages are shown as their key.
+pub fn translate(key: &str, args: Option<&FluentArgs>) -> String {
+    let locale = LOCALE.with(|locale| locale.get());
+    RESOURCES_CHANGED.with(|changed| changed.track());
+    let fallback = FALLBACK.with_borrow(|fallback| fallback.clone());
+    BUNDLES.with_borrow(|bundles| {
+        let same_language = bundles
+            .iter()
+            .filter(|(id, _)| id.language == locale.language)
+            // prefer the locale without a region, like `de` for `de-AT`
+            .min_by_key(|(id, _)| id.region.is_some())
+            .map(|(_, bundle)| bundle);
+        let candidates = [bundles.get(&locale), same_language, bundles.get(&fallback)];
+        candidates
+            .into_iter()
+            .flatten()
+            .find_map(|bundle| {
+                let pattern = bundle.get_message(key)?.value()?;
+                let mut errors = Vec::new();
+                Some(
+                    bundle
+                        .format_pattern(pattern, args, &mut errors)
+                        .into_owned(),
+                )
+            })
+            .unwrap_or_else(|| key.to_string())
+    })
+}
+
+/// Creates a closure that translates a message in the current locale, for the text of views.
+///
+/// The arguments are written as `name = value`, and are evaluated each time the message is
+/// translated, so they can read signals.
+///
+/// ```
+/// # use floem::reactive::{create_rw_signal, SignalGet};
+/// # use floem::views::label;
+/// # use floem::t;
+/// let count = create_rw_signal(1);
+/// label(t!("title"));
+/// label(t!("inbox", count = count.get()));
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:expr $(,)?) => {
+        move || $crate::i18n::translate($key, None)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        move || {
+            let mut args = $crate::i18n::FluentArgs::new();
+            $(args.set(stringify!($name), $value);)+
+            $crate::i18n::translate($key, Some(&args))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_fall_back_to_the_language_and_the_fallback_locale() {
+        add_resource(
+            "en-US",
+            "hello = Hello\nbye = Bye\nmissing-in-de = Only in English",
+        )
+        .unwrap();
+        add_resource("de", "hello = Hallo\nbye = Tschüss").unwrap();
+        add_resource("de-CH", "bye = Ade").unwrap();
+
+        set_locale("de-AT").unwrap();
+        assert_eq!(translate("hello", None), "Hallo");
+        assert_eq!(translate("missing-in-de", None), "Only in English");
+        assert_eq!(translate("unknown", None), "unknown");
+
+        set_locale("de-CH").unwrap();
+        assert_eq!(translate("bye", None), "Ade");
+        assert_eq!(translate("hello", None), "Hallo");
+        assert!(set_locale("not a locale").is_err());
+    }
+
+    #[test]
+    fn plurals_and_genders_select_variants() {
+        add_resource(
+            "en-US",
+            "emails = { $count ->\n    [one] One email\n   *[other] { $count } emails\n}\n\
+             owner = { $gender ->\n    [female] her\n   *[other] their\n}",
+        )
+        .unwrap();
+        set_locale("en-US").unwrap();
+        assert_eq!(t!("emails", count = 1)(), "One email");
+        assert_eq!(t!("emails", count = 4)(), "4 emails");
+        assert_eq!(t!("owner", gender = Gender::Female)(), "her");
+        assert_eq!(t!("owner", gender = Gender::Male)(), "their");
+    }
+}
@@ -6,22 +6,27 @@
 
 use std::{any::Any, cell::RefCell, rc::Rc};
 
+use floem_reactive::{create_updater, SignalUpdate};
 use floem_winit::window::WindowId;
-use peniko::kurbo::{Insets, Point, Rect, Size};
+use peniko::kurbo::{Affine, Insets, Point, Rect, Size};
 use slotmap::new_key_type;
 use taffy::{Display, Layout, NodeId, TaffyTree};
 
 use crate::{
     animate::{AnimStateCommand, Animation},
     context::{EventCallback, ResizeCallback},
+    debug_tree::{self, DebugTree, DebugTreeOptions},
     event::{EventListener, EventPropagation},
     menu::Menu,
-    style::{DisplayProp, Style, StyleClassRef, StyleSelector},
+    pointer::PointerLockMode,
+    style::{register_style_class, DisplayProp, Style, StyleClassRef, StyleProp, StyleSelector},
     unit::PxPct,
     update::{UpdateMessage, CENTRAL_DEFERRED_UPDATE_MESSAGES, CENTRAL_UPDATE_MESSAGES},
     view::{IntoView, View},
     view_state::{ChangeFlags, StackOffset, ViewState},
     view_storage::VIEW_STORAGE,
+    views::scroll::ScrollIntoView,
+    window::CaptionButton,
     window_tracking::{is_known_root, window_id_for_root},
     ScreenLayout,
 };
@@ -253,6 +258,17 @@ impl ViewId {
         Some(layout)
     }
 
+    /// Returns a snapshot of this view and the views below it, with their layout, text and style,
+    /// see [`debug_tree`](crate::debug_tree).
+    pub fn debug_tree(&self) -> DebugTree {
+        self.debug_tree_with(&DebugTreeOptions::new())
+    }
+
+    /// Returns a snapshot of this view and the views below it, including what `options` selects.
+    pub fn debug_tree_with(&self, options: &DebugTreeOptions) -> DebugTree {
+        debug_tree::debug_tree(*self, options, 0)
+    }
+
     /// Returns true if the computed style for this view is marked as hidden (Display::None)
     pub fn style_has_hidden(&self) -> bool {
         let state = self.state();
@@ -295,9 +311,28 @@ impl ViewId {
 
     /// Request that this view have it's paint pass run
     pub fn request_paint(&self) {
+        crate::layer::invalidate(*self);
         self.add_update_message(UpdateMessage::RequestPaint);
     }
 
+    /// Get the value of the custom property `P` as last computed for this view.
+    ///
+    /// The value is looked up through the ancestor chain, starting with this view,
+    /// then in the active [theme](crate::theme::Theme), before falling back to the default value of `P`.
+    pub fn get_var<P: StyleProp>(&self) -> P::Type {
+        let mut id = Some(*self);
+        while let Some(current) = id {
+            if let Some(value) = current.state().borrow().combined_style.get_prop::<P>() {
+                return value;
+            }
+            id = current.parent();
+        }
+        crate::theme::current_theme()
+            .style
+            .get_prop::<P>()
+            .unwrap_or_else(P::default_value)
+    }
+
     /// request that this node be styled again
     /// This will recursively request style for all parents.
     pub fn request_style(&self) {
@@ -332,6 +367,23 @@ impl ViewId {
         self.add_update_message(UpdateMessage::ClearFocus(*self));
     }
 
+    /// Request that this view hold the pointer, to receive its relative motion as
+    /// [`Event::PointerDelta`](crate::event::Event::PointerDelta) events, like for orbiting a
+    /// 3D viewport or scrubbing a value beyond the edge of the window.
+    ///
+    /// Each mode is only supported on some platforms, where the other mode is used instead.
+    /// The lock is released with [`ViewId::release_pointer_lock`], when `Escape` is pressed or the
+    /// window loses the focus, after which the view receives
+    /// [`Event::PointerLockReleased`](crate::event::Event::PointerLockReleased).
+    pub fn request_pointer_lock(&self, mode: PointerLockMode) {
+        self.add_update_message(UpdateMessage::PointerLock { id: *self, mode });
+    }
+
+    /// Release the pointer lock of this view, if it has it.
+    pub fn release_pointer_lock(&self) {
+        self.add_update_message(UpdateMessage::ReleasePointerLock { id: *self });
+    }
+
     /// Set the system context menu that should be shown when this view is right-clicked
     pub fn update_context_menu(&self, menu: impl Fn() -> Menu + 'static) {
         self.state().borrow_mut().context_menu = Some(Rc::new(menu));
@@ -358,6 +410,16 @@ impl ViewId {
         self.add_update_message(UpdateMessage::ClearActive(*self));
     }
 
+    /// Send `event` to this view through the normal event dispatch, as if it came from the
+    /// window: the capture listeners of the ancestors run first, and then the view and its
+    /// children handle it. Pointer positions are relative to this view.
+    ///
+    /// This is useful for tests, automation and remote control. To send an event to a window
+    /// as a whole, use [`dispatch_event`](crate::dispatch_event).
+    pub fn send_event(&self, event: crate::event::Event) {
+        self.add_update_message(UpdateMessage::SendEvent { id: *self, event });
+    }
+
     /// Send a message to the application to open the Inspector for this Window
     pub fn inspect(&self) {
         self.add_update_message(UpdateMessage::Inspect);
@@ -366,13 +428,54 @@ impl ViewId {
     /// Scrolls the view and all direct and indirect children to bring the view to be
     /// visible. The optional rectangle can be used to add an additional offset and intersection.
     pub fn scroll_to(&self, rect: Option<Rect>) {
-        self.add_update_message(UpdateMessage::ScrollTo { id: *self, rect });
+        self.add_update_message(UpdateMessage::ScrollTo {
+            id: *self,
+            rect,
+            options: None,
+        });
+    }
+
+    /// Smoothly scrolls all of the scroll views that contain this view to bring it into view,
+    /// keeping at least `margin` pixels between the view and the edges of the viewport.
+    ///
+    /// Use [`ViewId::scroll_into_view_with`] to control the alignment and the animation.
+    pub fn scroll_into_view_animated(&self, margin: f64) {
+        self.scroll_into_view_with(ScrollIntoView::new().margin(margin).animated_default());
+    }
+
+    /// Scrolls all of the scroll views that contain this view to bring it into view using `options`.
+    ///
+    /// A scroll animation that is still running is interrupted and continues from its current position.
+    pub fn scroll_into_view_with(&self, options: ScrollIntoView) {
+        self.add_update_message(UpdateMessage::ScrollTo {
+            id: *self,
+            rect: None,
+            options: Some(options),
+        });
     }
 
     pub(crate) fn transition_anim_complete(&self) {
         self.add_update_message(UpdateMessage::ViewTransitionAnimComplete(*self));
     }
 
+    pub(crate) fn add_animation(&self, animation: impl Fn(Animation) -> Animation + 'static) {
+        let view_id = *self;
+        let state = self.state();
+
+        let offset = state.borrow_mut().animations.next_offset();
+        let initial_animation = create_updater(
+            move || animation(Animation::new()),
+            move |animation| {
+                view_id.update_animation(offset, animation);
+            },
+        );
+        for effect_state in &initial_animation.effect_states {
+            effect_state.update(|stack| stack.push((view_id, offset)));
+        }
+
+        state.borrow_mut().animations.push(initial_animation);
+    }
+
     pub(crate) fn update_animation(&self, offset: StackOffset<Animation>, animation: Animation) {
         let state = self.state();
         state.borrow_mut().animations.set(offset, animation);
@@ -392,6 +495,16 @@ impl ViewId {
         self.request_style();
     }
 
+    pub(crate) fn update_animation_with(
+        &self,
+        offset: StackOffset<Animation>,
+        update: impl Fn(&mut Animation) + 'static,
+    ) {
+        let view_state = self.state();
+        view_state.borrow_mut().animations.update(offset, update);
+        self.request_style();
+    }
+
     /// Send a state update to the `update` method of the associated View
     pub fn update_state(&self, state: impl Any) {
         self.add_update_message(UpdateMessage::State {
@@ -406,10 +519,60 @@ impl ViewId {
         state.borrow_mut().viewport = Some(viewport);
     }
 
+    /// `transform` maps from the coordinates of the `id` view to the coordinates of its parent,
+    /// relative to its layout location.
+    pub(crate) fn set_zoom_transform(&self, transform: Affine) {
+        let state = self.state();
+        state.borrow_mut().zoom_transform = transform;
+    }
+
+    pub(crate) fn set_logical_parent(&self, parent: ViewId) {
+        let state = self.state();
+        state.borrow_mut().logical_parent = Some(parent);
+    }
+
+    /// The parent that events are routed through: the logical parent of the content of a
+    /// [`Portal`](crate::views::Portal), and the parent of every other view.
+    pub(crate) fn event_parent(&self) -> Option<ViewId> {
+        self.state()
+            .borrow()
+            .logical_parent
+            .or_else(|| self.parent())
+    }
+
     /// Add an callback on an action for a given `EventListener`
     pub fn add_event_listener(&self, listener: EventListener, action: Box<EventCallback>) {
+        self.add_event_listener_with_priority(listener, 0, action);
+    }
+
+    /// Add an callback on an action for a given `EventListener`, which runs before the callbacks
+    /// with a lower priority. Callbacks with the same priority run in the order they were added.
+    pub fn add_event_listener_with_priority(
+        &self,
+        listener: EventListener,
+        priority: i32,
+        action: Box<EventCallback>,
+    ) {
         let state = self.state();
-        state.borrow_mut().add_event_listener(listener, action);
+        state
+            .borrow_mut()
+            .add_event_listener(listener, priority, action);
+    }
+
+    /// Add a callback for a given `EventListener` that runs in the capture phase: when the event
+    /// is on its way to this view or one of its descendants, before they get to handle it.
+    ///
+    /// If a capture callback returns [`EventPropagation::Stop`], the event isn't delivered any further.
+    pub fn add_capture_listener(
+        &self,
+        listener: EventListener,
+        priority: i32,
+        action: Box<EventCallback>,
+    ) {
+        let state = self.state();
+        state
+            .borrow_mut()
+            .add_capture_listener(listener, priority, action);
     }
 
     /// Set a callback that should be run when the size of the view changes
@@ -444,6 +607,7 @@ impl ViewId {
 
     /// Add a class to the list of style classes that are associated with this ViewId
     pub fn add_class(&self, class: StyleClassRef) {
+        register_style_class(class);
         let state = self.state();
         state.borrow_mut().classes.push(class);
         self.request_style_recursive();
@@ -466,13 +630,9 @@ impl ViewId {
 
     pub(crate) fn update_style(&self, offset: StackOffset<Style>, style: Style) {
         let state = self.state();
-        let old_any_inherited = state.borrow().style().any_inherited();
         state.borrow_mut().style.set(offset, style);
-        if state.borrow().style().any_inherited() || old_any_inherited {
-            self.request_style_recursive();
-        } else {
-            self.request_style();
-        }
+        // the descendants are restyled by the style pass if an inherited property changed
+        self.request_style();
     }
 
     pub(crate) fn apply_event(
@@ -496,6 +656,26 @@ impl ViewId {
         }
     }
 
+    /// Runs the capture listeners of this view, from the highest priority, until one of them stops the event.
+    pub(crate) fn apply_capture(
+        &self,
+        listener: &EventListener,
+        event: &crate::event::Event,
+    ) -> EventPropagation {
+        let capture_listeners = self
+            .state()
+            .borrow()
+            .capture_listeners
+            .get(listener)
+            .cloned();
+        for handler in capture_listeners.into_iter().flatten() {
+            if (handler.borrow_mut())(event).is_processed() {
+                return EventPropagation::Stop;
+            }
+        }
+        EventPropagation::Continue
+    }
+
     /// Set whether this view should be marked as disabled or not.
     ///
     /// When a view is disabled it will not receive events and it can be styled with the disabled style.
@@ -552,6 +732,17 @@ impl ViewId {
         self.add_update_message(UpdateMessage::WindowVisible(visible));
     }
 
+    /// Zoom the views of the window the view represented by this ID is in, see
+    /// [`WindowIdExt::set_ui_scale`](crate::WindowIdExt::set_ui_scale).
+    pub(crate) fn set_window_ui_scale(&self, scale: f64) {
+        self.add_update_message(UpdateMessage::WindowScale(scale));
+    }
+
+    /// Mark this view as a caption button of a custom title bar.
+    pub(crate) fn set_caption_button(&self, button: CaptionButton) {
+        self.add_update_message(UpdateMessage::CaptionButton { id: *self, button });
+    }
+
     fn add_update_message(&self, msg: UpdateMessage) {
         CENTRAL_UPDATE_MESSAGES.with_borrow_mut(|msgs| {
             msgs.push((*self, msg));
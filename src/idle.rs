@@ -0,0 +1,220 @@
+//! Callbacks that run while the event loop is idle, see [`schedule_idle`].
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use crate::{
+    clock,
+    window_handle::{get_current_view, set_current_view},
+};
+
+/// How long the idle callbacks may run before the event loop handles events again.
+const IDLE_SLICE: Duration = Duration::from_millis(8);
+
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static IDLE_CALLBACKS: RefCell<VecDeque<IdleCallback>> = const { RefCell::new(VecDeque::new()) };
+}
+
+struct IdleCallback {
+    token: IdleToken,
+    action: Box<dyn FnOnce(IdleDeadline)>,
+    /// When the callback runs even if the event loop didn't become idle.
+    deadline: Option<Instant>,
+}
+
+/// A token associated with an idle callback, which can be used to cancel it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub struct IdleToken(u64);
+
+impl IdleToken {
+    /// Cancels the callback if it hasn't run yet.
+    pub fn cancel(self) {
+        let callback = IDLE_CALLBACKS.with_borrow_mut(|callbacks| {
+            let index = callbacks
+                .iter()
+                .position(|callback| callback.token == self)?;
+            callbacks.remove(index)
+        });
+        // the action is dropped outside the borrow, as it may own views
+        drop(callback);
+    }
+}
+
+/// The time that an idle callback has to do its work, passed to the callbacks of
+/// [`schedule_idle`].
+#[derive(Debug, Clone, Copy)]
+pub struct IdleDeadline {
+    end: Instant,
+    did_timeout: bool,
+}
+
+impl IdleDeadline {
+    /// How much time is left before the event loop handles events again. Work that takes longer
+    /// should be split up, scheduling the rest with another call to [`schedule_idle`].
+    pub fn time_remaining(&self) -> Duration {
+        self.end.saturating_duration_since(clock::now())
+    }
+
+    /// Whether the callback runs because its deadline passed while the event loop was busy,
+    /// rather than because the event loop is idle.
+    pub fn did_timeout(&self) -> bool {
+        self.did_timeout
+    }
+}
+
+/// Runs `callback` once the event loop is idle: when the events are handled and no views are
+/// animating.
+///
+/// The callbacks run in the order they were scheduled, in slices of a few milliseconds between
+/// frames, so work like measuring the items of a list that are scrolled out of view or decoding
+/// images doesn't delay the handling of input. If `deadline` is given and the event loop is
+/// still busy once it has passed, the callback runs anyway, with
+/// [`IdleDeadline::did_timeout`] set.
+///
+/// ```no_run
+/// # use floem::action::schedule_idle;
+/// fn measure_all(mut pending: Vec<String>) {
+///     schedule_idle(
+///         move |deadline| {
+///             while deadline.time_remaining() > std::time::Duration::ZERO {
+///                 let Some(_item) = pending.pop() else {
+///                     return;
+///                 };
+///                 // measure `_item`
+///             }
+///             measure_all(pending);
+///         },
+///         None,
+///     );
+/// }
+/// ```
+pub fn schedule_idle(
+    callback: impl FnOnce(IdleDeadline) + 'static,
+    deadline: Option<Duration>,
+) -> IdleToken {
+    let view = get_current_view();
+    let action = move |deadline| {
+        let current_view = get_current_view();
+        set_current_view(view);
+        callback(deadline);
+        set_current_view(current_view);
+    };
+
+    let token = IdleToken(NEXT_TOKEN.fetch_add(1, Ordering::Relaxed));
+    let deadline = deadline.map(|deadline| clock::now() + deadline);
+    IDLE_CALLBACKS.with_borrow_mut(|callbacks| {
+        callbacks.push_back(IdleCallback {
+            token,
+            action: Box::new(action),
+            deadline,
+        })
+    });
+    token
+}
+
+/// Runs the idle callbacks, returning whether any of them ran.
+///
+/// If the event loop is `idle`, the callbacks run in order until a slice of time is used up.
+/// Otherwise only the callbacks whose deadline has passed run. Callbacks scheduled while they
+/// run wait for the next call.
+pub(crate) fn run(idle: bool) -> bool {
+    let start = clock::now();
+    let slice_end = start + IDLE_SLICE;
+    let scheduled_before = NEXT_TOKEN.load(Ordering::Relaxed);
+    let mut ran = false;
+    loop {
+        let now = clock::now();
+        let callback = IDLE_CALLBACKS.with_borrow_mut(|callbacks| {
+            let waiting = |callback: &IdleCallback| callback.token.0 < scheduled_before;
+            let index = callbacks
+                .iter()
+                .position(|callback| {
+                    waiting(callback) && callback.deadline.is_some_and(|deadline| deadline <= now)
+                })
+                .or_else(|| {
+                    let has_time = idle && now < slice_end;
+                    callbacks.iter().position(waiting).filter(|_| has_time)
+                })?;
+            callbacks.remove(index)
+        });
+        let Some(callback) = callback else {
+            return ran;
+        };
+        let did_timeout = callback.deadline.is_some_and(|deadline| deadline <= now);
+        let end = if idle { slice_end } else { now };
+        (callback.action)(IdleDeadline { end, did_timeout });
+        ran = true;
+    }
+}
+
+pub(crate) fn has_pending() -> bool {
+    IDLE_CALLBACKS.with_borrow(|callbacks| !callbacks.is_empty())
+}
+
+/// The earliest deadline of the pending idle callbacks.
+pub(crate) fn next_deadline() -> Option<Instant> {
+    IDLE_CALLBACKS.with_borrow(|callbacks| {
+        callbacks
+            .iter()
+            .filter_map(|callback| callback.deadline)
+            .min()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    fn record(
+        log: &Rc<RefCell<Vec<(&'static str, bool)>>>,
+        name: &'static str,
+    ) -> impl FnOnce(IdleDeadline) {
+        let log = log.clone();
+        move |deadline| log.borrow_mut().push((name, deadline.did_timeout()))
+    }
+
+    #[test]
+    fn callbacks_wait_for_idle_unless_their_deadline_passed() {
+        clock::use_virtual_clock();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        schedule_idle(record(&log, "first"), None);
+        schedule_idle(record(&log, "urgent"), Some(Duration::from_millis(100)));
+        let cancelled = schedule_idle(record(&log, "cancelled"), None);
+        cancelled.cancel();
+
+        assert!(!run(false));
+        clock::advance(Duration::from_millis(100));
+        assert!(run(false));
+        assert_eq!(*log.borrow(), [("urgent", true)]);
+
+        {
+            let log = log.clone();
+            schedule_idle(
+                move |_| {
+                    // scheduled while the callbacks run, so it waits for the next slice
+                    schedule_idle(record(&log, "rescheduled"), None);
+                },
+                None,
+            );
+        }
+        assert!(run(true));
+        assert_eq!(*log.borrow(), [("urgent", true), ("first", false)]);
+        assert!(has_pending());
+        assert!(run(true));
+        assert_eq!(log.borrow().last(), Some(&("rescheduled", false)));
+        assert!(!has_pending());
+        clock::release_virtual_clock();
+    }
+}
@@ -1,4 +1,7 @@
 mod data;
+mod edit;
+pub(crate) mod events;
+pub(crate) mod overlay;
 mod view;
 
 use crate::app_state::AppState;
@@ -9,7 +12,8 @@ use crate::style::{Style, StyleClassRef, StylePropRef, Transition};
 use crate::view::{IntoView, View};
 use crate::view_state::ChangeFlags;
 use crate::views::{
-    button, dyn_container, stack, static_label, text, v_stack, v_stack_from_iter, Decorators, Label,
+    button, dyn_container, label, stack, static_label, text, v_stack, v_stack_from_iter,
+    Decorators, Label,
 };
 use crate::{style, Clipboard};
 use floem_reactive::{batch, RwSignal, Scope, SignalGet, SignalUpdate};
@@ -402,6 +406,7 @@ fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> i
                 let style_header = header("View Style");
                 let class_header = header("Class Header");
 
+                let view_id = view.id;
                 let direct: HashSet<_> = view.direct_style.map.keys().copied().collect();
 
                 let style = capture
@@ -455,8 +460,10 @@ fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> i
                             ))
                             .into_any()
                         };
-                        let mut v = (prop.info().debug_view)(&*value).unwrap_or_else(|| {
-                            static_label((prop.info().debug_any)(&*value)).into_any()
+                        let mut v = edit::prop_editor(view_id, prop, &value).unwrap_or_else(|| {
+                            (prop.info().debug_view)(&*value).unwrap_or_else(|| {
+                                static_label((prop.info().debug_any)(&*value)).into_any()
+                            })
                         });
                         if let Some(transition) = style
                             .map
@@ -512,8 +519,11 @@ fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> i
                     clear,
                     style_header,
                     style_list,
-                    class_header,
-                    v_stack_from_iter(class_list.iter().map(text)).style(|s| s.gap(10)),
+                    v_stack((
+                        class_header,
+                        v_stack_from_iter(class_list.iter().map(text)).style(|s| s.gap(10)),
+                    )),
+                    style_overrides(view_id),
                 ))
                 .style(|s| s.width_full())
                 .into_any()
@@ -525,6 +535,30 @@ fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> i
     .into_view()
 }
 
+/// The code of the style properties edited in the inspector, with buttons to copy and reset it.
+fn style_overrides(id: ViewId) -> impl IntoView {
+    let changed = edit::OVERRIDES_CHANGED.with(|changed| *changed);
+    let code = label(move || {
+        changed.get();
+        edit::overrides_code(id)
+            .unwrap_or_else(|| "Drag a number or pick a color to edit the style".to_string())
+    })
+    .style(|s| s.padding(5.0).font_family("monospace".to_string()));
+    let copy = button("Copy as Rust").on_click_stop(move |_| {
+        if let Some(code) = edit::overrides_code(id) {
+            // TODO: Log error
+            let _ = Clipboard::set_contents(code);
+        }
+    });
+    let reset = button("Reset").on_click_stop(move |_| edit::clear_overrides(id));
+    v_stack((
+        header("Style Overrides"),
+        code,
+        stack((copy, reset)).style(|s| s.margin(5.0).gap(5.0)),
+    ))
+    .style(|s| s.width_full())
+}
+
 #[derive(Clone, Copy)]
 struct CaptureView {
     expanding_selection: RwSignal<Option<(ViewId, bool)>>,
@@ -0,0 +1,333 @@
+//! Editing the style of the inspected views.
+//!
+//! The edited properties are kept as overrides in the state of the views, which are applied
+//! after the rest of their style, so the edits show up in the running app right away. They can
+//! be exported as Rust code to paste back into the app.
+
+use std::any::Any;
+use std::rc::Rc;
+
+use floem_reactive::{create_rw_signal, RwSignal, Scope, SignalGet, SignalUpdate};
+use peniko::{Brush, Color};
+
+use crate::context::EventCx;
+use crate::event::{Event, EventPropagation};
+use crate::id::ViewId;
+use crate::style::{CursorStyle, Style, StyleKey, StyleMapValue, StylePropRef};
+use crate::unit::{Pct, Px, PxPct, PxPctAuto};
+use crate::view::{AnyView, IntoView, View};
+use crate::views::slider::slider;
+use crate::views::{empty, h_stack, label, v_stack, Decorators};
+
+thread_local! {
+    /// Changed when an override is set or cleared, to update the exported code.
+    pub(crate) static OVERRIDES_CHANGED: RwSignal<u64> = Scope::new().create_rw_signal(0);
+}
+
+/// Overrides the style property `key` of the view `id` with `value`, which is a
+/// `StyleMapValue` of the type of the property.
+fn set_override(id: ViewId, key: StyleKey, value: Rc<dyn Any>) {
+    {
+        let state = id.state();
+        let mut state = state.borrow_mut();
+        state
+            .style_overrides
+            .get_or_insert_with(Style::new)
+            .map
+            .insert(key, value);
+    }
+    id.request_style();
+    OVERRIDES_CHANGED.with(|changed| changed.update(|changed| *changed += 1));
+}
+
+/// Removes the style overrides of the view `id`.
+pub(crate) fn clear_overrides(id: ViewId) {
+    id.state().borrow_mut().style_overrides = None;
+    id.request_style();
+    OVERRIDES_CHANGED.with(|changed| changed.update(|changed| *changed += 1));
+}
+
+/// The style overrides of the view `id` as a call to `.style()`, or `None` if the view has no
+/// overrides.
+pub(crate) fn overrides_code(id: ViewId) -> Option<String> {
+    let state = id.state();
+    let state = state.borrow();
+    state.style_overrides.as_ref().and_then(style_code)
+}
+
+fn val<T: Clone + 'static>(value: &Rc<dyn Any>) -> Option<T> {
+    match value.downcast_ref::<StyleMapValue<T>>()? {
+        StyleMapValue::Val(value) | StyleMapValue::Animated(value) => Some(value.clone()),
+        StyleMapValue::Unset => None,
+    }
+}
+
+fn map_value<T: 'static>(value: T) -> Rc<dyn Any> {
+    Rc::new(StyleMapValue::Val(value))
+}
+
+/// Returns an editor for the property `prop` of the view `id` if the type of the property can
+/// be edited: numbers are changed by dragging, and colors with a picker.
+pub(crate) fn prop_editor(id: ViewId, prop: StylePropRef, value: &Rc<dyn Any>) -> Option<AnyView> {
+    let key = prop.key;
+    let number = move |initial: f64, make: fn(f64) -> Rc<dyn Any>| {
+        Some(drag_number(initial, move |value| set_override(id, key, make(value))).into_any())
+    };
+    let color = move |initial: Color, make: fn(Color) -> Rc<dyn Any>| {
+        Some(color_picker(initial, move |color| set_override(id, key, make(color))).into_any())
+    };
+
+    if let Some(value) = val::<f64>(value) {
+        return number(value, map_value::<f64>);
+    }
+    if let Some(value) = val::<f32>(value) {
+        return number(value as f64, |value| map_value(value as f32));
+    }
+    if let Some(Some(value)) = val::<Option<f32>>(value) {
+        return number(value as f64, |value| map_value(Some(value as f32)));
+    }
+    if let Some(Px(value)) = val::<Px>(value) {
+        return number(value, |value| map_value(Px(value)));
+    }
+    if let Some(Pct(value)) = val::<Pct>(value) {
+        return number(value, |value| map_value(Pct(value)));
+    }
+    match val::<PxPct>(value) {
+        Some(PxPct::Px(value)) => return number(value, |value| map_value(PxPct::Px(value))),
+        Some(PxPct::Pct(value)) => return number(value, |value| map_value(PxPct::Pct(value))),
+        None => {}
+    }
+    match val::<PxPctAuto>(value) {
+        Some(PxPctAuto::Px(value)) => {
+            return number(value, |value| map_value(PxPctAuto::Px(value)))
+        }
+        Some(PxPctAuto::Pct(value)) => {
+            return number(value, |value| map_value(PxPctAuto::Pct(value)))
+        }
+        _ => {}
+    }
+    if let Some(value) = val::<Color>(value) {
+        return color(value, map_value::<Color>);
+    }
+    if let Some(value) = val::<Option<Color>>(value) {
+        return color(value.unwrap_or(Color::TRANSPARENT), |color| {
+            map_value(Some(color))
+        });
+    }
+    if let Some(Brush::Solid(value)) = val::<Brush>(value) {
+        return color(value, |color| map_value(Brush::Solid(color)));
+    }
+    match val::<Option<Brush>>(value) {
+        Some(Some(Brush::Solid(value))) => {
+            return color(value, |color| map_value(Some(Brush::Solid(color))))
+        }
+        Some(None) => {
+            return color(Color::TRANSPARENT, |color| {
+                map_value(Some(Brush::Solid(color)))
+            })
+        }
+        _ => {}
+    }
+    None
+}
+
+/// Formats `style` as a call to `.style()` that sets its properties.
+fn style_code(style: &Style) -> Option<String> {
+    let mut props = style
+        .style_props()
+        .filter_map(|prop| {
+            let name = format!("{:?}", prop.key);
+            let name = name.rsplit("::").next().unwrap_or(&name).to_string();
+            let value = rust_value(style.map.get(&prop.key)?)?;
+            Some((name, value))
+        })
+        .collect::<Vec<_>>();
+    if props.is_empty() {
+        return None;
+    }
+    props.sort();
+    let sets = props
+        .iter()
+        .map(|(name, value)| format!(".set({name}, {value})"))
+        .collect::<Vec<_>>()
+        .join("\n        ");
+    Some(format!(".style(|s| {{\n    s{sets}\n}})"))
+}
+
+/// Formats a value of a style property as a Rust expression.
+fn rust_value(value: &Rc<dyn Any>) -> Option<String> {
+    fn color(color: Color) -> String {
+        format!(
+            "Color::rgba8({}, {}, {}, {})",
+            color.r, color.g, color.b, color.a
+        )
+    }
+
+    if let Some(value) = val::<f64>(value) {
+        return Some(format!("{value:?}"));
+    }
+    if let Some(value) = val::<f32>(value) {
+        return Some(format!("{value:?}_f32"));
+    }
+    if let Some(value) = val::<Option<f32>>(value) {
+        return Some(match value {
+            Some(value) => format!("Some({value:?}_f32)"),
+            None => "None".to_string(),
+        });
+    }
+    if let Some(Px(value)) = val::<Px>(value) {
+        return Some(format!("Px({value:?})"));
+    }
+    if let Some(Pct(value)) = val::<Pct>(value) {
+        return Some(format!("Pct({value:?})"));
+    }
+    if let Some(value) = val::<PxPct>(value) {
+        return Some(match value {
+            PxPct::Px(value) => format!("PxPct::Px({value:?})"),
+            PxPct::Pct(value) => format!("PxPct::Pct({value:?})"),
+        });
+    }
+    if let Some(value) = val::<PxPctAuto>(value) {
+        return Some(match value {
+            PxPctAuto::Px(value) => format!("PxPctAuto::Px({value:?})"),
+            PxPctAuto::Pct(value) => format!("PxPctAuto::Pct({value:?})"),
+            PxPctAuto::Auto => "PxPctAuto::Auto".to_string(),
+        });
+    }
+    if let Some(value) = val::<Color>(value) {
+        return Some(color(value));
+    }
+    if let Some(value) = val::<Option<Color>>(value) {
+        return Some(match value {
+            Some(value) => format!("Some({})", color(value)),
+            None => "None".to_string(),
+        });
+    }
+    if let Some(Brush::Solid(value)) = val::<Brush>(value) {
+        return Some(format!("Brush::Solid({})", color(value)));
+    }
+    if let Some(value) = val::<Option<Brush>>(value) {
+        return match value {
+            Some(Brush::Solid(value)) => Some(format!("Some(Brush::Solid({}))", color(value))),
+            None => Some("None".to_string()),
+            Some(_) => None,
+        };
+    }
+    None
+}
+
+/// A number that is changed by dragging it horizontally, by 1 per pixel, or by 0.1 per pixel
+/// while shift is held.
+struct DragNumber {
+    id: ViewId,
+    value: RwSignal<f64>,
+    drag: Option<(f64, f64)>,
+    on_change: Box<dyn Fn(f64)>,
+}
+
+fn drag_number(initial: f64, on_change: impl Fn(f64) + 'static) -> DragNumber {
+    let id = ViewId::new();
+    let value = create_rw_signal(initial);
+    id.set_children(vec![label(move || format!("{:.1}", value.get()))
+        .style(|s| s.padding_horiz(4.0).border(1.).border_radius(3.0))
+        .into_any()]);
+    DragNumber {
+        id,
+        value,
+        drag: None,
+        on_change: Box::new(on_change),
+    }
+}
+
+impl View for DragNumber {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().cursor(CursorStyle::ColResize))
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::PointerDown(event) => {
+                cx.update_active(self.id);
+                self.drag = Some((event.pos.x, self.value.get_untracked()));
+                EventPropagation::Stop
+            }
+            Event::PointerMove(event) => {
+                let Some((start_x, start_value)) = self.drag else {
+                    return EventPropagation::Continue;
+                };
+                let step = if event.modifiers.shift() { 0.1 } else { 1.0 };
+                let value = start_value + ((event.pos.x - start_x) * step * 10.0).round() / 10.0;
+                if value != self.value.get_untracked() {
+                    self.value.set(value);
+                    (self.on_change)(value);
+                }
+                EventPropagation::Stop
+            }
+            Event::PointerUp(_) => {
+                self.drag = None;
+                EventPropagation::Stop
+            }
+            _ => EventPropagation::Continue,
+        }
+    }
+}
+
+/// A swatch of a color with sliders for its red, green, blue and alpha channels.
+fn color_picker(initial: Color, on_change: impl Fn(Color) + 'static) -> impl IntoView {
+    let color = create_rw_signal(initial);
+    let on_change = Rc::new(on_change);
+    let channel = move |name: &'static str, get: fn(Color) -> u8, set: fn(&mut Color, u8)| {
+        let on_change = on_change.clone();
+        h_stack((
+            label(move || format!("{name} {}", get(color.get()))).style(|s| s.width(44.0)),
+            slider(move || Pct(get(color.get()) as f64 / 255.0 * 100.0))
+                .on_change_pct(move |pct| {
+                    color.update(|color| set(color, (pct.0 / 100.0 * 255.0).round() as u8));
+                    on_change(color.get_untracked());
+                })
+                .style(|s| s.width(100.0)),
+        ))
+        .style(|s| s.items_center().gap(5.0))
+    };
+    h_stack((
+        empty().style(move |s| {
+            s.size(24.0, 24.0)
+                .border(1.)
+                .border_radius(3.0)
+                .background(color.get())
+        }),
+        v_stack((
+            channel("R", |color| color.r, |color, r| color.r = r),
+            channel("G", |color| color.g, |color, g| color.g = g),
+            channel("B", |color| color.b, |color, b| color.b = b),
+            channel("A", |color| color.a, |color, a| color.a = a),
+        )),
+    ))
+    .style(|s| s.gap(8.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{Background, PaddingLeft};
+
+    #[test]
+    fn overrides_are_exported_as_rust() {
+        let style = Style::new()
+            .set(PaddingLeft, PxPct::Px(12.0))
+            .set(Background, Some(Brush::Solid(Color::rgba8(255, 0, 0, 128))));
+        assert_eq!(
+            style_code(&style).unwrap(),
+            ".style(|s| {\n    s.set(Background, Some(Brush::Solid(Color::rgba8(255, 0, 0, 128))))\n        .set(PaddingLeft, PxPct::Px(12.0))\n})"
+        );
+    }
+
+    #[test]
+    fn empty_overrides_have_no_code() {
+        assert_eq!(style_code(&Style::new()), None);
+    }
+}
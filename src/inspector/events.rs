@@ -0,0 +1,385 @@
+//! The event log of the inspector.
+//!
+//! While the inspector is open, the events dispatched to the inspected window are recorded with
+//! the views they went through, along with the signal updates and the style, layout and paint
+//! passes of the window. The entries are numbered by the frame they belong to, so the updates
+//! that made a frame render again can be traced back to the event that caused them.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use floem_reactive::{
+    clear_signal_update_hook, create_effect, create_rw_signal, create_signal,
+    set_signal_update_hook, SignalGet, SignalUpdate, SignalUpdateInfo,
+};
+use floem_winit::window::WindowId;
+use peniko::Color;
+use slotmap::Key;
+
+use crate::event::Event;
+use crate::id::ViewId;
+use crate::inspector::header;
+use crate::view::{IntoView, View};
+use crate::views::{
+    button, dyn_container, empty, h_stack, scroll, static_label, text_input, v_stack,
+    virtual_stack, Checkbox, Decorators, VirtualDirection, VirtualItemSize,
+};
+
+/// The number of entries that are kept, the oldest ones are dropped first.
+const MAX_ENTRIES: usize = 2000;
+
+thread_local! {
+    static LOG: RefCell<EventLog> = RefCell::new(EventLog::default());
+}
+
+#[derive(Default)]
+struct EventLog {
+    /// The window that is recorded, or `None` if nothing is recorded.
+    window: Option<WindowId>,
+    entries: VecDeque<LogEntry>,
+    next_seq: u64,
+    frame: u64,
+    /// The events being dispatched, which may be nested.
+    dispatches: Vec<Dispatch>,
+}
+
+struct Dispatch {
+    /// The entry of the event, or `None` if the event is dispatched to another window.
+    seq: Option<u64>,
+    path: Vec<ViewId>,
+    target: Option<ViewId>,
+}
+
+#[derive(Clone)]
+pub(crate) struct LogEntry {
+    pub(crate) seq: u64,
+    /// The number of the frame that the entry led up to.
+    pub(crate) frame: u64,
+    pub(crate) kind: LogKind,
+}
+
+#[derive(Clone)]
+pub(crate) enum LogKind {
+    Event {
+        name: String,
+        details: String,
+        /// The view that handled the event.
+        target: Option<(ViewId, String)>,
+        /// The views that the event went through, from the root.
+        path: Vec<(ViewId, String)>,
+    },
+    SignalUpdate(SignalUpdateInfo),
+    Pass {
+        pass: Pass,
+        duration: Duration,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Pass {
+    Style,
+    Layout,
+    Paint,
+}
+
+impl EventLog {
+    fn push(&mut self, kind: LogKind) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            seq,
+            frame: self.frame,
+            kind,
+        });
+        seq
+    }
+
+    /// Whether an event of another window is being dispatched, whose signal updates are left
+    /// out of the log.
+    fn muted(&self) -> bool {
+        self.dispatches
+            .last()
+            .is_some_and(|dispatch| dispatch.seq.is_none())
+    }
+}
+
+/// Starts recording the window `window_id`.
+pub(crate) fn start(window_id: WindowId) {
+    LOG.with_borrow_mut(|log| {
+        log.window = Some(window_id);
+        log.dispatches.clear();
+    });
+    set_signal_update_hook(|info| {
+        LOG.with_borrow_mut(|log| {
+            if log.window.is_some() && !log.muted() {
+                log.push(LogKind::SignalUpdate(*info));
+            }
+        })
+    });
+}
+
+/// Stops recording, keeping the recorded entries.
+pub(crate) fn stop() {
+    LOG.with_borrow_mut(|log| log.window = None);
+    clear_signal_update_hook();
+}
+
+pub(crate) fn clear() {
+    LOG.with_borrow_mut(|log| log.entries.clear());
+}
+
+pub(crate) fn entries() -> im::Vector<LogEntry> {
+    LOG.with_borrow(|log| log.entries.iter().cloned().collect())
+}
+
+/// Called when `event` starts being dispatched to the window `window_id`.
+pub(crate) fn begin_event(window_id: WindowId, event: &Event) {
+    LOG.with_borrow_mut(|log| {
+        if log.window.is_none() {
+            return;
+        }
+        let seq = (log.window == Some(window_id)).then(|| {
+            let details = format!("{event:?}");
+            let name = details
+                .split(['(', ' ', '{'])
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            log.push(LogKind::Event {
+                name,
+                details,
+                target: None,
+                path: Vec::new(),
+            })
+        });
+        log.dispatches.push(Dispatch {
+            seq,
+            path: Vec::new(),
+            target: None,
+        });
+    });
+}
+
+/// Called when the event started by [`begin_event`] has been dispatched.
+pub(crate) fn end_event() {
+    let Some(dispatch) = LOG.with_borrow_mut(|log| log.dispatches.pop()) else {
+        return;
+    };
+    let Some(seq) = dispatch.seq else {
+        return;
+    };
+    // the views are named outside of the borrow of the log, as their names may be computed
+    let name = |id: ViewId| (id, view_name(id));
+    let path = dispatch.path.into_iter().map(name).collect();
+    let target = dispatch.target.map(name);
+    LOG.with_borrow_mut(|log| {
+        let entry = log.entries.iter_mut().rev().find(|entry| entry.seq == seq);
+        if let Some(LogEntry {
+            kind:
+                LogKind::Event {
+                    target: entry_target,
+                    path: entry_path,
+                    ..
+                },
+            ..
+        }) = entry
+        {
+            *entry_target = target;
+            *entry_path = path;
+        }
+    });
+}
+
+/// Called when the event being dispatched reaches the view `id`.
+pub(crate) fn visit(id: ViewId) {
+    LOG.with_borrow_mut(|log| {
+        if let Some(dispatch) = log.dispatches.last_mut() {
+            if dispatch.seq.is_some() {
+                dispatch.path.push(id);
+            }
+        }
+    });
+}
+
+/// Called when the view `id` stops the propagation of the event being dispatched, which is
+/// first called for the view that handled the event, and then for its ancestors.
+pub(crate) fn handled_by(id: ViewId) {
+    LOG.with_borrow_mut(|log| {
+        if let Some(dispatch) = log.dispatches.last_mut() {
+            dispatch.target.get_or_insert(id);
+        }
+    });
+}
+
+/// Records a pass over the views of the window `window_id`. A paint pass ends the frame.
+pub(crate) fn record_pass(window_id: WindowId, pass: Pass, duration: Duration) {
+    LOG.with_borrow_mut(|log| {
+        if log.window != Some(window_id) {
+            return;
+        }
+        log.push(LogKind::Pass { pass, duration });
+        if pass == Pass::Paint {
+            log.frame += 1;
+        }
+    });
+}
+
+fn view_name(id: ViewId) -> String {
+    let name = View::debug_name(&**id.view().borrow());
+    crate::debug_tree::kind(&name).to_string()
+}
+
+impl LogEntry {
+    fn summary(&self) -> String {
+        match &self.kind {
+            LogKind::Event { name, target, .. } => match target {
+                Some((id, target)) => format!("{name} handled by {target} {}", id.data().as_ffi()),
+                None => format!("{name} not handled"),
+            },
+            LogKind::SignalUpdate(info) => format!(
+                "{} signal created at {}, {} subscribers",
+                info.value_type, info.created_at, info.subscribers
+            ),
+            LogKind::Pass { pass, duration } => format!("{pass:?} pass, {duration:.2?}"),
+        }
+    }
+
+    fn details(&self) -> String {
+        match &self.kind {
+            LogKind::Event { details, path, .. } => {
+                let path = path
+                    .iter()
+                    .map(|(id, name)| format!("{name} {}", id.data().as_ffi()))
+                    .collect::<Vec<_>>()
+                    .join(" > ");
+                format!("{details}\n\nPath: {path}")
+            }
+            _ => self.summary(),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match &self.kind {
+            LogKind::Event { .. } => Color::rgb8(213, 208, 216),
+            LogKind::SignalUpdate(_) => Color::rgb8(228, 237, 216),
+            LogKind::Pass { .. } => Color::rgb8(240, 240, 240),
+        }
+    }
+}
+
+/// The event log tab of the inspector of the window `window_id`.
+pub(crate) fn event_log(window_id: WindowId) -> impl IntoView {
+    let recording = create_rw_signal(true);
+    let show_events = create_rw_signal(true);
+    let show_signals = create_rw_signal(true);
+    let show_passes = create_rw_signal(true);
+    let filter = create_rw_signal(String::new());
+    let (log, set_log) = create_signal(entries());
+    let selected = create_rw_signal(None::<LogEntry>);
+
+    create_effect(move |_| {
+        if recording.get() {
+            start(window_id);
+        } else {
+            stop();
+        }
+    });
+
+    let shown = move || {
+        let filter = filter.get().to_lowercase();
+        let (events, signals, passes) = (show_events.get(), show_signals.get(), show_passes.get());
+        log.get()
+            .into_iter()
+            .filter(|entry| match entry.kind {
+                LogKind::Event { .. } => events,
+                LogKind::SignalUpdate(_) => signals,
+                LogKind::Pass { .. } => passes,
+            })
+            .filter(|entry| filter.is_empty() || entry.details().to_lowercase().contains(&filter))
+            .collect::<im::Vector<_>>()
+    };
+
+    let toolbar = h_stack((
+        Checkbox::labeled_rw(recording, || "Recording"),
+        Checkbox::labeled_rw(show_events, || "Events"),
+        Checkbox::labeled_rw(show_signals, || "Signal Updates"),
+        Checkbox::labeled_rw(show_passes, || "Passes"),
+        text_input(filter)
+            .placeholder("Filter")
+            .style(|s| s.min_width(200.0)),
+        button("Refresh").on_click_stop(move |_| set_log.set(entries())),
+        button("Clear").on_click_stop(move |_| {
+            clear();
+            set_log.set(im::Vector::new());
+            selected.set(None);
+        }),
+    ))
+    .style(|s| s.padding(5.0).gap(10.0).items_center());
+
+    let rows = scroll(
+        virtual_stack(
+            VirtualDirection::Vertical,
+            VirtualItemSize::Fixed(Box::new(|| 22.0)),
+            shown,
+            |entry| entry.seq,
+            move |entry| {
+                let seq = entry.seq;
+                let color = entry.color();
+                h_stack((
+                    static_label(format!("#{}", entry.frame))
+                        .style(|s| s.min_width(60.0).color(Color::BLACK.multiply_alpha(0.6))),
+                    empty().style(move |s| {
+                        s.size(10.0, 10.0)
+                            .margin_right(6.0)
+                            .border_radius(2.0)
+                            .background(color)
+                    }),
+                    static_label(entry.summary()),
+                ))
+                .style(move |s| {
+                    s.height(22.0)
+                        .padding_horiz(5.0)
+                        .items_center()
+                        .hover(|s| s.background(Color::rgba8(228, 237, 216, 160)))
+                        .apply_if(
+                            selected.get().is_some_and(|selected| selected.seq == seq),
+                            |s| s.background(Color::rgb8(213, 208, 216)),
+                        )
+                })
+                .on_click_stop(move |_| selected.set(Some(entry.clone())))
+            },
+        )
+        .style(|s| s.flex_col().min_width_full()),
+    )
+    .style(|s| {
+        s.flex_basis(0.0)
+            .min_height(0.0)
+            .flex_grow(1.0)
+            .width_full()
+    });
+
+    let details = dyn_container(
+        move || selected.get(),
+        |entry| match entry {
+            Some(entry) => static_label(entry.details())
+                .style(|s| s.padding(5.0).font_family("monospace".to_string()))
+                .into_any(),
+            None => static_label("Select an entry to see its details")
+                .style(|s| s.padding(5.0).color(Color::BLACK.multiply_alpha(0.6)))
+                .into_any(),
+        },
+    );
+
+    v_stack((
+        toolbar,
+        header("Event Log"),
+        rows,
+        header("Details"),
+        scroll(details).style(|s| s.height(160.0).width_full()),
+    ))
+    .style(|s| s.width_full().height_full())
+}
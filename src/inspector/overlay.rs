@@ -0,0 +1,373 @@
+//! The layout overlay of the inspector, which is drawn over the inspected window.
+//!
+//! The view under the cursor is drawn with its margins, borders, padding and content, the gaps
+//! between its children, the baselines of its text, and a tag with its size and the sizing
+//! constraints of its style. Views with common layout problems are outlined everywhere in the
+//! window.
+
+use floem_reactive::{RwSignal, Scope};
+use peniko::kurbo::{Affine, Point, Rect, Stroke};
+use peniko::Color;
+use slotmap::Key;
+use taffy::style::{
+    Dimension, Display, FlexDirection, LengthPercentage, LengthPercentageAuto, Position,
+};
+
+use crate::context::PaintCx;
+use crate::id::ViewId;
+use crate::text::{Attrs, AttrsList, TextLayout};
+use crate::Renderer;
+
+thread_local! {
+    /// Whether the overlay is drawn over the inspected window, which is set in the inspector.
+    pub(crate) static LAYOUT_OVERLAY: RwSignal<bool> = Scope::new().create_rw_signal(false);
+}
+
+const MARGIN: Color = Color::rgba8(246, 178, 107, 140);
+const BORDER: Color = Color::rgba8(255, 229, 153, 160);
+const PADDING: Color = Color::rgba8(147, 196, 125, 140);
+const CONTENT: Color = Color::rgba8(111, 168, 220, 120);
+const GAP: Color = Color::rgba8(180, 120, 220, 120);
+const BASELINE: Color = Color::rgba8(220, 40, 60, 220);
+const PROBLEM: Color = Color::rgba8(220, 40, 60, 200);
+
+/// A common mistake in the layout of a view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Problem {
+    /// The view has content, but no width or height.
+    ZeroSize,
+    /// The content or the children of the view don't fit in it.
+    Overflow,
+    /// The view was shrunk by its flex container to less than the size of its content.
+    CollapsedFlexItem,
+}
+
+impl Problem {
+    pub(crate) fn description(&self) -> &'static str {
+        match self {
+            Problem::ZeroSize => "has content but a zero size",
+            Problem::Overflow => "content overflows",
+            Problem::CollapsedFlexItem => "flex item shrunk below its content, try `min_size`",
+        }
+    }
+}
+
+/// The layout problems of the view `id`.
+pub(crate) fn problems(id: ViewId) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let Some(layout) = id.get_layout() else {
+        return problems;
+    };
+    let children = id
+        .children()
+        .into_iter()
+        .filter(|child| !child.style_has_hidden())
+        .collect::<Vec<_>>();
+    let has_text = id
+        .view()
+        .borrow()
+        .text_content()
+        .is_some_and(|text| !text.is_empty());
+    let has_content = has_text || !children.is_empty();
+
+    let collapsed = id.parent().is_some_and(|parent| {
+        let taffy = id.taffy();
+        let taffy = taffy.borrow();
+        let (Ok(parent_style), Ok(style)) = (
+            taffy.style(parent.taffy_node()),
+            taffy.style(id.taffy_node()),
+        ) else {
+            return false;
+        };
+        if parent_style.display != Display::Flex || style.flex_shrink <= 0.0 {
+            return false;
+        }
+        let (size, content) = match parent_style.flex_direction {
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                (layout.size.width, layout.content_size.width)
+            }
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                (layout.size.height, layout.content_size.height)
+            }
+        };
+        has_content && content - size > 0.5
+    });
+
+    if collapsed {
+        problems.push(Problem::CollapsedFlexItem);
+    } else if has_content && (layout.size.width == 0.0 || layout.size.height == 0.0) {
+        problems.push(Problem::ZeroSize);
+    }
+
+    let rect = id.layout_rect();
+    let overflows = if children.is_empty() {
+        has_text
+            && (layout.content_size.width - layout.size.width > 0.5
+                || layout.content_size.height - layout.size.height > 0.5)
+    } else {
+        // the content of a scroll view is meant to be larger than it
+        let scrolled = children
+            .iter()
+            .any(|child| child.state().borrow().viewport.is_some());
+        !scrolled
+            && children.iter().any(|child| {
+                let absolute = child
+                    .taffy()
+                    .borrow()
+                    .style(child.taffy_node())
+                    .is_ok_and(|style| style.position == Position::Absolute);
+                let child = child.layout_rect();
+                !absolute
+                    && (child.x0 < rect.x0 - 0.5
+                        || child.y0 < rect.y0 - 0.5
+                        || child.x1 > rect.x1 + 0.5
+                        || child.y1 > rect.y1 + 0.5)
+            })
+    };
+    if overflows && !collapsed {
+        problems.push(Problem::Overflow);
+    }
+
+    problems
+}
+
+/// Draws the layout overlay over the window with the root view `root`.
+pub(crate) fn paint_layout_overlay(cx: &mut PaintCx, root: ViewId) {
+    cx.save();
+    cx.transform = Affine::IDENTITY;
+    cx.paint_state.renderer_mut().transform(Affine::IDENTITY);
+    cx.clear_clip();
+    cx.set_z_index(i32::MAX);
+
+    let mut flagged = Vec::new();
+    flag_problems(root, &mut flagged);
+    let dashes = Stroke::new(1.5).with_dashes(0.0, [4.0, 3.0]);
+    for id in &flagged {
+        cx.stroke(&id.layout_rect(), PROBLEM, &dashes);
+    }
+
+    if let Some(id) = view_at(root, cx.app_state.last_cursor_location) {
+        paint_view_guides(cx, id);
+    }
+
+    cx.restore();
+}
+
+fn flag_problems(id: ViewId, flagged: &mut Vec<ViewId>) {
+    if id.style_has_hidden() {
+        return;
+    }
+    if !problems(id).is_empty() {
+        flagged.push(id);
+    }
+    for child in id.children() {
+        flag_problems(child, flagged);
+    }
+}
+
+/// The topmost view at `pos`, in window coordinates.
+fn view_at(id: ViewId, pos: Point) -> Option<ViewId> {
+    if id.style_has_hidden() {
+        return None;
+    }
+    id.children()
+        .into_iter()
+        .rev()
+        .find_map(|child| view_at(child, pos))
+        .or_else(|| id.layout_rect().contains(pos).then_some(id))
+}
+
+fn paint_view_guides(cx: &mut PaintCx, id: ViewId) {
+    let Some(layout) = id.get_layout() else {
+        return;
+    };
+    let taffy = id.taffy();
+    let Ok(style) = taffy.borrow().style(id.taffy_node()).cloned() else {
+        return;
+    };
+    let parent_width = id.parent_size().map(|size| size.width).unwrap_or_default();
+
+    let border_box = id.layout_rect();
+    let margin_box = Rect::new(
+        border_box.x0 - length_auto(style.margin.left, parent_width),
+        border_box.y0 - length_auto(style.margin.top, parent_width),
+        border_box.x1 + length_auto(style.margin.right, parent_width),
+        border_box.y1 + length_auto(style.margin.bottom, parent_width),
+    );
+    let padding_box = Rect::new(
+        border_box.x0 + layout.border.left as f64,
+        border_box.y0 + layout.border.top as f64,
+        border_box.x1 - layout.border.right as f64,
+        border_box.y1 - layout.border.bottom as f64,
+    );
+    let content_box = Rect::new(
+        padding_box.x0 + layout.padding.left as f64,
+        padding_box.y0 + layout.padding.top as f64,
+        padding_box.x1 - layout.padding.right as f64,
+        padding_box.y1 - layout.padding.bottom as f64,
+    );
+    fill_frame(cx, margin_box, border_box, MARGIN);
+    fill_frame(cx, border_box, padding_box, BORDER);
+    fill_frame(cx, padding_box, content_box, PADDING);
+    cx.fill(&content_box, CONTENT, 0.0);
+
+    let children = id
+        .children()
+        .into_iter()
+        .filter(|child| !child.style_has_hidden())
+        .map(|child| child.layout_rect())
+        .collect::<Vec<_>>();
+    match style.display {
+        Display::Flex => {
+            let row = matches!(
+                style.flex_direction,
+                FlexDirection::Row | FlexDirection::RowReverse
+            );
+            let mut children = children;
+            if row {
+                children.sort_by(|a, b| a.x0.total_cmp(&b.x0));
+            } else {
+                children.sort_by(|a, b| a.y0.total_cmp(&b.y0));
+            }
+            for pair in children.windows(2) {
+                let gap = if row {
+                    Rect::new(pair[0].x1, content_box.y0, pair[1].x0, content_box.y1)
+                } else {
+                    Rect::new(content_box.x0, pair[0].y1, content_box.x1, pair[1].y0)
+                };
+                if gap.width() > 0.0 && gap.height() > 0.0 {
+                    cx.fill(&gap, GAP, 0.0);
+                }
+            }
+        }
+        Display::Grid => {
+            // the cells of the grid are outlined, the gaps are left between them
+            let dashes = Stroke::new(1.0).with_dashes(0.0, [2.0, 2.0]);
+            for child in &children {
+                cx.stroke(child, GAP, &dashes);
+            }
+        }
+        _ => {}
+    }
+
+    for baseline_id in std::iter::once(id).chain(id.children()) {
+        let baseline = baseline_id.view().borrow().baseline();
+        if let Some(baseline) = baseline {
+            let rect = baseline_id.layout_rect();
+            let y = rect.y0 + baseline;
+            cx.fill(
+                &Rect::new(rect.x0, y - 0.5, rect.x1, y + 0.5),
+                BASELINE,
+                0.0,
+            );
+        }
+    }
+
+    let mut lines = vec![format!(
+        "{} {}  {} × {}",
+        crate::debug_tree::kind(&id.view().borrow().debug_name()),
+        id.data().as_ffi(),
+        round(border_box.width()),
+        round(border_box.height()),
+    )];
+    lines.push(format!(
+        "size {} × {}  min {} × {}  max {} × {}",
+        dimension(style.size.width),
+        dimension(style.size.height),
+        dimension(style.min_size.width),
+        dimension(style.min_size.height),
+        dimension(style.max_size.width),
+        dimension(style.max_size.height),
+    ));
+    let in_flex = id.parent().is_some_and(|parent| {
+        taffy
+            .borrow()
+            .style(parent.taffy_node())
+            .is_ok_and(|style| style.display == Display::Flex)
+    });
+    if in_flex {
+        lines.push(format!(
+            "flex {} {} {}",
+            style.flex_grow,
+            style.flex_shrink,
+            dimension(style.flex_basis)
+        ));
+    }
+    if matches!(style.display, Display::Flex | Display::Grid) {
+        lines.push(format!(
+            "gap {} × {}",
+            length(style.gap.width),
+            length(style.gap.height)
+        ));
+    }
+    for problem in problems(id) {
+        lines.push(format!("⚠ {}", problem.description()));
+    }
+    paint_tag(cx, &lines.join("\n"), margin_box);
+}
+
+/// Fills the area between `outer` and `inner`.
+fn fill_frame(cx: &mut PaintCx, outer: Rect, inner: Rect, color: Color) {
+    let strips = [
+        Rect::new(outer.x0, outer.y0, outer.x1, inner.y0),
+        Rect::new(outer.x0, inner.y1, outer.x1, outer.y1),
+        Rect::new(outer.x0, inner.y0, inner.x0, inner.y1),
+        Rect::new(inner.x1, inner.y0, outer.x1, inner.y1),
+    ];
+    for strip in strips {
+        if strip.width() > 0.0 && strip.height() > 0.0 {
+            cx.fill(&strip, color, 0.0);
+        }
+    }
+}
+
+/// Draws `text` in a tag below `rect`, or above it if there's no room below.
+fn paint_tag(cx: &mut PaintCx, text: &str, rect: Rect) {
+    let mut layout = TextLayout::new();
+    layout.set_text(
+        text,
+        AttrsList::new(Attrs::new().font_size(11.0).color(Color::WHITE)),
+    );
+    let size = layout.size();
+    let window = cx.app_state.root_size / cx.app_state.scale;
+    let y = if rect.y1 + size.height + 12.0 <= window.height {
+        rect.y1 + 4.0
+    } else {
+        (rect.y0 - size.height - 12.0).max(0.0)
+    };
+    let x = rect.x0.min(window.width - size.width - 8.0).max(0.0);
+    let tag = Rect::new(x, y, x + size.width + 8.0, y + size.height + 8.0);
+    cx.fill(
+        &tag.to_rounded_rect(3.0),
+        Color::rgba8(40, 40, 40, 230),
+        0.0,
+    );
+    cx.draw_text(&layout, Point::new(x + 4.0, y + 4.0));
+}
+
+fn length(length: LengthPercentage) -> String {
+    match length {
+        LengthPercentage::Length(length) => format!("{}", round(length as f64)),
+        LengthPercentage::Percent(pct) => format!("{}%", round(pct as f64 * 100.0)),
+    }
+}
+
+fn length_auto(length: LengthPercentageAuto, parent_width: f64) -> f64 {
+    match length {
+        LengthPercentageAuto::Length(length) => length as f64,
+        LengthPercentageAuto::Percent(pct) => pct as f64 * parent_width,
+        LengthPercentageAuto::Auto => 0.0,
+    }
+}
+
+fn dimension(dimension: Dimension) -> String {
+    match dimension {
+        Dimension::Length(length) => format!("{}", round(length as f64)),
+        Dimension::Percent(pct) => format!("{}%", round(pct as f64 * 100.0)),
+        Dimension::Auto => "auto".to_string(),
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
@@ -1,13 +1,15 @@
 use crate::app::{add_app_update_event, AppUpdateEvent};
 use crate::event::{Event, EventListener, EventPropagation};
 use crate::inspector::data::{CapturedData, CapturedDatas};
+use crate::inspector::events::{self, event_log};
+use crate::inspector::overlay::LAYOUT_OVERLAY;
 use crate::inspector::{
     add_event, find_view, header, selected_view, stats, update_select_view_id, Capture,
     CaptureView, CAPTURE, RUNNING,
 };
 use crate::prelude::{
     button, container, dyn_container, empty, h_stack, img_dynamic, scroll, stack, static_label,
-    tab, text, text_input, v_stack, virtual_stack, VirtualDirection, VirtualItemSize,
+    tab, text, text_input, v_stack, virtual_stack, Checkbox, VirtualDirection, VirtualItemSize,
 };
 use crate::profiler::profiler;
 use crate::views::Decorators;
@@ -32,6 +34,14 @@ pub fn capture(window_id: WindowId) {
             move |_| {
                 let (selected, set_selected) = create_signal(0);
 
+                let layout_overlay = LAYOUT_OVERLAY.with(|overlay| *overlay);
+                create_effect(move |_| {
+                    add_app_update_event(AppUpdateEvent::LayoutOverlay {
+                        window_id,
+                        enabled: layout_overlay.get(),
+                    })
+                });
+
                 let tab_item = |name, index| {
                     text(name)
                         .on_click_stop(move |_| set_selected.set(index))
@@ -51,12 +61,16 @@ pub fn capture(window_id: WindowId) {
                         })
                 };
 
-                let tabs = h_stack((tab_item("Views", 0), tab_item("Profiler", 1)))
-                    .style(|s| s.background(Color::WHITE));
+                let tabs = h_stack((
+                    tab_item("Views", 0),
+                    tab_item("Profiler", 1),
+                    tab_item("Events", 2),
+                ))
+                .style(|s| s.background(Color::WHITE));
 
                 let tab = tab(
                     move || selected.get(),
-                    move || [0, 1].into_iter(),
+                    move || [0, 1, 2].into_iter(),
                     |it| *it,
                     move |it| match it {
                         0 => dyn_container(
@@ -68,6 +82,7 @@ pub fn capture(window_id: WindowId) {
                         .style(|s| s.width_full().height_full())
                         .into_any(),
                         1 => profiler(window_id).into_any(),
+                        2 => event_log(window_id).into_any(),
                         _ => panic!(),
                     },
                 )
@@ -94,8 +109,14 @@ pub fn capture(window_id: WindowId) {
                         }
                         EventPropagation::Continue
                     })
-                    .on_event(EventListener::WindowClosed, |_| {
+                    .on_event(EventListener::WindowClosed, move |_| {
                         RUNNING.set(false);
+                        events::stop();
+                        LAYOUT_OVERLAY.with(|overlay| overlay.set(false));
+                        add_app_update_event(AppUpdateEvent::LayoutOverlay {
+                            window_id,
+                            enabled: false,
+                        });
                         EventPropagation::Continue
                     })
             },
@@ -307,14 +328,16 @@ fn capture_view(
             selected_view(capture, capture_view.selected),
             header("Stats"),
             stats(capture),
-            button("Recapture")
-                .style(|s| s.margin(5.0))
-                .on_click_stop(move |_| {
+            h_stack((
+                button("Recapture").on_click_stop(move |_| {
                     add_app_update_event(AppUpdateEvent::CaptureWindow {
                         window_id,
                         capture: capture_s.write_only(),
                     })
                 }),
+                Checkbox::labeled_rw(LAYOUT_OVERLAY.with(|overlay| *overlay), || "Layout Overlay"),
+            ))
+            .style(|s| s.margin(5.0).gap(10.0).items_center()),
         ))
         .style(|s| s.min_width_full()),
     )
@@ -0,0 +1,121 @@
+//! Caching the paint of a view and its children in an image, see
+//! [`Decorators::cache_layer`](crate::views::Decorators::cache_layer).
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use floem_renderer::{Img, Renderer as _};
+use floem_tiny_skia_renderer::TinySkiaRenderer;
+use peniko::kurbo::{Affine, Size};
+
+use crate::{context::PaintCx, renderer::Renderer, ViewId};
+
+/// The paint of a view that is reused until the view or one of its children changes.
+#[derive(Default)]
+pub(crate) struct LayerCache {
+    image: Option<peniko::Image>,
+    /// Identifies the image in the image caches of the renderers, which is changed each time
+    /// the layer is painted again so the new image is uploaded.
+    hash: [u8; 8],
+    size: Size,
+    scale: f64,
+    dirty: bool,
+}
+
+impl LayerCache {
+    pub(crate) fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_valid(&self, size: Size, scale: f64) -> bool {
+        !self.dirty && self.image.is_some() && self.size == size && self.scale == scale
+    }
+}
+
+/// Marks the cached layers of `id` and its ancestors as outdated.
+pub(crate) fn invalidate(id: ViewId) {
+    let mut id = Some(id);
+    while let Some(current) = id {
+        if let Some(layer) = current.state().borrow_mut().layer.as_mut() {
+            layer.invalidate();
+        }
+        id = current.parent();
+    }
+}
+
+/// Paints the content of `id` with `paint` into its layer if the layer is outdated, and then
+/// paints the layer, of `size`, at the current transform.
+pub(crate) fn paint<'a>(
+    cx: &mut PaintCx<'a>,
+    id: ViewId,
+    size: Size,
+    paint: impl FnOnce(&mut PaintCx<'a>),
+) {
+    static NEXT_HASH: AtomicU64 = AtomicU64::new(0);
+
+    let scale = cx.paint_state.renderer().scale();
+    let state = id.state();
+    let valid = state
+        .borrow()
+        .layer
+        .as_ref()
+        .is_some_and(|layer| layer.is_valid(size, scale));
+    if !valid {
+        // cleared before painting, so the changes requested while painting aren't lost
+        if let Some(layer) = state.borrow_mut().layer.as_mut() {
+            layer.dirty = false;
+        }
+        let image = render(cx, size, scale, paint);
+        if let Some(layer) = state.borrow_mut().layer.as_mut() {
+            layer.image = image;
+            layer.hash = NEXT_HASH.fetch_add(1, Ordering::Relaxed).to_le_bytes();
+            layer.size = size;
+            layer.scale = scale;
+        }
+    }
+
+    let cached = state
+        .borrow()
+        .layer
+        .as_ref()
+        .and_then(|layer| Some((layer.image.clone()?, layer.hash)));
+    if let Some((image, hash)) = cached {
+        cx.draw_img(
+            Img {
+                img: image,
+                hash: &hash,
+            },
+            size.to_rect(),
+        );
+    }
+}
+
+/// Paints with `paint` into an image of `size` at `scale`, or straight into the window if the
+/// image can't be created, e.g. because `size` is empty.
+fn render<'a>(
+    cx: &mut PaintCx<'a>,
+    size: Size,
+    scale: f64,
+    paint: impl FnOnce(&mut PaintCx<'a>),
+) -> Option<peniko::Image> {
+    let width = (size.width * scale).ceil() as u32;
+    let height = (size.height * scale).ceil() as u32;
+    let Ok(offscreen) =
+        TinySkiaRenderer::new_offscreen(width, height, scale, cx.app_state.font_embolden)
+    else {
+        paint(cx);
+        return None;
+    };
+
+    let window_renderer =
+        std::mem::replace(cx.paint_state.renderer_mut(), Renderer::TinySkia(offscreen));
+    cx.save();
+    cx.transform = Affine::IDENTITY;
+    cx.clip = None;
+    cx.z_index = None;
+    cx.paint_state.renderer_mut().transform(Affine::IDENTITY);
+    paint(cx);
+    let mut layer = std::mem::replace(cx.paint_state.renderer_mut(), window_renderer);
+    // restores the transform and clip of the window renderer
+    cx.restore();
+    layer.finish()
+}
@@ -122,7 +122,7 @@
 // TODO: Add links on these
 //!
 //! In this same style value, floem supports:
-//! - themeing with [classes](style::Style::class)
+//! - themeing with [classes](style::Style::class) and [runtime switchable themes](theme::Theme)
 //! - [property transitions](style::Style::transition)
 //! - defining styles on different [interaction states](style::Style::hover)
 //! - reactive updates
@@ -176,49 +176,96 @@
 //!
 //! For additional information about animation, [see here](crate::animate::Animation).
 
+pub mod accessibility;
 pub mod action;
 pub mod animate;
 mod app;
 mod app_handle;
 pub(crate) mod app_state;
 mod clipboard;
+pub(crate) mod clock;
 pub mod context;
+pub mod debug_tree;
+pub mod dialogs;
 pub mod dropped_file;
 pub mod easing;
 pub mod event;
 pub mod ext_event;
 pub mod file;
-#[cfg(any(feature = "rfd-async-std", feature = "rfd-tokio"))]
 pub mod file_action;
+pub mod fonts;
+pub mod forced_colors;
+pub mod gestures;
+pub mod headless;
+mod hit_test;
+#[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+pub mod hot_reload;
+#[cfg(feature = "global-hotkey")]
+pub mod hotkey;
+#[cfg(feature = "i18n")]
+pub mod i18n;
 pub(crate) mod id;
+mod idle;
 mod inspector;
 pub mod keyboard;
+mod layer;
+pub mod macros;
 pub mod menu;
 mod nav;
+pub mod notification;
+mod occlusion;
 pub mod pointer;
+#[cfg(feature = "print")]
+pub mod print;
 mod profiler;
+#[cfg(all(feature = "remote-inspector", not(target_arch = "wasm32")))]
+pub mod remote_inspector;
 mod renderer;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod resource;
 pub mod responsive;
+pub mod router;
+pub mod safe_area;
 mod screen_layout;
+pub mod screens;
+#[cfg(target_os = "windows")]
+mod snap_layout;
+pub mod sound;
 pub mod style;
-pub(crate) mod theme;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod task;
+mod text_layout_cache;
+pub mod theme;
 pub mod touchpad;
+#[cfg(feature = "tray")]
+pub mod tray;
+pub mod ui_state;
 pub mod unit;
 mod update;
 pub(crate) mod view;
+pub mod view_macros;
 pub(crate) mod view_state;
 pub(crate) mod view_storage;
 pub mod view_tuple;
 pub mod views;
+#[cfg(target_arch = "wasm32")]
+pub mod web;
+#[cfg(all(feature = "websocket-sync", not(target_arch = "wasm32")))]
+pub mod websocket_sync;
 pub mod window;
+mod window_effects;
+pub mod window_geometry;
 mod window_handle;
 mod window_id;
 mod window_tracking;
 
-pub use app::{launch, quit_app, AppEvent, Application};
+pub use app::{app_scope, launch, lifecycle, quit_app, AppEvent, Application, Lifecycle};
 pub use app_state::AppState;
 pub use clipboard::{Clipboard, ClipboardError};
 pub use floem_reactive as reactive;
+pub use floem_renderer::glyph_cache;
+pub use floem_renderer::image_cache;
+pub use floem_renderer::svg_cache;
 pub use floem_renderer::text;
 pub use floem_renderer::Renderer;
 pub use id::ViewId;
@@ -226,8 +273,8 @@ pub use peniko;
 pub use peniko::kurbo;
 pub use screen_layout::ScreenLayout;
 pub use taffy;
-pub use view::{recursively_layout_view, AnyView, IntoView, View};
-pub use window::{close_window, new_window};
+pub use view::{default_compute_layout, recursively_layout_view, AnyView, IntoView, View};
+pub use window::{close_window, dispatch_event, new_child_window, new_window};
 pub use window_id::{Urgency, WindowIdExt};
 
 pub mod prelude {
@@ -0,0 +1,26 @@
+//! Derive macros for the types of applications and widget crates.
+//!
+//! [`Props`] derives a builder for the props of a reusable component, so components are
+//! parameterized the same way:
+//!
+//! ```
+//! use floem::macros::Props;
+//! use floem::views::{text, v_stack, Decorators};
+//! use floem::IntoView;
+//!
+//! #[derive(Props)]
+//! pub struct CardProps {
+//!     #[prop(into)]
+//!     title: String,
+//!     #[prop(default = 8.0)]
+//!     padding: f64,
+//! }
+//!
+//! pub fn card(props: CardProps, content: impl IntoView + 'static) -> impl IntoView {
+//!     v_stack((text(props.title), content)).style(move |s| s.padding(props.padding))
+//! }
+//!
+//! card(CardProps::builder().title("Inbox").build(), text("No messages"));
+//! ```
+
+pub use floem_macros::Props;
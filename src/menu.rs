@@ -75,6 +75,39 @@ impl Menu {
         }
         menu
     }
+
+    /// Converts the entries of the menu for the tray, moving the actions of the items into
+    /// `actions`.
+    #[cfg(feature = "tray")]
+    pub(crate) fn tray_menu(
+        self,
+        actions: &mut std::collections::HashMap<u64, Box<dyn Fn()>>,
+    ) -> Vec<crate::tray::TrayMenuEntry> {
+        use crate::tray::TrayMenuEntry;
+
+        self.children
+            .into_iter()
+            .map(|entry| match entry {
+                MenuEntry::Separator => TrayMenuEntry::Separator,
+                MenuEntry::Item(item) => {
+                    if let Some(action) = item.action {
+                        actions.insert(item.id, action);
+                    }
+                    TrayMenuEntry::Item {
+                        id: item.id,
+                        title: item.title,
+                        selected: item.selected,
+                        enabled: item.enabled,
+                    }
+                }
+                MenuEntry::SubMenu(m) => TrayMenuEntry::SubMenu {
+                    title: m.item.title.clone(),
+                    enabled: m.item.enabled,
+                    children: m.tray_menu(actions),
+                },
+            })
+            .collect()
+    }
 }
 
 pub struct MenuItem {
@@ -0,0 +1,342 @@
+#![deny(missing_docs)]
+//! Desktop notifications, see [`send_notification`](crate::action::send_notification).
+//!
+//! With the `notifications` feature the notifications are shown by the notification center of
+//! the system. Otherwise, or when the system can't show a notification, it's shown as a toast
+//! in the top right corner of the window it was sent from, which is styled with the
+//! [`ToastClass`].
+//!
+//! The callbacks of a notification are called on the main thread, in the context of the window
+//! that the notification was sent from.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+#[cfg(target_arch = "wasm32")]
+use web_time::Duration;
+
+use floem_reactive::{RwSignal, Scope, SignalGet, SignalUpdate, SignalWith};
+use peniko::kurbo::{Point, Rect};
+
+use crate::{
+    action::{add_overlay, exec_after, move_overlay, remove_overlay},
+    context::ComputeLayoutCx,
+    id::ViewId,
+    style_class,
+    view::{default_compute_layout, IntoView, View},
+    views::{button, dyn_stack, h_stack_from_iter, static_label, v_stack, Decorators},
+    window_handle::get_current_view,
+};
+
+/// How long a toast is shown.
+const TOAST_DURATION: Duration = Duration::from_secs(6);
+
+/// The distance of the toasts from the edges of the window.
+const TOAST_MARGIN: f64 = 12.0;
+
+style_class!(
+    /// The style class of the toasts that show notifications in the window.
+    pub ToastClass
+);
+
+thread_local! {
+    /// The notifications that can still be clicked, by their id.
+    static NOTIFICATIONS: RefCell<HashMap<u64, Pending>> = Default::default();
+    /// The toasts shown in each window, by the id of the window's root view.
+    static TOAST_LAYERS: RefCell<HashMap<ViewId, ToastLayer>> = Default::default();
+}
+
+/// A button of a notification.
+pub struct NotificationAction {
+    label: String,
+    on_action: Rc<dyn Fn()>,
+}
+
+impl NotificationAction {
+    /// A button with `label`, which calls `on_action` when it's clicked.
+    pub fn new(label: impl Into<String>, on_action: impl Fn() + 'static) -> Self {
+        Self {
+            label: label.into(),
+            on_action: Rc::new(on_action),
+        }
+    }
+}
+
+/// A notification that was sent with [`send_notification`](crate::action::send_notification).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NotificationHandle {
+    id: u64,
+}
+
+impl NotificationHandle {
+    /// Sets a function that is called when the notification itself is clicked, rather than one
+    /// of its buttons, e.g. to bring the window to the front.
+    ///
+    /// On macOS and Windows clicks on native notifications aren't reported.
+    pub fn on_click(self, on_click: impl Fn() + 'static) -> Self {
+        NOTIFICATIONS.with_borrow_mut(|notifications| {
+            if let Some(pending) = notifications.get_mut(&self.id) {
+                pending.on_click = Some(Rc::new(on_click));
+            }
+        });
+        self
+    }
+}
+
+/// A notification whose callbacks can still be called.
+struct Pending {
+    title: String,
+    body: String,
+    labels: Vec<String>,
+    on_click: Option<Rc<dyn Fn()>>,
+    actions: Vec<Rc<dyn Fn()>>,
+    /// The window that the notification was sent from.
+    window: ViewId,
+}
+
+/// How the user responded to a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Response {
+    Click,
+    Action(usize),
+    /// The notification was closed or timed out, or the system doesn't report the response.
+    Closed,
+    /// The system couldn't show the notification.
+    Failed,
+}
+
+pub(crate) fn send(
+    title: String,
+    body: String,
+    icon: Option<String>,
+    actions: Vec<NotificationAction>,
+) -> NotificationHandle {
+    static NOTIFICATION_COUNTER: AtomicU64 = AtomicU64::new(0);
+    let id = NOTIFICATION_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let (labels, actions) = actions
+        .into_iter()
+        .map(|action| (action.label, action.on_action))
+        .unzip();
+    let pending = Pending {
+        title,
+        body,
+        labels,
+        on_click: None,
+        actions,
+        window: get_current_view(),
+    };
+    #[cfg(all(feature = "notifications", not(target_arch = "wasm32")))]
+    let shown = native::show(id, &pending, icon);
+    #[cfg(not(all(feature = "notifications", not(target_arch = "wasm32"))))]
+    let shown = {
+        let _ = icon;
+        false
+    };
+    if !shown {
+        show_toast(id, &pending);
+    }
+    NOTIFICATIONS.with_borrow_mut(|notifications| notifications.insert(id, pending));
+    NotificationHandle { id }
+}
+
+/// Calls the callback of the notification `id` for `response`, once.
+fn respond(id: u64, response: Response) {
+    if response == Response::Failed {
+        NOTIFICATIONS.with_borrow(|notifications| {
+            if let Some(pending) = notifications.get(&id) {
+                show_toast(id, pending);
+            }
+        });
+        return;
+    }
+    let Some(pending) = NOTIFICATIONS.with_borrow_mut(|notifications| notifications.remove(&id))
+    else {
+        return;
+    };
+    hide_toast(pending.window, id);
+    let callback = match response {
+        Response::Click => pending.on_click,
+        Response::Action(index) => pending.actions.get(index).cloned(),
+        Response::Closed | Response::Failed => None,
+    };
+    if let Some(callback) = callback {
+        callback();
+    }
+}
+
+#[cfg(all(feature = "notifications", not(target_arch = "wasm32")))]
+mod native {
+    use floem_reactive::Scope;
+
+    use super::{respond, Pending, Response};
+    use crate::ext_event::create_ext_action;
+
+    /// The key of the action that is invoked by clicking a notification.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    const CLICK_ACTION: &str = "default";
+
+    /// Shows the notification with the system, unless it can't show its buttons.
+    pub(super) fn show(id: u64, pending: &Pending, icon: Option<String>) -> bool {
+        // the notification centers of macOS and Windows don't report the response, so
+        // notifications with buttons are shown as toasts
+        if cfg!(not(all(unix, not(target_os = "macos")))) && !pending.labels.is_empty() {
+            return false;
+        }
+        let mut notification = notify_rust::Notification::new();
+        notification.summary(&pending.title).body(&pending.body);
+        if let Some(icon) = &icon {
+            notification.icon(icon);
+        }
+        let send = create_ext_action(Scope::new(), move |response| respond(id, response));
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            notification.action(CLICK_ACTION, "");
+            for (index, label) in pending.labels.iter().enumerate() {
+                notification.action(&index.to_string(), label);
+            }
+            // waiting for the response blocks, and showing it talks to the notification server
+            std::thread::spawn(move || match notification.show() {
+                Ok(handle) => handle.wait_for_action(|action| {
+                    send(match action {
+                        CLICK_ACTION => Response::Click,
+                        action => action.parse().map_or(Response::Closed, Response::Action),
+                    })
+                }),
+                Err(_) => send(Response::Failed),
+            });
+        }
+
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        std::thread::spawn(move || match notification.show() {
+            Ok(_) => send(Response::Closed),
+            Err(_) => send(Response::Failed),
+        });
+
+        true
+    }
+}
+
+/// A notification shown as a toast.
+#[derive(Clone)]
+struct Toast {
+    id: u64,
+    title: String,
+    body: String,
+    labels: Vec<String>,
+}
+
+/// The toasts shown in a window.
+struct ToastLayer {
+    scope: Scope,
+    overlay: ViewId,
+    toasts: RwSignal<Vec<Toast>>,
+}
+
+fn show_toast(id: u64, pending: &Pending) {
+    let toast = Toast {
+        id,
+        title: pending.title.clone(),
+        body: pending.body.clone(),
+        labels: pending.labels.clone(),
+    };
+    TOAST_LAYERS.with_borrow_mut(|layers| {
+        let layer = layers.entry(pending.window).or_insert_with(ToastLayer::new);
+        layer.toasts.update(|toasts| toasts.push(toast));
+    });
+    exec_after(TOAST_DURATION, move |_| respond(id, Response::Closed));
+}
+
+fn hide_toast(window: ViewId, id: u64) {
+    TOAST_LAYERS.with_borrow_mut(|layers| {
+        let Some(layer) = layers.get(&window) else {
+            return;
+        };
+        layer
+            .toasts
+            .update(|toasts| toasts.retain(|toast| toast.id != id));
+        // the layer is removed with its last toast
+        if layer.toasts.with_untracked(Vec::is_empty) {
+            let layer = layers.remove(&window).unwrap();
+            remove_overlay(layer.overlay);
+            layer.scope.dispose();
+        }
+    });
+}
+
+impl ToastLayer {
+    fn new() -> Self {
+        let scope = Scope::new();
+        let toasts = scope.create_rw_signal(Vec::new());
+        let overlay = add_overlay(Point::ZERO, move |overlay| {
+            let id = ViewId::new();
+            let stack = dyn_stack(move || toasts.get(), |toast: &Toast| toast.id, toast_view)
+                .style(|s| s.flex_col().row_gap(8));
+            id.set_children(vec![stack.into_any()]);
+            ToastStack {
+                id,
+                overlay,
+                position: None,
+            }
+        });
+        Self {
+            scope,
+            overlay,
+            toasts,
+        }
+    }
+}
+
+fn toast_view(toast: Toast) -> impl IntoView {
+    let id = toast.id;
+    let buttons = toast
+        .labels
+        .into_iter()
+        .enumerate()
+        .map(move |(index, text)| {
+            button(static_label(text)).on_click_stop(move |_| respond(id, Response::Action(index)))
+        });
+    v_stack((
+        static_label(toast.title).style(|s| s.font_bold()),
+        static_label(toast.body),
+        h_stack_from_iter(buttons).style(|s| s.gap(6).margin_top(6)),
+    ))
+    .class(ToastClass)
+    .on_click_stop(move |_| respond(id, Response::Click))
+}
+
+/// The view in the overlay that holds the toasts and places itself in the top right corner of
+/// the window.
+struct ToastStack {
+    id: ViewId,
+    overlay: ViewId,
+    position: Option<Point>,
+}
+
+impl View for ToastStack {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Toasts".into()
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        let rect = default_compute_layout(self.id, cx);
+        let window = self.overlay.parent_size().unwrap_or_default();
+        let size = self.id.get_size().unwrap_or_default();
+        let position = Point::new(window.width - size.width - TOAST_MARGIN, TOAST_MARGIN);
+        if self.position != Some(position) {
+            self.position = Some(position);
+            move_overlay(self.overlay, position);
+        }
+        rect
+    }
+}
@@ -0,0 +1,238 @@
+//! Skipping the paint of views that are completely covered by an opaque sibling that is painted
+//! after them, like the content under a modal or a docked panel.
+
+use peniko::{
+    kurbo::{Affine, Point, Rect, Size},
+    Brush,
+};
+
+use crate::{
+    context::PaintCx,
+    style::ZIndex,
+    unit::PxPct,
+    view_state::{ViewState, ViewStyleProps},
+    ViewId,
+};
+
+/// The most siblings that a view is tested against, so the cost stays low for views with many
+/// children.
+const MAX_OCCLUDERS: usize = 8;
+
+/// The most descendants of a child that are looked at for what they paint out of their layout,
+/// beyond which the area the child paints in isn't known.
+const MAX_DESCENDANTS: usize = 64;
+
+/// How a child paints, in the coordinates of its parent.
+struct ChildPaint {
+    /// The area that the child and its children paint in, if it's known.
+    bounds: Option<Rect>,
+    /// The area that the child fills with an opaque background.
+    opaque: Option<Rect>,
+}
+
+impl ChildPaint {
+    /// Returns `None` if the child has a z-index, which can change the order of the paint.
+    fn of(id: ViewId) -> Option<Self> {
+        let unknown = ChildPaint {
+            bounds: None,
+            opaque: None,
+        };
+        let state = id.state();
+        let state = state.borrow();
+        if state.combined_style.get(ZIndex).is_some() {
+            return None;
+        }
+        if id.style_has_hidden() {
+            return Some(unknown);
+        }
+        let Some(layout) = id.get_layout() else {
+            return Some(unknown);
+        };
+        if !paints_in_layout(&state) {
+            return Some(unknown);
+        }
+        let Some(children) = subtree_bounds(id, state.layout_rect) else {
+            return Some(unknown);
+        };
+
+        let origin = Point::new(layout.location.x as f64, layout.location.y as f64);
+        let size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        let rect = size.to_rect().with_origin(origin);
+        // the rects of the children are in window coordinates
+        let children = children + (origin - state.window_origin);
+        let overflow = overflow(&state.view_style_props, size);
+        let bounds = rect.inflate(overflow, overflow).union(children);
+
+        let square = match state.view_style_props.border_radius() {
+            PxPct::Px(radius) | PxPct::Pct(radius) => radius <= 0.0,
+        };
+        let solid = matches!(
+            state.view_style_props.background(),
+            Some(Brush::Solid(color)) if color.a == 255
+        );
+        // the edges of cached layers are resampled
+        let opaque = (square && solid && state.layer.is_none()).then_some(rect);
+        Some(ChildPaint {
+            bounds: Some(bounds),
+            opaque,
+        })
+    }
+}
+
+/// Whether a view paints only within its layout, its outline and its shadow.
+fn paints_in_layout(state: &ViewState) -> bool {
+    let moved = state.transform != Affine::IDENTITY
+        || state.zoom_transform != Affine::IDENTITY
+        || state.layout_animation.is_some();
+    // paint hooks can paint anywhere
+    let hooks = !state.paint_before.is_empty() || !state.paint_after.is_empty();
+    !moved && !hooks
+}
+
+/// Extends `bounds` with the area that the descendants of `id` paint in with their outlines and
+/// shadows, in window coordinates, or returns `None` if one of them paints out of its layout, or
+/// there are too many to look at.
+fn subtree_bounds(id: ViewId, mut bounds: Rect) -> Option<Rect> {
+    let mut count = 0;
+    let mut stack = id.children();
+    while let Some(id) = stack.pop() {
+        count += 1;
+        if count > MAX_DESCENDANTS {
+            return None;
+        }
+        if id.style_has_hidden() {
+            continue;
+        }
+        let state = id.state();
+        let state = state.borrow();
+        if !paints_in_layout(&state) {
+            return None;
+        }
+        let layout = id.get_layout()?;
+        let size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        let overflow = overflow(&state.view_style_props, size);
+        let rect = size.to_rect().with_origin(state.window_origin);
+        bounds = bounds.union(rect.inflate(overflow, overflow));
+        stack.extend(id.children());
+    }
+    Some(bounds)
+}
+
+/// How far the outline and the shadow of a view of `size` reach out of it.
+fn overflow(style: &ViewStyleProps, size: Size) -> f64 {
+    let px = |value: PxPct| match value {
+        PxPct::Px(px) => px,
+        PxPct::Pct(pct) => size.min_side() * (pct / 100.),
+    };
+    let shadow = style.shadow().map_or(0.0, |shadow| {
+        let offset = px(shadow.h_offset).abs().max(px(shadow.v_offset).abs());
+        // the blur fades out over a few times its radius
+        offset + px(shadow.spread).max(0.0) + 3.0 * px(shadow.blur_radius).max(0.0)
+    });
+    style.outline().0.width.max(0.0) + shadow
+}
+
+/// Whether `visible` is inside one of the `occluders`.
+fn covered(visible: Rect, occluders: &[Rect]) -> bool {
+    occluders.iter().any(|occluder| {
+        occluder.x0 <= visible.x0
+            && occluder.y0 <= visible.y0
+            && occluder.x1 >= visible.x1
+            && occluder.y1 >= visible.y1
+    })
+}
+
+/// Which of the `children` of a view are covered within the clip of `cx` by opaque siblings
+/// painted after them.
+pub(crate) fn occluded(cx: &PaintCx, children: &[ViewId]) -> Vec<bool> {
+    let mut occluded = vec![false; children.len()];
+    // dragged views are painted away from their layout, and the inspector records every view
+    if children.len() < 2 || cx.app_state.dragging.is_some() || cx.app_state.capture.is_some() {
+        return occluded;
+    }
+    let clip = cx.clip.map(|clip| clip.rect());
+    let mut occluders = Vec::new();
+    for (i, child) in children.iter().enumerate().rev() {
+        let Some(paint) = ChildPaint::of(*child) else {
+            return vec![false; children.len()];
+        };
+        if let Some(bounds) = paint.bounds {
+            let visible = clip.map_or(bounds, |clip| bounds.intersect(clip));
+            occluded[i] = covered(visible, &occluders);
+        }
+        if let Some(opaque) = paint.opaque {
+            if !occluded[i] && occluders.len() < MAX_OCCLUDERS {
+                occluders.push(opaque);
+            }
+        }
+    }
+    occluded
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::{kurbo::Rect, Color};
+
+    use super::{covered, ChildPaint};
+    use crate::{
+        headless::HeadlessHarness,
+        style::Style,
+        views::{container, empty, Decorators},
+    };
+
+    /// The area that a view of 50 × 50 at the origin of the window paints in, when its child is
+    /// styled with `child`.
+    fn bounds(child: fn(Style) -> Style) -> Option<Rect> {
+        let mut harness = HeadlessHarness::new(move || {
+            container(empty().style(move |s| child(s.size(20, 20))))
+                .test_id("parent")
+                .style(|s| s.size(50, 50))
+        });
+        let parent = harness.find_by_test_id("parent").unwrap().id();
+        ChildPaint::of(parent).unwrap().bounds
+    }
+
+    #[test]
+    fn descendants_in_layout() {
+        let bounds = bounds(|s| s.background(Color::RED));
+        assert_eq!(bounds, Some(Rect::new(0.0, 0.0, 50.0, 50.0)));
+        assert!(covered(bounds.unwrap(), &[Rect::new(0.0, 0.0, 50.0, 50.0)]));
+    }
+
+    #[test]
+    fn descendant_shadow_reaches_past_the_occluder() {
+        let bounds = bounds(|s| s.box_shadow_blur(10.0).box_shadow_color(Color::BLACK));
+        // the blur fades out over three times its radius
+        assert_eq!(bounds, Some(Rect::new(-30.0, -30.0, 50.0, 50.0)));
+        assert!(!covered(
+            bounds.unwrap(),
+            &[Rect::new(0.0, 0.0, 50.0, 50.0)]
+        ));
+    }
+
+    #[test]
+    fn descendant_transform_is_unknown() {
+        assert_eq!(bounds(|s| s.translate_x(60.0)), None);
+    }
+
+    #[test]
+    fn covered_by_one_occluder() {
+        let occluders = [
+            Rect::new(0.0, 0.0, 50.0, 100.0),
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+        ];
+        assert!(covered(Rect::new(10.0, 10.0, 90.0, 90.0), &occluders));
+        assert!(covered(Rect::new(0.0, 0.0, 100.0, 100.0), &occluders));
+    }
+
+    #[test]
+    fn not_covered_by_several_occluders() {
+        // together they cover the rect, but the check is for a single occluder
+        let occluders = [
+            Rect::new(0.0, 0.0, 50.0, 100.0),
+            Rect::new(50.0, 0.0, 100.0, 100.0),
+        ];
+        assert!(!covered(Rect::new(10.0, 10.0, 90.0, 90.0), &occluders));
+        assert!(!covered(Rect::new(-1.0, 0.0, 40.0, 40.0), &occluders));
+    }
+}
@@ -1,4 +1,4 @@
-use floem_winit::event::MouseButton;
+use floem_winit::event::{MouseButton, TouchPhase};
 use peniko::kurbo::{Point, Vec2};
 
 use crate::keyboard::Modifiers;
@@ -68,3 +68,219 @@ pub struct PointerMoveEvent {
     pub pos: Point,
     pub modifiers: Modifiers,
 }
+
+/// How the pointer is held by a view with the pointer lock, see
+/// [`ViewId::request_pointer_lock`](crate::id::ViewId::request_pointer_lock).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerLockMode {
+    /// The cursor is hidden and stays where it is, like for orbiting a 3D viewport.
+    Locked,
+    /// The cursor can't leave the window.
+    Confined,
+}
+
+/// The relative motion of the pointer while a view has the pointer lock.
+///
+/// The motion is reported even when the cursor can't move anymore, like at the edge of the
+/// screen, so it isn't tied to a position.
+#[derive(Debug, Clone)]
+pub struct PointerDeltaEvent {
+    /// How far the pointer moved, in logical pixels. It may not include the pointer acceleration
+    /// of the system.
+    pub delta: Vec2,
+    pub modifiers: Modifiers,
+}
+
+/// Identifies one of the pointers that can be down at the same time, like the fingers on a touchscreen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointerId(pub u64);
+
+/// A finger on a touchscreen starting, moving, lifting or being cancelled.
+///
+/// After the [`TouchPhase::Started`] event a view can capture the pointer with
+/// [`EventCx::capture_pointer`](crate::context::EventCx::capture_pointer) to receive the
+/// rest of its events wherever the finger goes.
+#[derive(Debug, Clone)]
+pub struct TouchEvent {
+    pub pointer: PointerId,
+    pub phase: TouchPhase,
+    pub pos: Point,
+    /// The normalized force of the touch, if the device reports it.
+    pub force: Option<f64>,
+}
+
+/// A contact point tracked by [`Touches`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchPoint {
+    pub pointer: PointerId,
+    /// The position of the touch when the current set of touches was formed.
+    pub start: Point,
+    pub pos: Point,
+}
+
+/// The change of two touches since they were placed, see [`Touches::pinch`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pinch {
+    /// The center between the two touches.
+    pub center: Point,
+    /// The ratio of the distance between the touches to their distance at the start.
+    pub scale: f64,
+    /// The rotation of the line between the touches since the start, in radians.
+    pub rotation: f64,
+    /// How far the center between the touches has moved since the start.
+    pub translation: Vec2,
+}
+
+/// Tracks the touches that are down on a view, for interactions with more than one finger.
+///
+/// Feed it the [`Event::Touch`](crate::event::Event::Touch) events of the view. Whenever a touch
+/// is added or removed, the start positions of all the touches are reset to where they are, so
+/// [`pinch`](Self::pinch) is relative to the moment the current set of touches was formed.
+#[derive(Debug, Clone, Default)]
+pub struct Touches {
+    points: Vec<TouchPoint>,
+}
+
+impl Touches {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the touches with `event`.
+    pub fn update(&mut self, event: &TouchEvent) {
+        match event.phase {
+            TouchPhase::Started => {
+                self.points.retain(|point| point.pointer != event.pointer);
+                self.points.push(TouchPoint {
+                    pointer: event.pointer,
+                    start: event.pos,
+                    pos: event.pos,
+                });
+                self.rebase();
+            }
+            TouchPhase::Moved => {
+                if let Some(point) = self
+                    .points
+                    .iter_mut()
+                    .find(|point| point.pointer == event.pointer)
+                {
+                    point.pos = event.pos;
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                let len = self.points.len();
+                self.points.retain(|point| point.pointer != event.pointer);
+                if self.points.len() != len {
+                    self.rebase();
+                }
+            }
+        }
+    }
+
+    fn rebase(&mut self) {
+        for point in &mut self.points {
+            point.start = point.pos;
+        }
+    }
+
+    /// Forgets all the touches.
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The touches in the order they were placed.
+    pub fn iter(&self) -> impl Iterator<Item = &TouchPoint> {
+        self.points.iter()
+    }
+
+    pub fn get(&self, pointer: PointerId) -> Option<&TouchPoint> {
+        self.points.iter().find(|point| point.pointer == pointer)
+    }
+
+    /// The average position of the touches.
+    pub fn centroid(&self) -> Option<Point> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let sum = self
+            .points
+            .iter()
+            .fold(Vec2::ZERO, |sum, point| sum + point.pos.to_vec2());
+        Some((sum / self.points.len() as f64).to_point())
+    }
+
+    /// The scale, rotation and translation of the first two touches since they were placed.
+    pub fn pinch(&self) -> Option<Pinch> {
+        let [a, b, ..] = self.points.as_slice() else {
+            return None;
+        };
+        let start = b.start - a.start;
+        let now = b.pos - a.pos;
+        let start_center = a.start.midpoint(b.start);
+        let center = a.pos.midpoint(b.pos);
+        let start_len = start.hypot();
+        Some(Pinch {
+            center,
+            scale: if start_len > 0. {
+                now.hypot() / start_len
+            } else {
+                1.
+            },
+            rotation: now.atan2() - start.atan2(),
+            translation: center - start_center,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_winit::event::TouchPhase;
+    use peniko::kurbo::{Point, Vec2};
+
+    use super::{PointerId, TouchEvent, Touches};
+
+    fn touch(pointer: u64, phase: TouchPhase, x: f64, y: f64) -> TouchEvent {
+        TouchEvent {
+            pointer: PointerId(pointer),
+            phase,
+            pos: Point::new(x, y),
+            force: None,
+        }
+    }
+
+    #[test]
+    fn pinch_from_two_touches() {
+        let mut touches = Touches::new();
+        touches.update(&touch(1, TouchPhase::Started, 0., 0.));
+        assert!(touches.pinch().is_none());
+        touches.update(&touch(2, TouchPhase::Started, 10., 0.));
+        touches.update(&touch(2, TouchPhase::Moved, 0., 20.));
+
+        let pinch = touches.pinch().unwrap();
+        assert_eq!(pinch.scale, 2.);
+        assert!((pinch.rotation - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        assert_eq!(pinch.translation, Vec2::new(-5., 10.));
+        assert_eq!(touches.centroid(), Some(Point::new(0., 10.)));
+    }
+
+    #[test]
+    fn lifting_a_touch_rebases_the_rest() {
+        let mut touches = Touches::new();
+        touches.update(&touch(1, TouchPhase::Started, 0., 0.));
+        touches.update(&touch(2, TouchPhase::Started, 10., 0.));
+        touches.update(&touch(1, TouchPhase::Moved, 5., 5.));
+        touches.update(&touch(2, TouchPhase::Ended, 10., 0.));
+
+        assert_eq!(touches.len(), 1);
+        let point = touches.get(PointerId(1)).unwrap();
+        assert_eq!(point.start, point.pos);
+    }
+}
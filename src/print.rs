@@ -0,0 +1,584 @@
+#![deny(missing_docs)]
+//! Printing views, and exporting them to PDF.
+//!
+//! [`export_pdf`] lays out a view to the width of a page and splits it into pages, while
+//! [`export_pdf_pages`] builds a view for each page. The documents are vector PDFs where one
+//! logical pixel is one point, i.e. 1/72 inch, and [`print`] passes them to the system to be
+//! printed.
+//!
+//! The views are laid out and painted without a window, so they aren't interactive and their
+//! animations are painted in their current state.
+
+use std::{cell::RefCell, io, path::Path, rc::Rc};
+
+use floem_pdf_renderer::PdfRenderer;
+use floem_reactive::{with_scope, Scope};
+use floem_renderer::{
+    text::{Attrs, AttrsList, TextLayout},
+    Renderer as _,
+};
+use peniko::{
+    kurbo::{Affine, Point, Rect, Size},
+    Color,
+};
+
+use crate::{
+    app_state::AppState,
+    context::{ComputeLayoutCx, LayoutCx, PaintCx, PaintState, StyleCx},
+    id::ViewId,
+    renderer::Renderer,
+    style::Style,
+    view::{AnyView, IntoView, View},
+};
+
+/// The font size of the headers and footers.
+const HEADER_FONT_SIZE: f32 = 9.0;
+
+/// The space between the headers and footers and the content of the page.
+const HEADER_SPACING: f64 = 8.0;
+
+thread_local! {
+    static PRINT_HANDLER: RefCell<Option<Rc<dyn Fn(&Path) -> io::Result<()>>>> =
+        const { RefCell::new(None) };
+}
+
+/// The size of a page, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageSize {
+    /// The width of the page.
+    pub width: f64,
+    /// The height of the page.
+    pub height: f64,
+}
+
+impl PageSize {
+    /// ISO A3, 297 × 420 mm.
+    pub const A3: PageSize = PageSize::new(841.89, 1190.55);
+    /// ISO A4, 210 × 297 mm.
+    pub const A4: PageSize = PageSize::new(595.28, 841.89);
+    /// ISO A5, 148 × 210 mm.
+    pub const A5: PageSize = PageSize::new(419.53, 595.28);
+    /// US Letter, 8.5 × 11 in.
+    pub const LETTER: PageSize = PageSize::new(612.0, 792.0);
+    /// US Legal, 8.5 × 14 in.
+    pub const LEGAL: PageSize = PageSize::new(612.0, 1008.0);
+
+    /// Creates a page size in points.
+    pub const fn new(width: f64, height: f64) -> Self {
+        Self { width, height }
+    }
+
+    /// Creates a page size in millimeters.
+    pub fn from_mm(width: f64, height: f64) -> Self {
+        Self::new(width * 72.0 / 25.4, height * 72.0 / 25.4)
+    }
+
+    /// Returns the page size with the longer side horizontal.
+    pub fn landscape(self) -> Self {
+        Self::new(self.width.max(self.height), self.width.min(self.height))
+    }
+
+    fn size(self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+/// The margins of a page, in points. The headers and footers are in the top and bottom margins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageMargins {
+    /// The top margin.
+    pub top: f64,
+    /// The right margin.
+    pub right: f64,
+    /// The bottom margin.
+    pub bottom: f64,
+    /// The left margin.
+    pub left: f64,
+}
+
+impl PageMargins {
+    /// Creates margins with each side set separately.
+    pub const fn new(top: f64, right: f64, bottom: f64, left: f64) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Creates margins that are the same on each side.
+    pub const fn all(margin: f64) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+}
+
+impl Default for PageMargins {
+    /// Half an inch on each side.
+    fn default() -> Self {
+        Self::all(36.0)
+    }
+}
+
+/// The page being printed, which is passed to the headers, footers and page views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageInfo {
+    /// The number of the page, starting at 1.
+    pub number: usize,
+    /// The number of pages in the document.
+    pub count: usize,
+}
+
+/// How a document is laid out, see [`export_pdf`].
+///
+/// ```rust
+/// # use floem::print::{PageMargins, PageSize, PrintOptions};
+/// let options = PrintOptions::new()
+///     .page_size(PageSize::LETTER.landscape())
+///     .margins(PageMargins::all(54.0))
+///     .title("Report")
+///     .footer(|page| format!("Page {} of {}", page.number, page.count));
+/// ```
+#[derive(Clone)]
+pub struct PrintOptions {
+    page_size: PageSize,
+    margins: PageMargins,
+    title: Option<String>,
+    header: Option<Rc<dyn Fn(PageInfo) -> String>>,
+    footer: Option<Rc<dyn Fn(PageInfo) -> String>>,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrintOptions {
+    /// Creates options for A4 pages with the default margins, without headers and footers.
+    pub fn new() -> Self {
+        Self {
+            page_size: PageSize::A4,
+            margins: PageMargins::default(),
+            title: None,
+            header: None,
+            footer: None,
+        }
+    }
+
+    /// Sets the size of the pages.
+    pub fn page_size(mut self, page_size: PageSize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Sets the margins of the pages.
+    pub fn margins(mut self, margins: PageMargins) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Sets the title of the document, which is in its metadata.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the text centered in the top margin of each page.
+    pub fn header(mut self, header: impl Fn(PageInfo) -> String + 'static) -> Self {
+        self.header = Some(Rc::new(header));
+        self
+    }
+
+    /// Sets the text centered in the bottom margin of each page.
+    pub fn footer(mut self, footer: impl Fn(PageInfo) -> String + 'static) -> Self {
+        self.footer = Some(Rc::new(footer));
+        self
+    }
+
+    /// The area of the page inside the margins.
+    fn content_rect(&self) -> Rect {
+        let margins = self.margins;
+        Rect::new(
+            margins.left,
+            margins.top,
+            self.page_size.width - margins.right,
+            self.page_size.height - margins.bottom,
+        )
+    }
+}
+
+/// Exports the view built by `view` to a PDF document.
+///
+/// The view is laid out to the width of the page between the margins, and split into as many
+/// pages as its height needs.
+///
+/// ```no_run
+/// # use floem::print::{export_pdf, PrintOptions};
+/// # use floem::views::label;
+/// let document = export_pdf(|| label(|| "Hello"), &PrintOptions::new());
+/// std::fs::write("hello.pdf", document).unwrap();
+/// ```
+pub fn export_pdf<V: IntoView + 'static>(
+    view: impl FnOnce() -> V,
+    options: &PrintOptions,
+) -> Vec<u8> {
+    let content = options.content_rect();
+    let scope = Scope::new();
+    let view = with_scope(scope, || view().into_any());
+    let mut layout = PrintLayout::new(
+        view,
+        Style::new().width(content.width()),
+        content.size(),
+        scope,
+    );
+
+    let height = layout.id.layout_rect().height();
+    let count = ((height / content.height()).ceil() as usize).max(1);
+    let mut document = Document::new(options);
+    for index in 0..count {
+        let offset = index as f64 * content.height();
+        document.page(PageInfo {
+            number: index + 1,
+            count,
+        });
+        layout.paint(
+            &mut document.paint_state,
+            Point::new(content.x0, content.y0 - offset),
+            Rect::new(0.0, offset, content.width(), offset + content.height()),
+        );
+    }
+    document.finish()
+}
+
+/// Exports `count` pages to a PDF document, with the view of each page built by `page`.
+///
+/// The views have the size of the page between the margins.
+pub fn export_pdf_pages<V: IntoView + 'static>(
+    count: usize,
+    page: impl Fn(PageInfo) -> V,
+    options: &PrintOptions,
+) -> Vec<u8> {
+    let content = options.content_rect();
+    let mut document = Document::new(options);
+    for index in 0..count {
+        let info = PageInfo {
+            number: index + 1,
+            count,
+        };
+        let scope = Scope::new();
+        let view = with_scope(scope, || page(info).into_any());
+        let mut layout = PrintLayout::new(
+            view,
+            Style::new().size(content.width(), content.height()),
+            content.size(),
+            scope,
+        );
+        document.page(info);
+        layout.paint(
+            &mut document.paint_state,
+            content.origin(),
+            content.size().to_rect(),
+        );
+    }
+    document.finish()
+}
+
+/// Sets how [`print`] prints documents, instead of passing them to the system. The handler is
+/// called with the path of the PDF file to print.
+pub fn set_print_handler(handler: impl Fn(&Path) -> io::Result<()> + 'static) {
+    PRINT_HANDLER.with_borrow_mut(|current| *current = Some(Rc::new(handler)));
+}
+
+/// Prints a PDF document, e.g. one from [`export_pdf`].
+///
+/// The document is written to a temporary file named after `name`, which is passed to the
+/// handler set with [`set_print_handler`]. By default the file is passed to the system: on
+/// Windows it's printed by the application registered to print PDFs, which shows its print
+/// dialog, and on other platforms it's opened in the default PDF viewer to be printed from
+/// there.
+pub fn print(document: &[u8], name: &str) -> io::Result<()> {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = std::env::temp_dir().join(format!("{name}.pdf"));
+    std::fs::write(&path, document)?;
+    match PRINT_HANDLER.with_borrow(|handler| handler.clone()) {
+        Some(handler) => handler(&path),
+        None => print_native(&path),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn print_native(path: &Path) -> io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::UI::{Shell::ShellExecuteW, WindowsAndMessaging::SW_SHOWNORMAL};
+
+    let verb: Vec<u16> = "print\0".encode_utf16().collect();
+    let file: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let result = unsafe {
+        ShellExecuteW(
+            0,
+            verb.as_ptr(),
+            file.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+    // values up to 32 are errors
+    if result > 32 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn print_native(path: &Path) -> io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(opener)
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+}
+
+/// A PDF document being painted.
+struct Document<'a> {
+    options: &'a PrintOptions,
+    paint_state: PaintState,
+}
+
+impl<'a> Document<'a> {
+    fn new(options: &'a PrintOptions) -> Self {
+        let mut renderer = PdfRenderer::new();
+        if let Some(title) = options.title.as_ref() {
+            renderer.set_title(title);
+        }
+        Self {
+            options,
+            paint_state: PaintState::Initialized {
+                renderer: Renderer::Pdf(renderer),
+            },
+        }
+    }
+
+    fn renderer(&mut self) -> &mut PdfRenderer {
+        match self.paint_state.renderer_mut() {
+            Renderer::Pdf(renderer) => renderer,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Starts a page with its header and footer, clipping what's painted afterwards to the
+    /// content of the page.
+    fn page(&mut self, info: PageInfo) {
+        let options = self.options;
+        let content = options.content_rect();
+        let renderer = self.renderer();
+        renderer.begin_page(options.page_size.size());
+        renderer.begin(false);
+        if let Some(header) = options.header.as_ref() {
+            let text = text_layout(&header(info));
+            let y = content.y0 - HEADER_SPACING - text.size().height;
+            draw_centered(renderer, &text, content, y);
+        }
+        if let Some(footer) = options.footer.as_ref() {
+            let text = text_layout(&footer(info));
+            draw_centered(renderer, &text, content, content.y1 + HEADER_SPACING);
+        }
+        renderer.set_page_clip(Some(content));
+    }
+
+    fn finish(self) -> Vec<u8> {
+        match self.paint_state {
+            PaintState::Initialized {
+                renderer: Renderer::Pdf(renderer),
+            } => renderer.finish_document(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn text_layout(text: &str) -> TextLayout {
+    let attrs = Attrs::new()
+        .font_size(HEADER_FONT_SIZE)
+        .color(Color::rgb8(0x50, 0x50, 0x50));
+    TextLayout::new_with_text(text, AttrsList::new(attrs))
+}
+
+fn draw_centered(renderer: &mut PdfRenderer, text: &TextLayout, content: Rect, y: f64) {
+    let x = content.x0 + (content.width() - text.size().width) / 2.0;
+    renderer.transform(Affine::IDENTITY);
+    renderer.draw_text(text, Point::new(x, y));
+}
+
+/// A view tree that is laid out without a window.
+struct PrintLayout {
+    id: ViewId,
+    scope: Scope,
+    app_state: AppState,
+}
+
+impl PrintLayout {
+    fn new(view: AnyView, style: Style, size: Size, scope: Scope) -> Self {
+        let id = ViewId::new();
+        id.set_children(vec![view]);
+        id.set_view(PrintRoot { id, style }.into_any());
+
+        let mut app_state = AppState::new(id);
+        app_state.set_root_size(size);
+        StyleCx::new(&mut app_state, id).style_view(id);
+        let mut cx = LayoutCx::new(&mut app_state);
+        cx.app_state_mut().root = {
+            let view = id.view();
+            let mut view = view.borrow_mut();
+            Some(cx.layout_view(view.as_mut()))
+        };
+        app_state.compute_layout();
+        ComputeLayoutCx::new(&mut app_state, size.to_rect()).compute_view_layout(id);
+
+        Self {
+            id,
+            scope,
+            app_state,
+        }
+    }
+
+    /// Paints the views with their origin at `origin` on the page, skipping the views outside of
+    /// `visible`, which is in the coordinates of the views.
+    fn paint(&mut self, paint_state: &mut PaintState, origin: Point, visible: Rect) {
+        let transform = Affine::translate(origin.to_vec2());
+        paint_state.renderer_mut().transform(transform);
+        let mut cx = PaintCx {
+            app_state: &mut self.app_state,
+            paint_state,
+            transform,
+            clip: Some(visible.to_rounded_rect(0.0)),
+            z_index: None,
+            saved_transforms: Vec::new(),
+            saved_clips: Vec::new(),
+            saved_z_indexes: Vec::new(),
+        };
+        cx.paint_view(self.id);
+    }
+}
+
+impl Drop for PrintLayout {
+    fn drop(&mut self) {
+        self.app_state.remove_view(self.id);
+        self.scope.dispose();
+    }
+}
+
+/// The root of a [`PrintLayout`], which sizes its view to the page.
+struct PrintRoot {
+    id: ViewId,
+    style: Style,
+}
+
+impl View for PrintRoot {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(self.style.clone())
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Print".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use peniko::kurbo::Rect;
+
+    use super::{export_pdf, export_pdf_pages, PageInfo, PageMargins, PageSize, PrintOptions};
+    use crate::views::{empty, Decorators};
+
+    fn page_count(document: &[u8]) -> usize {
+        lopdf::Document::load_mem(document)
+            .unwrap()
+            .get_pages()
+            .len()
+    }
+
+    fn assert_size(size: PageSize, width: f64, height: f64) {
+        assert!(
+            (size.width - width).abs() < 0.01 && (size.height - height).abs() < 0.01,
+            "{size:?} isn't {width} × {height}"
+        );
+    }
+
+    #[test]
+    fn page_sizes() {
+        assert_size(PageSize::from_mm(210.0, 297.0), 595.28, 841.89);
+        assert_size(PageSize::from_mm(25.4, 50.8), 72.0, 144.0);
+        assert_eq!(PageSize::A4.landscape(), PageSize::new(841.89, 595.28));
+        assert_eq!(
+            PageSize::A4.landscape().landscape(),
+            PageSize::A4.landscape()
+        );
+    }
+
+    #[test]
+    fn content_is_inside_the_margins() {
+        let options = PrintOptions::new()
+            .page_size(PageSize::LETTER)
+            .margins(PageMargins::new(10.0, 20.0, 30.0, 40.0));
+        assert_eq!(options.content_rect(), Rect::new(40.0, 10.0, 592.0, 762.0));
+        let options = PrintOptions::new().page_size(PageSize::LETTER.landscape());
+        assert_eq!(options.content_rect(), Rect::new(36.0, 36.0, 756.0, 576.0));
+    }
+
+    #[test]
+    fn tall_views_are_split_into_pages() {
+        // the content of the pages is 200 high
+        let options = PrintOptions::new()
+            .page_size(PageSize::new(200.0, 300.0))
+            .margins(PageMargins::all(50.0));
+        for (height, pages) in [(0.0, 1), (200.0, 1), (201.0, 2), (500.0, 3)] {
+            let document = export_pdf(move || empty().style(move |s| s.height(height)), &options);
+            assert_eq!(page_count(&document), pages, "a view {height} high");
+        }
+    }
+
+    #[test]
+    fn each_page_gets_its_own_view() {
+        let pages = RefCell::new(Vec::new());
+        let options =
+            PrintOptions::new().footer(|page| format!("{} of {}", page.number, page.count));
+        let document = export_pdf_pages(
+            3,
+            |page| {
+                pages.borrow_mut().push(page);
+                empty()
+            },
+            &options,
+        );
+        assert_eq!(page_count(&document), 3);
+        assert_eq!(
+            *pages.borrow(),
+            [1, 2, 3].map(|number| PageInfo { number, count: 3 })
+        );
+
+        // a document always has a page
+        let document = export_pdf_pages(0, |_| empty(), &options);
+        assert_eq!(page_count(&document), 1);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::app::{add_app_update_event, AppUpdateEvent};
 use crate::event::{Event, EventListener, EventPropagation};
+use crate::id::ViewId;
 use crate::inspector::header;
 use crate::view::IntoView;
 use crate::views::{
@@ -9,8 +10,12 @@ use crate::views::{
 use floem_reactive::{create_rw_signal, RwSignal, Scope, SignalGet, SignalUpdate};
 use floem_winit::window::WindowId;
 use peniko::Color;
+use slotmap::Key;
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::mem;
+use std::ops::AddAssign;
 use std::rc::Rc;
 use taffy::style::FlexDirection;
 
@@ -26,9 +31,41 @@ pub struct ProfileEvent {
     pub name: &'static str,
 }
 
+/// A pass over a view, or over the whole window if `view` is `None`.
+#[derive(Clone)]
+pub struct ProfileSpan {
+    pub start: Instant,
+    pub end: Instant,
+    /// The number of spans that this span is nested in.
+    pub depth: usize,
+    pub pass: &'static str,
+    pub view: Option<(ViewId, String)>,
+}
+
+/// How well the layout was kept between the layout passes.
+#[derive(Clone, Copy, Default)]
+pub struct LayoutCacheStats {
+    /// The views laid out with the same style and children, which keep their cached layout.
+    pub hits: u64,
+    /// The views laid out with a new style or new children, which are laid out again.
+    pub misses: u64,
+    /// The layout passes where nothing changed, which kept the whole layout.
+    pub skipped: u64,
+}
+
+impl AddAssign for LayoutCacheStats {
+    fn add_assign(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.skipped += other.skipped;
+    }
+}
+
 #[derive(Default)]
 pub struct ProfileFrame {
     pub events: Vec<ProfileEvent>,
+    pub spans: Vec<ProfileSpan>,
+    pub layout_cache: LayoutCacheStats,
 }
 
 #[derive(Default)]
@@ -43,11 +80,184 @@ impl Profile {
     }
 }
 
+thread_local! {
+    /// The spans of the window being profiled, which are recorded while one of its window
+    /// events is handled.
+    static SPANS: RefCell<Option<SpanRecorder>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct SpanRecorder {
+    spans: Vec<ProfileSpan>,
+    /// The number of spans that haven't ended.
+    open: usize,
+    layout_cache: LayoutCacheStats,
+}
+
+/// Starts recording the spans of the passes, until they are returned by [`finish_recording`].
+pub(crate) fn start_recording() {
+    SPANS.with_borrow_mut(|spans| *spans = Some(SpanRecorder::default()));
+}
+
+/// Stops recording the spans of the passes, and returns them with the use of the layout cache.
+pub(crate) fn finish_recording() -> (Vec<ProfileSpan>, LayoutCacheStats) {
+    SPANS
+        .with_borrow_mut(Option::take)
+        .map(|recorder| (recorder.spans, recorder.layout_cache))
+        .unwrap_or_default()
+}
+
+/// Counts a use of the layout cache with `record` while the window is profiled.
+pub(crate) fn record_layout_cache(record: impl FnOnce(&mut LayoutCacheStats)) {
+    SPANS.with_borrow_mut(|recorder| {
+        if let Some(recorder) = recorder {
+            record(&mut recorder.layout_cache);
+        }
+    });
+}
+
+fn record(pass: &'static str, view: Option<(ViewId, String)>) -> Option<usize> {
+    SPANS.with_borrow_mut(|recorder| {
+        let recorder = recorder.as_mut()?;
+        let now = Instant::now();
+        recorder.spans.push(ProfileSpan {
+            start: now,
+            end: now,
+            depth: recorder.open,
+            pass,
+            view,
+        });
+        recorder.open += 1;
+        Some(recorder.spans.len() - 1)
+    })
+}
+
+/// A pass that lasts until this is dropped. It's entered as a `tracing` span with the `tracing`
+/// feature, and recorded for the profiler while it's profiling the window.
+pub(crate) struct PassSpan {
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+    recorded: Option<usize>,
+}
+
+impl Drop for PassSpan {
+    fn drop(&mut self) {
+        let Some(index) = self.recorded else {
+            return;
+        };
+        SPANS.with_borrow_mut(|recorder| {
+            if let Some(recorder) = recorder {
+                recorder.open = recorder.open.saturating_sub(1);
+                if let Some(span) = recorder.spans.get_mut(index) {
+                    span.end = Instant::now();
+                }
+            }
+        });
+    }
+}
+
+/// Starts the pass `pass` over the whole window.
+pub(crate) fn pass_span(pass: &'static str) -> PassSpan {
+    PassSpan {
+        #[cfg(feature = "tracing")]
+        _span: tracing::debug_span!("pass", pass).entered(),
+        recorded: record(pass, None),
+    }
+}
+
+/// Starts the pass `pass` over the view `id`. The type of the view is only named by `name` if
+/// the span is recorded, as the passes run for every view.
+pub(crate) fn view_span(
+    pass: &'static str,
+    id: ViewId,
+    name: impl FnOnce() -> Cow<'static, str>,
+) -> PassSpan {
+    #[cfg(feature = "tracing")]
+    let span = tracing::trace_span!(
+        "view",
+        pass,
+        id = id.data().as_ffi(),
+        kind = tracing::field::Empty
+    );
+    #[cfg(feature = "tracing")]
+    let traced = !span.is_disabled();
+    #[cfg(not(feature = "tracing"))]
+    let traced = false;
+    let recording = SPANS.with_borrow(Option::is_some);
+    let kind = (recording || traced).then(|| crate::debug_tree::kind(&name()).to_string());
+    #[cfg(feature = "tracing")]
+    if let Some(kind) = &kind {
+        span.record("kind", kind.as_str());
+    }
+    let recorded = kind
+        .filter(|_| recording)
+        .and_then(|kind| record(pass, Some((id, kind))));
+    PassSpan {
+        #[cfg(feature = "tracing")]
+        _span: span.entered(),
+        recorded,
+    }
+}
+
 struct ProfileFrameData {
     start: Option<Instant>,
     duration: Duration,
     sum: Duration,
     events: Vec<ProfileEvent>,
+    spans: Vec<ProfileSpan>,
+    layout_cache: LayoutCacheStats,
+}
+
+#[derive(Clone)]
+enum Hovered {
+    Event(ProfileEvent),
+    Span(ProfileSpan),
+}
+
+/// The height of a row of the timeline.
+const ROW_HEIGHT: f64 = 24.0;
+
+fn pass_color(pass: &'static str) -> Color {
+    match pass {
+        "style" => Color::rgb8(209, 222, 233),
+        "layout" => Color::rgb8(218, 233, 209),
+        "paint" => Color::rgb8(240, 222, 200),
+        _ => Color::rgb8(225, 212, 236),
+    }
+}
+
+/// A bar of the timeline of `frame` from `start` to `end`.
+fn timeline_bar(
+    frame: &ProfileFrameData,
+    start: Instant,
+    end: Instant,
+    top: f64,
+    text: String,
+    color: Color,
+) -> impl IntoView {
+    let duration = frame.duration.as_secs_f64();
+    let len = end.saturating_duration_since(start).as_secs_f64();
+    let left = start
+        .saturating_duration_since(frame.start.unwrap())
+        .as_secs_f64()
+        / duration;
+    let width = len / duration;
+    clip(static_label(text).style(|s| s.padding(5.0))).style(move |s| {
+        s.min_width(0)
+            .height(ROW_HEIGHT)
+            .width_pct(width * 100.0)
+            .absolute()
+            .inset_top(top)
+            .inset_left_pct(left * 100.0)
+            .border(0.3)
+            .border_color(Color::rgb8(129, 164, 192))
+            .background(color.multiply_alpha(0.6))
+            .text_clip()
+            .hover(|s| {
+                s.color(Color::WHITE)
+                    .background(Color::BLACK.multiply_alpha(0.6))
+            })
+    })
 }
 
 fn info(name: impl Display, value: String) -> impl IntoView {
@@ -87,12 +297,20 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
                 duration,
                 sum,
                 events: frame.events.clone(),
+                spans: frame.spans.clone(),
+                layout_cache: frame.layout_cache,
             })
         })
         .collect();
+    // the latest frame is shown first, to find what made the app slow just before profiling ended
+    let latest_frame = frames
+        .iter()
+        .rev()
+        .find(|frame| frame.start.is_some())
+        .cloned();
     frames.sort_by(|a, b| b.sum.cmp(&a.sum));
 
-    let selected_frame = create_rw_signal(None);
+    let selected_frame = create_rw_signal(latest_frame);
 
     let zoom = create_rw_signal(1.0);
 
@@ -126,12 +344,12 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
         })
         .collect();
 
-    let hovered_event: RwSignal<Option<ProfileEvent>> = create_rw_signal(None);
+    let hovered_event: RwSignal<Option<Hovered>> = create_rw_signal(None);
 
     let event_tooltip = dyn_container(
         move || hovered_event.get(),
-        move |hovered_event| {
-            if let Some(event) = hovered_event {
+        move |hovered_event| match hovered_event {
+            Some(Hovered::Event(event)) => {
                 let len = event
                     .end
                     .saturating_duration_since(event.start)
@@ -141,15 +359,52 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
                     info("Time", format!("{:.4} ms", len * 1000.0)),
                 ))
                 .into_any()
-            } else {
-                text("No hovered event")
-                    .style(|s| s.padding(5.0))
-                    .into_any()
             }
+            Some(Hovered::Span(span)) => {
+                let len = span.end.saturating_duration_since(span.start).as_secs_f64();
+                let view = match span.view {
+                    Some((id, kind)) => format!("{kind} {}", id.data().as_ffi()),
+                    None => "Window".to_string(),
+                };
+                v_stack((
+                    info("Pass", span.pass.to_string()),
+                    info("View", view),
+                    info("Time", format!("{:.4} ms", len * 1000.0)),
+                ))
+                .into_any()
+            }
+            None => text("No hovered event")
+                .style(|s| s.padding(5.0))
+                .into_any(),
         },
     )
     .style(|s| s.min_height(50));
 
+    let layout_cache = dyn_container(
+        move || selected_frame.get(),
+        |selected_frame| match selected_frame {
+            Some(frame) => {
+                let stats = frame.layout_cache;
+                let views = stats.hits + stats.misses;
+                let rate = if views > 0 {
+                    stats.hits as f64 / views as f64 * 100.0
+                } else {
+                    0.0
+                };
+                v_stack((
+                    info("Kept", format!("{} views", stats.hits)),
+                    info("Changed", format!("{} views", stats.misses)),
+                    info("Hit rate", format!("{rate:.1}%")),
+                    info("Skipped", format!("{} layouts", stats.skipped)),
+                ))
+                .into_any()
+            }
+            None => text("No selected frame")
+                .style(|s| s.padding(5.0))
+                .into_any(),
+        },
+    );
+
     let frames = v_stack((
         header("Frames"),
         scroll(v_stack_from_iter(frames).style(|s| s.width_full())).style(|s| {
@@ -158,6 +413,8 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
                 .min_height(0)
                 .flex_grow(1.0)
         }),
+        header("Layout Cache"),
+        layout_cache,
         header("Event"),
         event_tooltip,
     ))
@@ -172,45 +429,53 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
     let timeline = dyn_container(
         move || selected_frame.get(),
         move |selected_frame| {
-            if let Some(frame) = selected_frame {
-                let list = frame.events.iter().map(|event| {
-                    let len = event
-                        .end
-                        .saturating_duration_since(event.start)
-                        .as_secs_f64();
-                    let left = event
-                        .start
-                        .saturating_duration_since(frame.start.unwrap())
-                        .as_secs_f64()
-                        / frame.duration.as_secs_f64();
-                    let width = len / frame.duration.as_secs_f64();
+            if let Some(frame) = selected_frame.filter(|frame| frame.start.is_some()) {
+                let events = frame.events.iter().map(|event| {
+                    let len = event.end.saturating_duration_since(event.start);
                     let event_ = event.clone();
-                    clip(
-                        static_label(format!("{} ({:.4} ms)", event.name, len * 1000.0))
-                            .style(|s| s.padding(5.0)),
+                    timeline_bar(
+                        &frame,
+                        event.start,
+                        event.end,
+                        0.0,
+                        format!("{} ({:.4} ms)", event.name, len.as_secs_f64() * 1000.0),
+                        Color::rgb8(209, 222, 233),
                     )
-                    .style(move |s| {
-                        s.min_width(0)
-                            .width_pct(width * 100.0)
-                            .absolute()
-                            .inset_left_pct(left * 100.0)
-                            .border(0.3)
-                            .border_color(Color::rgb8(129, 164, 192))
-                            .background(Color::rgb8(209, 222, 233).multiply_alpha(0.6))
-                            .text_clip()
-                            .hover(|s| {
-                                s.color(Color::WHITE)
-                                    .background(Color::BLACK.multiply_alpha(0.6))
-                            })
+                    .on_event_cont(EventListener::PointerEnter, move |_| {
+                        hovered_event.set(Some(Hovered::Event(event_.clone())))
                     })
+                    .into_any()
+                });
+                // the flame chart of the passes is laid out below the events, which contain them
+                let spans = frame.spans.iter().map(|span| {
+                    let name = match &span.view {
+                        Some((_, kind)) => format!("{} {kind}", span.pass),
+                        None => span.pass.to_string(),
+                    };
+                    let span_ = span.clone();
+                    timeline_bar(
+                        &frame,
+                        span.start,
+                        span.end,
+                        (span.depth + 1) as f64 * ROW_HEIGHT,
+                        name,
+                        pass_color(span.pass),
+                    )
                     .on_event_cont(EventListener::PointerEnter, move |_| {
-                        hovered_event.set(Some(event_.clone()))
+                        hovered_event.set(Some(Hovered::Span(span_.clone())))
                     })
+                    .into_any()
                 });
-                scroll(
-                    v_stack_from_iter(list)
-                        .style(move |s| s.min_width_pct(zoom.get() * 100.0).height_full()),
-                )
+                let rows = frame
+                    .spans
+                    .iter()
+                    .map(|span| span.depth + 2)
+                    .max()
+                    .unwrap_or(1);
+                scroll(v_stack_from_iter(events.chain(spans)).style(move |s| {
+                    s.min_width_pct(zoom.get() * 100.0)
+                        .height(rows as f64 * ROW_HEIGHT)
+                }))
                 .style(|s| s.height_full().min_width(0).flex_basis(0).flex_grow(1.0))
                 .on_event(EventListener::PointerWheel, move |e| {
                     if let Event::PointerWheel(e) = e {
@@ -236,7 +501,7 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
             .background(Color::WHITE)
     });
 
-    let timeline = v_stack((header("Timeline"), timeline))
+    let timeline = v_stack((header("Timeline and Flame Chart"), timeline))
         .style(|s| s.min_width(0).flex_basis(0).flex_grow(1.0));
 
     h_stack((frames, separator, timeline)).style(|s| s.height_full().width_full().max_width_full())
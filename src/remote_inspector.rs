@@ -0,0 +1,331 @@
+#![deny(missing_docs)]
+//! Inspecting the views of an application from another process.
+//!
+//! [`serve`] starts a server on a TCP address that answers [`Request`]s about the windows of the
+//! application with [`Response`]s: the view tree with the styles of the views, and the
+//! performance counters of a window. The `remote-inspector` example is an inspector that
+//! connects to it with a [`RemoteInspectorClient`], for apps whose built-in inspector can't be
+//! opened, like release builds running fullscreen on a device.
+//!
+//! Each message is a single line of JSON, so the protocol can also be bridged to a WebSocket.
+//! The server exposes the whole view tree to anyone who can connect to it, so it should only be
+//! bound to a local address, and only in builds that are meant to be inspected.
+//!
+//! ```no_run
+//! # use floem::remote_inspector::serve;
+//! # use floem::views::label;
+//! // the server stops when it is dropped, so it's kept until the app exits
+//! let _server = serve("127.0.0.1:7878").expect("failed to start the remote inspector");
+//! floem::launch(|| label(|| "Inspect me"));
+//! ```
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use floem_reactive::SignalGet;
+use floem_winit::window::WindowId;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{Application, UserEvent},
+    debug_tree::{DebugTree, DebugTreeOptions},
+    id::ViewId,
+    window_handle::WindowHandle,
+};
+
+/// How long a connection waits for the application to answer a request.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A request sent to the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// Lists the open windows.
+    Windows,
+    /// Gets the view tree of a window.
+    Tree {
+        /// The id of the window, from [`RemoteWindow::id`].
+        window: u64,
+        /// Whether the style properties of the views are included.
+        #[serde(default)]
+        style: bool,
+    },
+    /// Gets the performance counters of a window.
+    Counters {
+        /// The id of the window, from [`RemoteWindow::id`].
+        window: u64,
+    },
+}
+
+/// The answer of the server to a [`Request`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum Response {
+    /// The open windows.
+    Windows(Vec<RemoteWindow>),
+    /// The view tree of a window.
+    Tree(DebugTree),
+    /// The performance counters of a window.
+    Counters(PerfCounters),
+    /// The request couldn't be answered.
+    Error(String),
+}
+
+/// A window of the inspected application.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteWindow {
+    /// The id of the window, used in the requests about it.
+    pub id: u64,
+    /// The title of the window.
+    pub title: String,
+    /// The logical width of the window.
+    pub width: f64,
+    /// The logical height of the window.
+    pub height: f64,
+    /// Whether the window has the keyboard focus.
+    pub focused: bool,
+}
+
+/// The number of style, layout and paint passes of a window, and how long the latest ones took.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerfCounters {
+    /// The number of views in the window.
+    pub views: usize,
+    /// The number of frames painted.
+    pub frames: u64,
+    /// The number of style passes.
+    pub style_passes: u64,
+    /// The number of layout passes.
+    pub layout_passes: u64,
+    /// How long the latest style pass took, in milliseconds.
+    pub last_style_ms: f64,
+    /// How long the latest layout pass took, in milliseconds.
+    pub last_layout_ms: f64,
+    /// How long the latest paint took, in milliseconds.
+    pub last_paint_ms: f64,
+}
+
+/// A running server started with [`serve`], which stops when it is dropped.
+#[derive(Debug)]
+pub struct RemoteInspector {
+    addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+}
+
+impl RemoteInspector {
+    /// The address that the server listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for RemoteInspector {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        // wake up the listener, which is waiting for a connection
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+/// Starts a server on `addr` that answers the [`Request`]s of remote inspectors, until the
+/// returned [`RemoteInspector`] is dropped.
+///
+/// The requests are answered by the event loop of the application, so they're only answered
+/// once the application is running.
+pub fn serve(addr: impl ToSocketAddrs) -> io::Result<RemoteInspector> {
+    let listener = TcpListener::bind(addr)?;
+    let addr = listener.local_addr()?;
+    let stopped = Arc::new(AtomicBool::new(false));
+    {
+        let stopped = stopped.clone();
+        thread::Builder::new()
+            .name("floem-remote-inspector".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if stopped.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    let stopped = stopped.clone();
+                    thread::spawn(move || {
+                        let _ = serve_connection(stream, &stopped);
+                    });
+                }
+            })?;
+    }
+    Ok(RemoteInspector { addr, stopped })
+}
+
+fn serve_connection(stream: TcpStream, stopped: &AtomicBool) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        if stopped.load(Ordering::Relaxed) {
+            break;
+        }
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => ask_app(request),
+            Err(err) => Response::Error(format!("invalid request: {err}")),
+        };
+        let mut message = serde_json::to_string(&response).map_err(io::Error::other)?;
+        message.push('\n');
+        writer.write_all(message.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Sends `request` to the event loop and waits for its answer.
+fn ask_app(request: Request) -> Response {
+    let (reply, response) = mpsc::channel();
+    let mut sent = false;
+    Application::with_event_loop_proxy(|proxy| {
+        sent = proxy
+            .send_event(UserEvent::RemoteInspector(PendingRequest {
+                request,
+                reply,
+            }))
+            .is_ok();
+    });
+    if !sent {
+        return Response::Error("the application isn't running".to_string());
+    }
+    response
+        .recv_timeout(RESPONSE_TIMEOUT)
+        .unwrap_or_else(|_| Response::Error("the application didn't respond".to_string()))
+}
+
+/// A request waiting to be answered by the event loop.
+#[derive(Debug)]
+pub(crate) struct PendingRequest {
+    request: Request,
+    reply: mpsc::Sender<Response>,
+}
+
+impl PendingRequest {
+    /// Answers the request with the state of the windows in `handles`.
+    pub(crate) fn answer(self, handles: &HashMap<WindowId, WindowHandle>) {
+        let _ = self.reply.send(respond(self.request, handles));
+    }
+}
+
+fn respond(request: Request, handles: &HashMap<WindowId, WindowHandle>) -> Response {
+    let window = |id: u64| handles.get(&WindowId::from(id));
+    let no_window = |id: u64| Response::Error(format!("there is no window {id}"));
+    match request {
+        Request::Windows => {
+            let windows = Application::windows()
+                .into_iter()
+                .filter_map(|info| {
+                    let handle = handles.get(&info.id)?;
+                    let size = handle.size.get_untracked();
+                    Some(RemoteWindow {
+                        id: u64::from(info.id),
+                        title: info.title,
+                        width: size.width,
+                        height: size.height,
+                        focused: info.focused,
+                    })
+                })
+                .collect();
+            Response::Windows(windows)
+        }
+        Request::Tree { window: id, style } => match window(id) {
+            Some(handle) => Response::Tree(
+                handle
+                    .id
+                    .debug_tree_with(&DebugTreeOptions::new().style(style)),
+            ),
+            None => no_window(id),
+        },
+        Request::Counters { window: id } => match window(id) {
+            Some(handle) => {
+                let counters = handle.counters;
+                Response::Counters(PerfCounters {
+                    views: count_views(handle.id),
+                    frames: counters.frames,
+                    style_passes: counters.style_passes,
+                    layout_passes: counters.layout_passes,
+                    last_style_ms: counters.last_style.as_secs_f64() * 1000.0,
+                    last_layout_ms: counters.last_layout.as_secs_f64() * 1000.0,
+                    last_paint_ms: counters.last_paint.as_secs_f64() * 1000.0,
+                })
+            }
+            None => no_window(id),
+        },
+    }
+}
+
+fn count_views(id: ViewId) -> usize {
+    1 + id.children().into_iter().map(count_views).sum::<usize>()
+}
+
+/// A connection to the server of an application started with [`serve`].
+#[derive(Debug)]
+pub struct RemoteInspectorClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl RemoteInspectorClient {
+    /// Connects to the server listening on `addr`.
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let writer = TcpStream::connect(addr)?;
+        writer.set_read_timeout(Some(RESPONSE_TIMEOUT * 2))?;
+        let reader = BufReader::new(writer.try_clone()?);
+        Ok(Self { reader, writer })
+    }
+
+    /// Sends `request` to the server and waits for its response.
+    pub fn request(&mut self, request: &Request) -> io::Result<Response> {
+        let mut message = serde_json::to_string(request).map_err(io::Error::other)?;
+        message.push('\n');
+        self.writer.write_all(message.as_bytes())?;
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        serde_json::from_str(&line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_single_lines_of_json() {
+        let request = Request::Tree {
+            window: 3,
+            style: true,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"type":"tree","window":3,"style":true}"#);
+        assert_eq!(serde_json::from_str::<Request>(&json).unwrap(), request);
+        assert_eq!(
+            serde_json::from_str::<Request>(r#"{"type":"tree","window":3}"#).unwrap(),
+            Request::Tree {
+                window: 3,
+                style: false
+            }
+        );
+
+        let response = Response::Error("there is no window 3".to_string());
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains('\n'));
+        assert_eq!(serde_json::from_str::<Response>(&json).unwrap(), response);
+    }
+}
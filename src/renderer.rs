@@ -48,6 +48,8 @@
 //! - Only one view can be focused at a time.
 //!
 use crate::kurbo::Point;
+#[cfg(feature = "print")]
+use floem_pdf_renderer::PdfRenderer;
 use floem_renderer::gpu_resources::GpuResources;
 use floem_renderer::text::LayoutRun;
 use floem_renderer::Img;
@@ -66,6 +68,9 @@ pub enum Renderer<W> {
     #[cfg(not(feature = "vello"))]
     Vger(VgerRenderer),
     TinySkia(TinySkiaRenderer<W>),
+    /// Renders into a PDF document, for printing
+    #[cfg(feature = "print")]
+    Pdf(PdfRenderer),
     /// Uninitialized renderer, used to allow the renderer to be created lazily
     /// All operations on this renderer are no-ops
     Uninitialized {
@@ -150,6 +155,8 @@ impl<W: wgpu::WindowHandle> Renderer<W> {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.resize(size.width as u32, size.height as u32, scale),
             Renderer::TinySkia(r) => r.resize(size.width as u32, size.height as u32, scale),
+            #[cfg(feature = "print")]
+            Renderer::Pdf(_) => {}
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -161,6 +168,8 @@ impl<W: wgpu::WindowHandle> Renderer<W> {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.set_scale(scale),
             Renderer::TinySkia(r) => r.set_scale(scale),
+            #[cfg(feature = "print")]
+            Renderer::Pdf(_) => {}
             Renderer::Uninitialized {
                 scale: old_scale, ..
             } => {
@@ -176,6 +185,8 @@ impl<W: wgpu::WindowHandle> Renderer<W> {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.scale(),
             Renderer::TinySkia(r) => r.scale(),
+            #[cfg(feature = "print")]
+            Renderer::Pdf(_) => 1.0,
             Renderer::Uninitialized { scale, .. } => *scale,
         }
     }
@@ -187,6 +198,8 @@ impl<W: wgpu::WindowHandle> Renderer<W> {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.size(),
             Renderer::TinySkia(r) => r.size(),
+            #[cfg(feature = "print")]
+            Renderer::Pdf(r) => r.page_size(),
             Renderer::Uninitialized { size, .. } => *size,
         }
     }
@@ -206,6 +219,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(r) => {
                 r.begin(capture);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(r) => {
+                r.begin(capture);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -223,6 +240,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.clip(shape);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.clip(shape);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -240,6 +261,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.clear_clip();
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.clear_clip();
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -262,6 +287,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.stroke(shape, brush, stroke);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.stroke(shape, brush, stroke);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -284,6 +313,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.fill(path, brush, blur_radius);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.fill(path, brush, blur_radius);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -305,6 +338,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.draw_text_with_layout(layout, pos);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.draw_text_with_layout(layout, pos);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -322,6 +359,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.draw_img(img, rect);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.draw_img(img, rect);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -344,6 +385,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.draw_svg(svg, rect, brush);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.draw_svg(svg, rect, brush);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -361,6 +406,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.transform(transform);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.transform(transform);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -378,6 +427,10 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             Renderer::TinySkia(v) => {
                 v.set_z_index(z_index);
             }
+            #[cfg(feature = "print")]
+            Renderer::Pdf(v) => {
+                v.set_z_index(z_index);
+            }
             Renderer::Uninitialized { .. } => {}
         }
     }
@@ -389,6 +442,8 @@ impl<W: wgpu::WindowHandle> floem_renderer::Renderer for Renderer<W> {
             #[cfg(not(feature = "vello"))]
             Renderer::Vger(r) => r.finish(),
             Renderer::TinySkia(r) => r.finish(),
+            #[cfg(feature = "print")]
+            Renderer::Pdf(r) => r.finish(),
             Renderer::Uninitialized { .. } => None,
         }
     }
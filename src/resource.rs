@@ -0,0 +1,166 @@
+#![deny(missing_docs)]
+//! Values that are loaded in the background, like the result of a query, see
+//! [`create_resource`].
+//!
+//! A [`Resource`] is read like a signal, and is `None` until it's loaded. The views that read
+//! resources while they're built can be wrapped in a [`suspense`](crate::views::suspense)
+//! boundary, which shows a fallback until they're all loaded.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use floem_reactive::{
+    create_effect, create_trigger, untrack, with_scope, RwSignal, Scope, SignalGet, SignalUpdate,
+    SignalWith, Trigger,
+};
+
+use crate::task::Task;
+
+thread_local! {
+    /// The suspense boundaries whose children are being built, innermost last.
+    static SUSPENSE_STACK: RefCell<Vec<SuspenseContext>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The loading states of the resources read while the children of a suspense boundary were
+/// built.
+#[derive(Clone, Default)]
+pub(crate) struct SuspenseContext {
+    loading: Rc<RefCell<Vec<RwSignal<bool>>>>,
+}
+
+impl SuspenseContext {
+    /// Builds the children of the boundary with `build`, collecting the resources they read.
+    pub(crate) fn collect<R>(&self, build: impl FnOnce() -> R) -> R {
+        SUSPENSE_STACK.with_borrow_mut(|stack| stack.push(self.clone()));
+        let result = build();
+        SUSPENSE_STACK.with_borrow_mut(|stack| stack.pop());
+        result
+    }
+
+    /// Whether any of the resources is loading, which is tracked.
+    pub(crate) fn is_loading(&self) -> bool {
+        self.loading.borrow().iter().any(|loading| loading.get())
+    }
+}
+
+/// A value that is loaded in the background, created with [`create_resource`].
+pub struct Resource<T: 'static> {
+    value: RwSignal<Option<T>>,
+    loading: RwSignal<bool>,
+    refetch: Trigger,
+}
+
+impl<T> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Resource<T> {}
+
+/// Creates a resource that is loaded by the task that `fetch` starts with the value of
+/// `source`, e.g. with [`spawn_blocking`](crate::task::spawn_blocking).
+///
+/// The resource is loaded again whenever `source` changes, or when [`Resource::refetch`] is
+/// called. The results of earlier loads that finish late are dropped.
+///
+/// ```no_run
+/// # use floem::reactive::{RwSignal, SignalGet};
+/// # use floem::resource::create_resource;
+/// # use floem::task::spawn_blocking;
+/// # use floem::views::{label, suspense};
+/// let path = RwSignal::new("notes.txt".to_string());
+/// let notes = create_resource(
+///     move || path.get(),
+///     |path| spawn_blocking(move || std::fs::read_to_string(path).unwrap_or_default()),
+/// );
+/// suspense(
+///     || label(|| "Loading..."),
+///     move || label(move || notes.get().unwrap_or_default()),
+/// );
+/// ```
+pub fn create_resource<S, T>(
+    source: impl Fn() -> S + 'static,
+    fetch: impl Fn(S) -> Task<T> + 'static,
+) -> Resource<T>
+where
+    S: 'static,
+    T: Send + 'static,
+{
+    let value = RwSignal::new(None);
+    let loading = RwSignal::new(true);
+    let refetch = create_trigger();
+    let scope = Scope::current();
+    // the number of the latest load, so earlier ones that finish late are dropped
+    let version = Rc::new(Cell::new(0u64));
+    create_effect(move |_| {
+        refetch.track();
+        let source = source();
+        let current = version.get() + 1;
+        version.set(current);
+        if !loading.get_untracked() {
+            loading.set(true);
+        }
+        let task = untrack(|| fetch(source));
+        let version = version.clone();
+        with_scope(scope, move || {
+            task.on_complete(move |result| {
+                if version.get() == current {
+                    value.set(Some(result));
+                    loading.set(false);
+                }
+            })
+        });
+    });
+    Resource {
+        value,
+        loading,
+        refetch,
+    }
+}
+
+impl<T: 'static> Resource<T> {
+    /// Marks the resource as read by the suspense boundary that is being built, if any.
+    fn register(&self) {
+        SUSPENSE_STACK.with_borrow(|stack| {
+            if let Some(context) = stack.last() {
+                context.loading.borrow_mut().push(self.loading);
+            }
+        });
+    }
+
+    /// Calls `f` with the value, which is `None` until it's loaded for the first time. While
+    /// it's loaded again, it's the previous value.
+    pub fn with<R>(&self, f: impl FnOnce(&Option<T>) -> R) -> R {
+        self.register();
+        self.value.with(f)
+    }
+
+    /// Whether the resource is being loaded.
+    pub fn loading(&self) -> bool {
+        self.loading.get()
+    }
+
+    /// Loads the resource again.
+    pub fn refetch(&self) {
+        self.refetch.notify();
+    }
+}
+
+impl<T: Clone + 'static> Resource<T> {
+    /// The value, which is `None` until it's loaded for the first time. While it's loaded
+    /// again, it's the previous value.
+    pub fn get(&self) -> Option<T> {
+        self.with(Clone::clone)
+    }
+}
+
+impl<T: 'static, E: Clone + 'static> Resource<Result<T, E>> {
+    /// The error of the last load if it failed, which can be shown by the
+    /// [`error_view`](crate::views::DynStack::error_view) of a stack of the loaded items.
+    pub fn error(&self) -> Option<E> {
+        self.with(|value| value.as_ref()?.as_ref().err().cloned())
+    }
+}
@@ -1,6 +1,7 @@
 use std::ops::{BitOr, Range, RangeBounds, RangeFrom, RangeTo};
 
 use bitflags::bitflags;
+use peniko::kurbo::Size;
 
 bitflags! {
   #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
@@ -172,11 +173,186 @@ impl BitOr for ScreenSize {
     }
 }
 
+/// The window state that responsive styles and media queries are resolved against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ScreenInfo {
+    pub(crate) breakpoint: ScreenSizeBp,
+    /// The logical size of the window.
+    pub(crate) size: Size,
+    /// The scale factor of the window, including the application zoom.
+    pub(crate) scale: f64,
+    /// Whether the [forced colors mode](crate::forced_colors) is active.
+    pub(crate) forced_colors: bool,
+}
+
+/// The orientation of a window, as matched by [`MediaQuery::orientation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// The window is at least as tall as it is wide.
+    Portrait,
+    /// The window is wider than it is tall.
+    Landscape,
+}
+
+/// A condition on the size, scale factor and orientation of the window,
+/// used with [`Style::media`](crate::style::Style::media).
+///
+/// Queries are built with the free functions of this module and can be narrowed
+/// by chaining, e.g. `min_width(600.).max_width(1200.).orientation(Orientation::Landscape)`.
+/// All bounds are inclusive.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MediaQuery {
+    min_width: Option<f64>,
+    max_width: Option<f64>,
+    min_height: Option<f64>,
+    max_height: Option<f64>,
+    min_scale: Option<f64>,
+    max_scale: Option<f64>,
+    orientation: Option<Orientation>,
+    forced_colors: Option<bool>,
+}
+
+impl MediaQuery {
+    pub fn min_width(mut self, width: impl Into<f64>) -> Self {
+        self.min_width = Some(width.into());
+        self
+    }
+
+    pub fn max_width(mut self, width: impl Into<f64>) -> Self {
+        self.max_width = Some(width.into());
+        self
+    }
+
+    pub fn min_height(mut self, height: impl Into<f64>) -> Self {
+        self.min_height = Some(height.into());
+        self
+    }
+
+    pub fn max_height(mut self, height: impl Into<f64>) -> Self {
+        self.max_height = Some(height.into());
+        self
+    }
+
+    pub fn min_scale(mut self, scale: impl Into<f64>) -> Self {
+        self.min_scale = Some(scale.into());
+        self
+    }
+
+    pub fn max_scale(mut self, scale: impl Into<f64>) -> Self {
+        self.max_scale = Some(scale.into());
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    /// Matches when the [forced colors mode](crate::forced_colors) is `active` or not.
+    pub fn forced_colors(mut self, active: bool) -> Self {
+        self.forced_colors = Some(active);
+        self
+    }
+
+    pub(crate) fn matches(&self, screen: &ScreenInfo) -> bool {
+        let in_range = |value: f64, min: Option<f64>, max: Option<f64>| {
+            min.map_or(true, |min| value >= min) && max.map_or(true, |max| value <= max)
+        };
+        let orientation = if screen.size.width > screen.size.height {
+            Orientation::Landscape
+        } else {
+            Orientation::Portrait
+        };
+        in_range(screen.size.width, self.min_width, self.max_width)
+            && in_range(screen.size.height, self.min_height, self.max_height)
+            && in_range(screen.scale, self.min_scale, self.max_scale)
+            && self.orientation.map_or(true, |o| o == orientation)
+            && self
+                .forced_colors
+                .map_or(true, |forced| forced == screen.forced_colors)
+    }
+}
+
+/// Matches windows that are at least `width` logical pixels wide.
+pub fn min_width(width: impl Into<f64>) -> MediaQuery {
+    MediaQuery::default().min_width(width)
+}
+
+/// Matches windows that are at most `width` logical pixels wide.
+pub fn max_width(width: impl Into<f64>) -> MediaQuery {
+    MediaQuery::default().max_width(width)
+}
+
+/// Matches windows that are at least `height` logical pixels tall.
+pub fn min_height(height: impl Into<f64>) -> MediaQuery {
+    MediaQuery::default().min_height(height)
+}
+
+/// Matches windows that are at most `height` logical pixels tall.
+pub fn max_height(height: impl Into<f64>) -> MediaQuery {
+    MediaQuery::default().max_height(height)
+}
+
+/// Matches windows with a scale factor of at least `scale`.
+pub fn min_scale(scale: impl Into<f64>) -> MediaQuery {
+    MediaQuery::default().min_scale(scale)
+}
+
+/// Matches windows with a scale factor of at most `scale`.
+pub fn max_scale(scale: impl Into<f64>) -> MediaQuery {
+    MediaQuery::default().max_scale(scale)
+}
+
+/// Matches windows with the given orientation.
+pub fn orientation(orientation: Orientation) -> MediaQuery {
+    MediaQuery::default().orientation(orientation)
+}
+
+/// Matches when the [forced colors mode](crate::forced_colors) is `active` or not.
+pub fn forced_colors(active: bool) -> MediaQuery {
+    MediaQuery::default().forced_colors(active)
+}
+
 #[cfg(test)]
 mod tests {
+    use peniko::kurbo::Size;
+
     use crate::responsive::SizeFlags;
 
-    use super::{range, ScreenSize};
+    use super::{
+        max_width, min_scale, min_width, orientation, range, Orientation, ScreenInfo, ScreenSize,
+        ScreenSizeBp,
+    };
+
+    fn screen(width: f64, height: f64, scale: f64) -> ScreenInfo {
+        ScreenInfo {
+            breakpoint: ScreenSizeBp::Md,
+            size: Size::new(width, height),
+            scale,
+            forced_colors: false,
+        }
+    }
+
+    #[test]
+    fn media_query_width() {
+        let query = min_width(600.).max_width(1200.);
+        assert!(!query.matches(&screen(599., 500., 1.)));
+        assert!(query.matches(&screen(600., 500., 1.)));
+        assert!(query.matches(&screen(1200., 500., 1.)));
+        assert!(!query.matches(&screen(1201., 500., 1.)));
+        assert!(max_width(600.).matches(&screen(300., 500., 1.)));
+    }
+
+    #[test]
+    fn media_query_scale_and_orientation() {
+        assert!(min_scale(2.).matches(&screen(800., 600., 2.)));
+        assert!(!min_scale(2.).matches(&screen(800., 600., 1.5)));
+
+        let landscape = orientation(Orientation::Landscape);
+        assert!(landscape.matches(&screen(800., 600., 1.)));
+        assert!(!landscape.matches(&screen(600., 800., 1.)));
+        assert!(orientation(Orientation::Portrait).matches(&screen(600., 600., 1.)));
+    }
 
     #[test]
     fn range_full() {
@@ -0,0 +1,398 @@
+#![deny(missing_docs)]
+//! Navigating between the screens of an application.
+//!
+//! The screens are the values of a type implementing [`Route`], usually an enum with a variant
+//! for each screen and fields for its parameters. A [`Router`] keeps the history of the routes
+//! that were navigated to, and [`router_view`] shows the view of the current route.
+//!
+//! ```
+//! # use floem::router::{match_path, router_view, Route, Router};
+//! # use floem::views::{button, label, v_stack, Decorators};
+//! # use floem::IntoView;
+//! #[derive(Clone, PartialEq)]
+//! enum Screen {
+//!     Home,
+//!     User { id: u32 },
+//! }
+//!
+//! impl Route for Screen {
+//!     fn from_path(path: &str) -> Option<Self> {
+//!         if let Some(params) = match_path("/users/:id", path) {
+//!             return Some(Screen::User { id: params.parse("id")? });
+//!         }
+//!         (path == "/").then_some(Screen::Home)
+//!     }
+//!
+//!     fn to_path(&self) -> String {
+//!         match self {
+//!             Screen::Home => "/".to_string(),
+//!             Screen::User { id } => format!("/users/{id}"),
+//!         }
+//!     }
+//! }
+//!
+//! # fn app() -> impl IntoView {
+//! // opens the route of a link like `myapp://users/3` that the app was started with
+//! let router = Router::from_startup_link(Screen::Home);
+//! router_view(router, move |screen| match screen {
+//!     Screen::Home => button("Open user 3")
+//!         .on_click_stop(move |_| {
+//!             router.navigate(Screen::User { id: 3 });
+//!         })
+//!         .into_any(),
+//!     Screen::User { id } => v_stack((
+//!         label(move || format!("User {id}")),
+//!         button("Back").on_click_stop(move |_| {
+//!             router.back();
+//!         }),
+//!     ))
+//!     .into_any(),
+//! })
+//! # }
+//! ```
+
+use std::{rc::Rc, str::FromStr};
+
+use floem_reactive::{
+    create_memo, create_rw_signal, Memo, RwSignal, SignalGet, SignalUpdate, SignalWith,
+};
+
+use crate::{
+    view::IntoView,
+    views::{dyn_container, DynamicContainer},
+};
+
+/// A screen of an application that can be navigated to, see the [module docs](self).
+pub trait Route: Clone + PartialEq + 'static {
+    /// Parses a path like `/users/3`, returning `None` if it isn't the path of a route.
+    fn from_path(path: &str) -> Option<Self>;
+
+    /// The path of the route, which [`from_path`](Route::from_path) parses back into it.
+    fn to_path(&self) -> String;
+}
+
+/// A guard that can prevent leaving the first route for the second one.
+type Guard<R> = dyn Fn(&R, &R) -> bool;
+
+#[derive(Clone)]
+struct History<R> {
+    entries: Vec<R>,
+    index: usize,
+}
+
+/// The current route of an application and the history of the routes it navigated to.
+///
+/// Routers are `Copy`, like signals, so they can be used in the closures of the views.
+pub struct Router<R: 'static> {
+    history: RwSignal<History<R>>,
+    current: Memo<R>,
+    guards: RwSignal<Vec<Rc<Guard<R>>>>,
+}
+
+impl<R: 'static> Clone for Router<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: 'static> Copy for Router<R> {}
+
+impl<R: Route> Router<R> {
+    /// Creates a router that starts at `initial`.
+    pub fn new(initial: R) -> Self {
+        let history = create_rw_signal(History {
+            entries: vec![initial],
+            index: 0,
+        });
+        let current =
+            create_memo(move |_| history.with(|history| history.entries[history.index].clone()));
+        Self {
+            history,
+            current,
+            guards: create_rw_signal(Vec::new()),
+        }
+    }
+
+    /// Creates a router that starts at the route of the link that the application was opened
+    /// with, see [`startup_link`], or at `default` if there is none.
+    pub fn from_startup_link(default: R) -> Self {
+        let initial = startup_link()
+            .and_then(|path| R::from_path(&path))
+            .unwrap_or(default);
+        Self::new(initial)
+    }
+
+    /// The current route, which is tracked when called in a reactive context.
+    pub fn route(&self) -> R {
+        self.current.get()
+    }
+
+    /// The current route as a memo, which only notifies its subscribers when the route changes.
+    pub fn current(&self) -> Memo<R> {
+        self.current
+    }
+
+    /// Navigates to `route`, dropping the routes that could be gone forward to. Returns `false`
+    /// if a guard prevented it.
+    pub fn navigate(&self, route: R) -> bool {
+        if self.current.with_untracked(|current| *current == route) {
+            return true;
+        }
+        if !self.allowed(&route) {
+            return false;
+        }
+        self.history.update(|history| {
+            history.entries.truncate(history.index + 1);
+            history.entries.push(route);
+            history.index += 1;
+        });
+        true
+    }
+
+    /// Replaces the current route with `route`, without adding to the history. Returns `false`
+    /// if a guard prevented it.
+    pub fn replace(&self, route: R) -> bool {
+        if !self.allowed(&route) {
+            return false;
+        }
+        self.history
+            .update(|history| history.entries[history.index] = route);
+        true
+    }
+
+    /// Goes back to the previous route. Returns `false` if there is none, or a guard prevented
+    /// it.
+    pub fn back(&self) -> bool {
+        self.go(-1)
+    }
+
+    /// Goes forward to the route that was gone back from. Returns `false` if there is none, or
+    /// a guard prevented it.
+    pub fn forward(&self) -> bool {
+        self.go(1)
+    }
+
+    /// Whether there is a route to go back to, which is tracked when called in a reactive
+    /// context.
+    pub fn can_go_back(&self) -> bool {
+        self.history.with(|history| history.index > 0)
+    }
+
+    /// Whether there is a route to go forward to, which is tracked when called in a reactive
+    /// context.
+    pub fn can_go_forward(&self) -> bool {
+        self.history
+            .with(|history| history.index + 1 < history.entries.len())
+    }
+
+    /// Navigates to the route of `link`, a path like `/users/3` or a URL like
+    /// `myapp://users/3`. Returns `false` if it isn't the link of a route, or a guard prevented
+    /// it.
+    ///
+    /// Links that the application receives while it runs, e.g. from another instance of it,
+    /// can be opened with it.
+    pub fn open_link(&self, link: &str) -> bool {
+        match R::from_path(&link_path(link)) {
+            Some(route) => self.navigate(route),
+            None => false,
+        }
+    }
+
+    /// Adds a guard, which is called with the current route and the route being navigated to,
+    /// and prevents the navigation by returning `false`, e.g. to keep unsaved changes.
+    pub fn add_guard(&self, guard: impl Fn(&R, &R) -> bool + 'static) {
+        self.guards.update(|guards| guards.push(Rc::new(guard)));
+    }
+
+    fn go(&self, delta: isize) -> bool {
+        let target = self.history.with_untracked(|history| {
+            let index = history.index.checked_add_signed(delta)?;
+            Some((index, history.entries.get(index)?.clone()))
+        });
+        let Some((index, route)) = target else {
+            return false;
+        };
+        if !self.allowed(&route) {
+            return false;
+        }
+        self.history.update(|history| history.index = index);
+        true
+    }
+
+    fn allowed(&self, to: &R) -> bool {
+        let from = self.current.get_untracked();
+        // the guards are cloned out of the signal, as they may use the router
+        let guards = self.guards.get_untracked();
+        guards.iter().all(|guard| guard(&from, to))
+    }
+}
+
+/// A view that shows the view of the current route of `router`, created with `view_fn` each
+/// time the route changes.
+///
+/// It's a [`DynamicContainer`], so the views can have enter and exit animations.
+pub fn router_view<R: Route, V: IntoView + 'static>(
+    router: Router<R>,
+    view_fn: impl Fn(R) -> V + 'static,
+) -> DynamicContainer<R> {
+    dyn_container(move || router.route(), view_fn)
+}
+
+/// The parameters of a path matched with [`match_path`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// The value of the parameter `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parses the value of the parameter `name`, like the number of `/users/3`.
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name)?.parse().ok()
+    }
+}
+
+/// Matches `path` with `pattern`, whose segments are either literal, a parameter like `:id`
+/// matching a single segment, or a last parameter like `*rest` matching the rest of the path.
+///
+/// The query and the fragment of `path` are ignored.
+///
+/// ```
+/// # use floem::router::match_path;
+/// let params = match_path("/users/:id/*tab", "/users/3/posts/recent?sort=new").unwrap();
+/// assert_eq!(params.parse::<u32>("id"), Some(3));
+/// assert_eq!(params.get("tab"), Some("posts/recent"));
+/// assert!(match_path("/users/:id", "/teams/3").is_none());
+/// ```
+pub fn match_path(pattern: &str, path: &str) -> Option<Params> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let mut params = Vec::new();
+    for part in pattern.split('/').filter(|part| !part.is_empty()) {
+        if let Some(name) = part.strip_prefix('*') {
+            let rest = segments.by_ref().collect::<Vec<_>>().join("/");
+            params.push((name.to_string(), rest));
+            return Some(Params(params));
+        }
+        let segment = segments.next()?;
+        if let Some(name) = part.strip_prefix(':') {
+            params.push((name.to_string(), segment.to_string()));
+        } else if part != segment {
+            return None;
+        }
+    }
+    segments.next().is_none().then_some(Params(params))
+}
+
+/// The path of `link`: a URL like `myapp://users/3` has the path `/users/3`, and other links
+/// are paths already.
+fn link_path(link: &str) -> String {
+    match link.split_once("://") {
+        Some((_, path)) => format!("/{}", path.trim_start_matches('/')),
+        None => link.to_string(),
+    }
+}
+
+/// The path of the link that the application was opened with.
+///
+/// On the web, it's the path of the URL of the page. On desktop platforms, it's the path of the
+/// first command line argument that is a URL like `myapp://users/3`, which is how the
+/// platforms pass the links of the URL schemes registered by an application.
+pub fn startup_link() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let location = web_sys::window()?.location();
+        let path = location.pathname().ok()?;
+        let search = location.search().unwrap_or_default();
+        Some(format!("{path}{search}"))
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::env::args()
+            .skip(1)
+            .find(|arg| arg.contains("://"))
+            .map(|link| link_path(&link))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Screen {
+        Home,
+        User(u32),
+    }
+
+    impl Route for Screen {
+        fn from_path(path: &str) -> Option<Self> {
+            if let Some(params) = match_path("/users/:id", path) {
+                return Some(Screen::User(params.parse("id")?));
+            }
+            (path == "/").then_some(Screen::Home)
+        }
+
+        fn to_path(&self) -> String {
+            match self {
+                Screen::Home => "/".to_string(),
+                Screen::User(id) => format!("/users/{id}"),
+            }
+        }
+    }
+
+    #[test]
+    fn paths_are_matched_by_segment() {
+        let params = match_path("/users/:id", "/users/3/").unwrap();
+        assert_eq!(params.get("id"), Some("3"));
+        assert!(match_path("/users/:id", "/users").is_none());
+        assert!(match_path("/users/:id", "/users/3/posts").is_none());
+        assert_eq!(match_path("/", "/"), Some(Params::default()));
+        assert_eq!(link_path("myapp://users/3"), "/users/3");
+        assert_eq!(link_path("/users/3"), "/users/3");
+    }
+
+    #[test]
+    fn history_goes_back_and_forward() {
+        let router = Router::new(Screen::Home);
+        assert!(router.navigate(Screen::User(1)));
+        assert!(router.open_link("myapp://users/2"));
+        assert!(!router.open_link("myapp://teams/2"));
+        assert!(router.back());
+        assert_eq!(router.route(), Screen::User(1));
+        assert!(router.can_go_forward());
+
+        // navigating drops the routes that could be gone forward to
+        assert!(router.navigate(Screen::User(3)));
+        assert!(!router.forward());
+        assert!(router.back());
+        assert!(router.back());
+        assert_eq!(router.route(), Screen::Home);
+        assert!(!router.back());
+    }
+
+    #[test]
+    fn guards_can_prevent_navigation() {
+        let router = Router::new(Screen::Home);
+        let unsaved = Rc::new(Cell::new(true));
+        {
+            let unsaved = unsaved.clone();
+            router.add_guard(move |from, _| !(unsaved.get() && *from == Screen::User(1)));
+        }
+        assert!(router.navigate(Screen::User(1)));
+        assert!(!router.back());
+        assert!(!router.replace(Screen::User(2)));
+        assert_eq!(router.route(), Screen::User(1));
+
+        unsaved.set(false);
+        assert!(router.back());
+        assert_eq!(router.route(), Screen::Home);
+    }
+}
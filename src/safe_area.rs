@@ -0,0 +1,133 @@
+#![deny(missing_docs)]
+//! The safe area of windows, which is the part of a window that isn't covered by the notch,
+//! the rounded corners, the status bar or the home indicator of mobile devices.
+//!
+//! The insets of the safe area are inherited style props that are set on the root of every
+//! window, so a view is padded to keep its content visible with
+//! [`Style::padding_safe_area`], or uses them in its own style with [`Style::set_var`]. The
+//! insets are also available as signals with [`safe_area_insets`]. They are zero on desktop
+//! platforms.
+//!
+//! ```rust
+//! # use floem::views::{container, text, Decorators};
+//! container(text("Title")).style(|s| s.width_full().padding_safe_area());
+//! ```
+
+use std::{cell::RefCell, collections::HashMap};
+
+use floem_reactive::{ReadSignal, RwSignal, SignalGet, SignalUpdate};
+use floem_winit::window::{Window, WindowId};
+use peniko::kurbo::Insets;
+
+use crate::{prop, style::Style};
+
+prop!(
+    /// The distance from the top of the window to the safe area, in logical pixels.
+    pub SafeAreaTop: f64 { inherited } = 0.0
+);
+prop!(
+    /// The distance from the right of the window to the safe area, in logical pixels.
+    pub SafeAreaRight: f64 { inherited } = 0.0
+);
+prop!(
+    /// The distance from the bottom of the window to the safe area, in logical pixels.
+    pub SafeAreaBottom: f64 { inherited } = 0.0
+);
+prop!(
+    /// The distance from the left of the window to the safe area, in logical pixels.
+    pub SafeAreaLeft: f64 { inherited } = 0.0
+);
+
+thread_local! {
+    static SAFE_AREAS: RefCell<HashMap<WindowId, RwSignal<Insets>>> = Default::default();
+}
+
+/// The insets of the safe area of the window, in logical pixels, which change when the device
+/// is rotated.
+pub fn safe_area_insets(window_id: WindowId) -> Option<ReadSignal<Insets>> {
+    SAFE_AREAS.with(|areas| {
+        areas
+            .borrow()
+            .get(&window_id)
+            .map(|insets| insets.read_only())
+    })
+}
+
+pub(crate) fn register(window_id: WindowId, insets: RwSignal<Insets>) {
+    SAFE_AREAS.with(|areas| {
+        areas.borrow_mut().insert(window_id, insets);
+    });
+}
+
+pub(crate) fn unregister(window_id: WindowId) {
+    SAFE_AREAS.with(|areas| {
+        areas.borrow_mut().remove(&window_id);
+    });
+}
+
+/// Sets `insets` and returns whether they changed.
+pub(crate) fn update(insets: RwSignal<Insets>, new: Insets) -> bool {
+    if insets.get_untracked() == new {
+        return false;
+    }
+    insets.set(new);
+    true
+}
+
+/// The style of the root of a window, which makes the insets available to its views.
+pub(crate) fn root_style(insets: Insets) -> Style {
+    Style::new()
+        .set(SafeAreaTop, insets.y0)
+        .set(SafeAreaRight, insets.x1)
+        .set(SafeAreaBottom, insets.y1)
+        .set(SafeAreaLeft, insets.x0)
+}
+
+/// The insets of the safe area of `window` in logical pixels, at the `scale` of the window.
+pub(crate) fn window_insets(window: &Window, scale: f64) -> Insets {
+    let insets = physical_insets(window);
+    Insets::new(
+        insets.x0 / scale,
+        insets.y0 / scale,
+        insets.x1 / scale,
+        insets.y1 / scale,
+    )
+}
+
+#[cfg(target_os = "ios")]
+fn physical_insets(window: &Window) -> Insets {
+    // the inner rect of windows is their safe area on iOS
+    let (Ok(inner), Ok(outer)) = (window.inner_position(), window.outer_position()) else {
+        return Insets::ZERO;
+    };
+    let inner_size = window.inner_size();
+    let outer_size = window.outer_size();
+    let left = (inner.x - outer.x) as f64;
+    let top = (inner.y - outer.y) as f64;
+    Insets::new(
+        left,
+        top,
+        outer_size.width as f64 - inner_size.width as f64 - left,
+        outer_size.height as f64 - inner_size.height as f64 - top,
+    )
+}
+
+#[cfg(target_os = "android")]
+fn physical_insets(window: &Window) -> Insets {
+    use floem_winit::platform::android::WindowExtAndroid;
+
+    // the content rect excludes the system bars and display cutouts
+    let content = window.content_rect();
+    let size = window.inner_size();
+    Insets::new(
+        content.left as f64,
+        content.top as f64,
+        size.width as f64 - content.right as f64,
+        size.height as f64 - content.bottom as f64,
+    )
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+fn physical_insets(_window: &Window) -> Insets {
+    Insets::ZERO
+}
@@ -0,0 +1,103 @@
+#![deny(missing_docs)]
+//! The monitors connected to the system.
+//!
+//! The list of monitors is a signal that is updated when a window is created, moved or changes
+//! its scale factor, and moving a window to another monitor is reported as an
+//! [`AppEvent::WindowMonitorChanged`](crate::AppEvent::WindowMonitorChanged).
+
+use floem_reactive::{ReadSignal, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use floem_winit::{monitor::MonitorHandle, window::Window};
+use peniko::kurbo::{Point, Rect};
+
+use crate::app::app_scope;
+
+thread_local! {
+    static SCREENS: RwSignal<Vec<Monitor>> = app_scope().create_rw_signal(Vec::new());
+}
+
+/// A monitor connected to the system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    /// The name of the monitor, if the platform provides one.
+    pub name: Option<String>,
+    /// The area of the monitor on the desktop, in physical pixels.
+    pub physical_bounds: Rect,
+    /// The scale factor of the monitor, which is the number of physical pixels of a logical
+    /// pixel.
+    pub scale_factor: f64,
+    /// The refresh rate of the monitor in hertz, if the platform provides it.
+    pub refresh_rate: Option<f64>,
+    /// Whether this is the primary monitor of the system.
+    pub primary: bool,
+}
+
+impl Monitor {
+    pub(crate) fn new(monitor: &MonitorHandle, primary: Option<&MonitorHandle>) -> Self {
+        let position = monitor.position();
+        let size = monitor.size();
+        Self {
+            name: monitor.name(),
+            physical_bounds: Rect::from_origin_size(
+                (position.x as f64, position.y as f64),
+                (size.width as f64, size.height as f64),
+            ),
+            scale_factor: monitor.scale_factor(),
+            refresh_rate: monitor
+                .refresh_rate_millihertz()
+                .map(|millihertz| millihertz as f64 / 1000.0),
+            primary: primary == Some(monitor),
+        }
+    }
+
+    /// The area of the monitor in logical pixels, using the scale factor of the monitor.
+    pub fn bounds(&self) -> Rect {
+        let scale = 1.0 / self.scale_factor;
+        Rect::new(
+            self.physical_bounds.x0 * scale,
+            self.physical_bounds.y0 * scale,
+            self.physical_bounds.x1 * scale,
+            self.physical_bounds.y1 * scale,
+        )
+    }
+}
+
+/// Returns a signal with the monitors connected to the system. The list is empty until the
+/// first window is created.
+pub fn screens() -> ReadSignal<Vec<Monitor>> {
+    SCREENS.with(|screens| screens.read_only())
+}
+
+/// Returns the monitor that contains `point`, in physical pixels on the desktop.
+pub fn monitor_at(point: Point) -> Option<Monitor> {
+    screens().with_untracked(|screens| {
+        screens
+            .iter()
+            .find(|monitor| monitor.physical_bounds.contains(point))
+            .cloned()
+    })
+}
+
+/// Returns the primary monitor of the system.
+pub fn primary_monitor() -> Option<Monitor> {
+    screens().with_untracked(|screens| screens.iter().find(|monitor| monitor.primary).cloned())
+}
+
+/// Updates the list of monitors from the ones that `window` can see.
+pub(crate) fn update_screens(window: &Window) {
+    let primary = window.primary_monitor();
+    let monitors: Vec<Monitor> = window
+        .available_monitors()
+        .map(|monitor| Monitor::new(&monitor, primary.as_ref()))
+        .collect();
+    SCREENS.with(|screens| {
+        if screens.get_untracked() != monitors {
+            screens.set(monitors);
+        }
+    });
+}
+
+/// Returns the monitor that `window` is mostly on.
+pub(crate) fn current_monitor(window: &Window) -> Option<Monitor> {
+    let monitor = window.current_monitor()?;
+    Some(Monitor::new(&monitor, window.primary_monitor().as_ref()))
+}
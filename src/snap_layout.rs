@@ -0,0 +1,101 @@
+//! Windows 11 shows the snap layouts when the pointer hovers the maximize button of a window,
+//! which it finds by hit testing the window. For a custom title bar the maximize button is a
+//! view, so the window is subclassed to report the area of that view as the maximize button.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use floem_winit::window::Window;
+use peniko::kurbo::{Point, Rect};
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use windows_sys::Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM},
+    Graphics::Gdi::ScreenToClient,
+    UI::{
+        Shell::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass},
+        WindowsAndMessaging::{
+            IsZoomed, ShowWindow, HTMAXBUTTON, SW_MAXIMIZE, SW_RESTORE, WM_NCDESTROY, WM_NCHITTEST,
+            WM_NCLBUTTONDBLCLK, WM_NCLBUTTONDOWN, WM_NCLBUTTONUP,
+        },
+    },
+};
+
+const SUBCLASS_ID: usize = 1;
+
+thread_local! {
+    /// The maximize buttons of the subclassed windows, in physical client coordinates.
+    static MAXIMIZE_BUTTONS: RefCell<HashMap<HWND, Rect>> = Default::default();
+}
+
+/// Sets the area of the window that is the maximize button, subclassing the window the first
+/// time and removing the subclass when the window no longer has a maximize button.
+pub(crate) fn set_maximize_button(window: &Window, rect: Option<Rect>) {
+    let Some(hwnd) = hwnd(window) else {
+        return;
+    };
+    let subclassed = MAXIMIZE_BUTTONS.with_borrow(|buttons| buttons.contains_key(&hwnd));
+    match rect {
+        Some(rect) => {
+            if !subclassed {
+                unsafe { SetWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID, 0) };
+            }
+            MAXIMIZE_BUTTONS.with_borrow_mut(|buttons| buttons.insert(hwnd, rect));
+        }
+        None if subclassed => {
+            MAXIMIZE_BUTTONS.with_borrow_mut(|buttons| buttons.remove(&hwnd));
+            unsafe { RemoveWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID) };
+        }
+        None => {}
+    }
+}
+
+pub(crate) fn hwnd(window: &Window) -> Option<HWND> {
+    match window.window_handle().ok()?.as_raw() {
+        RawWindowHandle::Win32(handle) => Some(handle.hwnd.get()),
+        _ => None,
+    }
+}
+
+unsafe extern "system" fn subclass_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _id: usize,
+    _data: usize,
+) -> LRESULT {
+    match msg {
+        WM_NCHITTEST => {
+            let mut point = POINT {
+                x: (lparam & 0xffff) as i16 as i32,
+                y: ((lparam >> 16) & 0xffff) as i16 as i32,
+            };
+            ScreenToClient(hwnd, &mut point);
+            let point = Point::new(point.x as f64, point.y as f64);
+            let over_button = MAXIMIZE_BUTTONS.with_borrow(|buttons| {
+                buttons
+                    .get(&hwnd)
+                    .is_some_and(|button| button.contains(point))
+            });
+            if over_button {
+                return HTMAXBUTTON as LRESULT;
+            }
+        }
+        // the system would draw the classic caption buttons when handling these
+        WM_NCLBUTTONDOWN | WM_NCLBUTTONDBLCLK if wparam == HTMAXBUTTON as WPARAM => return 0,
+        WM_NCLBUTTONUP if wparam == HTMAXBUTTON as WPARAM => {
+            let show = if IsZoomed(hwnd) != 0 {
+                SW_RESTORE
+            } else {
+                SW_MAXIMIZE
+            };
+            ShowWindow(hwnd, show);
+            return 0;
+        }
+        WM_NCDESTROY => {
+            let _ = MAXIMIZE_BUTTONS.try_with(|buttons| buttons.borrow_mut().remove(&hwnd));
+            RemoveWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID);
+        }
+        _ => {}
+    }
+    DefSubclassProc(hwnd, msg, wparam, lparam)
+}
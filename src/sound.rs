@@ -0,0 +1,229 @@
+#![deny(missing_docs)]
+//! Sounds for auditory feedback, like the sound of the system when an action fails.
+//!
+//! [`play_system_sound`](crate::action::play_system_sound) plays the sounds of the operating
+//! system, which applications can replace with [`set_system_sound_handler`], e.g. to mute them
+//! or play their own sounds. With the `audio` feature, [`Sound`] plays short sounds from memory,
+//! without setting up an audio engine.
+
+use std::{cell::RefCell, rc::Rc};
+
+type SystemSoundHandler = Rc<dyn Fn(SystemSound)>;
+
+thread_local! {
+    static SYSTEM_SOUND_HANDLER: RefCell<Option<SystemSoundHandler>> = const { RefCell::new(None) };
+}
+
+/// A sound of the operating system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemSound {
+    /// The default alert sound, the beep.
+    Beep,
+    /// The sound of an error.
+    Error,
+    /// The sound of a warning.
+    Warning,
+    /// The sound of a notification, like a new message.
+    Notification,
+}
+
+/// Sets how [`play_system_sound`](crate::action::play_system_sound) plays sounds, instead of
+/// playing the sounds of the system. A handler that does nothing mutes them.
+///
+/// ```
+/// # use floem::sound::set_system_sound_handler;
+/// // the sounds are muted in the settings of the application
+/// set_system_sound_handler(|_| {});
+/// ```
+pub fn set_system_sound_handler(handler: impl Fn(SystemSound) + 'static) {
+    SYSTEM_SOUND_HANDLER.with_borrow_mut(|current| *current = Some(Rc::new(handler)));
+}
+
+/// Plays `sound` with the handler, or with the system.
+pub(crate) fn play_system_sound(sound: SystemSound) {
+    match SYSTEM_SOUND_HANDLER.with_borrow(|handler| handler.clone()) {
+        Some(handler) => handler(sound),
+        None => system::play(sound),
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod system {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        MessageBeep, MB_ICONASTERISK, MB_ICONEXCLAMATION, MB_ICONHAND, MB_OK,
+    };
+
+    use super::SystemSound;
+
+    pub(super) fn play(sound: SystemSound) {
+        let kind = match sound {
+            SystemSound::Beep => MB_OK,
+            SystemSound::Error => MB_ICONHAND,
+            SystemSound::Warning => MB_ICONEXCLAMATION,
+            SystemSound::Notification => MB_ICONASTERISK,
+        };
+        unsafe {
+            MessageBeep(kind);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod system {
+    use std::process::Command;
+
+    use super::SystemSound;
+
+    pub(super) fn play(sound: SystemSound) {
+        let name = match sound {
+            // the alert sound chosen in the settings
+            SystemSound::Beep => {
+                let _ = Command::new("osascript").args(["-e", "beep"]).spawn();
+                return;
+            }
+            SystemSound::Error => "Basso",
+            SystemSound::Warning => "Funk",
+            SystemSound::Notification => "Glass",
+        };
+        let _ = Command::new("afplay")
+            .arg(format!("/System/Library/Sounds/{name}.aiff"))
+            .spawn();
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos"), not(target_arch = "wasm32")))]
+mod system {
+    use std::process::Command;
+
+    use super::SystemSound;
+
+    pub(super) fn play(sound: SystemSound) {
+        // the names of the freedesktop sound theme
+        let id = match sound {
+            SystemSound::Beep => "bell",
+            SystemSound::Error => "dialog-error",
+            SystemSound::Warning => "dialog-warning",
+            SystemSound::Notification => "message-new-instant",
+        };
+        // libcanberra plays the sound of the theme chosen by the user, and without it the
+        // default theme is played with PulseAudio
+        let played = Command::new("canberra-gtk-play")
+            .args(["--id", id])
+            .spawn()
+            .is_ok();
+        if !played {
+            let _ = Command::new("paplay")
+                .arg(format!("/usr/share/sounds/freedesktop/stereo/{id}.oga"))
+                .spawn();
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod system {
+    use super::SystemSound;
+
+    pub(super) fn play(_sound: SystemSound) {}
+}
+
+#[cfg(feature = "audio")]
+pub use audio::{Sound, SoundHandle};
+
+#[cfg(feature = "audio")]
+mod audio {
+    use std::{cell::RefCell, io::Cursor, sync::Arc};
+
+    use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+
+    thread_local! {
+        /// The output to the default audio device, opened when a sound is first played.
+        static OUTPUT: RefCell<Option<(OutputStream, OutputStreamHandle)>> =
+            const { RefCell::new(None) };
+    }
+
+    /// A short sound, like a click, that is decoded from WAV or Ogg Vorbis data when it's
+    /// played.
+    ///
+    /// ```no_run
+    /// # use floem::sound::Sound;
+    /// # use floem::views::{button, Decorators};
+    /// let click = Sound::new(std::fs::read("click.wav").unwrap()).volume(0.5);
+    /// button("Send").on_click_stop(move |_| {
+    ///     click.play();
+    /// });
+    /// ```
+    #[derive(Clone)]
+    pub struct Sound {
+        data: Arc<[u8]>,
+        volume: f32,
+    }
+
+    impl Sound {
+        /// A sound with the encoded `data`.
+        pub fn new(data: impl Into<Arc<[u8]>>) -> Self {
+            Self {
+                data: data.into(),
+                volume: 1.0,
+            }
+        }
+
+        /// Sets the volume the sound is played with, where `1.0` is the volume of the data.
+        pub fn volume(mut self, volume: f32) -> Self {
+            self.volume = volume;
+            self
+        }
+
+        /// Plays the sound on the default audio device. It plays until it ends, unless it's
+        /// stopped with the returned handle, which is `None` when there is no audio device or
+        /// the data can't be decoded.
+        pub fn play(&self) -> Option<SoundHandle> {
+            let source = Decoder::new(Cursor::new(self.data.clone())).ok()?;
+            let sink = OUTPUT.with_borrow_mut(|output| {
+                if output.is_none() {
+                    *output = OutputStream::try_default().ok();
+                }
+                let (_, handle) = output.as_ref()?;
+                Sink::try_new(handle).ok()
+            })?;
+            sink.set_volume(self.volume);
+            sink.append(source);
+            Some(SoundHandle { sink: Some(sink) })
+        }
+    }
+
+    /// A sound that is playing, see [`Sound::play`]. The sound keeps playing when the handle
+    /// is dropped.
+    pub struct SoundHandle {
+        sink: Option<Sink>,
+    }
+
+    impl SoundHandle {
+        /// Stops the sound.
+        pub fn stop(&self) {
+            if let Some(sink) = &self.sink {
+                sink.stop();
+            }
+        }
+
+        /// Whether the sound has ended or was stopped.
+        pub fn is_finished(&self) -> bool {
+            self.sink.as_ref().map_or(true, Sink::empty)
+        }
+
+        /// Changes the volume of the sound while it's playing.
+        pub fn set_volume(&self, volume: f32) {
+            if let Some(sink) = &self.sink {
+                sink.set_volume(volume);
+            }
+        }
+    }
+
+    impl Drop for SoundHandle {
+        fn drop(&mut self) {
+            // a dropped sink would stop the sound
+            if let Some(sink) = self.sink.take() {
+                sink.detach();
+            }
+        }
+    }
+}
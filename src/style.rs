@@ -1,12 +1,16 @@
 //! # Style
 
+use bitflags::bitflags;
 use floem_reactive::create_updater;
 use floem_renderer::text::{LineHeightValue, Weight};
 use im_rc::hashmap::Entry;
 use peniko::kurbo::{Point, Stroke};
 use peniko::{Brush, Color, ColorStop, ColorStops, Gradient, GradientKind};
 use rustc_hash::FxHasher;
+use sha2::{Digest, Sha256};
+use smallvec::SmallVec;
 use std::any::{type_name, Any};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::hash::Hasher;
@@ -20,7 +24,8 @@ use std::time::{Duration, Instant};
 use web_time::{Duration, Instant};
 
 pub use taffy::style::{
-    AlignContent, AlignItems, Dimension, Display, FlexDirection, FlexWrap, JustifyContent, Position,
+    AlignContent, AlignItems, Dimension, Display, FlexDirection, FlexWrap, GridAutoFlow,
+    JustifyContent, Position,
 };
 use taffy::{
     geometry::{MinMax, Size},
@@ -29,13 +34,16 @@ use taffy::{
         LengthPercentage, MaxTrackSizingFunction, MinTrackSizingFunction, Style as TaffyStyle,
         TrackSizingFunction,
     },
+    style_helpers::{evenly_sized_tracks, line, span},
 };
 
+use crate::clock;
 use crate::context::InteractionState;
 use crate::easing::*;
-use crate::responsive::{ScreenSize, ScreenSizeBp};
+use crate::responsive::{MediaQuery, ScreenInfo, ScreenSize, ScreenSizeBp};
 use crate::unit::{Pct, Px, PxPct, PxPctAuto, UnitExt};
 use crate::view::{IntoView, View};
+use crate::views::scroll::{ScrollSnapAlign, SnapAlign};
 use crate::views::{empty, stack, text, Decorators};
 
 pub trait StylePropValue: Clone + PartialEq + Debug {
@@ -46,33 +54,54 @@ pub trait StylePropValue: Clone + PartialEq + Debug {
     fn interpolate(&self, _other: &Self, _value: f64) -> Option<Self> {
         None
     }
+
+    /// How far `other` is from `self`, with a sign. This is only implemented for values with a single
+    /// dimension, and lets a spring that's re-targeted keep its speed.
+    fn difference(&self, _other: &Self) -> Option<f64> {
+        None
+    }
 }
 
 impl StylePropValue for i32 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as i32)
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        Some(*other as f64 - *self as f64)
+    }
 }
 impl StylePropValue for bool {}
 impl StylePropValue for f32 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some(*self * (1.0 - value as f32) + *other * value as f32)
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        Some((*other - *self) as f64)
+    }
 }
 impl StylePropValue for u16 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as u16)
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        Some(*other as f64 - *self as f64)
+    }
 }
 impl StylePropValue for usize {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as usize)
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        Some(*other as f64 - *self as f64)
+    }
 }
 impl StylePropValue for f64 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some(*self * (1.0 - value) + *other * value)
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        Some(*other - *self)
+    }
 }
 impl StylePropValue for Display {}
 impl StylePropValue for Position {}
@@ -86,6 +115,7 @@ impl StylePropValue for MaxTrackSizingFunction {}
 impl<T: StylePropValue, M: StylePropValue> StylePropValue for MinMax<T, M> {}
 impl<T: StylePropValue> StylePropValue for Line<T> {}
 impl StylePropValue for GridPlacement {}
+impl StylePropValue for GridAutoFlow {}
 impl StylePropValue for CursorStyle {}
 impl StylePropValue for BoxShadow {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
@@ -106,6 +136,9 @@ impl StylePropValue for Weight {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.0.interpolate(&other.0, value).map(Weight)
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        self.0.difference(&other.0)
+    }
 }
 impl StylePropValue for crate::text::Style {}
 impl StylePropValue for TextOverflow {}
@@ -121,6 +154,13 @@ impl StylePropValue for LineHeightValue {
             _ => None,
         }
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        match (self, other) {
+            (LineHeightValue::Normal(v1), LineHeightValue::Normal(v2))
+            | (LineHeightValue::Px(v1), LineHeightValue::Px(v2)) => v1.difference(v2),
+            _ => None,
+        }
+    }
 }
 impl StylePropValue for Size<LengthPercentage> {}
 
@@ -136,6 +176,12 @@ impl<T: StylePropValue> StylePropValue for Option<T> {
                 .and_then(|other| this.interpolate(other, value).map(Some))
         })
     }
+
+    fn difference(&self, other: &Self) -> Option<f64> {
+        self.as_ref()
+            .zip(other.as_ref())
+            .and_then(|(this, other)| this.difference(other))
+    }
 }
 impl<T: StylePropValue> StylePropValue for Vec<T> {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -163,6 +209,9 @@ impl StylePropValue for Px {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.0.interpolate(&other.0, value).map(Px)
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        self.0.difference(&other.0)
+    }
 }
 impl StylePropValue for Pct {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -171,6 +220,9 @@ impl StylePropValue for Pct {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.0.interpolate(&other.0, value).map(Pct)
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        self.0.difference(&other.0)
+    }
 }
 impl StylePropValue for PxPctAuto {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -190,6 +242,12 @@ impl StylePropValue for PxPctAuto {
             _ => None,
         }
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        match (self, other) {
+            (Self::Px(v1), Self::Px(v2)) | (Self::Pct(v1), Self::Pct(v2)) => Some(v2 - v1),
+            _ => None,
+        }
+    }
 }
 impl StylePropValue for PxPct {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -208,6 +266,12 @@ impl StylePropValue for PxPct {
             _ => None,
         }
     }
+    fn difference(&self, other: &Self) -> Option<f64> {
+        match (self, other) {
+            (Self::Px(v1), Self::Px(v2)) | (Self::Pct(v1), Self::Pct(v2)) => Some(v2 - v1),
+            _ => None,
+        }
+    }
 }
 impl StylePropValue for Color {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -501,12 +565,23 @@ pub trait StyleClass: Default + Copy + 'static {
 #[derive(Debug, Clone)]
 pub struct StyleClassInfo {
     pub(crate) name: fn() -> &'static str,
+    pub(crate) default_style: Option<fn(Style) -> Style>,
 }
 
 impl StyleClassInfo {
     pub const fn new<Name>() -> Self {
         StyleClassInfo {
             name: || std::any::type_name::<Name>(),
+            default_style: None,
+        }
+    }
+
+    /// The info of a class whose views are styled with `default_style`, under the styles that
+    /// the theme and the ancestors of the views set for the class.
+    pub const fn with_default_style<Name>(default_style: fn(Style) -> Style) -> Self {
+        StyleClassInfo {
+            name: || std::any::type_name::<Name>(),
+            default_style: Some(default_style),
         }
     }
 }
@@ -516,6 +591,49 @@ pub struct StyleClassRef {
     pub key: StyleKey,
 }
 
+impl StyleClassRef {
+    fn info(&self) -> Option<&'static StyleClassInfo> {
+        match self.key.info {
+            StyleKeyInfo::Class(info) => Some(info),
+            _ => None,
+        }
+    }
+
+    /// The name of the class, which is the path of its type.
+    pub fn name(&self) -> &'static str {
+        self.info().map_or("", |info| (info.name)())
+    }
+
+    /// The style that the class was declared with in [`style_class!`](crate::style_class), if
+    /// any.
+    pub fn default_style(&self) -> Option<Style> {
+        let default_style = self.info()?.default_style?;
+        Some(default_style(Style::new()))
+    }
+}
+
+thread_local! {
+    static STYLE_CLASSES: RefCell<Vec<StyleClassRef>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Adds `class` to the classes returned by [`style_classes`], unless it's already there.
+///
+/// Classes are registered when they're first added to a view or styled with [`Style::class`], so
+/// this is only needed for classes that a theme should know about before that.
+pub fn register_style_class(class: StyleClassRef) {
+    STYLE_CLASSES.with_borrow_mut(|classes| {
+        if !classes.contains(&class) {
+            classes.push(class);
+        }
+    });
+}
+
+/// The style classes that were registered, in the order they were registered, e.g. for a theme
+/// editor to list the classes that can be styled.
+pub fn style_classes() -> Vec<StyleClassRef> {
+    STYLE_CLASSES.with_borrow(|classes| classes.clone())
+}
+
 macro_rules! style_key_selector {
     ($v:vis $name:ident, $sel:expr) => {
         fn $name() -> $crate::style::StyleKey {
@@ -525,9 +643,28 @@ macro_rules! style_key_selector {
     };
 }
 
+/// Declares a style class, which views are added to with
+/// [`Decorators::class`](crate::views::Decorators::class) and which is styled with
+/// [`Style::class`].
+///
+/// A class can be declared with a default style, which its views have under the styles that the
+/// theme and their ancestors set for the class.
+///
+/// ```
+/// # use floem::style_class;
+/// style_class!(
+///     /// The style class of the badges of the application.
+///     pub BadgeClass
+/// );
+///
+/// style_class!(
+///     /// The style class of the chips of the application, which are rounded.
+///     pub ChipClass = |s| s.border_radius(12).padding_horiz(8)
+/// );
+/// ```
 #[macro_export]
 macro_rules! style_class {
-    ($(#[$meta:meta])* $v:vis $name:ident) => {
+    (@impl $(#[$meta:meta])* $v:vis $name:ident, $info:expr) => {
         $(#[$meta])*
         #[derive(Default, Copy, Clone)]
         $v struct $name;
@@ -535,12 +672,20 @@ macro_rules! style_class {
         impl $crate::style::StyleClass for $name {
             fn key() -> $crate::style::StyleKey {
                 static INFO: $crate::style::StyleKeyInfo = $crate::style::StyleKeyInfo::Class(
-                    $crate::style::StyleClassInfo::new::<$name>()
+                    $info
                 );
                 $crate::style::StyleKey { info: &INFO }
             }
         }
     };
+    ($(#[$meta:meta])* $v:vis $name:ident) => {
+        $crate::style_class!(@impl $(#[$meta])* $v $name,
+            $crate::style::StyleClassInfo::new::<$name>());
+    };
+    ($(#[$meta:meta])* $v:vis $name:ident = $default_style:expr) => {
+        $crate::style_class!(@impl $(#[$meta])* $v $name,
+            $crate::style::StyleClassInfo::with_default_style::<$name>($default_style));
+    };
 }
 
 pub trait StyleProp: Default + Copy + 'static {
@@ -555,6 +700,10 @@ pub trait StyleProp: Default + Copy + 'static {
 pub(crate) type InterpolateFn =
     fn(val1: &dyn Any, val2: &dyn Any, time: f64) -> Option<Rc<dyn Any>>;
 
+pub(crate) type EqFn = fn(val1: &dyn Any, val2: &dyn Any) -> bool;
+
+pub(crate) type DifferenceFn = fn(val1: &dyn Any, val2: &dyn Any) -> Option<f64>;
+
 #[derive(Debug)]
 pub struct StylePropInfo {
     pub(crate) name: fn() -> &'static str,
@@ -562,9 +711,15 @@ pub struct StylePropInfo {
     #[allow(unused)]
     pub(crate) default_as_any: fn() -> Rc<dyn Any>,
     pub(crate) interpolate: InterpolateFn,
+    /// Compares two values of the property, ignoring whether they are animated.
+    pub(crate) eq: EqFn,
+    /// The signed distance between two values of the property, see [`StylePropValue::difference`].
+    pub(crate) difference: DifferenceFn,
     pub(crate) debug_any: fn(val: &dyn Any) -> String,
     pub(crate) debug_view: fn(val: &dyn Any) -> Option<Box<dyn View>>,
     pub(crate) transition_key: StyleKey,
+    /// The key of a [`Style::set_var`] reference that sets this property.
+    pub(crate) var_key: StyleKey,
 }
 
 impl StylePropInfo {
@@ -572,6 +727,7 @@ impl StylePropInfo {
         inherited: bool,
         default_as_any: fn() -> Rc<dyn Any>,
         transition_key: StyleKey,
+        var_key: StyleKey,
     ) -> Self {
         StylePropInfo {
             name: || std::any::type_name::<Name>(),
@@ -616,6 +772,23 @@ impl StylePropInfo {
                     )
                 }
             },
+            eq: |val1, val2| match (
+                val1.downcast_ref::<StyleMapValue<T>>(),
+                val2.downcast_ref::<StyleMapValue<T>>(),
+            ) {
+                (Some(v1), Some(v2)) => v1.as_ref() == v2.as_ref(),
+                _ => false,
+            },
+            difference: |val1, val2| match (
+                val1.downcast_ref::<StyleMapValue<T>>(),
+                val2.downcast_ref::<StyleMapValue<T>>(),
+            ) {
+                (
+                    Some(StyleMapValue::Val(v1) | StyleMapValue::Animated(v1)),
+                    Some(StyleMapValue::Val(v2) | StyleMapValue::Animated(v2)),
+                ) => v1.difference(v2),
+                _ => None,
+            },
             debug_view: |val| {
                 if let Some(v) = val.downcast_ref::<StyleMapValue<T>>() {
                     match v {
@@ -632,10 +805,22 @@ impl StylePropInfo {
                 }
             },
             transition_key,
+            var_key,
         }
     }
 }
 
+/// Reads the referenced property from the computed style of the view, falling back to the style
+/// inherited from its ancestors.
+type ResolveVarFn = Rc<dyn Fn(&Style, &Style) -> Rc<dyn Any>>;
+
+/// A reference from a property to the value of another property, created by [`Style::set_var`].
+#[derive(Clone)]
+struct VarRef {
+    target: StyleKey,
+    resolve: ResolveVarFn,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct StylePropRef {
     pub key: StyleKey,
@@ -808,10 +993,12 @@ macro_rules! prop {
             type Type = $ty;
             fn key() -> $crate::style::StyleKey {
                 static TRANSITION_INFO: $crate::style::StyleKeyInfo = $crate::style::StyleKeyInfo::Transition;
+                static VAR_INFO: $crate::style::StyleKeyInfo = $crate::style::StyleKeyInfo::Var;
                 static INFO: $crate::style::StyleKeyInfo = $crate::style::StyleKeyInfo::Prop($crate::style::StylePropInfo::new::<$name, $ty>(
                     prop!([impl inherited][$($options)*]),
                     || std::rc::Rc::new($crate::style::StyleMapValue::Val($name::default_value())),
                     $crate::style::StyleKey { info: &TRANSITION_INFO },
+                    $crate::style::StyleKey { info: &VAR_INFO },
                 ));
                 $crate::style::StyleKey { info: &INFO }
             }
@@ -932,7 +1119,8 @@ pub struct TransitionState<T: StylePropValue> {
 
 impl<T: StylePropValue> TransitionState<T> {
     fn read(&mut self, transition: Option<Transition>) {
-        self.transition = transition;
+        // the props change at once while the user prefers reduced motion
+        self.transition = transition.filter(|_| !crate::accessibility::reduced_motion_untracked());
     }
 
     fn transition(&mut self, before: &T, after: &T) {
@@ -941,7 +1129,7 @@ impl<T: StylePropValue> TransitionState<T> {
         }
         if self.transition.is_some() {
             self.active = Some(ActiveTransition {
-                start: Instant::now(),
+                start: clock::now(),
                 before: before.clone(),
                 current: before.clone(),
                 after: after.clone(),
@@ -1029,9 +1217,20 @@ impl Transition {
 #[derive(Debug)]
 pub enum StyleKeyInfo {
     Transition,
+    Var,
     Prop(StylePropInfo),
     Selector(StyleSelectors),
     Class(StyleClassInfo),
+    Media(MediaKeyInfo),
+}
+
+#[derive(Debug)]
+pub struct MediaKeyInfo {
+    query: MediaQuery,
+    /// Whether the query is matched against the size of the view itself instead of the window.
+    container: bool,
+    /// Matching media styles are applied in the order their queries were first used.
+    order: usize,
 }
 
 #[derive(Copy, Clone)]
@@ -1041,18 +1240,56 @@ pub struct StyleKey {
 impl StyleKey {
     pub(crate) fn debug_any(&self, value: &dyn Any) -> String {
         match self.info {
-            StyleKeyInfo::Selector(..) | StyleKeyInfo::Transition => String::new(),
+            StyleKeyInfo::Selector(..)
+            | StyleKeyInfo::Media(..)
+            | StyleKeyInfo::Transition
+            | StyleKeyInfo::Var => String::new(),
             StyleKeyInfo::Class(info) => (info.name)().to_string(),
             StyleKeyInfo::Prop(v) => (v.debug_any)(value),
         }
     }
     fn inherited(&self) -> bool {
         match self.info {
-            StyleKeyInfo::Selector(..) | StyleKeyInfo::Transition => false,
+            StyleKeyInfo::Selector(..)
+            | StyleKeyInfo::Media(..)
+            | StyleKeyInfo::Transition
+            | StyleKeyInfo::Var => false,
             StyleKeyInfo::Class(..) => true,
             StyleKeyInfo::Prop(v) => v.inherited,
         }
     }
+
+    /// Compares two values of this key. Transitions and variables are only equal if they're the
+    /// same value, as they can't be compared.
+    fn value_eq(&self, val1: &Rc<dyn Any>, val2: &Rc<dyn Any>) -> bool {
+        if Rc::ptr_eq(val1, val2) {
+            return true;
+        }
+        match self.info {
+            StyleKeyInfo::Selector(..) | StyleKeyInfo::Media(..) | StyleKeyInfo::Class(..) => {
+                match (val1.downcast_ref::<Style>(), val2.downcast_ref::<Style>()) {
+                    (Some(style1), Some(style2)) => style1.changes(style2).is_empty(),
+                    _ => false,
+                }
+            }
+            StyleKeyInfo::Transition | StyleKeyInfo::Var => false,
+            StyleKeyInfo::Prop(v) => (v.eq)(val1.as_ref(), val2.as_ref()),
+        }
+    }
+}
+
+bitflags! {
+    /// The groups of properties that changed between two styles of a view, which decide what
+    /// has to be updated after its style pass.
+    #[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+    pub(crate) struct StyleChanges: u8 {
+        /// Inherited properties or classes changed, so the descendants have to be restyled.
+        const INHERITED = 1;
+        /// Properties of the view itself changed, so it has to be painted again.
+        const PAINT = 1 << 1;
+        /// The layout style changed, so the layout has to be computed again.
+        const LAYOUT = 1 << 2;
+    }
 }
 impl PartialEq for StyleKey {
     fn eq(&self, other: &Self) -> bool {
@@ -1069,7 +1306,9 @@ impl Debug for StyleKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.info {
             StyleKeyInfo::Selector(..) => write!(f, "selector"),
+            StyleKeyInfo::Media(v) => write!(f, "media {:?}", v.query),
             StyleKeyInfo::Transition => write!(f, "transition"),
+            StyleKeyInfo::Var => write!(f, "var"),
             StyleKeyInfo::Class(v) => write!(f, "{}", (v.name)()),
             StyleKeyInfo::Prop(v) => write!(f, "{}", (v.name)()),
         }
@@ -1085,6 +1324,32 @@ style_key_selector!(selector_lg, StyleSelectors::new().responsive());
 style_key_selector!(selector_xl, StyleSelectors::new().responsive());
 style_key_selector!(selector_xxl, StyleSelectors::new().responsive());
 
+thread_local! {
+    static MEDIA_KEYS: RefCell<Vec<&'static StyleKeyInfo>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns the key of the nested map for `query`.
+///
+/// Style keys are static, so the key info of every distinct query is leaked once.
+fn media_query_to_key(query: MediaQuery, container: bool) -> StyleKey {
+    MEDIA_KEYS.with_borrow_mut(|keys| {
+        let info = keys.iter().copied().find(|info| {
+            matches!(info, StyleKeyInfo::Media(info) if info.query == query && info.container == container)
+        });
+        let info = info.unwrap_or_else(|| {
+            let info: &'static StyleKeyInfo =
+                Box::leak(Box::new(StyleKeyInfo::Media(MediaKeyInfo {
+                    query,
+                    container,
+                    order: keys.len(),
+                })));
+            keys.push(info);
+            info
+        });
+        StyleKey { info }
+    })
+}
+
 fn screen_size_bp_to_key(breakpoint: ScreenSizeBp) -> StyleKey {
     match breakpoint {
         ScreenSizeBp::Xs => selector_xs(),
@@ -1148,10 +1413,23 @@ impl Style {
     pub(crate) fn selectors(&self) -> StyleSelectors {
         let mut result = StyleSelectors::new();
         for (k, v) in &self.map {
-            if let StyleKeyInfo::Selector(selector) = k.info {
-                result = result
-                    .union(*selector)
-                    .union(v.downcast_ref::<Style>().unwrap().selectors());
+            match k.info {
+                StyleKeyInfo::Selector(selector) => {
+                    result = result
+                        .union(*selector)
+                        .union(v.downcast_ref::<Style>().unwrap().selectors());
+                }
+                StyleKeyInfo::Media(info) => {
+                    let selector = if info.container {
+                        StyleSelectors::new().container_query()
+                    } else {
+                        StyleSelectors::new().responsive()
+                    };
+                    result = result
+                        .union(selector)
+                        .union(v.downcast_ref::<Style>().unwrap().selectors());
+                }
+                _ => {}
             }
         }
         result
@@ -1163,6 +1441,9 @@ impl Style {
         context: &Style,
     ) -> Style {
         for class in classes {
+            if let Some(default_style) = class.default_style() {
+                self.apply_mut(default_style);
+            }
             if let Some(map) = context.get_nested_map(class.key) {
                 self.apply_mut(map);
             }
@@ -1195,34 +1476,58 @@ impl Style {
     pub(crate) fn apply_interact_state(
         &mut self,
         interact_state: &InteractionState,
-        screen_size_bp: ScreenSizeBp,
+        screen: &ScreenInfo,
     ) {
-        if let Some(mut map) = self.get_nested_map(screen_size_bp_to_key(screen_size_bp)) {
-            map.apply_interact_state(interact_state, screen_size_bp);
+        if let Some(mut map) = self.get_nested_map(screen_size_bp_to_key(screen.breakpoint)) {
+            map.apply_interact_state(interact_state, screen);
+            self.apply_mut(map);
+        }
+
+        let container = ScreenInfo {
+            size: interact_state.size,
+            ..*screen
+        };
+        let mut media = self
+            .map
+            .iter()
+            .filter_map(|(k, v)| match k.info {
+                StyleKeyInfo::Media(info)
+                    if info
+                        .query
+                        .matches(if info.container { &container } else { screen }) =>
+                {
+                    Some((info.order, v.downcast_ref::<Style>().unwrap().clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        media.sort_unstable_by_key(|(order, _)| *order);
+        for (_, mut map) in media {
+            map.apply_interact_state(interact_state, screen);
             self.apply_mut(map);
         }
 
         if interact_state.is_hovered && !interact_state.is_disabled {
             if let Some(mut map) = self.get_nested_map(StyleSelector::Hover.to_key()) {
-                map.apply_interact_state(interact_state, screen_size_bp);
+                map.apply_interact_state(interact_state, screen);
                 self.apply_mut(map);
             }
         }
         if interact_state.is_focused {
             if let Some(mut map) = self.get_nested_map(StyleSelector::Focus.to_key()) {
-                map.apply_interact_state(interact_state, screen_size_bp);
+                map.apply_interact_state(interact_state, screen);
                 self.apply_mut(map);
             }
         }
         if interact_state.is_selected {
             if let Some(mut map) = self.get_nested_map(StyleSelector::Selected.to_key()) {
-                map.apply_interact_state(interact_state, screen_size_bp);
+                map.apply_interact_state(interact_state, screen);
                 self.apply_mut(map);
             }
         }
         if interact_state.is_disabled {
             if let Some(mut map) = self.get_nested_map(StyleSelector::Disabled.to_key()) {
-                map.apply_interact_state(interact_state, screen_size_bp);
+                map.apply_interact_state(interact_state, screen);
                 self.apply_mut(map);
             }
         }
@@ -1232,7 +1537,7 @@ impl Style {
 
         if focused_keyboard {
             if let Some(mut map) = self.get_nested_map(StyleSelector::FocusVisible.to_key()) {
-                map.apply_interact_state(interact_state, screen_size_bp);
+                map.apply_interact_state(interact_state, screen);
                 self.apply_mut(map);
             }
         }
@@ -1240,12 +1545,35 @@ impl Style {
         let active_mouse = interact_state.is_hovered && !interact_state.using_keyboard_navigation;
         if interact_state.is_clicking && (active_mouse || focused_keyboard) {
             if let Some(mut map) = self.get_nested_map(StyleSelector::Active.to_key()) {
-                map.apply_interact_state(interact_state, screen_size_bp);
+                map.apply_interact_state(interact_state, screen);
                 self.apply_mut(map);
             }
         }
     }
 
+    /// The groups of properties whose value is different in `new`. The layout isn't compared,
+    /// as it's derived from the style and the inherited properties.
+    pub(crate) fn changes(&self, new: &Style) -> StyleChanges {
+        let mut changes = StyleChanges::empty();
+        if self.map.ptr_eq(&new.map) {
+            return changes;
+        }
+        let removed = self.map.keys().filter(|key| !new.map.contains_key(key));
+        for key in new.map.keys().chain(removed) {
+            let same = match (self.map.get(key), new.map.get(key)) {
+                (Some(old), Some(new)) => key.value_eq(old, new),
+                _ => false,
+            };
+            if !same {
+                changes |= StyleChanges::PAINT;
+                if key.inherited() {
+                    changes |= StyleChanges::INHERITED;
+                }
+            }
+        }
+        changes
+    }
+
     pub(crate) fn any_inherited(&self) -> bool {
         self.map.iter().any(|(p, _)| p.inherited())
     }
@@ -1295,31 +1623,40 @@ impl Style {
     fn apply_iter(&mut self, iter: impl Iterator<Item = (StyleKey, Rc<dyn Any>)>) {
         for (k, v) in iter {
             match k.info {
-                StyleKeyInfo::Class(..) | StyleKeyInfo::Selector(..) => match self.map.entry(k) {
-                    Entry::Occupied(mut e) => {
-                        // We need to merge the new map with the existing map.
-
-                        let v = v.downcast_ref::<Style>().unwrap();
-                        match Rc::get_mut(e.get_mut()) {
-                            Some(current) => {
-                                current
-                                    .downcast_mut::<Style>()
-                                    .unwrap()
-                                    .apply_mut(v.clone());
-                            }
-                            None => {
-                                let mut current =
-                                    e.get_mut().downcast_ref::<Style>().unwrap().clone();
-                                current.apply_mut(v.clone());
-                                *e.get_mut() = Rc::new(current);
+                StyleKeyInfo::Class(..) | StyleKeyInfo::Selector(..) | StyleKeyInfo::Media(..) => {
+                    match self.map.entry(k) {
+                        Entry::Occupied(mut e) => {
+                            // We need to merge the new map with the existing map.
+
+                            let v = v.downcast_ref::<Style>().unwrap();
+                            match Rc::get_mut(e.get_mut()) {
+                                Some(current) => {
+                                    current
+                                        .downcast_mut::<Style>()
+                                        .unwrap()
+                                        .apply_mut(v.clone());
+                                }
+                                None => {
+                                    let mut current =
+                                        e.get_mut().downcast_ref::<Style>().unwrap().clone();
+                                    current.apply_mut(v.clone());
+                                    *e.get_mut() = Rc::new(current);
+                                }
                             }
                         }
+                        Entry::Vacant(e) => {
+                            e.insert(v);
+                        }
                     }
-                    Entry::Vacant(e) => {
-                        e.insert(v);
+                }
+                StyleKeyInfo::Prop(info) => {
+                    // A value set after a var reference replaces it.
+                    if self.map.contains_key(&info.var_key) {
+                        self.map.remove(&info.var_key);
                     }
-                },
-                StyleKeyInfo::Transition | StyleKeyInfo::Prop(..) => {
+                    self.map.insert(k, v);
+                }
+                StyleKeyInfo::Transition | StyleKeyInfo::Var => {
                     self.map.insert(k, v);
                 }
             }
@@ -1415,6 +1752,7 @@ impl StyleSelector {
 pub struct StyleSelectors {
     selectors: u8,
     responsive: bool,
+    container_query: bool,
 }
 
 impl StyleSelectors {
@@ -1422,6 +1760,7 @@ impl StyleSelectors {
         StyleSelectors {
             selectors: 0,
             responsive: false,
+            container_query: false,
         }
     }
     pub(crate) const fn set(mut self, selector: StyleSelector, value: bool) -> Self {
@@ -1439,6 +1778,7 @@ impl StyleSelectors {
         StyleSelectors {
             selectors: self.selectors | other.selectors,
             responsive: self.responsive | other.responsive,
+            container_query: self.container_query | other.container_query,
         }
     }
     pub(crate) const fn responsive(mut self) -> Self {
@@ -1448,6 +1788,13 @@ impl StyleSelectors {
     pub(crate) fn has_responsive(self) -> bool {
         self.responsive
     }
+    pub(crate) const fn container_query(mut self) -> Self {
+        self.container_query = true;
+        self
+    }
+    pub(crate) fn has_container_query(self) -> bool {
+        self.container_query
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1457,11 +1804,26 @@ pub enum TextOverflow {
     Ellipsis,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// The cursor that is shown while the pointer is over a view.
+#[derive(Debug, Clone, PartialEq)]
 pub enum CursorStyle {
     Default,
+    ContextMenu,
+    Help,
     Pointer,
+    Progress,
+    Wait,
+    Cell,
+    Crosshair,
     Text,
+    VerticalText,
+    Alias,
+    Copy,
+    Move,
+    NoDrop,
+    NotAllowed,
+    Grab,
+    Grabbing,
     ColResize,
     RowResize,
     WResize,
@@ -1472,8 +1834,58 @@ pub enum CursorStyle {
     NeResize,
     SwResize,
     SeResize,
+    EwResize,
+    NsResize,
     NeswResize,
     NwseResize,
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
+    /// A cursor drawn from an image.
+    Custom(CustomCursor),
+}
+
+/// A cursor drawn from an image, with a hotspot that is the point of the image at the position
+/// of the pointer.
+///
+/// The system cursor is hidden while a custom cursor is shown, and the image is painted on top of
+/// the window instead.
+#[derive(Debug, Clone)]
+pub struct CustomCursor {
+    pub(crate) image: peniko::Image,
+    pub(crate) hash: Rc<[u8]>,
+    pub(crate) hotspot: Point,
+}
+
+impl CustomCursor {
+    /// A cursor from the pixels of an image in RGBA order, `width` by `height` pixels large.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32, hotspot: impl Into<Point>) -> Self {
+        let hash = Sha256::digest(&rgba).to_vec().into();
+        let blob = peniko::Blob::new(std::sync::Arc::new(rgba));
+        CustomCursor {
+            image: peniko::Image::new(blob, peniko::Format::Rgba8, width, height),
+            hash,
+            hotspot: hotspot.into(),
+        }
+    }
+
+    /// A cursor from an encoded image, like a PNG file, or `None` if the image can't be decoded.
+    pub fn from_image(bytes: &[u8], hotspot: impl Into<Point>) -> Option<Self> {
+        let image = image::load_from_memory(bytes).ok()?;
+        let (width, height) = (image.width(), image.height());
+        Some(Self::from_rgba(
+            image.into_rgba8().into_vec(),
+            width,
+            height,
+            hotspot,
+        ))
+    }
+}
+
+impl PartialEq for CustomCursor {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.hotspot == other.hotspot
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -1620,6 +2032,7 @@ define_builtin_props!(
     GridAutoColumns grid_auto_columns: Vec<MinMax<MinTrackSizingFunction, MaxTrackSizingFunction>> {} = Vec::new(),
     GridRow grid_row: Line<GridPlacement> {} = Line::default(),
     GridColumn grid_column: Line<GridPlacement> {} = Line::default(),
+    GridAutoFlowProp grid_auto_flow: GridAutoFlow {} = GridAutoFlow::Row,
     AlignSelf align_self: Option<AlignItems> {} = None,
     BorderLeft border_left nocb: StrokeWrap {} = StrokeWrap::new(0.),
     BorderTop border_top nocb: StrokeWrap {} = StrokeWrap::new(0.0),
@@ -1787,10 +2200,66 @@ impl Style {
                 return self;
             }
         };
+        let var_key = P::prop_ref().info().var_key;
+        if self.map.contains_key(&var_key) {
+            self.map.remove(&var_key);
+        }
         self.map.insert(P::key(), Rc::new(insert));
         self
     }
 
+    /// Sets `prop` to the value of the custom property `var`, like `var()` in CSS.
+    ///
+    /// The reference is resolved whenever the style of the view is computed, using the
+    /// value of `var` set on the view itself or on its closest ancestor. For ancestors
+    /// to be searched, `var` must be declared as `inherited`. A change of the variable
+    /// on an ancestor then restyles the whole subtree.
+    ///
+    /// ```rust
+    /// # use floem::peniko::Color;
+    /// # use floem::prop;
+    /// # use floem::style::{Style, TextColor};
+    /// prop!(pub AccentColor: Color { inherited } = Color::BLUE);
+    ///
+    /// let child = Style::new().set_var(TextColor, AccentColor);
+    /// let parent = Style::new().set(AccentColor, Color::RED);
+    /// ```
+    pub fn set_var<P: StyleProp, V: StyleProp>(mut self, _prop: P, _var: V) -> Self
+    where
+        V::Type: Into<P::Type>,
+    {
+        let var = VarRef {
+            target: P::key(),
+            resolve: Rc::new(|style: &Style, inherited: &Style| -> Rc<dyn Any> {
+                let value = style
+                    .get_prop::<V>()
+                    .or_else(|| inherited.get_prop::<V>())
+                    .unwrap_or_else(V::default_value);
+                let value: P::Type = value.into();
+                Rc::new(StyleMapValue::Val(value))
+            }),
+        };
+        self.map.insert(P::prop_ref().info().var_key, Rc::new(var));
+        self
+    }
+
+    /// Resolves the references set with [`Style::set_var`] against this style and the style
+    /// inherited from the ancestors of the view.
+    pub(crate) fn resolve_vars(&mut self, inherited: &Style) {
+        let vars = self
+            .map
+            .iter()
+            .filter_map(|(k, v)| match k.info {
+                StyleKeyInfo::Var => Some(v.downcast_ref::<VarRef>().unwrap().clone()),
+                _ => None,
+            })
+            .collect::<SmallVec<[_; 4]>>();
+        for var in vars {
+            let value = (var.resolve)(self, inherited);
+            self.map.insert(var.target, value);
+        }
+    }
+
     pub fn transition<P: StyleProp>(mut self, _prop: P, transition: Transition) -> Self {
         self.map
             .insert(P::prop_ref().info().transition_key, Rc::new(transition));
@@ -1837,8 +2306,69 @@ impl Style {
         self
     }
 
+    /// Applies `style` while the window matches `query`.
+    ///
+    /// The query is re-evaluated whenever the window is resized or its scale factor changes.
+    ///
+    /// ```rust
+    /// # use floem::responsive::min_width;
+    /// # use floem::style::Style;
+    /// let style = Style::new()
+    ///     .flex_col()
+    ///     .media(min_width(800.), |s| s.flex_row());
+    /// ```
+    pub fn media(mut self, query: MediaQuery, style: impl FnOnce(Style) -> Style) -> Self {
+        let over = style(Style::default());
+        self.set_map_selector(media_query_to_key(query, false), over);
+        self
+    }
+
+    /// Sets whether the colors of the view are mapped to the system colors in the
+    /// [forced colors mode](crate::forced_colors).
+    pub fn forced_color_adjust(self, adjust: bool) -> Self {
+        self.set(crate::forced_colors::ForcedColorAdjust, adjust)
+    }
+
+    /// Applies `style` in the [forced colors mode](crate::forced_colors), as a fallback for the
+    /// colors of the view, which are then not mapped to the system colors.
+    ///
+    /// ```rust
+    /// # use floem::peniko::Color;
+    /// # use floem::style::Style;
+    /// let style = Style::new()
+    ///     .background(Color::rgb8(255, 120, 0))
+    ///     .forced_colors(|s| s.border(2.0).border_color(Color::WHITE));
+    /// ```
+    pub fn forced_colors(self, style: impl FnOnce(Style) -> Style) -> Self {
+        self.media(crate::responsive::forced_colors(true), |s| {
+            style(s).forced_color_adjust(false)
+        })
+    }
+
+    /// Applies `style` while the laid out size of this view matches `query`,
+    /// like a CSS container query.
+    ///
+    /// The style is first resolved against the size from the previous layout. When layout
+    /// changes the size of the view, it is restyled and laid out again in the same frame.
+    /// Styles that change the size of the view itself can make the query flip back and forth,
+    /// in which case the restyle is deferred to the next frame.
+    ///
+    /// ```rust
+    /// # use floem::responsive::min_width;
+    /// # use floem::style::Style;
+    /// let style = Style::new()
+    ///     .flex_col()
+    ///     .apply_if_size(min_width(400.), |s| s.flex_row());
+    /// ```
+    pub fn apply_if_size(mut self, query: MediaQuery, style: impl FnOnce(Style) -> Style) -> Self {
+        let over = style(Style::default());
+        self.set_map_selector(media_query_to_key(query, true), over);
+        self
+    }
+
     pub fn class<C: StyleClass>(mut self, _class: C, style: impl FnOnce(Style) -> Style) -> Self {
         let over = style(Style::default());
+        register_style_class(C::class_ref());
         self.set_class(C::class_ref(), over);
         self
     }
@@ -2036,6 +2566,17 @@ impl Style {
         self.padding_left(padding).padding_right(padding)
     }
 
+    /// Pads the view with the insets of the safe area of the window, so its content isn't
+    /// covered by the notch or the system bars of mobile devices.
+    pub fn padding_safe_area(self) -> Self {
+        use crate::safe_area::{SafeAreaBottom, SafeAreaLeft, SafeAreaRight, SafeAreaTop};
+
+        self.set_var(PaddingTop, SafeAreaTop)
+            .set_var(PaddingRight, SafeAreaRight)
+            .set_var(PaddingBottom, SafeAreaBottom)
+            .set_var(PaddingLeft, SafeAreaLeft)
+    }
+
     /// Sets `padding_top` and `padding_bottom` to `padding`
     pub fn padding_vert(self, padding: impl Into<PxPct>) -> Self {
         let padding = padding.into();
@@ -2133,6 +2674,13 @@ impl Style {
             .inset_bottom(inset)
     }
 
+    /// Makes this view a snap point of the scroll view it is in when it is a direct child of the scroll content.
+    ///
+    /// See [`ScrollCustomStyle::snap_type`](crate::views::scroll::ScrollCustomStyle::snap_type).
+    pub fn snap_align(self, align: SnapAlign) -> Self {
+        self.set(ScrollSnapAlign, Some(align))
+    }
+
     pub fn cursor(self, cursor: impl Into<StyleValue<CursorStyle>>) -> Self {
         self.set_style_value(Cursor, cursor.into().map(Some))
     }
@@ -2259,6 +2807,65 @@ impl Style {
         self.display(taffy::style::Display::Grid)
     }
 
+    /// Sets the grid template to `count` columns of equal width.
+    ///
+    /// Use [`Style::grid_template_columns`] with the helpers in [`taffy::style_helpers`]
+    /// for other track sizes.
+    pub fn grid_columns(self, count: u16) -> Self {
+        self.grid_template_columns(evenly_sized_tracks(count))
+    }
+
+    /// Sets the grid template to `count` rows of equal height.
+    pub fn grid_rows(self, count: u16) -> Self {
+        self.grid_template_rows(evenly_sized_tracks(count))
+    }
+
+    /// Places this grid item between the given row and column lines.
+    ///
+    /// Lines are 1-based like in CSS, and negative lines count from the end,
+    /// so `grid_area(1..2, 1..-1)` spans the whole first row.
+    pub fn grid_area(self, rows: std::ops::Range<i16>, columns: std::ops::Range<i16>) -> Self {
+        self.grid_row(Line {
+            start: line(rows.start),
+            end: line(rows.end),
+        })
+        .grid_column(Line {
+            start: line(columns.start),
+            end: line(columns.end),
+        })
+    }
+
+    /// Makes this grid item span `rows` rows, starting at the next free position.
+    pub fn grid_row_span(self, rows: u16) -> Self {
+        self.grid_row(Line {
+            start: span(rows),
+            end: GridPlacement::Auto,
+        })
+    }
+
+    /// Makes this grid item span `columns` columns, starting at the next free position.
+    pub fn grid_column_span(self, columns: u16) -> Self {
+        self.grid_column(Line {
+            start: span(columns),
+            end: GridPlacement::Auto,
+        })
+    }
+
+    /// Places grid items without an explicit position by filling each row in turn.
+    pub fn grid_flow_row(self) -> Self {
+        self.grid_auto_flow(GridAutoFlow::Row)
+    }
+
+    /// Places grid items without an explicit position by filling each column in turn.
+    pub fn grid_flow_col(self) -> Self {
+        self.grid_auto_flow(GridAutoFlow::Column)
+    }
+
+    /// Like [`Style::grid_flow_row`], but fills holes left by larger items earlier in the grid.
+    pub fn grid_flow_dense(self) -> Self {
+        self.grid_auto_flow(GridAutoFlow::RowDense)
+    }
+
     pub fn flex_row(self) -> Self {
         self.flex_direction(taffy::style::FlexDirection::Row)
     }
@@ -2399,6 +3006,7 @@ impl Style {
             grid_column: style.grid_column(),
             grid_auto_rows: style.grid_auto_rows(),
             grid_auto_columns: style.grid_auto_columns(),
+            grid_auto_flow: style.grid_auto_flow(),
             ..Default::default()
         }
     }
@@ -2430,9 +3038,11 @@ pub trait CustomStylable<S: Default + Into<Style> + 'static>:
 
 #[cfg(test)]
 mod tests {
-    use super::{Style, StyleValue};
+    use peniko::Color;
+
+    use super::{style_classes, Style, StyleChanges, StyleClass, StyleValue};
     use crate::{
-        style::{PaddingBottom, PaddingLeft},
+        style::{PaddingBottom, PaddingLeft, PaddingTop},
         unit::PxPct,
     };
 
@@ -2509,4 +3119,81 @@ mod tests {
             StyleValue::Val(PxPct::Px(100.0))
         );
     }
+
+    #[test]
+    fn var_reference() {
+        let inherited = Style::new().padding_top(8.0);
+
+        let mut style = Style::new().set_var(PaddingLeft, PaddingTop);
+        style.resolve_vars(&inherited);
+        assert_eq!(
+            style.get_style_value(PaddingLeft),
+            StyleValue::Val(PxPct::Px(8.0))
+        );
+
+        // The view's own value of the variable wins over the inherited one.
+        let mut style = Style::new()
+            .padding_top(4.0)
+            .set_var(PaddingLeft, PaddingTop);
+        style.resolve_vars(&inherited);
+        assert_eq!(
+            style.get_style_value(PaddingLeft),
+            StyleValue::Val(PxPct::Px(4.0))
+        );
+
+        // A value set after the reference replaces it.
+        let mut style = Style::new()
+            .set_var(PaddingLeft, PaddingTop)
+            .apply(Style::new().padding_left(2.0));
+        style.resolve_vars(&inherited);
+        assert_eq!(
+            style.get_style_value(PaddingLeft),
+            StyleValue::Val(PxPct::Px(2.0))
+        );
+    }
+
+    #[test]
+    fn changes_are_grouped() {
+        let style = Style::new().padding_left(8.0).color(Color::BLACK);
+        assert_eq!(style.changes(&style.clone()), StyleChanges::empty());
+        assert_eq!(
+            style.changes(&Style::new().padding_left(8.0).color(Color::BLACK)),
+            StyleChanges::empty()
+        );
+        assert_eq!(
+            style.changes(&style.clone().hover(|s| s.padding_left(4.0))),
+            StyleChanges::PAINT
+        );
+        assert_eq!(
+            style.changes(&style.clone().background(Color::RED)),
+            StyleChanges::PAINT
+        );
+        assert_eq!(
+            style.changes(&style.clone().color(Color::RED)),
+            StyleChanges::PAINT | StyleChanges::INHERITED
+        );
+        assert_eq!(
+            style.changes(&Style::new().padding_left(8.0)),
+            StyleChanges::PAINT | StyleChanges::INHERITED
+        );
+    }
+    #[test]
+    fn class_default_style_is_under_context() {
+        style_class!(ChipClass = |s| s.padding_left(4.0).padding_top(4.0));
+
+        let class = ChipClass::class_ref();
+        assert!(class.name().ends_with("ChipClass"));
+        let context = Style::new().class(ChipClass, |s| s.padding_left(8.0));
+        assert!(style_classes().contains(&class));
+
+        let style = Style::new().apply_classes_from_context(&[class], &context);
+        assert_eq!(
+            style.get_style_value(PaddingLeft),
+            StyleValue::Val(PxPct::Px(8.0))
+        );
+        assert_eq!(
+            style.get_style_value(PaddingTop),
+            StyleValue::Val(PxPct::Px(4.0))
+        );
+    }
 }
@@ -0,0 +1,228 @@
+#![deny(missing_docs)]
+//! Running blocking work on a pool of background threads.
+//!
+//! [`spawn_blocking`] runs a closure on the pool, and [`Task::on_complete`] passes its result
+//! to a closure on the UI thread, so reading a file or querying a database doesn't freeze the
+//! windows, and doesn't need a thread and a channel of its own. With the `tokio` feature,
+//! `spawn_async` does the same for futures.
+//!
+//! ```no_run
+//! # use floem::reactive::{create_rw_signal, SignalUpdate};
+//! # use floem::task::spawn_blocking;
+//! let contents = create_rw_signal(String::new());
+//! spawn_blocking(|| std::fs::read_to_string("notes.txt").unwrap_or_default())
+//!     .on_complete(move |text| contents.set(text));
+//! ```
+
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, OnceLock},
+    thread,
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use floem_reactive::Scope;
+use parking_lot::Mutex;
+
+use crate::ext_event::create_ext_action;
+
+/// The most threads the pool runs at once. Tasks submitted while they're all busy wait for one
+/// of them to finish.
+const MAX_THREADS: usize = 64;
+
+/// How long a thread of the pool waits for a task before it exits.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Job = Box<dyn FnOnce() + Send>;
+
+static POOL: OnceLock<Pool> = OnceLock::new();
+
+struct Pool {
+    sender: Sender<Job>,
+    receiver: Receiver<Job>,
+    threads: Mutex<Threads>,
+}
+
+#[derive(Default)]
+struct Threads {
+    running: usize,
+    idle: usize,
+}
+
+impl Pool {
+    fn get() -> &'static Pool {
+        POOL.get_or_init(|| {
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            Pool {
+                sender,
+                receiver,
+                threads: Mutex::new(Threads::default()),
+            }
+        })
+    }
+
+    fn submit(&'static self, job: Job) {
+        let mut threads = self.threads.lock();
+        let _ = self.sender.send(job);
+        if threads.idle > 0 {
+            // the job is claimed by an idle thread
+            threads.idle -= 1;
+            return;
+        }
+        if threads.running == MAX_THREADS {
+            return;
+        }
+        let spawned = thread::Builder::new()
+            .name("floem-task".to_string())
+            .spawn(move || self.work());
+        if spawned.is_ok() {
+            threads.running += 1;
+        }
+    }
+
+    fn work(&self) {
+        loop {
+            match self.receiver.recv_timeout(IDLE_TIMEOUT) {
+                Ok(job) => {
+                    // a panicking task doesn't take the thread down with it, it just never
+                    // completes
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                    self.threads.lock().idle += 1;
+                }
+                Err(_) => {
+                    let mut threads = self.threads.lock();
+                    // a job may have been submitted for this thread just as it timed out
+                    if !self.receiver.is_empty() {
+                        continue;
+                    }
+                    threads.idle = threads.idle.saturating_sub(1);
+                    threads.running -= 1;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+enum State<T> {
+    Running,
+    Finished(T),
+    Waiting(Box<dyn FnOnce(T) + Send>),
+}
+
+/// A closure running on the thread pool, started with [`spawn_blocking`], or a future started
+/// with `spawn_async`.
+pub struct Task<T> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+impl<T> fmt::Debug for Task<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Task")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+/// Runs `f` on a thread of the pool, starting a new thread if all of them are busy.
+///
+/// The task runs whether or not its result is used. If `f` panics, the panic is contained to
+/// the task, and [`Task::on_complete`] is never called.
+pub fn spawn_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> Task<T> {
+    let state = Arc::new(Mutex::new(State::Running));
+    {
+        let state = state.clone();
+        Pool::get().submit(Box::new(move || finish(&state, f())));
+    }
+    Task { state }
+}
+
+/// Runs `future` on the [`async_runtime`](crate::ext_event::async_runtime), for work that waits
+/// on the network or other IO instead of blocking a thread.
+///
+/// If `future` panics, [`Task::on_complete`] is never called.
+#[cfg(feature = "tokio")]
+pub fn spawn_async<T: Send + 'static>(
+    future: impl std::future::Future<Output = T> + Send + 'static,
+) -> Task<T> {
+    let state = Arc::new(Mutex::new(State::Running));
+    {
+        let state = state.clone();
+        crate::ext_event::async_runtime().spawn(async move { finish(&state, future.await) });
+    }
+    Task { state }
+}
+
+/// Stores the result of a task, or passes it to the completion callback if there is one.
+fn finish<T>(state: &Mutex<State<T>>, value: T) {
+    let mut state = state.lock();
+    match std::mem::replace(&mut *state, State::Running) {
+        State::Waiting(complete) => {
+            drop(state);
+            complete(value);
+        }
+        _ => *state = State::Finished(value),
+    }
+}
+
+impl<T> Task<T> {
+    /// Whether the closure of the task has returned.
+    pub fn is_finished(&self) -> bool {
+        matches!(*self.state.lock(), State::Finished(_))
+    }
+}
+
+impl<T: Send + 'static> Task<T> {
+    /// Runs `callback` with the result of the task on the UI thread, once the task is finished.
+    ///
+    /// The callback runs in a child of the current reactive scope, and doesn't run if that
+    /// scope is disposed before the task finishes, e.g. when the view that started it is
+    /// removed.
+    pub fn on_complete(self, callback: impl FnOnce(T) + 'static) {
+        let send = create_ext_action(Scope::current(), callback);
+        let mut state = self.state.lock();
+        match std::mem::replace(&mut *state, State::Running) {
+            State::Finished(value) => {
+                drop(state);
+                send(value);
+            }
+            _ => *state = State::Waiting(Box::new(send)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn tasks_run_on_the_pool() {
+        let (sender, receiver) = mpsc::channel();
+        let tasks = (0..4)
+            .map(|i| {
+                let sender = sender.clone();
+                spawn_blocking(move || {
+                    sender
+                        .send(thread::current().name().map(str::to_string))
+                        .unwrap();
+                    i
+                })
+            })
+            .collect::<Vec<_>>();
+        for _ in 0..tasks.len() {
+            let name = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+            assert_eq!(name.as_deref(), Some("floem-task"));
+        }
+
+        let panicked = spawn_blocking(|| panic!("the pool outlives this"));
+        let after = spawn_blocking(|| 5);
+        while !after.is_finished() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(!panicked.is_finished());
+    }
+}
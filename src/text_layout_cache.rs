@@ -0,0 +1,174 @@
+//! The text layouts of the labels of a window, shared by the labels with the same text,
+//! attributes and wrap width, like the cells of tables and lists, so each label doesn't shape
+//! its own copy of the text.
+
+use std::{collections::HashMap, rc::Rc};
+
+use crate::text::{AttrsList, TextLayout};
+
+/// How many layout passes a layout that no view uses anymore is kept for.
+const MAX_AGE: u64 = 2;
+
+struct Entry {
+    attrs: AttrsList,
+    width: Option<f32>,
+    layout: Rc<TextLayout>,
+    /// The layout pass the layout was last used in.
+    last_used: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct TextLayoutCache {
+    /// The layouts by their text.
+    entries: HashMap<String, Vec<Entry>>,
+    /// Counts the layout passes.
+    generation: u64,
+    /// The texts that labels will need layouts of in the next layout pass, which are shaped
+    /// together on the thread pool.
+    #[cfg(feature = "parallel")]
+    requested: Vec<(String, AttrsList)>,
+}
+
+impl TextLayoutCache {
+    /// The layout of `text` with `attrs`, wrapped at `width` if it's set.
+    pub(crate) fn get(
+        &mut self,
+        text: &str,
+        attrs: &AttrsList,
+        width: Option<f32>,
+    ) -> Rc<TextLayout> {
+        let generation = self.generation;
+        if let Some(entry) = self.entries.get_mut(text).and_then(|entries| {
+            entries
+                .iter_mut()
+                .find(|entry| entry.width == width && entry.attrs == *attrs)
+        }) {
+            entry.last_used = generation;
+            return entry.layout.clone();
+        }
+
+        let mut layout = TextLayout::new();
+        layout.set_text(text, attrs.clone());
+        if let Some(width) = width {
+            layout.set_size(width, f32::MAX);
+        }
+        let layout = Rc::new(layout);
+        self.entries
+            .entry(text.to_owned())
+            .or_default()
+            .push(Entry {
+                attrs: attrs.clone(),
+                width,
+                layout: layout.clone(),
+                last_used: generation,
+            });
+        layout
+    }
+
+    /// Asks for the layout of `text` with `attrs`, without wrapping, to be shaped before the
+    /// next layout pass.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn request(&mut self, text: &str, attrs: AttrsList) {
+        let cached = self.entries.get(text).is_some_and(|entries| {
+            entries
+                .iter()
+                .any(|entry| entry.width.is_none() && entry.attrs == attrs)
+        });
+        if !cached && !text.is_empty() {
+            self.requested.push((text.to_owned(), attrs));
+        }
+    }
+
+    /// Shapes the requested layouts in parallel, so the labels find them in the cache.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn shape_requested(&mut self) {
+        let mut requested = std::mem::take(&mut self.requested);
+        requested.sort_by(|(a, _), (b, _)| a.cmp(b));
+        requested.dedup();
+        let layouts = crate::text::shape_all(&requested);
+        for ((text, attrs), layout) in requested.into_iter().zip(layouts) {
+            self.entries.entry(text).or_default().push(Entry {
+                attrs,
+                width: None,
+                layout: Rc::new(layout),
+                last_used: self.generation,
+            });
+        }
+    }
+
+    /// Starts a layout pass, and drops the layouts that no view has used in the last passes.
+    pub(crate) fn next_generation(&mut self) {
+        self.generation += 1;
+        let generation = self.generation;
+        self.entries.retain(|_, entries| {
+            entries.retain(|entry| {
+                Rc::strong_count(&entry.layout) > 1 || generation - entry.last_used <= MAX_AGE
+            });
+            !entries.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{TextLayoutCache, MAX_AGE};
+    use crate::text::{Attrs, AttrsList};
+
+    #[test]
+    fn same_text_shares_the_layout() {
+        let mut cache = TextLayoutCache::default();
+        let attrs = AttrsList::new(Attrs::new());
+        let a = cache.get("cell", &attrs, None);
+        let b = cache.get("cell", &attrs, None);
+        assert!(Rc::ptr_eq(&a, &b));
+
+        let wrapped = cache.get("cell", &attrs, Some(10.0));
+        assert!(!Rc::ptr_eq(&a, &wrapped));
+        let bigger = AttrsList::new(Attrs::new().font_size(20.0));
+        assert!(!Rc::ptr_eq(&a, &cache.get("cell", &bigger, None)));
+    }
+
+    #[test]
+    fn unused_layouts_are_dropped() {
+        let mut cache = TextLayoutCache::default();
+        let attrs = AttrsList::new(Attrs::new());
+        let kept = cache.get("kept", &attrs, None);
+        let dropped = cache.get("dropped", &attrs, None);
+        drop(dropped);
+        for _ in 0..=MAX_AGE {
+            cache.next_generation();
+        }
+        assert!(cache.entries.contains_key("kept"));
+        assert!(!cache.entries.contains_key("dropped"));
+        assert!(Rc::ptr_eq(&kept, &cache.get("kept", &attrs, None)));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn requested_layouts_are_shaped_together() {
+        use crate::text::TextLayout;
+
+        let mut cache = TextLayoutCache::default();
+        let attrs = AttrsList::new(Attrs::new());
+        let cached = cache.get("cell 0", &attrs, None);
+        for i in 0..100 {
+            cache.request(&format!("cell {i}"), attrs.clone());
+        }
+        cache.request("cell 1", attrs.clone());
+        assert_eq!(cache.requested.len(), 100);
+
+        cache.next_generation();
+        cache.shape_requested();
+        assert!(cache.requested.is_empty());
+        assert!(Rc::ptr_eq(&cached, &cache.get("cell 0", &attrs, None)));
+        for i in 1..100 {
+            let text = format!("cell {i}");
+            assert_eq!(cache.entries[&text].len(), 1);
+            let shaped = cache.get(&text, &attrs, None);
+            let expected = TextLayout::new_with_text(&text, attrs.clone());
+            assert_eq!(shaped.size(), expected.size());
+        }
+    }
+}
@@ -1,25 +1,257 @@
 use crate::{
-    style::{Background, CursorStyle, Foreground, Style, Transition},
+    forced_colors::{forced_colors, system_colors, SystemColors},
+    notification::ToastClass,
+    prop,
+    style::{Background, CursorStyle, Foreground, Style, StyleClass, StyleProp, Transition},
     unit::{DurationUnitExt, UnitExt},
     views::{
+        breadcrumbs::{
+            BreadcrumbClass, BreadcrumbOverflowClass, BreadcrumbSeparatorClass,
+            CurrentBreadcrumbClass,
+        },
         dropdown::{self},
         scroll,
         slider::{self, SliderClass},
-        ButtonClass, CheckboxClass, LabelClass, LabelCustomStyle, LabeledCheckboxClass,
-        LabeledRadioButtonClass, ListClass, ListItemClass, PlaceholderTextClass, RadioButtonClass,
-        RadioButtonDotClass, TextInputClass, ToggleButtonCircleRad, ToggleButtonClass,
-        ToggleButtonInset, TooltipClass,
+        tab_bar::{
+            ActiveTabClass, TabBarHeaderClass, TabClass, TabCloseClass, TabOverflowButtonClass,
+        },
+        AvatarClass, AvatarGroupClass, BadgeClass, ButtonClass, CheckboxClass, ChipClass,
+        ChipCloseClass, EditableLabelClass, FocusedItemClass, HoveredItemClass, InvalidEditClass,
+        LabelClass, LabelCustomStyle, LabeledCheckboxClass, LabeledRadioButtonClass, ListClass,
+        ListItemClass, MinimapClass, MinimapViewportClass, OtpBoxClass, OtpInputClass,
+        PickedRegionClass, PlaceholderTextClass, ProgressBarClass, ProgressBarFillClass,
+        RadioButtonClass, RadioButtonDotClass, RegionMaskClass, RegionPickerClass, SegmentClass,
+        SegmentIndicatorClass, SegmentedControlClass, SelectedItemClass, SelectedSegmentClass,
+        SpinnerClass, TextInputClass, ToggleButtonCircleRad, ToggleButtonClass, ToggleButtonInset,
+        TooltipClass,
     },
+    window,
 };
+use floem_reactive::{ReadSignal, RwSignal, Scope, SignalGet, SignalUpdate, SignalWith};
 use peniko::{Brush, Color};
-use std::rc::Rc;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 use taffy::style::AlignItems;
 
-pub(crate) struct Theme {
+/// The name of the theme built into Floem, which is registered by default.
+pub const DEFAULT_THEME: &str = "default";
+
+prop!(
+    /// The accent color token of the current theme.
+    pub AccentColor: Color { inherited } = Color::rgb8(114, 74, 140)
+);
+prop!(
+    /// The color token used for surfaces such as popups and tooltips.
+    pub SurfaceColor: Color { inherited } = Color::WHITE_SMOKE
+);
+prop!(
+    /// The base unit of the spacing scale. See [`Theme::spacing`].
+    pub SpacingUnit: f64 { inherited } = 5.0
+);
+
+/// A named bundle of class styles and custom properties (design tokens)
+/// that is applied at the root of every window.
+///
+/// Themes are registered with [`register_theme`] and switched at runtime with
+/// [`set_theme`]. Switching restyles every view in every window that applies the
+/// default theme.
+#[derive(Clone)]
+pub struct Theme {
+    name: Rc<str>,
     pub(crate) background: Color,
     pub(crate) style: Rc<Style>,
 }
 
+impl Default for Theme {
+    /// Floem's built-in theme, named [`DEFAULT_THEME`].
+    fn default() -> Self {
+        default_theme()
+    }
+}
+
+impl Theme {
+    /// Creates an empty theme with a white window background.
+    pub fn new(name: impl Into<Rc<str>>) -> Self {
+        Self {
+            name: name.into(),
+            background: Color::WHITE,
+            style: Rc::new(Style::new()),
+        }
+    }
+
+    /// The name this theme is registered under.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns this theme with a different name, so that it can be registered
+    /// alongside the theme it was derived from.
+    pub fn with_name(mut self, name: impl Into<Rc<str>>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the color used to fill the window behind the root view.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Applies `style` to the root style of this theme.
+    pub fn style(mut self, style: impl FnOnce(Style) -> Style) -> Self {
+        self.style = Rc::new(style(self.style.as_ref().clone()));
+        self
+    }
+
+    /// Sets the style of every view with the class `class`.
+    pub fn class<C: StyleClass>(self, class: C, style: impl FnOnce(Style) -> Style) -> Self {
+        self.style(|s| s.class(class, style))
+    }
+
+    /// Sets a custom property, such as a color token or the [`SpacingUnit`].
+    ///
+    /// Views read tokens from their computed style, so the property should be
+    /// declared as `inherited` to reach every view.
+    pub fn token<P: StyleProp>(self, prop: P, value: impl Into<P::Type>) -> Self {
+        self.style(|s| s.set(prop, value))
+    }
+
+    /// Reads a custom property of this theme.
+    pub fn get<P: StyleProp>(&self, prop: P) -> P::Type {
+        self.style.get(prop)
+    }
+
+    /// Returns step `step` of this theme's spacing scale, a multiple of its [`SpacingUnit`].
+    pub fn spacing(&self, step: u32) -> f64 {
+        self.get(SpacingUnit) * step as f64
+    }
+
+    /// Maps the color tokens and the window background of this theme to `colors`, for the
+    /// [forced colors mode](crate::forced_colors).
+    pub fn with_forced_colors(self, colors: &SystemColors) -> Self {
+        self.background(colors.canvas)
+            .token(AccentColor, colors.highlight)
+            .token(SurfaceColor, colors.canvas)
+            .style(|s| s.color(colors.canvas_text))
+    }
+}
+
+struct ThemeRegistry {
+    themes: HashMap<Rc<str>, Theme>,
+    active: RwSignal<Rc<str>>,
+    system_color_scheme: RwSignal<Option<window::Theme>>,
+}
+
+thread_local! {
+    static THEMES: RefCell<ThemeRegistry> = {
+        // The registry outlives every window, so its signals are not owned by any window scope.
+        let scope = Scope::new();
+        RefCell::new(ThemeRegistry {
+            themes: HashMap::from([(Rc::from(DEFAULT_THEME), default_theme())]),
+            active: scope.create_rw_signal(Rc::from(DEFAULT_THEME)),
+            system_color_scheme: scope.create_rw_signal(None),
+        })
+    };
+}
+
+/// Registers `theme` under its name, replacing any theme with the same name.
+///
+/// If the replaced theme is the active one, every window is restyled.
+pub fn register_theme(theme: Theme) {
+    let active = THEMES.with(|themes| {
+        let mut themes = themes.borrow_mut();
+        let active = themes.active;
+        let is_active = active.with_untracked(|name| **name == *theme.name);
+        themes.themes.insert(theme.name.clone(), theme);
+        is_active.then_some(active)
+    });
+    if let Some(active) = active {
+        // Re-notify subscribers so the new styles are picked up.
+        active.update(|_| {});
+    }
+}
+
+/// Makes the theme registered as `name` the active theme of the application.
+///
+/// Returns `false` and leaves the active theme unchanged if no such theme is registered.
+pub fn set_theme(name: &str) -> bool {
+    let Some((name, active)) = THEMES.with(|themes| {
+        let themes = themes.borrow();
+        themes
+            .themes
+            .get_key_value(name)
+            .map(|(name, _)| (name.clone(), themes.active))
+    }) else {
+        return false;
+    };
+    if active.with_untracked(|active| *active != name) {
+        active.set(name);
+    }
+    true
+}
+
+/// The reactive signal holding the name of the active theme.
+///
+/// Reading it inside an effect subscribes to theme switches.
+pub fn theme_signal() -> RwSignal<Rc<str>> {
+    THEMES.with(|themes| themes.borrow().active)
+}
+
+/// The names of all registered themes.
+pub fn theme_names() -> Vec<Rc<str>> {
+    THEMES.with(|themes| themes.borrow().themes.keys().cloned().collect())
+}
+
+/// Returns the active theme, tracking the active theme signal when called in an effect.
+///
+/// In the [forced colors mode](crate::forced_colors), the colors of the theme are mapped to the
+/// system colors.
+pub fn current_theme() -> Theme {
+    let name = theme_signal().get();
+    let theme = THEMES.with(|themes| {
+        let themes = themes.borrow();
+        themes
+            .themes
+            .get(&name)
+            .cloned()
+            .unwrap_or_else(default_theme)
+    });
+    if forced_colors() {
+        theme.with_forced_colors(&system_colors())
+    } else {
+        theme
+    }
+}
+
+/// The light or dark color scheme reported by the operating system, or `None` if no
+/// window has reported it yet.
+///
+/// The signal is updated whenever the OS setting changes. Use
+/// [`WindowConfig::follow_system_color_scheme`](crate::window::WindowConfig::follow_system_color_scheme)
+/// to switch between a light and a dark theme automatically.
+pub fn system_color_scheme() -> ReadSignal<Option<window::Theme>> {
+    THEMES.with(|themes| themes.borrow().system_color_scheme.read_only())
+}
+
+pub(crate) fn set_system_color_scheme(scheme: window::Theme) {
+    let system_color_scheme = THEMES.with(|themes| themes.borrow().system_color_scheme);
+    if system_color_scheme.get_untracked() != Some(scheme) {
+        system_color_scheme.set(Some(scheme));
+    }
+}
+
+/// The accent color chosen in the settings of the operating system, if it can be queried.
+///
+/// It's the colorization color of the window manager on Windows, the accent color of the
+/// appearance settings on macOS, and the accent color of the desktop portal, or of GNOME, on
+/// Linux and the BSDs. The system is queried on each call, so the color should be kept rather
+/// than queried while styling.
+///
+/// Returns `None` on the web, and when the system has no accent color set, in which case the
+/// [`AccentColor`] token of the active theme should be used instead.
+pub fn system_accent_color() -> Option<Color> {
+    accent::color()
+}
+
 pub(crate) fn default_theme() -> Theme {
     let border = Color::rgb8(140, 140, 140);
 
@@ -239,6 +471,11 @@ pub(crate) fn default_theme() -> Theme {
         .class(scroll::Track, |s| {
             s.hover(|s| s.background(Color::rgba8(166, 166, 166, 30)))
         })
+        .class(MinimapClass, |s| {
+            s.width(100).class(MinimapViewportClass, |s| {
+                s.background(Color::rgba8(166, 166, 166, 60))
+            })
+        })
         .class(ToggleButtonClass, |_| toggle_button_style)
         .class(SliderClass, |s| {
             s.apply_custom(
@@ -254,6 +491,169 @@ pub(crate) fn default_theme() -> Theme {
             .height(15)
             .width(100)
         })
+        .class(ProgressBarClass, |s| {
+            s.height(6)
+                .width(100)
+                .border_radius(100.pct())
+                .background(Color::rgb8(212, 212, 212))
+                .class(ProgressBarFillClass, |s| {
+                    s.border_radius(100.pct())
+                        .background(Color::rgb8(114, 74, 140))
+                        .transition_width(Transition::ease_in_out(150.millis()))
+                })
+        })
+        .class(TabBarHeaderClass, |s| {
+            s.items_center()
+                .border_bottom(1.0)
+                .border_color(Color::rgb8(205, 205, 205))
+                .focus_visible(|s| s.outline(1.0).outline_color(Color::rgb8(114, 74, 140)))
+                .class(TabClass, |s| {
+                    s.items_center()
+                        .gap(6)
+                        .padding_horiz(10)
+                        .padding_vert(5)
+                        .color(Color::rgb8(80, 80, 80))
+                        .hover(|s| s.background(hover_bg_color))
+                })
+                .class(ActiveTabClass, |s| {
+                    s.background(Color::WHITE).color(Color::BLACK)
+                })
+                .class(TabCloseClass, |s| {
+                    s.padding_horiz(3)
+                        .border_radius(3.0)
+                        .selectable(false)
+                        .hover(|s| s.background(Color::rgb8(212, 212, 212)))
+                })
+                .class(TabOverflowButtonClass, |s| {
+                    s.padding_horiz(6)
+                        .selectable(false)
+                        .hover(|s| s.background(hover_bg_color))
+                        .disabled(|s| s.color(Color::GRAY))
+                })
+        })
+        .class(BreadcrumbClass, |s| {
+            s.padding_horiz(4)
+                .border_radius(3.0)
+                .selectable(false)
+                .color(Color::rgb8(80, 80, 80))
+                .hover(|s| s.background(hover_bg_color))
+        })
+        .class(CurrentBreadcrumbClass, |s| s.color(Color::BLACK))
+        .class(BreadcrumbSeparatorClass, |s| {
+            s.padding_horiz(2).selectable(false).color(Color::GRAY)
+        })
+        .class(BreadcrumbOverflowClass, |s| {
+            s.padding_horiz(4)
+                .border_radius(3.0)
+                .selectable(false)
+                .hover(|s| s.background(hover_bg_color))
+        })
+        .class(BadgeClass, |s| {
+            s.min_width(16)
+                .height(16)
+                .padding_horiz(4)
+                .border_radius(8.0)
+                .font_size(10.)
+                .selectable(false)
+                .color(Color::WHITE)
+                .background(Color::rgb8(200, 50, 50))
+        })
+        .class(ChipClass, |s| {
+            s.items_center()
+                .gap(4)
+                .padding_horiz(8)
+                .padding_vert(2)
+                .border(1.0)
+                .border_color(border)
+                .border_radius(100.pct())
+                .selectable(false)
+                .background(Color::WHITE_SMOKE)
+                .class(ChipCloseClass, |s| {
+                    s.color(Color::GRAY).hover(|s| s.color(Color::BLACK))
+                })
+        })
+        .class(AvatarClass, |s| {
+            s.size(32, 32)
+                .border_radius(100.pct())
+                .items_center()
+                .justify_center()
+                .font_size(13.)
+                .selectable(false)
+                .color(Color::WHITE)
+        })
+        .class(AvatarGroupClass, |s| {
+            s.padding_left(8).class(AvatarClass, |s| {
+                s.margin_left(-8).border(2.0).border_color(Color::WHITE)
+            })
+        })
+        .class(SegmentedControlClass, |s| {
+            s.items_center()
+                .padding(2)
+                .border_radius(6.0)
+                .background(Color::rgb8(228, 228, 228))
+                .class(SegmentClass, |s| {
+                    s.padding_horiz(12)
+                        .padding_vert(4)
+                        .border_radius(5.0)
+                        .selectable(false)
+                        .color(Color::rgb8(80, 80, 80))
+                        .focus_visible(|s| s.outline(1.0).outline_color(Color::rgb8(114, 74, 140)))
+                })
+                .class(SelectedSegmentClass, |s| s.color(Color::BLACK))
+                .class(SegmentIndicatorClass, |s| {
+                    s.border_radius(5.0)
+                        .background(Color::WHITE)
+                        .box_shadow_blur(2.0)
+                        .box_shadow_color(Color::BLACK.multiply_alpha(0.15))
+                })
+        })
+        .class(OtpInputClass, |s| {
+            s.gap(8).class(OtpBoxClass, |s| {
+                s.size(36, 44)
+                    .justify_center()
+                    .items_center()
+                    .font_size(20.)
+                    .apply(border_style.clone())
+            })
+        })
+        .class(EditableLabelClass, |s| {
+            s.class(TextInputClass, |s| {
+                s.width_full().padding_horiz(2).padding_vert(0)
+            })
+            .class(InvalidEditClass, |s| {
+                s.border_color(Color::rgb8(209, 52, 56))
+            })
+        })
+        .class(RegionPickerClass, |s| {
+            s.class(RegionMaskClass, |s| {
+                s.background(Color::BLACK.multiply_alpha(0.4))
+            })
+            .class(PickedRegionClass, |s| s.border_color(Color::WHITE))
+        })
+        .class(HoveredItemClass, |s| s.background(hover_bg_color))
+        .class(SelectedItemClass, |s| {
+            s.background(selected_bg_color)
+                .hover(|s| s.background(selected_hover_bg_color))
+        })
+        .class(FocusedItemClass, |s| {
+            s.outline(1.0).outline_color(Color::rgb8(114, 74, 140))
+        })
+        .class(ToastClass, |s| {
+            s.width(320)
+                .gap(2)
+                .padding(10.0)
+                .border(0.5)
+                .border_color(Color::rgb8(140, 140, 140))
+                .border_radius(border_radius)
+                .background(Color::WHITE)
+                .cursor(CursorStyle::Pointer)
+                .box_shadow_blur(6.0)
+                .box_shadow_v_offset(2.0)
+                .box_shadow_color(Color::BLACK.multiply_alpha(0.2))
+        })
+        .class(SpinnerClass, |s| {
+            s.size(20, 20).color(Color::rgb8(114, 74, 140))
+        })
         .class(PlaceholderTextClass, |s| {
             s.color(Color::rgba8(158, 158, 158, 30))
                 .font_size(FONT_SIZE)
@@ -295,7 +695,167 @@ pub(crate) fn default_theme() -> Theme {
         .color(Color::BLACK);
 
     Theme {
+        name: Rc::from(DEFAULT_THEME),
         background: Color::rgb8(248, 248, 248),
         style: Rc::new(theme),
     }
 }
+
+#[cfg(target_os = "windows")]
+mod accent {
+    use peniko::Color;
+    use windows_sys::Win32::{Foundation::BOOL, Graphics::Dwm::DwmGetColorizationColor};
+
+    pub(super) fn color() -> Option<Color> {
+        let mut color = 0;
+        let mut opaque: BOOL = 0;
+        if unsafe { DwmGetColorizationColor(&mut color, &mut opaque) } < 0 {
+            return None;
+        }
+        // the colorization color is 0xAARRGGBB, where the alpha is the one of the glass effect
+        let [b, g, r, _] = color.to_le_bytes();
+        Some(Color::rgb8(r, g, b))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod accent {
+    use std::process::Command;
+
+    use peniko::Color;
+
+    pub(super) fn color() -> Option<Color> {
+        let output = Command::new("defaults")
+            .args(["read", "-g", "AppleAccentColor"])
+            .output()
+            .ok()?;
+        // the key is missing while the default blue accent is chosen
+        let accent = if output.status.success() {
+            String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .parse()
+                .ok()?
+        } else {
+            4
+        };
+        let color = match accent {
+            -1 => Color::rgb8(142, 142, 147),
+            0 => Color::rgb8(255, 59, 48),
+            1 => Color::rgb8(255, 149, 0),
+            2 => Color::rgb8(255, 204, 0),
+            3 => Color::rgb8(40, 205, 65),
+            5 => Color::rgb8(175, 82, 222),
+            6 => Color::rgb8(255, 45, 85),
+            _ => Color::rgb8(0, 122, 255),
+        };
+        Some(color)
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos"), not(target_arch = "wasm32")))]
+mod accent {
+    use std::process::Command;
+
+    use peniko::Color;
+
+    fn run(program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub(super) fn color() -> Option<Color> {
+        let portal = run(
+            "gdbus",
+            &[
+                "call",
+                "--session",
+                "--dest",
+                "org.freedesktop.portal.Desktop",
+                "--object-path",
+                "/org/freedesktop/portal/desktop",
+                "--method",
+                "org.freedesktop.portal.Settings.ReadOne",
+                "org.freedesktop.appearance",
+                "accent-color",
+            ],
+        );
+        portal.as_deref().and_then(parse_portal).or_else(|| {
+            let name = run(
+                "gsettings",
+                &["get", "org.gnome.desktop.interface", "accent-color"],
+            )?;
+            gnome_color(name.trim_matches('\''))
+        })
+    }
+
+    /// Parses the reply of the portal, like `(<(0.21, 0.52, 0.89)>,)`, whose components are
+    /// outside of 0 to 1 when no accent color is set.
+    pub(super) fn parse_portal(reply: &str) -> Option<Color> {
+        let start = reply.find("<(")? + 2;
+        let end = start + reply[start..].find(')')?;
+        let mut components = reply[start..end]
+            .split(',')
+            .map(|component| component.trim().parse::<f64>().ok());
+        let mut next = || {
+            components
+                .next()
+                .flatten()
+                .filter(|c| (0.0..=1.0).contains(c))
+        };
+        let (r, g, b) = (next()?, next()?, next()?);
+        Some(Color::rgb(r, g, b))
+    }
+
+    /// The colors of the accents of GNOME, by their names in its settings.
+    pub(super) fn gnome_color(name: &str) -> Option<Color> {
+        let color = match name {
+            "blue" => Color::rgb8(0x35, 0x84, 0xe4),
+            "teal" => Color::rgb8(0x21, 0x90, 0xa4),
+            "green" => Color::rgb8(0x3a, 0x94, 0x4a),
+            "yellow" => Color::rgb8(0xc8, 0x88, 0x00),
+            "orange" => Color::rgb8(0xed, 0x5b, 0x00),
+            "red" => Color::rgb8(0xe6, 0x2d, 0x42),
+            "pink" => Color::rgb8(0xd5, 0x61, 0x99),
+            "purple" => Color::rgb8(0x91, 0x41, 0xac),
+            "slate" => Color::rgb8(0x6f, 0x83, 0x96),
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use peniko::Color;
+
+        use super::{gnome_color, parse_portal};
+
+        #[test]
+        fn portal_colors() {
+            assert_eq!(
+                parse_portal("(<(1.0, 0.5, 0.0)>,)"),
+                Some(Color::rgb(1.0, 0.5, 0.0))
+            );
+            // the portal replies with components out of range when there is no accent color
+            assert_eq!(parse_portal("(<(-1.0, -1.0, -1.0)>,)"), None);
+            assert_eq!(parse_portal("()"), None);
+        }
+
+        #[test]
+        fn gnome_colors() {
+            assert_eq!(gnome_color("blue"), Some(Color::rgb8(0x35, 0x84, 0xe4)));
+            assert_eq!(gnome_color("mauve"), None);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", all(unix, not(target_arch = "wasm32")))))]
+mod accent {
+    use peniko::Color;
+
+    pub(super) fn color() -> Option<Color> {
+        None
+    }
+}
@@ -0,0 +1,553 @@
+#![deny(missing_docs)]
+//! An icon in the system tray, or in the status bar on macOS, with an optional menu.
+//!
+//! On Linux the tray is managed by a GTK thread that is started with the first tray icon, as
+//! the tray implementations there are built on GTK rather than on the event loop of the
+//! windows.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Once,
+    },
+};
+
+use floem_winit::window::WindowId;
+use peniko::kurbo::Point;
+use tray_icon::{
+    menu::{CheckMenuItem, IsMenuItem, MenuEvent, MenuId, MenuItem, PredefinedMenuItem, Submenu},
+    MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent, TrayIconId,
+};
+
+use crate::{
+    app::{Application, UserEvent},
+    menu::Menu,
+    pointer::PointerButton,
+    window_id::WindowIdExt,
+};
+
+thread_local! {
+    static TRAYS: RefCell<HashMap<u64, TrayState>> = Default::default();
+}
+
+/// How often the GTK thread checks for updates of the tray icons.
+#[cfg(target_os = "linux")]
+const GTK_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// An event of a tray icon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrayEvent {
+    /// The icon was clicked. The position is in physical screen coordinates.
+    Click {
+        /// The button that was released.
+        button: PointerButton,
+        /// The position of the pointer.
+        position: Point,
+    },
+    /// The icon was double clicked. Not reported on Linux and macOS.
+    DoubleClick {
+        /// The button that was double clicked.
+        button: PointerButton,
+        /// The position of the pointer.
+        position: Point,
+    },
+}
+
+/// The error returned when a tray icon can't be created.
+#[derive(Debug, Clone)]
+pub struct TrayError(String);
+
+impl fmt::Display for TrayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to create the tray icon: {}", self.0)
+    }
+}
+
+impl std::error::Error for TrayError {}
+
+/// The image of a tray icon.
+#[derive(Debug, Clone)]
+pub struct TrayImage {
+    rgba: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl TrayImage {
+    /// Creates an image from RGBA pixels, returning `None` if the length of `rgba` isn't
+    /// `width * height * 4`.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Option<Self> {
+        (rgba.len() == width as usize * height as usize * 4).then_some(Self {
+            rgba,
+            width,
+            height,
+        })
+    }
+
+    /// Decodes an image in one of the enabled image formats, e.g. a PNG.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, image::ImageError> {
+        let image = image::load_from_memory(bytes)?.into_rgba8();
+        let (width, height) = image.dimensions();
+        Ok(Self {
+            rgba: image.into_raw(),
+            width,
+            height,
+        })
+    }
+
+    fn platform_icon(self) -> Option<tray_icon::Icon> {
+        tray_icon::Icon::from_rgba(self.rgba, self.width, self.height).ok()
+    }
+}
+
+/// The configuration of a tray icon, see [`new_tray`].
+#[derive(Default)]
+pub struct TrayConfig {
+    icon: Option<TrayImage>,
+    tooltip: Option<String>,
+    menu: Option<Menu>,
+    on_event: Option<Rc<dyn Fn(TrayEvent)>>,
+    restore_window: Option<WindowId>,
+}
+
+impl TrayConfig {
+    /// Sets the image of the icon.
+    pub fn icon(mut self, icon: TrayImage) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Sets the tooltip shown when hovering the icon.
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Sets the menu of the icon, which is opened by clicking the icon. The actions of the menu
+    /// items are run when they are selected, like those of the menus of a window.
+    pub fn menu(mut self, menu: Menu) -> Self {
+        self.menu = Some(menu);
+        self
+    }
+
+    /// Sets a function that is called with the clicks on the icon.
+    pub fn on_event(mut self, on_event: impl Fn(TrayEvent) + 'static) -> Self {
+        self.on_event = Some(Rc::new(on_event));
+        self
+    }
+
+    /// Shows the window when the icon is clicked with the primary button, to restore a window
+    /// that was hidden by [`minimize_to_tray`].
+    pub fn restore_window(mut self, window_id: WindowId) -> Self {
+        self.restore_window = Some(window_id);
+        self
+    }
+}
+
+/// A tray icon created with [`new_tray`]. The icon is removed when the last clone of it is
+/// dropped.
+#[derive(Clone)]
+pub struct Tray {
+    handle: Rc<TrayHandle>,
+}
+
+struct TrayHandle {
+    id: u64,
+}
+
+impl Drop for TrayHandle {
+    fn drop(&mut self) {
+        let _ = TRAYS.try_with(|trays| trays.borrow_mut().remove(&self.id));
+        send(Command::Remove(self.id));
+    }
+}
+
+impl Tray {
+    /// Replaces the image of the icon.
+    pub fn set_icon(&self, icon: Option<TrayImage>) {
+        send(Command::SetIcon(self.handle.id, icon));
+    }
+
+    /// Replaces the tooltip of the icon.
+    pub fn set_tooltip(&self, tooltip: Option<String>) {
+        send(Command::SetTooltip(self.handle.id, tooltip));
+    }
+
+    /// Replaces the menu of the icon.
+    pub fn set_menu(&self, menu: Option<Menu>) {
+        let id = self.handle.id;
+        let mut actions = HashMap::new();
+        let menu = menu.map(|menu| menu.tray_menu(&mut actions));
+        TRAYS.with(|trays| {
+            if let Some(state) = trays.borrow_mut().get_mut(&id) {
+                state.actions = Rc::new(actions);
+            }
+        });
+        send(Command::SetMenu(id, menu));
+    }
+
+    /// Shows or hides the icon.
+    pub fn set_visible(&self, visible: bool) {
+        send(Command::SetVisible(self.handle.id, visible));
+    }
+}
+
+/// Creates a tray icon. The icon is shown until the returned [`Tray`] is dropped, so it has
+/// to be kept, e.g. in the state of the application.
+///
+/// The icon can be updated after it is created, so it can follow the state of the
+/// application with an effect:
+///
+/// ```no_run
+/// # use floem::{menu::{Menu, MenuItem}, quit_app, reactive::{create_effect, create_rw_signal, SignalGet}};
+/// # use floem::tray::{new_tray, TrayConfig};
+/// let unread = create_rw_signal(0);
+/// let tray = new_tray(
+///     TrayConfig::default()
+///         .tooltip("Mail")
+///         .menu(Menu::new("").entry(MenuItem::new("Quit").action(quit_app))),
+/// )
+/// .unwrap();
+/// create_effect(move |_| tray.set_tooltip(Some(format!("Mail ({} unread)", unread.get()))));
+/// ```
+pub fn new_tray(config: TrayConfig) -> Result<Tray, TrayError> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    init_event_handlers();
+
+    let mut actions = HashMap::new();
+    let menu = config.menu.map(|menu| menu.tray_menu(&mut actions));
+    let (reply, result) = mpsc::channel();
+    send(Command::Create {
+        id,
+        icon: config.icon,
+        tooltip: config.tooltip,
+        menu,
+        reply,
+    });
+    result
+        .recv()
+        .unwrap_or_else(|_| Err(TrayError("the tray thread has stopped".to_string())))?;
+
+    TRAYS.with(|trays| {
+        trays.borrow_mut().insert(
+            id,
+            TrayState {
+                on_event: config.on_event,
+                restore_window: config.restore_window,
+                actions: Rc::new(actions),
+            },
+        )
+    });
+    Ok(Tray {
+        handle: Rc::new(TrayHandle { id }),
+    })
+}
+
+/// Hides the window so that it is only reachable through a tray icon. The window keeps its
+/// state and can be shown again with [`restore_from_tray`].
+pub fn minimize_to_tray(window_id: WindowId) {
+    window_id.set_visible(false);
+}
+
+/// Shows a window that was hidden with [`minimize_to_tray`].
+pub fn restore_from_tray(window_id: WindowId) {
+    window_id.set_visible(true);
+    window_id.minimized(false);
+}
+
+/// An entry of a tray menu, which unlike a [`Menu`] can be sent to the GTK thread.
+pub(crate) enum TrayMenuEntry {
+    Separator,
+    Item {
+        id: u64,
+        title: String,
+        selected: Option<bool>,
+        enabled: bool,
+    },
+    SubMenu {
+        title: String,
+        enabled: bool,
+        children: Vec<TrayMenuEntry>,
+    },
+}
+
+/// A message from the tray to the event loop.
+#[derive(Debug)]
+pub(crate) enum TrayMessage {
+    Icon { id: u64, event: TrayEvent },
+    Menu(u64),
+}
+
+struct TrayState {
+    on_event: Option<Rc<dyn Fn(TrayEvent)>>,
+    restore_window: Option<WindowId>,
+    actions: Rc<HashMap<u64, Box<dyn Fn()>>>,
+}
+
+pub(crate) fn handle_message(message: TrayMessage) {
+    match message {
+        TrayMessage::Icon { id, event } => {
+            let Some((on_event, restore_window)) = TRAYS.with(|trays| {
+                trays
+                    .borrow()
+                    .get(&id)
+                    .map(|state| (state.on_event.clone(), state.restore_window))
+            }) else {
+                return;
+            };
+            if let (
+                Some(window_id),
+                TrayEvent::Click {
+                    button: PointerButton::Primary,
+                    ..
+                },
+            ) = (restore_window, event)
+            {
+                restore_from_tray(window_id);
+            }
+            if let Some(on_event) = on_event {
+                on_event(event);
+            }
+        }
+        TrayMessage::Menu(item) => {
+            // the actions are cloned out so that they can replace the menu
+            let actions: Vec<_> =
+                TRAYS.with(|trays| trays.borrow().values().map(|s| s.actions.clone()).collect());
+            if let Some(action) = actions.iter().find_map(|actions| actions.get(&item)) {
+                action();
+            }
+        }
+    }
+}
+
+/// Forwards the events of the tray icons and their menus to the event loop, as they may be
+/// reported on another thread.
+fn init_event_handlers() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        TrayIconEvent::set_event_handler(Some(|event: TrayIconEvent| {
+            if let Some(message) = icon_message(event) {
+                Application::with_event_loop_proxy(|proxy| {
+                    let _ = proxy.send_event(UserEvent::Tray(message));
+                });
+            }
+        }));
+        MenuEvent::set_event_handler(Some(|event: MenuEvent| {
+            if let Ok(item) = event.id.0.parse() {
+                Application::with_event_loop_proxy(|proxy| {
+                    let _ = proxy.send_event(UserEvent::Tray(TrayMessage::Menu(item)));
+                });
+            }
+        }));
+    });
+}
+
+fn icon_message(event: TrayIconEvent) -> Option<TrayMessage> {
+    let button = |button| match button {
+        MouseButton::Left => PointerButton::Primary,
+        MouseButton::Right => PointerButton::Secondary,
+        MouseButton::Middle => PointerButton::Auxiliary,
+    };
+    let (id, event) = match event {
+        TrayIconEvent::Click {
+            id,
+            position,
+            button: clicked,
+            button_state: MouseButtonState::Up,
+            ..
+        } => (
+            id,
+            TrayEvent::Click {
+                button: button(clicked),
+                position: Point::new(position.x, position.y),
+            },
+        ),
+        TrayIconEvent::DoubleClick {
+            id,
+            position,
+            button: clicked,
+            ..
+        } => (
+            id,
+            TrayEvent::DoubleClick {
+                button: button(clicked),
+                position: Point::new(position.x, position.y),
+            },
+        ),
+        _ => return None,
+    };
+    Some(TrayMessage::Icon {
+        id: id.0.parse().ok()?,
+        event,
+    })
+}
+
+enum Command {
+    Create {
+        id: u64,
+        icon: Option<TrayImage>,
+        tooltip: Option<String>,
+        menu: Option<Vec<TrayMenuEntry>>,
+        reply: mpsc::Sender<Result<(), TrayError>>,
+    },
+    SetIcon(u64, Option<TrayImage>),
+    SetTooltip(u64, Option<String>),
+    SetMenu(u64, Option<Vec<TrayMenuEntry>>),
+    SetVisible(u64, bool),
+    Remove(u64),
+}
+
+/// Owns the platform tray icons, on the main thread or on the GTK thread on Linux.
+#[derive(Default)]
+struct Backend {
+    icons: HashMap<u64, tray_icon::TrayIcon>,
+}
+
+impl Backend {
+    fn run(&mut self, command: Command) {
+        match command {
+            Command::Create {
+                id,
+                icon,
+                tooltip,
+                menu,
+                reply,
+            } => {
+                let mut builder = TrayIconBuilder::new().with_id(TrayIconId::new(id.to_string()));
+                if let Some(icon) = icon.and_then(TrayImage::platform_icon) {
+                    builder = builder.with_icon(icon);
+                }
+                if let Some(tooltip) = tooltip {
+                    builder = builder.with_tooltip(tooltip);
+                }
+                if let Some(menu) = menu {
+                    builder = builder.with_menu(Box::new(platform_menu(&menu)));
+                }
+                let result = builder
+                    .build()
+                    .map(|icon| {
+                        self.icons.insert(id, icon);
+                    })
+                    .map_err(|err| TrayError(err.to_string()));
+                let _ = reply.send(result);
+            }
+            Command::SetIcon(id, icon) => {
+                if let Some(tray) = self.icons.get(&id) {
+                    let _ = tray.set_icon(icon.and_then(TrayImage::platform_icon));
+                }
+            }
+            Command::SetTooltip(id, tooltip) => {
+                if let Some(tray) = self.icons.get(&id) {
+                    let _ = tray.set_tooltip(tooltip);
+                }
+            }
+            Command::SetMenu(id, menu) => {
+                if let Some(tray) = self.icons.get(&id) {
+                    tray.set_menu(menu.map(|menu| {
+                        Box::new(platform_menu(&menu)) as Box<dyn tray_icon::menu::ContextMenu>
+                    }));
+                }
+            }
+            Command::SetVisible(id, visible) => {
+                if let Some(tray) = self.icons.get(&id) {
+                    let _ = tray.set_visible(visible);
+                }
+            }
+            Command::Remove(id) => {
+                self.icons.remove(&id);
+            }
+        }
+    }
+}
+
+fn platform_menu(entries: &[TrayMenuEntry]) -> tray_icon::menu::Menu {
+    let menu = tray_icon::menu::Menu::new();
+    for entry in entries {
+        let _ = menu.append(platform_entry(entry).as_ref());
+    }
+    menu
+}
+
+fn platform_entry(entry: &TrayMenuEntry) -> Box<dyn IsMenuItem> {
+    match entry {
+        TrayMenuEntry::Separator => Box::new(PredefinedMenuItem::separator()),
+        TrayMenuEntry::Item {
+            id,
+            title,
+            selected: Some(selected),
+            enabled,
+        } => Box::new(CheckMenuItem::with_id(
+            MenuId::new(id.to_string()),
+            title,
+            *enabled,
+            *selected,
+            None,
+        )),
+        TrayMenuEntry::Item {
+            id,
+            title,
+            selected: None,
+            enabled,
+        } => Box::new(MenuItem::with_id(
+            MenuId::new(id.to_string()),
+            title,
+            *enabled,
+            None,
+        )),
+        TrayMenuEntry::SubMenu {
+            title,
+            enabled,
+            children,
+        } => {
+            let submenu = Submenu::new(title, *enabled);
+            for child in children {
+                let _ = submenu.append(platform_entry(child).as_ref());
+            }
+            Box::new(submenu)
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send(command: Command) {
+    thread_local! {
+        static BACKEND: RefCell<Backend> = Default::default();
+    }
+    BACKEND.with(|backend| backend.borrow_mut().run(command));
+}
+
+#[cfg(target_os = "linux")]
+fn send(command: Command) {
+    static SENDER: std::sync::OnceLock<mpsc::Sender<Command>> = std::sync::OnceLock::new();
+    let sender = SENDER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || run_gtk(receiver));
+        sender
+    });
+    let _ = sender.send(command);
+}
+
+#[cfg(target_os = "linux")]
+fn run_gtk(receiver: mpsc::Receiver<Command>) {
+    if let Err(err) = gtk::init() {
+        for command in receiver {
+            if let Command::Create { reply, .. } = command {
+                let _ = reply.send(Err(TrayError(err.to_string())));
+            }
+        }
+        return;
+    }
+    let mut backend = Backend::default();
+    gtk::glib::timeout_add_local(GTK_POLL_INTERVAL, move || {
+        while let Ok(command) = receiver.try_recv() {
+            backend.run(command);
+        }
+        gtk::glib::ControlFlow::Continue
+    });
+    gtk::main();
+}
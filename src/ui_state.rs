@@ -0,0 +1,207 @@
+#![deny(missing_docs)]
+//! Saving the transient state of views, like scroll positions and selected tabs, and restoring
+//! it when the application is started again, see [`UiStateRegistry`].
+//!
+//! Views are given a persistence key, like [`Scroll::persist`](crate::views::Scroll::persist)
+//! and [`TabBar::persist`](crate::views::TabBar::persist), and any other state, like the
+//! expanded nodes of a tree or the size of a split pane, can be kept in a signal that is
+//! persisted with [`UiStateRegistry::persist_signal`].
+//!
+//! The state is kept in memory while the application runs, and written to the storage when a
+//! window is closed and when [`UiStateRegistry::save`] is called.
+
+use std::{cell::RefCell, collections::HashMap, fmt::Display, path::PathBuf, rc::Rc, str::FromStr};
+
+use floem_reactive::{create_effect, RwSignal, SignalUpdate, SignalWith};
+
+use crate::window_geometry::app_config_dir;
+
+thread_local! {
+    static STORAGE: RefCell<Option<Rc<dyn UiStateStorage>>> = const { RefCell::new(None) };
+    static REGISTRY: RefCell<Option<UiStateRegistry>> = const { RefCell::new(None) };
+}
+
+/// Where the state of the views is saved, see [`UiStateRegistry::set_storage`].
+pub trait UiStateStorage {
+    /// Returns all the saved state, by key.
+    fn load(&self) -> HashMap<String, String>;
+    /// Saves all the state, by key.
+    fn save(&self, state: &HashMap<String, String>);
+}
+
+/// Stores the state of the views in a text file, which is the default storage.
+pub struct FileUiStateStorage {
+    path: PathBuf,
+}
+
+impl FileUiStateStorage {
+    /// Creates a storage that uses the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The file used by default: `ui-state` in a directory named after the executable, in the
+    /// configuration directory of the platform.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(app_config_dir()?.join("ui-state"))
+    }
+}
+
+impl UiStateStorage for FileUiStateStorage {
+    fn load(&self) -> HashMap<String, String> {
+        std::fs::read_to_string(&self.path)
+            .map(|content| parse(&content))
+            .unwrap_or_default()
+    }
+
+    fn save(&self, state: &HashMap<String, String>) {
+        if let Some(dir) = self.path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(&self.path, format(state));
+    }
+}
+
+/// A line of the file for each key, followed by a tab and the value.
+fn format(state: &HashMap<String, String>) -> String {
+    let mut keys = state.keys().collect::<Vec<_>>();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| {
+            let clean = |text: &str| text.replace(['\t', '\n', '\r'], " ");
+            format!("{}\t{}\n", clean(key), clean(&state[key]))
+        })
+        .collect()
+}
+
+fn parse(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// The transient state of the views of the application, which is saved across restarts.
+///
+/// The state is a value for each persistence key, which is loaded from the storage when it's
+/// first used. Keys should be unique in the application, e.g. `"sidebar.scroll"`.
+///
+/// ```
+/// # use floem::reactive::RwSignal;
+/// # use floem::ui_state::UiStateRegistry;
+/// // the expanded nodes of a file tree, which are expanded again after a restart
+/// let expanded = RwSignal::new(String::new());
+/// UiStateRegistry::persist_signal("file-tree.expanded", expanded);
+/// ```
+pub struct UiStateRegistry {
+    state: HashMap<String, String>,
+    /// Whether the state changed since it was loaded or saved.
+    changed: bool,
+}
+
+impl UiStateRegistry {
+    fn with<R>(f: impl FnOnce(&mut UiStateRegistry) -> R) -> R {
+        REGISTRY.with_borrow_mut(|registry| {
+            let registry = registry.get_or_insert_with(|| UiStateRegistry {
+                state: storage().map(|storage| storage.load()).unwrap_or_default(),
+                changed: false,
+            });
+            f(registry)
+        })
+    }
+
+    /// Sets where the state is saved, instead of the [`FileUiStateStorage`] at its
+    /// [default path](FileUiStateStorage::default_path). The state is loaded from the new
+    /// storage when it's next used.
+    pub fn set_storage(storage: impl UiStateStorage + 'static) {
+        STORAGE.with_borrow_mut(|current| *current = Some(Rc::new(storage)));
+        REGISTRY.with_borrow_mut(|registry| *registry = None);
+    }
+
+    /// The value saved under `key`.
+    pub fn get(key: &str) -> Option<String> {
+        Self::with(|registry| registry.state.get(key).cloned())
+    }
+
+    /// Sets the value saved under `key`.
+    pub fn set(key: &str, value: impl Into<String>) {
+        let value = value.into();
+        Self::with(|registry| {
+            if registry.state.get(key) != Some(&value) {
+                registry.state.insert(key.to_string(), value);
+                registry.changed = true;
+            }
+        });
+    }
+
+    /// Removes the value saved under `key`.
+    pub fn remove(key: &str) {
+        Self::with(|registry| {
+            if registry.state.remove(key).is_some() {
+                registry.changed = true;
+            }
+        });
+    }
+
+    /// Writes the state to the storage if it changed. This is done when a window is closed, and
+    /// can be called to save the state at other times, e.g. periodically.
+    pub fn save() {
+        let Some(storage) = storage() else {
+            return;
+        };
+        REGISTRY.with_borrow_mut(|registry| {
+            if let Some(registry) = registry.as_mut().filter(|registry| registry.changed) {
+                storage.save(&registry.state);
+                registry.changed = false;
+            }
+        });
+    }
+
+    /// Sets `signal` to the value saved under `key`, if there is one that can be parsed, and
+    /// saves the value of the signal under `key` whenever it changes.
+    pub fn persist_signal<T>(key: impl Into<String>, signal: RwSignal<T>)
+    where
+        T: FromStr + Display + 'static,
+    {
+        let key = key.into();
+        if let Some(value) = Self::get(&key).and_then(|value| value.parse().ok()) {
+            signal.set(value);
+        }
+        create_effect(move |_| {
+            let value = signal.with(|value| value.to_string());
+            Self::set(&key, value);
+        });
+    }
+}
+
+fn storage() -> Option<Rc<dyn UiStateStorage>> {
+    STORAGE.with_borrow_mut(|storage| {
+        if storage.is_none() {
+            *storage = FileUiStateStorage::default_path()
+                .map(|path| Rc::new(FileUiStateStorage::new(path)) as Rc<dyn UiStateStorage>);
+        }
+        storage.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_format_round_trips() {
+        let state = HashMap::from([
+            ("sidebar.scroll".to_string(), "0 120.5".to_string()),
+            ("tabs".to_string(), "2".to_string()),
+        ]);
+        assert_eq!(format(&state), "sidebar.scroll\t0 120.5\ntabs\t2\n");
+        assert_eq!(parse(&format(&state)), state);
+
+        let state = HashMap::from([("a\tb".to_string(), "one\ntwo".to_string())]);
+        assert_eq!(
+            parse(&format(&state)),
+            HashMap::from([("a b".to_string(), "one two".to_string())])
+        );
+    }
+}
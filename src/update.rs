@@ -1,9 +1,13 @@
 use std::{any::Any, cell::RefCell, collections::HashMap};
 
 use floem_winit::window::ResizeDirection;
+use image::RgbaImage;
 use peniko::kurbo::{Point, Rect, Size, Vec2};
 
-use crate::{id::ViewId, menu::Menu, view::View};
+use crate::{
+    event::Event, id::ViewId, menu::Menu, pointer::PointerLockMode, style::CursorStyle, view::View,
+    views::scroll::ScrollIntoView, window::CaptionButton,
+};
 
 thread_local! {
     /// Stores all the update message with their original `ViewId`
@@ -25,6 +29,8 @@ thread_local! {
 }
 
 type DeferredUpdateMessages = HashMap<ViewId, Vec<(ViewId, Box<dyn Any>)>>;
+/// Called with the image of a [`UpdateMessage::Capture`].
+pub(crate) type CaptureCallback = Box<dyn FnOnce(Option<RgbaImage>)>;
 
 pub(crate) enum UpdateMessage {
     Focus(ViewId),
@@ -51,6 +57,10 @@ pub(crate) enum UpdateMessage {
     Draggable {
         id: ViewId,
     },
+    CaptionButton {
+        id: ViewId,
+        button: CaptionButton,
+    },
     ToggleWindowMaximized,
     SetWindowMaximized(bool),
     MinimizeWindow,
@@ -75,10 +85,19 @@ pub(crate) enum UpdateMessage {
     RemoveOverlay {
         id: ViewId,
     },
+    /// Removes a view that isn't a child of any view anymore.
+    RemoveView {
+        id: ViewId,
+    },
     Inspect,
+    SendEvent {
+        id: ViewId,
+        event: Event,
+    },
     ScrollTo {
         id: ViewId,
         rect: Option<Rect>,
+        options: Option<ScrollIntoView>,
     },
     FocusWindow,
     SetImeAllowed {
@@ -90,4 +109,29 @@ pub(crate) enum UpdateMessage {
     },
     WindowVisible(bool),
     ViewTransitionAnimComplete(ViewId),
+    OverrideCursor {
+        token: u64,
+        cursor: CursorStyle,
+    },
+    RestoreCursor {
+        token: u64,
+    },
+    PointerLock {
+        id: ViewId,
+        mode: PointerLockMode,
+    },
+    ReleasePointerLock {
+        id: ViewId,
+    },
+    Capture {
+        region: CaptureRegion,
+        on_capture: CaptureCallback,
+    },
+}
+
+/// The part of a window that is captured by [`UpdateMessage::Capture`].
+pub(crate) enum CaptureRegion {
+    View(ViewId),
+    /// A rect in the coordinates of the window.
+    Rect(Rect),
 }
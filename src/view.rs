@@ -268,6 +268,18 @@ pub trait View {
         core::any::type_name::<Self>().into()
     }
 
+    /// The text that the view shows, if it's a view showing text like a label, which is used to
+    /// find views in tests.
+    fn text_content(&self) -> Option<String> {
+        None
+    }
+
+    /// The distance from the top of the view to the baseline of its first line of text, if it
+    /// shows text, which is drawn by the layout overlay of the inspector.
+    fn baseline(&self) -> Option<f64> {
+        None
+    }
+
     /// Use this method to react to changes in view-related state.
     /// You will usually send state to this hook manually using the `View`'s `Id` handle
     ///
@@ -367,6 +379,14 @@ impl View for Box<dyn View> {
         (**self).debug_name()
     }
 
+    fn text_content(&self) -> Option<String> {
+        (**self).text_content()
+    }
+
+    fn baseline(&self) -> Option<f64> {
+        (**self).baseline()
+    }
+
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
         (**self).update(cx, state)
     }
@@ -0,0 +1,129 @@
+#![deny(missing_docs)]
+//! Writing custom views, which is how the views of Floem are written, for widgets that can't be
+//! composed from other views.
+//!
+//! A custom view is a type that implements [`View`](crate::View) and holds the
+//! [`ViewId`](crate::ViewId) of the view. [`custom_view!`] declares such a type, and the methods
+//! of the trait are implemented as needed:
+//!
+//! - **State**: changes of signals are sent to the view from an effect with
+//!   [`ViewId::update_state`](crate::ViewId::update_state), and handled in
+//!   [`View::update`](crate::View::update).
+//! - **Style**: [`View::style_pass`](crate::View::style_pass) reads the style props the view
+//!   uses, which are declared with [`prop_extractor!`](crate::prop_extractor) and read with
+//!   `read` from the [`StyleCx`](crate::context::StyleCx). Changes to them should request a
+//!   paint or layout.
+//! - **Measure and layout**: [`View::layout`](crate::View::layout) lays out the children with
+//!   [`LayoutCx::layout_node`](crate::context::LayoutCx::layout_node), or a view without
+//!   children with the measured size of its content with
+//!   [`LayoutCx::layout_measured`](crate::context::LayoutCx::layout_measured). The computed
+//!   size is read with [`ViewId::get_size`](crate::ViewId::get_size) after
+//!   [`View::compute_layout`](crate::View::compute_layout), whose default is
+//!   [`default_compute_layout`](crate::default_compute_layout).
+//! - **Events**: [`View::event_before_children`](crate::View::event_before_children) and
+//!   [`View::event_after_children`](crate::View::event_after_children) handle the events of the
+//!   view, in its own coordinates.
+//! - **Paint**: [`View::paint`](crate::View::paint) draws with the
+//!   [`Renderer`](crate::Renderer) methods of the [`PaintCx`](crate::context::PaintCx), like
+//!   `fill` and `draw_text`, in the coordinates of the view. The children are painted with
+//!   [`PaintCx::paint_children`](crate::context::PaintCx::paint_children).
+//!
+//! ```
+//! use floem::context::{PaintCx, StyleCx, UpdateCx};
+//! use floem::peniko::Color;
+//! use floem::reactive::create_effect;
+//! use floem::style::Background;
+//! use floem::view_macros::custom_view;
+//! use floem::{prop_extractor, Renderer, ViewId};
+//!
+//! prop_extractor! {
+//!     MeterStyle {
+//!         fill: Background,
+//!     }
+//! }
+//!
+//! custom_view! {
+//!     /// A bar that is filled by the value, from 0 to 1.
+//!     pub struct Meter {
+//!         value: f64,
+//!         style: MeterStyle,
+//!     }
+//!
+//!     impl View {
+//!         fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+//!             if let Ok(value) = state.downcast::<f64>() {
+//!                 self.value = *value;
+//!                 self.id.request_paint();
+//!             }
+//!         }
+//!
+//!         fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+//!             if self.style.read(cx) {
+//!                 self.id.request_paint();
+//!             }
+//!         }
+//!
+//!         fn paint(&mut self, cx: &mut PaintCx) {
+//!             let size = self.id.get_size().unwrap_or_default();
+//!             let filled = size.to_rect().with_size((size.width * self.value, size.height));
+//!             let fill = self.style.fill().unwrap_or(Color::GREEN.into());
+//!             cx.fill(&filled, &fill, 0.0);
+//!         }
+//!     }
+//! }
+//!
+//! pub fn meter(value: impl Fn() -> f64 + 'static) -> Meter {
+//!     let meter = Meter::new(0.0, MeterStyle::default());
+//!     let id = meter.id;
+//!     create_effect(move |_| id.update_state(value()));
+//!     meter
+//! }
+//! ```
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __custom_view {
+    (
+        $(#[$attrs:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attrs:meta])* $field_vis:vis $field:ident: $ty:ty),*
+            $(,)?
+        }
+
+        impl View {
+            $($items:tt)*
+        }
+    ) => {
+        $(#[$attrs])*
+        $vis struct $name {
+            id: $crate::ViewId,
+            $($(#[$field_attrs])* $field_vis $field: $ty,)*
+        }
+
+        impl $name {
+            /// Creates the view, with a new id.
+            #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+            $vis fn new($($field: $ty),*) -> Self {
+                Self {
+                    id: $crate::ViewId::new(),
+                    $($field,)*
+                }
+            }
+        }
+
+        impl $crate::View for $name {
+            fn id(&self) -> $crate::ViewId {
+                self.id
+            }
+
+            $($items)*
+        }
+    };
+}
+
+/// Declares a custom view: a struct with the given fields and an `id` field with its
+/// [`ViewId`](crate::ViewId), a `new` function that takes the fields and creates a new id, and
+/// an implementation of [`View`](crate::View) with the methods in `impl View`, besides `id`.
+///
+/// See the [module](self) for an example.
+pub use __custom_view as custom_view;
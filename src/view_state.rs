@@ -1,12 +1,18 @@
 use crate::{
-    animate::Animation,
+    animate::{Animation, LayoutAnimation, TransitionGroup},
     context::{
-        EventCallback, InteractionState, MenuCallback, MoveListener, ResizeCallback, ResizeListener,
+        EventCallback, InteractionState, MenuCallback, MoveListener, PaintCallback, ResizeCallback,
+        ResizeListener,
     },
     event::EventListener,
+    forced_colors,
+    gestures::GestureArena,
+    hit_test::HitIndex,
+    id::ViewId,
+    layer::LayerCache,
     pointer::PointerInputEvent,
     prop_extractor,
-    responsive::ScreenSizeBp,
+    responsive::ScreenInfo,
     style::{
         Background, BorderColor, BorderRadius, BoxShadowProp, LayoutProps, Outline, OutlineColor,
         Style, StyleClassRef, StyleSelectors,
@@ -14,7 +20,7 @@ use crate::{
 };
 use bitflags::bitflags;
 use im::HashSet;
-use peniko::kurbo::{Affine, Point, Rect};
+use peniko::kurbo::{Affine, Point, Rect, Size};
 use smallvec::SmallVec;
 use std::{cell::RefCell, collections::HashMap, marker::PhantomData, rc::Rc};
 use taffy::tree::NodeId;
@@ -154,29 +160,59 @@ pub struct ViewState {
     /// Layout is requested on all direct and indirect children.
     pub(crate) request_style_recursive: bool,
     pub(crate) has_style_selectors: StyleSelectors,
+    /// The size container queries were last resolved against.
+    pub(crate) container_size: Size,
     pub(crate) viewport: Option<Rect>,
     pub(crate) layout_rect: Rect,
+    /// The index of the children that finds those under the pointer, for views with many
+    /// children.
+    pub(crate) hit_index: Option<HitIndex>,
     pub(crate) layout_props: LayoutProps,
     pub(crate) view_style_props: ViewStyleProps,
     pub(crate) animations: Stack<Animation>,
+    pub(crate) layout_animation: Option<LayoutAnimation>,
+    pub(crate) gestures: Option<Rc<RefCell<GestureArena>>>,
     pub(crate) classes: Vec<StyleClassRef>,
     pub(crate) dragging_style: Option<Style>,
     pub(crate) combined_style: Style,
     pub(crate) taffy_style: taffy::style::Style,
     pub(crate) event_listeners: HashMap<EventListener, Vec<Rc<RefCell<EventCallback>>>>,
+    /// The priorities of `event_listeners`, which are kept sorted from the highest priority.
+    pub(crate) event_listener_priorities: HashMap<EventListener, Vec<i32>>,
+    /// Listeners that run on the way down to the target of an event, before its children.
+    pub(crate) capture_listeners: HashMap<EventListener, Vec<Rc<RefCell<EventCallback>>>>,
+    pub(crate) capture_listener_priorities: HashMap<EventListener, Vec<i32>>,
     pub(crate) context_menu: Option<Rc<MenuCallback>>,
     pub(crate) popout_menu: Option<Rc<MenuCallback>>,
     pub(crate) resize_listener: Option<Rc<RefCell<ResizeListener>>>,
     pub(crate) window_origin: Point,
     pub(crate) move_listener: Option<Rc<RefCell<MoveListener>>>,
     pub(crate) cleanup_listener: Option<Rc<dyn Fn()>>,
+    /// Painted after the background of the view, before its content.
+    pub(crate) paint_before: Vec<Rc<PaintCallback>>,
+    /// Painted after the content of the view, its border and its outline.
+    pub(crate) paint_after: Vec<Rc<PaintCallback>>,
+    /// The cached paint of the view, see
+    /// [`Decorators::cache_layer`](crate::views::Decorators::cache_layer).
+    pub(crate) layer: Option<LayerCache>,
     pub(crate) last_pointer_down: Option<PointerInputEvent>,
     pub(crate) is_hidden_state: IsHiddenState,
     pub(crate) num_waiting_animations: u16,
+    /// The group that is told when the view starts and stops playing its exit animations.
+    pub(crate) transition_group: Option<TransitionGroup>,
     pub(crate) disable_default_events: HashSet<EventListener>,
     pub(crate) pointer_events: bool,
     pub(crate) transform: Affine,
+    /// The zoom and pan applied by a parent [`Zoomable`](crate::views::Zoomable), on top of `transform`.
+    pub(crate) zoom_transform: Affine,
+    /// The view that events are routed through instead of the parent, for content that a
+    /// [`Portal`](crate::views::Portal) moved into an overlay.
+    pub(crate) logical_parent: Option<ViewId>,
     pub(crate) debug_name: SmallVec<[String; 1]>,
+    /// The style properties edited in the inspector, which are applied over the rest of the style.
+    pub(crate) style_overrides: Option<Style>,
+    /// The id that tests find the view by, see [`Decorators::test_id`](crate::views::Decorators::test_id).
+    pub(crate) test_id: Option<String>,
 }
 
 impl ViewState {
@@ -186,30 +222,45 @@ impl ViewState {
             viewport: None,
             style: Default::default(),
             layout_rect: Rect::ZERO,
+            hit_index: None,
             layout_props: Default::default(),
             view_style_props: Default::default(),
             requested_changes: ChangeFlags::all(),
             request_style_recursive: false,
             has_style_selectors: StyleSelectors::default(),
+            container_size: Size::ZERO,
             animations: Default::default(),
+            layout_animation: None,
+            gestures: None,
             classes: Vec::new(),
             combined_style: Style::new(),
             taffy_style: taffy::style::Style::DEFAULT,
             dragging_style: None,
             event_listeners: HashMap::new(),
+            event_listener_priorities: HashMap::new(),
+            capture_listeners: HashMap::new(),
+            capture_listener_priorities: HashMap::new(),
             context_menu: None,
             popout_menu: None,
             resize_listener: None,
             move_listener: None,
             cleanup_listener: None,
+            paint_before: Vec::new(),
+            paint_after: Vec::new(),
+            layer: None,
             last_pointer_down: None,
             window_origin: Point::ZERO,
             is_hidden_state: IsHiddenState::None,
             num_waiting_animations: 0,
+            transition_group: None,
             disable_default_events: HashSet::new(),
             pointer_events: true,
             transform: Affine::IDENTITY,
+            zoom_transform: Affine::IDENTITY,
+            logical_parent: None,
             debug_name: Default::default(),
+            style_overrides: None,
+            test_id: None,
         }
     }
 
@@ -219,7 +270,7 @@ impl ViewState {
         &mut self,
         view_style: Option<Style>,
         interact_state: InteractionState,
-        screen_size_bp: ScreenSizeBp,
+        screen: ScreenInfo,
         view_class: Option<StyleClassRef>,
         context: &Style,
     ) -> bool {
@@ -237,20 +288,37 @@ impl ViewState {
 
         self.has_style_selectors = computed_style.selectors();
 
-        computed_style.apply_interact_state(&interact_state, screen_size_bp);
+        computed_style.apply_interact_state(&interact_state, &screen);
+        computed_style.resolve_vars(context);
+        if screen.forced_colors {
+            computed_style = forced_colors::adjust(
+                computed_style,
+                &interact_state,
+                &forced_colors::system_colors(),
+            );
+        }
+        if let Some(overrides) = &self.style_overrides {
+            computed_style.apply_mut(overrides.clone());
+        }
 
-        for animation in self
-            .animations
-            .stack
-            .iter_mut()
-            .filter(|anim| anim.can_advance() || anim.should_apply_folded())
-        {
+        for animation in self.animations.stack.iter_mut() {
+            if let Some(spring) = &mut animation.spring {
+                new_frame |= spring.animate_into(&mut computed_style);
+                continue;
+            }
+            if !(animation.can_advance() || animation.should_apply_folded()) {
+                continue;
+            }
             if animation.can_advance() {
                 new_frame = true;
 
                 animation.animate_into(&mut computed_style);
 
                 animation.advance();
+            } else if animation.seeked {
+                // the position of a paused animation was changed so the props are animated once at the new position.
+                animation.seeked = false;
+                animation.animate_into(&mut computed_style);
             } else {
                 animation.apply_folded(&mut computed_style)
             }
@@ -264,7 +332,9 @@ impl ViewState {
 
     pub(crate) fn has_active_animation(&self) -> bool {
         for animation in self.animations.stack.iter() {
-            if animation.is_in_progress() {
+            if animation.is_in_progress()
+                || animation.spring.as_ref().is_some_and(|s| s.is_moving())
+            {
                 return true;
             }
         }
@@ -282,12 +352,31 @@ impl ViewState {
     pub(crate) fn add_event_listener(
         &mut self,
         listener: EventListener,
+        priority: i32,
         action: Box<EventCallback>,
     ) {
-        self.event_listeners
-            .entry(listener)
-            .or_default()
-            .push(Rc::new(RefCell::new(action)));
+        insert_listener(
+            &mut self.event_listeners,
+            &mut self.event_listener_priorities,
+            listener,
+            priority,
+            action,
+        );
+    }
+
+    pub(crate) fn add_capture_listener(
+        &mut self,
+        listener: EventListener,
+        priority: i32,
+        action: Box<EventCallback>,
+    ) {
+        insert_listener(
+            &mut self.capture_listeners,
+            &mut self.capture_listener_priorities,
+            listener,
+            priority,
+            action,
+        );
     }
 
     pub(crate) fn update_resize_listener(&mut self, action: Box<ResizeCallback>) {
@@ -308,3 +397,20 @@ impl ViewState {
         self.cleanup_listener = Some(Rc::new(action));
     }
 }
+
+/// Inserts `action` after the listeners with the same or a higher priority.
+fn insert_listener(
+    listeners: &mut HashMap<EventListener, Vec<Rc<RefCell<EventCallback>>>>,
+    priorities: &mut HashMap<EventListener, Vec<i32>>,
+    listener: EventListener,
+    priority: i32,
+    action: Box<EventCallback>,
+) {
+    let priorities = priorities.entry(listener).or_default();
+    let index = priorities.partition_point(|p| *p >= priority);
+    priorities.insert(index, priority);
+    listeners
+        .entry(listener)
+        .or_default()
+        .insert(index, Rc::new(RefCell::new(action)));
+}
@@ -0,0 +1,169 @@
+#![deny(missing_docs)]
+//! Avatars, round pictures of users that fall back to their initials, and groups of them.
+
+use std::{cell::Cell, path::PathBuf, rc::Rc, sync::Arc};
+
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate};
+use peniko::{Blob, Color};
+
+use crate::{
+    style_class,
+    task::spawn_blocking,
+    view::IntoView,
+    views::{clip, dyn_container, label, stack_from_iter, Clip, Decorators, Stack},
+};
+
+use super::img::img_dynamic;
+
+style_class!(
+    /// The style class that is applied to avatars, and to the avatar that counts the avatars of a
+    /// group that aren't shown.
+    pub AvatarClass
+);
+
+style_class!(
+    /// The style class that is applied to avatar groups.
+    pub AvatarGroupClass
+);
+
+/// The background colors of avatars without a picture, one of which is picked by the name.
+const AVATAR_COLORS: [Color; 8] = [
+    Color::rgb8(229, 57, 53),
+    Color::rgb8(216, 27, 96),
+    Color::rgb8(142, 36, 170),
+    Color::rgb8(57, 73, 171),
+    Color::rgb8(3, 155, 229),
+    Color::rgb8(0, 137, 123),
+    Color::rgb8(67, 160, 71),
+    Color::rgb8(244, 81, 30),
+];
+
+/// The background color of the avatar that counts the avatars of a group that aren't shown.
+const OVERFLOW_COLOR: Color = Color::rgb8(117, 117, 117);
+
+/// An avatar of the user called `name`, which shows the picture at the path returned by `image`.
+///
+/// The picture is loaded on a background thread, see [`spawn_blocking`]. Until it's loaded, or
+/// when there is no picture or it can't be loaded, the initials of the name are shown on a
+/// background color that is picked by the name, so a user has the same color everywhere.
+///
+/// ```no_run
+/// # use std::path::PathBuf;
+/// # use floem::views::avatar;
+/// avatar(|| "Ada Lovelace".to_string(), || Some(PathBuf::from("ada.png")));
+/// ```
+pub fn avatar(
+    name: impl Fn() -> String + 'static,
+    image: impl Fn() -> Option<PathBuf> + 'static,
+) -> Clip {
+    let name = Rc::new(name);
+    let loaded = RwSignal::new(None::<peniko::Image>);
+    // the picture that is being loaded, so a picture that finishes after the path changed is
+    // dropped
+    let generation = Rc::new(Cell::new(0u64));
+
+    create_effect(move |_| {
+        let path = image();
+        let current = generation.get() + 1;
+        generation.set(current);
+        loaded.set(None);
+        if let Some(path) = path {
+            let generation = generation.clone();
+            spawn_blocking(move || load_image(path)).on_complete(move |image| {
+                if generation.get() == current {
+                    loaded.set(image);
+                }
+            });
+        }
+    });
+
+    let initials_name = name.clone();
+    clip(dyn_container(
+        move || loaded.get(),
+        move |image| match image {
+            Some(image) => img_dynamic(move || image.clone())
+                .style(|s| s.size_full())
+                .into_any(),
+            None => {
+                let name = initials_name.clone();
+                label(move || initials(&name())).into_any()
+            }
+        },
+    ))
+    .class(AvatarClass)
+    .style(move |s| s.background(avatar_color(&name())))
+}
+
+/// A row of overlapping `avatars`, where only the first `max_shown` are shown, followed by an
+/// avatar that counts the others.
+pub fn avatar_group<V: IntoView + 'static>(
+    avatars: impl IntoIterator<Item = V>,
+    max_shown: usize,
+) -> Stack {
+    let mut avatars = avatars
+        .into_iter()
+        .map(IntoView::into_any)
+        .collect::<Vec<_>>();
+    let hidden = avatars.len().saturating_sub(max_shown);
+    if hidden > 0 {
+        avatars.truncate(max_shown);
+        avatars.push(
+            clip(label(move || format!("+{hidden}")))
+                .class(AvatarClass)
+                .style(|s| s.background(OVERFLOW_COLOR))
+                .into_any(),
+        );
+    }
+    stack_from_iter(avatars).class(AvatarGroupClass)
+}
+
+fn load_image(path: PathBuf) -> Option<peniko::Image> {
+    let image = image::open(path).ok()?.into_rgba8();
+    let (width, height) = image.dimensions();
+    let blob = Blob::new(Arc::new(image.into_vec()));
+    Some(peniko::Image::new(
+        blob,
+        peniko::Format::Rgba8,
+        width,
+        height,
+    ))
+}
+
+/// The first letters of the first and the last word of `name`, in uppercase.
+fn initials(name: &str) -> String {
+    let mut words = name.split_whitespace();
+    let first = words.next().and_then(|word| word.chars().next());
+    let last = words.last().and_then(|word| word.chars().next());
+    first
+        .into_iter()
+        .chain(last)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// The background color for `name`, which is the same every time for the same name.
+fn avatar_color(name: &str) -> Color {
+    let hash = name.bytes().fold(0u32, |hash, byte| {
+        hash.wrapping_mul(31).wrapping_add(byte as u32)
+    });
+    AVATAR_COLORS[hash as usize % AVATAR_COLORS.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initials_of_first_and_last_word() {
+        assert_eq!(initials("Ada Lovelace"), "AL");
+        assert_eq!(initials("  grace brewster murray hopper "), "GH");
+        assert_eq!(initials("ferris"), "F");
+        assert_eq!(initials(""), "");
+    }
+
+    #[test]
+    fn colors_follow_the_name() {
+        assert_eq!(avatar_color("Ada"), avatar_color("Ada"));
+        assert_ne!(avatar_color("Ada"), avatar_color("Alan"));
+    }
+}
@@ -0,0 +1,179 @@
+#![deny(missing_docs)]
+//! Badges that show a count over the corner of a view, and chips, small labels for tags and
+//! filters that can be removed with a close button.
+
+use std::fmt::Display;
+
+use floem_reactive::{create_memo, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use taffy::FlexWrap;
+
+use crate::{
+    style_class,
+    view::IntoView,
+    views::{dyn_stack, h_stack, label, stack, Decorators, Stack},
+};
+
+style_class!(
+    /// The style class that is applied to the count of a badge.
+    pub BadgeClass
+);
+
+style_class!(
+    /// The style class that is applied to chips, and to the chip that counts the chips of a group
+    /// that aren't shown.
+    pub ChipClass
+);
+
+style_class!(
+    /// The style class that is applied to the close buttons of chips.
+    pub ChipCloseClass
+);
+
+style_class!(
+    /// The style class that is applied to chip groups.
+    pub ChipGroupClass
+);
+
+/// The largest count that a badge shows, larger counts are shown as `99+`.
+const MAX_BADGE_COUNT: usize = 99;
+
+/// How far a badge sticks out over the corner of its view.
+const BADGE_OFFSET: f64 = -6.;
+
+/// A chip of a chip group.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum ChipEntry {
+    /// The chip of the item at an index.
+    Item(usize, String),
+    /// The chip that counts the chips that aren't shown.
+    More,
+}
+
+/// Shows `count` over the top right corner of `child`, or nothing while the count is zero.
+///
+/// ```
+/// # use floem::reactive::{RwSignal, SignalGet};
+/// # use floem::views::{badge, button};
+/// let unread = RwSignal::new(3);
+/// badge(button("Inbox"), move || unread.get());
+/// ```
+pub fn badge<V: IntoView + 'static>(child: V, count: impl Fn() -> usize + 'static) -> Stack {
+    let text = create_memo(move |_| badge_text(count()));
+    stack((
+        child,
+        label(move || text.get().unwrap_or_default())
+            .class(BadgeClass)
+            .style(move |s| {
+                s.absolute()
+                    .inset_top(BADGE_OFFSET)
+                    .inset_right(BADGE_OFFSET)
+                    .apply_if(text.with(Option::is_none), |s| s.hide())
+            }),
+    ))
+}
+
+/// A chip that shows `text`.
+pub fn chip<S: Display + 'static>(text: impl Fn() -> S + 'static) -> Stack {
+    h_stack((label(text),)).class(ChipClass)
+}
+
+/// A chip that shows `text`, with a button that calls `on_close`.
+pub fn closable_chip<S: Display + 'static>(
+    text: impl Fn() -> S + 'static,
+    on_close: impl Fn() + 'static,
+) -> Stack {
+    h_stack((
+        label(text),
+        label(|| "×")
+            .class(ChipCloseClass)
+            .on_click_stop(move |_| on_close()),
+    ))
+    .class(ChipClass)
+}
+
+/// A group of chips for `items` that wraps onto more lines, where only the first `max_shown`
+/// chips are shown, followed by a chip that counts the others and shows them when it's clicked.
+///
+/// When `closable` is true, the chips have close buttons that remove their item from `items`.
+pub fn chip_group(items: RwSignal<Vec<String>>, max_shown: usize, closable: bool) -> Stack {
+    let expanded = RwSignal::new(false);
+    let hidden = move || items.with(|items| hidden_chips(items.len(), max_shown, expanded.get()));
+
+    let chips = dyn_stack(
+        move || {
+            let hidden = hidden();
+            let mut chips = items.with(|items| {
+                items
+                    .iter()
+                    .take(items.len() - hidden)
+                    .cloned()
+                    .enumerate()
+                    .map(|(index, item)| ChipEntry::Item(index, item))
+                    .collect::<Vec<_>>()
+            });
+            if hidden > 0 {
+                chips.push(ChipEntry::More);
+            }
+            chips
+        },
+        |entry| entry.clone(),
+        move |entry| match entry {
+            ChipEntry::Item(index, item) if closable => closable_chip(
+                move || item.clone(),
+                move || {
+                    items.update(|items| {
+                        if index < items.len() {
+                            items.remove(index);
+                        }
+                    })
+                },
+            )
+            .into_any(),
+            ChipEntry::Item(_, item) => chip(move || item.clone()).into_any(),
+            ChipEntry::More => chip(move || format!("+{}", hidden()))
+                .on_click_stop(move |_| expanded.set(true))
+                .into_any(),
+        },
+    )
+    .style(|s| s.flex_wrap(FlexWrap::Wrap).items_center().gap(4));
+
+    stack((chips,)).class(ChipGroupClass)
+}
+
+/// The text of a badge for `count`.
+fn badge_text(count: usize) -> Option<String> {
+    match count {
+        0 => None,
+        count if count > MAX_BADGE_COUNT => Some(format!("{MAX_BADGE_COUNT}+")),
+        count => Some(count.to_string()),
+    }
+}
+
+/// How many of `len` chips are counted instead of shown.
+fn hidden_chips(len: usize, max_shown: usize, expanded: bool) -> usize {
+    if expanded {
+        0
+    } else {
+        len.saturating_sub(max_shown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn badge_counts_are_capped() {
+        assert_eq!(badge_text(0), None);
+        assert_eq!(badge_text(7).as_deref(), Some("7"));
+        assert_eq!(badge_text(99).as_deref(), Some("99"));
+        assert_eq!(badge_text(100).as_deref(), Some("99+"));
+    }
+
+    #[test]
+    fn extra_chips_are_counted() {
+        assert_eq!(hidden_chips(3, 5, false), 0);
+        assert_eq!(hidden_chips(8, 5, false), 3);
+        assert_eq!(hidden_chips(8, 5, true), 0);
+    }
+}
@@ -0,0 +1,236 @@
+#![deny(missing_docs)]
+//! Breadcrumbs, the segments of a path with separators between them, like the folders of a file
+//! or the pages of a settings dialog.
+//!
+//! Clicking a segment calls [`Breadcrumbs::on_navigate`] with its index. When the segments don't
+//! fit, the ones in the middle are collapsed into a button that opens a menu with them, while the
+//! first and the last segment stay visible.
+//!
+//! ```
+//! # use floem::reactive::{RwSignal, SignalGet, SignalUpdate};
+//! # use floem::views::breadcrumbs::breadcrumbs;
+//! let path = RwSignal::new(vec![
+//!     "Settings".to_string(),
+//!     "Editor".to_string(),
+//!     "Font".to_string(),
+//! ]);
+//! breadcrumbs(move || path.get())
+//!     .on_navigate(move |index| path.update(|path| path.truncate(index + 1)));
+//! ```
+use std::{any::Any, ops::Range};
+
+use floem_reactive::{create_effect, create_memo, RwSignal, SignalGet, SignalUpdate, SignalWith};
+
+use crate::{
+    context::UpdateCx,
+    id::ViewId,
+    menu::{Menu, MenuItem},
+    style::Style,
+    style_class,
+    view::{IntoView, View},
+    views::{dyn_stack, h_stack, label, Decorators},
+};
+
+style_class!(
+    /// The style class that is applied to breadcrumbs.
+    pub BreadcrumbsClass
+);
+
+style_class!(
+    /// The style class that is applied to every segment of breadcrumbs.
+    pub BreadcrumbClass
+);
+
+style_class!(
+    /// The style class that is applied to the last segment of breadcrumbs, after
+    /// [`BreadcrumbClass`].
+    pub CurrentBreadcrumbClass
+);
+
+style_class!(
+    /// The style class that is applied to the separators between segments.
+    pub BreadcrumbSeparatorClass
+);
+
+style_class!(
+    /// The style class that is applied to the button that opens the menu of collapsed segments.
+    pub BreadcrumbOverflowClass
+);
+
+/// The width that is assumed for the collapsed segments button before it was laid out.
+const OVERFLOW_WIDTH: f64 = 32.;
+
+/// Navigates to the segment at an index.
+struct Navigate(usize);
+
+/// Breadcrumbs, created with [`breadcrumbs`].
+pub struct Breadcrumbs {
+    id: ViewId,
+    separator: RwSignal<String>,
+    on_navigate: Option<Box<dyn Fn(usize)>>,
+}
+
+/// Creates breadcrumbs with a segment for each of the `items`.
+pub fn breadcrumbs(items: impl Fn() -> Vec<String> + 'static) -> Breadcrumbs {
+    let id = ViewId::new();
+    let separator = RwSignal::new("›".to_string());
+    let segments = RwSignal::new(Vec::new());
+    // the widths of the segments with their separators, which are kept while they're collapsed
+    let widths = RwSignal::new(Vec::<f64>::new());
+    let overflow_width = RwSignal::new(OVERFLOW_WIDTH);
+    let available = RwSignal::new(0.);
+
+    create_effect(move |_| {
+        let items = items();
+        widths.update(|widths| widths.resize(items.len(), 0.));
+        segments.set(items);
+    });
+
+    let collapsed = create_memo(move |_| {
+        widths.with(|widths| collapsed_segments(widths, overflow_width.get(), available.get()))
+    });
+
+    let separator_view = move || label(move || separator.get()).class(BreadcrumbSeparatorClass);
+
+    let overflow = move || {
+        h_stack((
+            separator_view(),
+            label(|| "…")
+                .class(BreadcrumbOverflowClass)
+                .popout_menu(move || {
+                    segments.with_untracked(|segments| {
+                        collapsed
+                            .get_untracked()
+                            .fold(Menu::new(""), |menu, index| {
+                                menu.entry(
+                                    MenuItem::new(segments[index].clone())
+                                        .action(move || id.update_state(Navigate(index))),
+                                )
+                            })
+                    })
+                }),
+        ))
+        .style(move |s| s.apply_if(collapsed.with(Range::is_empty), |s| s.hide()))
+        .on_resize(move |rect| {
+            if rect.width() > 0. {
+                overflow_width.set(rect.width());
+            }
+        })
+    };
+
+    let segment = move |(index, text): (usize, String)| {
+        let is_current = move || segments.with(|segments| index + 1 == segments.len());
+        let segment = h_stack((
+            separator_view().style(move |s| s.apply_if(index == 0, |s| s.hide())),
+            label(move || text.clone())
+                .class(BreadcrumbClass)
+                .class_if(is_current, CurrentBreadcrumbClass)
+                .on_click_stop(move |_| id.update_state(Navigate(index))),
+        ))
+        .style(move |s| {
+            let is_collapsed = collapsed.with(|collapsed| collapsed.contains(&index));
+            s.apply_if(is_collapsed, |s| s.hide())
+        })
+        .on_resize(move |rect| {
+            // collapsed segments are laid out without a size, so their last width is kept
+            if rect.width() > 0. {
+                widths.update(|widths| {
+                    if let Some(width) = widths.get_mut(index) {
+                        *width = rect.width();
+                    }
+                });
+            }
+        });
+        if index == 0 {
+            h_stack((segment, overflow())).into_any()
+        } else {
+            segment.into_any()
+        }
+    };
+
+    let list = dyn_stack(
+        move || segments.get().into_iter().enumerate(),
+        |(index, text)| (*index, text.clone()),
+        segment,
+    )
+    .style(|s| s.items_center());
+
+    id.set_children(vec![list]);
+
+    Breadcrumbs {
+        id,
+        separator,
+        on_navigate: None,
+    }
+    .on_resize(move |rect| available.set(rect.width()))
+    .class(BreadcrumbsClass)
+}
+
+impl View for Breadcrumbs {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Breadcrumbs".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        // the width doesn't follow the segments, so collapsing them can't change it
+        Some(Style::new().width_full().min_width(0.).items_center())
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if let Ok(navigate) = state.downcast::<Navigate>() {
+            if let Some(on_navigate) = &self.on_navigate {
+                on_navigate(navigate.0);
+            }
+        }
+    }
+}
+
+impl Breadcrumbs {
+    /// Sets the text between segments, which is `›` by default.
+    pub fn separator(self, separator: impl Into<String>) -> Self {
+        self.separator.set(separator.into());
+        self
+    }
+
+    /// Sets a function that is called with the index of a segment when the user clicks it, or
+    /// picks it from the menu of collapsed segments.
+    pub fn on_navigate(mut self, on_navigate: impl Fn(usize) + 'static) -> Self {
+        self.on_navigate = Some(Box::new(on_navigate));
+        self
+    }
+}
+
+/// The segments that are collapsed so the others fit in `available`, starting after the first
+/// one. The last segment is never collapsed.
+fn collapsed_segments(widths: &[f64], overflow_width: f64, available: f64) -> Range<usize> {
+    let mut width = widths.iter().sum::<f64>();
+    if width <= available || widths.len() <= 2 {
+        return 0..0;
+    }
+    width += overflow_width;
+    let mut end = 1;
+    while end < widths.len() - 1 && width > available {
+        width -= widths[end];
+        end += 1;
+    }
+    1..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn middle_segments_collapse() {
+        let widths = [50., 40., 40., 40., 60.];
+        assert_eq!(collapsed_segments(&widths, 20., 300.), 0..0);
+        assert_eq!(collapsed_segments(&widths, 20., 200.), 1..3);
+        // the first and the last segment stay when nothing fits
+        assert_eq!(collapsed_segments(&widths, 20., 10.), 1..4);
+        assert_eq!(collapsed_segments(&[100., 100.], 20., 10.), 0..0);
+    }
+}
@@ -4,20 +4,41 @@
 //!
 //! The decorator trait is the primary interface for extending the appearance and functionality of ['View']s.
 
-use floem_reactive::{create_effect, create_updater, SignalUpdate};
+use floem_reactive::{create_effect, create_updater};
 use floem_winit::keyboard::Key;
-use peniko::kurbo::{Point, Rect};
+use peniko::kurbo::{Point, Rect, Vec2};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+#[cfg(target_arch = "wasm32")]
+use web_time::Duration;
+
+use std::rc::Rc;
 
 use crate::{
-    action::{set_window_menu, set_window_scale, set_window_title},
-    animate::Animation,
+    action::{
+        drag_window, minimize_window, set_window_menu, set_window_scale, set_window_title,
+        toggle_window_maximized,
+    },
+    animate::{
+        add_enter_animation, add_exit_animation, Animation, LayoutAnimation, TransitionGroup,
+    },
+    context::PaintCx,
     event::{Event, EventListener, EventPropagation},
+    gestures::{
+        GestureArena, GestureRecognizer, HoverIntent, LongPress, Pan, PanGesture, Swipe,
+        SwipeDirection,
+    },
     keyboard::Modifiers,
+    layer::LayerCache,
     menu::Menu,
-    style::{Style, StyleClass, StyleSelector},
+    pointer::PointerButton,
+    style::{CursorStyle, Style, StyleClass, StyleSelector, Transition},
     view::{IntoView, View},
+    window::{close_window, CaptionButton},
 };
 
+use super::list_navigation::ListNavigation;
+
 /// A trait that extends the appearance and functionality of Views through styling and event handling.
 pub trait Decorators: IntoView<V = Self::DV> + Sized {
     /// The type of the decorated view.
@@ -98,6 +119,14 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Set an id that tests can find the view by, see
+    /// [`HeadlessHarness::find_by_test_id`](crate::headless::HeadlessHarness::find_by_test_id).
+    fn test_id(self, id: impl Into<String>) -> Self::DV {
+        let view = self.into_view();
+        view.id().state().borrow_mut().test_id = Some(id.into());
+        view
+    }
+
     /// The visual style to apply when the mouse hovers over the element
     fn dragging_style(self, style: impl Fn(Style) -> Style + 'static) -> Self::DV {
         let view = self.into_view();
@@ -182,6 +211,14 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Sets the cursor that is shown while the pointer is over the view.
+    ///
+    /// # Reactivity
+    /// The `cursor` function is reactive, so the cursor can change with a signal.
+    fn cursor_icon(self, cursor: impl Fn() -> CursorStyle + 'static) -> Self::DV {
+        self.style(move |s| s.cursor(cursor()))
+    }
+
     /// Mark the view as draggable
     fn draggable(self) -> Self::DV {
         let view = self.into_view();
@@ -216,6 +253,48 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Add an event handler for the given [EventListener] that runs before the handlers with a
+    /// lower priority. [`on_event`](Self::on_event) adds handlers with a priority of `0`.
+    fn on_event_with_priority(
+        self,
+        listener: EventListener,
+        priority: i32,
+        action: impl FnMut(&Event) -> EventPropagation + 'static,
+    ) -> Self::DV {
+        let view = self.into_view();
+        view.id()
+            .add_event_listener_with_priority(listener, priority, Box::new(action));
+        view
+    }
+
+    /// Add an event handler for the given [EventListener] that runs in the capture phase, when
+    /// the event is on its way to this view or any of its descendants and before they handle it.
+    ///
+    /// Keyboard events are captured on the ancestors of the focused view. Returning
+    /// [`EventPropagation::Stop`] keeps the event from reaching its target, which can be used for
+    /// global shortcuts or to trap focus in a modal.
+    fn on_event_capture(
+        self,
+        listener: EventListener,
+        action: impl FnMut(&Event) -> EventPropagation + 'static,
+    ) -> Self::DV {
+        self.on_event_capture_with_priority(listener, 0, action)
+    }
+
+    /// Like [`on_event_capture`](Self::on_event_capture), running before the capture handlers
+    /// of this view with a lower priority.
+    fn on_event_capture_with_priority(
+        self,
+        listener: EventListener,
+        priority: i32,
+        action: impl FnMut(&Event) -> EventPropagation + 'static,
+    ) -> Self::DV {
+        let view = self.into_view();
+        view.id()
+            .add_capture_listener(listener, priority, Box::new(action));
+        view
+    }
+
     /// Add an handler for pressing down a specific key.
     ///
     /// NOTE: View should have `.keyboard_navigable()` in order to receive keyboard events
@@ -343,6 +422,91 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         })
     }
 
+    /// Add an event handler for the pointer settling on the view or leaving it.
+    ///
+    /// The handler is called with `true` once the pointer has been over the view for
+    /// `enter_delay`, and with `false` once it has been away from the view for `exit_delay`.
+    /// Passing over the view quickly, or leaving it briefly, doesn't call the handler at all.
+    fn on_hover_intent(
+        self,
+        enter_delay: Duration,
+        exit_delay: Duration,
+        action: impl Fn(bool) + 'static,
+    ) -> Self::DV {
+        let view = self.into_view();
+        HoverIntent::add(view.id(), enter_delay, exit_delay, Rc::new(action));
+        view
+    }
+
+    /// Add a gesture recognizer to the view. It competes with the other recognizers of the view
+    /// for each press of the pointer, see the [gestures](crate::gestures) module.
+    fn on_gesture(self, recognizer: impl GestureRecognizer + 'static) -> Self::DV {
+        let view = self.into_view();
+        GestureArena::add(view.id(), Box::new(recognizer));
+        view
+    }
+
+    /// Add an event handler for holding the primary pointer button down on the view for
+    /// `duration` without moving. The handler receives the position of the pointer.
+    fn on_long_press(self, duration: Duration, action: impl Fn(Point) + 'static) -> Self::DV {
+        self.on_gesture(LongPress {
+            duration,
+            action: Box::new(action),
+        })
+    }
+
+    /// Add an event handler for dragging the pointer at least `threshold` pixels in `direction`
+    /// and releasing it. The handler receives the velocity of the pointer at release.
+    fn on_swipe(
+        self,
+        direction: SwipeDirection,
+        threshold: f64,
+        action: impl Fn(Vec2) + 'static,
+    ) -> Self::DV {
+        self.on_gesture(Swipe {
+            direction,
+            threshold,
+            action: Box::new(action),
+        })
+    }
+
+    /// Add an event handler for dragging the pointer over the view. The handler is called with
+    /// the [`GesturePhase::Began`](crate::gestures::GesturePhase::Began) phase once the pointer
+    /// moves a few pixels, and then on every move until the pointer is released.
+    fn on_pan(self, action: impl Fn(&PanGesture) + 'static) -> Self::DV {
+        self.on_gesture(Pan {
+            action: Box::new(action),
+            last: None,
+        })
+    }
+
+    /// Adds keyboard navigation between the items of a list, which are the children of the view,
+    /// like the items of a [`dyn_stack`](crate::views::dyn_stack) or a
+    /// [`virtual_stack`](crate::views::virtual_stack).
+    ///
+    /// Only one item of the list can be reached with `Tab`, the one that was focused last. From
+    /// there, the up and down arrow keys move the focus to the previous and next item, `Home` and
+    /// `End` to the first and last item, and `PageUp` and `PageDown` by the height of the parent
+    /// of the list, which is usually a scroll view. Typing the start of the text of an item, which
+    /// `item_text` returns for the index of a child, moves the focus to it.
+    ///
+    /// The items shouldn't be made [`keyboard_navigable`](Self::keyboard_navigable) themselves.
+    ///
+    /// ```rust
+    /// # use floem::reactive::{RwSignal, SignalGet, SignalWith};
+    /// # use floem::views::{dyn_stack, label, scroll, Decorators};
+    /// let fruits = RwSignal::new(vec!["Apple", "Banana", "Cherry"]);
+    /// scroll(
+    ///     dyn_stack(move || fruits.get(), |fruit| *fruit, |fruit| label(move || fruit))
+    ///         .list_navigation(move |index| fruits.with(|fruits| fruits[index].to_string())),
+    /// );
+    /// ```
+    fn list_navigation(self, item_text: impl Fn(usize) -> String + 'static) -> Self::DV {
+        let view = self.into_view();
+        ListNavigation::add(view.id(), item_text);
+        view
+    }
+
     /// Set the event handler for resize events for this view.
     ///
     /// There can only be one resize event handler for a view.
@@ -387,6 +551,71 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Adds a function that paints under the content of the view, after its background, e.g. a
+    /// debug underlay. It's called with the rect of the view, in the coordinates of the view, and
+    /// paints with the [`Renderer`](crate::Renderer) methods of the [`PaintCx`].
+    ///
+    /// Functions added more than once are called in the order they were added.
+    ///
+    /// # Reactivity
+    /// The function is called whenever the view is painted, and signals read in it aren't
+    /// tracked. Views that depend on signals should request a paint when they change.
+    fn on_paint_before(self, paint: impl Fn(&mut PaintCx, Rect) + 'static) -> Self::DV {
+        let view = self.into_view();
+        view.id()
+            .state()
+            .borrow_mut()
+            .paint_before
+            .push(Rc::new(paint));
+        view
+    }
+
+    /// Adds a function that paints over the view, after its content, border and outline, e.g. a
+    /// custom focus ring or a badge. It's called with the rect of the view, in the coordinates of
+    /// the view, and paints with the [`Renderer`](crate::Renderer) methods of the [`PaintCx`].
+    ///
+    /// ```
+    /// # use floem::peniko::Color;
+    /// # use floem::kurbo::Circle;
+    /// # use floem::views::{text, Decorators};
+    /// # use floem::Renderer;
+    /// // a dot in the top right corner
+    /// text("Inbox").on_paint_after(|cx, rect| {
+    ///     cx.fill(&Circle::new((rect.x1, rect.y0), 4.0), Color::RED, 0.0);
+    /// });
+    /// ```
+    ///
+    /// # Reactivity
+    /// The function is called whenever the view is painted, and signals read in it aren't
+    /// tracked. Views that depend on signals should request a paint when they change.
+    fn on_paint_after(self, paint: impl Fn(&mut PaintCx, Rect) + 'static) -> Self::DV {
+        let view = self.into_view();
+        view.id()
+            .state()
+            .borrow_mut()
+            .paint_after
+            .push(Rc::new(paint));
+        view
+    }
+
+    /// Caches the paint of the view and its children in an image, which is painted instead of
+    /// them until they change. This makes content that is expensive to paint but rarely
+    /// changes, like a chart, a minimap or a blurred background, cheap to paint with the rest
+    /// of the window.
+    ///
+    /// The image is at the scale of the window, and the children are clipped to the view. The
+    /// outline of the view and the functions added with [`Decorators::on_paint_after`] aren't
+    /// cached.
+    ///
+    /// # Reactivity
+    /// The cache is cleared when a view in it is styled, laid out or requests a paint. Views
+    /// that change what they paint otherwise should request a paint, as the built-in views do.
+    fn cache_layer(self) -> Self::DV {
+        let view = self.into_view();
+        view.id().state().borrow_mut().layer = Some(LayerCache::default());
+        view
+    }
+
     /// Add an animation to the view.
     ///
     /// You can add more than one animation to a view and all of them can be active at the same time.
@@ -397,22 +626,60 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
     /// The animation function will be updated in response to signal changes in the function. The behavior is the same as the [Decorators::style] method.
     fn animation(self, animation: impl Fn(Animation) -> Animation + 'static) -> Self::DV {
         let view = self.into_view();
-        let view_id = view.id();
-        let state = view_id.state();
+        view.id().add_animation(animation);
+        view
+    }
 
-        let offset = state.borrow_mut().animations.next_offset();
-        let initial_animation = create_updater(
-            move || animation(Animation::new()),
-            move |animation| {
-                view_id.update_animation(offset, animation);
-            },
-        );
-        for effect_state in &initial_animation.effect_states {
-            effect_state.update(|stack| stack.push((view_id, offset)));
-        }
+    /// Add an animation that is played when the view is shown after it was hidden with
+    /// [`Style::hide`](crate::style::Style::hide).
+    ///
+    /// The keyframes should go from the state the view enters from to its normal state.
+    /// [`dyn_stack`](crate::views::DynStack::on_enter_animation) and
+    /// [`dyn_container`](crate::views::DynamicContainer::on_enter_animation) have their own
+    /// method, which adds the animation to their children instead.
+    fn on_enter_animation(self, animation: impl Fn(Animation) -> Animation + 'static) -> Self::DV {
+        let view = self.into_view();
+        add_enter_animation(view.id(), animation);
+        view
+    }
+
+    /// Add an animation that is played when the view is hidden with
+    /// [`Style::hide`](crate::style::Style::hide).
+    ///
+    /// The view stays visible until the animation has completed. The keyframes should go from
+    /// the normal state of the view to the state it exits to.
+    /// [`dyn_stack`](crate::views::DynStack::on_exit_animation) and
+    /// [`dyn_container`](crate::views::DynamicContainer::on_exit_animation) have their own
+    /// method, which adds the animation to their children instead.
+    fn on_exit_animation(self, animation: impl Fn(Animation) -> Animation + 'static) -> Self::DV {
+        let view = self.into_view();
+        add_exit_animation(view.id(), animation);
+        view
+    }
+
+    /// Add the view to `group` while it plays its exit animations after it was hidden.
+    fn transition_group(self, group: TransitionGroup) -> Self::DV {
+        let view = self.into_view();
+        view.id().state().borrow_mut().transition_group = Some(group);
+        view
+    }
 
-        state.borrow_mut().animations.push(initial_animation);
+    /// Animate changes to the position and size of the view within its parent.
+    ///
+    /// When the layout of the view changes, e.g. because items were inserted, removed or reordered in a
+    /// [dyn_stack](crate::views::dyn_stack), the view will slide from its old position to its new one.
+    ///
+    /// This uses an ease in out transition of 200 milliseconds. See [Decorators::animate_layout_with] to use a different transition.
+    fn animate_layout(self) -> Self::DV {
+        self.animate_layout_with(Transition::ease_in_out(Duration::from_millis(200)))
+    }
 
+    /// Animate changes to the position and size of the view within its parent using `transition`.
+    ///
+    /// See [Decorators::animate_layout].
+    fn animate_layout_with(self, transition: Transition) -> Self::DV {
+        let view = self.into_view();
+        view.id().state().borrow_mut().layout_animation = Some(LayoutAnimation::new(transition));
         view
     }
 
@@ -491,6 +758,64 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         self
     }
 
+    /// Makes the view part of a custom title bar: dragging it moves the window and double
+    /// clicking it toggles whether the window is maximized. Children that handle the pointer
+    /// themselves, e.g. buttons, are not affected.
+    ///
+    /// This is meant for windows without the title bar of the platform, see
+    /// [`WindowConfig::show_titlebar`](crate::window::WindowConfig::show_titlebar).
+    fn window_drag_area(self) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        id.add_event_listener(
+            EventListener::PointerDown,
+            Box::new(|e| {
+                if let Event::PointerDown(input_event) = e {
+                    if input_event.button == PointerButton::Primary {
+                        drag_window();
+                    }
+                }
+                EventPropagation::Stop
+            }),
+        );
+        id.add_event_listener(
+            EventListener::DoubleClick,
+            Box::new(|_| {
+                toggle_window_maximized();
+                EventPropagation::Stop
+            }),
+        );
+        view
+    }
+
+    /// Makes the view a caption button of a custom title bar, which minimizes, maximizes or
+    /// closes the window when clicked.
+    ///
+    /// On Windows 11 the maximize button is also reported to the system, which shows the snap
+    /// layouts when it is hovered. The system then handles the pointer over the button, so the
+    /// view doesn't get pointer events and its hover style isn't applied.
+    fn window_caption_button(self, button: CaptionButton) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        id.set_caption_button(button);
+        id.add_event_listener(
+            EventListener::Click,
+            Box::new(move |_| {
+                match button {
+                    CaptionButton::Minimize => minimize_window(),
+                    CaptionButton::Maximize => toggle_window_maximized(),
+                    CaptionButton::Close => {
+                        if let Some(window_id) = id.window_id() {
+                            close_window(window_id);
+                        }
+                    }
+                }
+                EventPropagation::Stop
+            }),
+        );
+        view
+    }
+
     /// Adds a secondary-click context menu to the view, which opens at the mouse position.
     ///
     /// # Reactivity
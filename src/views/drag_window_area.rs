@@ -1,8 +1,5 @@
 use crate::{
-    action::{drag_window, toggle_window_maximized},
-    event::{Event, EventListener},
     id::ViewId,
-    pointer::PointerButton,
     view::{IntoView, View},
 };
 
@@ -16,18 +13,11 @@ pub struct DragWindowArea {
 /// A view that will move the window when the mouse is dragged.
 ///
 /// This can be useful when the window has the title bar turned off and you want to be able to still drag the window.
+/// See also [`Decorators::window_drag_area`].
 pub fn drag_window_area<V: IntoView + 'static>(child: V) -> DragWindowArea {
     let id = ViewId::new();
     id.set_children(vec![child]);
-    DragWindowArea { id }
-        .on_event_stop(EventListener::PointerDown, |e| {
-            if let Event::PointerDown(input_event) = e {
-                if input_event.button == PointerButton::Primary {
-                    drag_window();
-                }
-            }
-        })
-        .on_double_click_stop(|_| toggle_window_maximized())
+    DragWindowArea { id }.window_drag_area()
 }
 impl View for DragWindowArea {
     fn id(&self) -> ViewId {
@@ -10,21 +10,22 @@ use floem_reactive::{
     as_child_of_current_scope, create_effect, create_updater, Scope, SignalGet, SignalUpdate,
 };
 use floem_winit::keyboard::{Key, NamedKey};
-use peniko::{
-    kurbo::{Point, Rect},
-    Color,
-};
+use peniko::Color;
 
 use crate::{
-    action::{add_overlay, remove_overlay},
+    action::remove_overlay,
     event::{Event, EventListener, EventPropagation},
     id::ViewId,
     prop, prop_extractor,
     style::{CustomStylable, Style, StyleClass, Width},
     style_class,
     unit::PxPctAuto,
-    view::{default_compute_layout, IntoView, View},
-    views::{container, scroll, stack, svg, text, Decorators},
+    view::{IntoView, View},
+    views::{
+        container,
+        popover::{show_popover, PopoverAlign, PopoverAnchor, PopoverOptions},
+        scroll, stack, svg, text, Decorators,
+    },
     AnyView,
 };
 
@@ -175,7 +176,6 @@ pub struct Dropdown<T: 'static> {
     list_item_fn: Rc<dyn Fn(T) -> AnyView>,
     list_style: Style,
     overlay_id: Option<ViewId>,
-    window_origin: Option<Point>,
     on_accept: Option<Box<dyn Fn(T)>>,
     on_open: Option<Box<dyn Fn(bool)>>,
     style: DropdownStyle,
@@ -211,12 +211,6 @@ impl<T: 'static + Clone> View for Dropdown<T> {
         }
     }
 
-    fn compute_layout(&mut self, cx: &mut crate::context::ComputeLayoutCx) -> Option<Rect> {
-        self.window_origin = Some(cx.window_origin);
-
-        default_compute_layout(self.id, cx)
-    }
-
     fn update(&mut self, cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(state) = state.downcast::<Message>() {
             match *state {
@@ -404,7 +398,6 @@ impl<T: Clone> Dropdown<T> {
             list_item_fn,
             list_style: Style::new(),
             overlay_id: None,
-            window_origin: None,
             on_accept: None,
             on_open: None,
             style: Default::default(),
@@ -540,9 +533,7 @@ impl<T: Clone> Dropdown<T> {
             cx.app_state.compute_layout();
             if let Some(layout) = self.id.get_layout() {
                 self.update_list_style(layout.size.width as f64);
-                let point =
-                    self.window_origin.unwrap_or_default() + (0., layout.size.height as f64);
-                self.create_overlay(point);
+                self.create_overlay();
 
                 if let Some(on_open) = &self.on_open {
                     on_open(true);
@@ -567,18 +558,29 @@ impl<T: Clone> Dropdown<T> {
         }
     }
 
-    fn create_overlay(&mut self, point: Point) {
+    fn create_overlay(&mut self) {
         let list = self.list_view.clone();
         let list_style = self.list_style.clone();
         let list_item_fn = self.list_item_fn.clone();
-        self.overlay_id = Some(add_overlay(point, move |_| {
-            let list = list(&*list_item_fn.clone())
-                .style(move |s| s.apply(list_style.clone()))
-                .into_view();
-            let list_id = list.id();
-            list_id.request_focus();
-            list
-        }));
+        let id = self.id;
+        let options = PopoverOptions {
+            align: PopoverAlign::Start,
+            offset: 0.,
+            ..Default::default()
+        };
+        self.overlay_id = Some(show_popover(
+            PopoverAnchor::View(id),
+            options,
+            Some(Rc::new(move || id.update_state(Message::OpenState(false)))),
+            move || {
+                let list = list(&*list_item_fn.clone())
+                    .style(move |s| s.apply(list_style.clone()))
+                    .into_any();
+                let list_id = list.id();
+                list_id.request_focus();
+                list
+            },
+        ));
     }
 
     /// Sets the custom style properties of the `Dropdown`.
@@ -3,7 +3,9 @@ use std::any::Any;
 use floem_reactive::{as_child_of_current_scope, create_updater, Scope};
 
 use crate::{
-    animate::RepeatMode,
+    animate::{
+        start_enter_animations, start_exit_animations, Animation, ChildAnimations, TransitionGroup,
+    },
     context::UpdateCx,
     view::{AnyView, View},
     IntoView, ViewId,
@@ -42,6 +44,43 @@ pub struct DynamicContainer<T: 'static> {
     child_fn: Box<ChildFn<T>>,
     next_val_state: Option<(T, ViewId, Scope)>,
     num_started_animations: u16,
+    child_animations: ChildAnimations,
+}
+
+impl<T> DynamicContainer<T> {
+    /// Sets an animation that is played by each new child view when it replaces the previous one.
+    ///
+    /// The animation is added to the child with [`Animation::run_on_create`], so the keyframes should go
+    /// from the state the view enters from to its normal state.
+    pub fn on_enter_animation(
+        mut self,
+        animation: impl Fn(Animation) -> Animation + 'static,
+    ) -> Self {
+        self.child_animations.set_enter(animation);
+        self.child_animations
+            .attach_enter(self.child_id, self.child_scope);
+        self
+    }
+
+    /// Sets an animation that is played by the child view before it is replaced by a new one.
+    ///
+    /// The child stays in the tree until the animation has completed. The keyframes should go from the
+    /// normal state of the view to the state it exits to.
+    pub fn on_exit_animation(
+        mut self,
+        animation: impl Fn(Animation) -> Animation + 'static,
+    ) -> Self {
+        self.child_animations.set_exit(animation);
+        self.child_animations
+            .attach_exit(self.child_id, self.child_scope);
+        self
+    }
+
+    /// Adds the child view to `group` while it plays its exit animations before it's replaced.
+    pub fn transition_group(mut self, group: TransitionGroup) -> Self {
+        self.child_animations.set_group(group);
+        self
+    }
 }
 
 /// A container for a dynamically updating View
@@ -110,6 +149,7 @@ pub fn dyn_container<CF: Fn(T) -> IV + 'static, T: 'static, IV: IntoView>(
         child_fn,
         next_val_state: None,
         num_started_animations: 0,
+        child_animations: ChildAnimations::default(),
     }
 }
 enum DynMessage {
@@ -117,6 +157,15 @@ enum DynMessage {
     CompletedAnimation,
 }
 
+impl<T> Drop for DynamicContainer<T> {
+    fn drop(&mut self) {
+        // the child that was still exiting is removed with the container
+        if self.num_started_animations > 0 {
+            self.child_animations.exit_ended();
+        }
+    }
+}
+
 impl<T: 'static> View for DynamicContainer<T> {
     fn id(&self) -> ViewId {
         self.id
@@ -141,6 +190,7 @@ impl<T: 'static> View for DynamicContainer<T> {
                             .next_val_state
                             .take()
                             .expect("when waiting for animations the next value will be stored and all message effects should have been dropped by dropping the child id if another value was sent before the animations finished");
+                        self.child_animations.exit_ended();
                         self.swap_val(cx, next_val_state);
                     }
                 }
@@ -164,16 +214,20 @@ impl<T> DynamicContainer<T> {
             // force swap
             self.swap_val(cx, next_state);
             self.num_started_animations = 0;
+            self.child_animations.exit_ended();
         }
 
         self.num_started_animations =
-            animations_recursive_on_remove(id, old_child_id, old_child_scope);
+            start_exit_animations(old_child_id, old_child_scope, move || {
+                id.update_state(DynMessage::CompletedAnimation)
+            });
 
         let next_state = (val, old_child_id, old_child_scope);
         if self.num_started_animations == 0 {
             // after recursively checking, no animations were found that needed to be started
             self.swap_val(cx, next_state);
         } else {
+            self.child_animations.exit_started();
             self.next_val_state = Some(next_state);
         }
     }
@@ -184,64 +238,11 @@ impl<T> DynamicContainer<T> {
         self.child_id = new_child.id();
         self.id.set_children(vec![new_child]);
         self.child_scope = new_child_scope;
+        self.child_animations
+            .attach(self.child_id, self.child_scope);
         cx.app_state_mut().remove_view(old_child_id);
         old_child_scope.dispose();
-        animations_recursive_on_create(self.child_id);
+        start_enter_animations(self.child_id);
         self.id.request_all();
     }
 }
-
-fn animations_recursive_on_remove(id: ViewId, child_id: ViewId, child_scope: Scope) -> u16 {
-    let mut wait_for = 0;
-    let state = child_id.state();
-    let mut state = state.borrow_mut();
-    let animations = &mut state.animations.stack;
-    let mut request_style = false;
-    for anim in animations {
-        if anim.run_on_remove && !matches!(anim.repeat_mode, RepeatMode::LoopForever) {
-            anim.reverse_once.set(true);
-            anim.start_mut();
-            request_style = true;
-            wait_for += 1;
-            let trigger = anim.on_visual_complete;
-            child_scope.create_updater(
-                move || trigger.track(),
-                move |_| {
-                    id.update_state(DynMessage::CompletedAnimation);
-                },
-            );
-        }
-    }
-    drop(state);
-    if request_style {
-        child_id.request_style();
-    }
-
-    child_id
-        .children()
-        .into_iter()
-        .fold(wait_for, |acc, child_id| {
-            acc + animations_recursive_on_remove(id, child_id, child_scope)
-        })
-}
-fn animations_recursive_on_create(child_id: ViewId) {
-    let state = child_id.state();
-    let mut state = state.borrow_mut();
-    let animations = &mut state.animations.stack;
-    let mut request_style = false;
-    for anim in animations {
-        if anim.run_on_create && !matches!(anim.repeat_mode, RepeatMode::LoopForever) {
-            anim.start_mut();
-            request_style = true;
-        }
-    }
-    drop(state);
-    if request_style {
-        child_id.request_style();
-    }
-
-    child_id
-        .children()
-        .into_iter()
-        .for_each(animations_recursive_on_create);
-}
@@ -1,6 +1,7 @@
 use std::{
     hash::{BuildHasherDefault, Hash},
     marker::PhantomData,
+    rc::Rc,
 };
 
 use floem_reactive::{as_child_of_current_scope, create_effect, Scope};
@@ -8,6 +9,9 @@ use rustc_hash::FxHasher;
 use smallvec::SmallVec;
 
 use crate::{
+    animate::{
+        start_enter_animations, start_exit_animations, Animation, ChildAnimations, TransitionGroup,
+    },
     app_state::AppState,
     context::UpdateCx,
     id::ViewId,
@@ -18,10 +22,149 @@ pub(crate) type FxIndexSet<T> = indexmap::IndexSet<T, BuildHasherDefault<FxHashe
 
 type ViewFn<T> = Box<dyn Fn(T) -> (Box<dyn View>, Scope)>;
 
+/// A placeholder shown by a collection view in place of its items while it has none.
+pub(crate) struct EmptyView {
+    view_fn: ViewFn<()>,
+    shown: Option<(ViewId, Scope)>,
+}
+
+impl EmptyView {
+    pub(crate) fn new<V: IntoView + 'static>(view_fn: impl Fn() -> V + 'static) -> Self {
+        Self {
+            view_fn: Box::new(as_child_of_current_scope(move |()| view_fn().into_any())),
+            shown: None,
+        }
+    }
+
+    pub(crate) fn is_shown(&self) -> bool {
+        self.shown.is_some()
+    }
+
+    /// Shows or hides the placeholder.
+    ///
+    /// This must run after the children of `parent` were replaced by [`apply_diff`],
+    /// as the placeholder is the only child while it is shown.
+    pub(crate) fn update(&mut self, parent: ViewId, app_state: &mut AppState, is_empty: bool) {
+        let view_fn = &self.view_fn;
+        update_placeholder(
+            &mut self.shown,
+            parent,
+            app_state,
+            is_empty.then_some(|| view_fn(())),
+        );
+    }
+}
+
+/// Builds the view of an error, in a child scope of the scope the error view was set in.
+type ErrorViewFn = Rc<dyn Fn() -> (Box<dyn View>, Scope)>;
+
+/// Sent to a collection view when the error its error view shows changes.
+pub(crate) struct ErrorChanged(Option<ErrorViewFn>);
+
+/// A placeholder shown by a collection view in place of its items while it has none and
+/// loading them failed, instead of its [`EmptyView`].
+pub(crate) struct ErrorView {
+    view_fn: Option<ErrorViewFn>,
+    shown: Option<(ViewId, Scope)>,
+}
+
+impl ErrorView {
+    /// Creates the error view of the collection view `parent`, which is sent the changes of the
+    /// error returned by `error_fn`.
+    pub(crate) fn new<E, V>(
+        parent: ViewId,
+        error_fn: impl Fn() -> Option<E> + 'static,
+        view_fn: impl Fn(E) -> V + 'static,
+    ) -> Self
+    where
+        E: Clone + 'static,
+        V: IntoView + 'static,
+    {
+        let view_fn = Rc::new(as_child_of_current_scope(move |error: E| {
+            view_fn(error).into_any()
+        }));
+        create_effect(move |_| {
+            let view_fn = error_fn().map(|error| {
+                let view_fn = view_fn.clone();
+                Rc::new(move || view_fn(error.clone())) as ErrorViewFn
+            });
+            parent.update_state(ErrorChanged(view_fn));
+        });
+        Self {
+            view_fn: None,
+            shown: None,
+        }
+    }
+
+    pub(crate) fn is_shown(&self) -> bool {
+        self.shown.is_some()
+    }
+
+    pub(crate) fn has_error(&self) -> bool {
+        self.view_fn.is_some()
+    }
+
+    /// Sets the error that is shown, removing the view of the previous one.
+    pub(crate) fn set(&mut self, app_state: &mut AppState, changed: ErrorChanged) {
+        if let Some((id, scope)) = self.shown.take() {
+            app_state.remove_view(id);
+            scope.dispose();
+        }
+        self.view_fn = changed.0;
+    }
+
+    /// Shows or hides the placeholder, like [`EmptyView::update`].
+    pub(crate) fn update(&mut self, parent: ViewId, app_state: &mut AppState, is_empty: bool) {
+        let view_fn = self.view_fn.as_ref().filter(|_| is_empty);
+        update_placeholder(&mut self.shown, parent, app_state, view_fn.map(|f| || f()));
+    }
+}
+
+/// Shows a placeholder as the only child of `parent` if `build` is set, building it if none is
+/// `shown` yet, or removes the `shown` placeholder if `build` isn't set.
+fn update_placeholder(
+    shown: &mut Option<(ViewId, Scope)>,
+    parent: ViewId,
+    app_state: &mut AppState,
+    build: Option<impl FnOnce() -> (Box<dyn View>, Scope)>,
+) {
+    match (build, shown.take()) {
+        (Some(_), Some(placeholder)) => {
+            parent.set_children_ids(vec![placeholder.0]);
+            *shown = Some(placeholder);
+        }
+        (Some(build), None) => {
+            let (view, scope) = build();
+            let id = view.id();
+            id.set_view(view);
+            id.set_parent(parent);
+            parent.set_children_ids(vec![id]);
+            *shown = Some((id, scope));
+        }
+        (None, Some((id, scope))) => {
+            app_state.remove_view(id);
+            scope.dispose();
+        }
+        (None, None) => {}
+    }
+}
+
 #[derive(educe::Educe)]
 #[educe(Debug)]
 pub(crate) struct HashRun<T>(#[educe(Debug(ignore))] pub(crate) T);
 
+/// A child that was removed from the items, but stays in the stack until its exit animations have completed.
+struct ExitingChild {
+    id: ViewId,
+    scope: Scope,
+    /// The index the child had before it was removed
+    index: usize,
+    num_waiting_animations: u16,
+}
+
+/// Sent to a [`DynStack`] when an exit animation of an [`ExitingChild`] completes.
+struct ExitAnimationComplete(ViewId);
+
 pub struct DynStack<T>
 where
     T: 'static,
@@ -29,9 +172,115 @@ where
     id: ViewId,
     children: Vec<Option<(ViewId, Scope)>>,
     view_fn: ViewFn<T>,
+    empty_view: Option<EmptyView>,
+    error_view: Option<ErrorView>,
+    child_animations: ChildAnimations,
+    exiting: Vec<ExitingChild>,
+    /// Enter animations are not played for the children created from the initial items.
+    initialized: bool,
     phantom: PhantomData<T>,
 }
 
+impl<T> DynStack<T> {
+    /// Sets a view that is shown in place of the items while the collection is empty.
+    ///
+    /// The view is created each time the collection becomes empty and removed as soon as it has items again.
+    pub fn empty_view<V: IntoView + 'static>(mut self, view_fn: impl Fn() -> V + 'static) -> Self {
+        self.empty_view = Some(EmptyView::new(view_fn));
+        self
+    }
+
+    /// Sets a view that is shown in place of the empty view while the collection is empty and
+    /// `error_fn` returns an error, like the one of a failed load of the items.
+    ///
+    /// With a [`Resource`](crate::resource::Resource) that loads the items, the error is
+    /// [`Resource::error`](crate::resource::Resource::error), and the items are the loaded
+    /// ones, or none while the load failed:
+    ///
+    /// ```no_run
+    /// # use floem::resource::create_resource;
+    /// # use floem::task::spawn_blocking;
+    /// # use floem::views::{dyn_stack, label, text};
+    /// let names = create_resource(
+    ///     || (),
+    ///     |()| spawn_blocking(|| std::fs::read_to_string("names.txt").map_err(|e| e.to_string())),
+    /// );
+    /// dyn_stack(
+    ///     move || {
+    ///         let names = names.get().and_then(Result::ok).unwrap_or_default();
+    ///         names.lines().map(String::from).collect::<Vec<_>>()
+    ///     },
+    ///     |name| name.clone(),
+    ///     |name| text(name),
+    /// )
+    /// .error_view(move || names.error(), |error| label(move || error.clone()));
+    /// ```
+    pub fn error_view<E, V>(
+        mut self,
+        error_fn: impl Fn() -> Option<E> + 'static,
+        view_fn: impl Fn(E) -> V + 'static,
+    ) -> Self
+    where
+        E: Clone + 'static,
+        V: IntoView + 'static,
+    {
+        self.error_view = Some(ErrorView::new(self.id, error_fn, view_fn));
+        self
+    }
+
+    /// Sets an animation that is played by the view of each item that is added after the stack was created.
+    ///
+    /// The keyframes should go from the state the view enters from to its normal state.
+    pub fn on_enter_animation(
+        mut self,
+        animation: impl Fn(Animation) -> Animation + 'static,
+    ) -> Self {
+        self.child_animations.set_enter(animation);
+        self
+    }
+
+    /// Sets an animation that is played by the view of each item that is removed.
+    ///
+    /// The view stays in the stack at its old position until the animation has completed.
+    /// The keyframes should go from the normal state of the view to the state it exits to.
+    pub fn on_exit_animation(
+        mut self,
+        animation: impl Fn(Animation) -> Animation + 'static,
+    ) -> Self {
+        self.child_animations.set_exit(animation);
+        self
+    }
+
+    /// Adds the views of the items that are removed to `group` while they play their exit
+    /// animations.
+    pub fn transition_group(mut self, group: TransitionGroup) -> Self {
+        self.child_animations.set_group(group);
+        self
+    }
+
+    /// Sets the children of the stack to the current items, the children that are still exiting and the empty or error view.
+    fn update_children(&mut self, app_state: &mut AppState) {
+        let mut children_ids: Vec<ViewId> = self
+            .children
+            .iter()
+            .filter_map(|c| Some(c.as_ref()?.0))
+            .collect();
+        for exiting in &self.exiting {
+            children_ids.insert(exiting.index.min(children_ids.len()), exiting.id);
+        }
+        let is_empty = children_ids.is_empty();
+        self.id.set_children_ids(children_ids);
+        let has_error = self.error_view.as_ref().is_some_and(ErrorView::has_error);
+        if let Some(empty_view) = &mut self.empty_view {
+            empty_view.update(self.id, app_state, is_empty && !has_error);
+        }
+        if let Some(error_view) = &mut self.error_view {
+            error_view.update(self.id, app_state, is_empty);
+        }
+        self.id.request_all();
+    }
+}
+
 /// A stack whose items can be reactively updated.
 ///
 /// This is useful when you have a list of views that change over time.
@@ -121,10 +370,24 @@ where
         id,
         children: Vec::new(),
         view_fn,
+        empty_view: None,
+        error_view: None,
+        child_animations: ChildAnimations::default(),
+        exiting: Vec::new(),
+        initialized: false,
         phantom: PhantomData,
     }
 }
 
+impl<T> Drop for DynStack<T> {
+    fn drop(&mut self) {
+        // the children that were still exiting are removed with the stack
+        for _ in &self.exiting {
+            self.child_animations.exit_ended();
+        }
+    }
+}
+
 impl<T> View for DynStack<T> {
     fn id(&self) -> ViewId {
         self.id
@@ -135,15 +398,77 @@ impl<T> View for DynStack<T> {
     }
 
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
-        if let Ok(diff) = state.downcast() {
-            apply_diff(
-                self.id(),
-                cx.app_state,
-                *diff,
-                &mut self.children,
-                &self.view_fn,
-            );
-            self.id.request_all();
+        let state = match state.downcast() {
+            Ok(diff) => {
+                let stack_id = self.id;
+                let old_children: FxIndexSet<ViewId> = self
+                    .children
+                    .iter()
+                    .filter_map(|c| Some(c.as_ref()?.0))
+                    .collect();
+                let exiting = &mut self.exiting;
+                let child_animations = &self.child_animations;
+                apply_diff_with(
+                    self.id,
+                    cx.app_state,
+                    *diff,
+                    &mut self.children,
+                    new_child(&self.view_fn),
+                    |app_state, index, id, scope| {
+                        let num_waiting_animations = start_exit_animations(id, scope, move || {
+                            stack_id.update_state(ExitAnimationComplete(id))
+                        });
+                        if num_waiting_animations == 0 {
+                            app_state.remove_view(id);
+                            scope.dispose();
+                        } else {
+                            child_animations.exit_started();
+                            exiting.push(ExitingChild {
+                                id,
+                                scope,
+                                index,
+                                num_waiting_animations,
+                            });
+                        }
+                    },
+                );
+                for (id, scope) in self.children.iter().flatten() {
+                    if !old_children.contains(id) {
+                        self.child_animations.attach(*id, *scope);
+                        if self.initialized {
+                            start_enter_animations(*id);
+                        }
+                    }
+                }
+                self.initialized = true;
+                self.update_children(cx.app_state);
+                return;
+            }
+            Err(state) => state,
+        };
+        let state = match state.downcast::<ErrorChanged>() {
+            Ok(changed) => {
+                if let Some(error_view) = &mut self.error_view {
+                    error_view.set(cx.app_state, *changed);
+                    self.update_children(cx.app_state);
+                }
+                return;
+            }
+            Err(state) => state,
+        };
+        if let Ok(complete) = state.downcast::<ExitAnimationComplete>() {
+            let Some(pos) = self.exiting.iter().position(|e| e.id == complete.0) else {
+                return;
+            };
+            let exiting = &mut self.exiting[pos];
+            exiting.num_waiting_animations = exiting.num_waiting_animations.saturating_sub(1);
+            if exiting.num_waiting_animations == 0 {
+                let exiting = self.exiting.remove(pos);
+                cx.app_state.remove_view(exiting.id);
+                exiting.scope.dispose();
+                self.child_animations.exit_ended();
+                self.update_children(cx.app_state);
+            }
         }
     }
 }
@@ -277,22 +602,57 @@ fn remove_index(
     app_state: &mut AppState,
     children: &mut [Option<(ViewId, Scope)>],
     index: usize,
+    remove: &mut impl FnMut(&mut AppState, usize, ViewId, Scope),
 ) -> Option<()> {
     let (view_id, scope) = std::mem::take(&mut children[index])?;
-    app_state.remove_view(view_id);
-    scope.dispose();
+    remove(app_state, index, view_id, scope);
     Some(())
 }
 
 pub(super) fn apply_diff<T, VF>(
     view_id: ViewId,
     app_state: &mut AppState,
-    mut diff: Diff<T>,
+    diff: Diff<T>,
     children: &mut Vec<Option<(ViewId, Scope)>>,
     view_fn: &VF,
 ) where
     VF: Fn(T) -> (Box<dyn View>, Scope),
 {
+    apply_diff_with(
+        view_id,
+        app_state,
+        diff,
+        children,
+        new_child(view_fn),
+        |app_state, _, id, scope| {
+            app_state.remove_view(id);
+            scope.dispose();
+        },
+    );
+}
+
+/// Creates the view of an added item with `view_fn`, for [`apply_diff_with`].
+pub(super) fn new_child<T>(
+    view_fn: &impl Fn(T) -> (Box<dyn View>, Scope),
+) -> impl FnMut(T) -> (ViewId, Scope) + '_ {
+    move |item| {
+        let (view, scope) = view_fn(item);
+        let id = view.id();
+        id.set_view(view);
+        (id, scope)
+    }
+}
+
+/// Applies the diff to `children`, calling `add` to get the view of each item that is added,
+/// and `remove` with the old index of each child that is removed.
+pub(super) fn apply_diff_with<T>(
+    view_id: ViewId,
+    app_state: &mut AppState,
+    mut diff: Diff<T>,
+    children: &mut Vec<Option<(ViewId, Scope)>>,
+    mut add: impl FnMut(T) -> (ViewId, Scope),
+    mut remove: impl FnMut(&mut AppState, usize, ViewId, Scope),
+) {
     // Resize children if needed
     if diff.added.len().checked_sub(diff.removed.len()).is_some() {
         let target_size =
@@ -313,13 +673,13 @@ pub(super) fn apply_diff<T, VF>(
     // 4. Add
     if diff.clear {
         for i in 0..children.len() {
-            remove_index(app_state, children, i);
+            remove_index(app_state, children, i, &mut remove);
         }
         diff.removed.clear();
     }
 
     for DiffOpRemove { at } in diff.removed {
-        remove_index(app_state, children, at);
+        remove_index(app_state, children, at, &mut remove);
     }
 
     for DiffOpMove { from, to } in diff.moved {
@@ -328,10 +688,8 @@ pub(super) fn apply_diff<T, VF>(
     }
 
     for DiffOpAdd { at, view } in diff.added {
-        let new_child = view.map(view_fn);
-        children[at] = new_child.map(|(view, scope)| {
-            let id = view.id();
-            id.set_view(view);
+        children[at] = view.map(|item| {
+            let (id, scope) = add(item);
             id.set_parent(view_id);
             (id, scope)
         });
@@ -351,3 +709,67 @@ pub(super) fn apply_diff<T, VF>(
         .collect();
     view_id.set_children_ids(children_ids);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+    use crate::{headless::HeadlessHarness, views::text};
+
+    use super::dyn_stack;
+
+    #[test]
+    fn empty_view_is_shown_while_there_are_no_items() {
+        let items = RwSignal::new(Vec::<u32>::new());
+        let mut harness = HeadlessHarness::new(move || {
+            dyn_stack(move || items.get(), |item| *item, text).empty_view(|| text("Nothing"))
+        });
+        assert!(harness.find_by_text("Nothing").is_some());
+
+        items.set(vec![1, 2]);
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Nothing").is_none());
+        assert_eq!(harness.root().children().len(), 2);
+
+        items.set(Vec::new());
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Nothing").is_some());
+        assert_eq!(harness.root().children().len(), 1);
+    }
+
+    #[test]
+    fn error_view_is_shown_instead_of_the_empty_view() {
+        let items = RwSignal::new(Vec::<u32>::new());
+        let error = RwSignal::new(None::<String>);
+        let mut harness = HeadlessHarness::new(move || {
+            dyn_stack(move || items.get(), |item| *item, text)
+                .empty_view(|| text("Nothing"))
+                .error_view(move || error.get(), text)
+        });
+
+        error.set(Some("Failed".to_string()));
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Failed").is_some());
+        assert!(harness.find_by_text("Nothing").is_none());
+
+        error.set(Some("Failed again".to_string()));
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Failed").is_none());
+        assert!(harness.find_by_text("Failed again").is_some());
+
+        // the items take the place of the error view while there are some
+        items.set(vec![1]);
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Failed again").is_none());
+        assert!(harness.find_by_text("1").is_some());
+
+        items.set(Vec::new());
+        error.set(None);
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Failed again").is_none());
+        assert!(harness.find_by_text("Nothing").is_some());
+        assert_eq!(harness.root().children().len(), 1);
+    }
+}
@@ -0,0 +1,179 @@
+#![deny(missing_docs)]
+//! A label that can be renamed in place, like the items of file trees.
+
+use std::any::Any;
+
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalTrack, SignalUpdate, SignalWith};
+use floem_winit::keyboard::{Key, NamedKey};
+
+use crate::{
+    context::UpdateCx,
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    style_class,
+    view::{IntoView, View},
+    views::{label, text_input, Decorators},
+};
+
+style_class!(
+    /// The style class that is applied to editable labels.
+    pub EditableLabelClass
+);
+
+style_class!(
+    /// The style class that is applied to the text input of an editable label while the edited
+    /// text was rejected by [`EditableLabel::validate`].
+    pub InvalidEditClass
+);
+
+enum EditMessage {
+    Start,
+    /// Commits the edit, and moves the focus back to the label if it's set.
+    Commit(bool),
+    Cancel,
+}
+
+type ValidateFn = Box<dyn Fn(&str) -> bool>;
+
+/// A label that can be edited in place, created with [`editable_label`].
+pub struct EditableLabel {
+    id: ViewId,
+    input: ViewId,
+    text: RwSignal<String>,
+    draft: RwSignal<String>,
+    editing: RwSignal<bool>,
+    invalid: RwSignal<bool>,
+    validate: Option<ValidateFn>,
+}
+
+/// A label showing `text` that turns into a text input when it's double-clicked, or when `F2` is
+/// pressed while it has the focus.
+///
+/// `Enter` sets `text` to the edited text and `Escape` cancels the edit. Moving the focus
+/// somewhere else also commits the edit, unless [`EditableLabel::validate`] rejects it, in which
+/// case it's cancelled.
+///
+/// ```
+/// # use floem::reactive::RwSignal;
+/// # use floem::views::editable_label;
+/// let name = RwSignal::new("main.rs".to_string());
+/// editable_label(name).validate(|name| !name.trim().is_empty() && !name.contains('/'));
+/// ```
+pub fn editable_label(text: RwSignal<String>) -> EditableLabel {
+    let id = ViewId::new();
+    let draft = RwSignal::new(String::new());
+    let editing = RwSignal::new(false);
+    let invalid = RwSignal::new(false);
+
+    // a rejected text is only marked until it's changed
+    create_effect(move |_| {
+        draft.track();
+        invalid.set(false);
+    });
+
+    let shown = label(move || text.get()).style(move |s| s.apply_if(editing.get(), |s| s.hide()));
+    let input = text_input(draft)
+        .class_if(move || invalid.get(), InvalidEditClass)
+        .style(move |s| s.apply_if(!editing.get(), |s| s.hide()))
+        .on_event_capture(EventListener::KeyDown, move |event| match event {
+            Event::KeyDown(event) => match event.key.logical_key {
+                Key::Named(NamedKey::Enter) => {
+                    id.update_state(EditMessage::Commit(true));
+                    EventPropagation::Stop
+                }
+                // the text input would only give up the focus, which commits the edit
+                Key::Named(NamedKey::Escape) => {
+                    id.update_state(EditMessage::Cancel);
+                    EventPropagation::Stop
+                }
+                _ => EventPropagation::Continue,
+            },
+            _ => EventPropagation::Continue,
+        })
+        .on_event_cont(EventListener::FocusLost, move |_| {
+            id.update_state(EditMessage::Commit(false));
+        });
+    let input_id = input.id();
+    id.set_children(vec![shown.into_any(), input.into_any()]);
+
+    EditableLabel {
+        id,
+        input: input_id,
+        text,
+        draft,
+        editing,
+        invalid,
+        validate: None,
+    }
+    .keyboard_navigable()
+    .on_double_click_stop(move |_| id.update_state(EditMessage::Start))
+    .on_key_down(
+        Key::Named(NamedKey::F2),
+        |modifiers| modifiers.is_empty(),
+        move |_| id.update_state(EditMessage::Start),
+    )
+    .class(EditableLabelClass)
+}
+
+impl View for EditableLabel {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        format!("Editable Label: {:?}", self.text.get_untracked()).into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
+        let Ok(message) = state.downcast::<EditMessage>() else {
+            return;
+        };
+        match *message {
+            EditMessage::Start if !self.editing.get_untracked() => {
+                self.draft.set(self.text.get_untracked());
+                self.editing.set(true);
+                self.input.request_focus();
+            }
+            // the text input loses the focus after the edit ended, which is ignored here
+            EditMessage::Commit(refocus) if self.editing.get_untracked() => {
+                let valid = self.draft.with_untracked(|draft| {
+                    self.validate
+                        .as_ref()
+                        .map_or(true, |validate| validate(draft))
+                });
+                if valid {
+                    let draft = self.draft.get_untracked();
+                    if self.text.with_untracked(|text| *text != draft) {
+                        self.text.set(draft);
+                    }
+                    self.end_edit(refocus);
+                } else if refocus {
+                    self.invalid.set(true);
+                } else {
+                    self.end_edit(false);
+                }
+            }
+            EditMessage::Cancel if self.editing.get_untracked() => self.end_edit(true),
+            _ => {}
+        }
+    }
+}
+
+impl EditableLabel {
+    /// Sets a function that decides whether an edited text can be committed.
+    ///
+    /// When `Enter` is pressed with a rejected text, the text input stays open and has the
+    /// [`InvalidEditClass`] until the text is changed.
+    pub fn validate(mut self, validate: impl Fn(&str) -> bool + 'static) -> Self {
+        self.validate = Some(Box::new(validate));
+        self
+    }
+
+    fn end_edit(&self, refocus: bool) {
+        self.editing.set(false);
+        self.invalid.set(false);
+        if refocus {
+            self.id.request_focus();
+        }
+    }
+}
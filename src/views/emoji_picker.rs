@@ -0,0 +1,475 @@
+#![deny(missing_docs)]
+//! A view for picking an emoji, with a tab for each category, a search field and a choice of
+//! skin tone.
+
+use std::rc::Rc;
+
+use floem_reactive::{create_rw_signal, SignalGet, SignalUpdate, SignalWith};
+use peniko::Color;
+
+use crate::{
+    style::CursorStyle,
+    style_class,
+    taffy::FlexWrap,
+    views::{dyn_stack, h_stack_from_iter, scroll, text, text_input, v_stack, Decorators, Stack},
+};
+
+/// The background of the selected category tab and skin tone.
+const SELECTED_COLOR: Color = Color::rgba8(0, 0, 0, 30);
+
+const HOVER_COLOR: Color = Color::rgba8(0, 0, 0, 20);
+
+style_class!(
+    /// The style class that is applied to the emoji picker.
+    pub EmojiPickerClass
+);
+
+style_class!(
+    /// The style class that is applied to the tabs of the categories.
+    pub EmojiCategoryTabClass
+);
+
+style_class!(
+    /// The style class that is applied to the buttons of the skin tones.
+    pub EmojiSkinToneClass
+);
+
+style_class!(
+    /// The style class that is applied to each emoji of the grid.
+    pub EmojiClass
+);
+
+/// A category of emoji, which has a tab in the picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmojiCategory {
+    /// Faces and emotions.
+    Smileys,
+    /// People, gestures and body parts.
+    People,
+    /// Animals and plants.
+    Nature,
+    /// Food and drinks.
+    Food,
+    /// Sports, games and celebrations.
+    Activities,
+    /// Vehicles, places and weather.
+    Travel,
+    /// Tools, clothes and other things.
+    Objects,
+    /// Hearts, signs and shapes.
+    Symbols,
+    /// The flags of countries and others.
+    Flags,
+}
+
+impl EmojiCategory {
+    /// The categories in the order of their tabs.
+    pub const ALL: [EmojiCategory; 9] = [
+        EmojiCategory::Smileys,
+        EmojiCategory::People,
+        EmojiCategory::Nature,
+        EmojiCategory::Food,
+        EmojiCategory::Activities,
+        EmojiCategory::Travel,
+        EmojiCategory::Objects,
+        EmojiCategory::Symbols,
+        EmojiCategory::Flags,
+    ];
+
+    /// The name of the category.
+    pub fn name(self) -> &'static str {
+        match self {
+            EmojiCategory::Smileys => "Smileys & Emotion",
+            EmojiCategory::People => "People & Body",
+            EmojiCategory::Nature => "Animals & Nature",
+            EmojiCategory::Food => "Food & Drink",
+            EmojiCategory::Activities => "Activities",
+            EmojiCategory::Travel => "Travel & Places",
+            EmojiCategory::Objects => "Objects",
+            EmojiCategory::Symbols => "Symbols",
+            EmojiCategory::Flags => "Flags",
+        }
+    }
+
+    /// The emoji shown on the tab of the category.
+    pub fn icon(self) -> &'static str {
+        match self {
+            EmojiCategory::Smileys => "😀",
+            EmojiCategory::People => "👋",
+            EmojiCategory::Nature => "🐻",
+            EmojiCategory::Food => "🍔",
+            EmojiCategory::Activities => "⚽",
+            EmojiCategory::Travel => "🚗",
+            EmojiCategory::Objects => "💡",
+            EmojiCategory::Symbols => "❤️",
+            EmojiCategory::Flags => "🏁",
+        }
+    }
+}
+
+/// The skin tone of the emoji of people, applied with the Fitzpatrick modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SkinTone {
+    /// The yellow of the emoji without a modifier.
+    #[default]
+    Default,
+    /// Light skin tone.
+    Light,
+    /// Medium-light skin tone.
+    MediumLight,
+    /// Medium skin tone.
+    Medium,
+    /// Medium-dark skin tone.
+    MediumDark,
+    /// Dark skin tone.
+    Dark,
+}
+
+impl SkinTone {
+    /// The skin tones in the order of their buttons.
+    pub const ALL: [SkinTone; 6] = [
+        SkinTone::Default,
+        SkinTone::Light,
+        SkinTone::MediumLight,
+        SkinTone::Medium,
+        SkinTone::MediumDark,
+        SkinTone::Dark,
+    ];
+
+    /// The modifier that is added to an emoji for the skin tone.
+    pub fn modifier(self) -> Option<char> {
+        match self {
+            SkinTone::Default => None,
+            SkinTone::Light => Some('\u{1F3FB}'),
+            SkinTone::MediumLight => Some('\u{1F3FC}'),
+            SkinTone::Medium => Some('\u{1F3FD}'),
+            SkinTone::MediumDark => Some('\u{1F3FE}'),
+            SkinTone::Dark => Some('\u{1F3FF}'),
+        }
+    }
+
+    /// Applies the skin tone to `emoji`, by adding the modifier after its first character.
+    fn apply(self, emoji: &str) -> String {
+        let Some(modifier) = self.modifier() else {
+            return emoji.to_string();
+        };
+        let mut chars = emoji.chars();
+        let mut toned = String::new();
+        toned.extend(chars.next());
+        toned.push(modifier);
+        // the variation selector isn't needed once there is a modifier
+        let rest = chars.as_str();
+        toned.push_str(rest.strip_prefix('\u{FE0F}').unwrap_or(rest));
+        toned
+    }
+}
+
+/// An emoji of the picker.
+struct Emoji {
+    grapheme: &'static str,
+    name: &'static str,
+    category: EmojiCategory,
+    /// Whether the emoji can have a skin tone.
+    toned: bool,
+}
+
+const fn emoji(grapheme: &'static str, name: &'static str, category: EmojiCategory) -> Emoji {
+    Emoji {
+        grapheme,
+        name,
+        category,
+        toned: false,
+    }
+}
+
+const fn toned(grapheme: &'static str, name: &'static str) -> Emoji {
+    Emoji {
+        grapheme,
+        name,
+        category: EmojiCategory::People,
+        toned: true,
+    }
+}
+
+use EmojiCategory::*;
+
+const EMOJI: &[Emoji] = &[
+    emoji("😀", "grinning face", Smileys),
+    emoji("😃", "grinning face with big eyes", Smileys),
+    emoji("😄", "grinning face with smiling eyes", Smileys),
+    emoji("😁", "beaming face", Smileys),
+    emoji("😆", "grinning squinting face laughing", Smileys),
+    emoji("😅", "grinning face with sweat", Smileys),
+    emoji("😂", "face with tears of joy laughing", Smileys),
+    emoji("🙂", "slightly smiling face", Smileys),
+    emoji("😉", "winking face", Smileys),
+    emoji("😊", "smiling face with smiling eyes blush", Smileys),
+    emoji("😍", "smiling face with heart eyes love", Smileys),
+    emoji("😘", "face blowing a kiss", Smileys),
+    emoji("😎", "smiling face with sunglasses cool", Smileys),
+    emoji("🤔", "thinking face", Smileys),
+    emoji("😐", "neutral face", Smileys),
+    emoji("😴", "sleeping face", Smileys),
+    emoji("😢", "crying face sad", Smileys),
+    emoji("😭", "loudly crying face sad", Smileys),
+    emoji("😡", "pouting face angry", Smileys),
+    emoji("😱", "face screaming in fear", Smileys),
+    emoji("🥳", "partying face celebration", Smileys),
+    emoji("🤯", "exploding head", Smileys),
+    toned("👋", "waving hand hello"),
+    toned("👍", "thumbs up like yes"),
+    toned("👎", "thumbs down dislike no"),
+    toned("👏", "clapping hands"),
+    toned("🙌", "raising hands celebration"),
+    toned("🙏", "folded hands please thanks"),
+    toned("✌️", "victory hand peace"),
+    toned("🤞", "crossed fingers luck"),
+    toned("👌", "ok hand"),
+    toned("☝️", "index pointing up"),
+    toned("💪", "flexed biceps strong"),
+    toned("✍️", "writing hand"),
+    toned("🧑", "person"),
+    toned("👶", "baby"),
+    toned("🧑‍💻", "technologist developer computer"),
+    toned("🧑‍🎨", "artist painter"),
+    emoji("🐶", "dog face", Nature),
+    emoji("🐱", "cat face", Nature),
+    emoji("🐭", "mouse face", Nature),
+    emoji("🦊", "fox", Nature),
+    emoji("🐻", "bear", Nature),
+    emoji("🐼", "panda", Nature),
+    emoji("🐸", "frog", Nature),
+    emoji("🐧", "penguin", Nature),
+    emoji("🦀", "crab rust", Nature),
+    emoji("🐝", "honeybee", Nature),
+    emoji("🌵", "cactus", Nature),
+    emoji("🌲", "evergreen tree", Nature),
+    emoji("🌸", "cherry blossom flower", Nature),
+    emoji("🌻", "sunflower flower", Nature),
+    emoji("🍎", "red apple fruit", Food),
+    emoji("🍌", "banana fruit", Food),
+    emoji("🍇", "grapes fruit", Food),
+    emoji("🍓", "strawberry fruit", Food),
+    emoji("🥑", "avocado", Food),
+    emoji("🥕", "carrot", Food),
+    emoji("🍞", "bread", Food),
+    emoji("🧀", "cheese wedge", Food),
+    emoji("🍔", "hamburger burger", Food),
+    emoji("🍕", "pizza", Food),
+    emoji("🍣", "sushi", Food),
+    emoji("🍰", "shortcake cake", Food),
+    emoji("☕", "hot beverage coffee tea", Food),
+    emoji("🍺", "beer mug", Food),
+    emoji("⚽", "soccer ball football", Activities),
+    emoji("🏀", "basketball", Activities),
+    emoji("🎾", "tennis", Activities),
+    emoji("🎯", "direct hit target", Activities),
+    emoji("🎮", "video game controller", Activities),
+    emoji("🎲", "game die dice", Activities),
+    emoji("🧩", "puzzle piece jigsaw", Activities),
+    emoji("🎨", "artist palette", Activities),
+    emoji("🎸", "guitar music", Activities),
+    emoji("🎉", "party popper celebration tada", Activities),
+    emoji("🎁", "wrapped gift present", Activities),
+    emoji("🏆", "trophy winner", Activities),
+    emoji("🚗", "automobile car", Travel),
+    emoji("🚲", "bicycle bike", Travel),
+    emoji("🚂", "locomotive train", Travel),
+    emoji("✈️", "airplane plane", Travel),
+    emoji("🚀", "rocket launch", Travel),
+    emoji("⛵", "sailboat", Travel),
+    emoji("🏠", "house home", Travel),
+    emoji("🏔️", "snow-capped mountain", Travel),
+    emoji("🏖️", "beach with umbrella", Travel),
+    emoji("🌍", "globe showing europe africa earth world", Travel),
+    emoji("☀️", "sun weather", Travel),
+    emoji("🌧️", "cloud with rain weather", Travel),
+    emoji("🌈", "rainbow", Travel),
+    emoji("⭐", "star", Travel),
+    emoji("💡", "light bulb idea", Objects),
+    emoji("💻", "laptop computer", Objects),
+    emoji("📱", "mobile phone", Objects),
+    emoji("📷", "camera photo", Objects),
+    emoji("📚", "books", Objects),
+    emoji("✏️", "pencil", Objects),
+    emoji("📌", "pushpin pin", Objects),
+    emoji("📎", "paperclip", Objects),
+    emoji("🔒", "locked lock", Objects),
+    emoji("🔑", "key", Objects),
+    emoji("🔧", "wrench tool", Objects),
+    emoji("⏰", "alarm clock time", Objects),
+    emoji("👓", "glasses", Objects),
+    emoji("👕", "t-shirt clothes", Objects),
+    emoji("❤️", "red heart love", Symbols),
+    emoji("🧡", "orange heart", Symbols),
+    emoji("💛", "yellow heart", Symbols),
+    emoji("💚", "green heart", Symbols),
+    emoji("💙", "blue heart", Symbols),
+    emoji("💜", "purple heart", Symbols),
+    emoji("💔", "broken heart", Symbols),
+    emoji("✅", "check mark button done", Symbols),
+    emoji("❌", "cross mark no", Symbols),
+    emoji("⚠️", "warning", Symbols),
+    emoji("❓", "red question mark", Symbols),
+    emoji("💯", "hundred points", Symbols),
+    emoji("🔥", "fire hot", Symbols),
+    emoji("✨", "sparkles", Symbols),
+    emoji("🏁", "chequered flag finish", Flags),
+    emoji("🏳️", "white flag", Flags),
+    emoji("🏳️‍🌈", "rainbow flag pride", Flags),
+    emoji("🏴‍☠️", "pirate flag", Flags),
+    emoji("🇺🇳", "flag united nations", Flags),
+    emoji("🇪🇺", "flag european union", Flags),
+    emoji("🇺🇸", "flag united states", Flags),
+    emoji("🇬🇧", "flag united kingdom", Flags),
+    emoji("🇩🇪", "flag germany", Flags),
+    emoji("🇫🇷", "flag france", Flags),
+    emoji("🇯🇵", "flag japan", Flags),
+    emoji("🇧🇷", "flag brazil", Flags),
+    emoji("🇮🇳", "flag india", Flags),
+    emoji("🇨🇦", "flag canada", Flags),
+];
+
+/// The emoji shown for a category, or the emoji whose names contain all the words of the
+/// search query, with the skin tone applied.
+fn shown_emoji(category: EmojiCategory, query: &str, tone: SkinTone) -> Vec<String> {
+    let query = query.to_lowercase();
+    let words = query.split_whitespace().collect::<Vec<_>>();
+    EMOJI
+        .iter()
+        .filter(|emoji| {
+            if words.is_empty() {
+                emoji.category == category
+            } else {
+                words.iter().all(|word| emoji.name.contains(word))
+            }
+        })
+        .map(|emoji| {
+            if emoji.toned {
+                tone.apply(emoji.grapheme)
+            } else {
+                emoji.grapheme.to_string()
+            }
+        })
+        .collect()
+}
+
+/// A view for picking an emoji, which calls `on_pick` with the grapheme of the emoji that is
+/// clicked, including its skin tone.
+///
+/// The emoji of the selected category are shown until a search query is typed, which shows the
+/// emoji of all the categories that match it.
+///
+/// ```
+/// # use floem::reactive::{create_rw_signal, SignalUpdate};
+/// # use floem::views::emoji_picker;
+/// let message = create_rw_signal(String::new());
+/// emoji_picker(move |emoji| message.update(|message| message.push_str(&emoji)));
+/// ```
+pub fn emoji_picker(on_pick: impl Fn(String) + 'static) -> Stack {
+    let category = create_rw_signal(EmojiCategory::Smileys);
+    let query = create_rw_signal(String::new());
+    let tone = create_rw_signal(SkinTone::Default);
+
+    let tabs = h_stack_from_iter(EmojiCategory::ALL.into_iter().map(move |tab| {
+        text(tab.icon())
+            .class(EmojiCategoryTabClass)
+            .keyboard_navigable()
+            .on_click_stop(move |_| {
+                query.set(String::new());
+                category.set(tab);
+            })
+            .style(move |s| {
+                s.padding(4)
+                    .border_radius(4)
+                    .cursor(CursorStyle::Pointer)
+                    .apply_if(
+                        query.with(|query| query.is_empty()) && category.get() == tab,
+                        |s| s.background(SELECTED_COLOR),
+                    )
+            })
+    }))
+    .style(|s| s.gap(2));
+
+    let tones = h_stack_from_iter(SkinTone::ALL.into_iter().map(move |skin_tone| {
+        text(skin_tone.apply("✋"))
+            .class(EmojiSkinToneClass)
+            .keyboard_navigable()
+            .on_click_stop(move |_| tone.set(skin_tone))
+            .style(move |s| {
+                s.padding(2)
+                    .border_radius(4)
+                    .cursor(CursorStyle::Pointer)
+                    .apply_if(tone.get() == skin_tone, |s| s.background(SELECTED_COLOR))
+            })
+    }))
+    .style(|s| s.gap(2));
+
+    let on_pick = Rc::new(on_pick);
+    let grid = scroll(
+        dyn_stack(
+            move || query.with(|query| shown_emoji(category.get(), query, tone.get())),
+            |grapheme| grapheme.clone(),
+            move |grapheme| {
+                let on_pick = on_pick.clone();
+                let picked = grapheme.clone();
+                text(grapheme)
+                    .class(EmojiClass)
+                    .keyboard_navigable()
+                    .on_click_stop(move |_| on_pick(picked.clone()))
+                    .style(|s| {
+                        s.size(32, 32)
+                            .font_size(20.)
+                            .items_center()
+                            .justify_center()
+                            .border_radius(4)
+                            .cursor(CursorStyle::Pointer)
+                            .hover(|s| s.background(HOVER_COLOR))
+                    })
+            },
+        )
+        .style(|s| s.flex_wrap(FlexWrap::Wrap).width_full()),
+    )
+    .style(|s| s.height(200).width_full());
+
+    v_stack((
+        tabs,
+        text_input(query)
+            .placeholder("Search emoji")
+            .style(|s| s.width_full()),
+        grid,
+        tones,
+    ))
+    .class(EmojiPickerClass)
+    .style(|s| s.width(330).gap(6).padding(6))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skin_tones_replace_the_variation_selector() {
+        assert_eq!(SkinTone::Default.apply("👋"), "👋");
+        assert_eq!(SkinTone::Dark.apply("👋"), "👋\u{1F3FF}");
+        assert_eq!(SkinTone::Light.apply("✌️"), "✌\u{1F3FB}");
+        assert_eq!(SkinTone::Medium.apply("🧑‍💻"), "🧑\u{1F3FD}\u{200D}💻");
+    }
+
+    #[test]
+    fn searching_matches_all_the_words_in_every_category() {
+        assert_eq!(
+            shown_emoji(Smileys, "HEART flag", SkinTone::Default),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            shown_emoji(Smileys, "green heart", SkinTone::Default),
+            ["💚"]
+        );
+        assert_eq!(
+            shown_emoji(Flags, "thumbs", SkinTone::MediumDark),
+            ["👍\u{1F3FE}", "👎\u{1F3FE}"]
+        );
+        assert!(shown_emoji(Flags, " ", SkinTone::Default).contains(&"🏁".to_string()));
+    }
+}
@@ -1,13 +1,21 @@
 //! Module defining image view and its properties: style, position and fit.
+//!
+//! Images are decoded on the [pool of background threads](crate::task), which also makes
+//! smaller copies of the images that are drawn much smaller than their size, and their
+//! premultiplied pixels, which are kept in the shared [`image_cache`].
 #![deny(missing_docs)]
-use std::{path::PathBuf, sync::Arc};
+use std::{cell::Cell, path::PathBuf, rc::Rc, sync::Arc};
 
 use floem_reactive::create_effect;
-use peniko::Blob;
+use floem_renderer::image_cache;
+use peniko::{kurbo::Size, Blob};
 use sha2::{Digest, Sha256};
 use taffy::NodeId;
 
-use crate::{id::ViewId, style::Style, unit::UnitExt, view::View, Renderer};
+use crate::{id::ViewId, style::Style, task::spawn_blocking, unit::UnitExt, view::View, Renderer};
+
+/// The most times an image is halved for the size it's drawn at.
+const MAX_LEVEL: u32 = 8;
 
 /// Holds information about image position and size inside container.
 pub struct ImageStyle {
@@ -104,9 +112,27 @@ pub struct Img {
     id: ViewId,
     img: Option<peniko::Image>,
     img_hash: Option<Vec<u8>>,
+    /// A smaller copy of the image, drawn instead of it when it's drawn smaller.
+    variant: Option<Variant>,
+    /// The level of the copy that is being made.
+    pending_level: Option<u32>,
     content_node: Option<NodeId>,
 }
 
+/// An image with the hash that identifies it in the caches of the renderers.
+struct Prepared {
+    image: peniko::Image,
+    hash: Vec<u8>,
+}
+
+/// A copy of an image halved `level` times.
+struct Variant {
+    /// The hash of the image that was halved.
+    source: Vec<u8>,
+    level: u32,
+    prepared: Prepared,
+}
+
 /// A view that can display an image and controls its position.
 ///
 /// It takes function that produce `Vec<u8>` and will convert it into [Image](peniko::Image).
@@ -153,13 +179,8 @@ pub struct Img {
 /// });
 /// ```
 pub fn img(image: impl Fn() -> Vec<u8> + 'static) -> Img {
-    let image = image::load_from_memory(&image()).ok();
-    let width = image.as_ref().map_or(0, |img| img.width());
-    let height = image.as_ref().map_or(0, |img| img.height());
-    let data = Arc::new(image.map_or(Default::default(), |img| img.into_rgba8().into_vec()));
-    let blob = Blob::new(data);
-    let image = peniko::Image::new(blob, peniko::Format::Rgba8, width, height);
-    img_dynamic(move || image.clone())
+    let data = image();
+    img_decoded(move || image::load_from_memory(&data).ok())
 }
 
 /// A view that can display an image and controls its position.
@@ -181,25 +202,158 @@ pub fn img(image: impl Fn() -> Vec<u8> + 'static) -> Img {
 /// The `img` function is not reactive, so to make it change on event, wrap it
 /// with [dyn_view](crate::views::dyn_view::dyn_view).
 pub fn img_from_path(image: impl Fn() -> PathBuf + 'static) -> Img {
-    let image = image::open(image()).ok();
-    let width = image.as_ref().map_or(0, |img| img.width());
-    let height = image.as_ref().map_or(0, |img| img.height());
-    let data = Arc::new(image.map_or(Default::default(), |img| img.into_rgba8().into_vec()));
-    let blob = Blob::new(data);
-    let image = peniko::Image::new(blob, peniko::Format::Rgba8, width, height);
-    img_dynamic(move || image.clone())
+    let path = image();
+    img_decoded(move || image::open(path).ok())
+}
+
+/// An image view that shows the image returned by `decode` once it's run on the pool of
+/// background threads, and which is empty until then.
+fn img_decoded(decode: impl FnOnce() -> Option<image::DynamicImage> + Send + 'static) -> Img {
+    let id = ViewId::new();
+    spawn_blocking(move || prepare(rgba(decode())))
+        .on_complete(move |prepared| id.update_state(prepared));
+    Img::new(id)
 }
 
 pub(crate) fn img_dynamic(image: impl Fn() -> peniko::Image + 'static) -> Img {
     let id = ViewId::new();
+    let generation = Rc::new(Cell::new(0u64));
     create_effect(move |_| {
-        id.update_state(image());
+        let image = image();
+        let current = generation.get() + 1;
+        generation.set(current);
+        let generation = generation.clone();
+        spawn_blocking(move || prepare(image)).on_complete(move |prepared| {
+            // the images are prepared in parallel, so a newer one can be ready first
+            if generation.get() == current {
+                id.update_state(prepared);
+            }
+        });
     });
-    Img {
-        id,
-        img: None,
-        img_hash: None,
-        content_node: None,
+    Img::new(id)
+}
+
+/// The pixels of `image` as an RGBA image, which is empty if it couldn't be decoded.
+fn rgba(image: Option<image::DynamicImage>) -> peniko::Image {
+    let width = image.as_ref().map_or(0, |img| img.width());
+    let height = image.as_ref().map_or(0, |img| img.height());
+    let data = Arc::new(image.map_or(Default::default(), |img| img.into_rgba8().into_vec()));
+    let blob = Blob::new(data);
+    peniko::Image::new(blob, peniko::Format::Rgba8, width, height)
+}
+
+/// Hashes `image`, and adds its premultiplied pixels to the [`image_cache`] so they're ready
+/// when it's drawn.
+fn prepare(image: peniko::Image) -> Prepared {
+    let mut hasher = Sha256::new();
+    hasher.update(image.data.data());
+    let hash = hasher.finalize().to_vec();
+    image_cache::get_or_insert(&hash, &image);
+    Prepared { image, hash }
+}
+
+/// How many times an image of `width` by `height` can be halved and still cover `target`, all
+/// in physical pixels.
+fn mip_level(width: u32, height: u32, target: Size) -> u32 {
+    if target.is_zero_area() {
+        return 0;
+    }
+    let mut level = 0;
+    while level < MAX_LEVEL
+        && (width >> (level + 1)) as f64 >= target.width
+        && (height >> (level + 1)) as f64 >= target.height
+    {
+        level += 1;
+    }
+    level
+}
+
+/// `image` halved `level` times. The pixels are averaged premultiplied, so the colors of
+/// transparent pixels don't bleed into their neighbours.
+fn downscale(image: &peniko::Image, level: u32) -> peniko::Image {
+    let mut width = image.width as usize;
+    let mut height = image.height as usize;
+    let mut pixels = image
+        .data
+        .data()
+        .chunks_exact(4)
+        .map(|rgba| {
+            let alpha = rgba[3] as u32;
+            let channel = |c: u8| (c as u32 * alpha + 127) / 255;
+            [channel(rgba[0]), channel(rgba[1]), channel(rgba[2]), alpha]
+        })
+        .collect::<Vec<_>>();
+    for _ in 0..level {
+        let (half_width, half_height) = ((width / 2).max(1), (height / 2).max(1));
+        let mut half = Vec::with_capacity(half_width * half_height);
+        for y in 0..half_height {
+            for x in 0..half_width {
+                let mut sum = [0u32; 4];
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let pixel =
+                        pixels[(2 * y + dy).min(height - 1) * width + (2 * x + dx).min(width - 1)];
+                    for (sum, channel) in sum.iter_mut().zip(pixel) {
+                        *sum += channel;
+                    }
+                }
+                half.push(sum.map(|sum| (sum + 2) / 4));
+            }
+        }
+        pixels = half;
+        width = half_width;
+        height = half_height;
+    }
+    let data = pixels
+        .into_iter()
+        .flat_map(|[r, g, b, a]| {
+            let channel = |c: u32| (c * 255 + a / 2).checked_div(a).unwrap_or(0).min(255) as u8;
+            [channel(r), channel(g), channel(b), a as u8]
+        })
+        .collect::<Vec<_>>();
+    peniko::Image::new(
+        Blob::new(Arc::new(data)),
+        peniko::Format::Rgba8,
+        width as u32,
+        height as u32,
+    )
+}
+
+impl Img {
+    fn new(id: ViewId) -> Self {
+        Img {
+            id,
+            img: None,
+            img_hash: None,
+            variant: None,
+            pending_level: None,
+            content_node: None,
+        }
+    }
+
+    /// Starts making the copy of the image halved `level` times, unless it's already there.
+    fn request_variant(&mut self, level: u32) {
+        if level == 0 {
+            self.variant = None;
+            return;
+        }
+        let (Some(img), Some(hash)) = (&self.img, &self.img_hash) else {
+            return;
+        };
+        if self.variant.as_ref().is_some_and(|v| v.level == level)
+            || self.pending_level == Some(level)
+        {
+            return;
+        }
+        self.pending_level = Some(level);
+        let id = self.id;
+        let image = img.clone();
+        let source = hash.clone();
+        spawn_blocking(move || Variant {
+            source,
+            level,
+            prepared: prepare(downscale(&image, level)),
+        })
+        .on_complete(move |variant| id.update_state(variant));
     }
 }
 
@@ -213,13 +367,25 @@ impl View for Img {
     }
 
     fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
-        if let Ok(img) = state.downcast::<peniko::Image>() {
-            let mut hasher = Sha256::new();
-            hasher.update(img.data.data());
-            self.img_hash = Some(hasher.finalize().to_vec());
-
-            self.img = Some(*img);
-            self.id.request_layout();
+        match state.downcast::<Prepared>() {
+            Ok(prepared) => {
+                self.img = Some(prepared.image);
+                self.img_hash = Some(prepared.hash);
+                self.variant = None;
+                self.pending_level = None;
+                self.id.request_layout();
+            }
+            Err(state) => {
+                if let Ok(variant) = state.downcast::<Variant>() {
+                    if self.img_hash.as_ref() == Some(&variant.source)
+                        && self.pending_level == Some(variant.level)
+                    {
+                        self.pending_level = None;
+                        self.variant = Some(*variant);
+                        self.id.request_paint();
+                    }
+                }
+            }
         }
     }
 
@@ -253,15 +419,54 @@ impl View for Img {
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        if let Some(ref img) = self.img {
-            let rect = self.id.get_content_rect();
-            cx.draw_img(
-                floem_renderer::Img {
-                    img: img.clone(),
-                    hash: self.img_hash.as_ref().unwrap(),
-                },
-                rect,
-            );
-        }
+        let Some((width, height)) = self.img.as_ref().map(|img| (img.width, img.height)) else {
+            return;
+        };
+        let rect = self.id.get_content_rect();
+        let scale = cx.paint_state.renderer().scale();
+        let level = mip_level(width, height, rect.size() * scale);
+        self.request_variant(level);
+
+        // the full image is drawn until the copy for the level is made
+        let (img, hash) = match &self.variant {
+            Some(variant) if variant.level <= level => {
+                (&variant.prepared.image, &variant.prepared.hash)
+            }
+            _ => (self.img.as_ref().unwrap(), self.img_hash.as_ref().unwrap()),
+        };
+        cx.draw_img(
+            floem_renderer::Img {
+                img: img.clone(),
+                hash,
+            },
+            rect,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use peniko::{kurbo::Size, Blob};
+
+    use super::{downscale, mip_level};
+
+    #[test]
+    fn level_covers_the_target() {
+        assert_eq!(mip_level(1024, 1024, Size::new(1024.0, 1024.0)), 0);
+        assert_eq!(mip_level(1024, 1024, Size::new(256.0, 256.0)), 2);
+        assert_eq!(mip_level(1024, 1024, Size::new(300.0, 100.0)), 1);
+        assert_eq!(mip_level(1024, 512, Size::ZERO), 0);
+    }
+
+    #[test]
+    fn transparent_pixels_dont_bleed() {
+        // an opaque red pixel next to transparent black ones
+        let data = [[255, 0, 0, 255], [0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]].concat();
+        let image = peniko::Image::new(Blob::new(Arc::new(data)), peniko::Format::Rgba8, 2, 2);
+        let half = downscale(&image, 1);
+        assert_eq!((half.width, half.height), (1, 1));
+        assert_eq!(half.data.data(), &[255, 0, 0, 64]);
     }
 }
@@ -0,0 +1,132 @@
+#![deny(missing_docs)]
+//! A container that keeps the views it built for each key, see [`keep_alive`].
+
+use std::any::Any;
+
+use floem_reactive::{as_child_of_current_scope, create_updater, Scope};
+use taffy::style::Display;
+
+use crate::{
+    context::{PaintCx, StyleCx, UpdateCx},
+    id::ViewId,
+    style::DisplayProp,
+    view::{AnyView, IntoView, View},
+};
+
+// the child scopes are disposed with the scope of the container
+type ChildFn<K> = dyn Fn(K) -> (AnyView, Scope);
+
+/// A keep-alive container, created with [`keep_alive`].
+pub struct KeepAlive<K: 'static> {
+    id: ViewId,
+    /// The key of each child, in the order of the children.
+    keys: Vec<K>,
+    active: usize,
+    child_fn: Box<ChildFn<K>>,
+}
+
+/// A container that shows the view that `child_fn` builds for the key returned by `key_fn`,
+/// like [`dyn_container`](crate::views::dyn_container), but keeps the views it built for the
+/// other keys rather than dropping them.
+///
+/// When the key switches back, the view that was built for it is shown again as it was left,
+/// with its scroll positions, text inputs and signals, without building it again. The views
+/// that aren't shown are hidden, so they aren't laid out or painted.
+///
+/// ```
+/// # use floem::reactive::{RwSignal, SignalGet};
+/// # use floem::views::{keep_alive, scroll, text};
+/// # use floem::IntoView;
+/// #[derive(Clone, PartialEq)]
+/// enum Page {
+///     Inbox,
+///     Archive,
+/// }
+///
+/// let page = RwSignal::new(Page::Inbox);
+/// keep_alive(
+///     move || page.get(),
+///     |page| match page {
+///         Page::Inbox => scroll(text("Inbox")).into_any(),
+///         Page::Archive => scroll(text("Archive")).into_any(),
+///     },
+/// );
+/// ```
+pub fn keep_alive<K, V>(
+    key_fn: impl Fn() -> K + 'static,
+    child_fn: impl Fn(K) -> V + 'static,
+) -> KeepAlive<K>
+where
+    K: PartialEq + Clone + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    let initial = create_updater(key_fn, move |key| id.update_state(key));
+    let child_fn = Box::new(as_child_of_current_scope(move |key| {
+        child_fn(key).into_any()
+    }));
+    let (child, _) = child_fn(initial.clone());
+    id.set_children(vec![child]);
+    KeepAlive {
+        id,
+        keys: vec![initial],
+        active: 0,
+        child_fn,
+    }
+}
+
+impl<K: PartialEq + Clone + 'static> KeepAlive<K> {
+    fn show(&mut self, key: K) {
+        self.active = match self.keys.iter().position(|k| *k == key) {
+            Some(index) => index,
+            None => {
+                let (child, _) = (self.child_fn)(key.clone());
+                self.id.add_child(child);
+                self.keys.push(key);
+                self.keys.len() - 1
+            }
+        };
+        self.id.request_all();
+        for child in self.id.children() {
+            child.request_all();
+        }
+    }
+}
+
+impl<K: PartialEq + Clone + 'static> View for KeepAlive<K> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        format!("Keep Alive: {} of {}", self.active, self.keys.len()).into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if let Ok(key) = state.downcast::<K>() {
+            self.show(*key);
+        }
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        for (i, child) in self.id.children().into_iter().enumerate() {
+            cx.style_view(child);
+            let child_view = child.state();
+            let mut child_view = child_view.borrow_mut();
+            child_view.combined_style = child_view.combined_style.clone().set(
+                DisplayProp,
+                if i != self.active {
+                    Display::None
+                } else {
+                    Display::Flex
+                },
+            );
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        if let Some(active) = self.id.children().get(self.active) {
+            cx.paint_view(*active);
+        }
+    }
+}
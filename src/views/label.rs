@@ -1,4 +1,4 @@
-use std::{any::Any, fmt::Display, mem::swap};
+use std::{any::Any, fmt::Display, mem::swap, rc::Rc};
 
 use crate::{
     context::{PaintCx, UpdateCx},
@@ -12,6 +12,7 @@ use crate::{
     },
     style_class,
     text::{Attrs, AttrsList, FamilyOwned, TextLayout},
+    text_layout_cache::TextLayoutCache,
     unit::PxPct,
     view::View,
     Clipboard,
@@ -56,11 +57,13 @@ enum SelectionState {
 pub struct Label {
     id: ViewId,
     label: String,
-    text_layout: Option<TextLayout>,
+    /// The layout of the label, shared with the labels of the window with the same text and
+    /// style.
+    text_layout: Option<Rc<TextLayout>>,
     text_node: Option<NodeId>,
     available_text: Option<String>,
     available_width: Option<f32>,
-    available_text_layout: Option<TextLayout>,
+    available_text_layout: Option<Rc<TextLayout>>,
     text_overflow_listener: Option<TextOverflowListener>,
     selection_state: SelectionState,
     selection_range: Option<(Cursor, Cursor)>,
@@ -170,16 +173,12 @@ impl Label {
         AttrsList::new(attrs)
     }
 
-    fn set_text_layout(&mut self) {
-        let mut text_layout = TextLayout::new();
+    fn set_text_layout(&mut self, text_layouts: &mut TextLayoutCache) {
         let attrs_list = self.get_attrs_list();
-        text_layout.set_text(self.label.as_str(), attrs_list.clone());
-        self.text_layout = Some(text_layout);
+        self.text_layout = Some(text_layouts.get(&self.label, &attrs_list, None));
 
         if let Some(new_text) = self.available_text.as_ref() {
-            let mut text_layout = TextLayout::new();
-            text_layout.set_text(new_text, attrs_list);
-            self.available_text_layout = Some(text_layout);
+            self.available_text_layout = Some(text_layouts.get(new_text, &attrs_list, None));
         }
     }
 
@@ -302,6 +301,23 @@ impl View for Label {
         format!("Label: {:?}", self.label).into()
     }
 
+    fn text_content(&self) -> Option<String> {
+        Some(self.label.clone())
+    }
+
+    fn baseline(&self) -> Option<f64> {
+        self.text_layout.as_ref()?;
+        let location = self
+            .id
+            .taffy()
+            .borrow()
+            .layout(self.text_node?)
+            .ok()?
+            .location;
+        let run = self.effectve_text_layout().layout_runs().next()?;
+        Some(location.y as f64 + run.line_y as f64)
+    }
+
     fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
         if let Ok(state) = state.downcast() {
             self.label = *state;
@@ -371,6 +387,10 @@ impl View for Label {
             self.available_width = None;
             self.available_text_layout = None;
             self.id.request_layout();
+            #[cfg(feature = "parallel")]
+            cx.app_state
+                .text_layouts
+                .request(&self.label, self.get_attrs_list());
         }
         if self.selection_style.read(cx) {
             self.id.request_paint();
@@ -378,12 +398,12 @@ impl View for Label {
     }
 
     fn layout(&mut self, cx: &mut crate::context::LayoutCx) -> taffy::tree::NodeId {
-        cx.layout_node(self.id(), true, |_cx| {
+        cx.layout_node(self.id(), true, |cx| {
             let (width, height) = if self.label.is_empty() {
                 (0.0, self.font.size().unwrap_or(14.0))
             } else {
                 if self.text_layout.is_none() {
-                    self.set_text_layout();
+                    self.set_text_layout(&mut cx.app_state.text_layouts);
                 }
                 let text_layout = self.text_layout.as_ref().unwrap();
                 let size = text_layout.size();
@@ -417,7 +437,7 @@ impl View for Label {
         })
     }
 
-    fn compute_layout(&mut self, _cx: &mut crate::context::ComputeLayoutCx) -> Option<Rect> {
+    fn compute_layout(&mut self, cx: &mut crate::context::ComputeLayoutCx) -> Option<Rect> {
         if self.label.is_empty() {
             return None;
         }
@@ -438,14 +458,14 @@ impl View for Label {
             let text_overflow = style.text_overflow();
             (text_overflow, padding_left + padding_right)
         };
-        let text_layout = self.text_layout.as_ref().unwrap();
+        let text_layout = self.text_layout.clone().unwrap();
+        let text_layouts = &mut cx.app_state.text_layouts;
         let width = text_layout.size().width as f32;
         let available_width = layout.size.width - padding;
         if text_overflow == TextOverflow::Ellipsis {
             if width > available_width {
                 if self.available_width != Some(available_width) {
-                    let mut dots_text = TextLayout::new();
-                    dots_text.set_text("...", self.get_attrs_list());
+                    let dots_text = text_layouts.get("...", &self.get_attrs_list(), None);
 
                     let dots_width = dots_text.size().width as f32;
                     let width_left = available_width - dots_width;
@@ -459,7 +479,7 @@ impl View for Label {
                     };
                     self.available_text = Some(new_text);
                     self.available_width = Some(available_width);
-                    self.set_text_layout();
+                    self.set_text_layout(text_layouts);
                 }
             } else {
                 self.available_text = None;
@@ -469,8 +489,11 @@ impl View for Label {
         } else if text_overflow == TextOverflow::Wrap {
             if width > available_width {
                 if self.available_width != Some(available_width) {
-                    let mut text_layout = text_layout.clone();
-                    text_layout.set_size(available_width, f32::MAX);
+                    let text_layout = text_layouts.get(
+                        &self.label,
+                        &self.get_attrs_list(),
+                        Some(available_width),
+                    );
                     self.available_text_layout = Some(text_layout);
                     self.available_width = Some(available_width);
                     self.id.request_layout();
@@ -0,0 +1,179 @@
+#![deny(missing_docs)]
+//! A view whose content is built when it's first shown, see [`lazy`].
+
+use std::any::Any;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+use floem_reactive::{as_child_of_current_scope, Scope};
+use peniko::kurbo::Rect;
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use crate::{
+    action::{exec_after, TimerToken},
+    clock,
+    context::{ComputeLayoutCx, UpdateCx},
+    id::ViewId,
+    view::{default_compute_layout, AnyView, IntoView, View},
+};
+
+type BuildFn = dyn Fn(()) -> (AnyView, Scope);
+
+enum LazyMessage {
+    Build,
+    /// Checks whether the content has been hidden for long enough to be dropped.
+    Check,
+}
+
+/// A view whose content is built when it's first shown, created with [`lazy`].
+pub struct Lazy {
+    id: ViewId,
+    build: Box<BuildFn>,
+    content: Option<(ViewId, Scope)>,
+    /// Whether the view was in the viewport when it was last laid out.
+    in_viewport: bool,
+    drop_after: Option<Duration>,
+    hidden_since: Option<Instant>,
+    timer: Option<TimerToken>,
+}
+
+/// A view that builds its content with `build` the first time it's shown, rather than when it's
+/// created, which makes starting large applications faster.
+///
+/// The content is built when the view is laid out while it's shown, so not while it's in an
+/// inactive [`tab`](crate::views::tab) or a section that is hidden. Without a size of its own
+/// the view is laid out with no size until it's built, and with one, e.g. the expected height of
+/// the content, it's only built when it's scrolled into the viewport.
+///
+/// The content can be dropped again when it isn't shown, see [`Lazy::drop_after`].
+///
+/// ```
+/// # use floem::reactive::{RwSignal, SignalGet};
+/// # use floem::views::{lazy, tab, text};
+/// # use floem::IntoView;
+/// # fn settings_page() -> impl IntoView { text("Settings") }
+/// # fn history_page() -> impl IntoView { text("History") }
+/// let active = RwSignal::new(0);
+/// tab(
+///     move || active.get(),
+///     || [0, 1],
+///     |page| *page,
+///     |page| match page {
+///         0 => lazy(settings_page).into_any(),
+///         _ => lazy(history_page).into_any(),
+///     },
+/// );
+/// ```
+pub fn lazy<V: IntoView + 'static>(build: impl Fn() -> V + 'static) -> Lazy {
+    Lazy {
+        id: ViewId::new(),
+        build: Box::new(as_child_of_current_scope(move |_| build().into_any())),
+        content: None,
+        in_viewport: false,
+        drop_after: None,
+        hidden_since: None,
+        timer: None,
+    }
+}
+
+impl Lazy {
+    /// Drops the content once it hasn't been shown for `duration`, to free the memory it uses.
+    /// It's built again when the view is shown again.
+    ///
+    /// The state of the content is lost when it's dropped, unless it's kept in signals outside
+    /// of `build`.
+    pub fn drop_after(mut self, duration: Duration) -> Self {
+        self.drop_after = Some(duration);
+        self
+    }
+
+    fn build(&mut self) {
+        if self.content.is_some() {
+            return;
+        }
+        let (content, scope) = (self.build)(());
+        self.content = Some((content.id(), scope));
+        self.id.set_children(vec![content]);
+        self.id.request_all();
+        if let Some(duration) = self.drop_after {
+            self.hidden_since = None;
+            self.schedule_check(duration);
+        }
+    }
+
+    fn check(&mut self, cx: &mut UpdateCx) {
+        self.timer = None;
+        let (Some(drop_after), Some((content, scope))) = (self.drop_after, self.content) else {
+            return;
+        };
+        let now = clock::now();
+        if self.in_viewport && !self.id.is_hidden_recursive() {
+            self.hidden_since = None;
+            self.schedule_check(drop_after);
+            return;
+        }
+        let hidden_since = *self.hidden_since.get_or_insert(now);
+        let hidden_for = now.saturating_duration_since(hidden_since);
+        if hidden_for < drop_after {
+            self.schedule_check(drop_after - hidden_for);
+            return;
+        }
+        self.content = None;
+        self.hidden_since = None;
+        self.id.set_children_ids(Vec::new());
+        cx.app_state_mut().remove_view(content);
+        scope.dispose();
+        self.id.request_all();
+    }
+
+    fn schedule_check(&mut self, after: Duration) {
+        let id = self.id;
+        self.timer = Some(exec_after(after, move |_| {
+            id.update_state(LazyMessage::Check);
+        }));
+    }
+}
+
+impl View for Lazy {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        if self.content.is_some() {
+            "Lazy".into()
+        } else {
+            "Lazy (not built)".into()
+        }
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if let Ok(message) = state.downcast::<LazyMessage>() {
+            match *message {
+                LazyMessage::Build => self.build(),
+                LazyMessage::Check => self.check(cx),
+            }
+        }
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        // the viewport is the part of the view that is visible, and views that are hidden
+        // aren't laid out
+        let size = self.id.get_size().unwrap_or_default();
+        self.in_viewport = size.is_zero_area() || cx.current_viewport().area() > 0.0;
+        if self.in_viewport && self.content.is_none() {
+            // the children can't be changed while the tree is laid out
+            self.id.update_state(LazyMessage::Build);
+        }
+        default_compute_layout(self.id, cx)
+    }
+}
+
+impl Drop for Lazy {
+    fn drop(&mut self) {
+        if let Some(timer) = self.timer.take() {
+            timer.cancel();
+        }
+    }
+}
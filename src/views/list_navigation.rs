@@ -0,0 +1,285 @@
+//! Keyboard navigation between the items of a list, see [`Decorators::list_navigation`].
+//!
+//! [`Decorators::list_navigation`]: super::Decorators::list_navigation
+
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    rc::{Rc, Weak},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use floem_winit::keyboard::{Key, NamedKey};
+
+use crate::{
+    clock,
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    keyboard::Modifiers,
+};
+
+/// Typed characters are added to the typeahead search until no key was typed for this long.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// The keyboard navigation of a list, whose items are the children of the list view.
+///
+/// Only the current item can be reached with `Tab`, which is the roving tabindex pattern, and
+/// the arrow keys, `Home`, `End`, `PageUp`, `PageDown` and typing the start of an item move the
+/// focus between the items.
+pub(crate) struct ListNavigation {
+    this: Weak<RefCell<ListNavigation>>,
+    list: ViewId,
+    item_text: Box<dyn Fn(usize) -> String>,
+    /// The item that is focused, or would be focused when the list is tabbed to.
+    current: Option<ViewId>,
+    /// Whether an item has the focus, so keys typed into other views of the items aren't taken.
+    focused: bool,
+    /// The items that have focus listeners.
+    hooked: HashSet<ViewId>,
+    query: String,
+    last_typed: Option<Instant>,
+}
+
+impl ListNavigation {
+    pub(crate) fn add(list: ViewId, item_text: impl Fn(usize) -> String + 'static) {
+        let nav = Rc::new_cyclic(|this| {
+            RefCell::new(ListNavigation {
+                this: this.clone(),
+                list,
+                item_text: Box::new(item_text),
+                current: None,
+                focused: false,
+                hooked: HashSet::new(),
+                query: String::new(),
+                last_typed: None,
+            })
+        });
+        // the list is a tab stop until it has an item that can take the focus
+        list.keyboard_navigable();
+
+        let on_focus = nav.clone();
+        list.add_event_listener(
+            EventListener::FocusGained,
+            Box::new(move |_| {
+                let mut nav = on_focus.borrow_mut();
+                nav.sync();
+                if let Some(current) = nav.current {
+                    current.request_focus();
+                }
+                EventPropagation::Continue
+            }),
+        );
+
+        let on_press = nav.clone();
+        list.add_capture_listener(
+            EventListener::PointerDown,
+            0,
+            Box::new(move |event| {
+                if let Event::PointerDown(event) = event {
+                    let mut nav = on_press.borrow_mut();
+                    let pos = event.pos + nav.list.layout_rect().origin().to_vec2();
+                    let pressed = nav
+                        .items()
+                        .into_iter()
+                        .find(|item| item.layout_rect().contains(pos));
+                    if let Some(item) = pressed {
+                        nav.move_to(item);
+                    }
+                }
+                EventPropagation::Continue
+            }),
+        );
+
+        let on_key = nav.clone();
+        list.add_capture_listener(
+            EventListener::KeyDown,
+            0,
+            Box::new(move |event| match event {
+                Event::KeyDown(event) => {
+                    let key = &event.key.logical_key;
+                    on_key.borrow_mut().key_down(key, event.modifiers)
+                }
+                _ => EventPropagation::Continue,
+            }),
+        );
+    }
+
+    /// The items of the list that are shown.
+    fn items(&self) -> Vec<ViewId> {
+        self.list
+            .children()
+            .into_iter()
+            .filter(|item| !item.is_hidden_recursive())
+            .collect()
+    }
+
+    /// Makes only the current item reachable with `Tab`, and listens for the focus of new items.
+    fn sync(&mut self) {
+        let items = self.items();
+        if !self.current.is_some_and(|current| items.contains(&current)) {
+            self.current = items.first().copied();
+        }
+        self.hooked.retain(|item| items.contains(item));
+        for &item in &items {
+            if self.hooked.insert(item) {
+                self.hook(item);
+            }
+            if Some(item) == self.current {
+                item.keyboard_navigable();
+            } else {
+                item.remove_keyboard_navigatable();
+            }
+        }
+        if self.current.is_some() {
+            self.list.remove_keyboard_navigatable();
+        } else {
+            self.list.keyboard_navigable();
+        }
+    }
+
+    fn hook(&self, item: ViewId) {
+        let nav = self.this.clone();
+        item.add_event_listener(
+            EventListener::FocusGained,
+            Box::new(move |_| {
+                if let Some(nav) = nav.upgrade() {
+                    let mut nav = nav.borrow_mut();
+                    nav.focused = true;
+                    nav.current = Some(item);
+                    nav.sync();
+                }
+                EventPropagation::Continue
+            }),
+        );
+        let nav = self.this.clone();
+        item.add_event_listener(
+            EventListener::FocusLost,
+            Box::new(move |_| {
+                if let Some(nav) = nav.upgrade() {
+                    let mut nav = nav.borrow_mut();
+                    // the next item gains the focus before this one loses it
+                    if nav.current == Some(item) {
+                        nav.focused = false;
+                    }
+                }
+                EventPropagation::Continue
+            }),
+        );
+    }
+
+    fn move_to(&mut self, item: ViewId) {
+        self.current = Some(item);
+        self.sync();
+        item.request_focus();
+    }
+
+    fn key_down(&mut self, key: &Key, modifiers: Modifiers) -> EventPropagation {
+        if !self.focused {
+            return EventPropagation::Continue;
+        }
+        let items = self.items();
+        let Some(current) = items.iter().position(|item| Some(*item) == self.current) else {
+            return EventPropagation::Continue;
+        };
+        let last = items.len() - 1;
+        let target = match key {
+            Key::Named(NamedKey::ArrowDown) => (current + 1).min(last),
+            Key::Named(NamedKey::ArrowUp) => current.saturating_sub(1),
+            Key::Named(NamedKey::Home) => 0,
+            Key::Named(NamedKey::End) => last,
+            Key::Named(key @ (NamedKey::PageDown | NamedKey::PageUp)) => {
+                let tops = items
+                    .iter()
+                    .map(|item| item.layout_rect().y0)
+                    .collect::<Vec<_>>();
+                // the list is usually the content of a scroll view, whose height is a page
+                let page = self.list.parent_size().map_or(0., |size| size.height);
+                page_target(&tops, current, page, *key == NamedKey::PageDown)
+            }
+            Key::Character(ch) if modifiers.difference(Modifiers::SHIFT).is_empty() => {
+                let now = clock::now();
+                if self
+                    .last_typed
+                    .is_some_and(|last| now.saturating_duration_since(last) > TYPEAHEAD_TIMEOUT)
+                {
+                    self.query.clear();
+                }
+                self.last_typed = Some(now);
+                self.query.push_str(ch);
+                let texts = (0..items.len())
+                    .map(|index| (self.item_text)(index))
+                    .collect::<Vec<_>>();
+                match typeahead_match(&texts, current, &self.query) {
+                    Some(target) => target,
+                    None => return EventPropagation::Stop,
+                }
+            }
+            _ => return EventPropagation::Continue,
+        };
+        if target != current {
+            self.move_to(items[target]);
+        }
+        EventPropagation::Stop
+    }
+}
+
+/// The item a page away from `current`, given the top of each item and the height of a page.
+/// It's at least the item next to `current`.
+fn page_target(tops: &[f64], current: usize, page: f64, forward: bool) -> usize {
+    let last = tops.len().saturating_sub(1);
+    if forward {
+        let target = tops[current] + page;
+        let index = tops.iter().rposition(|&top| top <= target).unwrap_or(last);
+        index.max(current + 1).min(last)
+    } else {
+        let target = tops[current] - page;
+        let index = tops.iter().position(|&top| top >= target).unwrap_or(0);
+        index.min(current.saturating_sub(1))
+    }
+}
+
+/// The first item after `current` that starts with `query`, ignoring case. While the same
+/// character is typed repeatedly, it cycles through the items that start with it.
+fn typeahead_match(texts: &[String], current: usize, query: &str) -> Option<usize> {
+    let query = query.to_lowercase();
+    let mut chars = query.chars();
+    let first = chars.next()?;
+    let (query, start) = if chars.all(|ch| ch == first) {
+        (first.to_string(), current + 1)
+    } else {
+        (query, current)
+    };
+    (0..texts.len())
+        .map(|offset| (start + offset) % texts.len())
+        .find(|&index| texts[index].to_lowercase().starts_with(&query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_move_at_least_one_item() {
+        let tops = [0., 20., 40., 60., 80., 100.];
+        assert_eq!(page_target(&tops, 0, 50., true), 2);
+        assert_eq!(page_target(&tops, 4, 50., true), 5);
+        assert_eq!(page_target(&tops, 5, 50., false), 3);
+        assert_eq!(page_target(&tops, 0, 0., true), 1);
+        assert_eq!(page_target(&tops, 0, 50., false), 0);
+    }
+
+    #[test]
+    fn typeahead_finds_and_cycles() {
+        let texts = ["Apple", "Banana", "blueberry", "Cherry"].map(String::from);
+        assert_eq!(typeahead_match(&texts, 0, "b"), Some(1));
+        assert_eq!(typeahead_match(&texts, 1, "bb"), Some(2));
+        assert_eq!(typeahead_match(&texts, 2, "b"), Some(1));
+        assert_eq!(typeahead_match(&texts, 1, "bl"), Some(2));
+        assert_eq!(typeahead_match(&texts, 0, "ch"), Some(3));
+        assert_eq!(typeahead_match(&texts, 0, "x"), None);
+    }
+}
@@ -0,0 +1,117 @@
+#![deny(missing_docs)]
+//! A container that only builds its child again when its props change, see [`memo_view`].
+
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+use floem_reactive::{as_child_of_current_scope, create_stateful_updater, Scope};
+
+use crate::{
+    context::UpdateCx,
+    id::ViewId,
+    view::{AnyView, IntoView, View},
+};
+
+type ChildFn<P> = dyn Fn(P) -> (AnyView, Scope);
+
+/// A memoized view, created with [`memo_view`].
+pub struct MemoView<P: 'static> {
+    id: ViewId,
+    child: (ViewId, Scope),
+    child_fn: Box<ChildFn<P>>,
+    /// The latest props, which the child hasn't been built with yet.
+    pending: Rc<RefCell<Option<P>>>,
+}
+
+/// A container whose child is built by `child_fn` with the props returned by `props`, and is
+/// only built again when the props are different from the ones it was built with.
+///
+/// This avoids building a subtree again when the signals that `props` reads change often but
+/// the props don't, e.g. when they're derived from a larger state. When the props change more
+/// than once before the next frame, the child is only built again with the last ones.
+///
+/// ```
+/// # use floem::reactive::{RwSignal, SignalWith};
+/// # use floem::views::{memo_view, text};
+/// struct Document {
+///     title: String,
+///     cursor: usize,
+/// }
+///
+/// let document = RwSignal::new(Document {
+///     title: "Notes".to_string(),
+///     cursor: 0,
+/// });
+/// // moving the cursor doesn't build the header again
+/// memo_view(
+///     move || document.with(|document| document.title.clone()),
+///     |title| text(title),
+/// );
+/// ```
+pub fn memo_view<P, V>(
+    props: impl Fn() -> P + 'static,
+    child_fn: impl Fn(P) -> V + 'static,
+) -> MemoView<P>
+where
+    P: PartialEq + Clone + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    let pending = Rc::new(RefCell::new(None));
+    // the props are `None` when they're the same as the previous ones
+    let initial = create_stateful_updater(
+        move |prev: Option<P>| {
+            let props = props();
+            let changed = (prev.as_ref() != Some(&props)).then(|| props.clone());
+            (changed, props)
+        },
+        {
+            let pending = pending.clone();
+            move |changed, props| {
+                // a message is only sent for the first change before the child is built again
+                if let Some(changed) = changed {
+                    if pending.borrow_mut().replace(changed).is_none() {
+                        id.update_state(());
+                    }
+                }
+                props
+            }
+        },
+    );
+    let child_fn = Box::new(as_child_of_current_scope(move |props| {
+        child_fn(props).into_any()
+    }));
+    let (child, scope) = child_fn(initial.expect("the props are new when they're first read"));
+    let child_id = child.id();
+    id.set_children(vec![child]);
+    MemoView {
+        id,
+        child: (child_id, scope),
+        child_fn,
+        pending,
+    }
+}
+
+impl<P: 'static> View for MemoView<P> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Memo View".into()
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if state.downcast::<()>().is_err() {
+            return;
+        }
+        let Some(props) = self.pending.borrow_mut().take() else {
+            return;
+        };
+        let (child, scope) = (self.child_fn)(props);
+        let (old_child, old_scope) = std::mem::replace(&mut self.child, (child.id(), scope));
+        self.id.set_children(vec![child]);
+        cx.app_state_mut().remove_view(old_child);
+        old_scope.dispose();
+        self.id.request_all();
+    }
+}
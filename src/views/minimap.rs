@@ -0,0 +1,205 @@
+#![deny(missing_docs)]
+//! A minimap, a scaled down picture of the content of a scroll view that shows which part of it
+//! is visible, like the ones next to the text of code editors.
+
+use floem_reactive::{create_effect, ReadSignal, SignalGet, SignalTrack};
+use floem_renderer::Renderer;
+use peniko::kurbo::{Point, Rect, Size, Vec2};
+
+use crate::{
+    context::{EventCx, PaintCx, StyleCx},
+    event::{Event, EventPropagation},
+    id::ViewId,
+    prop_extractor,
+    style::{Background, Style},
+    style_class,
+    view::View,
+    views::{
+        scroll::{ScrollProgress, ScrollState},
+        Decorators, Scroll,
+    },
+};
+
+style_class!(
+    /// The style class that is applied to minimaps.
+    pub MinimapClass
+);
+
+style_class!(
+    /// The style class of the rect that shows the visible part of the content on a minimap.
+    pub MinimapViewportClass
+);
+
+prop_extractor! {
+    ViewportStyle {
+        background: Background,
+    }
+}
+
+/// The content is never drawn larger than this fraction of its size, so narrow content doesn't
+/// fill the minimap.
+const MAX_SCALE: f64 = 0.25;
+
+/// A minimap of a scroll view, created with [`minimap`].
+pub struct Minimap {
+    id: ViewId,
+    scroll: ViewId,
+    content: ViewId,
+    progress: ReadSignal<ScrollProgress>,
+    /// The pointer position and the scroll offset where a drag started.
+    drag: Option<(Point, Vec2)>,
+    viewport_style: ViewportStyle,
+}
+
+/// A minimap of the content of `scroll`, which paints the content scaled down to the width of
+/// the minimap and highlights the part of it that is visible in the scroll view.
+///
+/// Clicking the minimap scrolls to the clicked part of the content, and dragging it scrolls the
+/// content along. When the scaled content is taller than the minimap, the minimap scrolls with
+/// the scroll view.
+///
+/// ```
+/// # use floem::views::{h_stack, minimap, scroll, empty, Decorators};
+/// let content = scroll(empty().style(|s| s.size(400, 4000)));
+/// let map = minimap(&content);
+/// h_stack((content, map));
+/// ```
+pub fn minimap(scroll: &Scroll) -> Minimap {
+    let id = ViewId::new();
+    let progress = scroll.scroll_progress();
+
+    create_effect(move |_| {
+        progress.track();
+        id.request_paint();
+    });
+
+    Minimap {
+        id,
+        scroll: scroll.id(),
+        content: scroll.content_id(),
+        progress,
+        drag: None,
+        viewport_style: Default::default(),
+    }
+    .class(MinimapClass)
+}
+
+impl Minimap {
+    /// The size of the content, the scale it's painted at, and how far the scaled content is
+    /// moved up to keep the visible part on the minimap.
+    fn geometry(&self) -> Option<(Size, f64, f64)> {
+        let size = self.id.get_size()?;
+        let layout = self.content.get_layout()?;
+        let content_size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        if content_size.width <= 0. {
+            return None;
+        }
+        let scale = (size.width / content_size.width).min(MAX_SCALE);
+        let overflow = (content_size.height * scale - size.height).max(0.);
+        let map_offset = overflow * self.progress.get_untracked().y;
+        Some((content_size, scale, map_offset))
+    }
+
+    /// The part of the content that is visible in the scroll view.
+    fn viewport(&self, content_size: Size) -> Rect {
+        let progress = self.progress.get_untracked();
+        Rect::from_origin_size(
+            progress.offset.to_point(),
+            content_size - progress.max_offset.to_size(),
+        )
+    }
+
+    /// Scrolls so the visible part of the content is centered on `pos`.
+    fn center_on(&self, pos: Point) -> Option<Vec2> {
+        let (content_size, scale, map_offset) = self.geometry()?;
+        let viewport = self.viewport(content_size);
+        let max_offset = self.progress.get_untracked().max_offset;
+        let offset = Vec2::new(
+            (pos.x / scale - viewport.width() / 2.).clamp(0., max_offset.x),
+            ((pos.y + map_offset) / scale - viewport.height() / 2.).clamp(0., max_offset.y),
+        );
+        self.scroll
+            .update_state(ScrollState::ScrollTo(offset.to_point()));
+        Some(offset)
+    }
+}
+
+impl View for Minimap {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Minimap".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().min_height(0.))
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        let style = cx.style().apply_class(MinimapViewportClass);
+        self.viewport_style.read_style(cx, &style);
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::PointerDown(event) if event.button.is_primary() => {
+                let Some((content_size, scale, map_offset)) = self.geometry() else {
+                    return EventPropagation::Continue;
+                };
+                let viewport = self.viewport(content_size);
+                let pos = Point::new(event.pos.x / scale, (event.pos.y + map_offset) / scale);
+                // pressing the visible part drags it from where it is
+                let offset = if viewport.contains(pos) {
+                    Some(viewport.origin().to_vec2())
+                } else {
+                    self.center_on(event.pos)
+                };
+                self.drag = offset.map(|offset| (event.pos, offset));
+                cx.update_active(self.id);
+                EventPropagation::Stop
+            }
+            Event::PointerMove(event) => {
+                if let (Some((start, offset)), Some((_, scale, _))) = (self.drag, self.geometry()) {
+                    let offset = offset + (event.pos - start) / scale;
+                    self.scroll
+                        .update_state(ScrollState::ScrollTo(offset.to_point()));
+                    return EventPropagation::Stop;
+                }
+                EventPropagation::Continue
+            }
+            Event::PointerUp(_) => {
+                self.drag = None;
+                EventPropagation::Continue
+            }
+            _ => EventPropagation::Continue,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let Some((content_size, scale, map_offset)) = self.geometry() else {
+            return;
+        };
+        let Some(layout) = self.content.get_layout() else {
+            return;
+        };
+        let size = self.id.get_size().unwrap_or_default();
+
+        cx.save();
+        cx.clip(&size.to_rect());
+        // `paint_view` moves the content to where it is in the scroll view, which is undone here
+        cx.offset((
+            -layout.location.x as f64 * scale,
+            -layout.location.y as f64 * scale - map_offset,
+        ));
+        cx.scale(scale);
+        cx.paint_view(self.content);
+        cx.restore();
+
+        if let Some(background) = self.viewport_style.background() {
+            let viewport = self.viewport(content_size).scale_from_origin(scale);
+            cx.fill(&(viewport - Vec2::new(0., map_offset)), &background, 0.0);
+        }
+    }
+}
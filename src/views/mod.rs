@@ -100,6 +100,8 @@ pub use decorator::*;
 mod list;
 pub use list::*;
 
+mod list_navigation;
+
 mod virtual_list;
 pub use virtual_list::*;
 
@@ -107,11 +109,32 @@ mod virtual_stack;
 pub use virtual_stack::*;
 
 pub mod scroll;
-pub use scroll::{scroll, Scroll, ScrollExt};
+pub use scroll::{
+    scroll, Scroll, ScrollAlign, ScrollExt, ScrollIntoView, ScrollMarker, ScrollProgress,
+    SnapAlign, SnapType,
+};
+
+mod minimap;
+pub use minimap::*;
 
 mod tab;
 pub use tab::*;
 
+pub mod tab_bar;
+pub use tab_bar::{tab_bar, TabBar};
+
+pub mod breadcrumbs;
+pub use breadcrumbs::{breadcrumbs, Breadcrumbs};
+
+mod zoomable;
+pub use zoomable::*;
+
+mod portal;
+pub use portal::*;
+
+pub mod popover;
+pub use popover::{popover, Placement, Popover, PopoverAlign};
+
 mod tooltip;
 pub use tooltip::*;
 
@@ -156,3 +179,44 @@ pub use checkbox::*;
 
 mod toggle_button;
 pub use toggle_button::*;
+
+mod progress;
+pub use progress::*;
+
+mod badge;
+pub use badge::*;
+
+mod avatar;
+pub use avatar::*;
+
+mod segmented_control;
+pub use segmented_control::*;
+
+mod otp_input;
+pub use otp_input::*;
+
+mod selection_model;
+pub use selection_model::*;
+
+mod editable_label;
+pub use editable_label::*;
+
+mod region_picker;
+pub use region_picker::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod suspense;
+#[cfg(not(target_arch = "wasm32"))]
+pub use suspense::*;
+
+mod lazy;
+pub use lazy::*;
+
+mod keep_alive;
+pub use keep_alive::*;
+
+mod memo_view;
+pub use memo_view::*;
+
+mod emoji_picker;
+pub use emoji_picker::*;
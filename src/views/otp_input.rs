@@ -0,0 +1,227 @@
+#![deny(missing_docs)]
+//! An input for one-time codes, with a box for each character of the code.
+
+use std::any::Any;
+
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use floem_winit::keyboard::{Key, NamedKey};
+
+use crate::{
+    context::UpdateCx,
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    keyboard::{KeyEvent, Modifiers},
+    style::Style,
+    style_class,
+    view::{IntoView, View},
+    views::{container, label, Decorators},
+    Clipboard,
+};
+
+style_class!(
+    /// The style class that is applied to one-time code inputs.
+    pub OtpInputClass
+);
+
+style_class!(
+    /// The style class that is applied to the box of each character of a one-time code input.
+    pub OtpBoxClass
+);
+
+enum OtpMessage {
+    Focus(usize),
+    Complete(String),
+}
+
+/// An input for one-time codes, created with [`otp_input`].
+pub struct OtpInput {
+    id: ViewId,
+    boxes: Vec<ViewId>,
+    numeric: RwSignal<bool>,
+    on_complete: Option<Box<dyn Fn(String)>>,
+}
+
+/// An input for a one-time code of `len` characters, like the code of a sign-in email or an
+/// authenticator app, with a box for each character.
+///
+/// Typing a character moves to the next box, and `Backspace` clears the box, or the one before it
+/// when it's empty. Pasting a code fills the boxes from the focused one onwards.
+/// [`OtpInput::on_complete`] is called with the code when all of the boxes are filled.
+///
+/// ```
+/// # use floem::views::otp_input;
+/// otp_input(6)
+///     .numeric(true)
+///     .on_complete(|code| println!("verifying {code}"));
+/// ```
+pub fn otp_input(len: usize) -> OtpInput {
+    let id = ViewId::new();
+    let code = RwSignal::new(vec![None; len]);
+    let numeric = RwSignal::new(false);
+
+    create_effect(move |_| {
+        if let Some(complete) = code.with(|code| code.iter().copied().collect::<Option<String>>()) {
+            if !complete.is_empty() {
+                id.update_state(OtpMessage::Complete(complete));
+            }
+        }
+    });
+
+    let boxes = (0..len)
+        .map(|index| {
+            container(label(move || {
+                code.with(|code| code[index].map(String::from).unwrap_or_default())
+            }))
+            .class(OtpBoxClass)
+            .keyboard_navigable()
+            .on_event(EventListener::KeyDown, move |event| {
+                let Event::KeyDown(event) = event else {
+                    return EventPropagation::Continue;
+                };
+                let numeric = numeric.get_untracked();
+                let focus = match &event.key.logical_key {
+                    Key::Named(NamedKey::Backspace) => {
+                        code.try_update(|code| backspace(code, index))
+                    }
+                    Key::Named(NamedKey::ArrowLeft) => Some(index.saturating_sub(1)),
+                    Key::Named(NamedKey::ArrowRight) => Some((index + 1).min(len - 1)),
+                    Key::Character(ch) if is_paste(event, ch) => {
+                        let text = Clipboard::get_contents().unwrap_or_default();
+                        code.try_update(|code| fill(code, index, &text, numeric))
+                    }
+                    Key::Character(ch)
+                        if event.modifiers.difference(Modifiers::SHIFT).is_empty() =>
+                    {
+                        code.try_update(|code| fill(code, index, ch, numeric))
+                    }
+                    _ => return EventPropagation::Continue,
+                };
+                if let Some(focus) = focus {
+                    id.update_state(OtpMessage::Focus(focus));
+                }
+                EventPropagation::Stop
+            })
+            .into_any()
+        })
+        .collect::<Vec<_>>();
+    let box_ids = boxes.iter().map(|view| view.id()).collect();
+    id.set_children(boxes);
+
+    OtpInput {
+        id,
+        boxes: box_ids,
+        numeric,
+        on_complete: None,
+    }
+    .class(OtpInputClass)
+}
+
+impl View for OtpInput {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Otp Input".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_row())
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if let Ok(message) = state.downcast::<OtpMessage>() {
+            match *message {
+                OtpMessage::Focus(index) => {
+                    if let Some(view) = self.boxes.get(index) {
+                        view.request_focus();
+                    }
+                }
+                OtpMessage::Complete(code) => {
+                    if let Some(on_complete) = &self.on_complete {
+                        on_complete(code);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl OtpInput {
+    /// Sets whether only digits can be entered, instead of letters and digits.
+    pub fn numeric(self, numeric: bool) -> Self {
+        self.numeric.set(numeric);
+        self
+    }
+
+    /// Sets a function that is called with the code when all of the boxes are filled.
+    pub fn on_complete(mut self, on_complete: impl Fn(String) + 'static) -> Self {
+        self.on_complete = Some(Box::new(on_complete));
+        self
+    }
+}
+
+fn is_paste(event: &KeyEvent, ch: &str) -> bool {
+    #[cfg(target_os = "macos")]
+    let modifier = Modifiers::META;
+    #[cfg(not(target_os = "macos"))]
+    let modifier = Modifiers::CONTROL;
+    event.modifiers == modifier && ch.eq_ignore_ascii_case("v")
+}
+
+/// Fills the boxes from `start` with the characters of `text` that can be entered, and returns
+/// the box to focus next.
+fn fill(code: &mut [Option<char>], start: usize, text: &str, numeric: bool) -> usize {
+    let accepted = text.chars().filter(|ch| {
+        if numeric {
+            ch.is_ascii_digit()
+        } else {
+            ch.is_alphanumeric()
+        }
+    });
+    let mut next = start;
+    for (slot, ch) in code.iter_mut().skip(start).zip(accepted) {
+        *slot = Some(ch);
+        next += 1;
+    }
+    next.min(code.len().saturating_sub(1))
+}
+
+/// Clears the box at `index`, or the one before it when it's empty, and returns the box to
+/// focus next.
+fn backspace(code: &mut [Option<char>], index: usize) -> usize {
+    if code.get(index).is_some_and(Option::is_some) {
+        code[index] = None;
+        index
+    } else if index > 0 {
+        code[index - 1] = None;
+        index - 1
+    } else {
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typing_and_pasting_fill_boxes() {
+        let mut code = vec![None; 4];
+        assert_eq!(fill(&mut code, 0, "1", true), 1);
+        assert_eq!(fill(&mut code, 1, "a", true), 1);
+        assert_eq!(fill(&mut code, 1, "2-3 4 5", true), 3);
+        assert_eq!(code, [Some('1'), Some('2'), Some('3'), Some('4')]);
+        let mut code = vec![None; 3];
+        assert_eq!(fill(&mut code, 0, "ab", false), 2);
+    }
+
+    #[test]
+    fn backspace_moves_back_from_empty_boxes() {
+        let mut code = vec![Some('1'), Some('2'), None];
+        assert_eq!(backspace(&mut code, 2), 1);
+        assert_eq!(code, [Some('1'), None, None]);
+        assert_eq!(backspace(&mut code, 0), 0);
+        assert_eq!(code, [None, None, None]);
+    }
+}
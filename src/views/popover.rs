@@ -0,0 +1,613 @@
+#![deny(missing_docs)]
+//! A view that floats content next to an anchor in the overlay layer of the window.
+//!
+//! A [`Popover`] places its content on a side of its anchor, flips it to the opposite side when
+//! it doesn't fit in the window and shifts it along the anchor to keep it on screen. It can draw
+//! an arrow pointing at the anchor, and is dismissed by clicking outside of it or pressing Escape.
+
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::create_effect;
+use floem_renderer::Renderer;
+use peniko::kurbo::{BezPath, Point, Rect, Size};
+
+use crate::{
+    action::{add_overlay, move_overlay, remove_overlay},
+    app_state::AppState,
+    context::{ComputeLayoutCx, EventCx, PaintCx, StyleCx, UpdateCx},
+    event::{Event, EventPropagation},
+    id::ViewId,
+    prop, prop_extractor,
+    style::Background,
+    style_class,
+    view::{default_compute_layout, IntoView, View},
+    views::Decorators,
+    AnyView,
+};
+
+/// The distance a popover keeps from the edges of the window when it is flipped and shifted.
+const EDGE_PADDING: f64 = 5.0;
+
+style_class!(
+    /// A style class that is applied to the panel that holds the content of every popover.
+    pub PopoverClass
+);
+
+prop!(
+    /// The size of the arrow of a popover, from its base to its tip.
+    pub ArrowSize: f64 {} = 6.0
+);
+
+prop_extractor! {
+    PopoverStyle {
+        arrow_size: ArrowSize,
+        background: Background,
+    }
+}
+
+/// The side of the anchor that a popover is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// Above the anchor.
+    Top,
+    /// Below the anchor.
+    Bottom,
+    /// Left of the anchor.
+    Start,
+    /// Right of the anchor.
+    End,
+}
+
+impl Placement {
+    fn opposite(self) -> Self {
+        match self {
+            Placement::Top => Placement::Bottom,
+            Placement::Bottom => Placement::Top,
+            Placement::Start => Placement::End,
+            Placement::End => Placement::Start,
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Placement::Top | Placement::Bottom)
+    }
+}
+
+/// How a popover is aligned along the side of its anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopoverAlign {
+    /// Align the start of the popover with the start of the anchor.
+    Start,
+    /// Center the popover on the anchor.
+    Center,
+    /// Align the end of the popover with the end of the anchor.
+    End,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PopoverOptions {
+    pub(crate) placement: Placement,
+    pub(crate) align: PopoverAlign,
+    /// The gap between the anchor and the popover.
+    pub(crate) offset: f64,
+    /// How far the popover is moved along the side of the anchor.
+    pub(crate) cross_offset: f64,
+    pub(crate) flip: bool,
+    pub(crate) shift: bool,
+    pub(crate) arrow: bool,
+}
+
+impl Default for PopoverOptions {
+    fn default() -> Self {
+        Self {
+            placement: Placement::Bottom,
+            align: PopoverAlign::Center,
+            offset: 4.0,
+            cross_offset: 0.0,
+            flip: true,
+            shift: true,
+            arrow: false,
+        }
+    }
+}
+
+/// What a popover is placed next to.
+#[derive(Clone)]
+pub(crate) enum PopoverAnchor {
+    View(ViewId),
+    /// A rect in window coordinates, which can be moved while the popover is shown.
+    Rect(Rc<Cell<Rect>>),
+}
+
+impl PopoverAnchor {
+    fn rect(&self) -> Rect {
+        match self {
+            PopoverAnchor::View(id) => Rect::from_origin_size(
+                id.state().borrow().window_origin,
+                id.get_size().unwrap_or_default(),
+            ),
+            PopoverAnchor::Rect(rect) => rect.get(),
+        }
+    }
+}
+
+/// A popover that is dismissed by clicks outside of it and Escape.
+pub(crate) struct OpenPopover {
+    panel: ViewId,
+    /// Clicks on the anchor are left to the anchor, which usually toggles the popover.
+    anchor: Option<ViewId>,
+}
+
+impl OpenPopover {
+    pub(crate) fn is_panel(&self, id: ViewId) -> bool {
+        self.panel == id
+    }
+}
+
+/// Dismisses the open popovers from the top down to the first one that contains `pos`.
+pub(crate) fn dismiss_popovers_outside(app_state: &mut AppState, pos: Point) {
+    while let Some(open) = app_state.popovers.last() {
+        let inside = open.panel.layout_rect().contains(pos)
+            || open
+                .anchor
+                .is_some_and(|anchor| PopoverAnchor::View(anchor).rect().contains(pos));
+        if inside {
+            break;
+        }
+        open.panel.update_state(PanelMessage::Dismiss);
+        app_state.popovers.pop();
+    }
+}
+
+/// Dismisses the topmost open popover. Returns `false` if there is none.
+pub(crate) fn dismiss_top_popover(app_state: &mut AppState) -> bool {
+    if let Some(open) = app_state.popovers.pop() {
+        open.panel.update_state(PanelMessage::Dismiss);
+        true
+    } else {
+        false
+    }
+}
+
+/// Where a popover of `size` is placed next to `anchor` within `bounds`, with `gap` between them.
+/// Returns the origin of the popover and the side of the anchor that it ended up on.
+pub(crate) fn place(
+    anchor: Rect,
+    size: Size,
+    bounds: Rect,
+    options: &PopoverOptions,
+    gap: f64,
+) -> (Point, Placement) {
+    let room = |placement| match placement {
+        Placement::Top => anchor.y0 - gap - bounds.y0 - size.height,
+        Placement::Bottom => bounds.y1 - anchor.y1 - gap - size.height,
+        Placement::Start => anchor.x0 - gap - bounds.x0 - size.width,
+        Placement::End => bounds.x1 - anchor.x1 - gap - size.width,
+    };
+    let mut placement = options.placement;
+    if options.flip && room(placement) < 0.0 && room(placement.opposite()) > room(placement) {
+        placement = placement.opposite();
+    }
+
+    let align = |start: f64, end: f64, len: f64| {
+        let pos = match options.align {
+            PopoverAlign::Start => start,
+            PopoverAlign::Center => (start + end - len) / 2.0,
+            PopoverAlign::End => end - len,
+        };
+        pos + options.cross_offset
+    };
+    let mut origin = match placement {
+        Placement::Top => Point::new(
+            align(anchor.x0, anchor.x1, size.width),
+            anchor.y0 - gap - size.height,
+        ),
+        Placement::Bottom => Point::new(align(anchor.x0, anchor.x1, size.width), anchor.y1 + gap),
+        Placement::Start => Point::new(
+            anchor.x0 - gap - size.width,
+            align(anchor.y0, anchor.y1, size.height),
+        ),
+        Placement::End => Point::new(anchor.x1 + gap, align(anchor.y0, anchor.y1, size.height)),
+    };
+
+    if options.shift {
+        let shift = |pos: f64, len: f64, min: f64, max: f64| pos.min(max - len).max(min);
+        if placement.is_vertical() {
+            origin.x = shift(origin.x, size.width, bounds.x0, bounds.x1);
+        } else {
+            origin.y = shift(origin.y, size.height, bounds.y0, bounds.y1);
+        }
+    }
+
+    (origin, placement)
+}
+
+/// Shows `content` in the overlay layer of the window next to `anchor`, and returns the id of
+/// the overlay which is hidden again with [`remove_overlay`].
+///
+/// If `on_dismiss` is set, it is called when the user clicks outside of the popover or presses
+/// Escape.
+pub(crate) fn show_popover(
+    anchor: PopoverAnchor,
+    options: PopoverOptions,
+    on_dismiss: Option<Rc<dyn Fn()>>,
+    content: impl FnOnce() -> AnyView + 'static,
+) -> ViewId {
+    add_overlay(Point::ZERO, move |overlay| {
+        let id = ViewId::new();
+        id.set_children(vec![content()]);
+        PopoverPanel {
+            id,
+            overlay,
+            anchor,
+            options,
+            on_dismiss,
+            registered: false,
+            style: Default::default(),
+            position: None,
+            placement: options.placement,
+            arrow_offset: 0.0,
+        }
+        .class(PopoverClass)
+    })
+}
+
+enum PanelMessage {
+    Dismiss,
+}
+
+/// The view in the overlay that holds the content of a popover and places itself next to the anchor.
+struct PopoverPanel {
+    id: ViewId,
+    overlay: ViewId,
+    anchor: PopoverAnchor,
+    options: PopoverOptions,
+    on_dismiss: Option<Rc<dyn Fn()>>,
+    /// Whether the popover was added to the popovers that are dismissed by outside clicks.
+    registered: bool,
+    style: PopoverStyle,
+    position: Option<Point>,
+    /// The side of the anchor that the popover is on after flipping.
+    placement: Placement,
+    /// The position of the arrow along the side of the popover facing the anchor.
+    arrow_offset: f64,
+}
+
+impl View for PopoverPanel {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Popover Panel".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(state) = state.downcast::<PanelMessage>() {
+            match *state {
+                PanelMessage::Dismiss => {
+                    self.registered = false;
+                    if let Some(on_dismiss) = &self.on_dismiss {
+                        on_dismiss();
+                    }
+                }
+            }
+        }
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.style.read(cx) {
+            cx.app_state_mut().request_paint(self.id);
+        }
+        for child in self.id.children() {
+            cx.style_view(child);
+        }
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        if self.on_dismiss.is_some() && !self.registered {
+            self.registered = true;
+            let anchor = match self.anchor {
+                PopoverAnchor::View(anchor) => Some(anchor),
+                PopoverAnchor::Rect(_) => None,
+            };
+            cx.app_state.popovers.push(OpenPopover {
+                panel: self.id,
+                anchor,
+            });
+        }
+
+        let rect = default_compute_layout(self.id, cx);
+
+        let size = self.id.get_size().unwrap_or_default();
+        let bounds = self
+            .overlay
+            .parent_size()
+            .unwrap_or_default()
+            .to_rect()
+            .inset(-EDGE_PADDING);
+        let arrow_size = if self.options.arrow {
+            self.style.arrow_size()
+        } else {
+            0.0
+        };
+        let anchor = self.anchor.rect();
+        let (position, placement) = place(
+            anchor,
+            size,
+            bounds,
+            &self.options,
+            self.options.offset + arrow_size,
+        );
+        self.placement = placement;
+        self.arrow_offset = if placement.is_vertical() {
+            (anchor.center().x - position.x).clamp(arrow_size, size.width - arrow_size)
+        } else {
+            (anchor.center().y - position.y).clamp(arrow_size, size.height - arrow_size)
+        };
+        if self.position != Some(position) {
+            self.position = Some(position);
+            move_overlay(self.overlay, position);
+        }
+
+        rect
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        cx.paint_children(self.id);
+
+        let Some(background) = self.style.background().filter(|_| self.options.arrow) else {
+            return;
+        };
+        let size = self.id.get_size().unwrap_or_default();
+        let (arrow, offset) = (self.style.arrow_size(), self.arrow_offset);
+        let (base_start, base_end, tip) = match self.placement {
+            Placement::Top => (
+                Point::new(offset - arrow, size.height),
+                Point::new(offset + arrow, size.height),
+                Point::new(offset, size.height + arrow),
+            ),
+            Placement::Bottom => (
+                Point::new(offset - arrow, 0.0),
+                Point::new(offset + arrow, 0.0),
+                Point::new(offset, -arrow),
+            ),
+            Placement::Start => (
+                Point::new(size.width, offset - arrow),
+                Point::new(size.width, offset + arrow),
+                Point::new(size.width + arrow, offset),
+            ),
+            Placement::End => (
+                Point::new(0.0, offset - arrow),
+                Point::new(0.0, offset + arrow),
+                Point::new(-arrow, offset),
+            ),
+        };
+        let mut path = BezPath::new();
+        path.move_to(base_start);
+        path.line_to(tip);
+        path.line_to(base_end);
+        path.close_path();
+        cx.fill(&path, &background, 0.0);
+    }
+}
+
+enum PopoverState {
+    Open(bool),
+}
+
+/// A view that shows content in a popover next to its anchor. See [`popover`].
+pub struct Popover {
+    id: ViewId,
+    content: Rc<dyn Fn() -> AnyView>,
+    options: PopoverOptions,
+    overlay: Option<ViewId>,
+    on_open: Option<Box<dyn Fn(bool)>>,
+}
+
+/// Creates a view that shows `content` in a popover next to `anchor`.
+///
+/// Clicking the anchor toggles the popover, which can also be controlled with
+/// [`Popover::open`]. Clicking outside of the popover or pressing Escape closes it.
+///
+/// ```rust
+/// # use floem::views::{button, popover, text, Placement};
+/// let menu = popover(button("Options"), || text("Popover content"))
+///     .placement(Placement::End)
+///     .arrow(true);
+/// ```
+pub fn popover<V: IntoView + 'static, C: IntoView + 'static>(
+    anchor: V,
+    content: impl Fn() -> C + 'static,
+) -> Popover {
+    let id = ViewId::new();
+    id.set_children(vec![anchor.into_any()]);
+    Popover {
+        id,
+        content: Rc::new(move || content().into_any()),
+        options: PopoverOptions::default(),
+        overlay: None,
+        on_open: None,
+    }
+}
+
+impl Popover {
+    /// Sets the side of the anchor that the popover is placed on, [`Placement::Bottom`] by default.
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.options.placement = placement;
+        self
+    }
+
+    /// Sets how the popover is aligned along the side of the anchor, [`PopoverAlign::Center`] by default.
+    pub fn align(mut self, align: PopoverAlign) -> Self {
+        self.options.align = align;
+        self
+    }
+
+    /// Sets the gap between the anchor and the popover, `4.0` by default.
+    pub fn offset(mut self, offset: f64) -> Self {
+        self.options.offset = offset;
+        self
+    }
+
+    /// Moves the popover along the side of the anchor by `cross_offset`.
+    pub fn cross_offset(mut self, cross_offset: f64) -> Self {
+        self.options.cross_offset = cross_offset;
+        self
+    }
+
+    /// Sets whether the popover moves to the opposite side of the anchor when it doesn't fit in
+    /// the window on its side. Enabled by default.
+    pub fn flip(mut self, flip: bool) -> Self {
+        self.options.flip = flip;
+        self
+    }
+
+    /// Sets whether the popover is shifted along the side of the anchor to keep it inside the
+    /// window. Enabled by default.
+    pub fn shift(mut self, shift: bool) -> Self {
+        self.options.shift = shift;
+        self
+    }
+
+    /// Sets whether an arrow pointing at the anchor is drawn in the background color of the
+    /// popover. Its size is set with the [`ArrowSize`] property of the [`PopoverClass`].
+    pub fn arrow(mut self, arrow: bool) -> Self {
+        self.options.arrow = arrow;
+        self
+    }
+
+    /// Sets a reactive condition for showing or hiding the popover.
+    ///
+    /// # Reactivity
+    /// The `open` function will be re-run whenever any signal it depends on changes.
+    pub fn open(self, open: impl Fn() -> bool + 'static) -> Self {
+        let id = self.id;
+        create_effect(move |_| {
+            let open = open();
+            id.update_state(PopoverState::Open(open));
+        });
+        self
+    }
+
+    /// Sets a callback function that is called when the popover is opened or closed,
+    /// including when it is dismissed.
+    ///
+    /// Only one `on_open` callback can be set at a time.
+    pub fn on_open(mut self, on_open: impl Fn(bool) + 'static) -> Self {
+        self.on_open = Some(Box::new(on_open));
+        self
+    }
+
+    fn show(&mut self) {
+        if self.overlay.is_some() {
+            return;
+        }
+        let id = self.id;
+        let content = self.content.clone();
+        self.overlay = Some(show_popover(
+            PopoverAnchor::View(id),
+            self.options,
+            Some(Rc::new(move || id.update_state(PopoverState::Open(false)))),
+            move || content(),
+        ));
+        if let Some(on_open) = &self.on_open {
+            on_open(true);
+        }
+    }
+
+    fn hide(&mut self) {
+        if let Some(overlay) = self.overlay.take() {
+            remove_overlay(overlay);
+            if let Some(on_open) = &self.on_open {
+                on_open(false);
+            }
+        }
+    }
+}
+
+impl View for Popover {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Popover".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(state) = state.downcast::<PopoverState>() {
+            match *state {
+                PopoverState::Open(true) => self.show(),
+                PopoverState::Open(false) => self.hide(),
+            }
+        }
+    }
+
+    fn event_before_children(&mut self, _cx: &mut EventCx, event: &Event) -> EventPropagation {
+        if let Event::PointerDown(event) = event {
+            if event.button.is_primary() {
+                let open = self.overlay.is_none();
+                self.id.update_state(PopoverState::Open(open));
+            }
+        }
+        EventPropagation::Continue
+    }
+}
+
+impl Drop for Popover {
+    fn drop(&mut self) {
+        if let Some(overlay) = self.overlay {
+            remove_overlay(overlay)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::kurbo::{Point, Rect, Size};
+
+    use super::{place, Placement, PopoverAlign, PopoverOptions};
+
+    const BOUNDS: Rect = Rect::new(0.0, 0.0, 400.0, 300.0);
+
+    #[test]
+    fn places_on_the_requested_side() {
+        let options = PopoverOptions {
+            align: PopoverAlign::Start,
+            ..Default::default()
+        };
+        let anchor = Rect::new(100.0, 100.0, 150.0, 120.0);
+        let (origin, placement) = place(anchor, Size::new(80.0, 40.0), BOUNDS, &options, 4.0);
+        assert_eq!(placement, Placement::Bottom);
+        assert_eq!(origin, Point::new(100.0, 124.0));
+    }
+
+    #[test]
+    fn flips_when_there_is_no_room() {
+        let options = PopoverOptions::default();
+        let anchor = Rect::new(100.0, 260.0, 150.0, 280.0);
+        let (origin, placement) = place(anchor, Size::new(50.0, 40.0), BOUNDS, &options, 4.0);
+        assert_eq!(placement, Placement::Top);
+        assert_eq!(origin, Point::new(100.0, 216.0));
+    }
+
+    #[test]
+    fn shifts_along_the_anchor_to_stay_in_bounds() {
+        let options = PopoverOptions {
+            placement: Placement::Top,
+            ..Default::default()
+        };
+        let anchor = Rect::new(380.0, 100.0, 400.0, 120.0);
+        let (origin, placement) = place(anchor, Size::new(100.0, 40.0), BOUNDS, &options, 4.0);
+        assert_eq!(placement, Placement::Top);
+        assert_eq!(origin, Point::new(300.0, 56.0));
+
+        let options = PopoverOptions {
+            shift: false,
+            ..options
+        };
+        let (origin, _) = place(anchor, Size::new(100.0, 40.0), BOUNDS, &options, 4.0);
+        assert_eq!(origin.x, 340.0);
+    }
+}
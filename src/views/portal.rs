@@ -0,0 +1,101 @@
+#![deny(missing_docs)]
+//! A view that renders its content in the overlay layer of the window.
+
+use peniko::kurbo::{Point, Rect};
+
+use crate::{
+    action::{add_overlay, move_overlay, remove_overlay},
+    context::ComputeLayoutCx,
+    id::ViewId,
+    style::Style,
+    view::{default_compute_layout, IntoView, View},
+    AnyView,
+};
+
+/// A view that renders its content above the rest of the window. See [`portal`].
+pub struct Portal {
+    id: ViewId,
+    /// The content until it is moved into the overlay on the first layout.
+    content: Option<AnyView>,
+    overlay: Option<ViewId>,
+    window_origin: Point,
+}
+
+/// Creates a view that renders `content` in the overlay layer of the window, at the position of
+/// the portal but outside of the clipping of the views around it, e.g. a [`Scroll`](super::Scroll).
+///
+/// The portal itself takes no space. The content is created in the reactive scope of the caller
+/// and is disposed with the portal. Events are routed to the content as if it were a child of
+/// the portal: the capture listeners of the views around the portal see the events of the
+/// content first, and the events that the content doesn't handle go on to the listeners of the
+/// portal and its ancestors.
+///
+/// The content is styled as a child of the window rather than of the portal, so it doesn't
+/// inherit the styles of the views around the portal.
+///
+/// ```rust
+/// # use floem::views::{portal, scroll, text, v_stack, Decorators};
+/// scroll(v_stack((
+///     text("Clipped content"),
+///     portal(text("Drawn above the scroll view").style(|s| s.margin_top(20.0))),
+/// )));
+/// ```
+pub fn portal<V: IntoView + 'static>(content: V) -> Portal {
+    let id = ViewId::new();
+    let content = content.into_any();
+    content.id().set_logical_parent(id);
+    Portal {
+        id,
+        content: Some(content),
+        overlay: None,
+        window_origin: Point::ZERO,
+    }
+}
+
+impl View for Portal {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Portal".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().size(0.0, 0.0))
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        // the overlay is added on the first layout as that is when the portal is in a window
+        if let Some(content) = self.content.take() {
+            self.window_origin = cx.window_origin;
+            self.overlay = Some(add_overlay(cx.window_origin, move |_| content));
+        } else if self.window_origin != cx.window_origin {
+            self.window_origin = cx.window_origin;
+            if let Some(overlay) = self.overlay {
+                move_overlay(overlay, cx.window_origin);
+            }
+        }
+        default_compute_layout(self.id, cx)
+    }
+}
+
+impl Drop for Portal {
+    fn drop(&mut self) {
+        if let Some(overlay) = self.overlay {
+            remove_overlay(overlay);
+        }
+    }
+}
+
+/// A trait that adds a `portal` method to any type that implements `IntoView`.
+pub trait PortalExt {
+    /// Render the view in the overlay layer of the window, see [`portal`].
+    fn portal(self) -> Portal;
+}
+
+impl<T: IntoView + 'static> PortalExt for T {
+    fn portal(self) -> Portal {
+        portal(self)
+    }
+}
@@ -0,0 +1,151 @@
+#![deny(missing_docs)]
+//! Views that show the progress of work: a bar that fills up as the work is done, a bar that
+//! moves while it isn't known how long the work takes, and a spinner.
+
+use std::f64::consts::{PI, TAU};
+
+use floem_renderer::Renderer;
+
+use crate::{
+    accessibility::prefers_reduced_motion,
+    id::ViewId,
+    kurbo::{Arc, Cap, Point, Stroke},
+    prop, prop_extractor,
+    style::TextColor,
+    style_class,
+    unit::DurationUnitExt,
+    view::View,
+    views::{clip, empty, Clip, Decorators},
+};
+
+style_class!(
+    /// The style class that is applied to the track of a progress bar.
+    pub ProgressBarClass
+);
+
+style_class!(
+    /// The style class that is applied to the part of a progress bar that shows the progress.
+    pub ProgressBarFillClass
+);
+
+style_class!(
+    /// The style class that is applied to spinners.
+    pub SpinnerClass
+);
+
+prop!(
+    /// The width of the arc of a spinner.
+    pub SpinnerThickness: f64 {} = 2.5
+);
+
+prop_extractor! {
+    SpinnerStyle {
+        color: TextColor,
+        thickness: SpinnerThickness,
+    }
+}
+
+/// The width of the fill of an indeterminate progress bar, in percent of the bar.
+const INDETERMINATE_WIDTH: f64 = 40.;
+
+/// A progress bar that is filled by the fraction returned by `progress`, from `0.0` to `1.0`.
+///
+/// The bar is styled with [`ProgressBarClass`], and the fill with [`ProgressBarFillClass`], whose
+/// width changes are smoothed by a transition of the theme.
+///
+/// ```
+/// # use floem::reactive::create_rw_signal;
+/// # use floem::reactive::SignalGet;
+/// # use floem::views::progress_bar;
+/// let downloaded = create_rw_signal(0.3);
+/// progress_bar(move || downloaded.get());
+/// ```
+pub fn progress_bar(progress: impl Fn() -> f64 + 'static) -> Clip {
+    clip(
+        empty()
+            .class(ProgressBarFillClass)
+            .style(move |s| s.height_full().width_pct(progress().clamp(0., 1.) * 100.)),
+    )
+    .class(ProgressBarClass)
+}
+
+/// A progress bar whose fill moves from start to end repeatedly, for work whose duration isn't
+/// known.
+///
+/// When the user prefers reduced motion, the fill covers the bar instead of moving.
+pub fn indeterminate_progress_bar() -> Clip {
+    clip(
+        empty()
+            .class(ProgressBarFillClass)
+            .style(|s| {
+                let reduced_motion = prefers_reduced_motion();
+                s.absolute()
+                    .height_full()
+                    .width_pct(if reduced_motion {
+                        100.
+                    } else {
+                        INDETERMINATE_WIDTH
+                    })
+                    .apply_if(!reduced_motion, |s| s.inset_left_pct(-INDETERMINATE_WIDTH))
+            })
+            .animation(|a| {
+                a.keyframe(0, |f| f.style(|s| s.inset_left_pct(-INDETERMINATE_WIDTH)))
+                    .keyframe(100, |f| f.style(|s| s.inset_left_pct(100.)).ease_in_out())
+                    .duration(1500.millis())
+                    .repeat(true)
+            }),
+    )
+    .class(ProgressBarClass)
+}
+
+/// A spinner, an arc that turns while work is in progress.
+///
+/// The arc is drawn with the text color, and is as wide as the [`SpinnerThickness`]. When the
+/// user prefers reduced motion, it doesn't turn.
+pub fn spinner() -> Spinner {
+    Spinner {
+        id: ViewId::new(),
+        style: Default::default(),
+    }
+    .class(SpinnerClass)
+    .animation(|a| {
+        a.keyframe(0, |f| f.style(|s| s.rotate(0.)))
+            .keyframe(100, |f| f.style(|s| s.rotate(TAU)))
+            .duration(1.seconds())
+            .repeat(true)
+    })
+}
+
+/// A view that draws a turning arc, created with [`spinner`].
+pub struct Spinner {
+    id: ViewId,
+    style: SpinnerStyle,
+}
+
+impl View for Spinner {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn style_pass(&mut self, cx: &mut crate::context::StyleCx<'_>) {
+        if self.style.read(cx) {
+            cx.app_state_mut().request_paint(self.id);
+        }
+    }
+
+    fn paint(&mut self, cx: &mut crate::context::PaintCx) {
+        let Some(color) = self.style.color() else {
+            return;
+        };
+        let size = self.id.get_size().unwrap_or_default();
+        let thickness = self.style.thickness();
+        let radius = (size.width.min(size.height) - thickness) / 2.;
+        if radius <= 0. {
+            return;
+        }
+        let center = Point::new(size.width / 2., size.height / 2.);
+        // three quarters of a circle, with the gap at the top right
+        let arc = Arc::new(center, (radius, radius), -PI / 2., PI * 1.5, 0.);
+        cx.stroke(&arc, color, &Stroke::new(thickness).with_caps(Cap::Round));
+    }
+}
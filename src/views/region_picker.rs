@@ -0,0 +1,197 @@
+#![deny(missing_docs)]
+//! A view for picking a region of a window by dragging a rect, like for taking a screenshot.
+
+use floem_renderer::Renderer;
+use floem_winit::keyboard::{Key, NamedKey};
+use peniko::kurbo::{Point, Rect, Stroke};
+
+use crate::{
+    context::{EventCx, PaintCx, StyleCx},
+    event::{Event, EventPropagation},
+    id::ViewId,
+    prop_extractor,
+    style::{Background, BorderColor, CursorStyle, Style},
+    style_class,
+    view::View,
+    views::Decorators,
+};
+
+style_class!(
+    /// The style class that is applied to region pickers.
+    pub RegionPickerClass
+);
+
+style_class!(
+    /// The style class whose background dims the part of the window outside the region that is
+    /// being picked.
+    pub RegionMaskClass
+);
+
+style_class!(
+    /// The style class of the region that is being picked, whose border color is used for its
+    /// outline.
+    pub PickedRegionClass
+);
+
+prop_extractor! {
+    MaskStyle {
+        background: Background,
+    }
+}
+
+prop_extractor! {
+    RegionStyle {
+        border_color: BorderColor,
+    }
+}
+
+/// A region picker, created with [`region_picker`].
+pub struct RegionPicker {
+    id: ViewId,
+    /// Where the drag started and where the pointer is.
+    drag: Option<(Point, Point)>,
+    on_pick: Box<dyn Fn(Rect)>,
+    on_cancel: Option<Box<dyn Fn()>>,
+    mask_style: MaskStyle,
+    region_style: RegionStyle,
+}
+
+/// A view that covers its parent, where dragging a rect picks a region of the window, which
+/// `on_pick` is called with in the coordinates of the window. `Escape` cancels picking, see
+/// [`RegionPicker::on_cancel`].
+///
+/// The part of the window outside the region is dimmed with the background of the
+/// [`RegionMaskClass`]. The picker is meant to be shown in an overlay, which is removed before the
+/// region is captured with [`capture_region`](crate::action::capture_region).
+///
+/// ```no_run
+/// # use floem::action::{add_overlay, capture_region, remove_overlay};
+/// # use floem::kurbo::Point;
+/// # use floem::views::{region_picker, Decorators};
+/// add_overlay(Point::ZERO, |overlay| {
+///     region_picker(move |rect| {
+///         remove_overlay(overlay);
+///         capture_region(rect, |image| {
+///             if let Some(image) = image {
+///                 let _ = image.save("screenshot.png");
+///             }
+///         });
+///     })
+///     .on_cancel(move || remove_overlay(overlay))
+/// });
+/// ```
+pub fn region_picker(on_pick: impl Fn(Rect) + 'static) -> RegionPicker {
+    let id = ViewId::new();
+    // take the focus to receive `Escape`
+    id.request_focus();
+    RegionPicker {
+        id,
+        drag: None,
+        on_pick: Box::new(on_pick),
+        on_cancel: None,
+        mask_style: Default::default(),
+        region_style: Default::default(),
+    }
+    .keyboard_navigable()
+    .class(RegionPickerClass)
+}
+
+impl RegionPicker {
+    /// Sets a function that is called when picking is cancelled with `Escape`.
+    pub fn on_cancel(mut self, on_cancel: impl Fn() + 'static) -> Self {
+        self.on_cancel = Some(Box::new(on_cancel));
+        self
+    }
+
+    /// The picked region, relative to the picker.
+    fn region(&self) -> Option<Rect> {
+        self.drag.map(|(start, end)| Rect::from_points(start, end))
+    }
+}
+
+impl View for RegionPicker {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Region Picker".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(
+            Style::new()
+                .absolute()
+                .inset(0)
+                .cursor(CursorStyle::Crosshair),
+        )
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        let style = cx.style();
+        let mask = style.clone().apply_class(RegionMaskClass);
+        self.mask_style.read_style(cx, &mask);
+        let region = style.apply_class(PickedRegionClass);
+        self.region_style.read_style(cx, &region);
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::PointerDown(event) if event.button.is_primary() => {
+                self.drag = Some((event.pos, event.pos));
+                cx.update_active(self.id);
+                self.id.request_paint();
+            }
+            Event::PointerMove(event) => {
+                if let Some((_, end)) = &mut self.drag {
+                    *end = event.pos;
+                    self.id.request_paint();
+                }
+            }
+            Event::PointerUp(_) => {
+                if let Some(region) = self.region().filter(|region| region.area() > 0.) {
+                    let origin = self.id.state().borrow().window_origin;
+                    (self.on_pick)(region + origin.to_vec2());
+                }
+                self.drag = None;
+                self.id.request_paint();
+            }
+            Event::KeyDown(event) if event.key.logical_key == Key::Named(NamedKey::Escape) => {
+                self.drag = None;
+                if let Some(on_cancel) = &self.on_cancel {
+                    on_cancel();
+                }
+            }
+            _ => return EventPropagation::Continue,
+        }
+        EventPropagation::Stop
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let bounds = self.id.get_size().unwrap_or_default().to_rect();
+        let region = self.region().map(|region| region.intersect(bounds));
+        if let Some(mask) = self.mask_style.background() {
+            match region {
+                // the parts of the picker above, below, left and right of the region
+                Some(region) => {
+                    for rect in [
+                        Rect::new(bounds.x0, bounds.y0, bounds.x1, region.y0),
+                        Rect::new(bounds.x0, region.y1, bounds.x1, bounds.y1),
+                        Rect::new(bounds.x0, region.y0, region.x0, region.y1),
+                        Rect::new(region.x1, region.y0, bounds.x1, region.y1),
+                    ] {
+                        cx.fill(&rect, &mask, 0.0);
+                    }
+                }
+                None => cx.fill(&bounds, &mask, 0.0),
+            }
+        }
+        if let Some(region) = region {
+            cx.stroke(
+                &region,
+                &self.region_style.border_color(),
+                &Stroke::new(1.0),
+            );
+        }
+    }
+}
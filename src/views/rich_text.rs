@@ -64,6 +64,16 @@ impl View for RichText {
         .into()
     }
 
+    fn text_content(&self) -> Option<String> {
+        Some(
+            self.text_layout
+                .lines()
+                .iter()
+                .map(|text| text.text())
+                .collect(),
+        )
+    }
+
     fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
         if let Ok(state) = state.downcast() {
             self.text_layout = *state;
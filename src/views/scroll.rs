@@ -1,39 +1,75 @@
 #![deny(missing_docs)]
 //! Scroll View
 
-use floem_reactive::create_effect;
+use floem_reactive::{
+    create_effect, create_rw_signal, ReadSignal, RwSignal, SignalUpdate, SignalWith,
+};
 use peniko::kurbo::{Point, Rect, Size, Stroke, Vec2};
 use peniko::{Brush, Color};
+use taffy::{style::LengthPercentage, tree::NodeId};
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
 
 use crate::style::CustomStylable;
 use crate::unit::PxPct;
 use crate::{
+    action::{exec_after, remove_overlay, TimerToken},
     app_state::AppState,
-    context::{ComputeLayoutCx, PaintCx},
+    clock,
+    context::{ComputeLayoutCx, LayoutCx, PaintCx},
+    easing::Easing,
     event::{Event, EventPropagation},
+    gestures,
     id::ViewId,
     prop, prop_extractor,
-    style::{Background, BorderColor, BorderRadius, Style, StyleSelector},
+    style::{
+        Background, BorderColor, BorderRadius, Style, StylePropValue, StyleSelector, Transition,
+    },
     style_class,
+    ui_state::UiStateRegistry,
     unit::Px,
-    view::{IntoView, View},
+    view::{recursively_layout_view, IntoView, View},
+    view_state::ChangeFlags,
+    views::{
+        label,
+        popover::{show_popover, Placement, PopoverAnchor, PopoverOptions},
+        TooltipClass,
+    },
     Renderer,
 };
 
 use super::Decorators;
 
-enum ScrollState {
+pub(crate) enum ScrollState {
     EnsureVisible(Rect),
     ScrollDelta(Vec2),
     ScrollTo(Point),
+    ScrollToAnimated(Point, Transition),
     ScrollToPercent(f32),
     ScrollToView(ViewId),
+    /// Sent after the last wheel event to settle the viewport on a snap point
+    Settle(TimerToken),
+    Markers(Vec<ScrollMarker>),
+    /// Sent once the view was idle for a while to fade out automatically hidden bars
+    FadeOutBars(TimerToken),
 }
 
 /// Minimum length for any scrollbar to be when measured on that
 /// scrollbar's primary axis.
 const SCROLLBAR_MIN_SIZE: f64 = 10.0;
 
+/// The height of a marker on the vertical scrollbar track.
+const MARKER_HEIGHT: f64 = 3.0;
+
+/// How far past its edges a marker can be hovered to show its tooltip.
+const MARKER_HIT_SLOP: f64 = 2.0;
+
 /// Denotes which scrollbar, if any, is currently being dragged.
 #[derive(Debug, Copy, Clone)]
 enum BarHeldState {
@@ -110,6 +146,95 @@ prop!(
     pub OverflowClip: bool {} = true
 );
 
+prop!(
+    /// Hides the scroll bars while the view is idle. They fade in while the view is scrolled or
+    /// hovered, and fade out again after [`AutoHideDelay`]. Enabled by default on mobile
+    /// platforms.
+    pub AutoHideBars: bool {} = cfg!(any(target_os = "android", target_os = "ios"))
+);
+
+prop!(
+    /// How long automatically hidden scroll bars stay visible after the view was last scrolled
+    /// or hovered.
+    pub AutoHideDelay: Duration {} = Duration::from_millis(1000)
+);
+
+prop!(
+    /// Always reserves space for the vertical scroll bar next to the content, so the bar never
+    /// covers the content and the content doesn't move when the bar appears.
+    pub ReserveGutter: bool {} = false
+);
+
+/// How a scroll view settles on the snap points of its content. See [`ScrollCustomStyle::snap_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapType {
+    /// The viewport always settles on the nearest snap point.
+    Mandatory,
+    /// The viewport only settles on a snap point if it is already close to it.
+    Proximity,
+}
+impl StylePropValue for SnapType {}
+
+/// The part of a child that a scroll view snaps to. See [`Style::snap_align`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapAlign {
+    /// Snap the start of the child to the start of the viewport.
+    Start,
+    /// Snap the center of the child to the center of the viewport.
+    Center,
+    /// Snap the end of the child to the end of the viewport.
+    End,
+}
+impl StylePropValue for SnapAlign {}
+
+prop!(
+    /// Enables scroll snapping to the children of the content that have a [`ScrollSnapAlign`].
+    pub ScrollSnapType: Option<SnapType> {} = None
+);
+
+prop!(
+    /// Makes a direct child of the content of a scroll view a snap point.
+    pub ScrollSnapAlign: Option<SnapAlign> {} = None
+);
+
+prop!(
+    /// Lets the content be dragged with the pointer and flung, continuing to scroll with inertia
+    /// after it is released. Enabled by default on mobile platforms.
+    pub KineticScrolling: bool {} = cfg!(any(target_os = "android", target_os = "ios"))
+);
+
+prop!(
+    /// The deceleration of a fling in pixels per second squared.
+    pub FlingDeceleration: f64 {} = 2500.0
+);
+
+prop!(
+    /// Lets the content be pulled past its edges, bouncing back when released. Enabled by
+    /// default on mobile platforms.
+    pub Overscroll: bool {} = cfg!(any(target_os = "android", target_os = "ios"))
+);
+
+/// The pointer positions from this window before the release are used to compute the fling velocity.
+const FLING_VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+/// Releases slower than this, in pixels per second, don't start a fling.
+const MIN_FLING_SPEED: f64 = 50.0;
+
+/// How much of the velocity of a fling, in seconds, turns into overscroll when it hits an edge.
+const OVERSCROLL_IMPACT: f64 = 0.03;
+
+/// The rate at which the overscroll relaxes back to the edge.
+const OVERSCROLL_RELAX_RATE: f64 = 12.0;
+
+/// How long automatically hidden scroll bars take to fade in or out.
+const BAR_FADE_DURATION: Duration = Duration::from_millis(200);
+
+/// How long the scroll view waits after the last wheel event before it settles on a snap point.
+const SNAP_SETTLE_DELAY: Duration = Duration::from_millis(150);
+
+/// With [`SnapType::Proximity`] the viewport snaps to points that are within this fraction of the viewport size.
+const SNAP_PROXIMITY: f64 = 0.3;
+
 prop_extractor!(ScrollStyle {
     vertical_bar_inset: VerticalInset,
     horizontal_bar_inset: HorizontalInset,
@@ -117,6 +242,13 @@ prop_extractor!(ScrollStyle {
     propagate_pointer_wheel: PropagatePointerWheel,
     vertical_scroll_as_horizontal: VerticalScrollAsHorizontal,
     overflow_clip: OverflowClip,
+    auto_hide_bars: AutoHideBars,
+    auto_hide_delay: AutoHideDelay,
+    reserve_gutter: ReserveGutter,
+    snap_type: ScrollSnapType,
+    kinetic: KineticScrolling,
+    fling_deceleration: FlingDeceleration,
+    overscroll: Overscroll,
 });
 
 const HANDLE_COLOR: Brush = Brush::Solid(Color::rgba8(0, 0, 0, 120));
@@ -126,6 +258,227 @@ style_class!(
     pub ScrollClass
 );
 
+/// Where the target of a scroll into view request should end up in the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollAlign {
+    /// Scroll the smallest distance that makes the target visible.
+    #[default]
+    Nearest,
+    /// Align the start of the target with the start of the viewport.
+    Start,
+    /// Center the target in the viewport.
+    Center,
+    /// Align the end of the target with the end of the viewport.
+    End,
+}
+
+/// Options for [`ViewId::scroll_into_view_with`](crate::ViewId::scroll_into_view_with).
+#[derive(Debug, Clone, Default)]
+pub struct ScrollIntoView {
+    align: ScrollAlign,
+    margin: f64,
+    transition: Option<Transition>,
+}
+
+impl ScrollIntoView {
+    /// Scroll the smallest distance that makes the target visible, without an animation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets where the target should end up in the viewport.
+    pub fn align(mut self, align: ScrollAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets the space in pixels that should be kept between the target and the edges of the viewport.
+    pub fn margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Animate the scroll over `duration` using `easing`.
+    pub fn animated(mut self, duration: Duration, easing: impl Easing + 'static) -> Self {
+        self.transition = Some(Transition::new(duration, easing));
+        self
+    }
+
+    pub(crate) fn animated_default(mut self) -> Self {
+        self.transition = Some(Transition::ease_in_out(Duration::from_millis(300)));
+        self
+    }
+}
+
+/// An animation of the viewport of a [`Scroll`] to a new origin.
+struct ScrollAnimation {
+    from: Point,
+    to: Point,
+    start: Instant,
+    transition: Transition,
+}
+
+impl ScrollAnimation {
+    /// Returns the origin of the viewport at `now` and whether the animation has finished.
+    fn origin(&self, now: Instant) -> (Point, bool) {
+        let time = now.saturating_duration_since(self.start);
+        let duration = self.transition.duration.as_secs_f64();
+        let time_percent = if duration == 0. {
+            1.
+        } else {
+            time.as_secs_f64() / duration
+        };
+        if time_percent >= 1. && self.transition.easing.finished(time_percent) {
+            return (self.to, true);
+        }
+        let eased = self.transition.easing.eval(time_percent);
+        (self.from.lerp(self.to, eased), false)
+    }
+}
+
+/// A press on the content of a kinetic scroll view, which becomes a drag once the pointer moves far enough.
+struct DragScroll {
+    start_pos: Point,
+    start_origin: Point,
+    dragging: bool,
+    /// Recent pointer positions, used to compute the velocity on release.
+    samples: VecDeque<(Instant, Point)>,
+}
+
+impl DragScroll {
+    fn push_sample(&mut self, now: Instant, pos: Point) {
+        self.samples.push_back((now, pos));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(time, _)| now.duration_since(*time) > FLING_VELOCITY_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// The velocity of the viewport origin in pixels per second, which is opposite to the pointer.
+    fn velocity(&self) -> Vec2 {
+        let (Some((start, from)), Some((end, to))) = (self.samples.front(), self.samples.back())
+        else {
+            return Vec2::ZERO;
+        };
+        let dt = end.duration_since(*start).as_secs_f64();
+        if dt <= 0. {
+            return Vec2::ZERO;
+        }
+        (*from - *to) / dt
+    }
+}
+
+/// The fading in and out of scroll bars that are hidden while the view is idle.
+struct BarFade {
+    visible: bool,
+    /// When the bars started fading in or out.
+    start: Instant,
+    /// The opacity of the bars when they started fading.
+    from: f64,
+}
+
+impl BarFade {
+    fn opacity(&self, now: Instant) -> f64 {
+        let time = now.saturating_duration_since(self.start).as_secs_f64()
+            / BAR_FADE_DURATION.as_secs_f64();
+        let to = if self.visible { 1. } else { 0. };
+        self.from + (to - self.from) * time.min(1.)
+    }
+
+    fn is_fading(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) < BAR_FADE_DURATION
+    }
+
+    fn set_visible(&mut self, visible: bool, now: Instant) {
+        if self.visible != visible {
+            self.from = self.opacity(now);
+            self.visible = visible;
+            self.start = now;
+        }
+    }
+}
+
+/// Squashes `excess` past an edge so that overscroll gets harder the further it is pulled.
+fn rubber_band(excess: f64, size: f64) -> f64 {
+    if size <= 0. {
+        return 0.;
+    }
+    const RESISTANCE: f64 = 0.55;
+    (1. - 1. / (excess.abs() * RESISTANCE / size + 1.)) * size * excess.signum()
+}
+
+/// Slows a fling down by `deceleration` over `dt` seconds, returning its new velocity, or `None`
+/// once it has stopped.
+fn decelerate(velocity: Vec2, deceleration: f64, dt: f64) -> Option<Vec2> {
+    let speed = velocity.hypot();
+    let new_speed = (speed - deceleration * dt).max(0.);
+    (new_speed > 0.).then(|| velocity * (new_speed / speed))
+}
+
+/// Limits `overscroll` to a quarter of the viewport `size` and relaxes it towards the edge over
+/// `dt` seconds, letting go of it once it's under half a pixel.
+fn relax_overscroll(overscroll: Vec2, size: Size, dt: f64) -> Vec2 {
+    let overscroll = Vec2::new(
+        overscroll.x.clamp(-size.width / 4., size.width / 4.),
+        overscroll.y.clamp(-size.height / 4., size.height / 4.),
+    ) * (-OVERSCROLL_RELAX_RATE * dt).exp();
+    if overscroll.hypot() < 0.5 {
+        Vec2::ZERO
+    } else {
+        overscroll
+    }
+}
+
+/// The scroll position of a [`Scroll`] view. See [`Scroll::scroll_progress`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrollProgress {
+    /// How far the view is scrolled horizontally, from `0.0` at the start to `1.0` at the end.
+    ///
+    /// This is `0.0` if the content fits in the view.
+    pub x: f64,
+    /// How far the view is scrolled vertically, from `0.0` at the top to `1.0` at the bottom.
+    ///
+    /// This is `0.0` if the content fits in the view.
+    pub y: f64,
+    /// The scroll offset in pixels.
+    pub offset: Vec2,
+    /// The largest possible scroll offset in pixels.
+    pub max_offset: Vec2,
+}
+
+/// A marker on the vertical scrollbar track of a [`Scroll`], like a search result, a diagnostic
+/// or a changed line. See [`Scroll::markers`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrollMarker {
+    /// Where the marker is on the track, from `0.0` at the top of the content to `1.0` at the
+    /// bottom.
+    pub position: f64,
+    /// The color of the marker.
+    pub color: Color,
+    /// The text that is shown when the marker is hovered.
+    pub tooltip: Option<String>,
+}
+
+impl ScrollMarker {
+    /// A marker at `position` of the content, from `0.0` at the top to `1.0` at the bottom.
+    pub fn new(position: f64, color: Color) -> Self {
+        Self {
+            position,
+            color,
+            tooltip: None,
+        }
+    }
+
+    /// Sets the text that is shown when the marker is hovered.
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+}
+
 /// A scroll view
 pub struct Scroll {
     id: ViewId,
@@ -147,6 +500,17 @@ pub struct Scroll {
     computed_child_viewport: Rect,
 
     onscroll: Option<Box<dyn Fn(Rect)>>,
+    /// The key that the scroll position is saved under, see [`Scroll::persist`].
+    persist_key: Option<String>,
+    progress: RwSignal<ScrollProgress>,
+    scroll_animation: Option<ScrollAnimation>,
+    snap_timer: Option<TimerToken>,
+    drag: Option<DragScroll>,
+    /// The velocity of the viewport origin while it is flung, in pixels per second.
+    fling: Option<Vec2>,
+    /// How far the content is pulled past its edges. This only moves the painted content.
+    overscroll: Vec2,
+    last_physics_step: Option<Instant>,
     held: BarHeldState,
     v_handle_hover: bool,
     h_handle_hover: bool,
@@ -158,6 +522,13 @@ pub struct Scroll {
     track_style: ScrollTrackStyle,
     track_hover_style: ScrollTrackStyle,
     scroll_style: ScrollStyle,
+    markers: Vec<ScrollMarker>,
+    /// The hovered marker and the overlay with its tooltip.
+    marker_tip: Option<(usize, ViewId)>,
+    bar_fade: BarFade,
+    fade_out_timer: Option<TimerToken>,
+    /// The space that is reserved for the vertical bar, see [`ReserveGutter`].
+    gutter: f64,
 }
 
 /// Create a new scroll view
@@ -176,6 +547,14 @@ pub fn scroll<V: IntoView + 'static>(child: V) -> Scroll {
         child_viewport: Rect::ZERO,
         computed_child_viewport: Rect::ZERO,
         onscroll: None,
+        persist_key: None,
+        progress: create_rw_signal(ScrollProgress::default()),
+        scroll_animation: None,
+        snap_timer: None,
+        drag: None,
+        fling: None,
+        overscroll: Vec2::ZERO,
+        last_physics_step: None,
         held: BarHeldState::None,
         v_handle_hover: false,
         h_handle_hover: false,
@@ -187,6 +566,15 @@ pub fn scroll<V: IntoView + 'static>(child: V) -> Scroll {
         track_style: Default::default(),
         track_hover_style: Default::default(),
         scroll_style: Default::default(),
+        markers: Vec::new(),
+        marker_tip: None,
+        bar_fade: BarFade {
+            visible: false,
+            start: clock::now(),
+            from: 0.,
+        },
+        fade_out_timer: None,
+        gutter: 0.,
     }
     .class(ScrollClass)
 }
@@ -201,6 +589,74 @@ impl Scroll {
         self
     }
 
+    /// Saves the scroll position under `key` in the [`UiStateRegistry`], and scrolls to the
+    /// position that was saved when the application was last closed.
+    pub fn persist(mut self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        let saved = UiStateRegistry::get(&key).and_then(|origin| {
+            let (x, y) = origin.split_once(' ')?;
+            Some(Point::new(x.parse().ok()?, y.parse().ok()?))
+        });
+        if let Some(origin) = saved {
+            self.id.update_state_deferred(ScrollState::ScrollTo(origin));
+        }
+        self.persist_key = Some(key);
+        self
+    }
+
+    /// Returns a signal with the current scroll position of the view.
+    ///
+    /// The signal can be used to drive styles or animations from the scroll position,
+    /// e.g. for parallax headers, toolbars that shrink on scroll, or reading progress bars.
+    ///
+    /// ```rust
+    /// # use floem::reactive::SignalGet;
+    /// # use floem::views::{scroll, empty, stack, Decorators};
+    /// # use floem::unit::UnitExt;
+    /// let list = scroll(empty().style(|s| s.height(2000)));
+    /// let progress = list.scroll_progress();
+    /// stack((
+    ///     // a reading progress bar
+    ///     empty().style(move |s| s.height(4).width((progress.get().y * 100.).pct())),
+    ///     list,
+    /// ));
+    /// ```
+    pub fn scroll_progress(&self) -> ReadSignal<ScrollProgress> {
+        self.progress.read_only()
+    }
+
+    /// The id of the content of the view.
+    pub(crate) fn content_id(&self) -> ViewId {
+        self.child
+    }
+
+    /// Shows `markers` on the vertical scrollbar track, to point out places in the content like
+    /// search results, diagnostics or changed lines. Hovering a marker shows its tooltip, and
+    /// clicking it scrolls to it like any other click on the track.
+    ///
+    /// ```rust
+    /// # use floem::peniko::Color;
+    /// # use floem::views::{scroll, empty, Decorators, ScrollMarker};
+    /// scroll(empty().style(|s| s.height(2000))).markers(|| {
+    ///     vec![
+    ///         ScrollMarker::new(0.25, Color::YELLOW).tooltip("1 match"),
+    ///         ScrollMarker::new(0.8, Color::RED).tooltip("error: unused variable"),
+    ///     ]
+    /// });
+    /// ```
+    ///
+    /// # Reactivity
+    /// The markers will automatically update whenever the signals that `markers` depends on change.
+    pub fn markers(self, markers: impl Fn() -> Vec<ScrollMarker> + 'static) -> Self {
+        let id = self.id();
+        create_effect(move |_| {
+            let markers = markers();
+            id.update_state(ScrollState::Markers(markers));
+        });
+
+        self
+    }
+
     /// Ensures that a specific rectangular area is visible within the scroll view by automatically
     /// scrolling to it if necessary.
     ///
@@ -249,6 +705,31 @@ impl Scroll {
         self
     }
 
+    /// Smoothly scrolls the view to the specified target point over `duration` using `easing`.
+    ///
+    /// If the target changes while the view is still scrolling, the animation continues from the current position.
+    /// Scrolling with the mouse wheel or the scroll bars interrupts the animation.
+    ///
+    /// # Reactivity
+    /// The scroll position will automatically update whenever the target point changes,
+    /// as determined by the `origin` function which will update any time there are changes in the signals that it depends on.
+    pub fn scroll_to_animated(
+        self,
+        origin: impl Fn() -> Option<Point> + 'static,
+        duration: Duration,
+        easing: impl Easing + 'static,
+    ) -> Self {
+        let id = self.id();
+        let transition = Transition::new(duration, easing);
+        create_effect(move |_| {
+            if let Some(origin) = origin() {
+                id.update_state_deferred(ScrollState::ScrollToAnimated(origin, transition.clone()));
+            }
+        });
+
+        self
+    }
+
     /// Scrolls the view to the specified percentage (0-100) of its scrollable content.
     ///
     /// # Reactivity
@@ -288,11 +769,265 @@ impl Scroll {
         self.clamp_child_viewport(app_state, self.child_viewport.with_origin(origin));
     }
 
+    fn do_scroll_to_animated(&mut self, origin: Point, transition: Transition) {
+        self.scroll_animation = Some(ScrollAnimation {
+            from: self.child_viewport.origin(),
+            to: self.clamp_origin(origin),
+            start: clock::now(),
+            transition,
+        });
+        self.id.request_layout();
+    }
+
+    /// Settles the viewport on the nearest snap point, if scroll snapping is enabled.
+    fn settle(&mut self) {
+        self.snap_timer = None;
+        let Some(snap_type) = self.scroll_style.snap_type() else {
+            return;
+        };
+        let origin = self.snap_origin(snap_type);
+        if origin != self.child_viewport.origin() {
+            self.do_scroll_to_animated(origin, Transition::ease_in_out(Duration::from_millis(200)));
+        }
+    }
+
+    /// Settles on a snap point once no wheel events have been received for a while.
+    fn settle_after_delay(&mut self) {
+        if self.scroll_style.snap_type().is_none() {
+            return;
+        }
+        let id = self.id;
+        self.snap_timer = Some(exec_after(SNAP_SETTLE_DELAY, move |token| {
+            id.update_state(ScrollState::Settle(token));
+        }));
+    }
+
+    /// Fades in the bars if they're hidden while the view is idle, and fades them out again once
+    /// the view was idle for a while.
+    fn show_bars(&mut self) {
+        if !self.scroll_style.auto_hide_bars() {
+            return;
+        }
+        if !self.bar_fade.visible {
+            self.bar_fade.set_visible(true, clock::now());
+            self.id.request_paint();
+        }
+        let id = self.id;
+        self.fade_out_timer = Some(exec_after(
+            self.scroll_style.auto_hide_delay(),
+            move |token| id.update_state(ScrollState::FadeOutBars(token)),
+        ));
+    }
+
+    /// Fades out the bars, unless they're held or hovered.
+    fn fade_out_bars(&mut self) {
+        if self.are_bars_held() || self.v_track_hover || self.h_track_hover {
+            self.show_bars();
+            return;
+        }
+        self.fade_out_timer = None;
+        self.bar_fade.set_visible(false, clock::now());
+        self.id.request_paint();
+    }
+
+    /// The opacity that the bars are painted with.
+    fn bar_opacity(&self, app_state: &mut AppState) -> f32 {
+        if !self.scroll_style.auto_hide_bars() {
+            return 1.;
+        }
+        let now = clock::now();
+        if self.bar_fade.is_fading(now) {
+            app_state.schedule_paint(self.id);
+        }
+        self.bar_fade.opacity(now) as f32
+    }
+
+    /// Finds the viewport origin of the nearest snap point on each axis.
+    fn snap_origin(&self, snap_type: SnapType) -> Point {
+        let current = self.child_viewport.origin();
+        let viewport_size = self.child_viewport.size();
+        let mut x: Option<f64> = None;
+        let mut y: Option<f64> = None;
+        for child in self.child.children() {
+            let Some(align) = child.state().borrow().combined_style.get(ScrollSnapAlign) else {
+                continue;
+            };
+            let Some(layout) = child.get_layout() else {
+                continue;
+            };
+            let rect = Rect::from_origin_size(
+                (layout.location.x as f64, layout.location.y as f64),
+                (layout.size.width as f64, layout.size.height as f64),
+            );
+            let target = match align {
+                SnapAlign::Start => rect.origin(),
+                SnapAlign::Center => rect.center() - viewport_size.to_vec2() / 2.,
+                SnapAlign::End => Point::new(
+                    rect.max_x() - viewport_size.width,
+                    rect.max_y() - viewport_size.height,
+                ),
+            };
+            let target = self.clamp_origin(target);
+            if x.map_or(true, |x| {
+                (target.x - current.x).abs() < (x - current.x).abs()
+            }) {
+                x = Some(target.x);
+            }
+            if y.map_or(true, |y| {
+                (target.y - current.y).abs() < (y - current.y).abs()
+            }) {
+                y = Some(target.y);
+            }
+        }
+        let snap = |target: Option<f64>, current: f64, size: f64| match (target, snap_type) {
+            (Some(target), SnapType::Mandatory) => target,
+            (Some(target), SnapType::Proximity)
+                if (target - current).abs() <= size * SNAP_PROXIMITY =>
+            {
+                target
+            }
+            _ => current,
+        };
+        Point::new(
+            snap(x, current.x, viewport_size.width),
+            snap(y, current.y, viewport_size.height),
+        )
+    }
+
+    /// Stops any fling and lets go of a drag of the content.
+    fn stop_kinetic(&mut self) {
+        self.fling = None;
+        self.drag = None;
+    }
+
+    /// Moves the viewport with a drag of the content to the pointer at `pos`.
+    fn drag_to(&mut self, app_state: &mut AppState, pos: Point) {
+        let Some(drag) = &self.drag else {
+            return;
+        };
+        let origin = drag.start_origin - (pos - drag.start_pos);
+        let clamped = self.clamp_origin(origin);
+        if self.scroll_style.overscroll() {
+            let excess = origin - clamped;
+            let size = self.child_viewport.size();
+            self.overscroll = Vec2::new(
+                rubber_band(excess.x, size.width),
+                rubber_band(excess.y, size.height),
+            );
+            app_state.request_paint(self.id);
+        }
+        self.clamp_child_viewport(app_state, self.child_viewport.with_origin(clamped));
+    }
+
+    /// Ends a drag of the content, flinging it if the pointer was moving fast enough.
+    fn release_drag(&mut self) {
+        let Some(drag) = self.drag.take() else {
+            return;
+        };
+        let velocity = drag.velocity();
+        if velocity.hypot() >= MIN_FLING_SPEED {
+            self.fling = Some(velocity);
+        } else if self.overscroll == Vec2::ZERO {
+            self.settle();
+        }
+        self.last_physics_step = None;
+        self.id.request_layout();
+    }
+
+    /// Advances a fling and the relaxing of the overscroll, returning the new origin of the viewport.
+    fn step_physics(&mut self, app_state: &mut AppState, origin: Point) -> Point {
+        let dragging = self.drag.as_ref().is_some_and(|drag| drag.dragging);
+        if self.fling.is_none() && (self.overscroll == Vec2::ZERO || dragging) {
+            self.last_physics_step = None;
+            return origin;
+        }
+        let now = clock::now();
+        let dt = self
+            .last_physics_step
+            .map_or(0., |last| now.duration_since(last).as_secs_f64())
+            .min(0.1);
+        self.last_physics_step = Some(now);
+
+        let mut origin = origin;
+        if let Some(mut velocity) = self.fling {
+            let unclamped = origin + velocity * dt;
+            origin = self.clamp_origin(unclamped);
+            let excess = unclamped - origin;
+            if excess.x != 0. {
+                if self.scroll_style.overscroll() {
+                    self.overscroll.x += velocity.x * OVERSCROLL_IMPACT;
+                }
+                velocity.x = 0.;
+            }
+            if excess.y != 0. {
+                if self.scroll_style.overscroll() {
+                    self.overscroll.y += velocity.y * OVERSCROLL_IMPACT;
+                }
+                velocity.y = 0.;
+            }
+
+            self.fling = decelerate(velocity, self.scroll_style.fling_deceleration(), dt);
+        }
+
+        if self.overscroll != Vec2::ZERO {
+            self.overscroll = relax_overscroll(self.overscroll, self.child_viewport.size(), dt);
+            app_state.request_paint(self.id);
+        }
+
+        if self.fling.is_some() || self.overscroll != Vec2::ZERO {
+            app_state.schedule_layout(self.id);
+        } else {
+            self.last_physics_step = None;
+            self.settle();
+        }
+        origin
+    }
+
+    /// Clamps the origin of the viewport so that it stays within the content.
+    fn clamp_origin(&self, origin: Point) -> Point {
+        let max_x = (self.child_size.width - self.content_rect.width()).max(0.);
+        let max_y = (self.child_size.height - self.content_rect.height()).max(0.);
+        Point::new(origin.x.clamp(0., max_x), origin.y.clamp(0., max_y))
+    }
+
+    /// Scrolls to `rect`, which is relative to the child, with the alignment and animation of `options`.
+    fn scroll_into_view(&mut self, app_state: &mut AppState, rect: Rect, options: ScrollIntoView) {
+        let rect = rect.inflate(options.margin, options.margin);
+        let viewport_size = self.child_viewport.size();
+        let origin = match options.align {
+            ScrollAlign::Nearest => match contained_origin(self.child_viewport, rect) {
+                Some(origin) => origin,
+                None => return,
+            },
+            ScrollAlign::Start => rect.origin(),
+            ScrollAlign::Center => rect.center() - viewport_size.to_vec2() / 2.,
+            ScrollAlign::End => Point::new(
+                rect.max_x() - viewport_size.width,
+                rect.max_y() - viewport_size.height,
+            ),
+        };
+        match options.transition {
+            Some(transition) => self.do_scroll_to_animated(origin, transition),
+            None => {
+                self.scroll_animation = None;
+                self.do_scroll_to(app_state, origin);
+            }
+        }
+    }
+
     /// Pan the smallest distance that makes the target [`Rect`] visible.
     ///
     /// If the target rect is larger than viewport size, we will prioritize
     /// the region of the target closest to its origin.
     pub fn pan_to_visible(&mut self, app_state: &mut AppState, rect: Rect) {
+        if let Some(new_origin) = self.nearest_visible_origin(rect) {
+            self.clamp_child_viewport(app_state, self.child_viewport.with_origin(new_origin));
+        }
+    }
+
+    /// The viewport origin that is the smallest distance away that makes the target [`Rect`] visible,
+    /// or `None` if the target is visible enough already.
+    fn nearest_visible_origin(&self, rect: Rect) -> Option<Point> {
         // If target is larger than viewport
         if rect.width() > self.child_viewport.width()
             || rect.height() > self.child_viewport.height()
@@ -303,7 +1038,7 @@ impl Scroll {
                 && rect.min_y() < self.child_viewport.max_y()
                 && rect.max_y() > self.child_viewport.min_y()
             {
-                return;
+                return None;
             }
         } else {
             // For smaller elements, check if at least 50% is visible
@@ -317,7 +1052,7 @@ impl Scroll {
             let intersection_area = intersection.width() * intersection.height();
             let rect_area = rect.width() * rect.height();
             if intersection_area >= rect_area * 0.5 {
-                return;
+                return None;
             }
         }
 
@@ -369,8 +1104,25 @@ impl Scroll {
 
         let delta_x = if x0.abs() > x1.abs() { x0 } else { x1 };
         let delta_y = if y0.abs() > y1.abs() { y0 } else { y1 };
-        let new_origin = self.child_viewport.origin() + Vec2::new(delta_x, delta_y);
-        self.clamp_child_viewport(app_state, self.child_viewport.with_origin(new_origin));
+        Some(self.child_viewport.origin() + Vec2::new(delta_x, delta_y))
+    }
+
+    fn update_progress(&self) {
+        let max_offset = Vec2::new(
+            (self.child_size.width - self.content_rect.width()).max(0.),
+            (self.child_size.height - self.content_rect.height()).max(0.),
+        );
+        let offset = self.child_viewport.origin().to_vec2();
+        let percent = |offset: f64, max: f64| if max > 0. { offset / max } else { 0. };
+        let progress = ScrollProgress {
+            x: percent(offset.x, max_offset.x),
+            y: percent(offset.y, max_offset.y),
+            offset,
+            max_offset,
+        };
+        if self.progress.with_untracked(|p| *p != progress) {
+            self.progress.set(progress);
+        }
     }
 
     fn update_size(&mut self) {
@@ -413,9 +1165,15 @@ impl Scroll {
             app_state.request_compute_layout_recursive(self.id());
             app_state.request_paint(self.id());
             self.child_viewport = child_viewport;
+            self.update_progress();
+            self.show_bars();
             if let Some(onscroll) = &self.onscroll {
                 onscroll(child_viewport);
             }
+            if let Some(key) = &self.persist_key {
+                let origin = child_viewport.origin();
+                UiStateRegistry::set(key, format!("{} {}", origin.x, origin.y));
+            }
         } else {
             return None;
         }
@@ -450,6 +1208,10 @@ impl Scroll {
     }
 
     fn draw_bars(&self, cx: &mut PaintCx) {
+        let opacity = self.bar_opacity(cx.app_state);
+        if opacity <= 0. {
+            return;
+        }
         let scroll_offset = self.child_viewport.origin().to_vec2();
         let radius = |style: &ScrollTrackStyle, rect: Rect, vertical| {
             if style.rounded() {
@@ -479,14 +1241,19 @@ impl Scroll {
                 let mut bounds = bounds - scroll_offset;
                 bounds.y0 = self.total_rect.y0;
                 bounds.y1 = self.total_rect.y1;
-                cx.fill(&bounds, &color, 0.0);
+                cx.fill(&bounds, &color.multiply_alpha(opacity), 0.0);
+            }
+            for (marker, rect) in self.markers.iter().zip(self.marker_rects(bounds)) {
+                cx.fill(&rect, marker.color.multiply_alpha(opacity), 0.0);
             }
             let edge_width = style.border().0;
             let rect = (bounds - scroll_offset).inset(-edge_width / 2.0);
             let rect = rect.to_rounded_rect(radius(style, rect, true));
-            cx.fill(&rect, &style.color().unwrap_or(HANDLE_COLOR), 0.0);
+            let color = style.color().unwrap_or(HANDLE_COLOR);
+            cx.fill(&rect, &color.multiply_alpha(opacity), 0.0);
             if edge_width > 0.0 {
-                cx.stroke(&rect, &style.border_color(), &Stroke::new(edge_width));
+                let border_color = style.border_color().multiply_alpha(opacity);
+                cx.stroke(&rect, &border_color, &Stroke::new(edge_width));
             }
         }
 
@@ -504,14 +1271,16 @@ impl Scroll {
                 let mut bounds = bounds - scroll_offset;
                 bounds.x0 = self.total_rect.x0;
                 bounds.x1 = self.total_rect.x1;
-                cx.fill(&bounds, &color, 0.0);
+                cx.fill(&bounds, &color.multiply_alpha(opacity), 0.0);
             }
             let edge_width = style.border().0;
             let rect = (bounds - scroll_offset).inset(-edge_width / 2.0);
             let rect = rect.to_rounded_rect(radius(style, rect, false));
-            cx.fill(&rect, &style.color().unwrap_or(HANDLE_COLOR), 0.0);
+            let color = style.color().unwrap_or(HANDLE_COLOR);
+            cx.fill(&rect, &color.multiply_alpha(opacity), 0.0);
             if edge_width > 0.0 {
-                cx.stroke(&rect, &style.border_color(), &Stroke::new(edge_width));
+                let border_color = style.border_color().multiply_alpha(opacity);
+                cx.stroke(&rect, &border_color, &Stroke::new(edge_width));
             }
         }
     }
@@ -589,6 +1358,56 @@ impl Scroll {
         Some(Rect::new(x0, y0, x1, y1))
     }
 
+    /// The rects of the markers on the track of the vertical bar with `bounds`, relative to the
+    /// view.
+    fn marker_rects(&self, bounds: Rect) -> Vec<Rect> {
+        let bounds = bounds - self.child_viewport.origin().to_vec2();
+        let height = self.total_rect.height();
+        self.markers
+            .iter()
+            .map(|marker| {
+                let y0 = (marker.position.clamp(0., 1.) * height - MARKER_HEIGHT / 2.)
+                    .clamp(0., (height - MARKER_HEIGHT).max(0.));
+                Rect::new(bounds.x0, y0, bounds.x1, y0 + MARKER_HEIGHT)
+            })
+            .collect()
+    }
+
+    /// Shows the tooltip of the marker at `pos`, relative to the view, and hides the tooltip of
+    /// the marker that was hovered before.
+    fn update_marker_tip(&mut self, app_state: &mut AppState, pos: Option<Point>) {
+        let rects = match (pos, self.calc_vertical_bar_bounds(app_state)) {
+            (Some(_), Some(bounds)) => self.marker_rects(bounds),
+            _ => Vec::new(),
+        };
+        // later markers are painted over earlier ones
+        let hovered = pos.and_then(|pos| {
+            rects.iter().zip(&self.markers).rposition(|(rect, marker)| {
+                marker.tooltip.is_some() && rect.inflate(0., MARKER_HIT_SLOP).contains(pos)
+            })
+        });
+        if hovered == self.marker_tip.map(|(index, _)| index) {
+            return;
+        }
+        if let Some((_, overlay)) = self.marker_tip.take() {
+            remove_overlay(overlay);
+        }
+        let Some(index) = hovered else {
+            return;
+        };
+        let text = self.markers[index].tooltip.clone().unwrap_or_default();
+        let window_origin = self.id.state().borrow().window_origin;
+        let anchor = Rc::new(Cell::new(rects[index] + window_origin.to_vec2()));
+        let options = PopoverOptions {
+            placement: Placement::Start,
+            ..Default::default()
+        };
+        let overlay = show_popover(PopoverAnchor::Rect(anchor), options, None, move || {
+            label(move || text.clone()).class(TooltipClass).into_any()
+        });
+        self.marker_tip = Some((index, overlay));
+    }
+
     fn click_vertical_bar_area(&mut self, app_state: &mut AppState, pos: Point) {
         let new_y = (pos.y / self.content_rect.height()) * self.child_size.height
             - self.content_rect.height() / 2.0;
@@ -710,7 +1529,10 @@ impl Scroll {
                     + self.computed_child_viewport.origin().to_vec2(),
             );
 
-            self.pan_to_visible(app_state, rect);
+            match app_state.scroll_into_view.clone() {
+                Some(options) => self.scroll_into_view(app_state, rect, options),
+                None => self.pan_to_visible(app_state, rect),
+            }
         }
     }
 
@@ -738,6 +1560,27 @@ impl View for Scroll {
 
     fn update(&mut self, cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(state) = state.downcast::<ScrollState>() {
+            if let ScrollState::Markers(markers) = *state {
+                self.markers = markers;
+                self.update_marker_tip(cx.app_state, None);
+                self.id.request_paint();
+                return;
+            }
+            if let ScrollState::FadeOutBars(token) = *state {
+                if self.fade_out_timer == Some(token) {
+                    self.fade_out_bars();
+                }
+                return;
+            }
+            if !matches!(
+                *state,
+                ScrollState::ScrollToAnimated(..) | ScrollState::Settle(..)
+            ) {
+                self.scroll_animation = None;
+            }
+            if !matches!(*state, ScrollState::Settle(..)) {
+                self.stop_kinetic();
+            }
             match *state {
                 ScrollState::EnsureVisible(rect) => {
                     self.pan_to_visible(cx.app_state, rect);
@@ -748,6 +1591,9 @@ impl View for Scroll {
                 ScrollState::ScrollTo(origin) => {
                     self.do_scroll_to(cx.app_state, origin);
                 }
+                ScrollState::ScrollToAnimated(origin, transition) => {
+                    self.do_scroll_to_animated(origin, transition);
+                }
                 ScrollState::ScrollToPercent(percent) => {
                     let mut child_size = self.child_size;
                     child_size *= percent as f64;
@@ -757,6 +1603,12 @@ impl View for Scroll {
                 ScrollState::ScrollToView(id) => {
                     self.do_scroll_to_view(cx.app_state, id, None);
                 }
+                ScrollState::Settle(token) => {
+                    if self.snap_timer == Some(token) {
+                        self.settle();
+                    }
+                }
+                ScrollState::Markers(_) | ScrollState::FadeOutBars(_) => {}
             }
             self.id.request_layout();
         }
@@ -791,12 +1643,63 @@ impl View for Scroll {
         self.track_hover_style
             .read_style(cx, &track_style.apply_selectors(&[StyleSelector::Hover]));
 
+        let gutter = if self.scroll_style.reserve_gutter() {
+            self.handle_style.thickness().0 + self.scroll_style.vertical_bar_inset().0
+        } else {
+            0.
+        };
+        if gutter != self.gutter {
+            self.gutter = gutter;
+            self.id.request_layout();
+        }
+
         cx.style_view(self.child);
     }
 
+    fn layout(&mut self, cx: &mut LayoutCx) -> NodeId {
+        let changed = self
+            .id
+            .state()
+            .borrow()
+            .requested_changes
+            .contains(ChangeFlags::LAYOUT);
+        let node = recursively_layout_view(self.id, cx);
+        // the node only gets a new style when it's laid out again, so the gutter is only added
+        // to a fresh style
+        if changed && self.gutter > 0. {
+            let taffy = self.id.taffy();
+            let mut taffy = taffy.borrow_mut();
+            if let Ok(style) = taffy.style(node) {
+                let mut style = style.clone();
+                if let LengthPercentage::Length(right) = style.padding.right {
+                    style.padding.right = LengthPercentage::Length(right + self.gutter as f32);
+                    let _ = taffy.set_style(node, style);
+                }
+            }
+        }
+        node
+    }
+
     fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
         self.update_size();
-        self.clamp_child_viewport(cx.app_state_mut(), self.child_viewport);
+        let mut child_viewport = self.child_viewport;
+        let origin = self.step_physics(cx.app_state_mut(), child_viewport.origin());
+        child_viewport = child_viewport.with_origin(origin);
+        if let Some((origin, finished)) = self
+            .scroll_animation
+            .as_ref()
+            .map(|anim| anim.origin(clock::now()))
+        {
+            child_viewport = child_viewport.with_origin(origin);
+            if finished {
+                self.scroll_animation = None;
+            } else {
+                cx.app_state_mut().schedule_layout(self.id);
+            }
+        }
+        self.clamp_child_viewport(cx.app_state_mut(), child_viewport);
+        // the content or the view may have been resized without the viewport moving
+        self.update_progress();
         self.computed_child_viewport = self.child_viewport;
         cx.compute_view_layout(self.child);
         None
@@ -811,6 +1714,13 @@ impl View for Scroll {
         let scroll_offset = self.child_viewport.origin().to_vec2();
         let content_size = self.child_size;
 
+        if matches!(
+            event,
+            Event::PointerDown(_) | Event::PointerWheel(_) | Event::PointerLeave
+        ) {
+            self.update_marker_tip(cx.app_state, None);
+        }
+
         match &event {
             Event::PointerDown(event) => {
                 if !self.scroll_style.hide_bar() && event.button.is_primary() {
@@ -819,6 +1729,8 @@ impl View for Scroll {
                     let pos = event.pos + scroll_offset;
 
                     if self.point_hits_vertical_bar(cx.app_state, pos) {
+                        self.scroll_animation = None;
+                        self.stop_kinetic();
                         if self.point_hits_vertical_handle(cx.app_state, pos) {
                             self.held = BarHeldState::Vertical(
                                 // The bounds must be non-empty, because the point hits the scrollbar.
@@ -840,6 +1752,8 @@ impl View for Scroll {
                         cx.update_active(self.id());
                         return EventPropagation::Stop;
                     } else if self.point_hits_horizontal_bar(cx.app_state, pos) {
+                        self.scroll_animation = None;
+                        self.stop_kinetic();
                         if self.point_hits_horizontal_handle(cx.app_state, pos) {
                             self.held = BarHeldState::Horizontal(
                                 // The bounds must be non-empty, because the point hits the scrollbar.
@@ -862,18 +1776,50 @@ impl View for Scroll {
                         return EventPropagation::Stop;
                     }
                 }
+                if self.scroll_style.kinetic() && event.button.is_primary() {
+                    let now = clock::now();
+                    self.scroll_animation = None;
+                    self.fling = None;
+                    let mut samples = VecDeque::new();
+                    samples.push_back((now, event.pos));
+                    self.drag = Some(DragScroll {
+                        start_pos: event.pos,
+                        start_origin: self.child_viewport.origin(),
+                        dragging: false,
+                        samples,
+                    });
+                }
             }
             Event::PointerUp(_event) => {
                 if self.are_bars_held() {
                     self.held = BarHeldState::None;
+                    self.show_bars();
+                    self.settle();
                     // Force a repaint.
                     cx.app_state.request_paint(self.id());
                 }
+                if self.drag.as_ref().is_some_and(|drag| drag.dragging) {
+                    self.release_drag();
+                    // The press turned into a drag, so it isn't a click on the content.
+                    return EventPropagation::Stop;
+                }
+                self.drag = None;
+            }
+            Event::PointerMove(event) if self.drag.as_ref().is_some_and(|drag| drag.dragging) => {
+                if let Some(drag) = self.drag.as_mut() {
+                    drag.push_sample(clock::now(), event.pos);
+                }
+                self.drag_to(cx.app_state, event.pos);
+                return EventPropagation::Stop;
             }
             Event::PointerMove(event) => {
                 if !self.scroll_style.hide_bar() {
                     let pos = event.pos + scroll_offset;
                     self.update_hover_states(cx.app_state, event.pos);
+                    self.show_bars();
+                    if !self.are_bars_held() {
+                        self.update_marker_tip(cx.app_state, Some(event.pos));
+                    }
 
                     if self.are_bars_held() {
                         match self.held {
@@ -921,6 +1867,23 @@ impl View for Scroll {
         cx: &mut crate::context::EventCx,
         event: &Event,
     ) -> EventPropagation {
+        // A press only becomes a drag of the content here, after the children had the chance to
+        // claim it for a gesture of their own.
+        if let (Event::PointerMove(event), Some(drag)) = (&event, self.drag.as_mut()) {
+            if gestures::pointer_claimed_by_other(self.id) {
+                self.drag = None;
+                return EventPropagation::Continue;
+            }
+            drag.push_sample(clock::now(), event.pos);
+            if (event.pos - drag.start_pos).hypot() > gestures::DRAG_SLOP {
+                drag.dragging = true;
+                // Move on from the pressed point so the drag doesn't jump by the slop.
+                drag.start_pos = event.pos;
+                gestures::claim_pointer(self.id);
+                cx.update_active(self.id);
+                return EventPropagation::Stop;
+            }
+        }
         if let Event::PointerWheel(pointer_event) = &event {
             if let Some(listener) = event.listener() {
                 if self
@@ -940,7 +1903,12 @@ impl View for Scroll {
             } else {
                 delta
             };
+            self.scroll_animation = None;
+            self.stop_kinetic();
             let any_change = self.clamp_child_viewport(cx.app_state, self.child_viewport + delta);
+            if any_change.is_some() {
+                self.settle_after_delay();
+            }
 
             // Check if the scroll bars now hover
             self.update_hover_states(cx.app_state, pointer_event.pos);
@@ -969,7 +1937,10 @@ impl View for Scroll {
                 cx.clip(&self.total_rect);
             }
         }
-        cx.offset((-self.child_viewport.x0, -self.child_viewport.y0));
+        cx.offset((
+            -self.child_viewport.x0 - self.overscroll.x,
+            -self.child_viewport.y0 - self.overscroll.y,
+        ));
         cx.paint_view(self.child);
         cx.restore();
 
@@ -1010,6 +1981,34 @@ impl ScrollCustomStyle {
         self
     }
 
+    /// Enables scroll snapping. After scrolling, the viewport settles on the children of the
+    /// content that have a snap alignment set with [`Style::snap_align`].
+    pub fn snap_type(mut self, snap_type: SnapType) -> Self {
+        self = Self(self.0.set(ScrollSnapType, Some(snap_type)));
+        self
+    }
+
+    /// Lets the content be dragged with the pointer, like on a touchscreen. Releasing a drag
+    /// while the pointer is moving flings the content, which keeps scrolling and slows down
+    /// with [`fling_deceleration`](Self::fling_deceleration).
+    pub fn kinetic(mut self, kinetic: impl Into<bool>) -> Self {
+        self = Self(self.0.set(KineticScrolling, kinetic));
+        self
+    }
+
+    /// Sets the deceleration of a fling in pixels per second squared.
+    pub fn fling_deceleration(mut self, deceleration: f64) -> Self {
+        self = Self(self.0.set(FlingDeceleration, deceleration));
+        self
+    }
+
+    /// Lets the content be pulled past its edges while it is dragged, or overshoot them when it
+    /// is flung, and bounce back afterwards.
+    pub fn overscroll(mut self, overscroll: impl Into<bool>) -> Self {
+        self = Self(self.0.set(Overscroll, overscroll));
+        self
+    }
+
     /// Conditionally configures the scroll view to clip the overflow of the content.
     pub fn overflow_clip(mut self, clip: bool) -> Self {
         self = Self(self.0.set(OverflowClip, clip));
@@ -1106,6 +2105,28 @@ impl ScrollCustomStyle {
         self
     }
 
+    /// Hides the scroll bars while the view is idle, like overlay scroll bars on macOS. They fade
+    /// in while the view is scrolled or hovered, and fade out after
+    /// [`auto_hide_delay`](Self::auto_hide_delay).
+    pub fn auto_hide_bars(mut self, auto_hide: impl Into<bool>) -> Self {
+        self = Self(self.0.set(AutoHideBars, auto_hide));
+        self
+    }
+
+    /// Sets how long automatically hidden scroll bars stay visible after the view was last
+    /// scrolled or hovered.
+    pub fn auto_hide_delay(mut self, delay: Duration) -> Self {
+        self = Self(self.0.set(AutoHideDelay, delay));
+        self
+    }
+
+    /// Sets whether space is always reserved for the vertical scroll bar next to the content,
+    /// instead of the bar being drawn over the content.
+    pub fn reserve_gutter(mut self, reserve: impl Into<bool>) -> Self {
+        self = Self(self.0.set(ReserveGutter, reserve));
+        self
+    }
+
     /// Sets whether the pointer wheel events should be propagated.
     pub fn propagate_pointer_wheel(mut self, propagate: impl Into<bool>) -> Self {
         self = Self(self.0.set(PropagatePointerWheel, propagate));
@@ -1119,6 +2140,34 @@ impl ScrollCustomStyle {
     }
 }
 
+/// The origin of `viewport` that is the smallest distance away that shows all of `target`, or
+/// `None` if it's shown already.
+///
+/// On an axis where the target is larger than the viewport, the viewport is moved to be inside
+/// of the target, with the start of the target at its start if it has to move.
+fn contained_origin(viewport: Rect, target: Rect) -> Option<Point> {
+    fn delta(view_min: f64, view_max: f64, min: f64, max: f64) -> f64 {
+        if max - min <= view_max - view_min {
+            if min < view_min {
+                min - view_min
+            } else if max > view_max {
+                max - view_max
+            } else {
+                0.0
+            }
+        } else if view_min < min || view_max > max {
+            min - view_min
+        } else {
+            0.0
+        }
+    }
+    let delta = Vec2::new(
+        delta(viewport.x0, viewport.x1, target.x0, target.x1),
+        delta(viewport.y0, viewport.y1, target.y0, target.y1),
+    );
+    (delta != Vec2::ZERO).then(|| viewport.origin() + delta)
+}
+
 /// A trait that adds a `scroll` method to any type that implements `IntoView`.
 pub trait ScrollExt {
     /// Wrap the view in a scroll view.
@@ -1130,3 +2179,102 @@ impl<T: IntoView + 'static> ScrollExt for T {
         scroll(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use peniko::kurbo::{Point, Rect, Size, Vec2};
+
+    use super::{contained_origin, decelerate, relax_overscroll, rubber_band};
+
+    const VIEWPORT: Rect = Rect::new(0.0, 100.0, 100.0, 200.0);
+
+    #[test]
+    fn shown_targets_stay() {
+        assert_eq!(
+            contained_origin(VIEWPORT, Rect::new(10.0, 100.0, 90.0, 200.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn partly_shown_targets_are_scrolled_in() {
+        // more than half of the target is shown, and it's still scrolled to show all of it
+        let target = Rect::new(0.0, 180.0, 10.0, 210.0);
+        assert_eq!(
+            contained_origin(VIEWPORT, target),
+            Some(Point::new(0.0, 110.0))
+        );
+        let target = Rect::new(0.0, 90.0, 10.0, 120.0);
+        assert_eq!(
+            contained_origin(VIEWPORT, target),
+            Some(Point::new(0.0, 90.0))
+        );
+    }
+
+    #[test]
+    fn margins_are_kept() {
+        // a target that is shown, inflated by a margin of 10
+        let target = Rect::new(20.0, 170.0, 30.0, 195.0).inflate(10.0, 10.0);
+        assert_eq!(
+            contained_origin(VIEWPORT, target),
+            Some(Point::new(0.0, 105.0))
+        );
+    }
+
+    #[test]
+    fn large_targets_fill_the_viewport() {
+        let target = Rect::new(0.0, 150.0, 10.0, 400.0);
+        assert_eq!(
+            contained_origin(VIEWPORT, target),
+            Some(Point::new(0.0, 150.0))
+        );
+        // the viewport is inside of the target already
+        let target = Rect::new(0.0, 50.0, 100.0, 400.0);
+        assert_eq!(contained_origin(VIEWPORT, target), None);
+    }
+
+    #[test]
+    fn fling_stops_after_its_deceleration_distance() {
+        let (deceleration, dt) = (2500., 1. / 120.);
+        let mut velocity = Some(Vec2::new(600., 800.));
+        let mut moved = Vec2::ZERO;
+        let mut steps = 0;
+        while let Some(v) = velocity {
+            moved += v * dt;
+            velocity = decelerate(v, deceleration, dt);
+            steps += 1;
+        }
+        // v² / 2a, give or take the half step the velocity moves before it's slowed down
+        let speed: f64 = 1000.;
+        let distance = speed * speed / (2. * deceleration);
+        assert!((moved.hypot() - distance).abs() <= speed * dt / 2. + 1e-9);
+        // it keeps its direction, and stops after v / a seconds
+        assert!((moved.x / moved.y - 0.75).abs() < 1e-9);
+        assert_eq!(steps, (speed / deceleration / dt).round() as usize);
+    }
+
+    #[test]
+    fn overscroll_is_limited_to_a_quarter_of_the_viewport() {
+        let size = Size::new(100., 200.);
+        let clamped = relax_overscroll(Vec2::new(500., -500.), size, 0.);
+        assert_eq!(clamped, Vec2::new(25., -50.));
+        // pulling past the edge gets harder, and never reaches the size of the viewport
+        assert!(rubber_band(50., 100.) < 50.);
+        assert!(rubber_band(10_000., 100.) < 100.);
+        assert_eq!(rubber_band(-50., 100.), -rubber_band(50., 100.));
+    }
+
+    #[test]
+    fn overscroll_relaxes_to_zero() {
+        let size = Size::new(100., 200.);
+        let mut overscroll = Vec2::new(25., -50.);
+        let mut elapsed = 0.;
+        while overscroll != Vec2::ZERO {
+            let relaxed = relax_overscroll(overscroll, size, 1. / 60.);
+            assert!(relaxed.hypot() < overscroll.hypot());
+            overscroll = relaxed;
+            elapsed += 1. / 60.;
+            assert!(elapsed < 1., "the overscroll hasn't relaxed after a second");
+        }
+    }
+}
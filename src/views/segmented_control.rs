@@ -0,0 +1,145 @@
+#![deny(missing_docs)]
+//! Segmented controls, rows of buttons where one or several options are selected.
+
+use std::{collections::HashSet, fmt::Display, hash::Hash};
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith};
+use peniko::kurbo::Rect;
+
+use crate::{
+    style::{InsetLeft, InsetTop, Transition},
+    style_class,
+    unit::DurationUnitExt,
+    view::IntoView,
+    views::{empty, label, stack_from_iter, Decorators, Stack},
+};
+
+style_class!(
+    /// The style class that is applied to segmented controls.
+    pub SegmentedControlClass
+);
+
+style_class!(
+    /// The style class that is applied to every segment.
+    pub SegmentClass
+);
+
+style_class!(
+    /// The style class that is applied to selected segments, after [`SegmentClass`].
+    pub SelectedSegmentClass
+);
+
+style_class!(
+    /// The style class that is applied to the highlight that slides behind the selected segment
+    /// of a [`segmented_control`], and to the selected segments of a [`segmented_control_multi`].
+    pub SegmentIndicatorClass
+);
+
+/// How long the indicator takes to slide to the selected segment.
+const SLIDE_DURATION: u64 = 200;
+
+/// A segmented control with a segment for each of the `options`, where clicking a segment selects
+/// its option.
+///
+/// The selected segment is highlighted by an indicator that slides between the segments.
+///
+/// ```
+/// # use floem::reactive::RwSignal;
+/// # use floem::views::segmented_control;
+/// let view = RwSignal::new("Day");
+/// segmented_control(["Day", "Week", "Month"], view);
+/// ```
+pub fn segmented_control<T>(options: impl IntoIterator<Item = T>, selection: RwSignal<T>) -> Stack
+where
+    T: Clone + PartialEq + Display + 'static,
+{
+    let options = options.into_iter().collect::<Vec<_>>();
+    // the rects of the segments, relative to the control
+    let rects = RwSignal::new(vec![Rect::ZERO; options.len()]);
+    // the indicator only slides once the user changed the selection, not into its first place
+    let slide = RwSignal::new(false);
+
+    let indicator = {
+        let options = options.clone();
+        empty().class(SegmentIndicatorClass).style(move |s| {
+            let selected =
+                selection.with(|selection| options.iter().position(|option| option == selection));
+            let rect = selected.and_then(|index| rects.with(|rects| rects.get(index).copied()));
+            let s = match rect {
+                Some(rect) => s
+                    .absolute()
+                    .inset_left(rect.x0)
+                    .inset_top(rect.y0)
+                    .width(rect.width())
+                    .height(rect.height()),
+                None => s.hide(),
+            };
+            s.apply_if(slide.get(), |s| {
+                let transition = Transition::ease_in_out(SLIDE_DURATION.millis());
+                s.transition(InsetLeft, transition.clone())
+                    .transition(InsetTop, transition.clone())
+                    .transition_width(transition.clone())
+                    .transition_height(transition)
+            })
+        })
+    };
+
+    let segments = options.into_iter().enumerate().map(move |(index, option)| {
+        let text = option.to_string();
+        let is_selected = {
+            let option = option.clone();
+            move || selection.with(|selection| *selection == option)
+        };
+        label(move || text.clone())
+            .class(SegmentClass)
+            .class_if(is_selected, SelectedSegmentClass)
+            .keyboard_navigable()
+            .on_click_stop(move |_| {
+                slide.set(true);
+                selection.set(option.clone());
+            })
+            .on_resize(move |rect| {
+                rects.update(|rects| {
+                    if let Some(segment) = rects.get_mut(index) {
+                        *segment = rect;
+                    }
+                })
+            })
+            .into_any()
+    });
+
+    stack_from_iter(std::iter::once(indicator.into_any()).chain(segments))
+        .class(SegmentedControlClass)
+}
+
+/// A segmented control with a segment for each of the `options`, where clicking a segment adds
+/// its option to the selection, or removes it.
+pub fn segmented_control_multi<T>(
+    options: impl IntoIterator<Item = T>,
+    selection: RwSignal<HashSet<T>>,
+) -> Stack
+where
+    T: Clone + Eq + Hash + Display + 'static,
+{
+    let segments = options.into_iter().map(move |option| {
+        let text = option.to_string();
+        let is_selected = {
+            let option = option.clone();
+            move || selection.with(|selection| selection.contains(&option))
+        };
+        label(move || text.clone())
+            .class(SegmentClass)
+            .class_if(is_selected.clone(), SelectedSegmentClass)
+            .class_if(is_selected, SegmentIndicatorClass)
+            .keyboard_navigable()
+            .on_click_stop(move |_| {
+                selection.update(|selection| {
+                    if !selection.remove(&option) {
+                        selection.insert(option.clone());
+                    }
+                })
+            })
+    });
+
+    stack_from_iter(segments).class(SegmentedControlClass)
+}
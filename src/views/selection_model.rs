@@ -0,0 +1,335 @@
+#![deny(missing_docs)]
+//! A selection model that lists, tables and trees can share for selecting their items by key.
+//!
+//! ```
+//! # use floem::reactive::{RwSignal, SignalGet};
+//! # use floem::views::{dyn_stack, label, SelectionMode, SelectionModel};
+//! let files = RwSignal::new(vec!["a.rs", "b.rs", "c.rs"]);
+//! let selection = SelectionModel::new(SelectionMode::Multi, move || files.get());
+//! dyn_stack(
+//!     move || files.get(),
+//!     |file| *file,
+//!     move |file| selection.item(file, label(move || file)),
+//! );
+//! ```
+
+use std::{collections::HashSet, hash::Hash};
+
+use floem_reactive::{create_memo, Memo, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use floem_winit::keyboard::{Key, NamedKey};
+
+use crate::{
+    event::{Event, EventListener, EventPropagation},
+    keyboard::Modifiers,
+    style_class,
+    view::IntoView,
+    views::Decorators,
+};
+
+style_class!(
+    /// The style class that is applied to the selected items of a [`SelectionModel`].
+    pub SelectedItemClass
+);
+
+style_class!(
+    /// The style class that is applied to the item of a [`SelectionModel`] that has the focus.
+    pub FocusedItemClass
+);
+
+style_class!(
+    /// The style class that is applied to the item of a [`SelectionModel`] under the pointer.
+    pub HoveredItemClass
+);
+
+/// How items are selected with a [`SelectionModel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// One item at most is selected. Clicking an item or moving the focus to it selects it.
+    Single,
+    /// Clicking an item selects only that item, clicking it with `Ctrl` (`Cmd` on macOS) adds it
+    /// to the selection or removes it, and clicking it with `Shift` selects the items between it
+    /// and the item that was clicked before.
+    Multi,
+    /// Clicking an item adds it to the selection or removes it, like a checkbox, and clicking it
+    /// with `Shift` adds the items between it and the item that was clicked before.
+    Checkbox,
+}
+
+/// The selected keys of the items of a list, table or tree, see [`SelectionModel::item`].
+pub struct SelectionModel<K: 'static> {
+    mode: SelectionMode,
+    keys: Memo<Vec<K>>,
+    selected: RwSignal<HashSet<K>>,
+    /// The item that ranges are selected from.
+    anchor: RwSignal<Option<K>>,
+    focused: RwSignal<Option<K>>,
+    hovered: RwSignal<Option<K>>,
+}
+
+impl<K> Clone for SelectionModel<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for SelectionModel<K> {}
+
+impl<K: Clone + Eq + Hash + 'static> SelectionModel<K> {
+    /// A selection model for items with the `keys` in the order they're shown, which is the order
+    /// that ranges are selected in.
+    pub fn new(mode: SelectionMode, keys: impl Fn() -> Vec<K> + 'static) -> Self {
+        let keys = create_memo(move |_| keys());
+        let selected = RwSignal::new(HashSet::new());
+        Self {
+            mode,
+            keys,
+            selected,
+            anchor: RwSignal::new(None),
+            focused: RwSignal::new(None),
+            hovered: RwSignal::new(None),
+        }
+    }
+
+    /// The signal with the keys of the selected items, which can also be set to change the
+    /// selection.
+    pub fn selected(&self) -> RwSignal<HashSet<K>> {
+        self.selected
+    }
+
+    /// The signal with the key of the item that has the focus.
+    pub fn focused(&self) -> RwSignal<Option<K>> {
+        self.focused
+    }
+
+    /// The signal with the key of the item that is under the pointer.
+    pub fn hovered(&self) -> RwSignal<Option<K>> {
+        self.hovered
+    }
+
+    /// Whether the item with `key` is selected.
+    pub fn is_selected(&self, key: &K) -> bool {
+        self.selected.with(|selected| selected.contains(key))
+    }
+
+    /// Selects the item with `key` like a click on it with `modifiers` does, see
+    /// [`SelectionMode`].
+    pub fn click(&self, key: K, modifiers: Modifiers) {
+        let toggle = modifiers.contains(toggle_modifier());
+        let range = modifiers.contains(Modifiers::SHIFT);
+        let keys = self.keys.get_untracked();
+        let mut anchor = self.anchor.get_untracked();
+        self.selected.update(|selected| {
+            apply_click(self.mode, selected, &mut anchor, &keys, key, toggle, range)
+        });
+        self.anchor.set(anchor);
+    }
+
+    /// Selects all the items, unless only one item can be selected.
+    pub fn select_all(&self) {
+        if self.mode != SelectionMode::Single {
+            self.selected
+                .set(self.keys.get_untracked().into_iter().collect());
+        }
+    }
+
+    /// Deselects all the items.
+    pub fn clear(&self) {
+        self.selected.update(HashSet::clear);
+        self.anchor.set(None);
+    }
+
+    /// Makes `view` the item with `key`, which is selected by clicking it or pressing `Space`
+    /// while it has the focus.
+    ///
+    /// While the item is selected it has the [`SelectedItemClass`], while it has the focus it has
+    /// the [`FocusedItemClass`], and while it's under the pointer it has the
+    /// [`HoveredItemClass`].
+    pub fn item<V: IntoView + 'static>(&self, key: K, view: V) -> V::V {
+        let model = *self;
+        let is = move |signal: RwSignal<Option<K>>, key: K| {
+            move || signal.with(|current| current.as_ref() == Some(&key))
+        };
+        let selected = key.clone();
+        let focused = key.clone();
+        let pressed = key.clone();
+        let focus_gained = key.clone();
+        let focus_lost = key.clone();
+        let entered = key.clone();
+        let left = key.clone();
+        view.into_view()
+            .class_if(move || model.is_selected(&selected), SelectedItemClass)
+            .class_if(is(model.focused, focused), FocusedItemClass)
+            .class_if(is(model.hovered, key), HoveredItemClass)
+            .keyboard_navigable()
+            .on_event(EventListener::PointerDown, move |event| {
+                if let Event::PointerDown(event) = event {
+                    if event.button.is_primary() {
+                        model.click(pressed.clone(), event.modifiers);
+                    }
+                }
+                EventPropagation::Continue
+            })
+            .on_event(EventListener::KeyDown, move |event| match event {
+                Event::KeyDown(event) if event.key.logical_key == Key::Named(NamedKey::Space) => {
+                    if let Some(key) = model.focused.get_untracked() {
+                        model.click(key, event.modifiers);
+                    }
+                    EventPropagation::Stop
+                }
+                _ => EventPropagation::Continue,
+            })
+            .on_event_cont(EventListener::FocusGained, move |_| {
+                model.focused.set(Some(focus_gained.clone()));
+                // the selection follows the focus when only one item can be selected
+                if model.mode == SelectionMode::Single {
+                    model.click(focus_gained.clone(), Modifiers::empty());
+                }
+            })
+            .on_event_cont(EventListener::FocusLost, move |_| {
+                if model
+                    .focused
+                    .with_untracked(|focused| focused.as_ref() == Some(&focus_lost))
+                {
+                    model.focused.set(None);
+                }
+            })
+            .on_event_cont(EventListener::PointerEnter, move |_| {
+                model.hovered.set(Some(entered.clone()));
+            })
+            .on_event_cont(EventListener::PointerLeave, move |_| {
+                if model
+                    .hovered
+                    .with_untracked(|hovered| hovered.as_ref() == Some(&left))
+                {
+                    model.hovered.set(None);
+                }
+            })
+    }
+}
+
+/// The modifier that adds items to a selection or removes them.
+fn toggle_modifier() -> Modifiers {
+    if cfg!(target_os = "macos") {
+        Modifiers::META
+    } else {
+        Modifiers::CONTROL
+    }
+}
+
+/// Changes `selected` for a click on `key` in `mode`, where `keys` are the keys of all the items
+/// in order and `anchor` is the item that ranges are selected from.
+fn apply_click<K: Clone + Eq + Hash>(
+    mode: SelectionMode,
+    selected: &mut HashSet<K>,
+    anchor: &mut Option<K>,
+    keys: &[K],
+    key: K,
+    toggle: bool,
+    range: bool,
+) {
+    if mode == SelectionMode::Single {
+        selected.clear();
+        selected.insert(key.clone());
+        *anchor = Some(key);
+        return;
+    }
+    let start = anchor
+        .as_ref()
+        .and_then(|anchor| keys.iter().position(|k| k == anchor));
+    let end = keys.iter().position(|k| *k == key);
+    if let (true, Some(start), Some(end)) = (range, start, end) {
+        // a range replaces the selection, unless it's added to it
+        if mode == SelectionMode::Multi && !toggle {
+            selected.clear();
+        }
+        selected.extend(keys[start.min(end)..=start.max(end)].iter().cloned());
+        // the anchor stays, so the range can be changed by clicking another item
+        return;
+    }
+    if mode == SelectionMode::Checkbox || toggle {
+        if !selected.remove(&key) {
+            selected.insert(key.clone());
+        }
+    } else {
+        selected.clear();
+        selected.insert(key.clone());
+    }
+    *anchor = Some(key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(
+        mode: SelectionMode,
+        selected: &mut HashSet<u32>,
+        anchor: &mut Option<u32>,
+        key: u32,
+        toggle: bool,
+        range: bool,
+    ) -> Vec<u32> {
+        apply_click(mode, selected, anchor, &[1, 2, 3, 4, 5], key, toggle, range);
+        let mut keys = selected.iter().copied().collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn multi_selection_with_modifiers() {
+        let (mut selected, mut anchor) = (HashSet::new(), None);
+        let mode = SelectionMode::Multi;
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 2, false, false),
+            [2]
+        );
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 4, false, true),
+            [2, 3, 4]
+        );
+        // the range follows the last click from the same anchor
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 1, false, true),
+            [1, 2]
+        );
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 5, true, false),
+            [1, 2, 5]
+        );
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 2, true, false),
+            [1, 5]
+        );
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 3, false, false),
+            [3]
+        );
+    }
+
+    #[test]
+    fn single_and_checkbox_selection() {
+        let (mut selected, mut anchor) = (HashSet::new(), None);
+        let mode = SelectionMode::Single;
+        assert_eq!(click(mode, &mut selected, &mut anchor, 2, true, false), [2]);
+        assert_eq!(click(mode, &mut selected, &mut anchor, 4, false, true), [4]);
+
+        let (mut selected, mut anchor) = (HashSet::new(), None);
+        let mode = SelectionMode::Checkbox;
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 2, false, false),
+            [2]
+        );
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 4, false, false),
+            [2, 4]
+        );
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 2, false, false),
+            [4]
+        );
+        assert_eq!(
+            click(mode, &mut selected, &mut anchor, 5, false, true),
+            [2, 3, 4, 5]
+        );
+    }
+}
@@ -1,16 +1,19 @@
-//! A toggle button widget. An example can be found in widget-gallery/button in the floem examples.
+//! A slider widget. An example can be found in widget-gallery/slider in the floem examples.
 
 use floem_reactive::{create_updater, SignalGet, SignalUpdate};
 use floem_winit::keyboard::{Key, NamedKey};
-use peniko::kurbo::{Circle, Point, RoundedRect};
+use peniko::kurbo::{Circle, Point, Rect, RoundedRect};
 use peniko::{Brush, Color};
 
+use crate::text::{Attrs, AttrsList, TextLayout};
 use crate::unit::Pct;
 use crate::{
     event::EventPropagation,
     id::ViewId,
     prop, prop_extractor,
-    style::{Background, BorderRadius, CustomStylable, Foreground, Height, Style},
+    style::{
+        Background, BorderRadius, CustomStylable, FontSize, Foreground, Height, Style, TextColor,
+    },
     style_class,
     unit::{PxPct, PxPctAuto},
     view::View,
@@ -24,10 +27,36 @@ pub fn slider<P: Into<Pct>>(percent: impl Fn() -> P + 'static) -> Slider {
     Slider::new(percent)
 }
 
+/// Creates a new range [Slider] with two handles that keep the signals up to date.
+/// See [Slider::new_range_rw] for more documentation
+pub fn range_slider(
+    low: impl SignalGet<Pct> + SignalUpdate<Pct> + Copy + 'static,
+    high: impl SignalGet<Pct> + SignalUpdate<Pct> + Copy + 'static,
+) -> Slider {
+    Slider::new_range_rw(low, high)
+}
+
 enum SliderUpdate {
     Percent(f64),
+    High(f64),
+}
+
+/// The handle of a [Slider] that is moved by the pointer and the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Thumb {
+    Low,
+    High,
 }
 
+/// The percent that the arrow keys move the handle by when the slider has no step.
+const ARROW_STEP: f64 = 10.;
+
+/// The percent that the Page Up and Page Down keys move the handle by.
+const PAGE_STEP: f64 = 25.;
+
+/// The gap between the ticks and their labels.
+const TICK_LABEL_GAP: f64 = 2.;
+
 prop!(pub EdgeAlign: bool {} = false);
 prop!(pub HandleRadius: PxPct {} = PxPct::Pct(98.));
 
@@ -36,11 +65,14 @@ prop_extractor! {
         foreground: Foreground,
         handle_radius: HandleRadius,
         edge_align: EdgeAlign,
+        color: TextColor,
+        font_size: FontSize,
     }
 }
 style_class!(pub SliderClass);
 style_class!(pub BarClass);
 style_class!(pub AccentBarClass);
+style_class!(pub TickClass);
 
 prop_extractor! {
     BarStyle {
@@ -58,9 +90,14 @@ prop_extractor! {
 /// The slider is composed of four parts. The main view, the background bar, an accent bar and a handle.
 /// The background bar is separate from the main view because it is shortened when [`EdgeAlign`] is set to false;
 ///
+/// A slider created with [`Slider::new_range`] has a second handle, and the accent bar spans the range between the handles.
+/// The slider can snap to a [`Slider::step`] and show [`Slider::ticks`] with labels, and is made vertical with [`Slider::vertical`].
+///
 /// **Responding to events**:
-/// You can respond to events by calling the [`Slider::on_change_pct`], and [`Slider::on_change_px`] methods on [`Slider`] and passing in a callback. Both of these callbacks are called whenever a change is effected by either clicking or by the arrow keys.
-/// These callbacks will not be called on reactive updates, only on a mouse event or by using the arrow keys.
+/// You can respond to events by calling the [`Slider::on_change_pct`], and [`Slider::on_change_px`] methods on [`Slider`] and passing in a callback. Both of these callbacks are called whenever a change is effected by either clicking or by the keyboard.
+/// These callbacks will not be called on reactive updates, only on a mouse event or by using the keyboard.
+///
+/// The arrow keys move the handle by the step, or by 10% if there is none, Page Up and Page Down move it by 25%, and Home and End move it to the ends.
 ///
 /// You can also disable event handling [`Decorators::disabled`]. If you want to use this slider as a progress bar this may be useful.
 ///
@@ -87,14 +124,28 @@ pub struct Slider {
     id: ViewId,
     onchangepx: Option<Box<dyn Fn(f64)>>,
     onchangepct: Option<Box<dyn Fn(Pct)>>,
+    onchangerange: Option<Box<dyn Fn(Pct, Pct)>>,
     held: bool,
     percent: f64,
     prev_percent: f64,
+    /// The value of the second handle of a range slider.
+    high: Option<f64>,
+    prev_high: Option<f64>,
+    /// The handle that was last moved.
+    thumb: Thumb,
+    step: Option<f64>,
+    show_ticks: bool,
+    tick_label: Option<Box<dyn Fn(Pct) -> String>>,
+    tick_labels: Vec<(f64, TextLayout)>,
+    vertical: bool,
     base_bar_style: BarStyle,
     accent_bar_style: BarStyle,
+    tick_style: BarStyle,
     handle: Circle,
+    high_handle: Option<Circle>,
     base_bar: RoundedRect,
     accent_bar: RoundedRect,
+    ticks: Vec<Rect>,
     size: taffy::prelude::Size<f32>,
     style: SliderStyle,
 }
@@ -108,6 +159,7 @@ impl View for Slider {
         if let Ok(update) = state.downcast::<SliderUpdate>() {
             match *update {
                 SliderUpdate::Percent(percent) => self.percent = percent,
+                SliderUpdate::High(high) => self.high = Some(high),
             }
             self.id.request_layout();
         }
@@ -123,7 +175,9 @@ impl View for Slider {
                 cx.update_active(self.id());
                 self.id.request_layout();
                 self.held = true;
-                self.percent = event.pos.x / self.size.width as f64 * 100.;
+                let percent = self.pointer_percent(event.pos);
+                self.thumb = self.nearest_thumb(percent);
+                self.set_thumb_percent(percent);
                 true
             }
             crate::event::Event::PointerUp(event) => {
@@ -132,7 +186,7 @@ impl View for Slider {
                 // set the state based on the position of the slider
                 let changed = self.held;
                 if self.held {
-                    self.percent = event.pos.x / self.size.width as f64 * 100.;
+                    self.set_thumb_percent(self.pointer_percent(event.pos));
                     self.update_restrict_position();
                 }
                 self.held = false;
@@ -141,7 +195,7 @@ impl View for Slider {
             crate::event::Event::PointerMove(event) => {
                 self.id.request_layout();
                 if self.held {
-                    self.percent = event.pos.x / self.size.width as f64 * 100.;
+                    self.set_thumb_percent(self.pointer_percent(event.pos));
                     true
                 } else {
                     false
@@ -152,30 +206,42 @@ impl View for Slider {
                 false
             }
             crate::event::Event::KeyDown(event) => {
-                if event.key.logical_key == Key::Named(NamedKey::ArrowLeft) {
-                    self.id.request_layout();
-                    self.percent -= 10.;
-                    true
-                } else if event.key.logical_key == Key::Named(NamedKey::ArrowRight) {
+                let arrow_step = self.step.unwrap_or(ARROW_STEP);
+                let current = self.thumb_percent();
+                let percent = match event.key.logical_key {
+                    Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowDown) => {
+                        Some(current - arrow_step)
+                    }
+                    Key::Named(NamedKey::ArrowRight | NamedKey::ArrowUp) => {
+                        Some(current + arrow_step)
+                    }
+                    Key::Named(NamedKey::PageDown) => Some(current - PAGE_STEP.max(arrow_step)),
+                    Key::Named(NamedKey::PageUp) => Some(current + PAGE_STEP.max(arrow_step)),
+                    Key::Named(NamedKey::Home) => Some(0.),
+                    Key::Named(NamedKey::End) => Some(100.),
+                    _ => None,
+                };
+                if let Some(percent) = percent {
                     self.id.request_layout();
-                    self.percent += 10.;
-                    true
-                } else {
-                    false
+                    self.set_thumb_percent(percent);
                 }
+                percent.is_some()
             }
             _ => false,
         };
 
         self.update_restrict_position();
 
-        if pos_changed && self.percent != self.prev_percent {
+        if pos_changed && (self.percent != self.prev_percent || self.high != self.prev_high) {
             if let Some(onchangepx) = &self.onchangepx {
                 onchangepx(self.handle_center());
             }
             if let Some(onchangepct) = &self.onchangepct {
                 onchangepct(Pct(self.percent))
             }
+            if let (Some(onchangerange), Some(high)) = (&self.onchangerange, self.high) {
+                onchangerange(Pct(self.percent), Pct(high))
+            }
         }
 
         EventPropagation::Continue
@@ -188,9 +254,18 @@ impl View for Slider {
         let base_bar_style = style.clone().apply_class(BarClass);
         paint |= self.base_bar_style.read_style(cx, &base_bar_style);
 
-        let accent_bar_style = style.apply_class(AccentBarClass);
+        let accent_bar_style = style.clone().apply_class(AccentBarClass);
         paint |= self.accent_bar_style.read_style(cx, &accent_bar_style);
-        paint |= self.style.read(cx);
+
+        let tick_style = style.apply_class(TickClass);
+        paint |= self.tick_style.read_style(cx, &tick_style);
+
+        if self.style.read(cx) {
+            paint = true;
+            // the labels are laid out again with the new text style
+            self.tick_labels.clear();
+            self.id.request_layout();
+        }
         if paint {
             cx.app_state_mut().request_paint(self.id);
         }
@@ -204,25 +279,26 @@ impl View for Slider {
         let layout = self.id.get_layout().unwrap_or_default();
 
         self.size = layout.size;
+        let cross_length = self.cross_length();
 
         let circle_radius = match self.style.handle_radius() {
             PxPct::Px(px) => px,
             PxPct::Pct(pct) => self.size.width.min(self.size.height) as f64 / 2. * (pct / 100.),
         };
-        let width = self.size.width as f64 - circle_radius * 2.;
-        let center = width * (self.percent / 100.) + circle_radius;
-        let circle_point = Point::new(center, (self.size.height / 2.) as f64);
-        self.handle = crate::kurbo::Circle::new(circle_point, circle_radius);
+        self.handle = Circle::new(self.point(self.percent, circle_radius), circle_radius);
+        self.high_handle = self
+            .high
+            .map(|high| Circle::new(self.point(high, circle_radius), circle_radius));
 
         let base_bar_height = match self.base_bar_style.height() {
             PxPctAuto::Px(px) => px,
-            PxPctAuto::Pct(pct) => self.size.height as f64 * (pct / 100.),
-            PxPctAuto::Auto => self.size.height as f64,
+            PxPctAuto::Pct(pct) => cross_length * (pct / 100.),
+            PxPctAuto::Auto => cross_length,
         };
         let accent_bar_height = match self.accent_bar_style.height() {
             PxPctAuto::Px(px) => px,
-            PxPctAuto::Pct(pct) => self.size.height as f64 * (pct / 100.),
-            PxPctAuto::Auto => self.size.height as f64,
+            PxPctAuto::Pct(pct) => cross_length * (pct / 100.),
+            PxPctAuto::Auto => cross_length,
         };
 
         let base_bar_radius = match self.base_bar_style.border_radius() {
@@ -234,13 +310,13 @@ impl View for Slider {
             PxPct::Pct(pct) => accent_bar_height / 2. * (pct / 100.),
         };
 
-        let mut base_bar_length = self.size.width as f64;
+        let mut base_bar_length = self.main_length();
         if !self.style.edge_align() {
             base_bar_length -= self.handle.radius * 2.;
         }
 
-        let base_bar_y_start = self.size.height as f64 / 2. - base_bar_height / 2.;
-        let accent_bar_y_start = self.size.height as f64 / 2. - accent_bar_height / 2.;
+        let base_bar_y_start = cross_length / 2. - base_bar_height / 2.;
+        let accent_bar_y_start = cross_length / 2. - accent_bar_height / 2.;
 
         let bar_x_start = if self.style.edge_align() {
             0.
@@ -248,22 +324,35 @@ impl View for Slider {
             self.handle.radius
         };
 
-        self.base_bar = peniko::kurbo::Rect::new(
-            bar_x_start,
-            base_bar_y_start,
-            bar_x_start + base_bar_length,
-            base_bar_y_start + base_bar_height,
-        )
-        .to_rounded_rect(base_bar_radius);
-        self.accent_bar = peniko::kurbo::Rect::new(
-            bar_x_start,
-            accent_bar_y_start,
-            self.handle_center(),
-            accent_bar_y_start + accent_bar_height,
-        )
-        .to_rounded_rect(accent_bar_radius);
+        self.base_bar = self
+            .rect(
+                bar_x_start,
+                base_bar_y_start,
+                bar_x_start + base_bar_length,
+                base_bar_y_start + base_bar_height,
+            )
+            .to_rounded_rect(base_bar_radius);
+        let accent_bar_start = match self.high {
+            Some(_) => self.handle_center(),
+            None => bar_x_start,
+        };
+        let accent_bar_end = match self.high {
+            Some(high) => self.position(high),
+            None => self.handle_center(),
+        };
+        self.accent_bar = self
+            .rect(
+                accent_bar_start,
+                accent_bar_y_start,
+                accent_bar_end,
+                accent_bar_y_start + accent_bar_height,
+            )
+            .to_rounded_rect(accent_bar_radius);
+
+        self.layout_ticks(base_bar_y_start + base_bar_height);
 
         self.prev_percent = self.percent;
+        self.prev_high = self.high;
 
         None
     }
@@ -287,8 +376,32 @@ impl View for Slider {
         );
         cx.restore();
 
+        let tick_color = self.tick_style.color().unwrap_or(Color::DARK_GRAY.into());
+        for tick in &self.ticks {
+            cx.fill(tick, &tick_color, 0.);
+        }
+        for (percent, label) in &self.tick_labels {
+            let size = label.size();
+            let position = self.position(*percent);
+            let origin = if self.vertical {
+                Point::new(
+                    self.base_bar.rect().x1 + self.tick_length() + TICK_LABEL_GAP,
+                    position - size.height / 2.,
+                )
+            } else {
+                Point::new(
+                    position - size.width / 2.,
+                    self.base_bar.rect().y1 + self.tick_length() + TICK_LABEL_GAP,
+                )
+            };
+            cx.draw_text(label, origin);
+        }
+
         if let Some(color) = self.style.foreground() {
             cx.fill(&self.handle, &color, 0.);
+            if let Some(high_handle) = &self.high_handle {
+                cx.fill(high_handle, &color, 0.);
+            }
         }
     }
 }
@@ -329,14 +442,26 @@ impl Slider {
             id,
             onchangepx: None,
             onchangepct: None,
+            onchangerange: None,
             held: false,
             percent,
             prev_percent: 0.0,
+            high: None,
+            prev_high: None,
+            thumb: Thumb::Low,
+            step: None,
+            show_ticks: false,
+            tick_label: None,
+            tick_labels: Vec::new(),
+            vertical: false,
             handle: Default::default(),
+            high_handle: None,
             base_bar_style: Default::default(),
             accent_bar_style: Default::default(),
+            tick_style: Default::default(),
             base_bar: Default::default(),
             accent_bar: Default::default(),
+            ticks: Vec::new(),
             size: Default::default(),
             style: Default::default(),
         }
@@ -367,13 +492,260 @@ impl Slider {
         Self::new(move || percent.get()).on_change_pct(move |pct| percent.set(pct))
     }
 
+    /// Create a new reactive range slider, with a handle for the low and the high end of the range.
+    ///
+    /// The handles can't be moved past each other. Use [Slider::on_change_range] to respond to updates from the slider.
+    pub fn new_range<P: Into<Pct>>(
+        low: impl Fn() -> P + 'static,
+        high: impl Fn() -> P + 'static,
+    ) -> Self {
+        let mut slider = Self::new(low);
+        let id = slider.id;
+        slider.high = Some(create_updater(
+            move || high().into().0,
+            move |high| id.update_state(SliderUpdate::High(high)),
+        ));
+        slider
+    }
+
+    /// Create a new reactive range slider that keeps the signals of the low and the high end of the range up to date.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use floem::prelude::*;
+    /// let low = RwSignal::new(20.pct());
+    /// let high = RwSignal::new(80.pct());
+    ///
+    /// slider::Slider::new_range_rw(low, high)
+    ///     .step(10.)
+    ///     .ticks(true)
+    ///     .style(|s| s.width(200));
+    /// ```
+    pub fn new_range_rw(
+        low: impl SignalGet<Pct> + SignalUpdate<Pct> + Copy + 'static,
+        high: impl SignalGet<Pct> + SignalUpdate<Pct> + Copy + 'static,
+    ) -> Self {
+        Self::new_range(move || low.get(), move || high.get()).on_change_range(
+            move |new_low, new_high| {
+                low.set(new_low);
+                high.set(new_high);
+            },
+        )
+    }
+
     fn update_restrict_position(&mut self) {
-        self.percent = self.percent.clamp(0., 100.);
+        self.percent = self.snap(self.percent);
+        if let Some(high) = self.high {
+            let high = self.snap(high);
+            // the handle that is moved stops at the other one
+            match self.thumb {
+                Thumb::Low => {
+                    self.percent = self.percent.min(high);
+                    self.high = Some(high);
+                }
+                Thumb::High => self.high = Some(high.max(self.percent)),
+            }
+        }
+    }
+
+    /// Clamps `percent` to the slider, and rounds it to the nearest step.
+    fn snap(&self, percent: f64) -> f64 {
+        let percent = match self.step {
+            Some(step) if step > 0. => (percent / step).round() * step,
+            _ => percent,
+        };
+        percent.clamp(0., 100.)
+    }
+
+    fn thumb_percent(&self) -> f64 {
+        match (self.thumb, self.high) {
+            (Thumb::High, Some(high)) => high,
+            _ => self.percent,
+        }
+    }
+
+    fn set_thumb_percent(&mut self, percent: f64) {
+        match (self.thumb, self.high) {
+            (Thumb::High, Some(_)) => self.high = Some(percent),
+            _ => self.percent = percent,
+        }
+    }
+
+    /// The handle that is closest to `percent`, which is the one that is moved by a click.
+    fn nearest_thumb(&self, percent: f64) -> Thumb {
+        match self.high {
+            // when the handles are on top of each other, the one on the side of the click moves
+            Some(high) if (percent - high).abs() < (percent - self.percent).abs() => Thumb::High,
+            Some(high) if high == self.percent && percent > high => Thumb::High,
+            _ => Thumb::Low,
+        }
+    }
+
+    /// The percent of the slider at a position of the pointer.
+    fn pointer_percent(&self, pos: Point) -> f64 {
+        if self.vertical {
+            (self.size.height as f64 - pos.y) / self.size.height as f64 * 100.
+        } else {
+            pos.x / self.size.width as f64 * 100.
+        }
+    }
+
+    /// The length of the slider along which the handle moves.
+    fn main_length(&self) -> f64 {
+        if self.vertical {
+            self.size.height as f64
+        } else {
+            self.size.width as f64
+        }
+    }
+
+    /// The thickness of the slider, across the direction the handle moves in.
+    fn cross_length(&self) -> f64 {
+        if self.vertical {
+            self.size.width as f64
+        } else {
+            self.size.height as f64
+        }
+    }
+
+    /// The distance of the center of a handle at `percent` from the start of the slider, which is
+    /// the bottom of a vertical slider.
+    fn offset(&self, percent: f64, radius: f64) -> f64 {
+        let length = self.main_length() - radius * 2.;
+        length * (percent / 100.) + radius
+    }
+
+    /// The coordinate of a handle at `percent` along the slider.
+    fn position(&self, percent: f64) -> f64 {
+        let offset = self.offset(percent, self.handle.radius);
+        if self.vertical {
+            self.main_length() - offset
+        } else {
+            offset
+        }
+    }
+
+    fn point(&self, percent: f64, radius: f64) -> Point {
+        let offset = self.offset(percent, radius);
+        let cross = self.cross_length() / 2.;
+        if self.vertical {
+            Point::new(cross, self.main_length() - offset)
+        } else {
+            Point::new(offset, cross)
+        }
+    }
+
+    /// A rect from the distances along the slider and across it, which are flipped for a vertical
+    /// slider.
+    fn rect(&self, main_start: f64, cross_start: f64, main_end: f64, cross_end: f64) -> Rect {
+        if self.vertical {
+            let length = self.main_length();
+            Rect::new(
+                cross_start,
+                length - main_end,
+                cross_end,
+                length - main_start,
+            )
+        } else {
+            Rect::new(main_start, cross_start, main_end, cross_end)
+        }
+    }
+
+    fn tick_length(&self) -> f64 {
+        match self.tick_style.height() {
+            PxPctAuto::Px(px) => px,
+            PxPctAuto::Pct(pct) => self.cross_length() * (pct / 100.),
+            PxPctAuto::Auto => 4.,
+        }
+    }
+
+    /// The percents of the ticks, at each step.
+    fn tick_percents(&self) -> Vec<f64> {
+        match self.step {
+            Some(step) if step > 0. => (0..)
+                .map(|i| i as f64 * step)
+                .take_while(|percent| *percent <= 100.)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Places the ticks after the end of the bar, at `bar_end` across the slider, and lays out
+    /// their labels.
+    fn layout_ticks(&mut self, bar_end: f64) {
+        let percents = if self.show_ticks || self.tick_label.is_some() {
+            self.tick_percents()
+        } else {
+            Vec::new()
+        };
+        let tick_length = self.tick_length();
+        self.ticks = percents
+            .iter()
+            .map(|percent| {
+                let offset = self.offset(*percent, self.handle.radius);
+                self.rect(offset - 0.5, bar_end, offset + 0.5, bar_end + tick_length)
+            })
+            .collect();
+        if !self.show_ticks {
+            self.ticks.clear();
+        }
+
+        let Some(tick_label) = &self.tick_label else {
+            self.tick_labels.clear();
+            return;
+        };
+        if self.tick_labels.len() != percents.len() {
+            let mut attrs = Attrs::new().color(self.style.color().unwrap_or(Color::BLACK));
+            if let Some(font_size) = self.style.font_size() {
+                attrs = attrs.font_size(font_size);
+            }
+            self.tick_labels = percents
+                .iter()
+                .map(|percent| {
+                    let mut layout = TextLayout::new();
+                    layout.set_text(&tick_label(Pct(*percent)), AttrsList::new(attrs));
+                    (*percent, layout)
+                })
+                .collect();
+        }
     }
 
     fn handle_center(&self) -> f64 {
-        let width = self.size.width as f64 - self.handle.radius * 2.;
-        width * (self.percent / 100.) + self.handle.radius
+        self.position(self.percent)
+    }
+
+    /// Snaps the slider to multiples of `step`, a percent.
+    ///
+    /// The arrow keys move the handle by one step.
+    pub fn step(mut self, step: impl Into<Pct>) -> Self {
+        self.step = Some(step.into().0);
+        self.tick_labels.clear();
+        self
+    }
+
+    /// Shows a tick mark at each [step](Slider::step), after the bar.
+    ///
+    /// The ticks are styled with [SliderCustomStyle::tick_color] and [SliderCustomStyle::tick_height].
+    pub fn ticks(mut self, show: bool) -> Self {
+        self.show_ticks = show;
+        self
+    }
+
+    /// Shows a label at each [step](Slider::step), after the ticks, with the text returned by `label`.
+    ///
+    /// The labels use the text color and font size of the slider, and need room for them in its size.
+    pub fn tick_labels(mut self, label: impl Fn(Pct) -> String + 'static) -> Self {
+        self.tick_label = Some(Box::new(label));
+        self.tick_labels.clear();
+        self
+    }
+
+    /// Makes the slider vertical, with 0% at the bottom and 100% at the top.
+    ///
+    /// The heights of the bars are their thickness, relative to the width of the view.
+    pub fn vertical(mut self) -> Self {
+        self.vertical = true;
+        self
     }
 
     /// Add an event handler to be run when the slider is moved.
@@ -382,6 +754,8 @@ impl Slider {
     /// Calling it again will clear the previously set callback.
     ///
     /// You can set both an `on_change_pct` and [Slider::on_change_px] callbacks at the same time and both will be called on change.
+    ///
+    /// For a range slider, the callback is called with the low end of the range.
     pub fn on_change_pct(mut self, onchangepct: impl Fn(Pct) + 'static) -> Self {
         self.onchangepct = Some(Box::new(onchangepct));
         self
@@ -397,6 +771,15 @@ impl Slider {
         self
     }
 
+    /// Add an event handler to be run when a handle of a range slider is moved, with the low and the high end of the range.
+    ///
+    /// Only one callback of the range can be set on this view.
+    /// Calling it again will clear the previously set callback.
+    pub fn on_change_range(mut self, onchangerange: impl Fn(Pct, Pct) + 'static) -> Self {
+        self.onchangerange = Some(Box::new(onchangerange));
+        self
+    }
+
     /// Sets the custom style properties of the `Slider`.
     pub fn slider_style(
         self,
@@ -503,6 +886,24 @@ impl SliderCustomStyle {
         self = SliderCustomStyle(self.0.class(AccentBarClass, |s| s.height(height)));
         self
     }
+
+    /// Sets the color of the ticks of the slider.
+    ///
+    /// # Arguments
+    /// * `color` - A `StyleValue<Color>` that sets the ticks' color.
+    pub fn tick_color(mut self, color: impl Into<Brush>) -> Self {
+        self = SliderCustomStyle(self.0.class(TickClass, |s| s.background(color)));
+        self
+    }
+
+    /// Sets the length of the ticks of the slider.
+    ///
+    /// # Arguments
+    /// * `height` - A `PxPctAuto` value that sets the ticks' length. This can be a pixel value, or a percent value relative to the view's height.
+    pub fn tick_height(mut self, height: impl Into<PxPctAuto>) -> Self {
+        self = SliderCustomStyle(self.0.class(TickClass, |s| s.height(height)));
+        self
+    }
 }
 
 #[cfg(test)]
@@ -666,4 +1067,60 @@ mod test {
 
         assert!(callback_called.load(Ordering::SeqCst));
     }
+
+    #[test]
+    fn test_slider_step_snapping() {
+        let mut slider = Slider::new(|| 0.0).step(25.0);
+        update_slider_value(&mut slider, 62.0);
+        slider.update_restrict_position();
+        assert_eq!(slider.percent, 50.0);
+        assert_eq!(slider.tick_percents(), [0.0, 25.0, 50.0, 75.0, 100.0]);
+    }
+
+    #[test]
+    fn test_range_slider_handles() {
+        let mut slider = Slider::new_range(|| 20.0, || 60.0);
+        let mut cx = create_test_event_cx(slider.id());
+        slider.size = taffy::prelude::Size {
+            width: 100.0,
+            height: 20.0,
+        };
+
+        // the high handle is closest, and can't be dragged below the low one
+        let pointer_down = Event::PointerDown(PointerInputEvent {
+            pos: Point::new(50.0, 10.0),
+            button: PointerButton::Primary,
+            count: 1,
+            modifiers: Default::default(),
+        });
+        slider.event_before_children(&mut cx, &pointer_down);
+        assert_eq!(slider.high, Some(50.0));
+
+        let pointer_move = Event::PointerMove(PointerMoveEvent {
+            pos: Point::new(10.0, 10.0),
+            modifiers: Default::default(),
+        });
+        slider.event_before_children(&mut cx, &pointer_move);
+        assert_eq!(slider.percent, 20.0);
+        assert_eq!(slider.high, Some(20.0));
+    }
+
+    #[test]
+    fn test_vertical_slider_pointer() {
+        let mut slider = Slider::new(|| 0.0).vertical();
+        let mut cx = create_test_event_cx(slider.id());
+        slider.size = taffy::prelude::Size {
+            width: 20.0,
+            height: 100.0,
+        };
+
+        let pointer_down = Event::PointerDown(PointerInputEvent {
+            pos: Point::new(10.0, 25.0),
+            button: PointerButton::Primary,
+            count: 1,
+            modifiers: Default::default(),
+        });
+        slider.event_before_children(&mut cx, &pointer_down);
+        assert_eq!(slider.percent, 75.0);
+    }
 }
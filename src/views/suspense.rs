@@ -0,0 +1,54 @@
+#![deny(missing_docs)]
+//! A boundary that shows a fallback while the [`Resource`](crate::resource::Resource)s of its
+//! content are loading.
+
+use crate::{
+    id::ViewId,
+    resource::SuspenseContext,
+    view::{IntoView, View},
+    views::Decorators,
+};
+
+/// A suspense boundary, created with [`suspense`].
+pub struct Suspense {
+    id: ViewId,
+}
+
+/// Builds the `content` and shows the `fallback` instead of it while any of the resources read
+/// while building it are loading, e.g. the first time or when they're loaded again.
+///
+/// Boundaries can be nested, in which case the resources read in the content of the inner
+/// boundary are only waited for by the inner boundary. See
+/// [`create_resource`](crate::resource::create_resource) for an example.
+pub fn suspense<F: IntoView + 'static, C: IntoView + 'static>(
+    fallback: impl FnOnce() -> F,
+    content: impl FnOnce() -> C,
+) -> Suspense {
+    let id = ViewId::new();
+    let context = SuspenseContext::default();
+    let content = context.collect(content).into_any();
+
+    let loading = {
+        let context = context.clone();
+        move || context.is_loading()
+    };
+    let content = content.style({
+        let loading = loading.clone();
+        move |s| s.apply_if(loading(), |s| s.hide())
+    });
+    let fallback = fallback()
+        .into_any()
+        .style(move |s| s.apply_if(!loading(), |s| s.hide()));
+    id.set_children(vec![content, fallback]);
+    Suspense { id }
+}
+
+impl View for Suspense {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Suspense".into()
+    }
+}
@@ -0,0 +1,496 @@
+#![deny(missing_docs)]
+//! A tab bar, a row of tabs above the content of the active tab.
+//!
+//! Tabs can be closed with their close button, reordered by dragging them and navigated with the
+//! arrow keys, `Home` and `End` when the tab bar has focus, where `Delete` closes the active tab.
+//! When there are more tabs than fit, they are scrolled with buttons on both sides, or picked
+//! from a menu, see [`TabOverflow`].
+//!
+//! The content of a tab is only created when the tab is first activated, and is kept while the
+//! tab is open.
+//!
+//! ```
+//! # use floem::reactive::RwSignal;
+//! # use floem::views::{label, tab_bar::{tab_bar, TabOverflow}};
+//! let tabs = RwSignal::new(vec!["main.rs", "lib.rs", "Cargo.toml"]);
+//! let active = RwSignal::new(0);
+//! tab_bar(
+//!     tabs,
+//!     active,
+//!     |name| *name,
+//!     |name| name.to_string(),
+//!     |name| label(move || format!("The contents of {name}")),
+//! )
+//! .overflow(TabOverflow::Dropdown)
+//! .on_close(|name| println!("closed {name}"));
+//! ```
+use std::{any::Any, hash::Hash, rc::Rc};
+
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use floem_winit::keyboard::{Key, NamedKey};
+use peniko::kurbo::Vec2;
+
+use crate::{
+    context::UpdateCx,
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    menu::{Menu, MenuItem},
+    style::Style,
+    style_class,
+    ui_state::UiStateRegistry,
+    view::{IntoView, View},
+    views::{dyn_container, dyn_stack, empty, h_stack, label, scroll, tab, Decorators},
+};
+
+style_class!(
+    /// The style class that is applied to tab bars.
+    pub TabBarClass
+);
+
+style_class!(
+    /// The style class that is applied to the row of tabs of a tab bar.
+    pub TabBarHeaderClass
+);
+
+style_class!(
+    /// The style class that is applied to every tab.
+    pub TabClass
+);
+
+style_class!(
+    /// The style class that is applied to the active tab, after [`TabClass`].
+    pub ActiveTabClass
+);
+
+style_class!(
+    /// The style class that is applied to the close buttons of tabs.
+    pub TabCloseClass
+);
+
+style_class!(
+    /// The style class that is applied to the buttons that scroll the tabs, and to the button
+    /// that opens the menu of tabs.
+    pub TabOverflowButtonClass
+);
+
+/// How far the scroll buttons scroll the tabs.
+const SCROLL_STEP: f64 = 120.;
+
+/// What a tab bar does when there are more tabs than fit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TabOverflow {
+    /// Buttons on both sides of the tabs scroll them.
+    #[default]
+    Scroll,
+    /// A button after the tabs opens a menu with all of the tabs.
+    Dropdown,
+}
+
+/// Closes the tab at an index.
+struct CloseTab(usize);
+
+/// A tab bar, created with [`tab_bar`].
+pub struct TabBar<T: 'static> {
+    id: ViewId,
+    tabs: RwSignal<Vec<T>>,
+    active: RwSignal<usize>,
+    closable: RwSignal<bool>,
+    reorderable: RwSignal<bool>,
+    overflow: RwSignal<TabOverflow>,
+    on_close: Option<Box<dyn Fn(T)>>,
+}
+
+/// Creates a tab bar with a tab for each of the `tabs`, which is labeled with `label_fn`, and
+/// shows the content created by `view_fn` for the tab at the index in `active`.
+///
+/// Tabs are told apart by `key_fn`, which should return a unique key for each tab. Closing and
+/// reordering tabs updates both `tabs` and `active`, so the same tab stays active when it's
+/// moved.
+pub fn tab_bar<T, K, KF, LF, VF, V>(
+    tabs: RwSignal<Vec<T>>,
+    active: RwSignal<usize>,
+    key_fn: KF,
+    label_fn: LF,
+    view_fn: VF,
+) -> TabBar<T>
+where
+    T: Clone + 'static,
+    K: Eq + Hash + Clone + 'static,
+    KF: Fn(&T) -> K + 'static,
+    LF: Fn(&T) -> String + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    let closable = RwSignal::new(true);
+    let reorderable = RwSignal::new(true);
+    let overflow = RwSignal::new(TabOverflow::default());
+    let key_fn = Rc::new(key_fn);
+    let label_fn = Rc::new(label_fn);
+    let view_fn = Rc::new(view_fn);
+
+    let dragged = RwSignal::new(None::<K>);
+    let active_tab_id = RwSignal::new(None::<ViewId>);
+    let scroll_by = RwSignal::new(Vec2::ZERO);
+
+    let tab_view = {
+        let key_fn = key_fn.clone();
+        let label_fn = label_fn.clone();
+        move |(item, draggable): (T, bool)| {
+            let key = key_fn(&item);
+            let index = {
+                let key_fn = key_fn.clone();
+                let key = key.clone();
+                move || tabs.with(|tabs| position(tabs, &*key_fn, &key))
+            };
+            let label_fn = label_fn.clone();
+            let tab = h_stack((
+                label(move || label_fn(&item)),
+                label(|| "×")
+                    .class(TabCloseClass)
+                    .style(move |s| s.apply_if(!closable.get(), |s| s.hide()))
+                    .on_click_stop({
+                        let index = index.clone();
+                        move |_| {
+                            if let Some(index) = index() {
+                                id.update_state(CloseTab(index));
+                            }
+                        }
+                    }),
+            ))
+            .class(TabClass)
+            .class_if(
+                {
+                    let index = index.clone();
+                    move || index() == Some(active.get())
+                },
+                ActiveTabClass,
+            )
+            .on_click_stop({
+                let index = index.clone();
+                move |_| {
+                    if let Some(index) = index() {
+                        active.set(index);
+                    }
+                }
+            })
+            .on_event_cont(EventListener::DragStart, move |_| {
+                dragged.set(Some(key.clone()));
+            })
+            .on_event_cont(EventListener::DragOver, {
+                let key_fn = key_fn.clone();
+                let index = index.clone();
+                move |_| {
+                    let Some(dragged_key) = dragged.get_untracked() else {
+                        return;
+                    };
+                    let from = tabs.with_untracked(|tabs| position(tabs, &*key_fn, &dragged_key));
+                    if let (Some(from), Some(to)) = (from, index()) {
+                        if from != to {
+                            tabs.update(|tabs| {
+                                let tab = tabs.remove(from);
+                                tabs.insert(to, tab);
+                            });
+                            active.set(active_after_move(active.get_untracked(), from, to));
+                        }
+                    }
+                }
+            })
+            .on_event_cont(EventListener::DragEnd, move |_| dragged.set(None));
+
+            let tab_id = tab.id();
+            create_effect(move |_| {
+                if index() == Some(active.get()) {
+                    active_tab_id.set(Some(tab_id));
+                }
+            });
+
+            if draggable {
+                tab.draggable()
+            } else {
+                tab
+            }
+        }
+    };
+
+    let tabs_scroll = scroll(dyn_stack(
+        // a view can't stop being draggable, so the tabs are created again when that changes
+        move || {
+            let draggable = reorderable.get();
+            tabs.get().into_iter().map(move |tab| (tab, draggable))
+        },
+        {
+            let key_fn = key_fn.clone();
+            move |(tab, draggable): &(T, bool)| (key_fn(tab), *draggable)
+        },
+        tab_view,
+    ))
+    .scroll_style(|s| s.hide_bars(true).vertical_scroll_as_horizontal(true))
+    .scroll_delta(move || scroll_by.get())
+    .scroll_to_view(move || active_tab_id.get())
+    .style(|s| s.flex_grow(1.).min_width(0.));
+    let progress = tabs_scroll.scroll_progress();
+
+    let scroll_button = move |text: &'static str, step: f64| {
+        label(move || text)
+            .class(TabOverflowButtonClass)
+            .style(move |s| {
+                let shown = overflow.get() == TabOverflow::Scroll
+                    && progress.with(|progress| progress.max_offset.x > 0.);
+                s.apply_if(!shown, |s| s.hide())
+            })
+            .disabled(move || {
+                progress.with(|progress| {
+                    if step < 0. {
+                        progress.offset.x <= 0.
+                    } else {
+                        progress.offset.x >= progress.max_offset.x
+                    }
+                })
+            })
+            .on_click_stop(move |_| scroll_by.set(Vec2::new(step, 0.)))
+    };
+
+    let menu_button = label(|| "▾")
+        .class(TabOverflowButtonClass)
+        .style(move |s| s.apply_if(overflow.get() != TabOverflow::Dropdown, |s| s.hide()))
+        .popout_menu(move || {
+            tabs.with_untracked(|tabs| {
+                tabs.iter()
+                    .enumerate()
+                    .fold(Menu::new(""), |menu, (index, tab)| {
+                        menu.entry(MenuItem::new(label_fn(tab)).action(move || active.set(index)))
+                    })
+            })
+        });
+
+    let header = h_stack((
+        scroll_button("‹", -SCROLL_STEP),
+        tabs_scroll,
+        scroll_button("›", SCROLL_STEP),
+        menu_button,
+    ))
+    .class(TabBarHeaderClass)
+    .keyboard_navigable()
+    .on_event(EventListener::KeyDown, move |event| {
+        let Event::KeyDown(event) = event else {
+            return EventPropagation::Continue;
+        };
+        let len = tabs.with_untracked(Vec::len);
+        let current = active.get_untracked();
+        match &event.key.logical_key {
+            Key::Named(NamedKey::Delete) if closable.get_untracked() && current < len => {
+                id.update_state(CloseTab(current));
+            }
+            key => match navigate(key, current, len) {
+                Some(index) => active.set(index),
+                None => return EventPropagation::Continue,
+            },
+        }
+        EventPropagation::Stop
+    });
+
+    let content = tab(
+        move || active.get(),
+        move || tabs.get(),
+        {
+            let key_fn = key_fn.clone();
+            move |tab: &T| key_fn(tab)
+        },
+        move |item: T| {
+            let key = key_fn(&item);
+            let loaded = RwSignal::new(false);
+            let key_fn = key_fn.clone();
+            create_effect(move |_| {
+                if loaded.get_untracked() {
+                    return;
+                }
+                if tabs.with(|tabs| position(tabs, &*key_fn, &key)) == Some(active.get()) {
+                    loaded.set(true);
+                }
+            });
+            let view_fn = view_fn.clone();
+            dyn_container(
+                move || loaded.get(),
+                move |loaded| {
+                    if loaded {
+                        view_fn(item.clone()).into_any()
+                    } else {
+                        empty().into_any()
+                    }
+                },
+            )
+            .style(|s| s.size_full())
+        },
+    )
+    .style(|s| s.flex_grow(1.).min_height(0.));
+
+    id.set_children(vec![header.into_any(), content.into_any()]);
+
+    TabBar {
+        id,
+        tabs,
+        active,
+        closable,
+        reorderable,
+        overflow,
+        on_close: None,
+    }
+    .class(TabBarClass)
+}
+
+impl<T: 'static> View for TabBar<T> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Tab Bar".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if let Ok(close) = state.downcast::<CloseTab>() {
+            self.close(close.0);
+        }
+    }
+}
+
+impl<T: 'static> TabBar<T> {
+    /// Sets whether tabs have a button that closes them, which they have by default.
+    pub fn closable(self, closable: bool) -> Self {
+        self.closable.set(closable);
+        self
+    }
+
+    /// Sets whether tabs can be reordered by dragging them, which they can by default.
+    pub fn reorderable(self, reorderable: bool) -> Self {
+        self.reorderable.set(reorderable);
+        self
+    }
+
+    /// Sets what the tab bar does when there are more tabs than fit.
+    pub fn overflow(self, overflow: TabOverflow) -> Self {
+        self.overflow.set(overflow);
+        self
+    }
+
+    /// Saves the active tab under `key` in the [`UiStateRegistry`], and activates the tab that
+    /// was active when the application was last closed, if there are as many tabs.
+    pub fn persist(self, key: impl Into<String>) -> Self {
+        let key = key.into();
+        let saved = UiStateRegistry::get(&key).and_then(|active| active.parse::<usize>().ok());
+        if let Some(active) = saved.filter(|active| *active < self.tabs.with_untracked(Vec::len)) {
+            self.active.set(active);
+        }
+        let active = self.active;
+        create_effect(move |_| UiStateRegistry::set(&key, active.get().to_string()));
+        self
+    }
+
+    /// Sets a function that is called with a tab after the user closed it, and it was removed
+    /// from the tabs.
+    pub fn on_close(mut self, on_close: impl Fn(T) + 'static) -> Self {
+        self.on_close = Some(Box::new(on_close));
+        self
+    }
+
+    fn close(&self, index: usize) {
+        let Some(tab) = self
+            .tabs
+            .try_update(|tabs| (index < tabs.len()).then(|| tabs.remove(index)))
+            .flatten()
+        else {
+            return;
+        };
+        let len = self.tabs.with_untracked(Vec::len);
+        let active = self.active.get_untracked();
+        let new_active = active_after_close(active, index, len);
+        if new_active != active {
+            self.active.set(new_active);
+        }
+        if let Some(on_close) = &self.on_close {
+            on_close(tab);
+        }
+    }
+}
+
+fn position<T, K: PartialEq>(tabs: &[T], key_fn: &impl Fn(&T) -> K, key: &K) -> Option<usize> {
+    tabs.iter().position(|tab| key_fn(tab) == *key)
+}
+
+/// The active index after the tab at `closed` was removed, leaving `len` tabs.
+fn active_after_close(active: usize, closed: usize, len: usize) -> usize {
+    if closed < active {
+        active - 1
+    } else {
+        active.min(len.saturating_sub(1))
+    }
+}
+
+/// The active index after the tab at `from` was moved to `to`.
+fn active_after_move(active: usize, from: usize, to: usize) -> usize {
+    if active == from {
+        to
+    } else if from < active && active <= to {
+        active - 1
+    } else if to <= active && active < from {
+        active + 1
+    } else {
+        active
+    }
+}
+
+/// The index that a navigation key moves to, wrapping around at the ends.
+fn navigate(key: &Key, active: usize, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let active = active.min(len - 1);
+    match key {
+        Key::Named(NamedKey::ArrowLeft) => Some((active + len - 1) % len),
+        Key::Named(NamedKey::ArrowRight) => Some((active + 1) % len),
+        Key::Named(NamedKey::Home) => Some(0),
+        Key::Named(NamedKey::End) => Some(len - 1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closing_keeps_the_active_tab() {
+        // closing a tab before the active one
+        assert_eq!(active_after_close(2, 0, 3), 1);
+        // closing a tab after the active one
+        assert_eq!(active_after_close(1, 2, 3), 1);
+        // closing the active tab activates the next one, or the last one
+        assert_eq!(active_after_close(1, 1, 3), 1);
+        assert_eq!(active_after_close(3, 3, 3), 2);
+        assert_eq!(active_after_close(0, 0, 0), 0);
+    }
+
+    #[test]
+    fn moving_keeps_the_active_tab() {
+        assert_eq!(active_after_move(1, 1, 3), 3);
+        assert_eq!(active_after_move(2, 0, 3), 1);
+        assert_eq!(active_after_move(2, 3, 0), 3);
+        assert_eq!(active_after_move(0, 1, 2), 0);
+    }
+
+    #[test]
+    fn navigation_wraps_around() {
+        let left = Key::Named(NamedKey::ArrowLeft);
+        let right = Key::Named(NamedKey::ArrowRight);
+        assert_eq!(navigate(&left, 0, 3), Some(2));
+        assert_eq!(navigate(&right, 2, 3), Some(0));
+        assert_eq!(navigate(&right, 7, 3), Some(0));
+        assert_eq!(navigate(&Key::Named(NamedKey::End), 0, 3), Some(2));
+        assert_eq!(navigate(&right, 0, 0), None);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::action::exec_after;
+use crate::action::{exec_after, set_ime_allowed, set_ime_cursor_area};
+use crate::clock;
 use crate::event::{EventListener, EventPropagation};
 use crate::id::ViewId;
 use crate::keyboard::{self, KeyEvent, Modifiers};
@@ -165,14 +166,16 @@ pub fn text_input(buffer: RwSignal<String>) -> TextInput {
         height: 0.0,
         is_focused: false,
         last_pointer_down: Point::ZERO,
-        last_cursor_action_on: Instant::now(),
+        last_cursor_action_on: clock::now(),
     }
     .keyboard_navigable()
     .on_event_stop(EventListener::FocusGained, move |_| {
         is_focused.set(true);
+        set_ime_allowed(true);
     })
     .on_event_stop(EventListener::FocusLost, move |_| {
         is_focused.set(false);
+        set_ime_allowed(false);
     })
     .class(TextInputClass)
 }
@@ -854,6 +857,19 @@ impl TextInput {
         self.move_cursor(Movement::Glyph, Direction::Right)
     }
 
+    /// Inserts the text committed by an input method, replacing the selection.
+    fn commit_text(&mut self, text: &str) -> bool {
+        if let Some(selection) = self.selection.take() {
+            self.buffer
+                .update(|buf| replace_range(buf, selection.clone(), None));
+            self.cursor_glyph_idx = selection.start;
+        }
+        self.buffer
+            .update(|buf| buf.insert_str(self.cursor_glyph_idx, text));
+        self.cursor_glyph_idx += text.len();
+        true
+    }
+
     fn move_selection(
         &mut self,
         old_glyph_idx: usize,
@@ -1014,6 +1030,10 @@ impl View for TextInput {
         format!("TextInput: {:?}", self.buffer.get_untracked()).into()
     }
 
+    fn text_content(&self) -> Option<String> {
+        Some(self.buffer.get_untracked())
+    }
+
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
         if let Ok(state) = state.downcast::<(String, bool)>() {
             let (value, is_focused) = *state;
@@ -1079,12 +1099,13 @@ impl View for TextInput {
                 false
             }
             Event::KeyDown(event) => self.handle_key_down(cx, event),
+            Event::ImeCommit(text) => self.commit_text(text),
             _ => false,
         };
 
         if is_handled {
             self.id.request_layout();
-            self.last_cursor_action_on = Instant::now();
+            self.last_cursor_action_on = clock::now();
         }
 
         if is_handled {
@@ -1190,7 +1211,7 @@ impl View for TextInput {
         })
     }
 
-    fn compute_layout(&mut self, _cx: &mut crate::context::ComputeLayoutCx) -> Option<Rect> {
+    fn compute_layout(&mut self, cx: &mut crate::context::ComputeLayoutCx) -> Option<Rect> {
         self.update_text_layout();
 
         let text_buf = self.text_buf.as_ref().unwrap();
@@ -1219,6 +1240,15 @@ impl View for TextInput {
             self.cursor_x = hit_pos.point.x;
         }
 
+        if self.is_focused {
+            // the candidates of an input method are shown below the cursor
+            let cursor_rect = self.get_cursor_rect(&node_layout) + cx.window_origin().to_vec2();
+            set_ime_cursor_area(
+                Point::new(cursor_rect.x0, cursor_rect.y1),
+                cursor_rect.size(),
+            );
+        }
+
         None
     }
 
@@ -1254,7 +1284,9 @@ impl View for TextInput {
 
         let is_cursor_visible = cx.app_state.is_focused(&self.id())
             && self.selection.is_none()
-            && (self.last_cursor_action_on.elapsed().as_millis()
+            && (clock::now()
+                .saturating_duration_since(self.last_cursor_action_on)
+                .as_millis()
                 / CURSOR_BLINK_INTERVAL_MS as u128)
                 % 2
                 == 0;
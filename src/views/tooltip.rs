@@ -1,39 +1,52 @@
-use peniko::kurbo::Point;
-use std::cell::RefCell;
+use peniko::kurbo::{Point, Rect};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 #[cfg(not(target_arch = "wasm32"))]
-use std::time::Duration;
+use std::time::{Duration, Instant};
 #[cfg(target_arch = "wasm32")]
-use web_time::Duration;
+use web_time::{Duration, Instant};
 
-use crate::style::{Style, StyleClass as _};
+use crate::style::{CustomStylable, Style, StyleClass as _};
 use crate::views::Decorators;
 use crate::{
-    action::{add_overlay, exec_after, remove_overlay, TimerToken},
+    action::{exec_after, remove_overlay, TimerToken},
+    clock,
     context::{EventCx, UpdateCx},
     event::{Event, EventPropagation},
     id::ViewId,
     prop, prop_extractor, style_class,
     view::{default_compute_layout, IntoView, View},
+    views::popover::{show_popover, Placement, PopoverAlign, PopoverAnchor, PopoverOptions},
 };
 
 style_class!(pub TooltipClass);
 style_class!(pub TooltipContainerClass);
 
 prop!(pub Delay: Duration {} = Duration::from_millis(600));
+prop!(pub SkipDelay: Duration {} = Duration::from_millis(300));
+prop!(pub FollowCursor: bool {} = false);
 
 prop_extractor! {
     TooltipStyle {
         delay: Delay,
+        skip_delay: SkipDelay,
+        follow_cursor: FollowCursor,
     }
 }
 
+thread_local! {
+    /// When the last tooltip was hidden, so that moving to a sibling can show its tooltip right away.
+    static LAST_HIDDEN: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
 /// A view that displays a tooltip for its child.
 pub struct Tooltip {
     id: ViewId,
     hover: Option<(Point, TimerToken)>,
     overlay: Rc<RefCell<Option<ViewId>>>,
+    /// The pointer position in the window that the tip is placed next to.
+    anchor: Rc<Cell<Rect>>,
     tip: Rc<dyn Fn() -> Box<dyn View>>,
     style: TooltipStyle,
     tip_style: Style,
@@ -55,6 +68,7 @@ pub fn tooltip<V: IntoView + 'static, T: IntoView + 'static>(
         tip: Rc::new(move || tip().into_any()),
         hover: None,
         overlay: overlay.clone(),
+        anchor: Rc::new(Cell::new(Rect::ZERO)),
         style: Default::default(),
         tip_style: Default::default(),
         scale: 1.0,
@@ -68,6 +82,30 @@ pub fn tooltip<V: IntoView + 'static, T: IntoView + 'static>(
     })
 }
 
+impl Tooltip {
+    /// Sets the custom style properties of the `Tooltip`.
+    pub fn tooltip_style(
+        self,
+        style: impl Fn(TooltipCustomStyle) -> TooltipCustomStyle + 'static,
+    ) -> Self {
+        self.custom_style(style)
+    }
+
+    /// Places the tip next to the pointer at `pos`.
+    fn set_anchor(&self, window_origin: Point, pos: Point) {
+        let pos = window_origin + pos.to_vec2();
+        self.anchor.set(Rect::from_origin_size(pos, (0., 0.)));
+    }
+
+    fn hide(&mut self) {
+        self.hover = None;
+        if let Some(id) = self.overlay.borrow_mut().take() {
+            remove_overlay(id);
+            LAST_HIDDEN.set(Some(clock::now()));
+        }
+    }
+}
+
 impl View for Tooltip {
     fn id(&self) -> ViewId {
         self.id
@@ -80,13 +118,20 @@ impl View for Tooltip {
                     let tip = self.tip.clone();
 
                     let tip_style = self.tip_style.clone();
-                    let overlay_id = add_overlay(
-                        window_origin
-                            + self.hover.unwrap().0.to_vec2()
-                            + (10. / self.scale, 10. / self.scale),
-                        move |_| tip().style(move |_| tip_style.clone()),
+                    self.set_anchor(window_origin, self.hover.unwrap().0);
+                    let options = PopoverOptions {
+                        placement: Placement::Bottom,
+                        align: PopoverAlign::Start,
+                        offset: 10. / self.scale,
+                        cross_offset: 10. / self.scale,
+                        ..Default::default()
+                    };
+                    let overlay_id = show_popover(
+                        PopoverAnchor::Rect(self.anchor.clone()),
+                        options,
+                        None,
+                        move || tip().style(move |_| tip_style.clone()),
                     );
-                    // overlay_id.request_all();
                     *self.overlay.borrow_mut() = Some(overlay_id);
                 }
             }
@@ -108,9 +153,27 @@ impl View for Tooltip {
     fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
         match &event {
             Event::PointerMove(e) => {
-                if self.overlay.borrow().is_none() && cx.app_state.dragging.is_none() {
+                let overlay = *self.overlay.borrow();
+                if let Some(overlay) = overlay {
+                    match self.window_origin {
+                        Some(window_origin) if self.style.follow_cursor() => {
+                            self.set_anchor(window_origin, e.pos);
+                            overlay.request_layout();
+                        }
+                        _ => {}
+                    }
+                } else if cx.app_state.dragging.is_none() {
+                    // skip the delay when moving on from another tooltip that was just hidden
+                    let skip = LAST_HIDDEN.get().is_some_and(|hidden| {
+                        clock::now().saturating_duration_since(hidden) < self.style.skip_delay()
+                    });
+                    let delay = if skip {
+                        Duration::ZERO
+                    } else {
+                        self.style.delay()
+                    };
                     let id = self.id();
-                    let token = exec_after(self.style.delay(), move |token| {
+                    let token = exec_after(delay, move |token| {
                         id.update_state(token);
                     });
                     self.hover = Some((e.pos, token));
@@ -122,10 +185,7 @@ impl View for Tooltip {
             | Event::PointerWheel(_)
             | Event::KeyUp(_)
             | Event::KeyDown(_) => {
-                self.hover = None;
-                if let Some(id) = self.overlay.borrow_mut().take() {
-                    remove_overlay(id);
-                }
+                self.hide();
             }
             _ => {}
         }
@@ -141,6 +201,45 @@ impl View for Tooltip {
     }
 }
 
+/// Represents a custom style for a `Tooltip`.
+#[derive(Default, Debug, Clone)]
+pub struct TooltipCustomStyle(Style);
+impl From<TooltipCustomStyle> for Style {
+    fn from(value: TooltipCustomStyle) -> Self {
+        value.0
+    }
+}
+
+impl CustomStylable<TooltipCustomStyle> for Tooltip {
+    type DV = Self;
+}
+
+impl TooltipCustomStyle {
+    /// Creates a new `TooltipCustomStyle`.
+    pub fn new() -> Self {
+        Self(Style::new())
+    }
+
+    /// Sets how long the pointer has to rest on the view before the tooltip is shown.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self = Self(self.0.set(Delay, delay));
+        self
+    }
+
+    /// Sets for how long after another tooltip was hidden this tooltip is shown without a delay,
+    /// so that moving between views with tooltips doesn't wait for each of them.
+    pub fn skip_delay(mut self, skip_delay: Duration) -> Self {
+        self = Self(self.0.set(SkipDelay, skip_delay));
+        self
+    }
+
+    /// Sets whether the tooltip moves along with the pointer while it is shown.
+    pub fn follow_cursor(mut self, follow_cursor: bool) -> Self {
+        self = Self(self.0.set(FollowCursor, follow_cursor));
+        self
+    }
+}
+
 pub trait TooltipExt {
     fn tooltip<V: IntoView + 'static>(self, tip: impl Fn() -> V + 'static) -> Tooltip;
 }
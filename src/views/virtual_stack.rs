@@ -1,23 +1,30 @@
-use std::{hash::Hash, marker::PhantomData, ops::Range};
+use std::{cell::RefCell, collections::HashMap, hash::Hash, marker::PhantomData, ops::Range};
 
 use floem_reactive::{
-    as_child_of_current_scope, create_effect, create_signal, Scope, SignalGet, SignalUpdate,
-    WriteSignal,
+    as_child_of_current_scope, create_effect, create_signal, ReadSignal, Scope, SignalGet,
+    SignalUpdate, WriteSignal,
 };
 use peniko::kurbo::Rect;
 use smallvec::SmallVec;
 use taffy::{style::Dimension, tree::NodeId};
 
 use crate::{
+    action::add_update_message,
+    app_state::AppState,
     context::ComputeLayoutCx,
     id::ViewId,
     style::Style,
+    update::UpdateMessage,
     view::{self, IntoView, View},
 };
 
-use super::{apply_diff, diff, Diff, DiffOpAdd, FxIndexSet, HashRun};
+use super::{
+    apply_diff_with, diff, new_child, Diff, DiffOpAdd, EmptyView, ErrorChanged, ErrorView,
+    FxIndexSet, HashRun,
+};
 
 type ViewFn<T> = Box<dyn Fn(T) -> (Box<dyn View>, Scope)>;
+type RecycledViewFn<T> = Box<dyn Fn(T) -> ((Box<dyn View>, WriteSignal<T>), Scope)>;
 
 #[derive(Clone, Copy)]
 pub enum VirtualDirection {
@@ -61,17 +68,115 @@ where
     children: Vec<Option<(ViewId, Scope)>>,
     viewport: Rect,
     set_viewport: WriteSignal<Rect>,
-    view_fn: ViewFn<T>,
+    item_views: ItemViews<T>,
+    empty_view: Option<EmptyView>,
+    error_view: Option<ErrorView>,
+    is_empty: bool,
     phatom: PhantomData<T>,
     before_size: f64,
     content_size: f64,
     before_node: Option<NodeId>,
 }
 
+impl<T> VirtualStack<T> {
+    /// Sets a view that is shown in place of the items while the collection is empty.
+    ///
+    /// The view is created each time the collection becomes empty and removed as soon as it has items again.
+    pub fn empty_view<V: IntoView + 'static>(mut self, view_fn: impl Fn() -> V + 'static) -> Self {
+        self.empty_view = Some(EmptyView::new(view_fn));
+        self
+    }
+
+    /// Sets a view that is shown in place of the empty view while the collection is empty and
+    /// `error_fn` returns an error, like the one of a failed load of the items.
+    ///
+    /// See [`DynStack::error_view`](super::DynStack::error_view) for how it's used with a
+    /// [`Resource`](crate::resource::Resource).
+    pub fn error_view<E, V>(
+        mut self,
+        error_fn: impl Fn() -> Option<E> + 'static,
+        view_fn: impl Fn(E) -> V + 'static,
+    ) -> Self
+    where
+        E: Clone + 'static,
+        V: IntoView + 'static,
+    {
+        self.error_view = Some(ErrorView::new(self.id, error_fn, view_fn));
+        self
+    }
+
+    /// Sets the children of the stack to the views of the visible items, or to the empty or
+    /// error view in their place.
+    fn update_children(&mut self, app_state: &mut AppState) {
+        self.id.set_children_ids(
+            self.children
+                .iter()
+                .filter_map(|c| Some(c.as_ref()?.0))
+                .collect(),
+        );
+        let has_error = self.error_view.as_ref().is_some_and(ErrorView::has_error);
+        if let Some(empty_view) = &mut self.empty_view {
+            empty_view.update(self.id, app_state, self.is_empty && !has_error);
+        }
+        if let Some(error_view) = &mut self.error_view {
+            error_view.update(self.id, app_state, self.is_empty);
+        }
+        self.id.request_all();
+    }
+}
+
+/// How the views of the items are made.
+enum ItemViews<T> {
+    /// A view is created for each item that becomes visible, and disposed when it's hidden.
+    Created(ViewFn<T>),
+    Recycled(Recycler<T>),
+}
+
+/// The views of a [`recycled_virtual_stack`], which are kept when their items are scrolled out
+/// of the viewport to show other items.
+struct Recycler<T> {
+    view_fn: RecycledViewFn<T>,
+    /// The signal of the item shown by each view.
+    items: HashMap<ViewId, WriteSignal<T>>,
+    /// The views that aren't showing an item, which aren't children of the stack.
+    free: Vec<(ViewId, Scope)>,
+}
+
+impl<T: 'static> Recycler<T> {
+    /// Gets a view showing `item`, rebinding a free view to it if there is one.
+    fn bind(&mut self, item: T) -> (ViewId, Scope) {
+        if let Some((id, scope)) = self.free.pop() {
+            self.items[&id].set(item);
+            return (id, scope);
+        }
+        let ((view, set_item), scope) = (self.view_fn)(item);
+        let id = view.id();
+        id.set_view(view);
+        self.items.insert(id, set_item);
+        (id, scope)
+    }
+
+    /// Keeps the view `id` that stopped showing its item, unless it or one of its children has
+    /// the focus or is active, as that state would be moved to another item.
+    fn release(&mut self, app_state: &mut AppState, id: ViewId, scope: Scope) {
+        let contains = |view: Option<ViewId>| {
+            std::iter::successors(view, ViewId::parent).any(|ancestor| ancestor == id)
+        };
+        if contains(app_state.focus) || contains(app_state.active) {
+            self.items.remove(&id);
+            app_state.remove_view(id);
+            scope.dispose();
+        } else {
+            self.free.push((id, scope));
+        }
+    }
+}
+
 struct VirtualStackState<T> {
     diff: Diff<T>,
     before_size: f64,
     content_size: f64,
+    is_empty: bool,
 }
 
 /// A View that is like a [`dyn_stack`](super::dyn_stack()) but also lazily loads the items as they appear in a [scroll view](super::scroll())
@@ -120,6 +225,92 @@ where
     K: Eq + Hash + 'static,
     VF: Fn(T) -> V + 'static,
     V: IntoView + 'static,
+{
+    let view_fn = Box::new(as_child_of_current_scope(move |e| view_fn(e).into_any()));
+    virtual_stack_with(
+        direction,
+        item_size,
+        each_fn,
+        key_fn,
+        ItemViews::Created(view_fn),
+    )
+}
+
+/// A [`virtual_stack`] that reuses the views of the items that are scrolled out of the viewport
+/// to show the items that are scrolled into it, instead of disposing of them and creating new
+/// ones.
+///
+/// The views are made by `view_fn` from a signal of their item, which is set to the new item
+/// when a view is reused, so everything that depends on the item has to read it from the
+/// signal. This saves creating and styling the views of rows that are expensive to build while
+/// scrolling quickly, but the state of a view that isn't derived from its item, like the text of
+/// an input, stays with the view. Views that have the focus or are active aren't reused.
+///
+/// ## Example
+/// ```
+/// use floem::{reactive::*, views::*};
+///
+/// let long_list: im::Vector<i32> = (0..1000000).collect();
+/// let (long_list, _set_long_list) = create_signal(long_list);
+///
+/// scroll(
+///     recycled_virtual_stack(
+///         VirtualDirection::Vertical,
+///         VirtualItemSize::Fixed(Box::new(|| 20.0)),
+///         move || long_list.get(),
+///         move |item| *item,
+///         move |item| label(move || item.get().to_string()).style(|s| s.height(20.0)),
+///     )
+///     .style(|s| s.flex_col().width_full()),
+/// )
+/// .style(|s| s.width(100.0).height(100.0));
+/// ```
+pub fn recycled_virtual_stack<T, IF, I, KF, K, VF, V>(
+    direction: VirtualDirection,
+    item_size: VirtualItemSize<T>,
+    each_fn: IF,
+    key_fn: KF,
+    view_fn: VF,
+) -> VirtualStack<T>
+where
+    T: 'static,
+    IF: Fn() -> I + 'static,
+    I: VirtualVector<T>,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + 'static,
+    VF: Fn(ReadSignal<T>) -> V + 'static,
+    V: IntoView + 'static,
+{
+    let view_fn = Box::new(as_child_of_current_scope(move |item| {
+        let (item, set_item) = create_signal(item);
+        (view_fn(item).into_any(), set_item)
+    }));
+    virtual_stack_with(
+        direction,
+        item_size,
+        each_fn,
+        key_fn,
+        ItemViews::Recycled(Recycler {
+            view_fn,
+            items: HashMap::new(),
+            free: Vec::new(),
+        }),
+    )
+}
+
+fn virtual_stack_with<T, IF, I, KF, K>(
+    direction: VirtualDirection,
+    item_size: VirtualItemSize<T>,
+    each_fn: IF,
+    key_fn: KF,
+    item_views: ItemViews<T>,
+) -> VirtualStack<T>
+where
+    T: 'static,
+    IF: Fn() -> I + 'static,
+    I: VirtualVector<T>,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + 'static,
 {
     let id = ViewId::new();
 
@@ -127,6 +318,7 @@ where
 
     create_effect(move |prev| {
         let mut items_vector = each_fn();
+        let is_empty = items_vector.is_empty();
         let viewport = viewport.get();
         let min = match direction {
             VirtualDirection::Vertical => viewport.y0,
@@ -183,48 +375,58 @@ where
         };
 
         let hashed_items = items.iter().map(&key_fn).collect::<FxIndexSet<_>>();
-        let (prev_before_size, prev_content_size, diff) =
-            if let Some((prev_before_size, prev_content_size, HashRun(prev_hash_run))) = prev {
-                let mut diff = diff(&prev_hash_run, &hashed_items);
-                let mut items = items
-                    .into_iter()
-                    .map(|i| Some(i))
-                    .collect::<SmallVec<[Option<_>; 128]>>();
-                for added in &mut diff.added {
-                    added.view = Some(items[added.at].take().unwrap());
-                }
-                (prev_before_size, prev_content_size, diff)
-            } else {
-                let mut diff = Diff::default();
-                for (i, item) in items.into_iter().enumerate() {
-                    diff.added.push(DiffOpAdd {
-                        at: i,
-                        view: Some(item),
-                    });
-                }
-                (0.0, 0.0, diff)
-            };
+        let (prev_before_size, prev_content_size, prev_is_empty, diff) = if let Some((
+            prev_before_size,
+            prev_content_size,
+            prev_is_empty,
+            HashRun(prev_hash_run),
+        )) = prev
+        {
+            let mut diff = diff(&prev_hash_run, &hashed_items);
+            let mut items = items
+                .into_iter()
+                .map(|i| Some(i))
+                .collect::<SmallVec<[Option<_>; 128]>>();
+            for added in &mut diff.added {
+                added.view = Some(items[added.at].take().unwrap());
+            }
+            (prev_before_size, prev_content_size, prev_is_empty, diff)
+        } else {
+            let mut diff = Diff::default();
+            for (i, item) in items.into_iter().enumerate() {
+                diff.added.push(DiffOpAdd {
+                    at: i,
+                    view: Some(item),
+                });
+            }
+            (0.0, 0.0, false, diff)
+        };
 
-        if !diff.is_empty() || prev_before_size != before_size || prev_content_size != content_size
+        if !diff.is_empty()
+            || prev_before_size != before_size
+            || prev_content_size != content_size
+            || prev_is_empty != is_empty
         {
             id.update_state(VirtualStackState {
                 diff,
                 before_size,
                 content_size,
+                is_empty,
             });
         }
-        (before_size, content_size, HashRun(hashed_items))
+        (before_size, content_size, is_empty, HashRun(hashed_items))
     });
 
-    let view_fn = Box::new(as_child_of_current_scope(move |e| view_fn(e).into_any()));
-
     VirtualStack {
         id,
         direction,
         children: Vec::new(),
         viewport: Rect::ZERO,
         set_viewport,
-        view_fn,
+        item_views,
+        empty_view: None,
+        error_view: None,
+        is_empty: false,
         phatom: PhantomData,
         before_size: 0.0,
         content_size: 0.0,
@@ -242,27 +444,66 @@ impl<T> View for VirtualStack<T> {
     }
 
     fn update(&mut self, cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
+        let state = match state.downcast::<ErrorChanged>() {
+            Ok(changed) => {
+                if let Some(error_view) = &mut self.error_view {
+                    error_view.set(cx.app_state, *changed);
+                    self.update_children(cx.app_state);
+                }
+                return;
+            }
+            Err(state) => state,
+        };
         if let Ok(state) = state.downcast::<VirtualStackState<T>>() {
             if self.before_size == state.before_size
                 && self.content_size == state.content_size
+                && self.is_empty == state.is_empty
                 && state.diff.is_empty()
             {
                 return;
             }
             self.before_size = state.before_size;
             self.content_size = state.content_size;
-            apply_diff(
-                self.id(),
-                cx.app_state,
-                state.diff,
-                &mut self.children,
-                &self.view_fn,
-            );
-            self.id.request_all();
+            self.is_empty = state.is_empty;
+            match &mut self.item_views {
+                ItemViews::Created(view_fn) => apply_diff_with(
+                    self.id,
+                    cx.app_state,
+                    state.diff,
+                    &mut self.children,
+                    new_child(&*view_fn),
+                    |app_state, _, id, scope| {
+                        app_state.remove_view(id);
+                        scope.dispose();
+                    },
+                ),
+                ItemViews::Recycled(recycler) => {
+                    // the views are released before the added items are bound, so they're
+                    // reused in the same update
+                    let recycler = RefCell::new(recycler);
+                    apply_diff_with(
+                        self.id,
+                        cx.app_state,
+                        state.diff,
+                        &mut self.children,
+                        |item| recycler.borrow_mut().bind(item),
+                        |app_state, _, id, scope| {
+                            recycler.borrow_mut().release(app_state, id, scope)
+                        },
+                    );
+                }
+            }
+            self.update_children(cx.app_state);
         }
     }
 
     fn view_style(&self) -> Option<crate::style::Style> {
+        if self.empty_view.as_ref().is_some_and(EmptyView::is_shown)
+            || self.error_view.as_ref().is_some_and(ErrorView::is_shown)
+        {
+            // Let the placeholder determine the size.
+            return None;
+        }
         let style = match self.direction {
             VirtualDirection::Vertical => Style::new().height(self.content_size),
             VirtualDirection::Horizontal => Style::new().width(self.content_size),
@@ -322,6 +563,16 @@ impl<T> View for VirtualStack<T> {
     }
 }
 
+impl<T> Drop for VirtualStack<T> {
+    fn drop(&mut self) {
+        if let ItemViews::Recycled(recycler) = &self.item_views {
+            for (id, _) in &recycler.free {
+                add_update_message(UpdateMessage::RemoveView { id: *id });
+            }
+        }
+    }
+}
+
 impl<T: Clone> VirtualVector<T> for im::Vector<T> {
     fn total_len(&self) -> usize {
         self.len()
@@ -350,3 +601,49 @@ impl<V: VirtualVector<T>, T> VirtualVector<(usize, T)> for Enumerate<V, T> {
             .map(move |(i, e)| (i + start, e))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+    use crate::{headless::HeadlessHarness, views::text};
+
+    use super::{virtual_stack, VirtualDirection, VirtualItemSize};
+
+    #[test]
+    fn empty_and_error_views_take_the_place_of_the_items() {
+        let items = RwSignal::new(im::Vector::<u32>::new());
+        let error = RwSignal::new(None::<String>);
+        let mut harness = HeadlessHarness::new(move || {
+            virtual_stack(
+                VirtualDirection::Vertical,
+                VirtualItemSize::Fixed(Box::new(|| 20.0)),
+                move || items.get(),
+                |item| *item,
+                text,
+            )
+            .empty_view(|| text("Nothing"))
+            .error_view(move || error.get(), text)
+        });
+        assert!(harness.find_by_text("Nothing").is_some());
+
+        items.set(im::vector![1, 2]);
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Nothing").is_none());
+
+        items.set(im::Vector::new());
+        error.set(Some("Failed".to_string()));
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Nothing").is_none());
+        assert!(harness.find_by_text("Failed").is_some());
+        assert_eq!(harness.root().children().len(), 1);
+
+        error.set(None);
+        harness.advance_time(Duration::ZERO);
+        assert!(harness.find_by_text("Failed").is_none());
+        assert!(harness.find_by_text("Nothing").is_some());
+        assert_eq!(harness.root().children().len(), 1);
+    }
+}
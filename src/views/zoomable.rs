@@ -0,0 +1,277 @@
+#![deny(missing_docs)]
+//! A view that zooms and pans its child.
+
+use floem_reactive::{
+    create_effect, create_rw_signal, ReadSignal, RwSignal, SignalGet, SignalUpdate,
+};
+use peniko::kurbo::{Affine, Point, Rect, Vec2};
+
+use crate::{
+    context::{ComputeLayoutCx, EventCx, PaintCx, UpdateCx},
+    event::{Event, EventPropagation},
+    id::ViewId,
+    view::{IntoView, View},
+};
+
+/// How much a pixel of ctrl + wheel scrolling changes the zoom.
+const WHEEL_ZOOM_SPEED: f64 = 0.002;
+
+/// The zoom a double click on the fitted content zooms into.
+const DOUBLE_CLICK_ZOOM: f64 = 2.0;
+
+enum ZoomState {
+    ZoomTo(f64),
+    Fit,
+}
+
+/// A view that scales and pans its child with pinch gestures, ctrl + wheel and dragging. See [`zoomable`].
+pub struct Zoomable {
+    id: ViewId,
+    child: ViewId,
+    zoom: RwSignal<f64>,
+    min_zoom: f64,
+    max_zoom: f64,
+    /// The offset of the scaled child from its layout rect.
+    pan: Vec2,
+    /// The last pointer position, which pinch gestures zoom around.
+    pointer: Option<Point>,
+    /// The pointer position and pan when a drag of the content started.
+    panning: Option<(Point, Vec2)>,
+}
+
+/// Creates a view that zooms and pans `child`.
+///
+/// The child is laid out to fit the view at a zoom of `1.0`. Touchpad pinch gestures and the
+/// mouse wheel with ctrl held zoom around the pointer, the wheel and dragging pan the zoomed
+/// content, and a double click toggles between fitting the content and zooming into it.
+///
+/// ```rust
+/// # use floem::views::{zoomable, empty, Decorators};
+/// let canvas = zoomable(empty().style(|s| s.size_full()))
+///     .min_zoom(0.5)
+///     .max_zoom(8.0);
+/// let zoom = canvas.zoom();
+/// ```
+pub fn zoomable<V: IntoView + 'static>(child: V) -> Zoomable {
+    let id = ViewId::new();
+    let child = child.into_view();
+    let child_id = child.id();
+    id.set_children(vec![child]);
+
+    Zoomable {
+        id,
+        child: child_id,
+        zoom: create_rw_signal(1.0),
+        min_zoom: 1.0,
+        max_zoom: 10.0,
+        pan: Vec2::ZERO,
+        pointer: None,
+        panning: None,
+    }
+}
+
+impl Zoomable {
+    /// Sets the smallest zoom, `1.0` by default which fits the content in the view.
+    pub fn min_zoom(mut self, min_zoom: f64) -> Self {
+        self.min_zoom = min_zoom;
+        self
+    }
+
+    /// Sets the largest zoom, `10.0` by default.
+    pub fn max_zoom(mut self, max_zoom: f64) -> Self {
+        self.max_zoom = max_zoom;
+        self
+    }
+
+    /// Returns a signal with the current zoom of the view.
+    pub fn zoom(&self) -> ReadSignal<f64> {
+        self.zoom.read_only()
+    }
+
+    /// Zooms around the center of the view.
+    ///
+    /// # Reactivity
+    /// The zoom will update whenever the signals that `zoom` depends on change.
+    pub fn zoom_to(self, zoom: impl Fn() -> f64 + 'static) -> Self {
+        let id = self.id;
+        create_effect(move |_| {
+            let zoom = zoom();
+            id.update_state(ZoomState::ZoomTo(zoom));
+        });
+        self
+    }
+
+    /// Resets the zoom and pan so that the content fits the view.
+    ///
+    /// # Reactivity
+    /// The content is fitted whenever the signals that `fit` depends on change.
+    pub fn fit_when(self, fit: impl Fn() + 'static) -> Self {
+        let id = self.id;
+        create_effect(move |_| {
+            fit();
+            id.update_state(ZoomState::Fit);
+        });
+        self
+    }
+
+    /// The layout rect of the child, relative to this view.
+    fn child_rect(&self) -> Rect {
+        self.child
+            .get_layout()
+            .map(|layout| {
+                Rect::from_origin_size(
+                    (layout.location.x as f64, layout.location.y as f64),
+                    (layout.size.width as f64, layout.size.height as f64),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Zooms to `zoom`, keeping the content under `pos` in place.
+    fn zoom_at(&mut self, pos: Point, zoom: f64) {
+        let current = self.zoom.get_untracked();
+        let zoom = zoom.clamp(self.min_zoom, self.max_zoom);
+        let origin = self.child_rect().origin();
+        let content_pos = (pos - origin - self.pan) / current;
+        self.pan = pos - origin - content_pos * zoom;
+        if zoom != current {
+            self.zoom.set(zoom);
+        }
+        self.apply();
+    }
+
+    /// Clamps the pan so that the zoomed content covers the layout rect of the child, or is
+    /// centered in it when zoomed out, and applies the transform to the child.
+    fn apply(&mut self) {
+        let zoom = self.zoom.get_untracked();
+        let size = self.child_rect().size();
+        let clamp = |pan: f64, size: f64| {
+            let scaled = size * zoom;
+            if scaled >= size {
+                pan.clamp(size - scaled, 0.)
+            } else {
+                (size - scaled) / 2.
+            }
+        };
+        self.pan = Vec2::new(
+            clamp(self.pan.x, size.width),
+            clamp(self.pan.y, size.height),
+        );
+        self.child
+            .set_zoom_transform(Affine::translate(self.pan) * Affine::scale(zoom));
+        self.id.request_paint();
+    }
+
+    fn fit(&mut self) {
+        self.pan = Vec2::ZERO;
+        self.zoom.set(1.0_f64.clamp(self.min_zoom, self.max_zoom));
+        self.apply();
+    }
+
+    fn center(&self) -> Point {
+        self.child_rect().center()
+    }
+}
+
+impl View for Zoomable {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Zoomable".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(state) = state.downcast::<ZoomState>() {
+            match *state {
+                ZoomState::ZoomTo(zoom) => self.zoom_at(self.center(), zoom),
+                ZoomState::Fit => self.fit(),
+            }
+        }
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        let rect = cx.compute_view_layout(self.child);
+        // the child may have been resized, so the pan has to be clamped again
+        self.apply();
+        rect
+    }
+
+    fn event_before_children(&mut self, _cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::PointerMove(event) => {
+                self.pointer = Some(event.pos);
+                if let Some((start, pan)) = self.panning {
+                    self.pan = pan + (event.pos - start);
+                    self.apply();
+                    return EventPropagation::Stop;
+                }
+            }
+            Event::PointerUp(_) if self.panning.is_some() => {
+                self.panning = None;
+                return EventPropagation::Stop;
+            }
+            Event::TouchpadMagnify(event) => {
+                let pos = self.pointer.unwrap_or_else(|| self.center());
+                self.zoom_at(pos, self.zoom.get_untracked() * (1. + event.delta));
+                return EventPropagation::Stop;
+            }
+            Event::PointerWheel(event) if event.modifiers.control() => {
+                let zoom = self.zoom.get_untracked() * (-event.delta.y * WHEEL_ZOOM_SPEED).exp();
+                self.zoom_at(event.pos, zoom);
+                return EventPropagation::Stop;
+            }
+            _ => {}
+        }
+        EventPropagation::Continue
+    }
+
+    fn event_after_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::PointerDown(event) if event.button.is_primary() => {
+                if event.count == 2 {
+                    if self.zoom.get_untracked() == 1.0 {
+                        self.zoom_at(event.pos, DOUBLE_CLICK_ZOOM);
+                    } else {
+                        self.fit();
+                    }
+                    return EventPropagation::Stop;
+                }
+                self.panning = Some((event.pos, self.pan));
+                cx.update_active(self.id);
+                EventPropagation::Stop
+            }
+            Event::PointerWheel(event) if self.zoom.get_untracked() > 1.0 => {
+                let pan = self.pan;
+                self.pan -= event.delta;
+                self.apply();
+                if self.pan == pan {
+                    EventPropagation::Continue
+                } else {
+                    EventPropagation::Stop
+                }
+            }
+            _ => EventPropagation::Continue,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        cx.save();
+        cx.clip(&self.id.get_size().unwrap_or_default().to_rect());
+        cx.paint_children(self.id);
+        cx.restore();
+    }
+}
+
+/// A trait that adds a `zoomable` method to any type that implements `IntoView`.
+pub trait ZoomableExt {
+    /// Wrap the view in a zoomable view.
+    fn zoomable(self) -> Zoomable;
+}
+
+impl<T: IntoView + 'static> ZoomableExt for T {
+    fn zoomable(self) -> Zoomable {
+        zoomable(self)
+    }
+}
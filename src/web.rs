@@ -0,0 +1,355 @@
+#![deny(missing_docs)]
+//! Integration with the browser, on the web.
+//!
+//! The title of the page and the history of the browser can be kept in sync with the
+//! application, with [`sync_document_title`] and [`sync_router_with_history`]. The clipboard
+//! of the browser is used for copying and pasting in the views, and can be read with
+//! [`read_clipboard_text`], as reading it is asynchronous in the browser.
+//!
+//! A canvas configured with
+//! [`WebWindowConfig::fill_parent`](crate::window::WebWindowConfig::fill_parent) follows the
+//! size of its parent element, and the window is resized and redrawn at the device pixel ratio
+//! of the display when it changes.
+//!
+//! Text is typed in the window through a hidden input element, so the virtual keyboards of
+//! mobile browsers and the input methods of browsers can be used in text inputs.
+
+use std::{cell::Cell, rc::Rc};
+
+use floem_reactive::create_effect;
+use floem_winit::window::WindowId;
+use parking_lot::Mutex;
+use peniko::kurbo::Point;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    wasm_bindgen::{closure::Closure, JsCast, JsValue},
+    CompositionEvent, EventTarget, HtmlCanvasElement, HtmlInputElement, InputEvent, KeyboardEvent,
+    KeyboardEventInit,
+};
+
+use crate::{
+    event::Event,
+    router::{Route, Router},
+    window::dispatch_event,
+};
+
+/// The text that was last copied in the application or read from the clipboard of the browser.
+static CLIPBOARD_TEXT: Mutex<String> = Mutex::new(String::new());
+
+/// Sets the title of the page to `title`, whenever it changes.
+pub fn sync_document_title(title: impl Fn() -> String + 'static) {
+    create_effect(move |_| {
+        let title = title();
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            document.set_title(&title);
+        }
+    });
+}
+
+/// The path of the URL of the page, with its query.
+fn location_path() -> Option<String> {
+    let location = web_sys::window()?.location();
+    let path = location.pathname().ok()?;
+    let search = location.search().unwrap_or_default();
+    Some(format!("{path}{search}"))
+}
+
+/// Keeps the URL of the page at the path of the current route of `router`, so routes can be
+/// bookmarked and reloaded, see [`Router::from_startup_link`].
+///
+/// Navigating to a route adds an entry to the history of the browser, and its back and forward
+/// buttons navigate to the route of the entry they go to.
+pub fn sync_router_with_history<R: Route>(router: Router<R>) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let history = window.history().ok();
+    create_effect(move |_| {
+        let path = router.route().to_path();
+        if location_path().as_deref() != Some(path.as_str()) {
+            if let Some(history) = &history {
+                let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&path));
+            }
+        }
+    });
+    let on_pop_state = Closure::<dyn Fn()>::new(move || {
+        if let Some(route) = location_path().and_then(|path| R::from_path(&path)) {
+            router.replace(route);
+        }
+    });
+    let _ =
+        window.add_event_listener_with_callback("popstate", on_pop_state.as_ref().unchecked_ref());
+    // the listener lives as long as the page
+    on_pop_state.forget();
+}
+
+/// Reads the text in the clipboard of the browser, and calls `on_text` with it, or with `None`
+/// if the browser doesn't allow reading it.
+///
+/// Browsers only allow reading the clipboard in response to an input of the user, and may ask
+/// the user for permission first.
+pub fn read_clipboard_text(on_text: impl FnOnce(Option<String>) + 'static) {
+    let Some(window) = web_sys::window() else {
+        on_text(None);
+        return;
+    };
+    let read = window.navigator().clipboard().read_text();
+    spawn_local(async move {
+        let text = JsFuture::from(read)
+            .await
+            .ok()
+            .and_then(|text| text.as_string());
+        if let Some(text) = &text {
+            text.clone_into(&mut CLIPBOARD_TEXT.lock());
+        }
+        on_text(text);
+    });
+}
+
+/// Writes `text` to the clipboard of the browser.
+pub fn write_clipboard_text(text: &str) {
+    if let Some(window) = web_sys::window() {
+        // the promise is settled by the browser, and fails without a user input
+        let _ = window.navigator().clipboard().write_text(text);
+    }
+}
+
+/// The clipboard of the views, which writes to the clipboard of the browser.
+///
+/// Reading the clipboard of the browser is asynchronous, so pasting returns the text that was
+/// last copied in the application, or read with [`read_clipboard_text`].
+pub(crate) struct WebClipboard;
+
+impl copypasta::ClipboardProvider for WebClipboard {
+    fn get_contents(&mut self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(CLIPBOARD_TEXT.lock().clone())
+    }
+
+    fn set_contents(
+        &mut self,
+        text: String,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        write_clipboard_text(&text);
+        *CLIPBOARD_TEXT.lock() = text;
+        Ok(())
+    }
+}
+
+/// Sizes `canvas` to its parent element. The size of the canvas and the device pixel ratio are
+/// observed by the window, which is resized when they change.
+pub(crate) fn fill_parent(canvas: &HtmlCanvasElement) {
+    let style = canvas.style();
+    let _ = style.set_property("display", "block");
+    let _ = style.set_property("width", "100%");
+    let _ = style.set_property("height", "100%");
+}
+
+/// A hidden input element that has the focus of the page while text can be typed in the window,
+/// so the virtual keyboard of mobile browsers is shown, and the text composed with it or with an
+/// input method of the browser is sent to the window as [`Event::ImePreedit`] and
+/// [`Event::ImeCommit`].
+///
+/// The keys that aren't part of a composition are sent to the canvas, which doesn't receive
+/// them while the input has the focus.
+pub(crate) struct ImeInput {
+    input: HtmlInputElement,
+    canvas: HtmlCanvasElement,
+    allowed: Rc<Cell<bool>>,
+    /// The listeners of the input and the canvas, which are removed with the input.
+    listeners: Vec<(EventTarget, &'static str, Closure<dyn Fn(web_sys::Event)>)>,
+}
+
+impl ImeInput {
+    pub(crate) fn new(window_id: WindowId, canvas: HtmlCanvasElement) -> Option<Self> {
+        let document = web_sys::window()?.document()?;
+        let input = document
+            .create_element("input")
+            .ok()?
+            .dyn_into::<HtmlInputElement>()
+            .ok()?;
+        input.set_type("text");
+        let _ = input.set_attribute("autocomplete", "off");
+        let _ = input.set_attribute("autocapitalize", "off");
+        let _ = input.set_attribute("spellcheck", "false");
+        let _ = input.set_attribute("aria-hidden", "true");
+        let style = input.style();
+        let _ = style.set_property("position", "fixed");
+        let _ = style.set_property("width", "1px");
+        let _ = style.set_property("height", "1px");
+        let _ = style.set_property("padding", "0");
+        let _ = style.set_property("border", "0");
+        let _ = style.set_property("opacity", "0");
+        let _ = style.set_property("pointer-events", "none");
+        // smaller text makes mobile browsers zoom into the input when it's focused
+        let _ = style.set_property("font-size", "16px");
+        document.body()?.append_child(&input).ok()?;
+
+        let allowed = Rc::new(Cell::new(false));
+        let mut ime_input = Self {
+            input,
+            canvas,
+            allowed,
+            listeners: Vec::new(),
+        };
+
+        ime_input.listen_input("compositionupdate", move |event, _| {
+            if let Some(text) = event
+                .dyn_ref::<CompositionEvent>()
+                .and_then(|event| event.data())
+            {
+                let cursor = Some((text.len(), text.len()));
+                dispatch_event(window_id, Event::ImePreedit { text, cursor });
+            }
+        });
+        ime_input.listen_input("compositionend", move |event, input| {
+            let text = event
+                .dyn_ref::<CompositionEvent>()
+                .and_then(|event| event.data())
+                .unwrap_or_default();
+            dispatch_event(
+                window_id,
+                Event::ImePreedit {
+                    text: String::new(),
+                    cursor: None,
+                },
+            );
+            if !text.is_empty() {
+                dispatch_event(window_id, Event::ImeCommit(text));
+            }
+            input.set_value("");
+        });
+        let canvas = ime_input.canvas.clone();
+        ime_input.listen_input("input", move |event, input| {
+            let Some(event) = event.dyn_ref::<InputEvent>() else {
+                return;
+            };
+            if event.is_composing() {
+                return;
+            }
+            match event.input_type().as_str() {
+                "insertText" | "insertReplacementText" => {
+                    if let Some(text) = event.data().filter(|text| !text.is_empty()) {
+                        dispatch_event(window_id, Event::ImeCommit(text));
+                    }
+                }
+                // virtual keyboards delete without a key that can be sent to the canvas
+                "deleteContentBackward" => send_key(&canvas, "Backspace", "Backspace"),
+                "deleteContentForward" => send_key(&canvas, "Delete", "Delete"),
+                _ => {}
+            }
+            input.set_value("");
+        });
+        let canvas = ime_input.canvas.clone();
+        let on_key = move |event: web_sys::Event, _: &HtmlInputElement| {
+            let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+                return;
+            };
+            // the keys of a composition, or of virtual keyboards, are handled as input
+            if event.is_composing() || matches!(event.key().as_str(), "Process" | "Unidentified") {
+                return;
+            }
+            if let Some(key_event) = copy_key_event(event) {
+                event.prevent_default();
+                let _ = canvas.dispatch_event(&key_event);
+            }
+        };
+        ime_input.listen_input("keydown", on_key.clone());
+        ime_input.listen_input("keyup", on_key);
+
+        // the canvas takes the focus when it's clicked, which is given back to the input
+        let input = ime_input.input.clone();
+        let allowed = ime_input.allowed.clone();
+        ime_input.listen(ime_input.canvas.clone().into(), "focus", move |_| {
+            if allowed.get() {
+                let _ = input.focus();
+            }
+        });
+
+        Some(ime_input)
+    }
+
+    fn listen_input(
+        &mut self,
+        name: &'static str,
+        on_event: impl Fn(web_sys::Event, &HtmlInputElement) + 'static,
+    ) {
+        let input = self.input.clone();
+        self.listen(self.input.clone().into(), name, move |event| {
+            on_event(event, &input)
+        });
+    }
+
+    fn listen(
+        &mut self,
+        target: EventTarget,
+        name: &'static str,
+        on_event: impl Fn(web_sys::Event) + 'static,
+    ) {
+        let closure = Closure::<dyn Fn(web_sys::Event)>::new(on_event);
+        let _ = target.add_event_listener_with_callback(name, closure.as_ref().unchecked_ref());
+        self.listeners.push((target, name, closure));
+    }
+
+    /// Focuses the input when text can be typed, and gives the focus back to the canvas when it
+    /// can't.
+    pub(crate) fn set_allowed(&self, allowed: bool) {
+        if self.allowed.replace(allowed) == allowed {
+            return;
+        }
+        if allowed {
+            let _ = self.input.focus();
+        } else {
+            self.input.set_value("");
+            let _ = self.canvas.focus();
+        }
+    }
+
+    /// Moves the input to `position` in the window, so the candidates of an input method are
+    /// shown next to the text.
+    pub(crate) fn set_cursor_area(&self, position: Point) {
+        let rect = self.canvas.get_bounding_client_rect();
+        let style = self.input.style();
+        let _ = style.set_property("left", &format!("{}px", rect.left() + position.x));
+        let _ = style.set_property("top", &format!("{}px", rect.top() + position.y));
+    }
+}
+
+impl Drop for ImeInput {
+    fn drop(&mut self) {
+        for (target, name, closure) in self.listeners.drain(..) {
+            let _ =
+                target.remove_event_listener_with_callback(name, closure.as_ref().unchecked_ref());
+        }
+        self.input.remove();
+    }
+}
+
+/// A copy of `event` that can be dispatched to another element.
+fn copy_key_event(event: &KeyboardEvent) -> Option<KeyboardEvent> {
+    let init = KeyboardEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_key(&event.key());
+    init.set_code(&event.code());
+    init.set_location(event.location());
+    init.set_repeat(event.repeat());
+    init.set_alt_key(event.alt_key());
+    init.set_ctrl_key(event.ctrl_key());
+    init.set_meta_key(event.meta_key());
+    init.set_shift_key(event.shift_key());
+    KeyboardEvent::new_with_keyboard_event_init_dict(&event.type_(), &init).ok()
+}
+
+/// Sends a press and release of the key `key` to the canvas.
+fn send_key(canvas: &HtmlCanvasElement, key: &str, code: &str) {
+    for name in ["keydown", "keyup"] {
+        let init = KeyboardEventInit::new();
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        init.set_key(key);
+        init.set_code(code);
+        if let Ok(event) = KeyboardEvent::new_with_keyboard_event_init_dict(name, &init) {
+            let _ = canvas.dispatch_event(&event);
+        }
+    }
+}
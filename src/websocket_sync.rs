@@ -0,0 +1,333 @@
+#![deny(missing_docs)]
+//! Syncing signals with a server over a WebSocket.
+//!
+//! A [`WebSocketSync`] connects a set of signals to a WebSocket endpoint. Each change of one of
+//! the signals is sent to the server as a [`Patch`], and the patches sent by the server set the
+//! signals, so a dashboard or a collaborative view shows the same state as the server.
+//!
+//! Each message is a single [`Patch`] encoded as JSON, like
+//! `{"key":"count","value":3}`. The initial values of the signals aren't sent, the server is
+//! expected to send the values it has when a client connects. When the connection is lost it's
+//! opened again, and the patches of the changes made meanwhile are sent once it's back, only the
+//! latest one for each signal.
+//!
+//! ```no_run
+//! # use floem::reactive::create_rw_signal;
+//! # use floem::websocket_sync::WebSocketSync;
+//! let count = create_rw_signal(0);
+//! let title = create_rw_signal(String::new());
+//! // syncs the signals until the current scope is disposed
+//! WebSocketSync::new("ws://localhost:9000/state")
+//!     .signal("count", count)
+//!     .signal("title", title)
+//!     .connect();
+//! ```
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    fmt, io,
+    net::TcpStream,
+    rc::Rc,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, TryRecvError};
+use floem_reactive::{
+    with_scope, ReadSignal, RwSignal, Scope, SignalGet, SignalUpdate, SignalWith,
+};
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tungstenite::{stream::MaybeTlsStream, Message, WebSocket};
+
+use crate::ext_event::{register_ext_trigger, ExtSendTrigger};
+
+/// How long the connection waits for a message before it sends the pending patches.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long the connection waits before reconnecting the first time, which doubles with each
+/// failed attempt up to [`MAX_BACKOFF`].
+const MIN_BACKOFF: Duration = Duration::from_millis(500);
+
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// A change of a synced signal, sent in both directions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Patch {
+    /// The key that the signal was added with, see [`WebSocketSync::signal`].
+    pub key: String,
+    /// The new value of the signal.
+    pub value: Value,
+}
+
+/// The state of the connection to the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStatus {
+    /// The connection is being opened.
+    Connecting,
+    /// The signals are synced with the server.
+    Connected,
+    /// The connection was lost or couldn't be opened, and is opened again after a delay.
+    Disconnected,
+}
+
+struct SyncedSignal {
+    key: String,
+    /// Serializes the value of the signal, tracking it.
+    read: Box<dyn Fn() -> Option<Value>>,
+    /// Sets the signal to a value sent by the server.
+    write: Box<dyn Fn(Value)>,
+}
+
+/// A builder for the signals to sync with the WebSocket endpoint at a URL.
+pub struct WebSocketSync {
+    url: String,
+    signals: Vec<SyncedSignal>,
+}
+
+impl WebSocketSync {
+    /// Creates a builder for the endpoint at `url`, like `ws://localhost:9000/state`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            signals: Vec::new(),
+        }
+    }
+
+    /// Syncs `signal` with the value that the server has for `key`.
+    ///
+    /// Patches whose value can't be deserialized into a `T` are ignored.
+    pub fn signal<T>(mut self, key: impl Into<String>, signal: RwSignal<T>) -> Self
+    where
+        T: Serialize + DeserializeOwned + 'static,
+    {
+        self.signals.push(SyncedSignal {
+            key: key.into(),
+            read: Box::new(move || signal.with(|value| serde_json::to_value(value).ok())),
+            write: Box::new(move |value| {
+                if let Ok(value) = serde_json::from_value(value) {
+                    signal.set(value);
+                }
+            }),
+        });
+        self
+    }
+
+    /// Connects to the server on a background thread, syncing the signals until the current
+    /// scope is disposed or [`SyncConnection::disconnect`] is called.
+    pub fn connect(self) -> SyncConnection {
+        let cx = Scope::current().create_child();
+        let status = cx.create_rw_signal(SyncStatus::Connecting);
+        let (commands, receiver) = crossbeam_channel::unbounded();
+        // the values last received for each key, so setting them isn't sent back to the server
+        let received = Rc::new(RefCell::new(HashMap::<String, Value>::new()));
+
+        let mut writers = HashMap::new();
+        for SyncedSignal { key, read, write } in self.signals {
+            let commands = commands.clone();
+            let received = received.clone();
+            let patch_key = key.clone();
+            cx.create_effect(move |prev: Option<()>| {
+                let Some(value) = read() else {
+                    return;
+                };
+                let echo = received
+                    .borrow_mut()
+                    .remove(&patch_key)
+                    .is_some_and(|received| received == value);
+                if prev.is_some() && !echo {
+                    let _ = commands.send(Patch {
+                        key: patch_key.clone(),
+                        value,
+                    });
+                }
+            });
+            writers.insert(key, write);
+        }
+
+        let trigger = with_scope(cx, ExtSendTrigger::new);
+        let inbox = Arc::new(Mutex::new(VecDeque::new()));
+        {
+            let inbox = inbox.clone();
+            cx.create_effect(move |_| {
+                trigger.track();
+                // the connection stops when the scope is disposed, dropping the last sender
+                let _ = &commands;
+                let incoming = std::mem::take(&mut *inbox.lock());
+                for incoming in incoming {
+                    match incoming {
+                        Incoming::Patch(Patch { key, value }) => {
+                            if let Some(write) = writers.get(&key) {
+                                received.borrow_mut().insert(key, value.clone());
+                                write(value);
+                            }
+                        }
+                        Incoming::Status(new_status) => status.set(new_status),
+                    }
+                }
+            });
+        }
+
+        let deliver = move |incoming| {
+            inbox.lock().push_back(incoming);
+            register_ext_trigger(trigger);
+        };
+        let url = self.url;
+        let _ = thread::Builder::new()
+            .name("floem-websocket-sync".to_string())
+            .spawn(move || run(&url, &receiver, &deliver));
+
+        SyncConnection {
+            scope: cx,
+            status: status.read_only(),
+        }
+    }
+}
+
+/// The connection of a [`WebSocketSync`].
+#[derive(Clone, Copy)]
+pub struct SyncConnection {
+    scope: Scope,
+    status: ReadSignal<SyncStatus>,
+}
+
+impl fmt::Debug for SyncConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncConnection")
+            .field("status", &self.status.get_untracked())
+            .finish()
+    }
+}
+
+impl SyncConnection {
+    /// The state of the connection, to show whether the signals are synced.
+    pub fn status(&self) -> ReadSignal<SyncStatus> {
+        self.status
+    }
+
+    /// Stops syncing the signals and closes the connection.
+    pub fn disconnect(&self) {
+        self.scope.dispose();
+    }
+}
+
+enum Incoming {
+    Patch(Patch),
+    Status(SyncStatus),
+}
+
+/// Why a connection ended.
+enum Ended {
+    /// The signals aren't synced anymore.
+    Stopped,
+    /// The connection was lost, and is opened again.
+    Lost,
+}
+
+/// Keeps the connection open until the signals aren't synced anymore.
+fn run(url: &str, patches: &Receiver<Patch>, deliver: &impl Fn(Incoming)) {
+    let mut pending = Vec::new();
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        deliver(Incoming::Status(SyncStatus::Connecting));
+        if let Ok((mut socket, _)) = tungstenite::connect(url) {
+            backoff = MIN_BACKOFF;
+            if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+                let _ = stream.set_read_timeout(Some(POLL_INTERVAL));
+            }
+            deliver(Incoming::Status(SyncStatus::Connected));
+            if let Ended::Stopped = session(&mut socket, patches, &mut pending, deliver) {
+                let _ = socket.close(None);
+                let _ = socket.flush();
+                return;
+            }
+        }
+        deliver(Incoming::Status(SyncStatus::Disconnected));
+
+        let reconnect_at = Instant::now() + backoff;
+        loop {
+            match patches.recv_deadline(reconnect_at) {
+                Ok(patch) => buffer(&mut pending, patch),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+fn session(
+    socket: &mut WebSocket<MaybeTlsStream<TcpStream>>,
+    patches: &Receiver<Patch>,
+    pending: &mut Vec<Patch>,
+    deliver: &impl Fn(Incoming),
+) -> Ended {
+    loop {
+        loop {
+            match patches.try_recv() {
+                Ok(patch) => buffer(pending, patch),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Ended::Stopped,
+            }
+        }
+        while let Some(patch) = pending.first() {
+            let Ok(text) = serde_json::to_string(patch) else {
+                pending.remove(0);
+                continue;
+            };
+            // the patch stays buffered if it can't be sent, and is sent again after reconnecting
+            if socket.send(Message::text(text)).is_err() {
+                return Ended::Lost;
+            }
+            pending.remove(0);
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                if let Ok(patch) = serde_json::from_str::<Patch>(&text) {
+                    deliver(Incoming::Patch(patch));
+                }
+            }
+            Ok(Message::Close(_)) => return Ended::Lost,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(err))
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(_) => return Ended::Lost,
+        }
+    }
+}
+
+/// Adds `patch` to the patches waiting to be sent, replacing an older patch of the same signal.
+fn buffer(pending: &mut Vec<Patch>, patch: Patch) {
+    pending.retain(|pending| pending.key != patch.key);
+    pending.push(patch);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_latest_patch_of_a_signal_is_buffered() {
+        let patch = |key: &str, value: i32| Patch {
+            key: key.to_string(),
+            value: value.into(),
+        };
+        let mut pending = Vec::new();
+        buffer(&mut pending, patch("count", 1));
+        buffer(&mut pending, patch("title", 2));
+        buffer(&mut pending, patch("count", 3));
+        assert_eq!(pending, [patch("title", 2), patch("count", 3)]);
+
+        assert_eq!(
+            serde_json::to_string(&pending[1]).unwrap(),
+            r#"{"key":"count","value":3}"#
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use floem_reactive::{ReadSignal, SignalUpdate, WriteSignal};
 pub use floem_winit::window::Fullscreen;
 pub use floem_winit::window::Icon;
 pub use floem_winit::window::ResizeDirection;
@@ -7,7 +8,9 @@ pub use floem_winit::window::WindowId;
 pub use floem_winit::window::WindowLevel;
 use peniko::kurbo::{Point, Size};
 
-use crate::app::{add_app_update_event, AppUpdateEvent};
+use crate::app::{add_app_update_event, app_scope, AppUpdateEvent};
+use crate::event::Event;
+use crate::id::ViewId;
 use crate::view::IntoView;
 
 /// Configures various attributes (e.g. size, position, transparency, etc.) of a window.
@@ -27,8 +30,22 @@ pub struct WindowConfig {
     pub(crate) undecorated: bool,
     pub(crate) undecorated_shadow: bool,
     pub(crate) window_level: WindowLevel,
+    pub(crate) backdrop: WindowBackdrop,
+    pub(crate) corners: WindowCorners,
+    pub(crate) skip_taskbar: bool,
+    /// The key the geometry of the window is saved under, see
+    /// [`WindowConfig::with_persisted_geometry`].
+    pub(crate) persisted_geometry: Option<String>,
     pub(crate) apply_default_theme: bool,
+    pub(crate) color_scheme_themes: Option<(String, String)>,
     pub(crate) font_embolden: f32,
+    pub(crate) zoom_shortcuts: bool,
+    pub(crate) default_font_family: Option<String>,
+    pub(crate) default_font_size: Option<f64>,
+    /// The window that owns this one, see [`new_child_window`].
+    pub(crate) parent: Option<WindowId>,
+    /// Whether the parent ignores input while this window is open.
+    pub(crate) modal: bool,
     #[allow(dead_code)]
     pub(crate) mac_os_config: Option<MacOSWindowConfig>,
     pub(crate) web_config: Option<WebWindowConfig>,
@@ -51,8 +68,18 @@ impl Default for WindowConfig {
             undecorated: false,
             undecorated_shadow: false,
             window_level: WindowLevel::Normal,
+            backdrop: WindowBackdrop::None,
+            corners: WindowCorners::Default,
+            skip_taskbar: false,
+            persisted_geometry: None,
             apply_default_theme: true,
+            color_scheme_themes: None,
             font_embolden: if cfg!(target_os = "macos") { 0.2 } else { 0. },
+            zoom_shortcuts: false,
+            default_font_family: None,
+            default_font_size: None,
+            parent: None,
+            modal: false,
             mac_os_config: None,
             web_config: None,
         }
@@ -184,8 +211,52 @@ impl WindowConfig {
         self
     }
 
-    /// If set to true, the stylesheet for Floem's default theme will be
-    /// injected into your window. You may want to disable this when using a
+    /// Sets the material drawn behind the window. The window has to be transparent, see
+    /// [`with_transparent`](Self::with_transparent), and the background of its root view has
+    /// to be translucent for the material to show.
+    ///
+    /// The default is `WindowBackdrop::None`.
+    #[inline]
+    pub fn backdrop(mut self, backdrop: WindowBackdrop) -> Self {
+        self.backdrop = backdrop;
+        self
+    }
+
+    /// Sets how the corners of the window are rounded. Only supported on Windows 11.
+    ///
+    /// The default is `WindowCorners::Default`.
+    #[inline]
+    pub fn corners(mut self, corners: WindowCorners) -> Self {
+        self.corners = corners;
+        self
+    }
+
+    /// Sets whether the window is left out of the taskbar. Only supported on Windows.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn skip_taskbar(mut self, skip_taskbar: bool) -> Self {
+        self.skip_taskbar = skip_taskbar;
+        self
+    }
+
+    /// Saves the size, position and maximized state of the window under `key` when it is
+    /// closed, and restores them when a window with the same key is created, overriding
+    /// [`size`](Self::size) and [`position`](Self::position). A saved position that is no longer
+    /// on a monitor is ignored, and a saved size is reduced to fit the monitor.
+    ///
+    /// The geometry is saved in a file in the configuration directory of the platform, unless
+    /// another storage is set with
+    /// [`set_geometry_storage`](crate::window_geometry::set_geometry_storage).
+    #[inline]
+    pub fn with_persisted_geometry(mut self, key: impl Into<String>) -> Self {
+        self.persisted_geometry = Some(key.into());
+        self
+    }
+
+    /// If set to true, the stylesheet of the active [theme](crate::theme::Theme)
+    /// (Floem's default theme unless switched with [`set_theme`](crate::theme::set_theme))
+    /// will be injected into your window. You may want to disable this when using a
     /// completely custom theme.
     #[inline]
     pub fn apply_default_theme(mut self, apply_default_theme: bool) -> Self {
@@ -193,6 +264,21 @@ impl WindowConfig {
         self
     }
 
+    /// Switches the active [theme](crate::theme::Theme) to the theme registered as `light`
+    /// or `dark` whenever the color scheme of the operating system changes, and once when
+    /// the window is created.
+    ///
+    /// The default is to leave the active theme alone.
+    #[inline]
+    pub fn follow_system_color_scheme(
+        mut self,
+        light: impl Into<String>,
+        dark: impl Into<String>,
+    ) -> Self {
+        self.color_scheme_themes = Some((light.into(), dark.into()));
+        self
+    }
+
     /// Sets the amount by which fonts are emboldened.
     ///
     /// The default is 0.0 except for on macOS where the default is 0.2
@@ -202,6 +288,35 @@ impl WindowConfig {
         self
     }
 
+    /// If set to true, the window is zoomed in with Ctrl and `+` or `=`, zoomed out with Ctrl
+    /// and `-`, and the zoom is reset with Ctrl and `0`, with Cmd instead of Ctrl on macOS.
+    /// The default is false, see [`WindowIdExt::set_ui_scale`](crate::WindowIdExt::set_ui_scale)
+    /// to zoom the window from the application.
+    #[inline]
+    pub fn zoom_shortcuts(mut self, zoom_shortcuts: bool) -> Self {
+        self.zoom_shortcuts = zoom_shortcuts;
+        self
+    }
+
+    /// Sets the font family of the text in the window that doesn't set its own, which can be a
+    /// family list or a [fallback chain](crate::fonts::set_fallback_chain).
+    ///
+    /// The default is the font family of the theme.
+    #[inline]
+    pub fn default_font_family(mut self, family: impl Into<String>) -> Self {
+        self.default_font_family = Some(family.into());
+        self
+    }
+
+    /// Sets the font size of the text in the window that doesn't set its own.
+    ///
+    /// The default is the font size of the theme.
+    #[inline]
+    pub fn default_font_size(mut self, size: f64) -> Self {
+        self.default_font_size = Some(size);
+        self
+    }
+
     /// Set up Mac-OS specific configuration.  The passed closure will only be
     /// called on macOS.
     #[allow(unused_variables, unused_mut)] // build will complain on non-macOS's otherwise
@@ -227,9 +342,7 @@ impl WindowConfig {
         if let Some(existing_config) = self.web_config {
             self.web_config = Some(f(existing_config))
         } else {
-            let new_config = f(WebWindowConfig {
-                canvas_id: String::new(),
-            });
+            let new_config = f(WebWindowConfig::default());
             self.web_config = Some(new_config);
         }
         self
@@ -368,6 +481,7 @@ impl From<MacOsOptionAsAlt> for floem_winit::platform::macos::OptionAsAlt {
 pub struct WebWindowConfig {
     /// The id of the HTML canvas element that floem should render to.
     pub(crate) canvas_id: String,
+    pub(crate) fill_parent: bool,
 }
 
 impl WebWindowConfig {
@@ -376,6 +490,66 @@ impl WebWindowConfig {
         self.canvas_id = val.into();
         self
     }
+
+    /// Sizes the canvas to its parent element, so the window follows the size of the element,
+    /// e.g. the size of the page when the canvas is in the body, without any JavaScript.
+    pub fn fill_parent(mut self, fill_parent: bool) -> Self {
+        self.fill_parent = fill_parent;
+        self
+    }
+}
+
+/// The material drawn behind a transparent window, see [`WindowConfig::backdrop`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WindowBackdrop {
+    /// Only the content of the window is drawn.
+    #[default]
+    None,
+    /// Blurs what is behind the window. This is the acrylic material on Windows 11, vibrancy
+    /// on macOS and the blur of the compositor on KDE Plasma under Wayland.
+    Blur,
+    /// The mica material of Windows 11, which is tinted with the desktop background. It falls
+    /// back to [`WindowBackdrop::Blur`] on the other platforms.
+    Mica,
+}
+
+/// How the corners of a window are rounded, see [`WindowConfig::corners`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WindowCorners {
+    /// The corners chosen by the system.
+    #[default]
+    Default,
+    /// Rounded corners.
+    Round,
+    /// Corners with a smaller radius.
+    RoundSmall,
+    /// Square corners.
+    Square,
+}
+
+/// A caption button of a custom title bar, see
+/// [`Decorators::window_caption_button`](crate::views::Decorators::window_caption_button).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaptionButton {
+    /// Minimizes the window.
+    Minimize,
+    /// Maximizes the window, or restores it when it is maximized.
+    Maximize,
+    /// Closes the window.
+    Close,
+}
+
+/// An open window of the application, see [`Application::windows`](crate::Application::windows).
+#[derive(Debug, Clone)]
+pub struct WindowInfo {
+    /// The id of the window.
+    pub id: WindowId,
+    /// The title of the window.
+    pub title: String,
+    /// The root view of the window.
+    pub root: ViewId,
+    /// Whether the window has the keyboard focus.
+    pub focused: bool,
 }
 
 /// create a new window. You'll need to create Application first, otherwise it
@@ -390,7 +564,88 @@ pub fn new_window<V: IntoView + 'static>(
     });
 }
 
+/// Create a new window that is owned by the `parent` window: it stays above the parent and is
+/// minimized together with it. If `modal` is `true`, the parent ignores input until the child
+/// window is closed, which is what preference dialogs and confirmations usually want.
+///
+/// The view function gets a [`WindowResult`] to close the window with a result, which the
+/// returned signal is set to. The signal stays `None` if the window is closed without a result.
+///
+/// ```no_run
+/// # use floem::{new_child_window, window::WindowId, views::{button, Decorators}};
+/// # fn confirm(parent: WindowId) {
+/// let confirmed = new_child_window(
+///     parent,
+///     true,
+///     |_, result| button("Delete").action(move || result.close_with(true)),
+///     None,
+/// );
+/// # }
+/// ```
+pub fn new_child_window<T: 'static, V: IntoView + 'static>(
+    parent: WindowId,
+    modal: bool,
+    app_view: impl FnOnce(WindowId, WindowResult<T>) -> V + 'static,
+    config: Option<WindowConfig>,
+) -> ReadSignal<Option<T>> {
+    // the result has to outlive the scope of the child window
+    let (result, set_result) = app_scope().create_signal(None);
+    let config = WindowConfig {
+        parent: Some(parent),
+        modal,
+        ..config.unwrap_or_default()
+    };
+    new_window(
+        move |window_id| {
+            app_view(
+                window_id,
+                WindowResult {
+                    window_id,
+                    result: set_result,
+                },
+            )
+        },
+        Some(config),
+    );
+    result
+}
+
+/// Closes a window created with [`new_child_window`] with a result.
+pub struct WindowResult<T> {
+    window_id: WindowId,
+    result: WriteSignal<Option<T>>,
+}
+
+impl<T> Clone for WindowResult<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for WindowResult<T> {}
+
+impl<T: 'static> WindowResult<T> {
+    /// The id of the window.
+    pub fn window_id(&self) -> WindowId {
+        self.window_id
+    }
+
+    /// Sets the result of the window to `value` and closes it.
+    pub fn close_with(&self, value: T) {
+        self.result.set(Some(value));
+        close_window(self.window_id);
+    }
+}
+
 /// request the window to be closed
 pub fn close_window(window_id: WindowId) {
     add_app_update_event(AppUpdateEvent::CloseWindow { window_id });
 }
+
+/// Send `event` to a window as if it came from the platform, so it goes through the normal
+/// event dispatch of the window. Positions are in logical pixels relative to the window.
+///
+/// To send an event to a specific view, use [`ViewId::send_event`](crate::ViewId::send_event).
+pub fn dispatch_event(window_id: WindowId, event: Event) {
+    add_app_update_event(AppUpdateEvent::DispatchEvent { window_id, event });
+}
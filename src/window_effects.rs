@@ -0,0 +1,63 @@
+//! The backdrop and corners of windows, which are set through the platform as winit doesn't
+//! support them on all platforms.
+
+use floem_winit::window::Window;
+
+use crate::window::{WindowBackdrop, WindowCorners};
+
+#[cfg(target_os = "windows")]
+pub(crate) fn set_backdrop(window: &Window, backdrop: WindowBackdrop) {
+    use windows_sys::Win32::Graphics::Dwm::{
+        DWMSBT_MAINWINDOW, DWMSBT_NONE, DWMSBT_TRANSIENTWINDOW, DWMWA_SYSTEMBACKDROP_TYPE,
+    };
+
+    let value = match backdrop {
+        WindowBackdrop::None => DWMSBT_NONE,
+        WindowBackdrop::Blur => DWMSBT_TRANSIENTWINDOW,
+        WindowBackdrop::Mica => DWMSBT_MAINWINDOW,
+    };
+    set_dwm_attribute(window, DWMWA_SYSTEMBACKDROP_TYPE, value);
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn set_backdrop(window: &Window, backdrop: WindowBackdrop) {
+    window.set_blur(backdrop != WindowBackdrop::None);
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn set_corners(window: &Window, corners: WindowCorners) {
+    use windows_sys::Win32::Graphics::Dwm::{
+        DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT, DWMWCP_DONOTROUND, DWMWCP_ROUND,
+        DWMWCP_ROUNDSMALL,
+    };
+
+    let value = match corners {
+        WindowCorners::Default => DWMWCP_DEFAULT,
+        WindowCorners::Round => DWMWCP_ROUND,
+        WindowCorners::RoundSmall => DWMWCP_ROUNDSMALL,
+        WindowCorners::Square => DWMWCP_DONOTROUND,
+    };
+    set_dwm_attribute(window, DWMWA_WINDOW_CORNER_PREFERENCE, value);
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn set_corners(_window: &Window, _corners: WindowCorners) {}
+
+/// Sets an attribute of the desktop window manager. Windows versions without the attribute
+/// return an error, which is ignored as the effects are only hints.
+#[cfg(target_os = "windows")]
+fn set_dwm_attribute(window: &Window, attribute: i32, value: i32) {
+    use windows_sys::Win32::Graphics::Dwm::DwmSetWindowAttribute;
+
+    let Some(hwnd) = crate::snap_layout::hwnd(window) else {
+        return;
+    };
+    unsafe {
+        DwmSetWindowAttribute(
+            hwnd,
+            attribute,
+            &value as *const i32 as *const std::ffi::c_void,
+            std::mem::size_of::<i32>() as u32,
+        );
+    }
+}
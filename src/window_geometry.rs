@@ -0,0 +1,240 @@
+#![deny(missing_docs)]
+//! Saving the geometry of windows when they are closed and restoring it when they are opened
+//! again, see [`WindowConfig::with_persisted_geometry`](crate::window::WindowConfig::with_persisted_geometry).
+
+use std::{cell::RefCell, collections::HashMap, path::PathBuf, rc::Rc};
+
+use peniko::kurbo::{Point, Rect, Size};
+
+/// How much of a restored window has to be on a monitor, in physical pixels, for its saved
+/// position to be used.
+const MIN_VISIBLE: f64 = 50.0;
+
+thread_local! {
+    static STORAGE: RefCell<Option<Rc<dyn GeometryStorage>>> = RefCell::new(None);
+}
+
+/// The geometry of a window, in physical pixels on the desktop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    /// The position of the window including its frame.
+    pub position: Point,
+    /// The size of the content of the window. For a maximized window this is the size it is
+    /// restored to.
+    pub size: Size,
+    /// Whether the window is maximized.
+    pub maximized: bool,
+}
+
+/// Where the geometry of the windows is saved, see [`set_geometry_storage`].
+pub trait GeometryStorage {
+    /// Returns the geometry saved under `key`.
+    fn load(&self, key: &str) -> Option<WindowGeometry>;
+    /// Saves the geometry of a window under `key`.
+    fn save(&self, key: &str, geometry: WindowGeometry);
+}
+
+/// Stores the geometry of the windows in a text file, which is the default storage.
+pub struct FileGeometryStorage {
+    path: PathBuf,
+}
+
+impl FileGeometryStorage {
+    /// Creates a storage that uses the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The file used by default: `window-geometry` in a directory named after the executable,
+    /// in the configuration directory of the platform.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(app_config_dir()?.join("window-geometry"))
+    }
+
+    fn read(&self) -> HashMap<String, WindowGeometry> {
+        std::fs::read_to_string(&self.path)
+            .map(|content| content.lines().filter_map(parse_line).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// The directory named after the executable in the configuration directory of the platform,
+/// where the state of the application is saved by default.
+pub(crate) fn app_config_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let name = exe.file_stem()?;
+    #[cfg(target_os = "windows")]
+    let dir = PathBuf::from(std::env::var_os("APPDATA")?);
+    #[cfg(target_os = "macos")]
+    let dir = PathBuf::from(std::env::var_os("HOME")?).join("Library/Application Support");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join(name))
+}
+
+/// Parses a line of the file, which is the geometry followed by the key.
+fn parse_line(line: &str) -> Option<(String, WindowGeometry)> {
+    let mut fields = line.splitn(6, ' ');
+    let mut number = || fields.next()?.parse::<f64>().ok();
+    let (x, y, width, height) = (number()?, number()?, number()?, number()?);
+    let maximized = fields.next()? == "1";
+    let key = fields.next()?;
+    Some((
+        key.to_string(),
+        WindowGeometry {
+            position: Point::new(x, y),
+            size: Size::new(width, height),
+            maximized,
+        },
+    ))
+}
+
+impl GeometryStorage for FileGeometryStorage {
+    fn load(&self, key: &str) -> Option<WindowGeometry> {
+        self.read().remove(key)
+    }
+
+    fn save(&self, key: &str, geometry: WindowGeometry) {
+        let mut windows = self.read();
+        windows.insert(key.replace('\n', " "), geometry);
+        let content: String = windows
+            .iter()
+            .map(|(key, geometry)| {
+                format!(
+                    "{} {} {} {} {} {key}\n",
+                    geometry.position.x,
+                    geometry.position.y,
+                    geometry.size.width,
+                    geometry.size.height,
+                    if geometry.maximized { 1 } else { 0 },
+                )
+            })
+            .collect();
+        if let Some(dir) = self.path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(&self.path, content);
+    }
+}
+
+/// Sets where the geometry of the windows is saved, instead of the [`FileGeometryStorage`] at
+/// its [default path](FileGeometryStorage::default_path).
+pub fn set_geometry_storage(storage: impl GeometryStorage + 'static) {
+    STORAGE.with_borrow_mut(|current| *current = Some(Rc::new(storage)));
+}
+
+pub(crate) fn storage() -> Option<Rc<dyn GeometryStorage>> {
+    STORAGE.with_borrow_mut(|storage| {
+        if storage.is_none() {
+            *storage = FileGeometryStorage::default_path()
+                .map(|path| Rc::new(FileGeometryStorage::new(path)) as Rc<dyn GeometryStorage>);
+        }
+        storage.clone()
+    })
+}
+
+/// Fits saved geometry to the current monitors, which may have changed since it was saved.
+///
+/// The saved position is kept if enough of the window is on a monitor, and moved so that the
+/// window is within that monitor. Otherwise there is no position, so that the platform places
+/// the window. The size is reduced to fit the monitor.
+pub(crate) fn fit_to_monitors(
+    geometry: WindowGeometry,
+    monitors: &[Rect],
+) -> (Option<Point>, Size) {
+    let window = Rect::from_origin_size(geometry.position, geometry.size);
+    let visible = monitors
+        .iter()
+        .map(|monitor| (monitor, monitor.intersect(window)))
+        .filter(|(_, visible)| visible.width() >= MIN_VISIBLE && visible.height() >= MIN_VISIBLE)
+        .max_by(|(_, a), (_, b)| a.area().total_cmp(&b.area()))
+        .map(|(monitor, _)| *monitor);
+    let Some(monitor) = visible.or_else(|| monitors.first().copied()) else {
+        return (Some(geometry.position), geometry.size);
+    };
+    let size = Size::new(
+        geometry.size.width.min(monitor.width()),
+        geometry.size.height.min(monitor.height()),
+    );
+    let position = visible.map(|monitor| {
+        Point::new(
+            geometry
+                .position
+                .x
+                .clamp(monitor.x0, monitor.x1 - size.width),
+            geometry
+                .position
+                .y
+                .clamp(monitor.y0, monitor.y1 - size.height),
+        )
+    });
+    (position, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITORS: [Rect; 2] = [
+        Rect::new(0.0, 0.0, 1920.0, 1080.0),
+        Rect::new(1920.0, 0.0, 3840.0, 1440.0),
+    ];
+
+    fn geometry(x: f64, y: f64, width: f64, height: f64) -> WindowGeometry {
+        WindowGeometry {
+            position: Point::new(x, y),
+            size: Size::new(width, height),
+            maximized: false,
+        }
+    }
+
+    #[test]
+    fn on_monitor_is_unchanged() {
+        assert_eq!(
+            fit_to_monitors(geometry(2000.0, 100.0, 800.0, 600.0), &MONITORS),
+            (Some(Point::new(2000.0, 100.0)), Size::new(800.0, 600.0))
+        );
+    }
+
+    #[test]
+    fn partly_off_screen_is_moved_onto_monitor() {
+        assert_eq!(
+            fit_to_monitors(geometry(-200.0, 900.0, 800.0, 600.0), &MONITORS),
+            (Some(Point::new(0.0, 480.0)), Size::new(800.0, 600.0))
+        );
+    }
+
+    #[test]
+    fn disconnected_monitor_drops_position() {
+        assert_eq!(
+            fit_to_monitors(geometry(5000.0, 100.0, 2500.0, 600.0), &MONITORS),
+            (None, Size::new(1920.0, 600.0))
+        );
+    }
+
+    #[test]
+    fn too_large_is_shrunk() {
+        assert_eq!(
+            fit_to_monitors(geometry(0.0, 0.0, 1920.0, 2000.0), &MONITORS),
+            (Some(Point::new(0.0, 0.0)), Size::new(1920.0, 1080.0))
+        );
+    }
+
+    #[test]
+    fn file_lines_round_trip() {
+        assert_eq!(
+            parse_line("10 20 800 600 1 main window"),
+            Some((
+                "main window".to_string(),
+                WindowGeometry {
+                    position: Point::new(10.0, 20.0),
+                    size: Size::new(800.0, 600.0),
+                    maximized: true,
+                }
+            ))
+        );
+    }
+}
@@ -1,21 +1,32 @@
-use std::{cell::RefCell, mem, path::PathBuf, rc::Rc, sync::Arc};
+use std::{
+    cell::RefCell,
+    mem,
+    path::PathBuf,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, Instant};
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, Instant};
 
-use floem_reactive::{with_scope, RwSignal, Scope, SignalGet, SignalUpdate};
+use floem_reactive::{with_scope, RwSignal, Scope, SignalGet, SignalTrack, SignalUpdate};
 use floem_renderer::gpu_resources::GpuResources;
 use floem_renderer::Renderer;
+use floem_tiny_skia_renderer::TinySkiaRenderer;
 use floem_winit::{
     dpi::{LogicalPosition, LogicalSize},
-    event::{ElementState, Ime, MouseButton, MouseScrollDelta, TouchPhase},
+    event::{ElementState, Ime, MouseButton, MouseScrollDelta, Touch, TouchPhase},
     event_loop::EventLoopProxy,
     keyboard::{Key, ModifiersState, NamedKey},
-    window::{CursorIcon, WindowId},
+    window::{CursorGrabMode, CursorIcon, WindowId},
 };
-use peniko::kurbo::{Affine, Point, Rect, Size, Vec2};
+use image::RgbaImage;
+use peniko::kurbo::{Affine, Insets, Point, Rect, Size, Vec2};
 
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 use crate::reactive::SignalWith;
@@ -23,32 +34,44 @@ use crate::reactive::SignalWith;
 use crate::unit::UnitExt;
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 use crate::views::{container, stack};
+#[cfg(target_os = "windows")]
+use crate::window::CaptionButton;
 use crate::{
-    app::UserEvent,
+    app::{Application, UserEvent},
     app_state::AppState,
+    clock,
     context::{
         ComputeLayoutCx, EventCx, FrameUpdate, LayoutCx, PaintCx, PaintState, StyleCx, UpdateCx,
     },
     dropped_file::DroppedFileEvent,
     event::{Event, EventListener},
+    forced_colors, gestures,
     id::ViewId,
-    inspector::{self, Capture, CaptureState, CapturedView},
+    inspector::{self, events::Pass, Capture, CaptureState, CapturedView},
     keyboard::{KeyEvent, Modifiers},
     menu::Menu,
     nav::view_arrow_navigation,
-    pointer::{PointerButton, PointerInputEvent, PointerMoveEvent, PointerWheelEvent},
-    profiler::Profile,
+    pointer::{
+        PointerButton, PointerDeltaEvent, PointerId, PointerInputEvent, PointerLockMode,
+        PointerMoveEvent, PointerWheelEvent, TouchEvent,
+    },
+    profiler::{self, Profile},
+    safe_area,
     style::{CursorStyle, Style, StyleSelector},
-    theme::{default_theme, Theme},
+    theme::{current_theme, set_system_color_scheme, set_theme, theme_signal, Theme},
     touchpad::TouchpadMagnifyEvent,
     update::{
-        UpdateMessage, CENTRAL_DEFERRED_UPDATE_MESSAGES, CENTRAL_UPDATE_MESSAGES,
-        CURRENT_RUNNING_VIEW_HANDLE, DEFERRED_UPDATE_MESSAGES, UPDATE_MESSAGES,
+        CaptureCallback, CaptureRegion, UpdateMessage, CENTRAL_DEFERRED_UPDATE_MESSAGES,
+        CENTRAL_UPDATE_MESSAGES, CURRENT_RUNNING_VIEW_HANDLE, DEFERRED_UPDATE_MESSAGES,
+        UPDATE_MESSAGES,
     },
     view::{default_compute_layout, view_tab_navigation, IntoView, View},
     view_state::ChangeFlags,
-    views::Decorators,
-    window_tracking::{remove_window_id_mapping, store_window_id_mapping},
+    views::{popover, Decorators},
+    window_tracking::{
+        remove_headless_root, remove_window_id_mapping, store_headless_root,
+        store_window_id_mapping,
+    },
 };
 
 /// The top-level window handle that owns the winit Window.
@@ -59,38 +82,59 @@ use crate::{
 /// - requesting a new animation frame from the backend
 pub(crate) struct WindowHandle {
     pub(crate) window: Option<Arc<floem_winit::window::Window>>,
-    window_id: WindowId,
-    id: ViewId,
-    main_view: ViewId,
+    pub(crate) window_id: WindowId,
+    pub(crate) id: ViewId,
+    pub(crate) main_view: ViewId,
     /// Reactive Scope for this WindowHandle
     scope: Scope,
-    app_state: AppState,
+    pub(crate) app_state: AppState,
     paint_state: PaintState,
-    size: RwSignal<Size>,
+    font_embolden: f32,
+    pub(crate) size: RwSignal<Size>,
+    /// The insets of the safe area of the window, in logical pixels.
+    safe_area: RwSignal<Insets>,
     theme: Option<Theme>,
+    color_scheme_themes: Option<(String, String)>,
     pub(crate) profile: Option<Profile>,
+    pub(crate) counters: PassCounters,
     os_theme: RwSignal<Option<floem_winit::window::Theme>>,
     is_maximized: bool,
     transparent: bool,
     pub(crate) scale: f64,
+    /// Whether the window is zoomed with the keyboard, see
+    /// [`WindowConfig::zoom_shortcuts`](crate::window::WindowConfig::zoom_shortcuts).
+    pub(crate) zoom_shortcuts: bool,
     pub(crate) modifiers: Modifiers,
     pub(crate) cursor_position: Point,
+    /// Whether the pointer is over the window, which is where a custom cursor is painted.
+    pointer_in_window: bool,
+    /// The grab of the pointer that is applied to the window for the pointer lock.
+    pointer_grab: Option<PointerLockMode>,
+    /// The captures requested with [`capture_view`](crate::action::capture_view), which are
+    /// taken once the updates are processed.
+    pending_captures: Vec<(CaptureRegion, CaptureCallback)>,
     pub(crate) window_position: Point,
     pub(crate) last_pointer_down: Option<(u8, Point, Instant)>,
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     pub(crate) context_menu: RwSignal<Option<(Menu, Point)>>,
     dropper_file: Option<PathBuf>,
+    /// The hidden input element that receives the text typed in the window on the web.
+    #[cfg(target_arch = "wasm32")]
+    ime_input: Option<crate::web::ImeInput>,
 }
 
 impl WindowHandle {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         window: floem_winit::window::Window,
         event_proxy: EventLoopProxy<UserEvent>,
         view_fn: impl FnOnce(floem_winit::window::WindowId) -> Box<dyn View> + 'static,
         transparent: bool,
         apply_default_theme: bool,
+        color_scheme_themes: Option<(String, String)>,
         size: Option<LogicalSize<f64>>,
         font_embolden: f32,
+        font_style: Style,
     ) -> Self {
         let scope = Scope::new();
         let window_id = window.id();
@@ -132,7 +176,31 @@ impl WindowHandle {
         let (main_view_id, widget) = view;
         id.set_children(vec![widget]);
 
-        let view = WindowView { id };
+        if apply_default_theme {
+            // Restyle the whole window when the active theme is switched.
+            scope.create_effect(move |prev: Option<()>| {
+                theme_signal().track();
+                if prev.is_some() {
+                    id.request_style_recursive();
+                }
+            });
+        }
+        // Restyle the whole window when the forced colors mode is toggled.
+        scope.create_effect(move |prev: Option<()>| {
+            forced_colors::forced_colors();
+            if prev.is_some() {
+                id.request_style_recursive();
+            }
+        });
+
+        let safe_area = scope.create_rw_signal(safe_area::window_insets(&window, scale));
+        safe_area::register(window_id, safe_area);
+
+        let view = WindowView {
+            id,
+            font_style,
+            safe_area,
+        };
         id.set_view(view.into_any());
 
         let window = Arc::new(window);
@@ -160,30 +228,139 @@ impl WindowHandle {
             scope,
             app_state: AppState::new(id),
             paint_state,
+            font_embolden,
             size,
-            theme: apply_default_theme.then(default_theme),
+            safe_area,
+            theme: apply_default_theme.then(current_theme),
+            color_scheme_themes,
             os_theme: theme,
             is_maximized,
             transparent,
             profile: None,
+            counters: PassCounters::default(),
             scale,
+            zoom_shortcuts: false,
             modifiers: Modifiers::default(),
             cursor_position: Point::ZERO,
+            pointer_in_window: false,
+            pointer_grab: None,
+            pending_captures: Vec::new(),
             window_position: Point::ZERO,
             #[cfg(any(target_os = "linux", target_os = "freebsd"))]
             context_menu,
             last_pointer_down: None,
             dropper_file: None,
+            #[cfg(target_arch = "wasm32")]
+            ime_input: None,
         };
+        window_handle.app_state.window_scale = scale;
+        window_handle.app_state.font_embolden = font_embolden;
         window_handle.app_state.set_root_size(size.get_untracked());
         if let Some(theme) = theme.get_untracked() {
+            set_system_color_scheme(theme);
+            window_handle.follow_color_scheme(theme);
             window_handle.event(Event::ThemeChanged(theme));
         }
         window_handle
     }
 
+    /// Creates a window handle without a window, which paints each frame into an image with a
+    /// CPU renderer, for [`HeadlessHarness`](crate::headless::HeadlessHarness).
+    pub(crate) fn new_headless(
+        view_fn: impl FnOnce(WindowId) -> Box<dyn View>,
+        size: Size,
+        scale: f64,
+    ) -> Self {
+        static NEXT_WINDOW_ID: AtomicU64 = AtomicU64::new(u64::MAX);
+
+        let scope = Scope::new();
+        // counting down to stay clear of the ids of the platform windows
+        let window_id = WindowId::from(NEXT_WINDOW_ID.fetch_sub(1, Ordering::Relaxed));
+        let id = ViewId::new();
+        let size = scope.create_rw_signal(size);
+
+        set_current_view(id);
+
+        let main_view = with_scope(scope, move || view_fn(window_id));
+        let main_view_id = main_view.id();
+        id.set_children(vec![main_view]);
+
+        // Restyle the whole window when the active theme is switched.
+        scope.create_effect(move |prev: Option<()>| {
+            theme_signal().track();
+            // the theme is mapped to the system colors in the forced colors mode
+            forced_colors::forced_colors();
+            if prev.is_some() {
+                id.request_style_recursive();
+            }
+        });
+
+        let safe_area = scope.create_rw_signal(Insets::ZERO);
+        safe_area::register(window_id, safe_area);
+
+        let view = WindowView {
+            id,
+            font_style: Style::new(),
+            safe_area,
+        };
+        id.set_view(view.into_any());
+        store_headless_root(id);
+
+        let physical_size = size.get_untracked() * scale;
+        let renderer = TinySkiaRenderer::new_offscreen(
+            physical_size.width.max(1.0) as u32,
+            physical_size.height.max(1.0) as u32,
+            scale,
+            0.0,
+        )
+        .expect("failed to create the offscreen renderer");
+        let mut window_handle = Self {
+            window: None,
+            window_id,
+            id,
+            main_view: main_view_id,
+            scope,
+            app_state: AppState::new(id),
+            paint_state: PaintState::Initialized {
+                renderer: crate::renderer::Renderer::TinySkia(renderer),
+            },
+            font_embolden: 0.0,
+            size,
+            safe_area,
+            theme: Some(current_theme()),
+            color_scheme_themes: None,
+            os_theme: scope.create_rw_signal(None),
+            is_maximized: false,
+            transparent: false,
+            profile: None,
+            counters: PassCounters::default(),
+            scale,
+            zoom_shortcuts: false,
+            modifiers: Modifiers::default(),
+            cursor_position: Point::ZERO,
+            pointer_in_window: false,
+            pointer_grab: None,
+            pending_captures: Vec::new(),
+            window_position: Point::ZERO,
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            context_menu: scope.create_rw_signal(None),
+            last_pointer_down: None,
+            dropper_file: None,
+            #[cfg(target_arch = "wasm32")]
+            ime_input: None,
+        };
+        window_handle.app_state.window_scale = scale;
+        window_handle.app_state.set_root_size(size.get_untracked());
+        window_handle.style();
+        window_handle.layout();
+        window_handle.process_update_no_paint();
+        window_handle
+    }
+
     pub(crate) fn init_renderer(&mut self) {
-        self.paint_state.init_renderer();
+        if !self.paint_state.init_renderer() {
+            return;
+        }
         // On the web, we need to get the canvas size once. The size will be updated automatically
         // when the canvas element is resized subsequently. This is the correct place to do so
         // because the renderer is not initialized until now.
@@ -204,8 +381,10 @@ impl WindowHandle {
     }
 
     pub fn event(&mut self, event: Event) {
+        let _span = profiler::pass_span("event");
         set_current_view(self.id);
         let event = event.transform(Affine::scale(self.app_state.scale));
+        inspector::events::begin_event(self.window_id, &event);
 
         let mut cx = EventCx {
             app_state: &mut self.app_state,
@@ -227,7 +406,25 @@ impl WindowHandle {
             (None, None)
         };
 
+        let touch_capture = match &event {
+            Event::Touch(touch) => cx.app_state.pointer_capture(touch.pointer),
+            _ => None,
+        };
+
         let is_pointer_down = matches!(&event, Event::PointerDown(_));
+        if let Event::PointerDown(pointer_event) = &event {
+            popover::dismiss_popovers_outside(cx.app_state, pointer_event.pos);
+        }
+        if let Event::KeyDown(KeyEvent { key, .. }) = &event {
+            if key.logical_key == Key::Named(NamedKey::Escape)
+                && popover::dismiss_top_popover(cx.app_state)
+            {
+                inspector::events::end_event();
+                self.process_update();
+                return;
+            }
+        }
+
         let was_focused = if is_pointer_down {
             cx.app_state.clicking.clear();
             cx.app_state.focus.take()
@@ -240,9 +437,19 @@ impl WindowHandle {
 
             if !processed {
                 if let Some(id) = cx.app_state.focus {
+                    processed |= cx.capture_on_ancestors(id, &event).is_processed()
+                        || cx
+                            .unconditional_view_event(id, event.clone(), true)
+                            .is_processed();
+                } else if let Some(listener) = event.listener() {
+                    // without a focused view, capture listeners still get to intercept the event
                     processed |= cx
-                        .unconditional_view_event(id, event.clone(), true)
-                        .is_processed();
+                        .capture_on_ancestors(self.main_view, &event)
+                        .is_processed()
+                        || self
+                            .main_view
+                            .apply_capture(&listener, &event)
+                            .is_processed();
                 }
 
                 if !processed {
@@ -287,7 +494,7 @@ impl WindowHandle {
                         if let Some(id) = cx.app_state.active {
                             // To remove the styles applied by the Active selector
                             if cx.app_state.has_style_for_sel(id, StyleSelector::Active) {
-                                id.request_style_recursive();
+                                id.request_style();
                             }
 
                             cx.app_state.active = None;
@@ -295,28 +502,20 @@ impl WindowHandle {
                     }
                 }
             }
+        } else if let Some(id) = touch_capture {
+            send_event_to(&mut cx, id, event.clone(), true);
         } else if cx.app_state.active.is_some() && event.is_pointer() {
             if cx.app_state.is_dragging() {
                 cx.unconditional_view_event(self.id, event.clone(), false);
             }
 
             let id = cx.app_state.active.unwrap();
-
-            {
-                let window_origin = id.state().borrow().window_origin;
-                let layout = id.get_layout().unwrap_or_default();
-                let viewport = id.state().borrow().viewport.unwrap_or_default();
-                let transform = Affine::translate((
-                    window_origin.x - layout.location.x as f64 + viewport.x0,
-                    window_origin.y - layout.location.y as f64 + viewport.y0,
-                ));
-                cx.unconditional_view_event(id, event.clone().transform(transform), true);
-            }
+            send_event_to(&mut cx, id, event.clone(), true);
 
             if let Event::PointerUp(_) = &event {
                 // To remove the styles applied by the Active selector
                 if cx.app_state.has_style_for_sel(id, StyleSelector::Active) {
-                    id.request_style_recursive();
+                    id.request_style();
                 }
 
                 cx.app_state.active = None;
@@ -328,6 +527,11 @@ impl WindowHandle {
         if let Event::PointerUp(_) = &event {
             cx.app_state.drag_start = None;
         }
+        if let Event::Touch(touch) = &event {
+            if matches!(touch.phase, TouchPhase::Ended | TouchPhase::Cancelled) {
+                cx.app_state.pointer_captures.remove(&touch.pointer);
+            }
+        }
         if is_pointer_move {
             let hovered = &cx.app_state.hovered.clone();
             for id in was_hovered.unwrap().symmetric_difference(hovered) {
@@ -342,7 +546,7 @@ impl WindowHandle {
                         .has_style_selectors
                         .has(StyleSelector::Active)
                 {
-                    id.request_style_recursive();
+                    id.request_style();
                 }
                 if hovered.contains(id) {
                     id.apply_event(&EventListener::PointerEnter, &event);
@@ -362,6 +566,10 @@ impl WindowHandle {
                 }
             }
         }
+        if is_pointer_move && cx.app_state.layout_overlay {
+            // the overlay follows the cursor
+            cx.app_state.request_paint(self.id);
+        }
         if was_focused != cx.app_state.focus {
             cx.app_state.focus_changed(was_focused, cx.app_state.focus);
         }
@@ -369,36 +577,117 @@ impl WindowHandle {
         if is_pointer_down {
             for id in cx.app_state.clicking.clone() {
                 if cx.app_state.has_style_for_sel(id, StyleSelector::Active) {
-                    id.request_style_recursive();
+                    id.request_style();
                 }
             }
         }
         if matches!(&event, Event::PointerUp(_)) {
             for id in cx.app_state.clicking.clone() {
                 if cx.app_state.has_style_for_sel(id, StyleSelector::Active) {
-                    id.request_style_recursive();
+                    id.request_style();
                 }
             }
             cx.app_state.clicking.clear();
         }
 
+        inspector::events::end_event();
         self.process_update();
     }
 
+    /// Drops the renderer, as the surface of the window is destroyed while the app is suspended.
+    #[cfg(target_os = "android")]
+    pub(crate) fn suspend(&mut self) {
+        if self.window.is_some() {
+            self.paint_state.suspend();
+        }
+    }
+
+    /// Requests the GPU resources for a new surface, if the renderer was dropped when the app
+    /// was suspended.
+    pub(crate) fn resume(&mut self) {
+        let Some(window) = self.window.clone() else {
+            return;
+        };
+        if !self.paint_state.is_suspended() {
+            return;
+        }
+        let renderer = self.paint_state.renderer();
+        let (scale, size) = (renderer.scale(), renderer.size());
+        let mut gpu_resources = None;
+        Application::with_event_loop_proxy(|proxy| {
+            let proxy = proxy.clone();
+            gpu_resources = Some(GpuResources::request(
+                move |window_id| {
+                    let _ = proxy.send_event(UserEvent::GpuResourcesUpdate { window_id });
+                },
+                window.clone(),
+            ));
+        });
+        if let Some(gpu_resources) = gpu_resources {
+            self.paint_state =
+                PaintState::new(window, gpu_resources, scale, size, self.font_embolden);
+        }
+        self.update_safe_area();
+    }
+
+    /// Updates the insets of the safe area, which are inherited by the views from the style of
+    /// the root.
+    fn update_safe_area(&mut self) {
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        let insets = safe_area::window_insets(window, self.scale * self.app_state.scale);
+        if safe_area::update(self.safe_area, insets) {
+            self.id.request_style_recursive();
+        }
+    }
+
     pub(crate) fn scale(&mut self, scale: f64) {
         self.scale = scale;
+        self.app_state.window_scale = scale;
+        self.update_safe_area();
+        // Media queries may depend on the scale factor.
+        self.id.request_style_recursive();
         let scale = self.scale * self.app_state.scale;
         self.paint_state.set_scale(scale);
         self.schedule_repaint();
     }
 
+    /// Sets the zoom of the window, which multiplies its scale factor in layout and painting.
+    fn set_ui_scale(&mut self, scale: f64) {
+        if !scale.is_finite() || scale <= 0.0 {
+            return;
+        }
+        self.app_state.scale = scale;
+        self.update_safe_area();
+        self.id.request_style_recursive();
+        self.id.request_layout();
+        self.paint_state.set_scale(self.scale * scale);
+        self.schedule_repaint();
+    }
+
     pub(crate) fn os_theme_changed(&mut self, theme: floem_winit::window::Theme) {
         self.os_theme.set(Some(theme));
+        set_system_color_scheme(theme);
+        crate::accessibility::refresh_preferences();
+        self.follow_color_scheme(theme);
         self.event(Event::ThemeChanged(theme));
     }
 
+    fn follow_color_scheme(&self, scheme: floem_winit::window::Theme) {
+        if let Some((light, dark)) = &self.color_scheme_themes {
+            let name = match scheme {
+                floem_winit::window::Theme::Light => light,
+                floem_winit::window::Theme::Dark => dark,
+            };
+            set_theme(name);
+        }
+    }
+
     pub(crate) fn size(&mut self, size: Size) {
         self.size.set(size);
+        // the safe area changes when the device is rotated
+        self.update_safe_area();
         self.app_state.update_screen_size_bp(size);
         self.event(Event::WindowResized(size));
         let scale = self.scale * self.app_state.scale;
@@ -430,6 +719,23 @@ impl WindowHandle {
             modifiers: self.modifiers,
         };
         let is_altgr = matches!(event.key.logical_key, Key::Named(NamedKey::AltGraph));
+        if event.key.state.is_pressed()
+            && event.key.logical_key == Key::Named(NamedKey::Escape)
+            && self.app_state.pointer_lock.is_some()
+        {
+            set_current_view(self.id);
+            self.release_pointer_lock();
+            self.process_update();
+            return;
+        }
+        if self.zoom_shortcuts && event.key.state.is_pressed() {
+            if let Some(scale) = zoom_shortcut(&event, self.app_state.scale) {
+                set_current_view(self.id);
+                self.set_ui_scale(scale);
+                self.process_update();
+                return;
+            }
+        }
         if event.key.state.is_pressed() {
             self.event(Event::KeyDown(event));
             if is_altgr {
@@ -459,6 +765,10 @@ impl WindowHandle {
             };
             self.event(Event::PointerMove(event));
         }
+        self.pointer_in_window = true;
+        if self.has_custom_cursor() {
+            self.schedule_repaint();
+        }
     }
 
     pub(crate) fn pointer_leave(&mut self) {
@@ -479,13 +789,24 @@ impl WindowHandle {
                     .has(StyleSelector::Active)
                 || view_state.borrow().has_active_animation()
             {
-                id.request_style_recursive();
+                id.request_style();
             }
             cx.unconditional_view_event(id, Event::PointerLeave, true);
         }
+        self.pointer_in_window = false;
+        if self.has_custom_cursor() {
+            self.app_state.request_paint = true;
+        }
         self.process_update();
     }
 
+    fn has_custom_cursor(&self) -> bool {
+        matches!(
+            self.app_state.current_cursor(),
+            Some(CursorStyle::Custom(_))
+        )
+    }
+
     pub(crate) fn mouse_wheel(&mut self, delta: MouseScrollDelta) {
         let delta = match delta {
             MouseScrollDelta::LineDelta(x, y) => Vec2::new(-x as f64 * 60.0, -y as f64 * 60.0),
@@ -508,18 +829,18 @@ impl WindowHandle {
             if let Some((count, last_pos, instant)) = self.last_pointer_down.as_mut() {
                 if *count == 4 {
                     *count = 1;
-                } else if instant.elapsed().as_millis() < 500
+                } else if clock::now().saturating_duration_since(*instant).as_millis() < 500
                     && last_pos.distance(self.cursor_position) < 4.0
                 {
                     *count += 1;
                 } else {
                     *count = 1;
                 }
-                *instant = Instant::now();
+                *instant = clock::now();
                 *last_pos = self.cursor_position;
                 *count
             } else {
-                self.last_pointer_down = Some((1, self.cursor_position, Instant::now()));
+                self.last_pointer_down = Some((1, self.cursor_position, clock::now()));
                 1
             }
         } else {
@@ -533,6 +854,7 @@ impl WindowHandle {
         };
         match state {
             ElementState::Pressed => {
+                gestures::reset_pointer_owner();
                 self.event(Event::PointerDown(event));
             }
             ElementState::Released => {
@@ -541,6 +863,26 @@ impl WindowHandle {
         }
     }
 
+    /// Handles an event that was sent with [`dispatch_event`](crate::dispatch_event).
+    pub(crate) fn dispatch_event(&mut self, event: Event) {
+        match event {
+            // keep track of the cursor like for a real move
+            Event::PointerMove(event) => self.pointer_move(event.pos),
+            event => self.event(event),
+        }
+    }
+
+    pub(crate) fn touch(&mut self, touch: Touch) {
+        let position: LogicalPosition<f64> = touch.location.to_logical(self.scale);
+        let event = TouchEvent {
+            pointer: PointerId(touch.id),
+            phase: touch.phase,
+            pos: Point::new(position.x, position.y),
+            force: touch.force.map(|force| force.normalized()),
+        };
+        self.event(Event::Touch(event));
+    }
+
     pub(crate) fn touchpad_magnify(&mut self, delta: f64, phase: TouchPhase) {
         let event = TouchpadMagnifyEvent { delta, phase };
         self.event(Event::TouchpadMagnify(event));
@@ -550,19 +892,90 @@ impl WindowHandle {
         if focused {
             self.event(Event::WindowGotFocus);
         } else {
+            set_current_view(self.id);
+            self.release_pointer_lock();
             self.event(Event::WindowLostFocus);
         }
     }
 
+    /// Sends the relative motion of the pointer to the view with the pointer lock.
+    pub(crate) fn pointer_delta(&mut self, delta: Vec2) {
+        let Some((id, _)) = self.app_state.pointer_lock else {
+            return;
+        };
+        set_current_view(self.id);
+        let event = PointerDeltaEvent {
+            delta: delta / self.app_state.scale,
+            modifiers: self.modifiers,
+        };
+        let mut cx = EventCx {
+            app_state: &mut self.app_state,
+        };
+        cx.unconditional_view_event(id, Event::PointerDelta(event), true);
+        self.process_update();
+    }
+
+    /// Releases the pointer lock, and tells the view that held it.
+    fn release_pointer_lock(&mut self) {
+        if let Some((id, _)) = self.app_state.pointer_lock.take() {
+            let mut cx = EventCx {
+                app_state: &mut self.app_state,
+            };
+            cx.unconditional_view_event(id, Event::PointerLockReleased, true);
+        }
+    }
+
+    /// Grabs or releases the pointer to match the pointer lock.
+    fn sync_pointer_grab(&mut self) {
+        let mode = self.app_state.pointer_lock.map(|(_, mode)| mode);
+        if mode == self.pointer_grab {
+            return;
+        }
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+        let grabbed = match mode {
+            Some(mode) => {
+                let (grab, fallback) = match mode {
+                    PointerLockMode::Locked => (CursorGrabMode::Locked, CursorGrabMode::Confined),
+                    PointerLockMode::Confined => (CursorGrabMode::Confined, CursorGrabMode::Locked),
+                };
+                // each mode is only supported on some platforms
+                window
+                    .set_cursor_grab(grab)
+                    .or_else(|_| window.set_cursor_grab(fallback))
+                    .is_ok()
+            }
+            None => {
+                let _ = window.set_cursor_grab(CursorGrabMode::None);
+                true
+            }
+        };
+        if !grabbed {
+            self.release_pointer_lock();
+            return;
+        }
+        window.set_cursor_visible(
+            mode != Some(PointerLockMode::Locked) && self.app_state.last_cursor.is_some(),
+        );
+        self.pointer_grab = mode;
+    }
+
     fn style(&mut self) {
+        let _span = profiler::pass_span("style");
         let mut cx = StyleCx::new(&mut self.app_state, self.id);
-        if let Some(theme) = &self.theme {
+        if let Some(theme) = &mut self.theme {
+            *theme = current_theme();
             cx.current = theme.style.clone();
         }
         cx.style_view(self.id);
     }
 
     fn layout(&mut self) -> Duration {
+        let _span = profiler::pass_span("layout");
+        self.app_state.text_layouts.next_generation();
+        #[cfg(feature = "parallel")]
+        self.app_state.text_layouts.shape_requested();
         let mut cx = LayoutCx::new(&mut self.app_state);
 
         cx.app_state_mut().root = {
@@ -585,18 +998,28 @@ impl WindowHandle {
         let viewport = (self.app_state.root_size / self.app_state.scale).to_rect();
         let mut cx = ComputeLayoutCx::new(&mut self.app_state, viewport);
         cx.compute_view_layout(self.id);
+        #[cfg(target_os = "windows")]
+        self.update_maximize_button();
     }
 
-    pub(crate) fn render_frame(&mut self) {
-        // Processes updates scheduled on this frame.
-        for update in mem::take(&mut self.app_state.scheduled_updates) {
-            match update {
-                FrameUpdate::Style(id) => id.request_style(),
-                FrameUpdate::Layout(id) => id.request_layout(),
-                FrameUpdate::Paint(id) => self.app_state.request_paint(id),
-            }
+    /// Reports the maximize button of a custom title bar to the system, which shows the snap
+    /// layouts when it is hovered.
+    #[cfg(target_os = "windows")]
+    fn update_maximize_button(&self) {
+        let scale = self.scale * self.app_state.scale;
+        let rect = self
+            .app_state
+            .caption_buttons
+            .iter()
+            .find(|(id, button)| **button == CaptionButton::Maximize && !id.is_hidden_recursive())
+            .map(|(id, _)| id.layout_rect().scale_from_origin(scale));
+        if let Some(window) = self.window.as_ref() {
+            crate::snap_layout::set_maximize_button(window, rect);
         }
+    }
 
+    pub(crate) fn render_frame(&mut self) {
+        self.process_scheduled_updates();
         self.process_update_no_paint();
         self.paint();
 
@@ -606,7 +1029,27 @@ impl WindowHandle {
         }
     }
 
+    /// Renders a frame, returning its image if the renderer has no window.
+    pub(crate) fn render_image(&mut self) -> Option<peniko::Image> {
+        self.process_scheduled_updates();
+        self.process_update_no_paint();
+        self.paint()
+    }
+
+    /// Processes updates scheduled on this frame.
+    fn process_scheduled_updates(&mut self) {
+        for update in mem::take(&mut self.app_state.scheduled_updates) {
+            match update {
+                FrameUpdate::Style(id) => id.request_style(),
+                FrameUpdate::Layout(id) => id.request_layout(),
+                FrameUpdate::Paint(id) => self.app_state.request_paint(id),
+            }
+        }
+    }
+
     pub fn paint(&mut self) -> Option<peniko::Image> {
+        let start = Instant::now();
+        let span = profiler::pass_span("paint");
         let mut cx = PaintCx {
             app_state: &mut self.app_state,
             paint_state: &mut self.paint_state,
@@ -640,12 +1083,58 @@ impl WindowHandle {
             );
         }
         cx.paint_view(self.id);
+        if cx.app_state.layout_overlay && cx.app_state.capture.is_none() {
+            inspector::overlay::paint_layout_overlay(&mut cx, self.id);
+        }
+        if let Some(CursorStyle::Custom(cursor)) = cx.app_state.current_cursor().cloned() {
+            if cx.app_state.capture.is_none() && self.pointer_in_window {
+                let origin = cx.app_state.last_cursor_location - cursor.hotspot.to_vec2();
+                let size = Size::new(cursor.image.width as f64, cursor.image.height as f64);
+                cx.draw_img(
+                    floem_renderer::Img {
+                        img: cursor.image,
+                        hash: &cursor.hash,
+                    },
+                    Rect::from_origin_size(origin, size),
+                );
+            }
+        }
         if let Some(window) = self.window.as_ref() {
             if cx.app_state.capture.is_none() {
                 window.pre_present_notify();
             }
         }
-        cx.paint_state.renderer_mut().finish()
+        let image = cx.paint_state.renderer_mut().finish();
+        drop(span);
+        self.pass_finished(Pass::Paint, start.elapsed());
+        image
+    }
+
+    /// Counts a style, layout or paint pass that took `duration`.
+    fn pass_finished(&mut self, pass: Pass, duration: Duration) {
+        let counters = &mut self.counters;
+        match pass {
+            Pass::Style => {
+                counters.style_passes += 1;
+                counters.last_style = duration;
+            }
+            Pass::Layout => {
+                counters.layout_passes += 1;
+                counters.last_layout = duration;
+            }
+            Pass::Paint => {
+                counters.frames += 1;
+                counters.last_paint = duration;
+            }
+        }
+        inspector::events::record_pass(self.window_id, pass, duration);
+    }
+
+    /// Shows or hides the layout overlay of the inspector.
+    pub(crate) fn set_layout_overlay(&mut self, enabled: bool) {
+        self.app_state.layout_overlay = enabled;
+        self.app_state.request_paint(self.id);
+        self.schedule_repaint();
     }
 
     pub(crate) fn capture(&mut self) -> Capture {
@@ -717,6 +1206,52 @@ impl WindowHandle {
         capture
     }
 
+    /// Paints the window to take the pending captures.
+    fn take_captures(&mut self) {
+        self.app_state.capture = Some(CaptureState::default());
+        let frame = self.paint();
+        self.app_state.capture = None;
+        // the captured frame isn't shown, so the window is painted again
+        self.app_state.request_paint = true;
+
+        let frame = frame.and_then(|frame| {
+            RgbaImage::from_raw(frame.width, frame.height, frame.data.data().to_vec())
+        });
+        let scale = self.scale * self.app_state.scale;
+        for (region, on_capture) in mem::take(&mut self.pending_captures) {
+            let rect = match region {
+                CaptureRegion::View(id) => (!id.is_hidden_recursive()).then(|| {
+                    let origin = id.state().borrow().window_origin;
+                    id.get_size()
+                        .unwrap_or_default()
+                        .to_rect()
+                        .with_origin(origin)
+                }),
+                CaptureRegion::Rect(rect) => Some(rect),
+            };
+            let image = frame.as_ref().zip(rect).and_then(|(frame, rect)| {
+                // the pixels that are at least partly covered by the rect
+                let rect = rect.scale_from_origin(scale).expand().intersect(Rect::new(
+                    0.,
+                    0.,
+                    frame.width() as f64,
+                    frame.height() as f64,
+                ));
+                (rect.area() > 0.).then(|| {
+                    image::imageops::crop_imm(
+                        frame,
+                        rect.x0 as u32,
+                        rect.y0 as u32,
+                        rect.width() as u32,
+                        rect.height() as u32,
+                    )
+                    .to_image()
+                })
+            });
+            on_capture(image);
+        }
+    }
+
     pub(crate) fn process_update(&mut self) {
         if self.process_update_no_paint() {
             self.schedule_repaint();
@@ -727,6 +1262,7 @@ impl WindowHandle {
     /// Returns `true` if painting is required.
     pub(crate) fn process_update_no_paint(&mut self) -> bool {
         let mut paint = false;
+        self.app_state.container_query_restyled.clear();
         loop {
             self.process_update_messages();
             if !self.needs_layout()
@@ -738,13 +1274,17 @@ impl WindowHandle {
             }
 
             if self.needs_style() {
-                paint = true;
+                // the style pass requests a paint if the style of a view changed
+                let start = Instant::now();
                 self.style();
+                self.pass_finished(Pass::Style, start.elapsed());
             }
 
             if self.needs_layout() {
                 paint = true;
+                let start = Instant::now();
                 self.layout();
+                self.pass_finished(Pass::Layout, start.elapsed());
             }
 
             if self.app_state.request_compute_layout {
@@ -754,7 +1294,11 @@ impl WindowHandle {
             self.process_deferred_update_messages();
         }
 
+        if !self.pending_captures.is_empty() {
+            self.take_captures();
+        }
         self.set_cursor();
+        self.sync_pointer_grab();
 
         // TODO: This should only use `self.app_state.request_paint)`
         paint || mem::take(&mut self.app_state.request_paint)
@@ -860,12 +1404,12 @@ impl WindowHandle {
                                 .app_state
                                 .has_style_for_sel(old_id, StyleSelector::Active)
                             {
-                                old_id.request_style_recursive();
+                                old_id.request_style();
                             }
                         }
 
                         if cx.app_state.has_style_for_sel(id, StyleSelector::Active) {
-                            id.request_style_recursive();
+                            id.request_style();
                         }
                     }
                     UpdateMessage::ClearActive(id) => {
@@ -873,11 +1417,13 @@ impl WindowHandle {
                             cx.app_state.active = None;
                         }
                     }
-                    UpdateMessage::ScrollTo { id, rect } => {
+                    UpdateMessage::ScrollTo { id, rect, options } => {
+                        cx.app_state.scroll_into_view = options;
                         self.id
                             .view()
                             .borrow_mut()
                             .scroll_to(cx.app_state, id, rect);
+                        cx.app_state.scroll_into_view = None;
                     }
                     UpdateMessage::Disabled { id, is_disabled } => {
                         if is_disabled {
@@ -901,6 +1447,9 @@ impl WindowHandle {
                     UpdateMessage::Draggable { id } => {
                         cx.app_state.draggable.insert(id);
                     }
+                    UpdateMessage::CaptionButton { id, button } => {
+                        cx.app_state.caption_buttons.insert(id, button);
+                    }
                     UpdateMessage::DragWindow => {
                         if let Some(window) = self.window.as_ref() {
                             let _ = window.drag_window();
@@ -940,10 +1489,7 @@ impl WindowHandle {
                         }
                     }
                     UpdateMessage::WindowScale(scale) => {
-                        cx.app_state.scale = scale;
-                        self.id.request_layout();
-                        let scale = self.scale * cx.app_state.scale;
-                        self.paint_state.set_scale(scale);
+                        self.set_ui_scale(scale);
                     }
                     UpdateMessage::ShowContextMenu { menu, pos } => {
                         let mut menu = menu.popup();
@@ -971,8 +1517,16 @@ impl WindowHandle {
                         if let Some(window) = self.window.as_ref() {
                             window.set_ime_allowed(allowed);
                         }
+                        #[cfg(target_arch = "wasm32")]
+                        if let Some(ime_input) = self.ime_input(allowed) {
+                            ime_input.set_allowed(allowed);
+                        }
                     }
                     UpdateMessage::SetImeCursorArea { position, size } => {
+                        #[cfg(target_arch = "wasm32")]
+                        if let Some(ime_input) = self.ime_input(false) {
+                            ime_input.set_cursor_area(position);
+                        }
                         if let Some(window) = self.window.as_ref() {
                             window.set_ime_cursor_area(
                                 floem_winit::dpi::Position::Logical(
@@ -993,6 +1547,16 @@ impl WindowHandle {
                     UpdateMessage::Inspect => {
                         inspector::capture(self.window_id);
                     }
+                    UpdateMessage::SendEvent { id, event } => {
+                        let mut cx = EventCx {
+                            app_state: cx.app_state,
+                        };
+                        // the event is relative to the view, while `send_event_to` expects it
+                        // relative to the window
+                        let window_origin = id.state().borrow().window_origin;
+                        let event = event.transform(Affine::translate(-window_origin.to_vec2()));
+                        send_event_to(&mut cx, id, event, false);
+                    }
                     UpdateMessage::AddOverlay { id, position, view } => {
                         let scope = self.scope.create_child();
 
@@ -1020,6 +1584,9 @@ impl WindowHandle {
                         cx.app_state.remove_view(id);
                         self.id.request_all();
                     }
+                    UpdateMessage::RemoveView { id } => {
+                        cx.app_state.remove_view(id);
+                    }
                     UpdateMessage::WindowVisible(visible) => {
                         if let Some(window) = self.window.as_ref() {
                             window.set_visible(visible);
@@ -1030,6 +1597,43 @@ impl WindowHandle {
                             id.state().borrow().num_waiting_animations.saturating_sub(1);
                         id.state().borrow_mut().num_waiting_animations = num_waiting;
                     }
+                    UpdateMessage::OverrideCursor { token, cursor } => {
+                        cx.app_state.cursor_overrides.push((token, cursor));
+                        cx.app_state.request_paint = true;
+                    }
+                    UpdateMessage::PointerLock { id, mode } => {
+                        let previous = cx.app_state.pointer_lock.replace((id, mode));
+                        if let Some((previous, _)) =
+                            previous.filter(|(previous, _)| *previous != id)
+                        {
+                            let mut cx = EventCx {
+                                app_state: cx.app_state,
+                            };
+                            cx.unconditional_view_event(previous, Event::PointerLockReleased, true);
+                        }
+                    }
+                    UpdateMessage::ReleasePointerLock { id } => {
+                        if cx
+                            .app_state
+                            .pointer_lock
+                            .is_some_and(|(locked, _)| locked == id)
+                        {
+                            cx.app_state.pointer_lock = None;
+                            let mut cx = EventCx {
+                                app_state: cx.app_state,
+                            };
+                            cx.unconditional_view_event(id, Event::PointerLockReleased, true);
+                        }
+                    }
+                    UpdateMessage::Capture { region, on_capture } => {
+                        self.pending_captures.push((region, on_capture));
+                    }
+                    UpdateMessage::RestoreCursor { token } => {
+                        cx.app_state
+                            .cursor_overrides
+                            .retain(|(override_token, _)| *override_token != token);
+                        cx.app_state.request_paint = true;
+                    }
                 }
             }
         }
@@ -1093,27 +1697,55 @@ impl WindowHandle {
     }
 
     fn set_cursor(&mut self) {
-        let cursor = match self.app_state.cursor {
-            Some(CursorStyle::Default) => CursorIcon::Default,
-            Some(CursorStyle::Pointer) => CursorIcon::Pointer,
-            Some(CursorStyle::Text) => CursorIcon::Text,
-            Some(CursorStyle::ColResize) => CursorIcon::ColResize,
-            Some(CursorStyle::RowResize) => CursorIcon::RowResize,
-            Some(CursorStyle::WResize) => CursorIcon::WResize,
-            Some(CursorStyle::EResize) => CursorIcon::EResize,
-            Some(CursorStyle::NwResize) => CursorIcon::NwResize,
-            Some(CursorStyle::NeResize) => CursorIcon::NeResize,
-            Some(CursorStyle::SwResize) => CursorIcon::SwResize,
-            Some(CursorStyle::SeResize) => CursorIcon::SeResize,
-            Some(CursorStyle::SResize) => CursorIcon::SResize,
-            Some(CursorStyle::NResize) => CursorIcon::NResize,
-            Some(CursorStyle::NeswResize) => CursorIcon::NeswResize,
-            Some(CursorStyle::NwseResize) => CursorIcon::NwseResize,
-            None => CursorIcon::Default,
+        let cursor = match self.app_state.current_cursor() {
+            Some(CursorStyle::Default) | None => Some(CursorIcon::Default),
+            Some(CursorStyle::ContextMenu) => Some(CursorIcon::ContextMenu),
+            Some(CursorStyle::Help) => Some(CursorIcon::Help),
+            Some(CursorStyle::Pointer) => Some(CursorIcon::Pointer),
+            Some(CursorStyle::Progress) => Some(CursorIcon::Progress),
+            Some(CursorStyle::Wait) => Some(CursorIcon::Wait),
+            Some(CursorStyle::Cell) => Some(CursorIcon::Cell),
+            Some(CursorStyle::Crosshair) => Some(CursorIcon::Crosshair),
+            Some(CursorStyle::Text) => Some(CursorIcon::Text),
+            Some(CursorStyle::VerticalText) => Some(CursorIcon::VerticalText),
+            Some(CursorStyle::Alias) => Some(CursorIcon::Alias),
+            Some(CursorStyle::Copy) => Some(CursorIcon::Copy),
+            Some(CursorStyle::Move) => Some(CursorIcon::Move),
+            Some(CursorStyle::NoDrop) => Some(CursorIcon::NoDrop),
+            Some(CursorStyle::NotAllowed) => Some(CursorIcon::NotAllowed),
+            Some(CursorStyle::Grab) => Some(CursorIcon::Grab),
+            Some(CursorStyle::Grabbing) => Some(CursorIcon::Grabbing),
+            Some(CursorStyle::ColResize) => Some(CursorIcon::ColResize),
+            Some(CursorStyle::RowResize) => Some(CursorIcon::RowResize),
+            Some(CursorStyle::WResize) => Some(CursorIcon::WResize),
+            Some(CursorStyle::EResize) => Some(CursorIcon::EResize),
+            Some(CursorStyle::NwResize) => Some(CursorIcon::NwResize),
+            Some(CursorStyle::NeResize) => Some(CursorIcon::NeResize),
+            Some(CursorStyle::SwResize) => Some(CursorIcon::SwResize),
+            Some(CursorStyle::SeResize) => Some(CursorIcon::SeResize),
+            Some(CursorStyle::SResize) => Some(CursorIcon::SResize),
+            Some(CursorStyle::NResize) => Some(CursorIcon::NResize),
+            Some(CursorStyle::EwResize) => Some(CursorIcon::EwResize),
+            Some(CursorStyle::NsResize) => Some(CursorIcon::NsResize),
+            Some(CursorStyle::NeswResize) => Some(CursorIcon::NeswResize),
+            Some(CursorStyle::NwseResize) => Some(CursorIcon::NwseResize),
+            Some(CursorStyle::AllScroll) => Some(CursorIcon::AllScroll),
+            Some(CursorStyle::ZoomIn) => Some(CursorIcon::ZoomIn),
+            Some(CursorStyle::ZoomOut) => Some(CursorIcon::ZoomOut),
+            // custom cursors are painted over the window instead
+            Some(CursorStyle::Custom(_)) => None,
         };
         if cursor != self.app_state.last_cursor {
+            // a custom cursor is painted, or was painted and has to be removed
+            if cursor.is_none() || self.app_state.last_cursor.is_none() {
+                self.app_state.request_paint = true;
+            }
             if let Some(window) = self.window.as_ref() {
-                window.set_cursor_icon(cursor);
+                let locked = self.pointer_grab == Some(PointerLockMode::Locked);
+                window.set_cursor_visible(cursor.is_some() && !locked);
+                if let Some(cursor) = cursor {
+                    window.set_cursor_icon(cursor);
+                }
             }
             self.app_state.last_cursor = cursor;
         }
@@ -1127,8 +1759,13 @@ impl WindowHandle {
 
     pub(crate) fn destroy(&mut self) {
         self.event(Event::WindowClosed);
+        safe_area::unregister(self.window_id);
         self.scope.dispose();
-        remove_window_id_mapping(&self.id, &self.window_id);
+        if self.window.is_some() {
+            remove_window_id_mapping(&self.id, &self.window_id);
+        } else {
+            remove_headless_root(&self.id);
+        }
     }
 
     #[cfg(target_os = "macos")]
@@ -1191,6 +1828,18 @@ impl WindowHandle {
         }
     }
 
+    /// The hidden input element of the window, which is created when `create` is true.
+    #[cfg(target_arch = "wasm32")]
+    fn ime_input(&mut self, create: bool) -> Option<&crate::web::ImeInput> {
+        use floem_winit::platform::web::WindowExtWebSys;
+
+        if self.ime_input.is_none() && create {
+            let canvas = self.window.as_ref()?.canvas()?;
+            self.ime_input = crate::web::ImeInput::new(self.window_id, canvas);
+        }
+        self.ime_input.as_ref()
+    }
+
     pub(crate) fn ime(&mut self, ime: Ime) {
         match ime {
             Ime::Enabled => {
@@ -1218,6 +1867,61 @@ impl WindowHandle {
     }
 }
 
+/// The number of style, layout and paint passes of a window, and how long the latest ones took.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct PassCounters {
+    pub(crate) style_passes: u64,
+    pub(crate) layout_passes: u64,
+    pub(crate) frames: u64,
+    pub(crate) last_style: Duration,
+    pub(crate) last_layout: Duration,
+    pub(crate) last_paint: Duration,
+}
+
+/// Sends a window-positioned `event` straight to `id`, like to the active view or the view
+/// that captured a touch, after the capture listeners of its ancestors.
+///
+/// If `directed` is false, the event also propagates to the children of `id`.
+fn send_event_to(cx: &mut EventCx, id: ViewId, event: Event, directed: bool) {
+    if cx.capture_on_ancestors(id, &event).is_processed() {
+        return;
+    }
+    let window_origin = id.state().borrow().window_origin;
+    let layout = id.get_layout().unwrap_or_default();
+    let viewport = id.state().borrow().viewport.unwrap_or_default();
+    let transform = Affine::translate((
+        window_origin.x - layout.location.x as f64 + viewport.x0,
+        window_origin.y - layout.location.y as f64 + viewport.y0,
+    ));
+    cx.unconditional_view_event(id, event.transform(transform), directed);
+}
+
+/// The zoom of a window after the shortcut of `event` is applied to its zoom `scale`, or `None`
+/// if `event` isn't a zoom shortcut.
+fn zoom_shortcut(event: &KeyEvent, scale: f64) -> Option<f64> {
+    const STEP: f64 = 1.1;
+    const MIN: f64 = 0.25;
+    const MAX: f64 = 5.0;
+
+    let primary = if cfg!(target_os = "macos") {
+        event.modifiers.meta()
+    } else {
+        event.modifiers.control()
+    };
+    if !primary || event.modifiers.alt() {
+        return None;
+    }
+    let Key::Character(c) = &event.key.logical_key else {
+        return None;
+    };
+    match c.as_str() {
+        "+" | "=" => Some((scale * STEP).min(MAX)),
+        "-" => Some((scale / STEP).max(MIN)),
+        "0" => Some(1.0),
+        _ => None,
+    }
+}
+
 pub(crate) fn get_current_view() -> ViewId {
     CURRENT_RUNNING_VIEW_HANDLE.with(|running| *running.borrow())
 }
@@ -1556,6 +2260,14 @@ impl View for OverlayView {
         "Overlay".into()
     }
 
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(position) = state.downcast::<Point>() {
+            self.position = *position;
+            self.id.request_style();
+            self.id.request_layout();
+        }
+    }
+
     fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
         self.window_origin = cx.window_origin;
         if let Some(parent_size) = self.id.parent_size() {
@@ -1588,6 +2300,9 @@ impl View for OverlayView {
 /// A view representing a window which manages the main window view and any overlays.
 struct WindowView {
     id: ViewId,
+    /// The default font of the window, inherited by the views that don't set their own.
+    font_style: Style,
+    safe_area: RwSignal<Insets>,
 }
 
 impl View for WindowView {
@@ -1596,7 +2311,13 @@ impl View for WindowView {
     }
 
     fn view_style(&self) -> Option<crate::style::Style> {
-        Some(Style::new().width_full().height_full())
+        Some(
+            Style::new()
+                .width_full()
+                .height_full()
+                .apply(self.font_style.clone())
+                .apply(safe_area::root_style(self.safe_area.get_untracked())),
+        )
     }
 
     fn debug_name(&self) -> std::borrow::Cow<'static, str> {
@@ -1,5 +1,8 @@
 use crate::{
     screen_layout::screen_layout_for_window,
+    screens::{self, Monitor},
+    window::{WindowBackdrop, WindowCorners},
+    window_effects,
     window_tracking::{force_window_repaint, with_window},
     ScreenLayout, ViewId,
 };
@@ -11,7 +14,7 @@ use super::window_tracking::{
 };
 use floem_winit::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Pixel},
-    window::{UserAttentionType, Window, WindowId},
+    window::{UserAttentionType, Window, WindowId, WindowLevel},
 };
 use peniko::kurbo::{Point, Rect, Size};
 
@@ -25,7 +28,7 @@ thread_local! {
 
 /// Enum of state updates that can be requested on a window which are processed
 /// asynchronously after event processing.
-#[allow(dead_code)] // DocumentEdited is seen as unused on non-mac builds
+#[allow(dead_code)] // DocumentEdited and SkipTaskbar are seen as unused on some builds
 enum WindowUpdate {
     Visibility(bool),
     InnerBounds(Rect),
@@ -38,6 +41,11 @@ enum WindowUpdate {
     RequestAttention(Option<UserAttentionType>),
     Minimize(bool),
     Maximize(bool),
+    Backdrop(WindowBackdrop),
+    Corners(WindowCorners),
+    AlwaysOnTop(bool),
+    // Windows only
+    SkipTaskbar(bool),
     // macOS only
     #[allow(unused_variables)] // seen as unused on linux, etc.
     DocumentEdited(bool),
@@ -119,6 +127,9 @@ pub trait WindowIdExt: WindowIdExtSealed {
     fn position_of_content_on_screen(&self) -> Option<Point>;
     /// Get the logical bounds of the monitor this window is on.
     fn monitor_bounds(&self) -> Option<Rect>;
+    /// Get the monitor this window is mostly on, see [`screens`](crate::screens::screens)
+    /// for all monitors.
+    fn monitor(&self) -> Option<Monitor>;
     /// Determine if this window is currently visible.  Note that if a
     /// call to set a window visible which is invisible has happened within
     /// the current event loop cycle, the state returned will not reflect that.
@@ -195,6 +206,38 @@ pub trait WindowIdExt: WindowIdExtSealed {
         self.add_window_update(WindowUpdate::RequestAttention(urgency.into()))
     }
 
+    /// Change the material drawn behind this window, see
+    /// [`WindowConfig::backdrop`](crate::window::WindowConfig::backdrop).
+    fn set_backdrop(&self, backdrop: WindowBackdrop) {
+        self.add_window_update(WindowUpdate::Backdrop(backdrop))
+    }
+
+    /// Change how the corners of this window are rounded.  Only has an effect
+    /// on Windows 11.
+    fn set_corners(&self, corners: WindowCorners) {
+        self.add_window_update(WindowUpdate::Corners(corners))
+    }
+
+    /// Keep this window above all other windows, or return it to the normal
+    /// window level.
+    fn set_always_on_top(&self, always_on_top: bool) {
+        self.add_window_update(WindowUpdate::AlwaysOnTop(always_on_top))
+    }
+
+    /// Leave this window out of the taskbar.  Only has an effect on Windows.
+    #[allow(unused_variables)] // skip unused on non-windows builds
+    fn set_skip_taskbar(&self, skip: bool) {
+        #[cfg(target_os = "windows")]
+        self.add_window_update(WindowUpdate::SkipTaskbar(skip))
+    }
+
+    /// Zoom the views of this window by `scale`, which multiplies the scale factor
+    /// used for layout and painting independently of the DPI of the monitor, like
+    /// the zoom of a browser.  A scale of `1.0` resets the zoom, see
+    /// [`WindowConfig::zoom_shortcuts`](crate::window::WindowConfig::zoom_shortcuts)
+    /// for the usual keyboard shortcuts.
+    fn set_ui_scale(&self, scale: f64);
+
     /// Force a repaint of this window through the native window's repaint mechanism,
     /// bypassing floem's normal repaint mechanism.
     ///
@@ -242,6 +285,10 @@ impl WindowIdExt for WindowId {
         monitor_bounds(self)
     }
 
+    fn monitor(&self) -> Option<Monitor> {
+        with_window(self, screens::current_monitor).flatten()
+    }
+
     fn is_visible(&self) -> bool {
         with_window(self, |window| window.is_visible().unwrap_or(false)).unwrap_or(false)
     }
@@ -278,6 +325,12 @@ impl WindowIdExt for WindowId {
         root_view_id(self)
     }
 
+    fn set_ui_scale(&self, scale: f64) {
+        if let Some(root) = root_view_id(self) {
+            root.set_window_ui_scale(scale);
+        }
+    }
+
     fn screen_layout(&self) -> Option<ScreenLayout> {
         with_window(self, move |window| screen_layout_for_window(*self, window)).unwrap_or(None)
     }
@@ -346,6 +399,29 @@ pub(crate) fn process_window_updates(id: &WindowId) -> bool {
                 WindowUpdate::Maximize(maximize) => {
                     with_window(id, |window| window.set_maximized(maximize));
                 }
+                WindowUpdate::Backdrop(backdrop) => {
+                    with_window(id, |window| window_effects::set_backdrop(window, backdrop));
+                }
+                WindowUpdate::Corners(corners) => {
+                    with_window(id, |window| window_effects::set_corners(window, corners));
+                }
+                WindowUpdate::AlwaysOnTop(always_on_top) => {
+                    with_window(id, |window| {
+                        window.set_window_level(if always_on_top {
+                            WindowLevel::AlwaysOnTop
+                        } else {
+                            WindowLevel::Normal
+                        });
+                    });
+                }
+                #[allow(unused_variables)] // non windows - skip is unused
+                WindowUpdate::SkipTaskbar(skip) => {
+                    #[cfg(target_os = "windows")]
+                    with_window(id, |window| {
+                        use floem_winit::platform::windows::WindowExtWindows;
+                        window.set_skip_taskbar(skip);
+                    });
+                }
                 WindowUpdate::OuterLocation(outer) => {
                     with_window(id, |window| {
                         window.set_outer_position(LogicalPosition::new(outer.x, outer.y));
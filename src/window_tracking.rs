@@ -3,7 +3,7 @@
 //! that views can retrieve the `WindowId` of the window that contains them
 //! and use the methods that look up the `Window` for that id to retrieve information
 //! such as screen position.
-use crate::ViewId;
+use crate::{window::WindowInfo, ViewId};
 use floem_winit::{
     dpi::{PhysicalPosition, PhysicalSize},
     monitor::MonitorHandle,
@@ -11,7 +11,7 @@ use floem_winit::{
 };
 use peniko::kurbo::{Point, Rect};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{Arc, OnceLock, RwLock},
 };
 
@@ -26,6 +26,21 @@ pub fn store_window_id_mapping(
     with_window_map_mut(move |m| m.add(root_id, window_id, window.clone()));
 }
 
+/// Add the root of a window handle that has no window, so that its views are known to be in a
+/// window.
+pub fn store_headless_root(root_id: ViewId) {
+    with_window_map_mut(move |m| {
+        m.headless_roots.insert(root_id);
+    });
+}
+
+/// Remove the root of a window handle that has no window.
+pub fn remove_headless_root(root_id: &ViewId) {
+    with_window_map_mut(move |m| {
+        m.headless_roots.remove(root_id);
+    });
+}
+
 /// Remove the mapping from `root_id` -> `window_id` -> `window` for the given triple.
 pub fn remove_window_id_mapping(root_id: &ViewId, window_id: &WindowId) {
     with_window_map_mut(move |m| m.remove(root_id, window_id));
@@ -37,6 +52,7 @@ pub fn remove_window_id_mapping(root_id: &ViewId, window_id: &WindowId) {
 struct WindowMapping {
     window_for_window_id: HashMap<WindowId, Arc<Window>>,
     window_id_for_root_view_id: HashMap<ViewId, WindowId>,
+    headless_roots: HashSet<ViewId>,
 }
 
 impl WindowMapping {
@@ -75,6 +91,21 @@ impl WindowMapping {
         self.window_id_for_root_view_id.get(id).copied()
     }
 
+    fn windows(&self) -> Vec<WindowInfo> {
+        self.window_id_for_root_view_id
+            .iter()
+            .filter_map(|(root, window_id)| {
+                let window = self.window_for_window_id.get(window_id)?;
+                Some(WindowInfo {
+                    id: *window_id,
+                    title: window.title(),
+                    root: *root,
+                    focused: window.has_focus(),
+                })
+            })
+            .collect()
+    }
+
     fn root_view_id_for(&self, window_id: &WindowId) -> Option<ViewId> {
         for (k, v) in self.window_id_for_root_view_id.iter() {
             if v == window_id {
@@ -95,7 +126,10 @@ pub fn with_window_id_and_window<F: FnOnce(&WindowId, &Window) -> T, T>(
 }
 
 pub fn is_known_root(id: &ViewId) -> bool {
-    with_window_map(|map| map.window_id_for_root_view_id.contains_key(id)).unwrap_or(false)
+    with_window_map(|map| {
+        map.window_id_for_root_view_id.contains_key(id) || map.headless_roots.contains(id)
+    })
+    .unwrap_or(false)
 }
 
 fn with_window_map_mut<F: FnMut(&mut WindowMapping)>(mut f: F) -> bool {
@@ -121,6 +155,10 @@ pub fn with_window<F: FnOnce(&Window) -> T, T>(window: &WindowId, f: F) -> Optio
     with_window_map(|m| m.with_window(window, |w| f(w.as_ref()))).unwrap_or(None)
 }
 
+pub fn windows() -> Vec<WindowInfo> {
+    with_window_map(|m| m.windows()).unwrap_or_default()
+}
+
 pub fn root_view_id(window: &WindowId) -> Option<ViewId> {
     with_window_map(|m| m.root_view_id_for(window)).unwrap_or(None)
 }
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use floem::{
+    action::exec_after,
+    animate::TransitionGroup,
+    headless::HeadlessHarness,
+    kurbo::Size,
+    peniko::Brush,
+    prelude::*,
+    style::{Background, Transition, Width},
+};
+
+fn square() -> impl IntoView {
+    empty().style(|s| {
+        s.margin(10)
+            .size(20, 10)
+            .background(Color::rgb8(200, 40, 40))
+    })
+}
+
+#[test]
+fn renders_the_window() {
+    let mut harness = HeadlessHarness::new_with_size(square, Size::new(40.0, 30.0), 1.0);
+    let image = harness.render_to_image();
+    assert_eq!(image.dimensions(), (40, 30));
+    // the window background of the default theme, and the square
+    assert_eq!(image.get_pixel(5, 5).0, [248, 248, 248, 255]);
+    assert_eq!(image.get_pixel(20, 15).0, [200, 40, 40, 255]);
+
+    harness.assert_matches_golden("square");
+}
+
+#[test]
+fn finds_and_clicks_views() {
+    let saved = create_rw_signal(0);
+    let mut harness = HeadlessHarness::new(move || {
+        (
+            label(|| "Title"),
+            button("Save")
+                .test_id("save")
+                .action(move || saved.update(|saved| *saved += 1)),
+        )
+            .h_stack()
+    });
+
+    let save = harness
+        .find_by_test_id("save")
+        .expect("the button is shown")
+        .id();
+    let button = harness
+        .find_by_class(ButtonClass)
+        .expect("the button has its class");
+    assert_eq!(button.id(), save);
+    let text = harness
+        .find_by_text("Save")
+        .expect("the label of the button is shown");
+    assert_eq!(text.id().parent(), Some(save));
+    assert!(harness.find_by_text("Cancel").is_none());
+    assert!(harness.find_by_test_id("cancel").is_none());
+
+    harness.find_by_test_id("save").unwrap().click();
+    assert_eq!(saved.get_untracked(), 1);
+    harness.find_by_text("Title").unwrap().click();
+    assert_eq!(saved.get_untracked(), 1);
+}
+
+#[test]
+fn hover_applies_the_hover_style() {
+    let mut harness = HeadlessHarness::new(|| {
+        (
+            empty().test_id("other").style(|s| s.size(50, 20)),
+            empty().test_id("target").style(|s| {
+                s.size(50, 20)
+                    .background(Color::WHITE)
+                    .hover(|s| s.background(Color::RED))
+            }),
+        )
+            .h_stack()
+    });
+    let background = |harness: &mut HeadlessHarness| {
+        harness
+            .find_by_test_id("target")
+            .unwrap()
+            .style()
+            .get(Background)
+    };
+    assert_eq!(background(&mut harness), Some(Brush::Solid(Color::WHITE)));
+
+    harness.find_by_test_id("target").unwrap().hover();
+    assert_eq!(background(&mut harness), Some(Brush::Solid(Color::RED)));
+
+    harness.find_by_test_id("other").unwrap().hover();
+    assert_eq!(background(&mut harness), Some(Brush::Solid(Color::WHITE)));
+}
+
+#[test]
+fn advancing_time_fires_timers() {
+    let mut harness = HeadlessHarness::new(empty);
+    let fired = create_rw_signal(false);
+    exec_after(100.millis(), move |_| fired.set(true));
+
+    harness.advance_time(99.millis());
+    assert!(!fired.get_untracked());
+    harness.advance_time(1.millis());
+    assert!(fired.get_untracked());
+}
+
+#[test]
+fn advancing_time_moves_transitions() {
+    let wide = create_rw_signal(false);
+    let mut harness = HeadlessHarness::new(move || {
+        empty().test_id("bar").style(move |s| {
+            s.height(10)
+                .width(if wide.get() { 100 } else { 0 })
+                .transition(Width, Transition::linear(100.millis()))
+        })
+    });
+    let width = |harness: &mut HeadlessHarness| {
+        harness
+            .find_by_test_id("bar")
+            .unwrap()
+            .layout_rect()
+            .width()
+    };
+    assert_eq!(width(&mut harness), 0.0);
+
+    wide.set(true);
+    harness.advance_time(50.millis());
+    assert!((width(&mut harness) - 50.0).abs() < 1.0);
+
+    harness.advance_time(60.millis());
+    assert_eq!(width(&mut harness), 100.0);
+
+    // without time passing, the change doesn't move the transition
+    wide.set(false);
+    harness.advance_time(Duration::ZERO);
+    assert_eq!(width(&mut harness), 100.0);
+    harness.advance_time(100.millis());
+    assert_eq!(width(&mut harness), 0.0);
+}
+
+#[test]
+fn hidden_views_stay_in_their_group_until_they_exited() {
+    let shown = create_rw_signal(true);
+    let exited = create_rw_signal(false);
+    let group = TransitionGroup::new();
+    let mut harness = HeadlessHarness::new(move || {
+        empty()
+            .style(move |s| s.size(10, 10).apply_if(!shown.get(), |s| s.hide()))
+            .on_exit_animation(|a| {
+                a.duration(100.millis())
+                    .keyframe(100, |f| f.style(|s| s.width(0)))
+            })
+            .transition_group(group)
+    });
+    assert_eq!(group.exiting(), 0);
+
+    shown.set(false);
+    harness.advance_time(Duration::ZERO);
+    assert_eq!(group.exiting(), 1);
+    group.after_exit(move || exited.set(true));
+
+    harness.advance_time(50.millis());
+    assert!(!exited.get_untracked());
+    // the view is hidden in the frame after the animation completed
+    harness.advance_time(60.millis());
+    harness.advance_time(Duration::ZERO);
+    assert_eq!(group.exiting(), 0);
+    assert!(exited.get_untracked());
+}
+
+#[test]
+fn removed_items_stay_in_their_group_until_they_exited() {
+    let items = create_rw_signal(vec![1, 2, 3]);
+    let group = TransitionGroup::new();
+    let mut harness = HeadlessHarness::new(move || {
+        dyn_stack(
+            move || items.get(),
+            |item| *item,
+            |item| label(move || item),
+        )
+        .on_exit_animation(|a| {
+            a.duration(100.millis())
+                .keyframe(100, |f| f.style(|s| s.height(0)))
+        })
+        .transition_group(group)
+    });
+
+    items.set(vec![2]);
+    harness.advance_time(Duration::ZERO);
+    assert_eq!(group.exiting(), 2);
+    assert!(harness.find_by_text("1").is_some());
+
+    harness.advance_time(110.millis());
+    harness.advance_time(Duration::ZERO);
+    assert_eq!(group.exiting(), 0);
+    assert!(harness.find_by_text("1").is_none());
+    assert!(harness.find_by_text("2").is_some());
+}
@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result};
+use floem_renderer::glyph_cache::GlyphCache;
 use floem_renderer::swash::SwashScaler;
 use floem_renderer::text::{CacheKey, LayoutRun, SwashContent};
 use floem_renderer::tiny_skia::{
@@ -7,15 +8,16 @@ use floem_renderer::tiny_skia::{
 };
 use floem_renderer::Img;
 use floem_renderer::Renderer;
+use floem_renderer::{image_cache, svg_cache};
 use peniko::kurbo::{self, PathEl, Size, Vec2};
 use peniko::{
     kurbo::{Affine, Point, Rect, Shape},
     BrushRef, Color, GradientKind,
 };
 use softbuffer::{Context, Surface};
-use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::rc::Rc;
+use std::sync::Arc;
 use tiny_skia::{LineCap, LineJoin};
 
 macro_rules! try_ret {
@@ -34,25 +36,19 @@ struct Glyph {
     top: f32,
 }
 
-#[derive(PartialEq, Clone, Copy)]
-struct CacheColor(bool);
-
 pub struct TinySkiaRenderer<W> {
     #[allow(unused)]
-    context: Context<W>,
-    surface: Surface<W, W>,
+    context: Option<Context<W>>,
+    /// The surface presenting the frames, which offscreen renderers don't have.
+    surface: Option<Surface<W, W>>,
     pixmap: Pixmap,
     mask: Mask,
     scale: f64,
     transform: Affine,
     clip: Option<Rect>,
+    capture: bool,
 
-    /// The cache color value set for cache entries accessed this frame.
-    cache_color: CacheColor,
-
-    image_cache: HashMap<Vec<u8>, (CacheColor, Rc<Pixmap>)>,
-    #[allow(clippy::type_complexity)]
-    glyph_cache: HashMap<(CacheKey, Color), (CacheColor, Option<Rc<Glyph>>)>,
+    glyph_cache: GlyphCache<(CacheKey, Color), Option<Rc<Glyph>>>,
     swash_scaler: SwashScaler,
 }
 
@@ -74,34 +70,22 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
             )
             .map_err(|_| anyhow!("failed to resize surface"))?;
 
-        let pixmap =
-            Pixmap::new(width, height).ok_or_else(|| anyhow!("unable to create pixmap"))?;
-
-        let mask = Mask::new(width, height).ok_or_else(|| anyhow!("unable to create mask"))?;
-
-        Ok(Self {
-            context,
-            surface,
-            pixmap,
-            mask,
-            scale,
-            transform: Affine::IDENTITY,
-            clip: None,
-            cache_color: CacheColor(false),
-            image_cache: Default::default(),
-            glyph_cache: Default::default(),
-            swash_scaler: SwashScaler::new(font_embolden),
-        })
+        let mut renderer = Self::new_offscreen(width, height, scale, font_embolden)?;
+        renderer.context = Some(context);
+        renderer.surface = Some(surface);
+        Ok(renderer)
     }
 
     pub fn resize(&mut self, width: u32, height: u32, scale: f64) {
         if width != self.pixmap.width() || height != self.pixmap.width() {
-            self.surface
-                .resize(
-                    NonZeroU32::new(width).unwrap_or(NonZeroU32::new(1).unwrap()),
-                    NonZeroU32::new(height).unwrap_or(NonZeroU32::new(1).unwrap()),
-                )
-                .expect("failed to resize surface");
+            if let Some(surface) = self.surface.as_mut() {
+                surface
+                    .resize(
+                        NonZeroU32::new(width).unwrap_or(NonZeroU32::new(1).unwrap()),
+                        NonZeroU32::new(height).unwrap_or(NonZeroU32::new(1).unwrap()),
+                    )
+                    .expect("failed to resize surface");
+            }
             self.pixmap = Pixmap::new(width, height).expect("unable to create pixmap");
             self.mask = Mask::new(width, height).expect("unable to create mask");
         }
@@ -129,7 +113,62 @@ fn to_point(point: Point) -> tiny_skia::Point {
     tiny_skia::Point::from_xy(point.x as f32, point.y as f32)
 }
 
+/// Fills a pixmap of the size of `pixmap` with `paint`, masked by the alpha of `pixmap`.
+fn fill_pixmap(pixmap: &Pixmap, paint: &Paint) -> Option<Pixmap> {
+    let mut fill = Pixmap::new(pixmap.width(), pixmap.height())?;
+    fill.fill_rect(
+        tiny_skia::Rect::from_xywh(0.0, 0.0, pixmap.width() as f32, pixmap.height() as f32)?,
+        paint,
+        Transform::identity(),
+        None,
+    );
+    let mask = Mask::from_pixmap(pixmap.as_ref(), MaskType::Alpha);
+    fill.apply_mask(&mask);
+    Some(fill)
+}
+
 impl<W> TinySkiaRenderer<W> {
+    /// Creates a renderer without a window, which returns each frame as an image from
+    /// [`Renderer::finish`].
+    pub fn new_offscreen(width: u32, height: u32, scale: f64, font_embolden: f32) -> Result<Self> {
+        let pixmap =
+            Pixmap::new(width, height).ok_or_else(|| anyhow!("unable to create pixmap"))?;
+
+        let mask = Mask::new(width, height).ok_or_else(|| anyhow!("unable to create mask"))?;
+
+        Ok(Self {
+            context: None,
+            surface: None,
+            pixmap,
+            mask,
+            scale,
+            transform: Affine::IDENTITY,
+            clip: None,
+            capture: false,
+            glyph_cache: Default::default(),
+            swash_scaler: SwashScaler::new(font_embolden),
+        })
+    }
+
+    /// The current frame as an RGBA image, from the premultiplied pixels of the pixmap.
+    fn image(&self) -> peniko::Image {
+        let data: Vec<u8> = self
+            .pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect();
+        peniko::Image::new(
+            peniko::Blob::new(Arc::new(data)),
+            peniko::Format::Rgba8,
+            self.pixmap.width(),
+            self.pixmap.height(),
+        )
+    }
+
     fn shape_to_path(&self, shape: &impl Shape) -> Option<Path> {
         let mut builder = PathBuilder::new();
         for element in shape.path_elements(0.1) {
@@ -260,9 +299,14 @@ impl<W> TinySkiaRenderer<W> {
                 SpreadMode::Pad,
                 FilterQuality::Bilinear,
                 1.0,
-                Transform::from_scale(
+                // the pattern starts at the origin of `rect`, not of the transform
+                Transform::from_row(
                     rect.width() / pixmap.width() as f32,
+                    0.0,
+                    0.0,
                     rect.height() / pixmap.height() as f32,
+                    rect.x(),
+                    rect.y(),
                 ),
             ),
             ..Default::default()
@@ -288,22 +332,7 @@ impl<W> TinySkiaRenderer<W> {
             return self.render_pixmap_rect(pixmap, rect);
         };
 
-        let mut fill = try_ret!(Pixmap::new(pixmap.width(), pixmap.height()));
-        fill.fill_rect(
-            try_ret!(tiny_skia::Rect::from_xywh(
-                0.0,
-                0.0,
-                pixmap.width() as f32,
-                pixmap.height() as f32
-            )),
-            &paint,
-            Transform::identity(),
-            None,
-        );
-
-        let mask = Mask::from_pixmap(pixmap.as_ref(), MaskType::Alpha);
-        fill.apply_mask(&mask);
-
+        let fill = try_ret!(fill_pixmap(pixmap, &paint));
         self.render_pixmap_rect(&fill, rect);
     }
 
@@ -322,53 +351,55 @@ impl<W> TinySkiaRenderer<W> {
     }
 
     fn cache_glyph(&mut self, cache_key: CacheKey, color: Color) -> Option<Rc<Glyph>> {
-        if let Some((color, glyph)) = self.glyph_cache.get_mut(&(cache_key, color)) {
-            *color = self.cache_color;
-            return glyph.clone();
-        }
-
-        let image = self.swash_scaler.get_image(cache_key)?;
-
-        let result = if image.placement.width == 0 || image.placement.height == 0 {
-            // We can't create an empty `Pixmap`
-            None
-        } else {
-            let mut pixmap = Pixmap::new(image.placement.width, image.placement.height)?;
-
-            if image.content == SwashContent::Mask {
-                for (a, &alpha) in pixmap.pixels_mut().iter_mut().zip(image.data.iter()) {
-                    *a = tiny_skia::Color::from_rgba8(color.r, color.g, color.b, alpha)
-                        .premultiply()
-                        .to_color_u8();
-                }
-            } else if image.content == SwashContent::Color {
-                for (a, b) in pixmap.pixels_mut().iter_mut().zip(image.data.chunks(4)) {
-                    *a = tiny_skia::Color::from_rgba8(b[0], b[1], b[2], b[3])
-                        .premultiply()
-                        .to_color_u8();
-                }
-            } else {
-                return None;
-            }
-
-            Some(Rc::new(Glyph {
-                pixmap,
-                left: image.placement.left as f32,
-                top: image.placement.top as f32,
-            }))
-        };
+        let swash_scaler = &mut self.swash_scaler;
+        self.glyph_cache.get_or_insert_with((cache_key, color), || {
+            let glyph = rasterize_glyph(swash_scaler, cache_key, color);
+            let size = glyph.as_ref().map_or(0, |glyph| glyph.pixmap.data().len());
+            (glyph, size)
+        })
+    }
+}
 
-        self.glyph_cache
-            .insert((cache_key, color), (self.cache_color, result.clone()));
+fn rasterize_glyph(
+    swash_scaler: &mut SwashScaler,
+    cache_key: CacheKey,
+    color: Color,
+) -> Option<Rc<Glyph>> {
+    let image = swash_scaler.get_image(cache_key)?;
+    if image.placement.width == 0 || image.placement.height == 0 {
+        // We can't create an empty `Pixmap`
+        return None;
+    }
 
-        result
+    let mut pixmap = Pixmap::new(image.placement.width, image.placement.height)?;
+    if image.content == SwashContent::Mask {
+        for (a, &alpha) in pixmap.pixels_mut().iter_mut().zip(image.data.iter()) {
+            *a = tiny_skia::Color::from_rgba8(color.r, color.g, color.b, alpha)
+                .premultiply()
+                .to_color_u8();
+        }
+    } else if image.content == SwashContent::Color {
+        for (a, b) in pixmap.pixels_mut().iter_mut().zip(image.data.chunks(4)) {
+            *a = tiny_skia::Color::from_rgba8(b[0], b[1], b[2], b[3])
+                .premultiply()
+                .to_color_u8();
+        }
+    } else {
+        return None;
     }
+
+    Some(Rc::new(Glyph {
+        pixmap,
+        left: image.placement.left as f32,
+        top: image.placement.top as f32,
+    }))
 }
 
 impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle> Renderer
     for TinySkiaRenderer<W>
 {
-    fn begin(&mut self, _capture: bool) {
+    fn begin(&mut self, capture: bool) {
+        self.capture = capture;
         self.transform = Affine::IDENTITY;
         self.pixmap.fill(tiny_skia::Color::WHITE);
         self.clip = None;
@@ -494,29 +525,9 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
 
     fn draw_img(&mut self, img: Img<'_>, rect: Rect) {
         let rect = try_ret!(self.rect(rect));
-        if let Some((color, pixmap)) = self.image_cache.get_mut(img.hash) {
-            *color = self.cache_color;
-            let pixmap = pixmap.clone();
-            self.render_pixmap_rect(&pixmap, rect);
-            return;
-        }
-
-        let image_data = img.img.data.data();
-        let mut pixmap = try_ret!(Pixmap::new(img.img.width, img.img.height));
-        for (a, b) in pixmap
-            .pixels_mut()
-            .iter_mut()
-            .zip(image_data.chunks_exact(4))
-        {
-            *a = tiny_skia::Color::from_rgba8(b[0], b[1], b[2], b[3])
-                .premultiply()
-                .to_color_u8();
-        }
-
+        // the pixels are shared with the renderers of the other windows
+        let pixmap = try_ret!(image_cache::get_or_insert(img.hash, &img.img));
         self.render_pixmap_rect(&pixmap, rect);
-
-        self.image_cache
-            .insert(img.hash.to_owned(), (self.cache_color, Rc::new(pixmap)));
     }
 
     fn draw_svg<'b>(
@@ -530,27 +541,27 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
 
         let rect = try_ret!(self.rect(rect));
 
-        let paint = brush.and_then(|brush| self.brush_to_paint(brush));
-
-        if let Some((color, pixmap)) = self.image_cache.get_mut(svg.hash) {
-            *color = self.cache_color;
-            let pixmap = pixmap.clone();
-            self.render_pixmap_paint(&pixmap, rect, paint);
+        let brush: Option<BrushRef<'b>> = brush.map(Into::into);
+        let raster = || svg_cache::rasterize(svg.tree, width, height, svg_cache::Fit::Fill);
+        if let Some(BrushRef::Solid(color)) = brush {
+            // the raster is colored once and cached with its color
+            let key = svg_cache::key(svg.hash, width, height, svg_cache::Fit::Fill, Some(color));
+            let paint = self.brush_to_paint(color);
+            let pixmap = try_ret!(svg_cache::get_or_insert(&key, || {
+                let pixmap = raster()?;
+                match paint {
+                    Some(paint) => fill_pixmap(&pixmap, &paint),
+                    None => Some(pixmap),
+                }
+            }));
+            self.render_pixmap_rect(&pixmap, rect);
             return;
         }
 
-        let mut pixmap = try_ret!(tiny_skia::Pixmap::new(width, height));
-        // let rtree = resvg::Tree::from_usvg(svg.tree);
-        let svg_transform = tiny_skia::Transform::from_scale(
-            width as f32 / svg.tree.size().width(),
-            height as f32 / svg.tree.size().height(),
-        );
-        resvg::render(svg.tree, svg_transform, &mut pixmap.as_mut());
-
+        let paint = brush.and_then(|brush| self.brush_to_paint(brush));
+        let key = svg_cache::key(svg.hash, width, height, svg_cache::Fit::Fill, None);
+        let pixmap = try_ret!(svg_cache::get_or_insert(&key, raster));
         self.render_pixmap_paint(&pixmap, rect, paint);
-
-        self.image_cache
-            .insert(svg.hash.to_owned(), (self.cache_color, Rc::new(pixmap)));
     }
 
     fn transform(&mut self, transform: Affine) {
@@ -594,14 +605,13 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
 
     fn finish(&mut self) -> Option<peniko::Image> {
         // Remove cache entries which were not accessed.
-        self.image_cache.retain(|_, (c, _)| *c == self.cache_color);
-        self.glyph_cache.retain(|_, (c, _)| *c == self.cache_color);
+        self.glyph_cache.finish_frame();
 
-        // Swap the cache color.
-        self.cache_color = CacheColor(!self.cache_color.0);
-
-        let mut buffer = self
-            .surface
+        let surface = match self.surface.as_mut() {
+            Some(surface) if !self.capture => surface,
+            _ => return Some(self.image()),
+        };
+        let mut buffer = surface
             .buffer_mut()
             .expect("failed to get the surface buffer");
 
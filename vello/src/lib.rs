@@ -4,11 +4,13 @@ use std::sync::mpsc::sync_channel;
 use std::sync::Arc;
 
 use anyhow::Result;
+use floem_renderer::glyph_cache::GlyphCache;
 use floem_renderer::gpu_resources::GpuResources;
+use floem_renderer::swash::{is_color_font, SwashScaler};
 use floem_renderer::text::fontdb::ID;
-use floem_renderer::text::{LayoutGlyph, LayoutRun, FONT_SYSTEM};
+use floem_renderer::text::{CacheKey, LayoutGlyph, LayoutRun, SwashContent, FONT_SYSTEM};
 use floem_renderer::{Img, Renderer};
-use peniko::kurbo::Size;
+use peniko::kurbo::{Size, Vec2};
 use peniko::{
     kurbo::{Affine, Point, Rect, Shape},
     Blob, BrushRef, Color,
@@ -33,6 +35,16 @@ pub struct VelloRenderer {
     transform: Affine,
     capture: bool,
     font_cache: HashMap<ID, vello::peniko::Font>,
+    /// Whether the fonts have color glyphs, which vello can't draw, so they're rendered by swash.
+    color_fonts: HashMap<ID, bool>,
+    /// The images of the glyphs of color fonts, with their offsets from the glyph origin.
+    color_glyph_cache: GlyphCache<(CacheKey, [u8; 4]), Option<(vello::peniko::Image, Vec2)>>,
+    /// The paths of the SVGs drawn in the last two frames, which are drawn at any size without
+    /// being rasterized, with the frame they were drawn in.
+    svg_scenes: HashMap<Vec<u8>, (u64, Scene)>,
+    /// Counts the frames, to drop the paths of the SVGs that aren't drawn anymore.
+    svg_frame: u64,
+    swash_scaler: SwashScaler,
 }
 
 impl VelloRenderer {
@@ -41,7 +53,7 @@ impl VelloRenderer {
         width: u32,
         height: u32,
         scale: f64,
-        _font_embolden: f32,
+        font_embolden: f32,
     ) -> Result<Self> {
         let GpuResources {
             surface,
@@ -113,6 +125,11 @@ impl VelloRenderer {
             transform: Affine::IDENTITY,
             capture: false,
             font_cache: HashMap::new(),
+            color_fonts: HashMap::new(),
+            color_glyph_cache: GlyphCache::default(),
+            svg_scenes: HashMap::new(),
+            svg_frame: 0,
+            swash_scaler: SwashScaler::new(font_embolden),
         })
     }
 
@@ -330,32 +347,35 @@ impl Renderer for VelloRenderer {
         let translate_x = rect.min_x();
         let translate_y = rect.min_y();
 
-        let new = brush.map_or_else(
-            || vello_svg::render_tree(svg.tree),
-            |brush| {
+        let svg_frame = self.svg_frame;
+        let (frame, tree) = self
+            .svg_scenes
+            .entry(svg.hash.to_owned())
+            .or_insert_with(|| (svg_frame, vello_svg::render_tree(svg.tree)));
+        *frame = svg_frame;
+
+        // Apply transformations to fit the SVG within the provided rectangle
+        let transform = self
+            .transform
+            .pre_scale_non_uniform(scale_x, scale_y)
+            .pre_translate((translate_x, translate_y).into())
+            .then_scale(self.window_scale);
+        match brush {
+            Some(brush) => {
                 let brush = brush.into();
-                alpha_mask_scene(
+                let new = alpha_mask_scene(
                     rect.size(),
                     |scene| {
-                        scene.append(&vello_svg::render_tree(svg.tree), None);
+                        scene.append(tree, None);
                     },
                     move |scene| {
                         scene.fill(Fill::NonZero, Affine::IDENTITY, brush, None, &rect);
                     },
-                )
-            },
-        );
-
-        // Apply transformations to fit the SVG within the provided rectangle
-        self.scene.append(
-            &new,
-            Some(
-                self.transform
-                    .pre_scale_non_uniform(scale_x, scale_y)
-                    .pre_translate((translate_x, translate_y).into())
-                    .then_scale(self.window_scale),
-            ),
-        );
+                );
+                self.scene.append(&new, Some(transform));
+            }
+            None => self.scene.append(tree, Some(transform)),
+        }
     }
 
     fn transform(&mut self, transform: Affine) {
@@ -383,6 +403,12 @@ impl Renderer for VelloRenderer {
     }
 
     fn finish(&mut self) -> Option<vello::peniko::Image> {
+        let svg_frame = self.svg_frame;
+        self.svg_scenes
+            .retain(|_, (frame, _)| *frame >= svg_frame.saturating_sub(1));
+        self.svg_frame += 1;
+        self.color_glyph_cache.finish_frame();
+
         if self.capture {
             self.render_capture_image()
         } else {
@@ -597,6 +623,14 @@ impl VelloRenderer {
     }
 
     fn draw_glyph_run(&mut self, run: GlyphRun, transform: Affine) {
+        if *self
+            .color_fonts
+            .entry(run.font_id)
+            .or_insert_with(|| is_color_font(run.font_id))
+        {
+            self.draw_color_glyph_run(run, transform);
+            return;
+        }
         let font = self.get_font(run.font_id);
         self.scene
             .draw_glyphs(&font)
@@ -613,4 +647,73 @@ impl VelloRenderer {
                 }),
             );
     }
+
+    /// Draws the glyphs of a color font, like emoji, as images rendered by swash at the scale of
+    /// the window.
+    fn draw_color_glyph_run(&mut self, run: GlyphRun, transform: Affine) {
+        let scale = self.window_scale;
+        let color = [run.color.r, run.color.g, run.color.b, run.color.a];
+        for glyph in run.glyphs {
+            let (cache_key, _, _) = CacheKey::new(
+                run.font_id,
+                glyph.glyph_id,
+                run.font_size * scale as f32,
+                (0., 0.),
+                glyph.cache_key_flags,
+            );
+            let swash_scaler = &mut self.swash_scaler;
+            let image = self
+                .color_glyph_cache
+                .get_or_insert_with((cache_key, color), || {
+                    let glyph = color_glyph(swash_scaler, cache_key, color);
+                    let size = glyph
+                        .as_ref()
+                        .map_or(0, |(image, _)| image.data.data().len());
+                    (glyph, size)
+                });
+            if let Some((image, offset)) = image {
+                self.scene.draw_image(
+                    &image,
+                    transform
+                        * Affine::translate((glyph.x as f64, glyph.y as f64))
+                        * Affine::scale(1. / scale)
+                        * Affine::translate(offset),
+                );
+            }
+        }
+    }
+}
+
+/// The image of a glyph of a color font rendered by swash, with its offset from the glyph
+/// origin.
+fn color_glyph(
+    swash_scaler: &mut SwashScaler,
+    cache_key: CacheKey,
+    color: [u8; 4],
+) -> Option<(vello::peniko::Image, Vec2)> {
+    let image = swash_scaler.get_image(cache_key)?;
+    let placement = image.placement;
+    if placement.width == 0 || placement.height == 0 {
+        return None;
+    }
+    let data = match image.content {
+        SwashContent::Color => image.data,
+        SwashContent::Mask => image
+            .data
+            .iter()
+            .flat_map(|&alpha| {
+                let alpha = (alpha as u16 * color[3] as u16 / 255) as u8;
+                [color[0], color[1], color[2], alpha]
+            })
+            .collect(),
+        SwashContent::SubpixelMask => return None,
+    };
+    let image = vello::peniko::Image::new(
+        Blob::new(Arc::new(data)),
+        vello::peniko::Format::Rgba8,
+        placement.width,
+        placement.height,
+    );
+    let offset = Vec2::new(placement.left as f64, -placement.top as f64);
+    Some((image, offset))
 }
@@ -6,7 +6,7 @@ use anyhow::Result;
 use floem_renderer::gpu_resources::GpuResources;
 use floem_renderer::swash::SwashScaler;
 use floem_renderer::text::{CacheKey, LayoutRun};
-use floem_renderer::{tiny_skia, Img, Renderer};
+use floem_renderer::{glyph_cache, svg_cache, tiny_skia, Img, Renderer};
 use floem_vger_rs::{Image, PaintIndex, PixelFormat, Vger};
 use image::EncodableLayout;
 use peniko::kurbo::{Size, Stroke};
@@ -487,6 +487,8 @@ impl Renderer for VgerRenderer {
 
                     let glyph_x = new_x as f32;
                     let glyph_y = new_y as f32;
+                    // the atlas of vger rasterizes the glyphs it doesn't have
+                    let mut hit = true;
                     self.vger.render_glyph(
                         glyph_x,
                         glyph_y,
@@ -495,11 +497,13 @@ impl Renderer for VgerRenderer {
                         font_size,
                         (cache_key.x_bin, cache_key.y_bin),
                         || {
+                            hit = false;
                             let image = self.swash_scaler.get_image(cache_key);
                             image.unwrap_or_default()
                         },
                         paint,
                     );
+                    glyph_cache::record(hit);
                 }
             }
         }
@@ -563,26 +567,20 @@ impl Renderer for VgerRenderer {
 
         let paint = brush.and_then(|b| self.brush_to_paint(b));
 
+        // vger colors the raster when drawing it, so the color isn't part of the key
+        let key = svg_cache::key(svg.hash, width, height, svg_cache::Fit::Contain, None);
         self.vger.render_svg(
             x,
             y,
-            svg.hash,
+            &key,
             width,
             height,
             || {
-                let mut img = tiny_skia::Pixmap::new(width, height).unwrap();
-
-                let svg_scale = (width as f32 / svg.tree.size().width())
-                    .min(height as f32 / svg.tree.size().height());
-
-                let final_scale_x = svg_scale;
-                let final_scale_y = svg_scale;
-
-                let transform = tiny_skia::Transform::from_scale(final_scale_x, final_scale_y);
-
-                resvg::render(svg.tree, transform, &mut img.as_mut());
-
-                img.take()
+                svg_cache::get_or_insert(&key, || {
+                    svg_cache::rasterize(svg.tree, width, height, svg_cache::Fit::Contain)
+                })
+                .map(|pixmap| pixmap.data().to_vec())
+                .unwrap_or_else(|| vec![0; width as usize * height as usize * 4])
             },
             paint,
         );